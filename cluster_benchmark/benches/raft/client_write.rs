@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use openraft::Config;
+use tokio::runtime::Runtime;
+
+use crate::network::BenchRaft;
+use crate::network::Router;
+use crate::store::ClientRequest;
+
+/// Proposals per second of a cluster of 3, with 1 client for the latency and with 64 clients for
+/// the throughput.
+pub fn bench_client_write(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut group = c.benchmark_group("client_write");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+
+    let router = rt.block_on(new_cluster((0..3).collect()));
+    let leader = router.get_raft(0);
+
+    for n_client in [1, 64] {
+        group.bench_with_input(BenchmarkId::new("clients", n_client), &n_client, |b, &n_client| {
+            b.to_async(&rt).iter_custom(|iters| client_write(leader.clone(), n_client, iters));
+        });
+    }
+    group.finish();
+
+    rt.block_on(shutdown(router));
+}
+
+/// Cost of replicating to more followers: proposals per second with 64 clients, with 0 to 6
+/// followers.
+pub fn bench_fan_out(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut group = c.benchmark_group("replication_fan_out");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+
+    for n_follower in [0, 2, 4, 6] {
+        let router = rt.block_on(new_cluster((0..=n_follower).collect()));
+        let leader = router.get_raft(0);
+
+        group.bench_with_input(BenchmarkId::new("followers", n_follower), &n_follower, |b, _| {
+            b.to_async(&rt).iter_custom(|iters| client_write(leader.clone(), 64, iters));
+        });
+
+        rt.block_on(shutdown(router));
+    }
+    group.finish();
+}
+
+/// Write `iters` requests with `n_client` concurrent clients and return the time spent.
+async fn client_write(leader: BenchRaft, n_client: u64, iters: u64) -> Duration {
+    let now = Instant::now();
+
+    let handles = (0..n_client)
+        .map(|i| {
+            let n = iters / n_client + u64::from(i < iters % n_client);
+            let leader = leader.clone();
+            tokio::spawn(async move {
+                for _ in 0..n {
+                    leader.client_write(ClientRequest {}).await.unwrap();
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for h in handles {
+        h.await.unwrap();
+    }
+
+    now.elapsed()
+}
+
+pub(crate) fn runtime() -> Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(8)
+        .enable_all()
+        .thread_name("bench-raft")
+        .build()
+        .unwrap()
+}
+
+/// Build a cluster in which node 0 is the leader.
+pub(crate) async fn new_cluster(members: BTreeSet<u64>) -> Router {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 200,
+            election_timeout_max: 2000,
+            purge_batch_size: 1024,
+            ..Default::default()
+        }
+        .validate()
+        .unwrap(),
+    );
+
+    let mut router = Router::new();
+    router.new_cluster(config, members).await.unwrap();
+    router
+}
+
+pub(crate) async fn shutdown(router: Router) {
+    let rafts = std::mem::take(&mut *router.table.lock().unwrap());
+    for raft in rafts.into_values() {
+        raft.shutdown().await.unwrap();
+    }
+}
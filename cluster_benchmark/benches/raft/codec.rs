@@ -0,0 +1,56 @@
+use criterion::black_box;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use openraft::raft::AppendEntriesRequest;
+use openraft::storage::codec::FixedWidthCodec;
+use openraft::testing::log_id;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::Vote;
+
+use crate::store::ClientRequest;
+use crate::store::TypeConfig;
+
+/// Entries per second of encoding and decoding an `AppendEntriesRequest`, with different number of
+/// entries.
+pub fn bench_append_entries_codec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("append_entries_codec");
+
+    for n in [1, 64, 1024] {
+        let entries = (1..=n).map(|index| Entry {
+            log_id: log_id::<TypeConfig>(1, 0, index),
+            payload: EntryPayload::Normal(ClientRequest {}),
+        });
+        let req = AppendEntriesRequest::<TypeConfig>::new(
+            Vote::new_committed(1, 0),
+            Some(log_id::<TypeConfig>(1, 0, 0)),
+            entries,
+            Some(log_id::<TypeConfig>(1, 0, 0)),
+        );
+        let bytes = req.encode().unwrap();
+
+        group.throughput(Throughput::Elements(n));
+        group.bench_with_input(BenchmarkId::new("encode", n), &req, |b, req| {
+            b.iter(|| black_box(req).encode().unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("decode", n), &bytes, |b, bytes| {
+            b.iter(|| AppendEntriesRequest::<TypeConfig>::decode(black_box(bytes)).unwrap());
+        });
+    }
+    group.finish();
+}
+
+/// Encoding and decoding a log id into the fixed-width key of a log store.
+pub fn bench_log_id_codec(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log_id_codec");
+
+    let log_id = log_id::<TypeConfig>(5, 3, 1_000_000);
+    let bytes = log_id.encode();
+
+    group.bench_function("encode", |b| b.iter(|| black_box(&log_id).encode()));
+    group.bench_function("decode", |b| {
+        b.iter(|| openraft::LogId::<TypeConfig>::decode(black_box(&bytes)).unwrap())
+    });
+    group.finish();
+}
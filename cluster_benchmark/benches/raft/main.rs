@@ -0,0 +1,33 @@
+//! Criterion benchmarks of the hot paths of Openraft.
+//!
+//! They share the minimized store and network with `tests/benchmark`, so that the numbers show the
+//! overhead of Openraft itself.
+
+#![deny(unused_qualifications)]
+#![cfg_attr(feature = "bt", feature(error_generic_member_access))]
+
+/// The store and network of `tests/benchmark`.
+#[allow(dead_code, unused_imports)]
+#[path = "../../tests/benchmark"]
+mod shared {
+    pub(crate) mod network;
+    pub(crate) mod store;
+}
+
+pub(crate) use shared::network;
+pub(crate) use shared::store;
+
+mod client_write;
+mod codec;
+mod snapshot;
+
+criterion::criterion_group!(
+    benches,
+    client_write::bench_client_write,
+    client_write::bench_fan_out,
+    snapshot::bench_build_snapshot,
+    snapshot::bench_install_snapshot,
+    codec::bench_append_entries_codec,
+    codec::bench_log_id_codec,
+);
+criterion::criterion_main!(benches);
@@ -0,0 +1,135 @@
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use maplit::btreeset;
+use openraft::storage::Snapshot;
+use openraft::testing::log_id;
+use openraft::Config;
+use openraft::Membership;
+use openraft::Raft;
+use openraft::SnapshotMeta;
+use openraft::StoredMembership;
+use openraft::Vote;
+
+use crate::client_write::new_cluster;
+use crate::client_write::runtime;
+use crate::client_write::shutdown;
+use crate::network::Router;
+use crate::store::ClientRequest;
+use crate::store::LogStore;
+use crate::store::StateMachine;
+use crate::store::StateMachineStore;
+use crate::store::TypeConfig;
+
+/// Snapshots built per second by a single node cluster, from triggering a snapshot until it is
+/// reported in the metrics.
+pub fn bench_build_snapshot(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut group = c.benchmark_group("build_snapshot");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+
+    let router = rt.block_on(new_cluster(btreeset! {0}));
+    let leader = router.get_raft(0);
+
+    group.bench_function("single_node", |b| {
+        b.to_async(&rt).iter_custom(|iters| {
+            let leader = leader.clone();
+            async move {
+                let mut elapsed = Duration::ZERO;
+                for _ in 0..iters {
+                    // A new log makes every snapshot different from the last one.
+                    let log_id = leader.client_write(ClientRequest {}).await.unwrap().log_id;
+
+                    let now = Instant::now();
+                    leader.trigger().snapshot().await.unwrap();
+                    leader.wait(timeout()).snapshot(log_id, "build snapshot").await.unwrap();
+                    elapsed += now.elapsed();
+                }
+                elapsed
+            }
+        });
+    });
+    group.finish();
+
+    rt.block_on(shutdown(router));
+}
+
+/// Bytes per second of installing a full snapshot of different sizes on a follower.
+pub fn bench_install_snapshot(c: &mut Criterion) {
+    let rt = runtime();
+
+    let mut group = c.benchmark_group("install_snapshot");
+    group.sample_size(10);
+
+    let follower = rt.block_on(async {
+        let config = Arc::new(Config::default().validate().unwrap());
+        let log_store = Arc::new(LogStore::default());
+        let sm = Arc::new(StateMachineStore::new());
+        Raft::new(1, config, Router::new(), log_store, sm).await.unwrap()
+    });
+
+    let mut index = 0;
+
+    for size in [4 * 1024, 1024 * 1024] {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("bytes", size), &size, |b, &size| {
+            b.to_async(&rt).iter_custom(|iters| {
+                let follower = follower.clone();
+
+                // Every snapshot is newer than the installed one, otherwise it is ignored.
+                let snapshots = (0..iters)
+                    .map(|_| {
+                        index += 1;
+                        new_snapshot(index, size)
+                    })
+                    .collect::<Vec<_>>();
+
+                async move {
+                    let now = Instant::now();
+                    for snapshot in snapshots {
+                        follower.install_full_snapshot(Vote::new_committed(1, 0), snapshot).await.unwrap();
+                    }
+                    now.elapsed()
+                }
+            });
+        });
+    }
+    group.finish();
+
+    rt.block_on(follower.shutdown()).unwrap();
+}
+
+/// Build a snapshot at `index` whose data is about `size` bytes, padded with a field the state
+/// machine ignores.
+fn new_snapshot(index: u64, size: usize) -> Snapshot<TypeConfig> {
+    let last_log_id = log_id::<TypeConfig>(1, 0, index);
+    let membership = Membership::new_with_defaults(vec![btreeset! {0}], []);
+    let last_membership = StoredMembership::new(Some(log_id::<TypeConfig>(1, 0, 1)), membership);
+
+    let sm = StateMachine {
+        last_applied_log: Some(last_log_id),
+        last_membership: last_membership.clone(),
+    };
+    let mut data = serde_json::to_value(sm).unwrap();
+    data["padding"] = "x".repeat(size).into();
+
+    Snapshot {
+        meta: SnapshotMeta {
+            last_log_id: Some(last_log_id),
+            last_membership,
+            snapshot_id: format!("bench-{}", index),
+        },
+        snapshot: Cursor::new(serde_json::to_vec(&data).unwrap()),
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
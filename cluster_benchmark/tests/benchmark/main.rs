@@ -2,6 +2,9 @@
 #![deny(unused_qualifications)]
 #![cfg_attr(feature = "bt", feature(error_generic_member_access))]
 
+// Used by the criterion benches in `benches/`.
+use criterion as _;
+
 pub(crate) mod network;
 pub(crate) mod store;
 
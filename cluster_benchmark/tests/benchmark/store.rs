@@ -276,7 +276,7 @@ impl RaftStateMachine<TypeConfig> for Arc<StateMachineStore> {
             sm.last_applied_log = Some(entry.log_id);
 
             match entry.payload {
-                EntryPayload::Blank => res.push(ClientResponse {}),
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => res.push(ClientResponse {}),
                 EntryPayload::Normal(_) => res.push(ClientResponse {}),
                 EntryPayload::Membership(ref mem) => {
                     sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
@@ -0,0 +1,52 @@
+//! The wire format of Raft RPC bodies.
+//!
+//! The sending end, [`NetworkFactory`](crate::NetworkFactory), and the receiving end, the HTTP
+//! handlers of an application, must use the same [`Codec`].
+
+use std::io;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Encodes and decodes the bodies of Raft RPC requests and responses.
+pub trait Codec: Send + Sync + 'static {
+    /// The `Content-Type` of an encoded body.
+    const CONTENT_TYPE: &'static str;
+
+    fn encode<T: Serialize>(value: &T) -> io::Result<Vec<u8>>;
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> io::Result<T>;
+}
+
+/// Human-readable JSON, convenient for debugging with `curl`.
+pub struct Json;
+
+impl Codec for Json {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn encode<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> io::Result<T> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Compact binary encoding with `bincode`.
+///
+/// It is smaller and faster than [`Json`], especially for `AppendEntries` requests that carry
+/// many entries.
+pub struct Bincode;
+
+impl Codec for Bincode {
+    const CONTENT_TYPE: &'static str = "application/octet-stream";
+
+    fn encode<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+        bincode::serialize(value).map_err(io::Error::other)
+    }
+
+    fn decode<T: DeserializeOwned>(bytes: &[u8]) -> io::Result<T> {
+        bincode::deserialize(bytes).map_err(io::Error::other)
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::marker::PhantomData;
 
 use openraft::error::Infallible;
 use openraft::error::InstallSnapshotError;
@@ -25,17 +26,46 @@ use tokio::io::AsyncRead;
 use tokio::io::AsyncSeek;
 use tokio::io::AsyncWrite;
 
-pub struct NetworkFactory {}
+use crate::codec::Bincode;
+use crate::codec::Codec;
 
-impl<C> RaftNetworkFactory<C> for NetworkFactory
+pub mod codec;
+
+/// Creates HTTP clients to other nodes, which encode RPC bodies with the codec `K`.
+pub struct NetworkFactory<K: Codec = Bincode> {
+    _codec: PhantomData<K>,
+}
+
+impl NetworkFactory {
+    /// Create a factory that encodes RPC bodies with the default codec, [`Bincode`].
+    pub fn new() -> Self {
+        Self::with_codec()
+    }
+}
+
+impl<K: Codec> NetworkFactory<K> {
+    /// Create a factory that encodes RPC bodies with the codec `K`.
+    pub fn with_codec() -> Self {
+        Self { _codec: PhantomData }
+    }
+}
+
+impl Default for NetworkFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, K> RaftNetworkFactory<C> for NetworkFactory<K>
 where
     C: RaftTypeConfig<Node = BasicNode>,
+    K: Codec,
     // RaftNetworkV2 is implemented automatically for RaftNetwork, but requires the following trait bounds.
     // In V2 network, the snapshot has no constraints, but RaftNetwork assumes a Snapshot is a file-like
     // object that can be seeked, read from, and written to.
     <C as RaftTypeConfig>::SnapshotData: AsyncRead + AsyncWrite + AsyncSeek + Unpin,
 {
-    type Network = Network<C>;
+    type Network = Network<C, K>;
 
     #[tracing::instrument(level = "debug", skip_all)]
     async fn new_client(&mut self, target: C::NodeId, node: &BasicNode) -> Self::Network {
@@ -43,20 +73,28 @@ where
 
         let client = Client::builder().no_proxy().build().unwrap();
 
-        Network { addr, client, target }
+        Network {
+            addr,
+            client,
+            target,
+            _codec: PhantomData,
+        }
     }
 }
 
-pub struct Network<C>
+pub struct Network<C, K = Bincode>
 where C: RaftTypeConfig
 {
     addr: String,
     client: Client,
     target: C::NodeId,
+    _codec: PhantomData<K>,
 }
 
-impl<C> Network<C>
-where C: RaftTypeConfig
+impl<C, K> Network<C, K>
+where
+    C: RaftTypeConfig,
+    K: Codec,
 {
     async fn request<Req, Resp, Err>(&mut self, uri: impl Display, req: Req) -> Result<Result<Resp, Err>, RPCError<C>>
     where
@@ -71,16 +109,26 @@ where C: RaftTypeConfig
         //     serde_json::to_string_pretty(&req).unwrap()
         // );
 
-        let resp = self.client.post(url.clone()).json(&req).send().await.map_err(|e| {
-            if e.is_connect() {
-                // `Unreachable` informs the caller to backoff for a short while to avoid error log flush.
-                RPCError::Unreachable(Unreachable::new(&e))
-            } else {
-                RPCError::Network(NetworkError::new(&e))
-            }
-        })?;
+        let body = K::encode(&req).map_err(|e| NetworkError::new(&e))?;
+
+        let resp = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, K::CONTENT_TYPE)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    // `Unreachable` informs the caller to backoff for a short while to avoid error log flush.
+                    RPCError::Unreachable(Unreachable::new(&e))
+                } else {
+                    RPCError::Network(NetworkError::new(&e))
+                }
+            })?;
 
-        let res: Result<Resp, Err> = resp.json().await.map_err(|e| NetworkError::new(&e))?;
+        let bytes = resp.bytes().await.map_err(|e| NetworkError::new(&e))?;
+        let res: Result<Resp, Err> = K::decode(&bytes).map_err(|e| NetworkError::new(&e))?;
         // println!(
         //     "<<< network recv reply from {}: {}",
         //     url,
@@ -92,8 +140,10 @@ where C: RaftTypeConfig
 }
 
 #[allow(clippy::blocks_in_conditions)]
-impl<C> RaftNetwork<C> for Network<C>
-where C: RaftTypeConfig
+impl<C, K> RaftNetwork<C> for Network<C, K>
+where
+    C: RaftTypeConfig,
+    K: Codec,
 {
     #[tracing::instrument(level = "debug", skip_all, err(Debug))]
     async fn append_entries(
@@ -0,0 +1,34 @@
+//! This mod implements the Raft protocol API of a node.
+
+use crate::app::App;
+use crate::decode;
+use crate::encode;
+use crate::typ::*;
+
+pub async fn vote(app: &mut App, req: String) -> String {
+    let res = app.raft.vote(decode(&req)).await;
+    encode(res)
+}
+
+pub async fn append(app: &mut App, req: String) -> String {
+    let res = app.raft.append_entries(decode(&req)).await;
+    encode(res)
+}
+
+/// Receive a snapshot and install it.
+pub async fn snapshot(app: &mut App, req: String) -> String {
+    let (vote, snapshot_meta, snapshot_data): (Vote, SnapshotMeta, SnapshotData) = decode(&req);
+    let snapshot = Snapshot {
+        meta: snapshot_meta,
+        snapshot: snapshot_data,
+    };
+    let res = app.raft.install_full_snapshot(vote, snapshot).await.map_err(RaftError::<Infallible>::Fatal);
+    encode(res)
+}
+
+/// Receive a request to take over the leadership, sent by the leader with
+/// `Raft::trigger().transfer_leader()`.
+pub async fn transfer_leader(app: &mut App, req: String) -> String {
+    let res = app.raft.handle_transfer_leader(decode(&req)).await.map_err(RaftError::<Infallible>::Fatal);
+    encode(res)
+}
@@ -0,0 +1,55 @@
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+use crate::api;
+use crate::router::Router;
+use crate::typ;
+use crate::NodeId;
+
+pub type Path = String;
+pub type Payload = String;
+pub type ResponseTx = oneshot::Sender<String>;
+pub type RequestTx = mpsc::UnboundedSender<(Path, Payload, ResponseTx)>;
+
+/// Receives the Raft protocol requests sent to a node through the [`Router`].
+///
+/// Client requests do not go through the router: a client calls `Raft::client_write()` of the
+/// leader directly.
+pub struct App {
+    pub id: NodeId,
+    pub raft: typ::Raft,
+
+    /// Receive Raft protocol requests.
+    pub rx: mpsc::UnboundedReceiver<(Path, Payload, ResponseTx)>,
+}
+
+impl App {
+    pub fn new(id: NodeId, raft: typ::Raft, router: &Router) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        {
+            let mut targets = router.targets.lock().unwrap();
+            targets.insert(id, tx);
+        }
+
+        Self { id, raft, rx }
+    }
+
+    pub async fn run(mut self) -> Option<()> {
+        loop {
+            let (path, payload, response_tx) = self.rx.recv().await?;
+
+            let res = match path.as_str() {
+                "/raft/append" => api::append(&mut self, payload).await,
+                "/raft/snapshot" => api::snapshot(&mut self, payload).await,
+                "/raft/vote" => api::vote(&mut self, payload).await,
+                "/raft/transfer-leader" => api::transfer_leader(&mut self, payload).await,
+
+                _ => panic!("unknown path: {}", path),
+            };
+
+            // The sender may have given up waiting, e.g., a vote request that timed out.
+            let _ = response_tx.send(res);
+        }
+    }
+}
@@ -0,0 +1,150 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+//! Apply every write request of a client exactly once, even if the client retries it.
+//!
+//! A [`Client`] tags every write with its session and a sequence number, and retries it with the
+//! same tag until receiving a reply. The state machine stores the response of the last request of
+//! every session in a [`SessionTable`](client_session::SessionTable): a retry of an applied request
+//! is replied with the stored response instead of being applied again.
+//!
+//! The Raft protocol requests between the nodes go through an in-process [`Router`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft::Config;
+
+use crate::app::App;
+use crate::client_session::ClientSession;
+use crate::client_session::SessionError;
+use crate::router::Router;
+use crate::store::Command;
+use crate::store::Request;
+use crate::store::Response;
+use crate::store::StateMachineData;
+
+pub mod router;
+
+pub mod api;
+pub mod app;
+#[path = "../../utils/client_session.rs"]
+pub mod client_session;
+pub mod network;
+pub mod store;
+
+pub type NodeId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store.
+    pub TypeConfig, pub mod typ:
+        D = Request,
+        R = Response,
+        // In this example, snapshot is just a copy of the state machine.
+        // And it can be any type.
+        SnapshotData = StateMachineData,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+
+pub fn encode<T: serde::Serialize>(t: T) -> String {
+    serde_json::to_string(&t).unwrap()
+}
+
+pub fn decode<T: serde::de::DeserializeOwned>(s: &str) -> T {
+    serde_json::from_str(s).unwrap()
+}
+
+/// Create a Raft node, and register it to `router`.
+///
+/// Run the returned [`App`] to serve the Raft protocol requests from the other nodes.
+pub async fn new_raft(node_id: NodeId, router: &Router) -> (typ::Raft, App, Arc<StateMachineStore>) {
+    // Create a configuration for the raft instance.
+    let config = Config {
+        heartbeat_interval: 100,
+        election_timeout_min: 300,
+        election_timeout_max: 600,
+        // Once snapshot is built, delete the logs at once.
+        // So that all further replication will be based on the snapshot.
+        max_in_snapshot_log_to_keep: 0,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config.validate().unwrap());
+
+    // Create a instance of where the Raft logs will be stored.
+    let log_store = LogStore::default();
+
+    // Create a instance of where the state machine data will be stored.
+    let state_machine_store = Arc::new(StateMachineStore::default());
+
+    // Create a local raft instance.
+    let raft = openraft::Raft::new(node_id, config, router.clone(), log_store, state_machine_store.clone())
+        .await
+        .unwrap();
+
+    let app = App::new(node_id, raft.clone(), router);
+
+    (raft, app, state_machine_store)
+}
+
+/// A client that retries a write until it receives a reply.
+///
+/// A write that times out may or may not be applied: it is retried with the same sequence number,
+/// so that it is applied at most once.
+pub struct Client {
+    session: ClientSession,
+
+    /// The nodes of the cluster, to find the leader.
+    nodes: BTreeMap<NodeId, typ::Raft>,
+
+    /// How long to wait for the reply to a write before retrying it.
+    timeout: Duration,
+}
+
+impl Client {
+    pub fn new(client_id: impl ToString, nodes: BTreeMap<NodeId, typ::Raft>) -> Self {
+        Self {
+            session: ClientSession::new(client_id),
+            nodes,
+            timeout: Duration::from_millis(1_000),
+        }
+    }
+
+    /// Apply `cmd` exactly once, and return the value it wrote.
+    pub async fn write(&mut self, cmd: Command) -> Result<String, SessionError> {
+        let req = self.session.request(cmd);
+        self.send(&req).await
+    }
+
+    /// Send a request to the leader until receiving a reply.
+    ///
+    /// Sending a request again returns the reply to the first attempt, as long as it is the last
+    /// request of the session.
+    pub async fn send(&self, req: &Request) -> Result<String, SessionError> {
+        loop {
+            if let Some(leader) = self.leader() {
+                match tokio::time::timeout(self.timeout, leader.client_write(req.clone())).await {
+                    Ok(Ok(resp)) => {
+                        return match resp.data {
+                            Response::Value(value) => Ok(value),
+                            Response::SessionError(e) => Err(e),
+                            Response::None => unreachable!("a client request is replied with a value or an error"),
+                        };
+                    }
+                    Ok(Err(e)) => tracing::info!("retry {}, after error: {}", req, e),
+                    Err(_elapsed) => tracing::info!("retry {}, after timeout", req),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    fn leader(&self) -> Option<&typ::Raft> {
+        let leader_id = self.nodes.values().find_map(|raft| raft.metrics().borrow().current_leader)?;
+        self.nodes.get(&leader_id)
+    }
+}
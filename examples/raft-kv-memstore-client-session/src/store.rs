@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft::storage::RaftStateMachine;
+use openraft::ApplyCommand;
+use openraft::EntryPayload;
+use openraft::RaftSnapshotBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::client_session::SessionError;
+use crate::client_session::SessionRequest;
+use crate::client_session::SessionTable;
+use crate::typ::*;
+use crate::TypeConfig;
+
+pub type LogStore = mem_log::LogStore<TypeConfig>;
+
+/// A write command of a client.
+///
+/// `Incr` is not idempotent: applying it twice returns another value, and leaves another value in
+/// the state machine.
+///
+/// `Command::apply()`, which applies a command to the key-value data, is generated by
+/// `ApplyCommand`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ApplyCommand)]
+#[apply(state = BTreeMap<String, String>, response = Response)]
+pub enum Command {
+    #[apply(with = set, map = Response::Value)]
+    Set { key: String, value: String },
+
+    /// Add one to the integer value of `key`, which is `0` if it does not exist or is not an
+    /// integer.
+    #[apply(with = incr, map = Response::Value)]
+    Incr { key: String },
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Set { key, value } => write!(f, "Set {{ key: {}, value: {} }}", key, value),
+            Command::Incr { key } => write!(f, "Incr {{ key: {} }}", key),
+        }
+    }
+}
+
+impl Command {
+    pub fn set(key: impl ToString, value: impl ToString) -> Self {
+        Self::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn incr(key: impl ToString) -> Self {
+        Self::Incr { key: key.to_string() }
+    }
+}
+
+/// A command tagged with the client session and the sequence number of the request.
+pub type Request = SessionRequest<Command>;
+
+/// The result of applying a log entry to the state machine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// A blank or membership log entry is applied.
+    None,
+
+    /// A command is applied, or it is a retry of an applied command: the value it wrote.
+    Value(String),
+
+    /// The request is not applied.
+    SessionError(SessionError),
+}
+
+#[derive(Debug)]
+pub struct StoredSnapshot {
+    pub meta: SnapshotMeta,
+
+    /// The data of the state machine at the time of this snapshot.
+    pub data: SnapshotData,
+}
+
+/// Data contained in the Raft state machine.
+///
+/// Note that we are using `serde` to serialize the
+/// `data`, which has a implementation to be serialized. Note that for this test we set both the key
+/// and value as String, but you could set any type of value that has the serialization impl.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct StateMachineData {
+    pub last_applied: Option<LogId>,
+
+    pub last_membership: StoredMembership,
+
+    /// Application data.
+    pub data: BTreeMap<String, String>,
+
+    /// The response of the last request of every client.
+    ///
+    /// It is part of the state machine, thus it is included in a snapshot: a node that installs a
+    /// snapshot replies a retried request the same as the node that applied it.
+    pub sessions: SessionTable<Response>,
+}
+
+/// Defines a state machine for the Raft cluster. This state machine represents a copy of the
+/// data for this node. Additionally, it is responsible for storing the last snapshot of the data.
+#[derive(Debug, Default)]
+pub struct StateMachineStore {
+    /// The Raft state machine.
+    pub state_machine: Mutex<StateMachineData>,
+
+    snapshot_idx: Mutex<u64>,
+
+    /// The last received snapshot.
+    current_snapshot: Mutex<Option<StoredSnapshot>>,
+}
+
+impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn build_snapshot(&mut self) -> Result<Snapshot, StorageError> {
+        let data;
+        let last_applied_log;
+        let last_membership;
+
+        {
+            // Serialize the data of the state machine.
+            let state_machine = self.state_machine.lock().unwrap().clone();
+
+            last_applied_log = state_machine.last_applied;
+            last_membership = state_machine.last_membership.clone();
+            data = state_machine;
+        }
+
+        let snapshot_idx = {
+            let mut l = self.snapshot_idx.lock().unwrap();
+            *l += 1;
+            *l
+        };
+
+        let snapshot_id = if let Some(last) = last_applied_log {
+            format!("{}-{}-{}", last.committed_leader_id(), last.index(), snapshot_idx)
+        } else {
+            format!("--{}", snapshot_idx)
+        };
+
+        let meta = SnapshotMeta {
+            last_log_id: last_applied_log,
+            last_membership,
+            snapshot_id,
+        };
+
+        let snapshot = StoredSnapshot {
+            meta: meta.clone(),
+            data: data.clone(),
+        };
+
+        {
+            let mut current_snapshot = self.current_snapshot.lock().unwrap();
+            *current_snapshot = Some(snapshot);
+        }
+
+        Ok(Snapshot { meta, snapshot: data })
+    }
+}
+
+/// Set `key` to `value`, and return the value written.
+fn set(data: &mut BTreeMap<String, String>, key: &str, value: &str) -> String {
+    data.insert(key.to_string(), value.to_string());
+    value.to_string()
+}
+
+/// Add one to the value of `key`, and return the value written.
+fn incr(data: &mut BTreeMap<String, String>, key: &str) -> String {
+    let n = data.get(key).and_then(|v| v.parse::<i64>().ok()).unwrap_or_default() + 1;
+    data.insert(key.to_string(), n.to_string());
+    n.to_string()
+}
+
+impl RaftStateMachine<TypeConfig> for Arc<StateMachineStore> {
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogId>, StoredMembership), StorageError> {
+        let state_machine = self.state_machine.lock().unwrap();
+        Ok((state_machine.last_applied, state_machine.last_membership.clone()))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, entries))]
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<Response>, StorageError>
+    where I: IntoIterator<Item = Entry> {
+        let mut res = Vec::new(); //No `with_capacity`; do not know `len` of iterator
+
+        let mut sm = self.state_machine.lock().unwrap();
+
+        for entry in entries {
+            tracing::debug!(%entry.log_id, "replicate to sm");
+
+            sm.last_applied = Some(entry.log_id);
+
+            match entry.payload {
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => res.push(Response::None),
+                EntryPayload::Normal(ref req) => {
+                    let StateMachineData { data, sessions, .. } = &mut *sm;
+
+                    let res_or_err = sessions.apply(req, |cmd| cmd.apply(data));
+                    res.push(res_or_err.unwrap_or_else(Response::SessionError))
+                }
+                EntryPayload::Membership(ref mem) => {
+                    sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
+                    res.push(Response::None)
+                }
+            };
+        }
+        Ok(res)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotData, StorageError> {
+        Ok(Default::default())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, snapshot))]
+    async fn install_snapshot(&mut self, meta: &SnapshotMeta, snapshot: SnapshotData) -> Result<(), StorageError> {
+        tracing::info!("install snapshot");
+
+        let new_snapshot = StoredSnapshot {
+            meta: meta.clone(),
+            data: snapshot,
+        };
+
+        // Update the state machine.
+        {
+            let updated_state_machine: StateMachineData = new_snapshot.data.clone();
+            let mut state_machine = self.state_machine.lock().unwrap();
+            *state_machine = updated_state_machine;
+        }
+
+        // Update current snapshot.
+        let mut current_snapshot = self.current_snapshot.lock().unwrap();
+        *current_snapshot = Some(new_snapshot);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot>, StorageError> {
+        match &*self.current_snapshot.lock().unwrap() {
+            Some(snapshot) => {
+                let data = snapshot.data.clone();
+                Ok(Some(Snapshot {
+                    meta: snapshot.meta.clone(),
+                    snapshot: data,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+}
@@ -0,0 +1,3 @@
+#!/bin/bash
+
+echo "No shell test script for this example"
\ No newline at end of file
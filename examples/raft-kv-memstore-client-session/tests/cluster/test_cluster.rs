@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft::BasicNode;
+use raft_kv_memstore_client_session::client_session::ClientSession;
+use raft_kv_memstore_client_session::client_session::SessionError;
+use raft_kv_memstore_client_session::new_raft;
+use raft_kv_memstore_client_session::router::Router;
+use raft_kv_memstore_client_session::store::Command;
+use raft_kv_memstore_client_session::typ::Raft;
+use raft_kv_memstore_client_session::Client;
+use raft_kv_memstore_client_session::NodeId;
+use raft_kv_memstore_client_session::StateMachineStore;
+
+/// A retried request is applied once, and replied with the response of the first attempt:
+///
+/// - when the reply to the first attempt is lost;
+/// - when it is retried on another leader;
+/// - on a node that received the state machine in a snapshot.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_cluster() {
+    let router = Router::default();
+
+    let mut rafts = BTreeMap::<NodeId, Raft>::new();
+    let mut state_machines = BTreeMap::<NodeId, Arc<StateMachineStore>>::new();
+
+    for node_id in 1..=4 {
+        let (raft, app, state_machine) = new_raft(node_id, &router).await;
+        tokio::spawn(app.run());
+        rafts.insert(node_id, raft);
+        state_machines.insert(node_id, state_machine);
+    }
+
+    println!("=== init cluster of node 1,2,3");
+    let leader_id = {
+        let nodes = (1..=3).map(|id| (id, BasicNode::default())).collect::<BTreeMap<_, _>>();
+        rafts[&1].initialize(nodes).await.unwrap();
+
+        let metrics = rafts[&1]
+            .wait(timeout())
+            .metrics(|m| m.current_leader.is_some(), "a leader is elected")
+            .await
+            .unwrap();
+        metrics.current_leader.unwrap()
+    };
+
+    let voters = rafts.iter().filter(|(id, _)| **id <= 3).map(|(id, raft)| (*id, raft.clone())).collect();
+    let mut client = Client::new("c1", voters);
+
+    println!("=== incr: a new request is applied");
+    {
+        assert_eq!(Ok("1".to_string()), client.write(Command::incr("n")).await);
+    }
+
+    println!("=== incr: the reply to the first attempt is lost, the retry is not applied again");
+    let req = {
+        let mut session = ClientSession::new("c2");
+        let req = session.request(Command::incr("n"));
+
+        // The request is applied, but the reply does not reach the client.
+        let _lost = rafts[&leader_id].client_write(req.clone()).await.unwrap();
+
+        assert_eq!(Ok("2".to_string()), client.send(&req).await);
+        assert_eq!(Ok("2".to_string()), client.send(&req).await);
+        assert_eq!(Ok("3".to_string()), client.write(Command::incr("n")).await);
+        req
+    };
+
+    println!("=== retry on a new leader");
+    {
+        let new_leader = (1..=3).find(|id| *id != leader_id).unwrap();
+        rafts[&leader_id].trigger().transfer_leader(new_leader).await.unwrap();
+        rafts[&leader_id]
+            .wait(timeout())
+            .metrics(
+                |m| m.current_leader.is_some() && m.current_leader != Some(leader_id),
+                "another node becomes the leader",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(Ok("2".to_string()), client.send(&req).await);
+        assert_eq!(Ok("4".to_string()), client.write(Command::incr("n")).await);
+    }
+
+    println!("=== a request older than the last one of its session is rejected");
+    {
+        let mut session = ClientSession::new("c2");
+        let mut older = session.request(Command::incr("n"));
+        older.seq = 0;
+
+        let want = SessionError::Stale {
+            client: "c2".to_string(),
+            seq: 0,
+            last_seq: 1,
+        };
+        assert_eq!(Err(want), client.send(&older).await);
+    }
+
+    println!("=== node 4 receives the sessions in a snapshot, and replies a retry the same");
+    {
+        let leader_id = rafts[&1].metrics().borrow().current_leader.unwrap();
+        let leader = &rafts[&leader_id];
+
+        leader.trigger().snapshot().await.unwrap();
+        let metrics = leader
+            .wait(timeout())
+            .metrics(
+                |m| m.snapshot.is_some() && m.snapshot == m.last_applied,
+                "snapshot built",
+            )
+            .await
+            .unwrap();
+        let last_log_index = metrics.snapshot.map(|s| s.index);
+
+        leader.add_learner(4, BasicNode::default(), true).await.unwrap();
+        leader.change_membership(BTreeSet::from([4, 1, 2, 3]), false).await.unwrap();
+
+        let n4 = &rafts[&4];
+        n4.wait(timeout())
+            .metrics(
+                |m| m.snapshot.map(|s| s.index) == last_log_index,
+                "node 4 installs the snapshot",
+            )
+            .await
+            .unwrap();
+
+        {
+            let sm = state_machines[&4].state_machine.lock().unwrap();
+            assert_eq!(Some("4"), sm.data.get("n").map(|v| v.as_str()));
+            assert_eq!(Some(1), sm.sessions.last_seq("c2"));
+            assert_eq!(Some(3), sm.sessions.last_seq("c1"));
+        }
+
+        transfer_leader(&rafts, 4).await;
+
+        let client = Client::new("c3", rafts.clone());
+        assert_eq!(Ok("2".to_string()), client.send(&req).await);
+
+        let mut client = Client::new("c3", rafts.clone());
+        assert_eq!(Ok("5".to_string()), client.write(Command::incr("n")).await);
+    }
+
+    for raft in rafts.values() {
+        raft.shutdown().await.unwrap();
+    }
+}
+
+/// Transfer the leadership to `to`, again if another node wins the election.
+async fn transfer_leader(rafts: &BTreeMap<NodeId, Raft>, to: NodeId) {
+    for _ in 0..10 {
+        let leader_id = rafts[&to].metrics().borrow().current_leader;
+        if let Some(leader_id) = leader_id {
+            rafts[&leader_id].trigger().transfer_leader(to).await.unwrap();
+        }
+
+        let res = rafts[&to].wait(Some(Duration::from_millis(1_000))).current_leader(to, "transfer leader").await;
+        if res.is_ok() {
+            return;
+        }
+    }
+    panic!("node {} does not become the leader", to);
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -9,9 +9,6 @@ pub mod protobuf {
     tonic::include_proto!("openraftpb");
 }
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
-
 mod pb_impl;
 
 #[cfg(test)]
@@ -21,7 +18,7 @@ use crate::protobuf as pb;
 
 openraft::declare_raft_types!(
     /// Declare the type configuration for example K/V store.
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = pb::SetRequest,
         R = pb::Response,
         LeaderId = pb::LeaderId,
@@ -3,12 +3,12 @@ use crate::typ::AppendEntriesRequest;
 
 impl From<pb::AppendEntriesRequest> for AppendEntriesRequest {
     fn from(proto_req: pb::AppendEntriesRequest) -> Self {
-        AppendEntriesRequest {
-            vote: proto_req.vote.unwrap(),
-            prev_log_id: proto_req.prev_log_id.map(|log_id| log_id.into()),
-            entries: proto_req.entries,
-            leader_commit: proto_req.leader_commit.map(|log_id| log_id.into()),
-        }
+        AppendEntriesRequest::new(
+            proto_req.vote.unwrap(),
+            proto_req.prev_log_id.map(|log_id| log_id.into()),
+            proto_req.entries,
+            proto_req.leader_commit.map(|log_id| log_id.into()),
+        )
     }
 }
 
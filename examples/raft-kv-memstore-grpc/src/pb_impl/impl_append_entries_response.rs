@@ -11,10 +11,22 @@ impl From<pb::AppendEntriesResponse> for AppendEntriesResponse {
             return AppendEntriesResponse::Conflict;
         }
 
+        if r.apply_backlog > 0 {
+            return AppendEntriesResponse::Busy {
+                apply_backlog: r.apply_backlog,
+            };
+        }
+
         if let Some(log_id) = r.last_log_id {
             AppendEntriesResponse::PartialSuccess(Some(log_id.into()))
         } else {
             AppendEntriesResponse::Success
+            AppendEntriesResponse::Busy { apply_backlog } => pb::AppendEntriesResponse {
+                rejected_by: None,
+                conflict: false,
+                last_log_id: None,
+                apply_backlog,
+            },
         }
     }
 }
@@ -26,21 +38,31 @@ impl From<AppendEntriesResponse> for pb::AppendEntriesResponse {
                 rejected_by: None,
                 conflict: false,
                 last_log_id: None,
+                apply_backlog: 0,
             },
             AppendEntriesResponse::PartialSuccess(p) => pb::AppendEntriesResponse {
                 rejected_by: None,
                 conflict: false,
                 last_log_id: p.map(|log_id| log_id.into()),
+                apply_backlog: 0,
             },
             AppendEntriesResponse::Conflict => pb::AppendEntriesResponse {
                 rejected_by: None,
                 conflict: true,
                 last_log_id: None,
+                apply_backlog: 0,
             },
             AppendEntriesResponse::HigherVote(v) => pb::AppendEntriesResponse {
                 rejected_by: Some(v),
                 conflict: false,
                 last_log_id: None,
+                apply_backlog: 0,
+            },
+            AppendEntriesResponse::Busy { apply_backlog } => pb::AppendEntriesResponse {
+                rejected_by: None,
+                conflict: false,
+                last_log_id: None,
+                apply_backlog,
             },
         }
     }
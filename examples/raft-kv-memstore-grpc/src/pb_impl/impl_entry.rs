@@ -25,10 +25,12 @@ impl RaftEntry<TypeConfig> for pb::Entry {
     fn new(log_id: LogIdOf<TypeConfig>, payload: EntryPayload<TypeConfig>) -> Self {
         let mut app_data = None;
         let mut membership = None;
+        let mut version_bump = None;
         match payload {
             EntryPayload::Blank => {}
             EntryPayload::Normal(data) => app_data = Some(data),
             EntryPayload::Membership(m) => membership = Some(m.into()),
+            EntryPayload::VersionBump(v) => version_bump = Some(v),
         }
 
         Self {
@@ -36,6 +38,7 @@ impl RaftEntry<TypeConfig> for pb::Entry {
             index: log_id.index,
             app_data,
             membership,
+            version_bump,
         }
     }
 
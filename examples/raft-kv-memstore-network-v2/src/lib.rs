@@ -22,7 +22,7 @@ pub type NodeId = u64;
 
 openraft::declare_raft_types!(
     /// Declare the type configuration for example K/V store.
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = Request,
         R = Response,
         // In this example, snapshot is just a copy of the state machine.
@@ -33,9 +33,6 @@ openraft::declare_raft_types!(
 pub type LogStore = store::LogStore;
 pub type StateMachineStore = store::StateMachineStore;
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
-
 pub fn encode<T: serde::Serialize>(t: T) -> String {
     serde_json::to_string(&t).unwrap()
 }
@@ -147,7 +147,7 @@ impl RaftStateMachine<TypeConfig> for Arc<StateMachineStore> {
             sm.last_applied = Some(entry.log_id);
 
             match entry.payload {
-                EntryPayload::Blank => res.push(Response { value: None }),
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => res.push(Response { value: None }),
                 EntryPayload::Normal(ref req) => match req {
                     Request::Set { key, value, .. } => {
                         sm.data.insert(key.clone(), value.clone());
@@ -24,7 +24,7 @@ pub type NodeId = u64;
 
 openraft::declare_raft_types!(
     /// Declare the type configuration for example K/V store.
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = Request,
         R = Response,
         // In this example, snapshot is a path pointing to a file stored in shared storage.
@@ -34,9 +34,6 @@ openraft::declare_raft_types!(
 pub type LogStore = store::LogStore;
 pub type StateMachineStore = store::StateMachineStore;
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
-
 pub fn encode<T: serde::Serialize>(t: T) -> String {
     serde_json::to_string(&t).unwrap()
 }
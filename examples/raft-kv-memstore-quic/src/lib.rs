@@ -0,0 +1,94 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use openraft::Config;
+
+use crate::network::tls::TlsConfig;
+use crate::network::QuicNetwork;
+use crate::store::Request;
+use crate::store::Response;
+use crate::store::StateMachineData;
+
+pub mod network;
+#[path = "../../utils/mem_kv_store.rs"]
+pub mod store;
+
+pub type NodeId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store.
+    pub TypeConfig, pub mod typ:
+        D = Request,
+        R = Response,
+        // In this example, snapshot is just a copy of the state machine.
+        // And it can be any type.
+        SnapshotData = StateMachineData,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+
+/// A Raft node that serves Raft RPCs over QUIC.
+pub struct RaftNode {
+    pub id: NodeId,
+
+    /// The address the QUIC server of this node listens on.
+    ///
+    /// It is the `addr` of this node in the membership config.
+    pub addr: SocketAddr,
+
+    pub raft: typ::Raft,
+
+    /// The connections from this node to its peers.
+    pub network: QuicNetwork,
+
+    pub state_machine: Arc<StateMachineStore>,
+}
+
+/// Start a Raft node listening on `addr`.
+///
+/// Use port 0 to let the OS pick a port, and get the actual address from [`RaftNode::addr`].
+pub async fn start_raft_node(node_id: NodeId, addr: SocketAddr, tls: &TlsConfig) -> io::Result<RaftNode> {
+    // Create a configuration for the raft instance.
+    let config = Config {
+        heartbeat_interval: 500,
+        election_timeout_min: 1500,
+        election_timeout_max: 3000,
+        // Once snapshot is built, delete the logs at once.
+        // So that all further replication will be based on the snapshot.
+        max_in_snapshot_log_to_keep: 0,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config.validate().unwrap());
+
+    // Create a instance of where the Raft logs will be stored.
+    let log_store = LogStore::default();
+
+    // Create a instance of where the state machine data will be stored.
+    let state_machine_store = Arc::new(StateMachineStore::default());
+
+    let server = network::server::bind(addr, tls)?;
+    let addr = server.local_addr()?;
+
+    let network = QuicNetwork::new(tls)?;
+
+    // Create a local raft instance.
+    let raft = openraft::Raft::new(node_id, config, network.clone(), log_store, state_machine_store.clone())
+        .await
+        .unwrap();
+
+    tokio::spawn(network::server::serve(server, raft.clone()));
+
+    Ok(RaftNode {
+        id: node_id,
+        addr,
+        raft,
+        network,
+        state_machine: state_machine_store,
+    })
+}
@@ -0,0 +1,222 @@
+//! The sending end of the QUIC network.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft::error::NetworkError;
+use openraft::error::ReplicationClosed;
+use openraft::error::Unreachable;
+use openraft::network::v2::RaftNetworkV2;
+use openraft::network::RPCOption;
+use openraft::BasicNode;
+use openraft::OptionalSend;
+use openraft::RaftNetworkFactory;
+
+use crate::network::tls::TlsConfig;
+use crate::network::RpcKind;
+use crate::network::MAX_MESSAGE_SIZE;
+use crate::network::SERVER_NAME;
+use crate::typ::*;
+use crate::NodeId;
+use crate::TypeConfig;
+
+/// Connects this node to its peers.
+///
+/// All RPCs to a peer share one connection, which is re-established when it is lost.
+#[derive(Debug, Clone)]
+pub struct QuicNetwork {
+    endpoint: quinn::Endpoint,
+
+    tls: TlsConfig,
+
+    /// The state of every peer that has been connected.
+    peers: Arc<Mutex<BTreeMap<NodeId, PeerState>>>,
+}
+
+#[derive(Debug)]
+struct PeerState {
+    /// The client config used only for this peer.
+    ///
+    /// Session tickets are cached by the server name, which is the same for all peers in this
+    /// example. A config per peer keeps the tickets of a peer from being sent to another one, which
+    /// would reject the 0-RTT data.
+    client_config: quinn::ClientConfig,
+
+    connection: Option<quinn::Connection>,
+}
+
+impl QuicNetwork {
+    pub fn new(tls: &TlsConfig) -> io::Result<Self> {
+        let endpoint = quinn::Endpoint::client(SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0)))?;
+
+        Ok(Self {
+            endpoint,
+            tls: tls.clone(),
+            peers: Default::default(),
+        })
+    }
+
+    /// Close the connections to all peers.
+    ///
+    /// The next RPC to a peer reconnects. The session tickets received on the closed connections
+    /// are kept, so that the reconnection sends its first request in 0-RTT data.
+    pub fn close_connections(&self) {
+        let mut peers = self.peers.lock().unwrap();
+        for peer in peers.values_mut() {
+            if let Some(conn) = peer.connection.take() {
+                conn.close(0u32.into(), b"closed by application");
+            }
+        }
+    }
+}
+
+impl RaftNetworkFactory<TypeConfig> for QuicNetwork {
+    type Network = QuicPeer;
+
+    async fn new_client(&mut self, target: NodeId, node: &BasicNode) -> Self::Network {
+        QuicPeer {
+            network: self.clone(),
+            target,
+            addr: node.addr.clone(),
+        }
+    }
+}
+
+/// Sends RPCs to a single peer.
+pub struct QuicPeer {
+    network: QuicNetwork,
+    target: NodeId,
+    addr: String,
+}
+
+impl QuicPeer {
+    /// Return the connection to the target, connect if there is no usable one.
+    async fn connection(&self) -> Result<quinn::Connection, Unreachable> {
+        let client_config = {
+            let mut peers = self.network.peers.lock().unwrap();
+
+            let peer = match peers.entry(self.target) {
+                Entry::Occupied(e) => e.into_mut(),
+                Entry::Vacant(e) => e.insert(PeerState {
+                    client_config: self.network.tls.client_config().map_err(|e| Unreachable::new(&e))?,
+                    connection: None,
+                }),
+            };
+
+            if let Some(conn) = &peer.connection {
+                if conn.close_reason().is_none() {
+                    return Ok(conn.clone());
+                }
+            }
+
+            peer.client_config.clone()
+        };
+
+        let addr: SocketAddr = self.addr.parse().map_err(|e| Unreachable::new(&e))?;
+        let connecting = self
+            .network
+            .endpoint
+            .connect_with(client_config, addr, SERVER_NAME)
+            .map_err(|e| Unreachable::new(&e))?;
+
+        // With a session ticket from a previous connection to this peer, the connection can be
+        // used before the handshake completes, and the first request is sent in 0-RTT data.
+        //
+        // 0-RTT data may be replayed by an attacker. Raft tolerates it: a replayed AppendEntries
+        // or Vote request is the same as a duplicated network packet.
+        let conn = match connecting.into_0rtt() {
+            Ok((conn, _accepted)) => {
+                tracing::debug!("connect to node-{} with 0-RTT", self.target);
+                conn
+            }
+            Err(connecting) => connecting.await.map_err(|e| Unreachable::new(&e))?,
+        };
+
+        if let Some(peer) = self.network.peers.lock().unwrap().get_mut(&self.target) {
+            peer.connection = Some(conn.clone());
+        }
+        Ok(conn)
+    }
+
+    /// Send a request on a new stream and read the response.
+    async fn call<Resp>(&self, kind: RpcKind, req: &[u8]) -> Result<Resp, RPCError>
+    where Resp: serde::de::DeserializeOwned {
+        let conn = self.connection().await?;
+
+        let resp = async {
+            let (mut send, mut recv) = conn.open_bi().await.map_err(|e| self.stream_error(&conn, &e))?;
+
+            send.write_all(&[kind as u8]).await.map_err(|e| self.stream_error(&conn, &e))?;
+            send.write_all(req).await.map_err(|e| self.stream_error(&conn, &e))?;
+            send.finish().map_err(|e| self.stream_error(&conn, &e))?;
+
+            recv.read_to_end(MAX_MESSAGE_SIZE).await.map_err(|e| self.stream_error(&conn, &e))
+        }
+        .await?;
+
+        let res: Result<Resp, RaftError> = serde_json::from_slice(&resp).map_err(|e| NetworkError::new(&e))?;
+
+        // The remote node returns an error only when it is shutting down.
+        res.map_err(|e| Unreachable::new(&e).into())
+    }
+
+    /// Build the error of a failed stream.
+    ///
+    /// A stream fails either because the connection is lost, and the peer may be down, or
+    /// because the stream alone is reset, for example, when the 0-RTT data is rejected by the
+    /// peer. The former backs off before retrying, the latter retries at once.
+    fn stream_error(&self, conn: &quinn::Connection, e: &(impl Error + 'static)) -> RPCError {
+        if conn.close_reason().is_none() {
+            return NetworkError::new(e).into();
+        }
+
+        if let Some(peer) = self.network.peers.lock().unwrap().get_mut(&self.target) {
+            if peer.connection.as_ref().map(|c| c.stable_id()) == Some(conn.stable_id()) {
+                peer.connection = None;
+            }
+        }
+
+        Unreachable::new(e).into()
+    }
+}
+
+impl RaftNetworkV2<TypeConfig> for QuicPeer {
+    async fn append_entries(
+        &mut self,
+        req: AppendEntriesRequest,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse, RPCError> {
+        let req = req.encode().map_err(|e| NetworkError::new(&e))?;
+        self.call(RpcKind::AppendEntries, &req).await
+    }
+
+    /// Send the snapshot on a stream of its own, so that it does not block other RPCs.
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote,
+        snapshot: Snapshot,
+        cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse, StreamingError> {
+        let req = serde_json::to_vec(&(vote, snapshot.meta, snapshot.snapshot)).map_err(|e| NetworkError::new(&e))?;
+
+        // Dropping the unfinished call resets the stream, and the peer discards the partial
+        // snapshot.
+        tokio::select! {
+            resp = self.call(RpcKind::Snapshot, &req) => Ok(resp?),
+            closed = cancel => Err(StreamingError::Closed(closed)),
+        }
+    }
+
+    async fn vote(&mut self, req: VoteRequest, _option: RPCOption) -> Result<VoteResponse, RPCError> {
+        let req = req.encode().map_err(|e| NetworkError::new(&e))?;
+        self.call(RpcKind::Vote, &req).await
+    }
+}
@@ -0,0 +1,62 @@
+//! A Raft network over QUIC.
+//!
+//! A node keeps one QUIC connection to each peer, and sends every RPC on a new bidirectional
+//! stream of this connection. QUIC streams are independent of each other: a large snapshot being
+//! sent does not delay the heartbeats and vote requests sent to the same peer, as it would with
+//! requests queued on a single TCP connection.
+//!
+//! A stream carries exactly one request and its response:
+//!
+//! ```text
+//! client -> server: kind: u8 | request
+//! server -> client: response
+//! ```
+//!
+//! `AppendEntriesRequest` and `VoteRequest` are encoded with their own `encode()`, the snapshot
+//! and all responses are encoded in JSON.
+
+pub mod client;
+pub mod server;
+pub mod tls;
+
+use std::io;
+
+pub use client::QuicNetwork;
+pub use client::QuicPeer;
+
+/// The max size of a request or a response.
+///
+/// A snapshot of this example is sent in a single message, thus it must be large enough to hold
+/// the whole state machine.
+pub const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// The name the server certificate is issued to, and the client connects to.
+pub const SERVER_NAME: &str = "localhost";
+
+/// The application protocol negotiated with TLS.
+pub const ALPN: &[u8] = b"openraft";
+
+/// The RPC carried by a stream, sent as the first byte of the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RpcKind {
+    AppendEntries = 1,
+    Vote = 2,
+    Snapshot = 3,
+}
+
+impl TryFrom<u8> for RpcKind {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(RpcKind::AppendEntries),
+            2 => Ok(RpcKind::Vote),
+            3 => Ok(RpcKind::Snapshot),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unknown rpc kind: {}", value),
+            )),
+        }
+    }
+}
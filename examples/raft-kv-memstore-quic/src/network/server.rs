@@ -0,0 +1,80 @@
+//! The receiving end of the QUIC network.
+
+use std::io;
+use std::net::SocketAddr;
+
+use crate::network::tls::TlsConfig;
+use crate::network::RpcKind;
+use crate::network::MAX_MESSAGE_SIZE;
+use crate::typ::*;
+
+/// Create the endpoint that accepts connections from peers.
+pub fn bind(addr: SocketAddr, tls: &TlsConfig) -> io::Result<quinn::Endpoint> {
+    quinn::Endpoint::server(tls.server_config()?, addr)
+}
+
+/// Accept connections and serve the Raft RPCs received on them, until the endpoint is closed.
+pub async fn serve(endpoint: quinn::Endpoint, raft: Raft) {
+    while let Some(incoming) = endpoint.accept().await {
+        let raft = raft.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_connection(incoming, raft).await {
+                tracing::debug!("connection closed: {}", e);
+            }
+        });
+    }
+}
+
+/// Serve every stream opened on a connection concurrently.
+async fn serve_connection(incoming: quinn::Incoming, raft: Raft) -> Result<(), quinn::ConnectionError> {
+    let connecting = incoming.accept()?;
+
+    // Serve the requests sent in 0-RTT data before the handshake completes.
+    let conn = match connecting.into_0rtt() {
+        Ok((conn, _accepted)) => conn,
+        Err(connecting) => connecting.await?,
+    };
+
+    loop {
+        let (send, recv) = conn.accept_bi().await?;
+
+        let raft = raft.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_stream(send, recv, raft).await {
+                tracing::warn!("failed to serve stream: {}", e);
+            }
+        });
+    }
+}
+
+async fn serve_stream(mut send: quinn::SendStream, mut recv: quinn::RecvStream, raft: Raft) -> io::Result<()> {
+    let req = recv.read_to_end(MAX_MESSAGE_SIZE).await.map_err(io::Error::other)?;
+
+    let Some((kind, body)) = req.split_first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "empty request"));
+    };
+
+    let resp = match RpcKind::try_from(*kind)? {
+        RpcKind::AppendEntries => {
+            let req = AppendEntriesRequest::decode(body).map_err(io::Error::other)?;
+            serde_json::to_vec(&raft.append_entries(req).await)?
+        }
+        RpcKind::Vote => {
+            let req = VoteRequest::decode(body).map_err(io::Error::other)?;
+            serde_json::to_vec(&raft.vote(req).await)?
+        }
+        RpcKind::Snapshot => {
+            let (vote, meta, snapshot): (Vote, SnapshotMeta, SnapshotData) = serde_json::from_slice(body)?;
+            let res = raft
+                .install_full_snapshot(vote, Snapshot { meta, snapshot })
+                .await
+                .map_err(RaftError::<Infallible>::Fatal);
+            serde_json::to_vec(&res)?
+        }
+    };
+
+    send.write_all(&resp).await.map_err(io::Error::other)?;
+    send.finish().map_err(io::Error::other)?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! TLS configuration of the QUIC endpoints.
+//!
+//! QUIC always encrypts. In this example all nodes share one self-signed certificate: a server
+//! presents it and a client trusts only it. A real deployment issues a certificate to every node
+//! from its own CA.
+
+use std::io;
+use std::sync::Arc;
+
+use quinn::crypto::rustls::QuicClientConfig;
+use quinn::crypto::rustls::QuicServerConfig;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivatePkcs8KeyDer;
+
+use crate::network::ALPN;
+use crate::network::SERVER_NAME;
+
+/// The certificate and the private key shared by all nodes of the cluster.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    cert: CertificateDer<'static>,
+
+    /// The PKCS#8 DER encoded private key.
+    key: Vec<u8>,
+}
+
+impl TlsConfig {
+    /// Generate a self-signed certificate for [`SERVER_NAME`].
+    pub fn self_signed() -> io::Result<Self> {
+        let certified = rcgen::generate_simple_self_signed(vec![SERVER_NAME.to_string()]).map_err(io::Error::other)?;
+
+        Ok(Self {
+            cert: certified.cert.der().clone(),
+            key: certified.key_pair.serialize_der(),
+        })
+    }
+
+    /// Build the config of the endpoint that accepts connections from peers.
+    pub fn server_config(&self) -> io::Result<quinn::ServerConfig> {
+        let key = PrivatePkcs8KeyDer::from(self.key.clone());
+
+        let mut crypto =
+            rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_protocol_versions(&[&rustls::version::TLS13])
+                .map_err(io::Error::other)?
+                .with_no_client_auth()
+                .with_single_cert(vec![self.cert.clone()], key.into())
+                .map_err(io::Error::other)?;
+
+        crypto.alpn_protocols = vec![ALPN.to_vec()];
+
+        // Accept requests sent in 0-RTT data by a peer that reconnects with a session ticket.
+        crypto.max_early_data_size = u32::MAX;
+
+        let crypto = QuicServerConfig::try_from(crypto).map_err(io::Error::other)?;
+        Ok(quinn::ServerConfig::with_crypto(Arc::new(crypto)))
+    }
+
+    /// Build the config of the endpoint that connects to peers.
+    pub fn client_config(&self) -> io::Result<quinn::ClientConfig> {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(self.cert.clone()).map_err(io::Error::other)?;
+
+        let mut crypto =
+            rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_protocol_versions(&[&rustls::version::TLS13])
+                .map_err(io::Error::other)?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+
+        crypto.alpn_protocols = vec![ALPN.to_vec()];
+
+        // Send requests in 0-RTT data when reconnecting to a peer this node has connected to.
+        crypto.enable_early_data = true;
+
+        let crypto = QuicClientConfig::try_from(crypto).map_err(io::Error::other)?;
+        Ok(quinn::ClientConfig::new(Arc::new(crypto)))
+    }
+}
@@ -0,0 +1,3 @@
+#![allow(clippy::uninlined_format_args)]
+
+mod test_cluster;
@@ -0,0 +1,131 @@
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::panic::PanicHookInfo;
+use std::time::Duration;
+
+use openraft::BasicNode;
+use raft_kv_memstore_quic::network::tls::TlsConfig;
+use raft_kv_memstore_quic::start_raft_node;
+use raft_kv_memstore_quic::store::Request;
+use raft_kv_memstore_quic::RaftNode;
+use tracing_subscriber::EnvFilter;
+
+pub fn log_panic(panic: &PanicHookInfo) {
+    let backtrace = format!("{:?}", Backtrace::force_capture());
+
+    eprintln!("{}", panic);
+
+    if let Some(location) = panic.location() {
+        tracing::error!(
+            message = %panic,
+            backtrace = %backtrace,
+            panic.file = location.file(),
+            panic.line = location.line(),
+            panic.column = location.column(),
+        );
+        eprintln!("{}:{}:{}", location.file(), location.line(), location.column());
+    } else {
+        tracing::error!(message = %panic, backtrace = %backtrace);
+    }
+
+    eprintln!("{}", backtrace);
+}
+
+/// This test shows how to run a cluster over QUIC:
+///
+/// - Setup a 3 nodes cluster, every node listens on a QUIC endpoint;
+/// - Write logs, which are replicated on the connection between the leader and each follower;
+/// - Close the connections, the leader reconnects with 0-RTT and keeps replicating;
+/// - Add a learner node-4 that receives the snapshot on a stream of its own.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_cluster() {
+    std::panic::set_hook(Box::new(|panic| {
+        log_panic(panic);
+    }));
+
+    tracing_subscriber::fmt()
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_level(true)
+        .with_ansi(false)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let tls = TlsConfig::self_signed().unwrap();
+
+    let mut nodes = BTreeMap::new();
+    for id in 1..=4 {
+        let node = start_raft_node(id, SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), &tls).await.unwrap();
+        nodes.insert(id, node);
+    }
+
+    let basic_node = |n: &RaftNode| BasicNode::new(n.addr);
+    let raft1 = &nodes[&1].raft;
+
+    println!("=== init single node cluster");
+    {
+        raft1.initialize(btree([(1, basic_node(&nodes[&1]))])).await.unwrap();
+    }
+
+    println!("=== add learner node-2, node-3 and change membership");
+    {
+        raft1.add_learner(2, basic_node(&nodes[&2]), true).await.unwrap();
+        raft1.add_learner(3, basic_node(&nodes[&3]), true).await.unwrap();
+        raft1.change_membership([1, 2, 3], false).await.unwrap();
+    }
+
+    println!("=== write logs and wait for replication");
+    let mut log_index = {
+        let resp = raft1.client_write(Request::set("foo1", "bar1")).await.unwrap();
+        resp.log_id.index
+    };
+    wait_applied(&nodes, [1, 2, 3], log_index).await;
+
+    println!("=== close connections, node-1 reconnects to the followers");
+    {
+        nodes[&1].network.close_connections();
+
+        let resp = raft1.client_write(Request::set("foo2", "bar2")).await.unwrap();
+        log_index = resp.log_id.index;
+        wait_applied(&nodes, [1, 2, 3], log_index).await;
+
+        let sm = nodes[&3].state_machine.state_machine.lock().unwrap();
+        assert_eq!(Some("bar2"), sm.data.get("foo2").map(|x| x.as_str()));
+    }
+
+    println!("=== let node-1 take a snapshot, add learner node-4 that receives it");
+    {
+        raft1.trigger().snapshot().await.unwrap();
+
+        let last_applied = raft1.metrics().borrow().last_applied.unwrap();
+        raft1.wait(timeout()).snapshot(last_applied, "node-1 built snapshot").await.unwrap();
+
+        raft1.add_learner(4, basic_node(&nodes[&4]), true).await.unwrap();
+
+        nodes[&4].raft.wait(timeout()).snapshot(last_applied, "node-4 received snapshot").await.unwrap();
+
+        let sm = nodes[&4].state_machine.state_machine.lock().unwrap();
+        assert_eq!(Some("bar1"), sm.data.get("foo1").map(|x| x.as_str()));
+    }
+}
+
+async fn wait_applied(nodes: &BTreeMap<u64, RaftNode>, ids: impl IntoIterator<Item = u64>, index: u64) {
+    for id in ids {
+        nodes[&id]
+            .raft
+            .wait(timeout())
+            .applied_index_at_least(Some(index), "logs are replicated")
+            .await
+            .unwrap();
+    }
+}
+
+fn btree<K: Ord, V>(items: impl IntoIterator<Item = (K, V)>) -> BTreeMap<K, V> {
+    items.into_iter().collect()
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,47 @@
+use std::collections::BTreeMap;
+
+use openraft::BasicNode;
+use raft_kv_memstore_redis::new_raft;
+use raft_kv_memstore_redis::router::Router;
+use raft_kv_memstore_redis::RespServer;
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+/// Start a cluster of 3 nodes in this process, serving Redis clients on 3 consecutive ports.
+///
+/// Usage: `raft-kv-redis [first-port]`, the first port is `6379` by default.
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env()).init();
+
+    let first_port: u16 = match std::env::args().nth(1) {
+        Some(port) => port.parse().expect("port must be a number"),
+        None => 6379,
+    };
+
+    let router = Router::default();
+    let mut nodes = BTreeMap::new();
+    let mut rafts = vec![];
+
+    for node_id in 1..=3 {
+        let addr = format!("127.0.0.1:{}", first_port + node_id as u16 - 1);
+        let listener = TcpListener::bind(&addr).await?;
+
+        let (raft, app, state_machine) = new_raft(node_id, &router).await;
+        tokio::spawn(app.run());
+        tokio::spawn(RespServer::new(raft.clone(), state_machine).serve(listener));
+
+        println!("node {} serves Redis clients on {}", node_id, addr);
+        nodes.insert(node_id, BasicNode { addr });
+        rafts.push(raft);
+    }
+
+    rafts[0].initialize(nodes).await.unwrap();
+
+    tokio::signal::ctrl_c().await?;
+
+    for raft in rafts {
+        raft.shutdown().await.unwrap();
+    }
+    Ok(())
+}
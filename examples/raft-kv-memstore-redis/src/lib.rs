@@ -0,0 +1,91 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+//! A replicated key-value store served with the Redis protocol, RESP.
+//!
+//! Every node serves Redis clients with a [`RespServer`], the Raft protocol requests between the
+//! nodes go through an in-process [`Router`]. The `addr` of a [`BasicNode`](openraft::BasicNode)
+//! is the address of its `RespServer`, to redirect clients to the leader.
+//!
+//! ```ignore
+//! let (raft, app, state_machine) = new_raft(1, &router).await;
+//! tokio::spawn(app.run());
+//!
+//! let listener = TcpListener::bind("127.0.0.1:6379").await?;
+//! tokio::spawn(RespServer::new(raft, state_machine).serve(listener));
+//! ```
+
+use std::sync::Arc;
+
+use openraft::Config;
+
+use crate::app::App;
+use crate::router::Router;
+use crate::store::Request;
+use crate::store::Response;
+use crate::store::StateMachineData;
+
+pub mod router;
+
+pub mod api;
+pub mod app;
+pub mod network;
+pub mod resp;
+pub mod server;
+pub mod store;
+
+pub use server::RespServer;
+
+pub type NodeId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store.
+    pub TypeConfig, pub mod typ:
+        D = Request,
+        R = Response,
+        // In this example, snapshot is just a copy of the state machine.
+        // And it can be any type.
+        SnapshotData = StateMachineData,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+
+pub fn encode<T: serde::Serialize>(t: T) -> String {
+    serde_json::to_string(&t).unwrap()
+}
+
+pub fn decode<T: serde::de::DeserializeOwned>(s: &str) -> T {
+    serde_json::from_str(s).unwrap()
+}
+
+/// Create a Raft node, and register it to `router`.
+///
+/// Run the returned [`App`] to serve the Raft protocol requests from the other nodes, and serve
+/// the returned state machine to clients with a [`RespServer`].
+pub async fn new_raft(node_id: NodeId, router: &Router) -> (typ::Raft, App, Arc<StateMachineStore>) {
+    // Create a configuration for the raft instance.
+    let config = Config {
+        heartbeat_interval: 100,
+        election_timeout_min: 300,
+        election_timeout_max: 600,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config.validate().unwrap());
+
+    // Create a instance of where the Raft logs will be stored.
+    let log_store = LogStore::default();
+
+    // Create a instance of where the state machine data will be stored.
+    let state_machine_store = Arc::new(StateMachineStore::default());
+
+    // Create a local raft instance.
+    let raft = openraft::Raft::new(node_id, config, router.clone(), log_store, state_machine_store.clone())
+        .await
+        .unwrap();
+
+    let app = App::new(node_id, raft.clone(), router);
+
+    (raft, app, state_machine_store)
+}
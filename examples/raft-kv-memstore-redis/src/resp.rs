@@ -0,0 +1,240 @@
+//! The subset of RESP2, the Redis serialization protocol, used by the commands of this example.
+//!
+//! See: <https://redis.io/docs/latest/develop/reference/protocol-spec/>
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+
+use tokio::io::AsyncBufRead;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncReadExt;
+
+/// Max size of a bulk string, the same as the default `proto-max-bulk-len` of Redis.
+const MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+
+/// Max size of a line, such as an inline command or the header of a bulk string or an array.
+const MAX_LINE_LEN: u64 = 64 * 1024;
+
+/// A RESP2 value, sent by a client as a command or by the server as a reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// `+OK\r\n`
+    Simple(String),
+
+    /// `-ERR message\r\n`, the first word is the error kind, such as `ERR` or `MOVED`.
+    Error(String),
+
+    /// `:1\r\n`
+    Integer(i64),
+
+    /// `$3\r\nfoo\r\n`, or `$-1\r\n` for the null bulk string.
+    Bulk(Option<Vec<u8>>),
+
+    /// `*2\r\n...`, the elements follow the header.
+    Array(Vec<Value>),
+}
+
+impl Value {
+    pub fn ok() -> Self {
+        Value::Simple("OK".to_string())
+    }
+
+    pub fn bulk(data: impl Into<Vec<u8>>) -> Self {
+        Value::Bulk(Some(data.into()))
+    }
+
+    /// Build an error of kind `ERR`.
+    pub fn err(msg: impl ToString) -> Self {
+        Value::Error(format!("ERR {}", msg.to_string()))
+    }
+
+    /// Build a command as a client sends it: an array of bulk strings.
+    pub fn command<'a>(args: impl IntoIterator<Item = &'a str>) -> Self {
+        Value::Array(args.into_iter().map(Value::bulk).collect())
+    }
+
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Value::Simple(s) => {
+                buf.push(b'+');
+                buf.extend_from_slice(s.as_bytes());
+            }
+            Value::Error(s) => {
+                buf.push(b'-');
+                buf.extend_from_slice(s.as_bytes());
+            }
+            Value::Integer(n) => {
+                buf.push(b':');
+                buf.extend_from_slice(n.to_string().as_bytes());
+            }
+            Value::Bulk(None) => buf.extend_from_slice(b"$-1"),
+            Value::Bulk(Some(data)) => {
+                buf.push(b'$');
+                buf.extend_from_slice(data.len().to_string().as_bytes());
+                buf.extend_from_slice(b"\r\n");
+                buf.extend_from_slice(data);
+            }
+            Value::Array(elements) => {
+                buf.push(b'*');
+                buf.extend_from_slice(elements.len().to_string().as_bytes());
+                buf.extend_from_slice(b"\r\n");
+                for e in elements {
+                    e.encode(buf);
+                }
+                return;
+            }
+        }
+        buf.extend_from_slice(b"\r\n");
+    }
+
+    /// Read a value, or `None` if the stream is closed before it.
+    ///
+    /// A line that does not start with a RESP type byte is an inline command, such as `PING\r\n`
+    /// typed in `telnet`: like Redis, it is read as an array of bulk strings split by whitespace.
+    pub fn read<R>(r: &mut R) -> Pin<Box<dyn Future<Output = io::Result<Option<Value>>> + Send + '_>>
+    where R: AsyncBufRead + Unpin + Send {
+        // Boxed because an array contains values.
+        Box::pin(async move {
+            let Some(line) = read_line(r).await? else {
+                return Ok(None);
+            };
+
+            let (kind, rest) = match line.split_first() {
+                Some((kind, rest)) => (*kind, rest),
+                None => return Ok(Some(Value::Array(vec![]))),
+            };
+
+            let value = match kind {
+                b'+' => Value::Simple(utf8(rest)?),
+                b'-' => Value::Error(utf8(rest)?),
+                b':' => Value::Integer(parse_int(rest)?),
+                b'$' => {
+                    let Some(len) = parse_len(rest, MAX_BULK_LEN)? else {
+                        return Ok(Some(Value::Bulk(None)));
+                    };
+
+                    let mut data = vec![0; len + 2];
+                    r.read_exact(&mut data).await?;
+                    if !data.ends_with(b"\r\n") {
+                        return Err(invalid_data("bulk string not terminated by CRLF"));
+                    }
+                    data.truncate(len);
+                    Value::Bulk(Some(data))
+                }
+                b'*' => {
+                    let Some(len) = parse_len(rest, MAX_BULK_LEN)? else {
+                        return Ok(Some(Value::Array(vec![])));
+                    };
+
+                    let mut elements = Vec::with_capacity(len.min(1024));
+                    for _ in 0..len {
+                        let Some(e) = Value::read(r).await? else {
+                            return Err(io::ErrorKind::UnexpectedEof.into());
+                        };
+                        elements.push(e);
+                    }
+                    Value::Array(elements)
+                }
+                _ => Value::Array(
+                    line.split(|b| b.is_ascii_whitespace()).filter(|w| !w.is_empty()).map(Value::bulk).collect(),
+                ),
+            };
+
+            Ok(Some(value))
+        })
+    }
+}
+
+/// Read a line without the trailing `\r\n` or `\n`, or `None` if the stream is closed.
+async fn read_line<R>(r: &mut R) -> io::Result<Option<Vec<u8>>>
+where R: AsyncBufRead + Unpin + Send {
+    let mut line = Vec::new();
+    let n = (&mut *r).take(MAX_LINE_LEN).read_until(b'\n', &mut line).await?;
+    if n == 0 {
+        return Ok(None);
+    }
+
+    if line.pop() != Some(b'\n') {
+        return Err(invalid_data("line too long or not terminated"));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(Some(line))
+}
+
+/// Parse the length in the header of a bulk string or an array, `-1` is the null value.
+fn parse_len(s: &[u8], max: usize) -> io::Result<Option<usize>> {
+    let n = parse_int(s)?;
+    if n == -1 {
+        return Ok(None);
+    }
+
+    match usize::try_from(n) {
+        Ok(n) if n <= max => Ok(Some(n)),
+        _ => Err(invalid_data(format!("invalid length: {}", n))),
+    }
+}
+
+fn parse_int(s: &[u8]) -> io::Result<i64> {
+    utf8(s)?.parse().map_err(|_| invalid_data("invalid integer"))
+}
+
+fn utf8(s: &[u8]) -> io::Result<String> {
+    String::from_utf8(s.to_vec()).map_err(|_| invalid_data("invalid UTF-8"))
+}
+
+fn invalid_data(msg: impl ToString) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+
+    async fn decode(mut input: &[u8]) -> Option<Value> {
+        Value::read(&mut input).await.unwrap()
+    }
+
+    fn encode(v: &Value) -> Vec<u8> {
+        let mut buf = Vec::new();
+        v.encode(&mut buf);
+        buf
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let values = [
+            Value::ok(),
+            Value::err("unknown command"),
+            Value::Integer(-3),
+            Value::Bulk(None),
+            Value::bulk("a\r\nb"),
+            Value::Array(vec![Value::command(["GET", "foo"]), Value::Integer(1)]),
+        ];
+
+        for v in values {
+            assert_eq!(Some(v.clone()), decode(&encode(&v)).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inline_command() {
+        assert_eq!(
+            Some(Value::command(["SET", "foo", "bar"])),
+            decode(b"SET  foo bar\r\n").await
+        );
+        assert_eq!(Some(Value::command(["PING"])), decode(b"PING\n").await);
+        assert_eq!(None, decode(b"").await);
+    }
+
+    #[tokio::test]
+    async fn test_invalid() {
+        let mut input: &[u8] = b"$3\r\nfoobar\r\n";
+        assert!(Value::read(&mut input).await.is_err());
+
+        let mut input: &[u8] = b"*-2\r\n";
+        assert!(Value::read(&mut input).await.is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Serve the replicated key-value store to Redis clients.
+//!
+//! | Command                  | Raft                                      | Reply                     |
+//! |--------------------------|-------------------------------------------|---------------------------|
+//! | `SET key value`          | `Raft::client_write()`, on the leader      | `+OK`                     |
+//! | `DEL key [key ...]`      | `Raft::client_write()`, on the leader      | number of keys removed    |
+//! | `GET key`                | `Raft::read()`, then read the state machine | the value, or null        |
+//! | `READONLY`, `READWRITE`  | choose the `ReadGuarantee` of `GET`        | `+OK`                     |
+//! | `PING [message]`, `QUIT` | -                                         | `+PONG`, or the message   |
+//!
+//! A command the leader has to serve is redirected with a Redis Cluster `MOVED` error, carrying
+//! the address of the leader, like a command sent to a node that does not own the key.
+
+use std::sync::Arc;
+
+use openraft::ReadGuarantee;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+use crate::resp::Value;
+use crate::store::Request;
+use crate::store::Response;
+use crate::typ::*;
+use crate::StateMachineStore;
+
+/// Serves the state machine of a Raft node with the Redis protocol.
+#[derive(Clone)]
+pub struct RespServer {
+    raft: Raft,
+    state_machine: Arc<StateMachineStore>,
+
+    /// The guarantee of a `GET` on a connection that is not in `READONLY` mode.
+    read_guarantee: ReadGuarantee,
+}
+
+/// The state of a client connection.
+#[derive(Debug, Default)]
+struct Session {
+    /// Set by `READONLY`: like on a Redis replica, `GET` reads the local state machine and may
+    /// return stale data.
+    readonly: bool,
+}
+
+/// A command parsed from the arguments sent by a client.
+#[derive(Debug)]
+enum Command {
+    Ping(Option<Vec<u8>>),
+    Get(String),
+    Set(String, String),
+    Del(Vec<String>),
+    ReadOnly,
+    ReadWrite,
+    Quit,
+}
+
+impl RespServer {
+    /// Create a server with linearizable reads: `GET` is served with `ReadGuarantee::ReadIndex`.
+    pub fn new(raft: Raft, state_machine: Arc<StateMachineStore>) -> Self {
+        Self {
+            raft,
+            state_machine,
+            read_guarantee: ReadGuarantee::ReadIndex,
+        }
+    }
+
+    /// Set the guarantee of a `GET` on a connection that is not in `READONLY` mode.
+    ///
+    /// `ReadGuarantee::LeaderLease` saves the round trip to a quorum of `ReadIndex`, if the clock
+    /// drift between the nodes is negligible.
+    pub fn with_read_guarantee(mut self, read_guarantee: ReadGuarantee) -> Self {
+        self.read_guarantee = read_guarantee;
+        self
+    }
+
+    /// Accept client connections and serve them until `listener` fails.
+    pub async fn serve(self, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = self.clone();
+
+            tokio::spawn(async move {
+                if let Err(e) = server.serve_connection(stream).await {
+                    tracing::debug!("connection from {} closed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn serve_connection(&self, stream: TcpStream) -> std::io::Result<()> {
+        let (rx, mut tx) = stream.into_split();
+        let mut rx = BufReader::new(rx);
+
+        let mut session = Session::default();
+        let mut buf = Vec::new();
+
+        // Commands are executed one by one in the order they are received, a pipelined command
+        // is read once the reply to the previous one is sent.
+        while let Some(value) = Value::read(&mut rx).await? {
+            let (reply, quit) = match Command::parse(value) {
+                Ok(Command::Quit) => (Value::ok(), true),
+                Ok(cmd) => (self.execute(&mut session, cmd).await, false),
+                Err(reply) => (reply, false),
+            };
+
+            buf.clear();
+            reply.encode(&mut buf);
+            tx.write_all(&buf).await?;
+
+            if quit {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, session: &mut Session, cmd: Command) -> Value {
+        match cmd {
+            Command::Ping(None) => Value::Simple("PONG".to_string()),
+            Command::Ping(Some(message)) => Value::bulk(message),
+            Command::Get(key) => self.get(session, &key).await,
+            Command::Set(key, value) => self.write(Request::set(key, value)).await,
+            Command::Del(keys) => self.write(Request::del(keys)).await,
+            Command::ReadOnly => {
+                session.readonly = true;
+                Value::ok()
+            }
+            Command::ReadWrite => {
+                session.readonly = false;
+                Value::ok()
+            }
+            Command::Quit => Value::ok(),
+        }
+    }
+
+    async fn get(&self, session: &Session, key: &str) -> Value {
+        let guarantee = if session.readonly {
+            ReadGuarantee::Stale
+        } else {
+            self.read_guarantee.clone()
+        };
+
+        // Once `read()` returns, the state machine has applied every write the read must observe.
+        if let Err(e) = self.raft.read(guarantee).await {
+            return match e {
+                RaftError::APIError(CheckIsLeaderError::ForwardToLeader(f)) => moved(&f),
+                RaftError::APIError(CheckIsLeaderError::QuorumNotEnough(e)) => Value::Error(format!("TRYAGAIN {}", e)),
+                RaftError::Fatal(e) => Value::err(e),
+            };
+        }
+
+        let state_machine = self.state_machine.state_machine.lock().unwrap();
+        Value::Bulk(state_machine.data.get(key).map(|v| v.clone().into_bytes()))
+    }
+
+    async fn write(&self, req: Request) -> Value {
+        match self.raft.client_write(req).await {
+            Ok(resp) => match resp.data {
+                Response::Deleted(n) => Value::Integer(n as i64),
+                Response::Ok | Response::None => Value::ok(),
+            },
+            Err(e) => match e.forward_to_leader() {
+                Some(f) => moved(f),
+                None => Value::err(e),
+            },
+        }
+    }
+}
+
+/// Redirect a client to the leader with a Redis Cluster `MOVED` error.
+///
+/// All keys belong to one Raft group, thus the hash slot in the error is always `0`: a client
+/// only needs the address to follow the redirection.
+fn moved(f: &ForwardToLeader) -> Value {
+    match &f.leader_node {
+        Some(node) => Value::Error(format!("MOVED 0 {}", node.addr)),
+        None => Value::Error("CLUSTERDOWN no leader is elected".to_string()),
+    }
+}
+
+impl Command {
+    /// Parse the arguments sent by a client, or return the error reply.
+    fn parse(value: Value) -> Result<Self, Value> {
+        let Value::Array(args) = value else {
+            return Err(Value::err("Protocol error: expected an array of bulk strings"));
+        };
+
+        let mut args = args
+            .into_iter()
+            .map(|a| match a {
+                Value::Bulk(Some(data)) => Ok(data),
+                _ => Err(Value::err("Protocol error: expected an array of bulk strings")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if args.is_empty() {
+            return Err(Value::err("empty command"));
+        }
+        let name = String::from_utf8_lossy(&args.remove(0)).to_lowercase();
+
+        let wrong_arity = || Value::err(format!("wrong number of arguments for '{}' command", name));
+
+        let cmd = match (name.as_str(), args.len()) {
+            ("ping", 0) => Command::Ping(None),
+            ("ping", 1) => Command::Ping(args.pop()),
+            ("get", 1) => Command::Get(string(args.remove(0))?),
+            ("set", 2) => {
+                let value = string(args.remove(1))?;
+                Command::Set(string(args.remove(0))?, value)
+            }
+            ("del", n) if n > 0 => Command::Del(args.into_iter().map(string).collect::<Result<_, _>>()?),
+            ("readonly", 0) => Command::ReadOnly,
+            ("readwrite", 0) => Command::ReadWrite,
+            ("quit", 0) => Command::Quit,
+            ("ping" | "get" | "set" | "del" | "readonly" | "readwrite" | "quit", _) => return Err(wrong_arity()),
+            _ => return Err(Value::err(format!("unknown command '{}'", name))),
+        };
+
+        Ok(cmd)
+    }
+}
+
+/// Keys and values are stored as `String` in this example.
+fn string(data: Vec<u8>) -> Result<String, Value> {
+    String::from_utf8(data).map_err(|_| Value::err("keys and values must be UTF-8 strings"))
+}
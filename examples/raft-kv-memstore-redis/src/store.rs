@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use openraft::storage::RaftStateMachine;
+use openraft::EntryPayload;
+use openraft::RaftSnapshotBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::typ::*;
+use crate::TypeConfig;
+
+pub type LogStore = mem_log::LogStore<TypeConfig>;
+
+/// A write command, replicated by Raft and applied to the state machine.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum Request {
+    /// Redis `SET key value`.
+    Set { key: String, value: String },
+
+    /// Redis `DEL key [key ...]`.
+    Del { keys: Vec<String> },
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Request::Set { key, value } => write!(f, "Set {{ key: {}, value: {} }}", key, value),
+            Request::Del { keys } => write!(f, "Del {{ keys: {} }}", keys.join(",")),
+        }
+    }
+}
+
+impl Request {
+    pub fn set(key: impl ToString, value: impl ToString) -> Self {
+        Self::Set {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    pub fn del(keys: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self::Del {
+            keys: keys.into_iter().map(|k| k.to_string()).collect(),
+        }
+    }
+}
+
+/// The result of applying a log entry to the state machine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// A blank or membership log entry is applied.
+    None,
+
+    /// A `Set` is applied.
+    Ok,
+
+    /// A `Del` is applied, removing this many keys.
+    Deleted(u64),
+}
+
+#[derive(Debug)]
+pub struct StoredSnapshot {
+    pub meta: SnapshotMeta,
+
+    /// The data of the state machine at the time of this snapshot.
+    pub data: SnapshotData,
+}
+
+/// Data contained in the Raft state machine.
+///
+/// Note that we are using `serde` to serialize the
+/// `data`, which has a implementation to be serialized. Note that for this test we set both the key
+/// and value as String, but you could set any type of value that has the serialization impl.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct StateMachineData {
+    pub last_applied: Option<LogId>,
+
+    pub last_membership: StoredMembership,
+
+    /// Application data.
+    pub data: BTreeMap<String, String>,
+}
+
+/// Defines a state machine for the Raft cluster. This state machine represents a copy of the
+/// data for this node. Additionally, it is responsible for storing the last snapshot of the data.
+#[derive(Debug, Default)]
+pub struct StateMachineStore {
+    /// The Raft state machine.
+    pub state_machine: Mutex<StateMachineData>,
+
+    snapshot_idx: Mutex<u64>,
+
+    /// The last received snapshot.
+    current_snapshot: Mutex<Option<StoredSnapshot>>,
+}
+
+impl RaftSnapshotBuilder<TypeConfig> for Arc<StateMachineStore> {
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn build_snapshot(&mut self) -> Result<Snapshot, StorageError> {
+        let data;
+        let last_applied_log;
+        let last_membership;
+
+        {
+            // Serialize the data of the state machine.
+            let state_machine = self.state_machine.lock().unwrap().clone();
+
+            last_applied_log = state_machine.last_applied;
+            last_membership = state_machine.last_membership.clone();
+            data = state_machine;
+        }
+
+        let snapshot_idx = {
+            let mut l = self.snapshot_idx.lock().unwrap();
+            *l += 1;
+            *l
+        };
+
+        let snapshot_id = if let Some(last) = last_applied_log {
+            format!("{}-{}-{}", last.committed_leader_id(), last.index(), snapshot_idx)
+        } else {
+            format!("--{}", snapshot_idx)
+        };
+
+        let meta = SnapshotMeta {
+            last_log_id: last_applied_log,
+            last_membership,
+            snapshot_id,
+        };
+
+        let snapshot = StoredSnapshot {
+            meta: meta.clone(),
+            data: data.clone(),
+        };
+
+        {
+            let mut current_snapshot = self.current_snapshot.lock().unwrap();
+            *current_snapshot = Some(snapshot);
+        }
+
+        Ok(Snapshot { meta, snapshot: data })
+    }
+}
+
+impl RaftStateMachine<TypeConfig> for Arc<StateMachineStore> {
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogId>, StoredMembership), StorageError> {
+        let state_machine = self.state_machine.lock().unwrap();
+        Ok((state_machine.last_applied, state_machine.last_membership.clone()))
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, entries))]
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<Response>, StorageError>
+    where I: IntoIterator<Item = Entry> {
+        let mut res = Vec::new(); //No `with_capacity`; do not know `len` of iterator
+
+        let mut sm = self.state_machine.lock().unwrap();
+
+        for entry in entries {
+            tracing::debug!(%entry.log_id, "replicate to sm");
+
+            sm.last_applied = Some(entry.log_id);
+
+            match entry.payload {
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => res.push(Response::None),
+                EntryPayload::Normal(ref req) => match req {
+                    Request::Set { key, value } => {
+                        sm.data.insert(key.clone(), value.clone());
+                        res.push(Response::Ok)
+                    }
+                    Request::Del { keys } => {
+                        let deleted = keys.iter().filter(|k| sm.data.remove(*k).is_some()).count();
+                        res.push(Response::Deleted(deleted as u64))
+                    }
+                },
+                EntryPayload::Membership(ref mem) => {
+                    sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
+                    res.push(Response::None)
+                }
+            };
+        }
+        Ok(res)
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn begin_receiving_snapshot(&mut self) -> Result<SnapshotData, StorageError> {
+        Ok(Default::default())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self, snapshot))]
+    async fn install_snapshot(&mut self, meta: &SnapshotMeta, snapshot: SnapshotData) -> Result<(), StorageError> {
+        tracing::info!("install snapshot");
+
+        let new_snapshot = StoredSnapshot {
+            meta: meta.clone(),
+            data: snapshot,
+        };
+
+        // Update the state machine.
+        {
+            let updated_state_machine: StateMachineData = new_snapshot.data.clone();
+            let mut state_machine = self.state_machine.lock().unwrap();
+            *state_machine = updated_state_machine;
+        }
+
+        // Update current snapshot.
+        let mut current_snapshot = self.current_snapshot.lock().unwrap();
+        *current_snapshot = Some(new_snapshot);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot>, StorageError> {
+        match &*self.current_snapshot.lock().unwrap() {
+            Some(snapshot) => {
+                let data = snapshot.data.clone();
+                Ok(Some(Snapshot {
+                    meta: snapshot.meta.clone(),
+                    snapshot: data,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use openraft::BasicNode;
+use raft_kv_memstore_redis::new_raft;
+use raft_kv_memstore_redis::resp::Value;
+use raft_kv_memstore_redis::router::Router;
+use raft_kv_memstore_redis::typ::Raft;
+use raft_kv_memstore_redis::NodeId;
+use raft_kv_memstore_redis::RespServer;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+/// A Redis client sending one command at a time.
+struct Client {
+    stream: BufReader<TcpStream>,
+}
+
+impl Client {
+    async fn connect(addr: &str) -> Self {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    async fn send_raw(&mut self, data: &[u8]) -> Value {
+        self.stream.get_mut().write_all(data).await.unwrap();
+        Value::read(&mut self.stream).await.unwrap().unwrap()
+    }
+
+    async fn cmd(&mut self, args: &[&str]) -> Value {
+        let mut buf = Vec::new();
+        Value::command(args.iter().copied()).encode(&mut buf);
+        self.send_raw(&buf).await
+    }
+}
+
+/// Serve a cluster of 3 nodes to Redis clients:
+///
+/// - The leader serves writes and linearizable reads;
+/// - A follower redirects them to the leader with a `MOVED` error;
+/// - After `READONLY`, a follower serves reads from its local state machine, like a Redis replica.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_cluster() {
+    let router = Router::default();
+
+    let mut rafts = BTreeMap::<NodeId, Raft>::new();
+    let mut nodes = BTreeMap::new();
+
+    for node_id in 1..=3 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        let (raft, app, state_machine) = new_raft(node_id, &router).await;
+        tokio::spawn(app.run());
+        tokio::spawn(RespServer::new(raft.clone(), state_machine).serve(listener));
+
+        rafts.insert(node_id, raft);
+        nodes.insert(node_id, BasicNode { addr });
+    }
+
+    println!("=== init cluster of 3 nodes");
+    let leader_id = {
+        rafts[&1].initialize(nodes.clone()).await.unwrap();
+
+        let metrics = rafts[&1]
+            .wait(timeout())
+            .metrics(|m| m.current_leader.is_some(), "a leader is elected")
+            .await
+            .unwrap();
+        metrics.current_leader.unwrap()
+    };
+    let follower_id = *nodes.keys().find(|id| **id != leader_id).unwrap();
+
+    let leader_addr = nodes[&leader_id].addr.clone();
+    let mut leader = Client::connect(&leader_addr).await;
+    let mut follower = Client::connect(&nodes[&follower_id].addr).await;
+
+    println!("=== ping, with a RESP command and with an inline command");
+    {
+        assert_eq!(Value::Simple("PONG".to_string()), leader.cmd(&["PING"]).await);
+        assert_eq!(Value::bulk("hi"), leader.cmd(&["ping", "hi"]).await);
+        assert_eq!(Value::Simple("PONG".to_string()), leader.send_raw(b"PING\r\n").await);
+    }
+
+    println!("=== write and read on the leader");
+    {
+        assert_eq!(Value::ok(), leader.cmd(&["SET", "foo", "bar"]).await);
+        assert_eq!(Value::ok(), leader.cmd(&["SET", "baz", "qux"]).await);
+        assert_eq!(Value::bulk("bar"), leader.cmd(&["GET", "foo"]).await);
+
+        assert_eq!(Value::Integer(1), leader.cmd(&["DEL", "foo", "no-such-key"]).await);
+        assert_eq!(Value::Bulk(None), leader.cmd(&["GET", "foo"]).await);
+    }
+
+    println!("=== a follower redirects writes and linearizable reads to the leader");
+    {
+        let moved = Value::Error(format!("MOVED 0 {}", leader_addr));
+        assert_eq!(moved, follower.cmd(&["SET", "foo", "x"]).await);
+        assert_eq!(moved, follower.cmd(&["GET", "baz"]).await);
+    }
+
+    println!("=== a follower serves reads in READONLY mode");
+    {
+        let last_log_index = rafts[&leader_id].metrics().borrow().last_log_index;
+        rafts[&follower_id]
+            .wait(timeout())
+            .applied_index(last_log_index, "the follower applied all logs")
+            .await
+            .unwrap();
+
+        assert_eq!(Value::ok(), follower.cmd(&["READONLY"]).await);
+        assert_eq!(Value::bulk("qux"), follower.cmd(&["GET", "baz"]).await);
+        assert_eq!(Value::Bulk(None), follower.cmd(&["GET", "foo"]).await);
+
+        // Writes are still served by the leader only.
+        let moved = Value::Error(format!("MOVED 0 {}", leader_addr));
+        assert_eq!(moved, follower.cmd(&["DEL", "baz"]).await);
+
+        assert_eq!(Value::ok(), follower.cmd(&["READWRITE"]).await);
+        assert_eq!(moved, follower.cmd(&["GET", "baz"]).await);
+    }
+
+    println!("=== invalid commands");
+    {
+        assert_eq!(
+            Value::err("wrong number of arguments for 'get' command"),
+            leader.cmd(&["GET"]).await
+        );
+        assert_eq!(
+            Value::err("unknown command 'hget'"),
+            leader.cmd(&["HGET", "a", "b"]).await
+        );
+        assert_eq!(Value::ok(), leader.cmd(&["QUIT"]).await);
+    }
+
+    for raft in rafts.values() {
+        raft.shutdown().await.unwrap();
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -44,7 +44,7 @@ impl std::fmt::Display for NodeId {
 
 openraft::declare_raft_types!(
     /// Declare the type configuration for example K/V store.
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = Request,
         R = Response,
         NodeId = NodeId,
@@ -53,9 +53,6 @@ openraft::declare_raft_types!(
 pub type LogStore = store::LogStore;
 pub type StateMachineStore = store::StateMachineStore;
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
-
 pub fn encode<T: serde::Serialize>(t: T) -> String {
     serde_json::to_string(&t).unwrap()
 }
@@ -0,0 +1,88 @@
+#![allow(clippy::uninlined_format_args)]
+#![deny(unused_qualifications)]
+
+//! A Raft network over Unix domain sockets, for Raft nodes running on the same host.
+//!
+//! Nodes in the same host or pod, such as the nodes of a test rig or the replicas of several
+//! shards, do not need TCP: a Unix domain socket skips the TCP/IP stack, and is addressed by a
+//! file path instead of a port that has to be allocated.
+//!
+//! A process listens on one socket with [`UdsServer`], and serves every Raft group it hosts on
+//! it: every request carries the [`GroupId`] of the group it is sent to. The `addr` of a
+//! [`BasicNode`](openraft::BasicNode) is the socket path of the process hosting the node.
+//!
+//! ```ignore
+//! let server = UdsServer::bind("/tmp/raft/node-1.sock")?;
+//!
+//! for group in [1, 2, 3] {
+//!     let (raft, _state_machine) = new_raft(1, group).await;
+//!     server.add_group(group, raft);
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use openraft::Config;
+
+use crate::network::UdsNetwork;
+use crate::store::Request;
+use crate::store::Response;
+use crate::store::StateMachineData;
+
+pub mod network;
+#[path = "../../utils/mem_kv_store.rs"]
+pub mod store;
+
+pub use network::UdsServer;
+
+pub type NodeId = u64;
+
+/// Identifies a Raft group among the groups that share a socket.
+pub type GroupId = u64;
+
+openraft::declare_raft_types!(
+    /// Declare the type configuration for example K/V store.
+    pub TypeConfig, pub mod typ:
+        D = Request,
+        R = Response,
+        // In this example, snapshot is just a copy of the state machine.
+        // And it can be any type.
+        SnapshotData = StateMachineData,
+);
+
+pub type LogStore = store::LogStore;
+pub type StateMachineStore = store::StateMachineStore;
+
+/// Create the member `node_id` of the Raft group `group`.
+///
+/// Register it to the [`UdsServer`] of this process to receive requests from other members.
+pub async fn new_raft(node_id: NodeId, group: GroupId) -> (typ::Raft, Arc<StateMachineStore>) {
+    // Create a configuration for the raft instance.
+    let config = Config {
+        heartbeat_interval: 100,
+        election_timeout_min: 300,
+        election_timeout_max: 600,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config.validate().unwrap());
+
+    // Create a instance of where the Raft logs will be stored.
+    let log_store = LogStore::default();
+
+    // Create a instance of where the state machine data will be stored.
+    let state_machine_store = Arc::new(StateMachineStore::default());
+
+    // Create a local raft instance.
+    let raft = openraft::Raft::new(
+        node_id,
+        config,
+        UdsNetwork::new(group),
+        log_store,
+        state_machine_store.clone(),
+    )
+    .await
+    .unwrap();
+
+    (raft, state_machine_store)
+}
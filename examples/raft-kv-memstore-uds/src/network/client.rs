@@ -0,0 +1,155 @@
+use std::future::Future;
+use std::path::PathBuf;
+
+use openraft::error::NetworkError;
+use openraft::error::ReplicationClosed;
+use openraft::error::Unreachable;
+use openraft::network::v2::RaftNetworkV2;
+use openraft::network::RPCOption;
+use openraft::BasicNode;
+use openraft::OptionalSend;
+use openraft::RaftNetworkFactory;
+use serde::de::DeserializeOwned;
+use tokio::io::AsyncWriteExt;
+use tokio::net::UnixStream;
+
+use crate::network::frame::read_frame;
+use crate::network::frame::write_frame;
+use crate::network::frame::Envelope;
+use crate::network::frame::RaftRequest;
+use crate::typ::*;
+use crate::GroupId;
+use crate::NodeId;
+use crate::TypeConfig;
+
+/// Creates connections from a member of the Raft group `group` to the other members.
+#[derive(Debug, Clone)]
+pub struct UdsNetwork {
+    group: GroupId,
+}
+
+impl UdsNetwork {
+    pub fn new(group: GroupId) -> Self {
+        Self { group }
+    }
+}
+
+impl RaftNetworkFactory<TypeConfig> for UdsNetwork {
+    type Network = UdsPeer;
+
+    async fn new_client(&mut self, target: NodeId, node: &BasicNode) -> Self::Network {
+        UdsPeer {
+            group: self.group,
+            target,
+            path: PathBuf::from(&node.addr),
+            stream: None,
+        }
+    }
+}
+
+/// A connection to a member of a Raft group, identified by the socket path of its process and
+/// the group.
+///
+/// Requests are sent one at a time on a single stream, which is re-established after a failure.
+/// A snapshot is sent on a new stream, see [`RaftRequest::Snapshot`].
+pub struct UdsPeer {
+    group: GroupId,
+    target: NodeId,
+    path: PathBuf,
+    stream: Option<UnixStream>,
+}
+
+impl UdsPeer {
+    async fn connect(&self) -> Result<UnixStream, Unreachable> {
+        UnixStream::connect(&self.path).await.map_err(|e| Unreachable::new(&e))
+    }
+
+    fn envelope(&self, request: RaftRequest) -> Envelope {
+        Envelope {
+            group: self.group,
+            request,
+        }
+    }
+
+    async fn call<Resp>(&mut self, request: RaftRequest) -> Result<Resp, RPCError>
+    where Resp: DeserializeOwned {
+        // Take the stream out: if this call is canceled in the middle of a request, the stream is
+        // dropped instead of being reused with a partial frame on it.
+        let mut stream = match self.stream.take() {
+            Some(stream) => stream,
+            None => self.connect().await?,
+        };
+
+        let resp = self.request(&mut stream, request).await?;
+
+        self.stream = Some(stream);
+        Ok(resp)
+    }
+
+    /// Send a request on `stream` and read the response.
+    async fn request<Resp>(&self, stream: &mut UnixStream, request: RaftRequest) -> Result<Resp, RPCError>
+    where Resp: DeserializeOwned {
+        write_frame(stream, &self.envelope(request)).await.map_err(|e| NetworkError::new(&e))?;
+        self.read_response(stream).await
+    }
+
+    async fn read_response<Resp>(&self, stream: &mut UnixStream) -> Result<Resp, RPCError>
+    where Resp: DeserializeOwned {
+        // The server closes the stream if it does not host the group.
+        let res: Result<Resp, RaftError> = read_frame(stream).await.map_err(|e| {
+            tracing::debug!("failed to read response from node-{}: {}", self.target, e);
+            Unreachable::new(&e)
+        })?;
+
+        // The remote node returns an error only when it is shutting down.
+        res.map_err(|e| Unreachable::new(&e).into())
+    }
+
+    /// Send a snapshot on a new stream.
+    ///
+    /// The snapshot does not hold back the other requests to the target, which are sent on
+    /// [`Self::stream`], and the stream is dropped if the transfer is canceled.
+    async fn send_snapshot(&self, vote: Vote, snapshot: Snapshot) -> Result<SnapshotResponse, RPCError> {
+        let data = serde_json::to_vec(&snapshot.snapshot).map_err(|e| NetworkError::new(&e))?;
+
+        let mut stream = self.connect().await?;
+
+        let req = RaftRequest::Snapshot {
+            vote,
+            meta: snapshot.meta,
+            size: data.len() as u64,
+        };
+        write_frame(&mut stream, &self.envelope(req)).await.map_err(|e| NetworkError::new(&e))?;
+        stream.write_all(&data).await.map_err(|e| NetworkError::new(&e))?;
+
+        self.read_response(&mut stream).await
+    }
+}
+
+impl RaftNetworkV2<TypeConfig> for UdsPeer {
+    async fn append_entries(
+        &mut self,
+        req: AppendEntriesRequest,
+        _option: RPCOption,
+    ) -> Result<AppendEntriesResponse, RPCError> {
+        self.call(RaftRequest::AppendEntries(req)).await
+    }
+
+    async fn vote(&mut self, req: VoteRequest, _option: RPCOption) -> Result<VoteResponse, RPCError> {
+        self.call(RaftRequest::Vote(req)).await
+    }
+
+    /// Send the full snapshot on a stream of its own.
+    async fn full_snapshot(
+        &mut self,
+        vote: Vote,
+        snapshot: Snapshot,
+        cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+        _option: RPCOption,
+    ) -> Result<SnapshotResponse, StreamingError> {
+        tokio::select! {
+            resp = self.send_snapshot(vote, snapshot) => Ok(resp?),
+            closed = cancel => Err(StreamingError::Closed(closed)),
+        }
+    }
+}
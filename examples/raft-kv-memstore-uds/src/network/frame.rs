@@ -0,0 +1,81 @@
+//! Messages on a socket are JSON encoded and framed with a 4 bytes little-endian length.
+//!
+//! The only exception is the snapshot data, see [`RaftRequest::Snapshot`].
+
+use std::io;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+
+use crate::typ::*;
+use crate::GroupId;
+
+/// The max size of a frame.
+///
+/// The snapshot data is not sent in a frame, see [`RaftRequest::Snapshot`].
+const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// A request sent to the Raft group `group`.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Envelope {
+    pub(crate) group: GroupId,
+    pub(crate) request: RaftRequest,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) enum RaftRequest {
+    AppendEntries(AppendEntriesRequest),
+    Vote(VoteRequest),
+
+    /// Install a snapshot, whose JSON encoded data of `size` bytes follows this frame unframed.
+    ///
+    /// The data is not limited by [`MAX_FRAME_SIZE`]. A snapshot is sent on a stream of its own,
+    /// so that the data does not hold back the other requests.
+    Snapshot {
+        vote: Vote,
+        meta: SnapshotMeta,
+        size: u64,
+    },
+}
+
+pub(crate) async fn write_frame<W, T>(w: &mut W, value: &T) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let buf = serde_json::to_vec(value)?;
+    if buf.len() > MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame too large: {} bytes", buf.len()),
+        ));
+    }
+
+    w.write_u32_le(buf.len() as u32).await?;
+    w.write_all(&buf).await?;
+    w.flush().await
+}
+
+pub(crate) async fn read_frame<R, T>(r: &mut R) -> io::Result<T>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let len = r.read_u32_le().await? as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame too large: {} bytes", len),
+        ));
+    }
+
+    let mut buf = vec![0; len];
+    r.read_exact(&mut buf).await?;
+
+    Ok(serde_json::from_slice(&buf)?)
+}
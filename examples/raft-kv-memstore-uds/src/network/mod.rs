@@ -0,0 +1,9 @@
+//! Every request on a socket is sent to a Raft group, see [`frame::Envelope`].
+
+mod client;
+mod frame;
+mod server;
+
+pub use client::UdsNetwork;
+pub use client::UdsPeer;
+pub use server::UdsServer;
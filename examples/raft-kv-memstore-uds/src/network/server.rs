@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::io::AsyncReadExt;
+use tokio::net::UnixListener;
+use tokio::net::UnixStream;
+use tokio::task::JoinHandle;
+
+use crate::network::frame::read_frame;
+use crate::network::frame::write_frame;
+use crate::network::frame::Envelope;
+use crate::network::frame::RaftRequest;
+use crate::typ::*;
+use crate::GroupId;
+
+type Groups = Arc<Mutex<BTreeMap<GroupId, Raft>>>;
+
+/// Listens on a Unix domain socket and serves the Raft groups hosted by this process.
+///
+/// The socket file is removed when the server is dropped.
+pub struct UdsServer {
+    path: PathBuf,
+    groups: Groups,
+    accept_handle: JoinHandle<()>,
+}
+
+impl UdsServer {
+    /// Listen on the socket at `path`.
+    ///
+    /// A socket file left at `path` by a previous process is removed.
+    pub fn bind(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let listener = UnixListener::bind(&path)?;
+        let groups = Groups::default();
+
+        let accept_handle = tokio::spawn(Self::accept(listener, groups.clone()));
+
+        Ok(Self {
+            path,
+            groups,
+            accept_handle,
+        })
+    }
+
+    /// The socket path, i.e., the `addr` of the nodes hosted by this server.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Serve the requests to the Raft group `group` with `raft`.
+    pub fn add_group(&self, group: GroupId, raft: Raft) {
+        self.groups.lock().unwrap().insert(group, raft);
+    }
+
+    /// Stop serving the Raft group `group`, and return its `Raft` if it is served.
+    pub fn remove_group(&self, group: GroupId) -> Option<Raft> {
+        self.groups.lock().unwrap().remove(&group)
+    }
+
+    async fn accept(listener: UnixListener, groups: Groups) {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _addr)) => stream,
+                Err(e) => {
+                    tracing::error!("failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+
+            tokio::spawn(Self::serve_stream(stream, groups.clone()));
+        }
+    }
+
+    /// Serve the requests on a stream one by one, until the client closes it.
+    ///
+    /// A client sends a snapshot on a stream of its own, thus installing it does not hold back the
+    /// requests on the other streams.
+    async fn serve_stream(mut stream: UnixStream, groups: Groups) {
+        loop {
+            let envelope: Envelope = match read_frame(&mut stream).await {
+                Ok(x) => x,
+                Err(e) => {
+                    if e.kind() != io::ErrorKind::UnexpectedEof {
+                        tracing::warn!("failed to read request: {}", e);
+                    }
+                    return;
+                }
+            };
+
+            let raft = groups.lock().unwrap().get(&envelope.group).cloned();
+            let Some(raft) = raft else {
+                tracing::warn!("unknown group: {}, close the connection", envelope.group);
+                return;
+            };
+
+            let res = match envelope.request {
+                RaftRequest::AppendEntries(req) => write_frame(&mut stream, &raft.append_entries(req).await).await,
+                RaftRequest::Vote(req) => write_frame(&mut stream, &raft.vote(req).await).await,
+                RaftRequest::Snapshot { vote, meta, size } => {
+                    let mut data = vec![0; size as usize];
+                    if let Err(e) = stream.read_exact(&mut data).await {
+                        tracing::warn!("failed to read snapshot {}: {}", meta.snapshot_id, e);
+                        return;
+                    }
+
+                    let data = match serde_json::from_slice(&data) {
+                        Ok(x) => x,
+                        Err(e) => {
+                            tracing::warn!("failed to decode snapshot {}: {}", meta.snapshot_id, e);
+                            return;
+                        }
+                    };
+
+                    let snapshot = Snapshot { meta, snapshot: data };
+                    let resp = raft.install_full_snapshot(vote, snapshot).await.map_err(RaftError::<Infallible>::Fatal);
+                    write_frame(&mut stream, &resp).await
+                }
+            };
+
+            if let Err(e) = res {
+                tracing::warn!("failed to write response: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for UdsServer {
+    fn drop(&mut self) {
+        self.accept_handle.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
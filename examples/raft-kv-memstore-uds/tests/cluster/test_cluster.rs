@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use openraft::BasicNode;
+use raft_kv_memstore_uds::new_raft;
+use raft_kv_memstore_uds::store::Request;
+use raft_kv_memstore_uds::typ::Raft;
+use raft_kv_memstore_uds::GroupId;
+use raft_kv_memstore_uds::NodeId;
+use raft_kv_memstore_uds::StateMachineStore;
+use raft_kv_memstore_uds::UdsServer;
+
+/// A Raft node of a group, and its state machine.
+struct GroupMember {
+    raft: Raft,
+    state_machine: Arc<StateMachineStore>,
+}
+
+/// Run two Raft groups on three processes, every process hosts one member of each group and
+/// serves both groups on a single socket.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_co_located_groups() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let node_ids: [NodeId; 3] = [1, 2, 3];
+    let groups: [GroupId; 2] = [10, 20];
+
+    let mut servers = BTreeMap::new();
+    let mut members = BTreeMap::new();
+
+    for id in node_ids {
+        let server = UdsServer::bind(dir.path().join(format!("node-{}.sock", id))).unwrap();
+
+        for group in groups {
+            let (raft, state_machine) = new_raft(id, group).await;
+            server.add_group(group, raft.clone());
+            members.insert((group, id), GroupMember { raft, state_machine });
+        }
+
+        servers.insert(id, server);
+    }
+
+    let nodes = node_ids
+        .iter()
+        .map(|id| (*id, BasicNode::new(servers[id].path().display())))
+        .collect::<BTreeMap<_, _>>();
+
+    println!("=== initialize every group with a different leader");
+    for (group, leader) in groups.into_iter().zip([1, 2]) {
+        let raft = &members[&(group, leader)].raft;
+        raft.initialize(nodes.clone()).await.unwrap();
+        raft.wait(timeout()).current_leader(leader, "leader elected").await.unwrap();
+    }
+
+    println!("=== write to every group");
+    for (group, leader) in groups.into_iter().zip([1, 2]) {
+        let raft = &members[&(group, leader)].raft;
+        let resp = raft.client_write(Request::set("group", group)).await.unwrap();
+
+        for id in node_ids {
+            let m = &members[&(group, id)];
+            m.raft.wait(timeout()).applied_index_at_least(Some(resp.log_id.index), "replicated").await.unwrap();
+
+            let sm = m.state_machine.state_machine.lock().unwrap();
+            assert_eq!(
+                Some(&group.to_string()),
+                sm.data.get("group"),
+                "group-{} node-{}",
+                group,
+                id
+            );
+        }
+    }
+
+    println!("=== a group removed from a server is unreachable, the other group is not affected");
+    {
+        let removed = servers[&3].remove_group(20).unwrap();
+
+        let raft = &members[&(10, 1)].raft;
+        let resp = raft.client_write(Request::set("foo", "bar")).await.unwrap();
+        members[&(10, 3)]
+            .raft
+            .wait(timeout())
+            .applied_index_at_least(Some(resp.log_id.index), "group 10 still replicates to node-3")
+            .await
+            .unwrap();
+
+        removed.shutdown().await.unwrap();
+    }
+}
+
+/// A snapshot larger than a frame is sent in chunks, and installed by a new learner.
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_large_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let group: GroupId = 10;
+
+    let server_1 = UdsServer::bind(dir.path().join("node-1.sock")).unwrap();
+    let server_2 = UdsServer::bind(dir.path().join("node-2.sock")).unwrap();
+
+    let (raft_1, _sm_1) = new_raft(1, group).await;
+    let (raft_2, sm_2) = new_raft(2, group).await;
+    server_1.add_group(group, raft_1.clone());
+    server_2.add_group(group, raft_2.clone());
+
+    println!("=== write 20 MiB to a single node group");
+    raft_1.initialize(BTreeMap::from([(1, BasicNode::new(server_1.path().display()))])).await.unwrap();
+    raft_1.wait(timeout()).current_leader(1, "leader elected").await.unwrap();
+
+    let value = "x".repeat(1024 * 1024);
+    let mut last_log_id = None;
+    for i in 0..20 {
+        let resp = raft_1.client_write(Request::set(format!("key-{}", i), &value)).await.unwrap();
+        last_log_id = Some(resp.log_id);
+    }
+    let last_log_id = last_log_id.unwrap();
+
+    println!("=== build a snapshot and purge the logs");
+    raft_1.trigger().snapshot().await.unwrap();
+    raft_1.wait(timeout()).snapshot(last_log_id, "snapshot built").await.unwrap();
+    raft_1.trigger().purge_log(last_log_id.index).await.unwrap();
+    raft_1.wait(timeout()).purged(Some(last_log_id), "logs purged").await.unwrap();
+
+    println!("=== a new learner receives the snapshot");
+    raft_1.add_learner(2, BasicNode::new(server_2.path().display()), true).await.unwrap();
+    raft_2.wait(timeout()).snapshot(last_log_id, "snapshot installed").await.unwrap();
+
+    let sm = sm_2.state_machine.lock().unwrap();
+    assert_eq!(20, sm.data.len());
+    assert_eq!(Some(&value), sm.data.get("key-19"));
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use actix_web::middleware;
 use actix_web::middleware::Logger;
+use actix_web::web;
 use actix_web::web::Data;
 use actix_web::HttpServer;
 use openraft::Config;
@@ -26,7 +27,7 @@ pub type NodeId = u64;
 
 openraft::declare_raft_types!(
     /// Declare the type configuration for example K/V store.
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = Request,
         R = Response,
 );
@@ -35,8 +36,11 @@ pub type LogStore = store::LogStore;
 pub type StateMachineStore = store::StateMachineStore;
 pub type Raft = openraft::Raft<TypeConfig>;
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
+/// The wire format of Raft RPC bodies, shared by the server handlers and the network client.
+pub type RaftCodec = network_v1_http::codec::Bincode;
+
+/// Max size of a Raft RPC body the server accepts.
+const MAX_RAFT_PAYLOAD: usize = 64 * 1024 * 1024;
 
 pub async fn start_example_raft_node(node_id: NodeId, http_addr: String) -> std::io::Result<()> {
     // Create a configuration for the raft instance.
@@ -56,7 +60,7 @@ pub async fn start_example_raft_node(node_id: NodeId, http_addr: String) -> std:
 
     // Create the network layer that will connect and communicate the raft instances and
     // will be used in conjunction with the store created above.
-    let network = network_v1_http::NetworkFactory {};
+    let network = network_v1_http::NetworkFactory::<RaftCodec>::with_codec();
 
     // Create a local raft instance.
     let raft = openraft::Raft::new(
@@ -85,10 +89,11 @@ pub async fn start_example_raft_node(node_id: NodeId, http_addr: String) -> std:
             .wrap(Logger::new("%a %{User-Agent}i"))
             .wrap(middleware::Compress::default())
             .app_data(app_data.clone())
+            .app_data(web::PayloadConfig::new(MAX_RAFT_PAYLOAD))
             // raft internal RPC
-            .service(raft::append)
-            .service(raft::snapshot)
-            .service(raft::vote)
+            .route("/append", web::post().to(raft::append::<RaftCodec>))
+            .route("/snapshot", web::post().to(raft::snapshot::<RaftCodec>))
+            .route("/vote", web::post().to(raft::vote::<RaftCodec>))
             // admin API
             .service(management::init)
             .service(management::add_learner)
@@ -1,33 +1,46 @@
-use actix_web::post;
+use actix_web::error::ErrorBadRequest;
+use actix_web::error::ErrorInternalServerError;
+use actix_web::web::Bytes;
 use actix_web::web::Data;
-use actix_web::web::Json;
-use actix_web::Responder;
+use actix_web::HttpResponse;
+use network_v1_http::codec::Codec;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::InstallSnapshotRequest;
 use openraft::raft::VoteRequest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::app::App;
 use crate::TypeConfig;
 
 // --- Raft communication
+//
+// The bodies are encoded with the codec `K`, which must be the same one the
+// `network_v1_http::NetworkFactory` of the other nodes uses.
 
-#[post("/vote")]
-pub async fn vote(app: Data<App>, req: Json<VoteRequest<TypeConfig>>) -> actix_web::Result<impl Responder> {
-    let res = app.raft.vote(req.0).await;
-    Ok(Json(res))
+pub async fn vote<K: Codec>(app: Data<App>, body: Bytes) -> actix_web::Result<HttpResponse> {
+    let req: VoteRequest<TypeConfig> = decode::<K, _>(&body)?;
+    let res = app.raft.vote(req).await;
+    encode::<K, _>(&res)
 }
 
-#[post("/append")]
-pub async fn append(app: Data<App>, req: Json<AppendEntriesRequest<TypeConfig>>) -> actix_web::Result<impl Responder> {
-    let res = app.raft.append_entries(req.0).await;
-    Ok(Json(res))
+pub async fn append<K: Codec>(app: Data<App>, body: Bytes) -> actix_web::Result<HttpResponse> {
+    let req: AppendEntriesRequest<TypeConfig> = decode::<K, _>(&body)?;
+    let res = app.raft.append_entries(req).await;
+    encode::<K, _>(&res)
 }
 
-#[post("/snapshot")]
-pub async fn snapshot(
-    app: Data<App>,
-    req: Json<InstallSnapshotRequest<TypeConfig>>,
-) -> actix_web::Result<impl Responder> {
-    let res = app.raft.install_snapshot(req.0).await;
-    Ok(Json(res))
+pub async fn snapshot<K: Codec>(app: Data<App>, body: Bytes) -> actix_web::Result<HttpResponse> {
+    let req: InstallSnapshotRequest<TypeConfig> = decode::<K, _>(&body)?;
+    let res = app.raft.install_snapshot(req).await;
+    encode::<K, _>(&res)
+}
+
+fn decode<K: Codec, T: DeserializeOwned>(body: &[u8]) -> actix_web::Result<T> {
+    K::decode(body).map_err(ErrorBadRequest)
+}
+
+fn encode<K: Codec, T: Serialize>(value: &T) -> actix_web::Result<HttpResponse> {
+    let body = K::encode(value).map_err(ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().content_type(K::CONTENT_TYPE).body(body))
 }
@@ -0,0 +1,90 @@
+//! Compare the codecs of Raft RPC bodies on `AppendEntries` requests.
+//!
+//! It is ignored by default, run it with:
+//!
+//! ```text
+//! cargo test --release --test benchmark -- --ignored --nocapture
+//! ```
+
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+
+use network_v1_http::codec::Bincode;
+use network_v1_http::codec::Codec;
+use network_v1_http::codec::Json;
+use openraft::entry::RaftEntry;
+use openraft::raft::AppendEntriesRequest;
+use openraft::testing::log_id;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::Vote;
+use raft_kv_memstore::store::Request;
+use raft_kv_memstore::TypeConfig;
+
+/// Number of times every request is encoded and decoded.
+const ROUNDS: u32 = 200;
+
+#[test]
+#[ignore]
+fn bench_append_entries_codec() -> anyhow::Result<()> {
+    println!(
+        "{:<8} {:>8} {:>12} {:>12} {:>12}",
+        "codec", "entries", "bytes", "encode", "decode"
+    );
+
+    for n in [1, 64, 1024] {
+        let req = append_request(n);
+        bench::<Json>("json", n, &req)?;
+        bench::<Bincode>("bincode", n, &req)?;
+    }
+
+    Ok(())
+}
+
+fn bench<K: Codec>(name: &str, n: u64, req: &AppendEntriesRequest<TypeConfig>) -> anyhow::Result<()> {
+    let mut size = 0;
+    let mut encode = Duration::ZERO;
+    let mut decode = Duration::ZERO;
+
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        let bytes = K::encode(black_box(req))?;
+        encode += start.elapsed();
+
+        let start = Instant::now();
+        let got: AppendEntriesRequest<TypeConfig> = K::decode(black_box(&bytes))?;
+        decode += start.elapsed();
+
+        assert_eq!(req.entries.len(), got.entries.len());
+        size = bytes.len();
+    }
+
+    println!(
+        "{:<8} {:>8} {:>12} {:>12?} {:>12?}",
+        name,
+        n,
+        size,
+        encode / ROUNDS,
+        decode / ROUNDS
+    );
+    Ok(())
+}
+
+/// Build an `AppendEntries` request carrying `n` `Set` entries.
+fn append_request(n: u64) -> AppendEntriesRequest<TypeConfig> {
+    let entries = (1..=n).map(|i| {
+        let req = Request::Set {
+            key: format!("key-{:08}", i),
+            value: format!("value-{:032}", i),
+        };
+        Entry::new(log_id::<TypeConfig>(3, 1, i), EntryPayload::Normal(req))
+    });
+
+    AppendEntriesRequest::new(
+        Vote::new_committed(3, 1),
+        Some(log_id(3, 1, 0)),
+        entries,
+        Some(log_id(3, 1, 0)),
+    )
+}
@@ -0,0 +1,113 @@
+#!/bin/bash
+# Run the cluster of `docker-compose.yml` with a load generator, while killing, pausing and
+# partitioning the nodes, then verify that the data is consistent.
+#
+# Usage: ./chaos.sh [duration-in-seconds]
+#
+# One node is faulted at a time, so that a quorum is always available. The output of a run is kept
+# in `./chaos-work/`.
+
+set -o errexit -o nounset -o pipefail
+
+cd "$(dirname "$0")"
+source ./lib.sh
+
+duration=${1:-60}
+work=./chaos-work
+
+# Kill a node and restart it: it recovers from its RocksDB data.
+kill_node() {
+    echo "--- kill node $1"
+    docker kill "$(container "$1")" > /dev/null
+    sleep $((RANDOM % 3 + 1))
+    docker start "$(container "$1")" > /dev/null
+}
+
+# Pause a node, like a long GC pause or a stalled disk.
+pause_node() {
+    echo "--- pause node $1"
+    docker pause "$(container "$1")" > /dev/null
+    sleep $((RANDOM % 3 + 1))
+    docker unpause "$(container "$1")" > /dev/null
+}
+
+# Disconnect a node from the other nodes and reconnect it with the same address.
+partition_node() {
+    echo "--- partition node $1"
+    docker network disconnect "$NETWORK" "$(container "$1")"
+    sleep $((RANDOM % 3 + 1))
+    docker network connect --ip "$(node_ip "$1")" --alias "n$1" "$NETWORK" "$(container "$1")"
+}
+
+# Undo the faults that are left by an interrupted run.
+heal() {
+    local n c
+    for n in $NODES; do
+        c=$(container "$n")
+        if [ "$(docker inspect -f '{{.State.Paused}}' "$c")" = "true" ]; then
+            docker unpause "$c" > /dev/null
+        fi
+        if [ "$(docker inspect -f '{{.State.Running}}' "$c")" != "true" ]; then
+            docker start "$c" > /dev/null
+        fi
+        if ! docker inspect -f '{{json .NetworkSettings.Networks}}' "$c" | jq -e "has(\"$NETWORK\")" > /dev/null; then
+            docker network connect --ip "$(node_ip "$n")" --alias "n$n" "$NETWORK" "$c"
+        fi
+    done
+}
+
+all_up() {
+    local n
+    for n in $NODES; do
+        rpc "$n" metrics > /dev/null || return 1
+    done
+}
+
+has_leader() {
+    leader > /dev/null
+}
+
+echo "--- start a new cluster"
+compose down --volumes --remove-orphans
+compose up --detach --build
+wait_for 60 all_up
+
+rpc 1 init '[[1, "n1:21001"], [2, "n2:21001"], [3, "n3:21001"]]' > /dev/null
+wait_for 30 has_leader
+echo "leader: $(leader)"
+
+rm -rf "$work"
+mkdir -p "$work"
+touch "$work/sent" "$work/acked"
+
+echo "--- start the load generator"
+./load.sh "$work" &
+load_pid=$!
+trap 'kill $load_pid 2> /dev/null || true; heal' EXIT
+
+echo "--- inject faults for $duration seconds"
+deadline=$((SECONDS + duration))
+while [ $SECONDS -lt $deadline ]; do
+    node=$((RANDOM % 3 + 1))
+    case $((RANDOM % 3)) in
+        0) kill_node "$node" ;;
+        1) pause_node "$node" ;;
+        2) partition_node "$node" ;;
+    esac
+    sleep $((RANDOM % 3 + 1))
+done
+
+kill "$load_pid"
+wait "$load_pid" 2> /dev/null || true
+heal
+
+if [ ! -s "$work/acked" ]; then
+    echo "FAILED: no write is acknowledged"
+    exit 1
+fi
+
+./verify.sh "$work"
+
+for n in $NODES; do
+    compose logs "n$n" > "$work/n$n.log" 2>&1
+done
@@ -0,0 +1,59 @@
+# Functions shared by the scripts to access the cluster in `docker-compose.yml`.
+#
+# They require `docker compose`, `curl` and `jq`.
+
+NODES="1 2 3"
+NETWORK=raft-kv-rocksdb
+
+container() { echo "raft-kv-n$1"; }
+host_port() { echo "2100$1"; }
+node_ip() { echo "172.28.0.1$1"; }
+
+compose() { docker compose -f "$(dirname "${BASH_SOURCE[0]}")/docker-compose.yml" "$@"; }
+
+# Send a request to a node and print the response body.
+# It is a GET without a body, and a POST of the JSON body otherwise.
+# It fails if the node can not be reached or responds an HTTP error.
+rpc() {
+    local node=$1 path=$2 body=${3:-}
+    local url="127.0.0.1:$(host_port "$node")/$path"
+
+    if [ -z "$body" ]; then
+        curl --silent --fail --max-time 3 "$url"
+    else
+        curl --silent --fail --max-time 3 "$url" -H "Content-Type: application/json" -d "$body"
+    fi
+}
+
+# Print a field of the metrics of a node, e.g., `metric 1 .current_leader`.
+metric() {
+    rpc "$1" metrics | jq -r ".Ok$2 // empty"
+}
+
+# Print the id of the leader, as known by the first node that knows one.
+leader() {
+    local n l
+    for n in $NODES; do
+        l=$(metric "$n" .current_leader 2>/dev/null) || continue
+        if [ -n "$l" ]; then
+            echo "$l"
+            return 0
+        fi
+    done
+    return 1
+}
+
+# Wait until `cond` with the remaining arguments succeeds, for at most `timeout` seconds.
+wait_for() {
+    local timeout=$1
+    shift
+    local deadline=$((SECONDS + timeout))
+
+    until "$@"; do
+        if [ $SECONDS -ge $deadline ]; then
+            echo "timeout waiting for: $*" >&2
+            return 1
+        fi
+        sleep 1
+    done
+}
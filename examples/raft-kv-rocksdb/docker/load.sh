@@ -0,0 +1,41 @@
+#!/bin/bash
+# Write keys `k1`, `k2`, ... to the leader, until it is killed.
+#
+# Usage: ./load.sh <work-dir>
+#
+# A `<key> <value>` line is appended to `<work-dir>/sent` before a key is written, and to
+# `<work-dir>/acked` after the cluster acknowledges the write. A write that is not acknowledged may
+# or may not be applied.
+
+set -o nounset
+
+cd "$(dirname "$0")"
+source ./lib.sh
+
+work=$1
+leader=1
+i=0
+
+while true; do
+    i=$((i + 1))
+    key="k$i"
+    value="v$i"
+    echo "$key $value" >> "$work/sent"
+
+    for _ in 1 2 3 4 5; do
+        resp=$(rpc "$leader" write "{\"Set\":{\"key\":\"$key\",\"value\":\"$value\"}}") || resp=""
+
+        if echo "$resp" | jq -e 'has("Ok")' > /dev/null 2>&1; then
+            echo "$key $value" >> "$work/acked"
+            break
+        fi
+
+        # The leader is unreachable or not a leader any more: find the new one.
+        sleep 0.2
+        if found=$(leader); then
+            leader=$found
+        else
+            leader=$((leader % 3 + 1))
+        fi
+    done
+done
@@ -0,0 +1,62 @@
+#!/bin/bash
+# Verify the data of the cluster after a chaos run.
+#
+# Usage: ./verify.sh <work-dir>
+#
+# It waits until every node applies all the logs of the leader, then checks that:
+# - every acknowledged write in `<work-dir>/acked` is on every node;
+# - every node has the same value for every key in `<work-dir>/sent`, acknowledged or not.
+
+set -o errexit -o nounset -o pipefail
+
+cd "$(dirname "$0")"
+source ./lib.sh
+
+work=$1
+
+# All nodes apply every log of the leader.
+converged() {
+    local l last n applied
+    l=$(leader) || return 1
+    last=$(metric "$l" .last_log_index) || return 1
+    [ -n "$last" ] || return 1
+
+    for n in $NODES; do
+        applied=$(metric "$n" .last_applied.index) || return 1
+        [ "$applied" = "$last" ] || return 1
+    done
+    echo "all nodes applied up to index $last, leader: $l"
+}
+
+echo "--- wait for the nodes to converge"
+wait_for 60 converged
+
+declare -A acked
+while read -r key value; do
+    acked[$key]=$value
+done < "$work/acked"
+
+echo "--- check ${#acked[@]} acknowledged writes of $(wc -l < "$work/sent") sent"
+
+errors=0
+while read -r key _; do
+    values=()
+    for n in $NODES; do
+        values+=("$(rpc "$n" read "\"$key\"" | jq -r '.Ok')")
+    done
+
+    if [ "${values[0]}" != "${values[1]}" ] || [ "${values[0]}" != "${values[2]}" ]; then
+        echo "inconsistent: $key: ${values[*]}"
+        errors=$((errors + 1))
+    elif [ -n "${acked[$key]:-}" ] && [ "${values[0]}" != "${acked[$key]}" ]; then
+        echo "lost acknowledged write: $key: expect: ${acked[$key]}, got: ${values[0]}"
+        errors=$((errors + 1))
+    fi
+done < "$work/sent"
+
+if [ $errors -gt 0 ]; then
+    echo "FAILED: $errors errors"
+    exit 1
+fi
+
+echo "OK: the nodes are consistent, and no acknowledged write is lost"
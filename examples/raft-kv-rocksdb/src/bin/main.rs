@@ -1,15 +1,29 @@
 use clap::Parser;
+use raft_kv_rocksdb::k8s;
 use raft_kv_rocksdb::start_example_raft_node;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser, Clone, Debug)]
 #[clap(author, version, about, long_about = None)]
 pub struct Opt {
+    /// The node id. If not set, it is the ordinal of the StatefulSet pod in `$HOSTNAME`.
     #[clap(long)]
-    pub id: u64,
+    pub id: Option<u64>,
 
+    /// The address to listen on, which is also the address of this node unless `--advertise-addr`
+    /// is set.
     #[clap(long)]
     pub addr: String,
+
+    /// The address other nodes connect to this node at, e.g., the DNS name of a StatefulSet pod.
+    #[clap(long)]
+    pub advertise_addr: Option<String>,
+
+    /// The stable addresses of all nodes, e.g., `raft-kv-0.raft-kv:21001,raft-kv-1.raft-kv:21001`.
+    ///
+    /// If set, this node joins the cluster of these nodes by itself: see `raft_kv_rocksdb::k8s`.
+    #[clap(long, value_delimiter = ',')]
+    pub peers: Vec<String>,
 }
 
 #[actix_web::main]
@@ -26,5 +40,21 @@ async fn main() -> std::io::Result<()> {
     // Parse the parameters passed by arguments.
     let options = Opt::parse();
 
-    start_example_raft_node(options.id, format!("{}.db", options.addr), options.addr).await
+    let id = match options.id {
+        Some(id) => id,
+        None => {
+            let hostname = std::env::var("HOSTNAME").unwrap_or_default();
+            k8s::node_id_from_hostname(&hostname)
+                .unwrap_or_else(|| panic!("--id is not set, and no pod ordinal in HOSTNAME: {:?}", hostname))
+        }
+    };
+    let dir = format!("{}.db", options.addr);
+
+    if options.peers.is_empty() {
+        return start_example_raft_node(id, dir, options.addr).await;
+    }
+
+    let peers = k8s::peers_by_id(&options.peers).unwrap();
+    let advertise_addr = options.advertise_addr.unwrap_or_else(|| options.addr.clone());
+    k8s::start_k8s_raft_node(id, dir, options.addr, advertise_addr, peers).await
 }
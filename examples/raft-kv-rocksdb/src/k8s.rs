@@ -0,0 +1,242 @@
+//! Run the example as a Kubernetes StatefulSet, see `k8s/` for the manifests.
+//!
+//! - Pod `{statefulset}-{ordinal}` is node `ordinal`: see [`node_id_from_hostname()`].
+//! - The pods find each other by the stable DNS names of a headless service. The first pod
+//!   initializes the cluster, and the others join it as voters: see [`join_cluster()`].
+//! - When a pod restarts with another address, it asks the leader to update its address in the
+//!   membership with `ChangeMembers::SetNodes`.
+//! - `/healthz` and `/readyz` report the state of the Raft node to the liveness and readiness
+//!   probes: see [`check_ready()`].
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+use actix_web::web::Data;
+use openraft::Config;
+use serde::Serialize;
+
+use crate::app::App;
+use crate::typ::Node;
+use crate::typ::RaftMetrics;
+use crate::NodeId;
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Derive the node id from the name of a StatefulSet pod, i.e., the ordinal in
+/// `{statefulset}-{ordinal}`.
+///
+/// The ordinal of a pod never changes, even if the pod is rescheduled to another host.
+pub fn node_id_from_hostname(hostname: &str) -> Option<NodeId> {
+    let (_, ordinal) = hostname.rsplit_once('-')?;
+    ordinal.parse().ok()
+}
+
+/// Map the stable addresses of the pods, such as `raft-kv-1.raft-kv.default.svc:21001`, to node
+/// ids.
+pub fn peers_by_id(peers: &[String]) -> Result<BTreeMap<NodeId, String>, String> {
+    peers
+        .iter()
+        .map(|addr| {
+            let hostname = addr.split(['.', ':']).next().unwrap_or_default();
+            let id = node_id_from_hostname(hostname).ok_or_else(|| format!("no pod ordinal in peer: {}", addr))?;
+            Ok((id, addr.clone()))
+        })
+        .collect()
+}
+
+/// Start a node of a StatefulSet: serve on `listen_addr`, and join the cluster of `peers` with the
+/// address `advertise_addr`.
+pub async fn start_k8s_raft_node<P>(
+    node_id: NodeId,
+    dir: P,
+    listen_addr: String,
+    advertise_addr: String,
+    peers: BTreeMap<NodeId, String>,
+) -> std::io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let app = crate::new_app(node_id, dir, advertise_addr).await;
+
+    actix_web::rt::spawn(join_cluster(app.clone(), peers));
+
+    crate::serve(app, listen_addr).await
+}
+
+/// Make this node a voter of the cluster with its current address, retrying until it succeeds.
+///
+/// The node with the smallest id initializes the cluster, unless another node already has the data
+/// of a cluster. Every other node asks the leader to add it, or to update its address if it is
+/// already a member with another address.
+pub async fn join_cluster(app: Data<App>, peers: BTreeMap<NodeId, String>) {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(3)).build().unwrap();
+
+    loop {
+        match try_join(&app, &client, &peers).await {
+            Ok(()) => {
+                tracing::info!(id = app.id, addr = app.addr, "joined the cluster");
+                return;
+            }
+            Err(e) => {
+                tracing::info!(id = app.id, "not joined yet: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+async fn try_join(app: &App, client: &reqwest::Client, peers: &BTreeMap<NodeId, String>) -> Result<(), BoxError> {
+    let first = peers.keys().next().copied().unwrap_or(app.id).min(app.id);
+
+    // Do not initialize another cluster if the first node restarts without its data.
+    if app.id == first && !app.raft.is_initialized().await? && !cluster_exists(app, client, peers).await {
+        let nodes = BTreeMap::from([(app.id, Node { addr: app.addr.clone() })]);
+        app.raft.initialize(nodes).await?;
+        return Ok(());
+    }
+
+    let (leader, metrics) = find_leader(client, peers).await?;
+    let membership = metrics.membership_config.membership();
+
+    match membership.get_node(&app.id) {
+        None => {
+            tracing::info!(id = app.id, addr = app.addr, "add this node as a learner");
+            post(client, &leader, "add-learner", &(app.id, &app.addr)).await?;
+        }
+        Some(node) if node.addr != app.addr => {
+            tracing::info!(
+                id = app.id,
+                from = node.addr,
+                to = app.addr,
+                "update the address of this node"
+            );
+            post(client, &leader, "set-node", &(app.id, &app.addr)).await?;
+        }
+        Some(_) => {}
+    }
+
+    let mut voters = membership.voter_ids().collect::<BTreeSet<_>>();
+    if voters.insert(app.id) {
+        tracing::info!(id = app.id, "promote this node to a voter");
+        post(client, &leader, "change-membership", &voters).await?;
+    }
+
+    Ok(())
+}
+
+/// Whether any other peer has data of a cluster.
+async fn cluster_exists(app: &App, client: &reqwest::Client, peers: &BTreeMap<NodeId, String>) -> bool {
+    for (id, addr) in peers {
+        if *id == app.id {
+            continue;
+        }
+        if let Ok(metrics) = get_metrics(client, addr).await {
+            if metrics.last_log_index.is_some() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Find the leader through the peers, return its address and metrics.
+async fn find_leader(
+    client: &reqwest::Client,
+    peers: &BTreeMap<NodeId, String>,
+) -> Result<(String, RaftMetrics), BoxError> {
+    for addr in peers.values() {
+        let Ok(metrics) = get_metrics(client, addr).await else {
+            continue;
+        };
+        let Some(leader_addr) = metrics.current_leader.and_then(|id| peers.get(&id)) else {
+            continue;
+        };
+
+        let leader_metrics = get_metrics(client, leader_addr).await?;
+        if leader_metrics.current_leader == Some(leader_metrics.id) {
+            return Ok((leader_addr.clone(), leader_metrics));
+        }
+    }
+
+    Err("no leader found".into())
+}
+
+async fn get_metrics(client: &reqwest::Client, addr: &str) -> Result<RaftMetrics, BoxError> {
+    let res: Result<RaftMetrics, String> = client.get(format!("http://{}/metrics", addr)).send().await?.json().await?;
+    Ok(res?)
+}
+
+/// Send a management request, and fail if the response is not `Ok`.
+async fn post<T: Serialize>(client: &reqwest::Client, addr: &str, path: &str, body: &T) -> Result<(), BoxError> {
+    let res: serde_json::Value =
+        client.post(format!("http://{}/{}", addr, path)).json(body).send().await?.json().await?;
+
+    match res.get("Ok") {
+        Some(_) => Ok(()),
+        None => Err(format!("{} failed: {}", path, res).into()),
+    }
+}
+
+/// Check if a node is ready to serve requests, and return the reason if not:
+/// - the Raft node is running;
+/// - it is a member of the cluster, and knows a leader;
+/// - if it is the leader, a quorum acknowledged it within the max election timeout.
+pub fn check_ready(metrics: &RaftMetrics, config: &Config) -> Result<(), String> {
+    if let Err(fatal) = &metrics.running_state {
+        return Err(format!("raft stopped: {}", fatal));
+    }
+
+    if metrics.membership_config.membership().get_node(&metrics.id).is_none() {
+        return Err("not a member".to_string());
+    }
+
+    let Some(leader) = metrics.current_leader else {
+        return Err("no leader".to_string());
+    };
+
+    if leader == metrics.id {
+        let since_quorum_ack = metrics.last_quorum_acked.as_ref().map(|acked| acked.elapsed());
+        match since_quorum_ack {
+            Some(d) if d <= Duration::from_millis(config.election_timeout_max) => {}
+            d => {
+                return Err(format!(
+                    "leader not acknowledged by a quorum, since last quorum ack: {:?}",
+                    d
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_id_from_hostname() {
+        assert_eq!(Some(0), node_id_from_hostname("raft-kv-0"));
+        assert_eq!(Some(12), node_id_from_hostname("my-raft-kv-12"));
+        assert_eq!(None, node_id_from_hostname("raft"));
+        assert_eq!(None, node_id_from_hostname("raft-kv-x"));
+    }
+
+    #[test]
+    fn test_peers_by_id() {
+        let peers = vec![
+            "raft-kv-1.raft-kv.default.svc:21001".to_string(),
+            "raft-kv-0.raft-kv:21001".to_string(),
+        ];
+        let want = BTreeMap::from([
+            (0, "raft-kv-0.raft-kv:21001".to_string()),
+            (1, "raft-kv-1.raft-kv.default.svc:21001".to_string()),
+        ]);
+        assert_eq!(Ok(want), peers_by_id(&peers));
+
+        assert!(peers_by_id(&["raft-kv:21001".to_string()]).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use actix_web::middleware;
 use actix_web::middleware::Logger;
+use actix_web::web;
 use actix_web::web::Data;
 use actix_web::HttpServer;
 use openraft::Config;
@@ -19,13 +20,14 @@ use crate::store::Request;
 use crate::store::Response;
 
 pub mod app;
+pub mod k8s;
 pub mod network;
 pub mod store;
 
 pub type NodeId = u64;
 
 openraft::declare_raft_types!(
-    pub TypeConfig:
+    pub TypeConfig, pub mod typ:
         D = Request,
         R = Response,
 );
@@ -34,10 +36,20 @@ pub type LogStore = openraft_rocksstore::log_store::RocksLogStore<TypeConfig>;
 pub type StateMachineStore = store::StateMachineStore;
 pub type Raft = openraft::Raft<TypeConfig>;
 
-#[path = "../../utils/declare_types.rs"]
-pub mod typ;
+/// The wire format of Raft RPC bodies, shared by the server handlers and the network client.
+pub type RaftCodec = network_v1_http::codec::Bincode;
+
+/// Max size of a Raft RPC body the server accepts.
+const MAX_RAFT_PAYLOAD: usize = 64 * 1024 * 1024;
 
 pub async fn start_example_raft_node<P>(node_id: NodeId, dir: P, addr: String) -> std::io::Result<()>
+where P: AsRef<Path> {
+    let app_data = new_app(node_id, dir, addr.clone()).await;
+    serve(app_data, addr).await
+}
+
+/// Create a raft node storing its data in `dir`, which other nodes connect to at `addr`.
+pub async fn new_app<P>(node_id: NodeId, dir: P, addr: String) -> Data<App>
 where P: AsRef<Path> {
     // Create a configuration for the raft instance.
     let config = Config {
@@ -55,21 +67,24 @@ where P: AsRef<Path> {
     let kvs = state_machine_store.data.kvs.clone();
 
     // Create the network layer using network-v1 crate
-    let network = network_v1_http::NetworkFactory {};
+    let network = network_v1_http::NetworkFactory::<RaftCodec>::with_codec();
 
     // Create a local raft instance.
     let raft = openraft::Raft::new(node_id, config.clone(), network, log_store, state_machine_store).await.unwrap();
 
     // Create an application that will store all the instances created above, this will
     // later be used on the actix-web services.
-    let app_data = Data::new(App {
+    Data::new(App {
         id: node_id,
-        addr: addr.clone(),
+        addr,
         raft,
         key_values: kvs,
         config,
-    });
+    })
+}
 
+/// Serve the APIs of a raft node on `listen_addr`.
+pub async fn serve(app_data: Data<App>, listen_addr: String) -> std::io::Result<()> {
     // Start the actix-web server.
     let server = HttpServer::new(move || {
         actix_web::App::new()
@@ -77,22 +92,27 @@ where P: AsRef<Path> {
             .wrap(Logger::new("%a %{User-Agent}i"))
             .wrap(middleware::Compress::default())
             .app_data(app_data.clone())
+            .app_data(web::PayloadConfig::new(MAX_RAFT_PAYLOAD))
             // raft internal RPC
-            .service(raft::append)
-            .service(raft::snapshot)
-            .service(raft::vote)
+            .route("/append", web::post().to(raft::append::<RaftCodec>))
+            .route("/snapshot", web::post().to(raft::snapshot::<RaftCodec>))
+            .route("/vote", web::post().to(raft::vote::<RaftCodec>))
             // admin API
             .service(management::init)
             .service(management::add_learner)
             .service(management::change_membership)
+            .service(management::set_node)
             .service(management::metrics)
+            // health probes
+            .service(management::healthz)
+            .service(management::readyz)
             // application API
             .service(api::write)
             .service(api::read)
             .service(api::linearizable_read)
     });
 
-    let x = server.bind(addr)?;
+    let x = server.bind(listen_addr)?;
 
     x.run().await
 }
@@ -5,12 +5,15 @@ use actix_web::get;
 use actix_web::post;
 use actix_web::web::Data;
 use actix_web::web::Json;
+use actix_web::HttpResponse;
 use actix_web::Responder;
 use openraft::error::decompose::DecomposeResult;
 use openraft::error::Infallible;
 use openraft::BasicNode;
+use openraft::ChangeMembers;
 
 use crate::app::App;
+use crate::k8s;
 use crate::typ::*;
 use crate::NodeId;
 
@@ -37,6 +40,18 @@ pub async fn change_membership(app: Data<App>, req: Json<BTreeSet<NodeId>>) -> a
     Ok(Json(res))
 }
 
+/// Update the address of a node, e.g., after the node is rescheduled to another address.
+///
+/// The new address must belong to the same node with the same data, otherwise it may cause a split
+/// brain: see `ChangeMembers::SetNodes`.
+#[post("/set-node")]
+pub async fn set_node(app: Data<App>, req: Json<(NodeId, String)>) -> actix_web::Result<impl Responder> {
+    let (node_id, addr) = req.0;
+    let change = ChangeMembers::SetNodes(BTreeMap::from([(node_id, Node { addr })]));
+    let res = app.raft.change_membership(change, true).await.decompose().unwrap();
+    Ok(Json(res))
+}
+
 /// Initialize a cluster.
 #[post("/init")]
 pub async fn init(app: Data<App>, req: Json<Vec<(NodeId, String)>>) -> actix_web::Result<impl Responder> {
@@ -60,3 +75,24 @@ pub async fn metrics(app: Data<App>) -> actix_web::Result<impl Responder> {
     let res: Result<RaftMetrics, Infallible> = Ok(metrics);
     Ok(Json(res))
 }
+
+// --- Health probes
+
+/// Liveness: the Raft node is running.
+#[get("/healthz")]
+pub async fn healthz(app: Data<App>) -> HttpResponse {
+    match &app.raft.metrics().borrow().running_state {
+        Ok(()) => HttpResponse::Ok().body("ok"),
+        Err(fatal) => HttpResponse::ServiceUnavailable().body(fatal.to_string()),
+    }
+}
+
+/// Readiness: the Raft node is a member of a cluster with a working leader.
+#[get("/readyz")]
+pub async fn readyz(app: Data<App>) -> HttpResponse {
+    let raft_metrics = app.raft.metrics().borrow().clone();
+    match k8s::check_ready(&raft_metrics, &app.config) {
+        Ok(()) => HttpResponse::Ok().body("ok"),
+        Err(reason) => HttpResponse::ServiceUnavailable().body(reason),
+    }
+}
@@ -220,7 +220,7 @@ impl RaftStateMachine<TypeConfig> for StateMachineStore {
             let mut resp_value = None;
 
             match ent.payload {
-                EntryPayload::Blank => {}
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => {}
                 EntryPayload::Normal(req) => match req {
                     Request::Set { key, value } => {
                         resp_value = Some(value.clone());
@@ -0,0 +1,19 @@
+//! Dump the raft data of a stopped node in human-readable form.
+//!
+//! Usage: `raft-dump <db-path>`
+
+use std::io;
+use std::process::exit;
+
+use openraft_rocksstore::read_only::RocksStore;
+use openraft_rocksstore::TypeConfig;
+
+fn main() -> Result<(), io::Error> {
+    let Some(db_path) = std::env::args().nth(1) else {
+        eprintln!("Usage: raft-dump <db-path>");
+        exit(2);
+    };
+
+    let store = RocksStore::<TypeConfig>::open_read_only(&db_path)?;
+    store.dump(&mut io::stdout().lock())
+}
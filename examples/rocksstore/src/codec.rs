@@ -0,0 +1,23 @@
+//! Encode the values this store persists with [`openraft::storage::codec`].
+//!
+//! Log ids and votes are encoded with [`FixedWidthCodec`]. A snapshot file is encoded with
+//! [`SnapshotFile::encode()`](crate::state_machine::SnapshotFile::encode).
+//!
+//! Values written as JSON by an earlier version of this store are still read, they are replaced
+//! with the new encoding the next time they are written.
+
+use openraft::storage::codec::CodecError;
+use openraft::storage::codec::FixedWidthCodec;
+use serde::de::DeserializeOwned;
+
+/// Decode a value encoded with [`FixedWidthCodec`], or with JSON by an earlier version.
+pub(crate) fn decode_fixed<T>(bytes: &[u8]) -> Result<T, CodecError>
+where T: FixedWidthCodec + DeserializeOwned {
+    // A JSON value is never as short as a fixed-width encoding, thus it fails with a length error.
+    T::decode(bytes).or_else(|e| serde_json::from_slice(bytes).map_err(|_| e))
+}
+
+/// Build a [`CodecError`] from any error, such as a JSON error.
+pub(crate) fn invalid(e: impl ToString) -> CodecError {
+    CodecError::Invalid { reason: e.to_string() }
+}
@@ -0,0 +1,167 @@
+//! Encryption-at-rest hooks.
+//!
+//! An [`Encryptor`] is applied to log entries before they are written to RocksDB and to snapshot
+//! data before it is written to the snapshot directory. Keys used for lookup, the vote and other
+//! small metadata are stored in plain text.
+//!
+//! Every encrypted value is stored with the [`KeyId`] of the key it is encrypted with, so that keys
+//! can be rotated with a [`KeyRing`] without downtime.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+use byteorder::BigEndian;
+use byteorder::ReadBytesExt;
+use byteorder::WriteBytesExt;
+
+/// Identifies an encryption key.
+pub type KeyId = u32;
+
+/// User-supplied encryption of values written to disk.
+///
+/// `decrypt(k, encrypt(k, x))` must return `x`. The output of `encrypt()` may be of any length, so
+/// implementations are free to prepend a nonce or append an authentication tag.
+pub trait Encryptor: Debug + Send + Sync + 'static {
+    /// Returns the id of the key new values are encrypted with.
+    fn key_id(&self) -> KeyId {
+        0
+    }
+
+    /// Encrypt `plain` with the key `key_id`.
+    fn encrypt(&self, key_id: KeyId, plain: &[u8]) -> Result<Vec<u8>, io::Error>;
+
+    /// Decrypt `cipher` that was encrypted with the key `key_id`.
+    fn decrypt(&self, key_id: KeyId, cipher: &[u8]) -> Result<Vec<u8>, io::Error>;
+}
+
+/// The default [`Encryptor`] that stores data as is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoEncryption;
+
+impl Encryptor for NoEncryption {
+    fn encrypt(&self, _key_id: KeyId, plain: &[u8]) -> Result<Vec<u8>, io::Error> {
+        Ok(plain.to_vec())
+    }
+
+    fn decrypt(&self, _key_id: KeyId, cipher: &[u8]) -> Result<Vec<u8>, io::Error> {
+        Ok(cipher.to_vec())
+    }
+}
+
+pub(crate) fn no_encryption() -> Arc<dyn Encryptor> {
+    Arc::new(NoEncryption)
+}
+
+/// An [`Encryptor`] that holds several keys and encrypts with the current one.
+///
+/// To rotate keys while the store is running:
+/// - call [`rotate`](Self::rotate) so that new values are encrypted with the new key;
+/// - rewrite existing data with [`RocksLogStore::reencrypt`] and
+///   [`RocksStateMachine::reencrypt_snapshots`];
+/// - then drop the old key with [`remove_key`](Self::remove_key).
+///
+/// When reopening a store, add every key that may still be in use with
+/// [`add_key`](Self::add_key).
+///
+/// [`RocksLogStore::reencrypt`]: crate::log_store::RocksLogStore::reencrypt
+/// [`RocksStateMachine::reencrypt_snapshots`]: crate::state_machine::RocksStateMachine::reencrypt_snapshots
+#[derive(Debug)]
+pub struct KeyRing {
+    inner: RwLock<KeyRingInner>,
+}
+
+#[derive(Debug)]
+struct KeyRingInner {
+    current: KeyId,
+    keys: BTreeMap<KeyId, Arc<dyn Encryptor>>,
+}
+
+impl KeyRing {
+    /// Create a key ring whose current key is `key`, identified by `key_id`.
+    ///
+    /// Each key is used as a single-key [`Encryptor`]: it is called with its own `key_id`.
+    pub fn new(key_id: KeyId, key: Arc<dyn Encryptor>) -> Self {
+        Self {
+            inner: RwLock::new(KeyRingInner {
+                current: key_id,
+                keys: BTreeMap::from([(key_id, key)]),
+            }),
+        }
+    }
+
+    /// Add a key that is only used to decrypt existing data.
+    pub fn add_key(&self, key_id: KeyId, key: Arc<dyn Encryptor>) {
+        let mut inner = self.inner.write().unwrap();
+        inner.keys.insert(key_id, key);
+    }
+
+    /// Add a key and encrypt all new values with it.
+    pub fn rotate(&self, key_id: KeyId, key: Arc<dyn Encryptor>) {
+        let mut inner = self.inner.write().unwrap();
+        inner.keys.insert(key_id, key);
+        inner.current = key_id;
+    }
+
+    /// Remove a key that is no longer used by any stored data.
+    ///
+    /// The current key can not be removed.
+    pub fn remove_key(&self, key_id: KeyId) -> Result<(), io::Error> {
+        let mut inner = self.inner.write().unwrap();
+        if inner.current == key_id {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("can not remove the current key {}", key_id),
+            ));
+        }
+        inner.keys.remove(&key_id);
+        Ok(())
+    }
+
+    fn get(&self, key_id: KeyId) -> Result<Arc<dyn Encryptor>, io::Error> {
+        let inner = self.inner.read().unwrap();
+        inner
+            .keys
+            .get(&key_id)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("encryption key {} not found", key_id)))
+    }
+}
+
+impl Encryptor for KeyRing {
+    fn key_id(&self) -> KeyId {
+        self.inner.read().unwrap().current
+    }
+
+    fn encrypt(&self, key_id: KeyId, plain: &[u8]) -> Result<Vec<u8>, io::Error> {
+        self.get(key_id)?.encrypt(key_id, plain)
+    }
+
+    fn decrypt(&self, key_id: KeyId, cipher: &[u8]) -> Result<Vec<u8>, io::Error> {
+        self.get(key_id)?.decrypt(key_id, cipher)
+    }
+}
+
+/// Encrypt `plain` with the current key and prepend the key id: `key_id(u32, big-endian) + cipher`.
+pub(crate) fn seal(encryptor: &dyn Encryptor, plain: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let key_id = encryptor.key_id();
+    let cipher = encryptor.encrypt(key_id, plain)?;
+
+    let mut buf = Vec::with_capacity(4 + cipher.len());
+    buf.write_u32::<BigEndian>(key_id)?;
+    buf.extend_from_slice(&cipher);
+    Ok(buf)
+}
+
+/// Decrypt a value built by [`seal`] with the key it was encrypted with.
+pub(crate) fn unseal(encryptor: &dyn Encryptor, sealed: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let key_id = sealed_key_id(sealed)?;
+    encryptor.decrypt(key_id, &sealed[4..])
+}
+
+/// Returns the id of the key a value built by [`seal`] is encrypted with.
+pub(crate) fn sealed_key_id(mut sealed: &[u8]) -> Result<KeyId, io::Error> {
+    sealed.read_u32::<BigEndian>()
+}
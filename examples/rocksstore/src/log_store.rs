@@ -2,7 +2,10 @@ use std::error::Error;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::RangeBounds;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
 
 use byteorder::BigEndian;
 use byteorder::ReadBytesExt;
@@ -12,6 +15,7 @@ use openraft::alias::EntryOf;
 use openraft::alias::LogIdOf;
 use openraft::alias::VoteOf;
 use openraft::entry::RaftEntry;
+use openraft::storage::codec::FixedWidthCodec;
 use openraft::storage::IOFlushed;
 use openraft::storage::RaftLogStorage;
 use openraft::type_config::TypeConfigExt;
@@ -22,89 +26,258 @@ use openraft::RaftTypeConfig;
 use openraft::StorageError;
 use openraft::TokioRuntime;
 use rocksdb::ColumnFamily;
+use rocksdb::ColumnFamilyDescriptor;
 use rocksdb::Direction;
+use rocksdb::Options;
 use rocksdb::DB;
 use tokio::task::spawn_blocking;
+use tokio::task::JoinHandle;
 
+use crate::codec::decode_fixed;
+use crate::encryption::no_encryption;
+use crate::encryption::seal;
+use crate::encryption::sealed_key_id;
+use crate::encryption::unseal;
+use crate::encryption::Encryptor;
+use crate::metrics::collect_db_metrics;
+use crate::metrics::RocksStoreMetrics;
+
+/// Column family storing the vote and the last purged log id.
+pub const CF_META: &str = "meta";
+
+/// Column family storing log entries, keyed by big-endian log index.
+pub const CF_LOGS: &str = "logs";
+
+/// Raft log storage backed by RocksDB.
+///
+/// It does not depend on [`RocksStateMachine`](crate::state_machine::RocksStateMachine) and can be
+/// used with any other [`RaftStateMachine`](openraft::storage::RaftStateMachine) implementation.
 #[derive(Debug, Clone)]
 pub struct RocksLogStore<C>
 where C: RaftTypeConfig
 {
     db: Arc<DB>,
+    encryptor: Arc<dyn Encryptor>,
+
+    /// Serializes writes to `logs` with [`reencrypt`](Self::reencrypt), so that it never writes
+    /// back an entry that has been truncated or purged in the meantime.
+    write_lock: Arc<Mutex<()>>,
+
+    /// Prefix of every key this store reads or writes.
+    ///
+    /// It is empty for a store that owns the column families,
+    /// and is the group id for a group in a
+    /// [`RocksMultiStore`](crate::multi_raft::RocksMultiStore).
+    prefix: Vec<u8>,
     _p: PhantomData<C>,
 }
 
 impl<C> RocksLogStore<C>
 where C: RaftTypeConfig
 {
+    /// Returns the column families a log store requires.
+    ///
+    /// Add them to the descriptors when opening a DB shared with other components.
+    pub fn column_families() -> Vec<ColumnFamilyDescriptor> {
+        vec![
+            ColumnFamilyDescriptor::new(CF_META, Options::default()),
+            ColumnFamilyDescriptor::new(CF_LOGS, Options::default()),
+        ]
+    }
+
+    /// Open a standalone log store in its own DB at `db_path`.
+    pub fn open<P: AsRef<Path>>(db_path: P) -> Result<Self, std::io::Error> {
+        let mut db_opts = Options::default();
+        db_opts.create_missing_column_families(true);
+        db_opts.create_if_missing(true);
+
+        let db = DB::open_cf_descriptors(&db_opts, db_path, Self::column_families()).map_err(std::io::Error::other)?;
+
+        Ok(Self::new(Arc::new(db)))
+    }
+
+    /// Create a log store on an already opened DB.
+    ///
+    /// The DB must contain the column families returned by [`Self::column_families`].
     pub fn new(db: Arc<DB>) -> Self {
-        db.cf_handle("meta").expect("column family `meta` not found");
-        db.cf_handle("logs").expect("column family `logs` not found");
+        db.cf_handle(CF_META).expect("column family `meta` not found");
+        db.cf_handle(CF_LOGS).expect("column family `logs` not found");
 
         Self {
             db,
+            encryptor: no_encryption(),
+            write_lock: Default::default(),
+            prefix: vec![],
             _p: Default::default(),
         }
     }
 
+    /// Store all keys under `prefix`, so that several stores can share the same column families.
+    pub(crate) fn with_prefix(mut self, prefix: Vec<u8>) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Encrypt log entries with `encryptor` before writing them to RocksDB.
+    ///
+    /// The same encryptor must be used every time the DB is opened.
+    pub fn with_encryptor(mut self, encryptor: Arc<dyn Encryptor>) -> Self {
+        self.encryptor = encryptor;
+        self
+    }
+
+    /// Rewrite the log entries that are encrypted with a key other than the current key of the
+    /// encryptor, and return the number of rewritten entries.
+    ///
+    /// It can run in a background task while the store is in use: an entry is rewritten only if
+    /// it has not been changed since it was read.
+    /// It blocks on RocksDB IO, call it with `spawn_blocking()` in an async context.
+    pub fn reencrypt(&self) -> Result<u64, StorageError<C>> {
+        let current = self.encryptor.key_id();
+        let mut rewritten = 0;
+
+        let start = self.log_key(0);
+        let it = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::From(&start, Direction::Forward));
+        for item_res in it {
+            let (key, val) = item_res.map_err(read_logs_err)?;
+
+            if self.log_index(&key).is_none() {
+                break;
+            }
+            if sealed_key_id(&val).map_err(read_logs_err)? == current {
+                continue;
+            }
+
+            let plain = unseal(self.encryptor.as_ref(), &val).map_err(read_logs_err)?;
+            let sealed = seal(self.encryptor.as_ref(), &plain).map_err(|e| StorageError::write_logs(&e))?;
+
+            let _guard = self.write_lock.lock().unwrap();
+
+            let latest = self.db.get_cf(self.cf_logs(), &key).map_err(read_logs_err)?;
+            if latest.as_deref() != Some(&*val) {
+                continue;
+            }
+            self.db.put_cf(self.cf_logs(), &key, sealed).map_err(|e| StorageError::write_logs(&e))?;
+            rewritten += 1;
+        }
+
+        Ok(rewritten)
+    }
+
     fn cf_meta(&self) -> &ColumnFamily {
-        self.db.cf_handle("meta").unwrap()
+        self.db.cf_handle(CF_META).unwrap()
     }
 
     fn cf_logs(&self) -> &ColumnFamily {
-        self.db.cf_handle("logs").unwrap()
+        self.db.cf_handle(CF_LOGS).unwrap()
+    }
+
+    fn log_key(&self, index: u64) -> Vec<u8> {
+        prefixed(&self.prefix, &id_to_bin(index))
+    }
+
+    /// Returns the log index of a key in `logs`, or `None` if the key belongs to another prefix.
+    fn log_index(&self, key: &[u8]) -> Option<u64> {
+        key.strip_prefix(self.prefix.as_slice()).map(bin_to_id)
+    }
+
+    /// Collect the number of live log entries of this store and the disk usage of the DB.
+    pub fn metrics(&self) -> Result<RocksStoreMetrics, std::io::Error> {
+        let mut metrics = collect_db_metrics(&self.db)?;
+
+        let start = self.log_key(0);
+        let end = self.log_key(u64::MAX);
+
+        let mut first = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::From(&start, Direction::Forward));
+        let mut last = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::From(&end, Direction::Reverse));
+
+        let first = first.next().transpose().map_err(std::io::Error::other)?;
+        let last = last.next().transpose().map_err(std::io::Error::other)?;
+
+        // Logs are contiguous, the count is derived from the first and the last index.
+        if let (Some((first, _)), Some((last, _))) = (first, last) {
+            if let (Some(first), Some(last)) = (self.log_index(&first), self.log_index(&last)) {
+                metrics.log_entries = last + 1 - first;
+            }
+        }
+
+        Ok(metrics)
+    }
+
+    /// Spawn a task that collects [`metrics`](Self::metrics) every `interval` and emits them
+    /// with `tracing::info!`.
+    ///
+    /// The task runs until the returned handle is aborted.
+    pub fn spawn_metrics_reporter(&self, interval: Duration) -> JoinHandle<()> {
+        let store = self.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+
+                match store.metrics() {
+                    Ok(m) => tracing::info!("rocksstore metrics: {}", m),
+                    Err(e) => tracing::warn!("failed to collect rocksstore metrics: {}", e),
+                }
+            }
+        })
     }
 
     /// Get a store metadata.
     ///
     /// It returns `None` if the store does not have such a metadata stored.
     fn get_meta<M: StoreMeta<C>>(&self) -> Result<Option<M::Value>, StorageError<C>> {
-        let bytes = self.db.get_cf(self.cf_meta(), M::KEY).map_err(M::read_err)?;
+        let key = prefixed(&self.prefix, M::KEY.as_bytes());
+        let bytes = self.db.get_cf(self.cf_meta(), key).map_err(M::read_err)?;
 
         let Some(bytes) = bytes else {
             return Ok(None);
         };
 
-        let t = serde_json::from_slice(&bytes).map_err(M::read_err)?;
+        let t = decode_fixed(&bytes).map_err(M::read_err)?;
 
         Ok(Some(t))
     }
 
     /// Save a store metadata.
     fn put_meta<M: StoreMeta<C>>(&self, value: &M::Value) -> Result<(), StorageError<C>> {
-        let json_value = serde_json::to_vec(value).map_err(|e| M::write_err(value, e))?;
-
-        self.db.put_cf(self.cf_meta(), M::KEY, json_value).map_err(|e| M::write_err(value, e))?;
+        let key = prefixed(&self.prefix, M::KEY.as_bytes());
+        self.db.put_cf(self.cf_meta(), key, value.encode()).map_err(|e| M::write_err(value, e))?;
 
         Ok(())
     }
 }
 
 impl<C> RaftLogReader<C> for RocksLogStore<C>
-where C: RaftTypeConfig
+where
+    C: RaftTypeConfig,
+    VoteOf<C>: FixedWidthCodec,
 {
     async fn try_get_log_entries<RB: RangeBounds<u64> + Clone + Debug + OptionalSend>(
         &mut self,
         range: RB,
     ) -> Result<Vec<C::Entry>, StorageError<C>> {
         let start = match range.start_bound() {
-            std::ops::Bound::Included(x) => id_to_bin(*x),
-            std::ops::Bound::Excluded(x) => id_to_bin(*x + 1),
-            std::ops::Bound::Unbounded => id_to_bin(0),
+            std::ops::Bound::Included(x) => self.log_key(*x),
+            std::ops::Bound::Excluded(x) => self.log_key(*x + 1),
+            std::ops::Bound::Unbounded => self.log_key(0),
         };
 
         let mut res = Vec::new();
 
         let it = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::From(&start, Direction::Forward));
         for item_res in it {
-            let (id, val) = item_res.map_err(read_logs_err)?;
+            let (key, val) = item_res.map_err(read_logs_err)?;
 
-            let id = bin_to_id(&id);
+            let Some(id) = self.log_index(&key) else {
+                break;
+            };
             if !range.contains(&id) {
                 break;
             }
 
-            let entry: EntryOf<C> = serde_json::from_slice(&val).map_err(read_logs_err)?;
+            let entry: EntryOf<C> = decode_entry(self.encryptor.as_ref(), &val)?;
 
             assert_eq!(id, entry.index());
 
@@ -120,19 +293,27 @@ where C: RaftTypeConfig
 
 // It requires TokioRuntime because it uses spawn_blocking internally.
 impl<C> RaftLogStorage<C> for RocksLogStore<C>
-where C: RaftTypeConfig<AsyncRuntime = TokioRuntime>
+where
+    C: RaftTypeConfig<AsyncRuntime = TokioRuntime>,
+    LogIdOf<C>: FixedWidthCodec,
+    VoteOf<C>: FixedWidthCodec,
 {
     type LogReader = Self;
 
     async fn get_log_state(&mut self) -> Result<LogState<C>, StorageError<C>> {
-        let last = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::End).next();
+        let end = self.log_key(u64::MAX);
+        let last = self.db.iterator_cf(self.cf_logs(), rocksdb::IteratorMode::From(&end, Direction::Reverse)).next();
 
         let last_log_id = match last {
             None => None,
             Some(res) => {
-                let (_log_index, entry_bytes) = res.map_err(read_logs_err)?;
-                let ent = serde_json::from_slice::<EntryOf<C>>(&entry_bytes).map_err(read_logs_err)?;
-                Some(ent.log_id())
+                let (key, entry_bytes) = res.map_err(read_logs_err)?;
+                if self.log_index(&key).is_some() {
+                    let ent = decode_entry::<C>(self.encryptor.as_ref(), &entry_bytes)?;
+                    Some(ent.log_id())
+                } else {
+                    None
+                }
             }
         };
 
@@ -168,15 +349,14 @@ where C: RaftTypeConfig<AsyncRuntime = TokioRuntime>
 
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
     where I: IntoIterator<Item = EntryOf<C>> + Send {
-        for entry in entries {
-            let id = id_to_bin(entry.index());
-            self.db
-                .put_cf(
-                    self.cf_logs(),
-                    id,
-                    serde_json::to_vec(&entry).map_err(|e| StorageError::write_logs(&e))?,
-                )
-                .map_err(|e| StorageError::write_logs(&e))?;
+        {
+            let _guard = self.write_lock.lock().unwrap();
+            for entry in entries {
+                let id = self.log_key(entry.index());
+                self.db
+                    .put_cf(self.cf_logs(), id, encode_entry(self.encryptor.as_ref(), &entry)?)
+                    .map_err(|e| StorageError::write_logs(&e))?;
+            }
         }
 
         // Make sure the logs are persisted to disk before invoking the callback.
@@ -197,11 +377,14 @@ where C: RaftTypeConfig<AsyncRuntime = TokioRuntime>
     async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>> {
         tracing::debug!("truncate: [{:?}, +oo)", log_id);
 
-        let from = id_to_bin(log_id.index());
-        let to = id_to_bin(u64::MAX);
+        let from = self.log_key(log_id.index());
+        let to = self.log_key(u64::MAX);
+        let _guard = self.write_lock.lock().unwrap();
         self.db.delete_range_cf(self.cf_logs(), &from, &to).map_err(|e| StorageError::write_logs(&e))?;
 
-        // Truncating does not need to be persisted.
+        // Truncating does not need to be persisted here:
+        // it is in the WAL before the entries of the next `append()`, which flushes the WAL before
+        // invoking its callback.
         Ok(())
     }
 
@@ -213,8 +396,9 @@ where C: RaftTypeConfig<AsyncRuntime = TokioRuntime>
         // Therefore, there is no need to do it in a transaction.
         self.put_meta::<meta::LastPurged>(&log_id)?;
 
-        let from = id_to_bin(0);
-        let to = id_to_bin(log_id.index() + 1);
+        let from = self.log_key(0);
+        let to = self.log_key(log_id.index() + 1);
+        let _guard = self.write_lock.lock().unwrap();
         self.db.delete_range_cf(self.cf_logs(), &from, &to).map_err(|e| StorageError::write_logs(&e))?;
 
         // Purging does not need to be persistent.
@@ -226,9 +410,10 @@ where C: RaftTypeConfig<AsyncRuntime = TokioRuntime>
 ///
 /// In raft, except logs and state machine, the store also has to store several piece of metadata.
 /// This sub mod defines the key-value pairs of these metadata.
-mod meta {
+pub(crate) mod meta {
     use openraft::alias::LogIdOf;
     use openraft::alias::VoteOf;
+    use openraft::storage::codec::FixedWidthCodec;
     use openraft::AnyError;
     use openraft::ErrorSubject;
     use openraft::ErrorVerb;
@@ -242,8 +427,11 @@ mod meta {
         /// The key used to store in rocksdb
         const KEY: &'static str;
 
-        /// The type of the value to store
-        type Value: serde::Serialize + serde::de::DeserializeOwned;
+        /// The type of the value to store.
+        ///
+        /// It is encoded with [`FixedWidthCodec`], and decoded from JSON if it is written by an
+        /// earlier version.
+        type Value: FixedWidthCodec + serde::de::DeserializeOwned;
 
         /// The subject this meta belongs to, and will be embedded into the returned storage error.
         fn subject(v: Option<&Self::Value>) -> ErrorSubject<C>;
@@ -261,7 +449,9 @@ mod meta {
     pub(crate) struct Vote {}
 
     impl<C> StoreMeta<C> for LastPurged
-    where C: RaftTypeConfig
+    where
+        C: RaftTypeConfig,
+        LogIdOf<C>: FixedWidthCodec,
     {
         const KEY: &'static str = "last_purged_log_id";
         type Value = LogIdOf<C>;
@@ -271,7 +461,9 @@ mod meta {
         }
     }
     impl<C> StoreMeta<C> for Vote
-    where C: RaftTypeConfig
+    where
+        C: RaftTypeConfig,
+        VoteOf<C>: FixedWidthCodec,
     {
         const KEY: &'static str = "vote";
         type Value = VoteOf<C>;
@@ -284,16 +476,38 @@ mod meta {
 
 /// converts an id to a byte vector for storing in the database.
 /// Note that we're using big endian encoding to ensure correct sorting of keys
-fn id_to_bin(id: u64) -> Vec<u8> {
+pub(crate) fn id_to_bin(id: u64) -> Vec<u8> {
     let mut buf = Vec::with_capacity(8);
     buf.write_u64::<BigEndian>(id).unwrap();
     buf
 }
 
-fn bin_to_id(buf: &[u8]) -> u64 {
+pub(crate) fn bin_to_id(buf: &[u8]) -> u64 {
     (&buf[0..8]).read_u64::<BigEndian>().unwrap()
 }
 
+/// Returns `key` with `prefix` prepended.
+pub(crate) fn prefixed(prefix: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(prefix.len() + key.len());
+    buf.extend_from_slice(prefix);
+    buf.extend_from_slice(key);
+    buf
+}
+
+/// Serialize a log entry and encrypt it.
+pub(crate) fn encode_entry<C>(encryptor: &dyn Encryptor, entry: &EntryOf<C>) -> Result<Vec<u8>, StorageError<C>>
+where C: RaftTypeConfig {
+    let plain = serde_json::to_vec(entry).map_err(|e| StorageError::write_logs(&e))?;
+    seal(encryptor, &plain).map_err(|e| StorageError::write_logs(&e))
+}
+
+/// Decrypt a log entry and deserialize it.
+pub(crate) fn decode_entry<C>(encryptor: &dyn Encryptor, bytes: &[u8]) -> Result<EntryOf<C>, StorageError<C>>
+where C: RaftTypeConfig {
+    let plain = unseal(encryptor, bytes).map_err(read_logs_err)?;
+    serde_json::from_slice(&plain).map_err(read_logs_err)
+}
+
 fn read_logs_err<C>(e: impl Error + 'static) -> StorageError<C>
 where C: RaftTypeConfig {
     StorageError::read_logs(&e)
@@ -0,0 +1,121 @@
+//! Disk usage metrics of a RocksDB backed store.
+
+use std::fmt;
+use std::io;
+
+use rocksdb::properties;
+use rocksdb::properties::PropName;
+use rocksdb::Options;
+use rocksdb::DB;
+
+/// Size and compaction estimates of one column family, as reported by RocksDB.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnFamilyMetrics {
+    pub name: String,
+
+    /// Estimated size in bytes of the live data.
+    pub estimate_live_data_size: u64,
+
+    /// Total size in bytes of all SST files.
+    pub total_sst_files_size: u64,
+
+    /// Size in bytes of all memtables.
+    pub size_all_mem_tables: u64,
+
+    /// Estimated number of keys.
+    pub estimate_num_keys: u64,
+
+    /// Whether at least one compaction is pending.
+    pub compaction_pending: bool,
+
+    /// Estimated bytes compaction needs to rewrite to get all levels down to target size.
+    pub estimate_pending_compaction_bytes: u64,
+}
+
+/// Metrics of a [`RocksLogStore`](crate::log_store::RocksLogStore) and the DB it is stored in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RocksStoreMetrics {
+    /// Metrics of every column family in the DB.
+    pub column_families: Vec<ColumnFamilyMetrics>,
+
+    /// Number of log entries that are not purged.
+    pub log_entries: u64,
+
+    /// Number of compactions running in the DB.
+    pub num_running_compactions: u64,
+}
+
+impl RocksStoreMetrics {
+    /// Total size in bytes of SST files and memtables of all column families.
+    pub fn disk_usage(&self) -> u64 {
+        self.column_families.iter().map(|cf| cf.total_sst_files_size + cf.size_all_mem_tables).sum()
+    }
+}
+
+impl fmt::Display for RocksStoreMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "log_entries: {}, disk_usage: {}, running_compactions: {}, column_families: [",
+            self.log_entries,
+            self.disk_usage(),
+            self.num_running_compactions
+        )?;
+
+        for (i, cf) in self.column_families.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(
+                f,
+                "{}: {{live: {}, sst: {}, mem: {}, keys: {}, pending_compaction: {}}}",
+                cf.name,
+                cf.estimate_live_data_size,
+                cf.total_sst_files_size,
+                cf.size_all_mem_tables,
+                cf.estimate_num_keys,
+                cf.estimate_pending_compaction_bytes
+            )?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+/// Collect DB level metrics, leaving `log_entries` as 0.
+pub(crate) fn collect_db_metrics(db: &DB) -> Result<RocksStoreMetrics, io::Error> {
+    let cf_names = DB::list_cf(&Options::default(), db.path()).map_err(io::Error::other)?;
+
+    let mut column_families = Vec::with_capacity(cf_names.len());
+    for name in cf_names {
+        let Some(cf) = db.cf_handle(&name) else {
+            continue;
+        };
+
+        let prop = |name: &PropName| -> Result<u64, io::Error> {
+            let v = db.property_int_value_cf(cf, name).map_err(io::Error::other)?;
+            Ok(v.unwrap_or_default())
+        };
+
+        column_families.push(ColumnFamilyMetrics {
+            estimate_live_data_size: prop(properties::ESTIMATE_LIVE_DATA_SIZE)?,
+            total_sst_files_size: prop(properties::TOTAL_SST_FILES_SIZE)?,
+            size_all_mem_tables: prop(properties::SIZE_ALL_MEM_TABLES)?,
+            estimate_num_keys: prop(properties::ESTIMATE_NUM_KEYS)?,
+            compaction_pending: prop(properties::COMPACTION_PENDING)? > 0,
+            estimate_pending_compaction_bytes: prop(properties::ESTIMATE_PENDING_COMPACTION_BYTES)?,
+            name,
+        });
+    }
+
+    let num_running_compactions = db
+        .property_int_value(properties::NUM_RUNNING_COMPACTIONS)
+        .map_err(io::Error::other)?
+        .unwrap_or_default();
+
+    Ok(RocksStoreMetrics {
+        column_families,
+        log_entries: 0,
+        num_running_compactions,
+    })
+}
@@ -0,0 +1,165 @@
+//! Host the logs and state machines of many Raft groups in one RocksDB instance.
+//!
+//! Every group shares the column families of a single-group store. Keys of a group are prefixed
+//! with the big-endian encoded group id, so that dropping a group is a range deletion.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use openraft::RaftTypeConfig;
+use rocksdb::ColumnFamilyDescriptor;
+use rocksdb::Options;
+use rocksdb::WriteBatch;
+use rocksdb::DB;
+
+use crate::log_store::bin_to_id;
+use crate::log_store::id_to_bin;
+use crate::log_store::RocksLogStore;
+use crate::log_store::CF_LOGS;
+use crate::log_store::CF_META;
+use crate::state_machine::RocksStateMachine;
+use crate::state_machine::CF_SM_DATA;
+use crate::state_machine::CF_SM_META;
+use crate::TypeConfig;
+
+/// Column family registering the groups hosted by a [`RocksMultiStore`].
+pub const CF_GROUPS: &str = "groups";
+
+/// Identifies a Raft group in a [`RocksMultiStore`].
+pub type GroupId = u64;
+
+/// A RocksDB instance shared by many Raft groups.
+///
+/// A group must be created with [`create_group`](Self::create_group) before its storage is opened
+/// with [`open_group`](Self::open_group).
+#[derive(Debug, Clone)]
+pub struct RocksMultiStore {
+    db: Arc<DB>,
+    db_path: PathBuf,
+}
+
+impl RocksMultiStore {
+    /// Open or create a multi-raft DB at `db_path`.
+    pub fn open<P: AsRef<Path>>(db_path: P) -> Result<Self, io::Error> {
+        let mut db_opts = Options::default();
+        db_opts.create_missing_column_families(true);
+        db_opts.create_if_missing(true);
+
+        let mut cfs = vec![ColumnFamilyDescriptor::new(CF_GROUPS, Options::default())];
+        // Column families do not depend on the type config
+        cfs.extend(RocksLogStore::<TypeConfig>::column_families());
+        cfs.extend(RocksStateMachine::<TypeConfig>::column_families());
+
+        let db_path = db_path.as_ref();
+        let db = DB::open_cf_descriptors(&db_opts, db_path, cfs).map_err(io::Error::other)?;
+
+        Ok(Self {
+            db: Arc::new(db),
+            db_path: db_path.to_path_buf(),
+        })
+    }
+
+    /// Register a new empty group.
+    ///
+    /// It returns an [`io::ErrorKind::AlreadyExists`] error if the group already exists.
+    pub fn create_group(&self, group_id: GroupId) -> Result<(), io::Error> {
+        if self.has_group(group_id)? {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("group {} already exists", group_id),
+            ));
+        }
+
+        // Remove snapshot files left by an interrupted `drop_group()`.
+        let snapshot_dir = self.snapshot_dir(group_id);
+        if snapshot_dir.exists() {
+            fs::remove_dir_all(&snapshot_dir)?;
+        }
+
+        self.db.put_cf(self.cf(CF_GROUPS), id_to_bin(group_id), b"").map_err(io::Error::other)
+    }
+
+    /// Remove a group and all of its logs, metadata and state machine data.
+    ///
+    /// All keys of the group are deleted in a single atomic write batch.
+    /// It returns an [`io::ErrorKind::NotFound`] error if the group does not exist.
+    pub fn drop_group(&self, group_id: GroupId) -> Result<(), io::Error> {
+        if !self.has_group(group_id)? {
+            return Err(group_not_found(group_id));
+        }
+
+        let from = id_to_bin(group_id);
+        let to = match group_id.checked_add(1) {
+            Some(next) => id_to_bin(next),
+            // Every key of the last group is shorter than this
+            None => vec![0xff; 9],
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(self.cf(CF_GROUPS), &from);
+        for cf_name in [CF_META, CF_LOGS, CF_SM_META, CF_SM_DATA] {
+            batch.delete_range_cf(self.cf(cf_name), &from, &to);
+        }
+        self.db.write(batch).map_err(io::Error::other)?;
+
+        let snapshot_dir = self.snapshot_dir(group_id);
+        if snapshot_dir.exists() {
+            fs::remove_dir_all(&snapshot_dir)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether a group is registered.
+    pub fn has_group(&self, group_id: GroupId) -> Result<bool, io::Error> {
+        let v = self.db.get_cf(self.cf(CF_GROUPS), id_to_bin(group_id)).map_err(io::Error::other)?;
+        Ok(v.is_some())
+    }
+
+    /// Returns ids of all registered groups in ascending order.
+    pub fn list_groups(&self) -> Result<Vec<GroupId>, io::Error> {
+        let mut groups = vec![];
+        for item in self.db.iterator_cf(self.cf(CF_GROUPS), rocksdb::IteratorMode::Start) {
+            let (key, _) = item.map_err(io::Error::other)?;
+            groups.push(bin_to_id(&key));
+        }
+        Ok(groups)
+    }
+
+    /// Open the log store and the state machine of a registered group.
+    ///
+    /// It returns an [`io::ErrorKind::NotFound`] error if the group does not exist.
+    pub async fn open_group<C>(
+        &self,
+        group_id: GroupId,
+    ) -> Result<(RocksLogStore<C>, RocksStateMachine<C>), io::Error>
+    where
+        C: RaftTypeConfig,
+    {
+        if !self.has_group(group_id)? {
+            return Err(group_not_found(group_id));
+        }
+
+        let prefix = id_to_bin(group_id);
+
+        let log_store = RocksLogStore::new(self.db.clone()).with_prefix(prefix.clone());
+        let sm = RocksStateMachine::new(self.db.clone(), self.snapshot_dir(group_id)).await?.with_prefix(prefix);
+
+        Ok((log_store, sm))
+    }
+
+    fn snapshot_dir(&self, group_id: GroupId) -> PathBuf {
+        self.db_path.join("snapshots").join(group_id.to_string())
+    }
+
+    fn cf(&self, name: &str) -> &rocksdb::ColumnFamily {
+        self.db.cf_handle(name).unwrap()
+    }
+}
+
+fn group_not_found(group_id: GroupId) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("group {} not found", group_id))
+}
@@ -0,0 +1,221 @@
+//! Read-only access to the data directory of a node, for offline inspection.
+
+use std::fs;
+use std::io;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use openraft::alias::EntryOf;
+use openraft::alias::LogIdOf;
+use openraft::alias::VoteOf;
+use openraft::entry::RaftEntry;
+use openraft::storage::codec::FixedWidthCodec;
+use openraft::AnyError;
+use openraft::RaftTypeConfig;
+use openraft::SnapshotMeta;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use rocksdb::Direction;
+use rocksdb::Options;
+use rocksdb::DB;
+
+use crate::codec::decode_fixed;
+use crate::encryption::no_encryption;
+use crate::encryption::unseal;
+use crate::encryption::Encryptor;
+use crate::log_store::bin_to_id;
+use crate::log_store::decode_entry;
+use crate::log_store::id_to_bin;
+use crate::log_store::meta;
+use crate::log_store::meta::StoreMeta;
+use crate::log_store::CF_LOGS;
+use crate::log_store::CF_META;
+use crate::state_machine::SnapshotFile;
+use crate::state_machine::CF_SM_META;
+
+/// A read-only handle to a RocksDB data directory written by this crate.
+///
+/// The DB is opened with RocksDB's read-only mode, so it can be used against the data directory
+/// of a stopped node without risking any write.
+/// Column families that do not exist in the DB, for example the state machine column families
+/// of a directory that only contains a log store, are reported as empty.
+#[derive(Debug)]
+pub struct RocksStore<C>
+where C: RaftTypeConfig
+{
+    db: DB,
+    snapshot_dir: PathBuf,
+    encryptor: Arc<dyn Encryptor>,
+    _p: PhantomData<C>,
+}
+
+impl<C> RocksStore<C>
+where
+    C: RaftTypeConfig,
+    LogIdOf<C>: FixedWidthCodec,
+    VoteOf<C>: FixedWidthCodec,
+{
+    /// Open the DB at `db_path` in read-only mode.
+    pub fn open_read_only<P: AsRef<Path>>(db_path: P) -> Result<Self, io::Error> {
+        let db_path = db_path.as_ref();
+        let opts = Options::default();
+
+        let cfs = DB::list_cf(&opts, db_path).map_err(io::Error::other)?;
+        let db = DB::open_cf_for_read_only(&opts, db_path, cfs, false).map_err(io::Error::other)?;
+
+        Ok(Self {
+            db,
+            snapshot_dir: db_path.join("snapshots"),
+            encryptor: no_encryption(),
+            _p: PhantomData,
+        })
+    }
+
+    /// Decrypt log entries and snapshot files with `encryptor`.
+    ///
+    /// It must be the encryptor the data directory was written with.
+    pub fn with_encryptor(mut self, encryptor: Arc<dyn Encryptor>) -> Self {
+        self.encryptor = encryptor;
+        self
+    }
+
+    /// Read the persisted vote.
+    pub fn read_vote(&self) -> Result<Option<VoteOf<C>>, StorageError<C>> {
+        self.get_meta::<meta::Vote>()
+    }
+
+    /// Read the id of the last purged log entry.
+    pub fn read_last_purged_log_id(&self) -> Result<Option<LogIdOf<C>>, StorageError<C>> {
+        self.get_meta::<meta::LastPurged>()
+    }
+
+    /// Read and decode log entries in `range`.
+    pub fn read_entries<RB: RangeBounds<u64>>(&self, range: RB) -> Result<Vec<EntryOf<C>>, StorageError<C>> {
+        let Some(cf) = self.db.cf_handle(CF_LOGS) else {
+            return Ok(vec![]);
+        };
+
+        let start = match range.start_bound() {
+            Bound::Included(x) => id_to_bin(*x),
+            Bound::Excluded(x) => id_to_bin(*x + 1),
+            Bound::Unbounded => id_to_bin(0),
+        };
+
+        let mut res = Vec::new();
+
+        let it = self.db.iterator_cf(cf, rocksdb::IteratorMode::From(&start, Direction::Forward));
+        for item_res in it {
+            let (id, val) = item_res.map_err(|e| StorageError::read_logs(&e))?;
+
+            let id = bin_to_id(&id);
+            if !range.contains(&id) {
+                break;
+            }
+
+            let entry: EntryOf<C> = decode_entry(self.encryptor.as_ref(), &val)?;
+            res.push(entry);
+        }
+        Ok(res)
+    }
+
+    /// Read the last applied log id and the last membership of the state machine.
+    #[allow(clippy::type_complexity)]
+    pub fn read_applied_state(&self) -> Result<(Option<LogIdOf<C>>, StoredMembership<C>), StorageError<C>> {
+        let Some(cf) = self.db.cf_handle(CF_SM_META) else {
+            return Ok((None, StoredMembership::default()));
+        };
+
+        let last_applied_log = self
+            .db
+            .get_cf(cf, "last_applied_log")
+            .map_err(|e| StorageError::read(&e))?
+            .map(|bytes| decode_fixed(&bytes).map_err(|e| StorageError::read(&e)))
+            .transpose()?;
+
+        let last_membership = self
+            .db
+            .get_cf(cf, "last_membership")
+            .map_err(|e| StorageError::read(&e))?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(|e| StorageError::read(&e)))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok((last_applied_log, last_membership))
+    }
+
+    /// Read the metadata of every snapshot file in the snapshot directory, sorted by snapshot id.
+    pub fn read_snapshot_metas(&self) -> Result<Vec<SnapshotMeta<C>>, StorageError<C>> {
+        if !self.snapshot_dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut paths = vec![];
+        for entry in fs::read_dir(&self.snapshot_dir).map_err(|e| StorageError::read_snapshot(None, &e))? {
+            let path = entry.map_err(|e| StorageError::read_snapshot(None, &e))?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut metas = Vec::with_capacity(paths.len());
+        for path in paths {
+            let file_bytes = fs::read(&path).map_err(|e| StorageError::read_snapshot(None, &e))?;
+            let file_bytes =
+                unseal(self.encryptor.as_ref(), &file_bytes).map_err(|e| StorageError::read_snapshot(None, &e))?;
+            let snapshot_file = SnapshotFile::<C>::decode(&file_bytes)
+                .map_err(|e| StorageError::read_snapshot(None, AnyError::new(&e)))?;
+            metas.push(snapshot_file.meta);
+        }
+
+        Ok(metas)
+    }
+
+    /// Write a human-readable dump of all raft data to `w`.
+    pub fn dump<W: io::Write>(&self, w: &mut W) -> Result<(), io::Error> {
+        let vote = self.read_vote().map_err(io::Error::other)?;
+        let purged = self.read_last_purged_log_id().map_err(io::Error::other)?;
+        let (last_applied, membership) = self.read_applied_state().map_err(io::Error::other)?;
+
+        writeln!(w, "vote: {}", display_opt(vote.as_ref()))?;
+        writeln!(w, "last_purged_log_id: {}", display_opt(purged.as_ref()))?;
+        writeln!(w, "last_applied_log_id: {}", display_opt(last_applied.as_ref()))?;
+        writeln!(w, "last_membership: {}", membership)?;
+
+        writeln!(w, "snapshots:")?;
+        for meta in self.read_snapshot_metas().map_err(io::Error::other)? {
+            writeln!(w, "  {}", meta)?;
+        }
+
+        writeln!(w, "logs:")?;
+        for entry in self.read_entries(..).map_err(io::Error::other)? {
+            writeln!(w, "  {}: {}", entry.index(), entry)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_meta<M: StoreMeta<C>>(&self) -> Result<Option<M::Value>, StorageError<C>> {
+        let Some(cf) = self.db.cf_handle(CF_META) else {
+            return Ok(None);
+        };
+
+        let Some(bytes) = self.db.get_cf(cf, M::KEY).map_err(M::read_err)? else {
+            return Ok(None);
+        };
+
+        let t = decode_fixed(&bytes).map_err(M::read_err)?;
+        Ok(Some(t))
+    }
+}
+
+fn display_opt<T: std::fmt::Display>(v: Option<&T>) -> String {
+    match v {
+        Some(v) => v.to_string(),
+        None => "None".to_string(),
+    }
+}
@@ -0,0 +1,606 @@
+use std::fs;
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use openraft::entry::RaftEntry;
+use openraft::storage::codec::CodecError;
+use openraft::storage::codec::FixedWidthCodec;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::Snapshot;
+use openraft::AnyError;
+use openraft::Entry;
+use openraft::EntryPayload;
+use openraft::LogId;
+use openraft::OptionalSend;
+use openraft::RaftSnapshotBuilder;
+use openraft::RaftTypeConfig;
+use openraft::SnapshotMeta;
+use openraft::StorageError;
+use openraft::StoredMembership;
+use rand::Rng;
+use rocksdb::ColumnFamily;
+use rocksdb::ColumnFamilyDescriptor;
+use rocksdb::Direction;
+use rocksdb::Options;
+use rocksdb::WriteBatch;
+use rocksdb::DB;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::task::spawn_blocking;
+
+use crate::codec::decode_fixed;
+use crate::codec::invalid;
+use crate::encryption::no_encryption;
+use crate::encryption::seal;
+use crate::encryption::sealed_key_id;
+use crate::encryption::unseal;
+use crate::encryption::Encryptor;
+use crate::log_store::prefixed;
+
+/// Column family storing state machine metadata: last applied log id and last membership.
+pub const CF_SM_META: &str = "sm_meta";
+
+/// Column family storing application data of the state machine.
+pub const CF_SM_DATA: &str = "sm_data";
+
+/// Defines how an application request is applied to a [`RocksStateMachine`].
+///
+/// The implementation adds its writes to `batch`, which is committed atomically together with
+/// the state machine metadata, and returns the response to the client.
+/// Responses for blank and membership entries are built with `R::default()`.
+pub trait RocksApply<R> {
+    fn apply(&self, batch: &mut SmWriteBatch<'_>) -> R;
+}
+
+/// Application data writes of a [`RocksStateMachine`].
+///
+/// Keys are relative to the state machine: they are stored in [`CF_SM_DATA`] under the prefix of
+/// the state machine.
+pub struct SmWriteBatch<'a> {
+    cf_data: &'a ColumnFamily,
+    prefix: &'a [u8],
+    batch: &'a mut WriteBatch,
+}
+
+impl SmWriteBatch<'_> {
+    pub fn put(&mut self, key: impl AsRef<[u8]>, value: impl AsRef<[u8]>) {
+        self.batch.put_cf(self.cf_data, prefixed(self.prefix, key.as_ref()), value);
+    }
+
+    pub fn delete(&mut self, key: impl AsRef<[u8]>) {
+        self.batch.delete_cf(self.cf_data, prefixed(self.prefix, key.as_ref()));
+    }
+}
+
+/// State machine backed by RocksDB for full persistence.
+///
+/// All application data is stored directly in the [`CF_SM_DATA`] column family.
+/// Snapshots are persisted to the `snapshot_dir` directory, encrypted with the configured
+/// [`Encryptor`].
+///
+/// It does not depend on [`RocksLogStore`](crate::log_store::RocksLogStore) and can be used with
+/// any other [`RaftLogStorage`](openraft::storage::RaftLogStorage) implementation.
+#[derive(Debug)]
+pub struct RocksStateMachine<C>
+where C: RaftTypeConfig
+{
+    db: Arc<DB>,
+    snapshot_dir: PathBuf,
+    encryptor: Arc<dyn Encryptor>,
+
+    /// Prefix of every key this state machine reads or writes.
+    ///
+    /// It is empty for a state machine that owns the column families,
+    /// and is the group id for a group in a
+    /// [`RocksMultiStore`](crate::multi_raft::RocksMultiStore).
+    prefix: Vec<u8>,
+    _p: PhantomData<C>,
+}
+
+impl<C> Clone for RocksStateMachine<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            snapshot_dir: self.snapshot_dir.clone(),
+            encryptor: self.encryptor.clone(),
+            prefix: self.prefix.clone(),
+            _p: PhantomData,
+        }
+    }
+}
+
+impl<C> RocksStateMachine<C>
+where C: RaftTypeConfig
+{
+    /// Returns the column families a state machine requires.
+    ///
+    /// Add them to the descriptors when opening a DB shared with other components.
+    pub fn column_families() -> Vec<ColumnFamilyDescriptor> {
+        vec![
+            ColumnFamilyDescriptor::new(CF_SM_META, Options::default()),
+            ColumnFamilyDescriptor::new(CF_SM_DATA, Options::default()),
+        ]
+    }
+
+    /// Open a standalone state machine in its own DB at `db_path`.
+    ///
+    /// Snapshots are stored in the `snapshots` sub-directory of `db_path`.
+    pub async fn open<P: AsRef<Path>>(db_path: P) -> Result<Self, std::io::Error> {
+        let mut db_opts = Options::default();
+        db_opts.create_missing_column_families(true);
+        db_opts.create_if_missing(true);
+
+        let db_path = db_path.as_ref();
+        let db = DB::open_cf_descriptors(&db_opts, db_path, Self::column_families()).map_err(std::io::Error::other)?;
+
+        Self::new(Arc::new(db), db_path.join("snapshots")).await
+    }
+
+    /// Create a state machine on an already opened DB.
+    ///
+    /// The DB must contain the column families returned by [`Self::column_families`].
+    pub async fn new(db: Arc<DB>, snapshot_dir: PathBuf) -> Result<Self, std::io::Error> {
+        // Validate column families exist at construction time
+        db.cf_handle(CF_SM_META).ok_or_else(|| std::io::Error::other("column family `sm_meta` not found"))?;
+        db.cf_handle(CF_SM_DATA).ok_or_else(|| std::io::Error::other("column family `sm_data` not found"))?;
+
+        // Create snapshot directory if it doesn't exist
+        fs::create_dir_all(&snapshot_dir)?;
+
+        Ok(Self {
+            db,
+            snapshot_dir,
+            encryptor: no_encryption(),
+            prefix: vec![],
+            _p: PhantomData,
+        })
+    }
+
+    /// Store all keys under `prefix`, so that several state machines can share the same column
+    /// families.
+    pub(crate) fn with_prefix(mut self, prefix: Vec<u8>) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Encrypt snapshot files with `encryptor` before writing them to the snapshot directory.
+    ///
+    /// The same encryptor must be used every time the state machine is opened.
+    pub fn with_encryptor(mut self, encryptor: Arc<dyn Encryptor>) -> Self {
+        self.encryptor = encryptor;
+        self
+    }
+
+    /// Rewrite the snapshot files that are encrypted with a key other than the current key of the
+    /// encryptor, and return the number of rewritten files.
+    ///
+    /// It can run in a background task while the state machine is in use: a rewritten file is
+    /// written to a temporary directory first, then renamed over the original one.
+    /// It blocks on file IO, call it with `spawn_blocking()` in an async context.
+    pub fn reencrypt_snapshots(&self) -> Result<u64, StorageError<C>> {
+        let current = self.encryptor.key_id();
+        let mut rewritten = 0;
+
+        // A sub-directory is not a snapshot file, thus it is ignored when reading snapshots.
+        let tmp_dir = self.snapshot_dir.join("tmp");
+        fs::create_dir_all(&tmp_dir).map_err(|e| StorageError::write_snapshot(None, &e))?;
+
+        for entry in fs::read_dir(&self.snapshot_dir).map_err(|e| StorageError::read_snapshot(None, &e))? {
+            let path = entry.map_err(|e| StorageError::read_snapshot(None, &e))?.path();
+            if !path.is_file() {
+                continue;
+            }
+
+            let file_bytes = fs::read(&path).map_err(|e| StorageError::read_snapshot(None, &e))?;
+            if sealed_key_id(&file_bytes).map_err(|e| StorageError::read_snapshot(None, &e))? == current {
+                continue;
+            }
+
+            let plain =
+                unseal(self.encryptor.as_ref(), &file_bytes).map_err(|e| StorageError::read_snapshot(None, &e))?;
+            let file_bytes =
+                seal(self.encryptor.as_ref(), &plain).map_err(|e| StorageError::write_snapshot(None, &e))?;
+
+            let tmp_path = tmp_dir.join(path.file_name().unwrap());
+            fs::write(&tmp_path, &file_bytes).map_err(|e| StorageError::write_snapshot(None, &e))?;
+            fs::rename(&tmp_path, &path).map_err(|e| StorageError::write_snapshot(None, &e))?;
+            rewritten += 1;
+        }
+
+        Ok(rewritten)
+    }
+
+    fn cf_sm_meta(&self) -> &ColumnFamily {
+        self.db.cf_handle(CF_SM_META).unwrap()
+    }
+
+    fn cf_sm_data(&self) -> &ColumnFamily {
+        self.db.cf_handle(CF_SM_DATA).unwrap()
+    }
+
+    /// Read the value of an application key.
+    pub fn get(&self, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, StorageError<C>> {
+        let key = prefixed(&self.prefix, key.as_ref());
+        self.db.get_cf(self.cf_sm_data(), key).map_err(|e| StorageError::read_state_machine(&e))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn get_meta(&self) -> Result<(Option<LogId<C>>, StoredMembership<C>), StorageError<C>>
+    where LogId<C>: FixedWidthCodec {
+        let cf = self.cf_sm_meta();
+
+        let last_applied_log = self
+            .db
+            .get_cf(cf, prefixed(&self.prefix, b"last_applied_log"))
+            .map_err(|e| StorageError::read(&e))?
+            .map(|bytes| decode_fixed(&bytes).map_err(|e| StorageError::read(&e)))
+            .transpose()?;
+
+        let last_membership = self
+            .db
+            .get_cf(cf, prefixed(&self.prefix, b"last_membership"))
+            .map_err(|e| StorageError::read(&e))?
+            .map(|bytes| deserialize(&bytes))
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok((last_applied_log, last_membership))
+    }
+}
+
+fn serialize<C, T>(value: &T) -> Result<Vec<u8>, StorageError<C>>
+where
+    C: RaftTypeConfig,
+    T: Serialize,
+{
+    serde_json::to_vec(value).map_err(|e| StorageError::write(&e))
+}
+
+fn deserialize<C, T>(bytes: &[u8]) -> Result<T, StorageError<C>>
+where
+    C: RaftTypeConfig,
+    T: for<'de> Deserialize<'de>,
+{
+    serde_json::from_slice(bytes).map_err(|e| StorageError::read(&e))
+}
+
+/// Snapshot file format: metadata + data stored together
+///
+/// It derives `Deserialize` only to read the JSON encoded files written by an earlier version.
+#[derive(Deserialize)]
+#[serde(bound = "")]
+pub(crate) struct SnapshotFile<C>
+where C: RaftTypeConfig
+{
+    pub(crate) meta: SnapshotMeta<C>,
+    pub(crate) data: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<C> SnapshotFile<C>
+where
+    C: RaftTypeConfig,
+    LogId<C>: FixedWidthCodec,
+{
+    /// Encode the snapshot file as:
+    ///
+    /// ```text
+    /// meta length: u32 | SnapshotMeta::encode() | JSON encoded data
+    /// ```
+    pub(crate) fn encode(&self) -> Result<Vec<u8>, CodecError> {
+        let meta = self.meta.encode();
+
+        let mut buf = Vec::with_capacity(u32::ENCODED_LEN + meta.len());
+        (meta.len() as u32).encode_to(&mut buf);
+        buf.extend_from_slice(&meta);
+        serde_json::to_writer(&mut buf, &self.data).map_err(invalid)?;
+
+        Ok(buf)
+    }
+
+    /// Decode a snapshot file encoded by [`Self::encode()`], or a JSON encoded one written by an
+    /// earlier version.
+    pub(crate) fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        // The first byte of an encoded file is the most significant byte of the meta length, which
+        // is never `{`, the first byte of a JSON encoded file, for a meta smaller than 2 GiB.
+        if bytes.first() == Some(&b'{') {
+            return serde_json::from_slice(bytes).map_err(invalid);
+        }
+
+        if bytes.len() < u32::ENCODED_LEN {
+            return Err(CodecError::Length {
+                expected: u32::ENCODED_LEN,
+                got: bytes.len(),
+            });
+        }
+        let (len, rest) = bytes.split_at(u32::ENCODED_LEN);
+        let len = u32::decode(len)? as usize;
+
+        if rest.len() < len {
+            return Err(CodecError::Length {
+                expected: u32::ENCODED_LEN + len,
+                got: bytes.len(),
+            });
+        }
+        let (meta, data) = rest.split_at(len);
+
+        Ok(Self {
+            meta: SnapshotMeta::decode(meta)?,
+            data: serde_json::from_slice(data).map_err(invalid)?,
+        })
+    }
+}
+
+impl<C> RaftSnapshotBuilder<C> for RocksStateMachine<C>
+where
+    C: RaftTypeConfig<SnapshotData = Cursor<Vec<u8>>>,
+    LogId<C>: FixedWidthCodec,
+{
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn build_snapshot(&mut self) -> Result<Snapshot<C>, StorageError<C>> {
+        let (last_applied_log, last_membership) = self.get_meta()?;
+
+        // Generate a random snapshot index.
+        let snapshot_idx: u64 = rand::rng().random_range(0..1000);
+
+        let snapshot_id = if let Some(last) = &last_applied_log {
+            format!("{}-{}-{}", last.committed_leader_id(), last.index(), snapshot_idx)
+        } else {
+            format!("--{}", snapshot_idx)
+        };
+
+        let meta = SnapshotMeta {
+            last_log_id: last_applied_log,
+            last_membership,
+            snapshot_id: snapshot_id.clone(),
+        };
+
+        // Use RocksDB snapshot for consistent point-in-time view
+        let db = self.db.clone();
+        let prefix = self.prefix.clone();
+        let meta_clone = meta.clone();
+
+        let data = spawn_blocking(move || {
+            let snapshot = db.snapshot();
+            let cf_data = db.cf_handle(CF_SM_DATA).expect("column family `sm_data` not found");
+
+            let mut snapshot_data = Vec::new();
+            let iter = snapshot.iterator_cf(cf_data, rocksdb::IteratorMode::From(&prefix, Direction::Forward));
+
+            for item in iter {
+                let (key, value) = item.map_err(|e| StorageError::read_snapshot(Some(meta_clone.signature()), &e))?;
+                let Some(key) = key.strip_prefix(prefix.as_slice()) else {
+                    break;
+                };
+                snapshot_data.push((key.to_vec(), value.to_vec()));
+            }
+
+            Ok::<_, StorageError<C>>(snapshot_data)
+        })
+        .await
+        .map_err(|e| StorageError::read_snapshot(Some(meta.signature()), &std::io::Error::other(e.to_string())))??;
+
+        // Serialize both metadata and data together
+        let snapshot_file = SnapshotFile {
+            meta: meta.clone(),
+            data: data.clone(),
+        };
+        let file_bytes = snapshot_file
+            .encode()
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), AnyError::new(&e)))?;
+        let file_bytes = seal(self.encryptor.as_ref(), &file_bytes)
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), &e))?;
+
+        // Write complete snapshot to file
+        let snapshot_path = self.snapshot_dir.join(&snapshot_id);
+        fs::write(&snapshot_path, &file_bytes).map_err(|e| StorageError::write_snapshot(Some(meta.signature()), &e))?;
+
+        // Return snapshot with data-only for backward compatibility with the data field
+        let data_bytes = serialize::<C, _>(&data)
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), AnyError::new(&e)))?;
+
+        Ok(Snapshot {
+            meta,
+            snapshot: Cursor::new(data_bytes),
+        })
+    }
+}
+
+impl<C> RaftStateMachine<C> for RocksStateMachine<C>
+where
+    C: RaftTypeConfig<Entry = Entry<C>, SnapshotData = Cursor<Vec<u8>>>,
+    C::D: RocksApply<C::R>,
+    C::R: Default,
+    LogId<C>: FixedWidthCodec,
+{
+    type SnapshotBuilder = Self;
+
+    async fn applied_state(&mut self) -> Result<(Option<LogId<C>>, StoredMembership<C>), StorageError<C>> {
+        self.get_meta()
+    }
+
+    async fn apply<I>(&mut self, entries: I) -> Result<Vec<C::R>, StorageError<C>>
+    where I: IntoIterator<Item = Entry<C>> + OptionalSend {
+        let entries_iter = entries.into_iter();
+        let mut res = Vec::with_capacity(entries_iter.size_hint().0);
+
+        let cf_meta = self.cf_sm_meta();
+
+        let mut batch = WriteBatch::default();
+        let mut sm_batch = SmWriteBatch {
+            cf_data: self.cf_sm_data(),
+            prefix: &self.prefix,
+            batch: &mut batch,
+        };
+        let mut last_applied_log = None;
+        let mut last_membership = None;
+
+        for entry in entries_iter {
+            tracing::debug!(%entry.log_id, "replicate to sm");
+
+            last_applied_log = Some(entry.log_id());
+
+            match entry.payload {
+                EntryPayload::Blank | EntryPayload::VersionBump(_) => res.push(C::R::default()),
+                EntryPayload::Normal(ref req) => res.push(req.apply(&mut sm_batch)),
+                EntryPayload::Membership(ref mem) => {
+                    last_membership = Some(StoredMembership::new(Some(entry.log_id), mem.clone()));
+                    res.push(C::R::default())
+                }
+            };
+        }
+
+        // Add metadata writes to the batch for atomic commit
+        if let Some(ref log_id) = last_applied_log {
+            let key = prefixed(&self.prefix, b"last_applied_log");
+            batch.put_cf(cf_meta, key, log_id.encode());
+        }
+
+        if let Some(ref membership) = last_membership {
+            let key = prefixed(&self.prefix, b"last_membership");
+            batch.put_cf(cf_meta, key, serialize::<C, _>(membership)?);
+        }
+
+        // Atomic write of all data + metadata
+        self.db.write(batch).map_err(|e| StorageError::write(&e))?;
+
+        Ok(res)
+    }
+
+    async fn get_snapshot_builder(&mut self) -> Self::SnapshotBuilder {
+        self.clone()
+    }
+
+    async fn begin_receiving_snapshot(&mut self) -> Result<Cursor<Vec<u8>>, StorageError<C>> {
+        Ok(Cursor::new(Vec::new()))
+    }
+
+    async fn install_snapshot(
+        &mut self,
+        meta: &SnapshotMeta<C>,
+        snapshot: Cursor<Vec<u8>>,
+    ) -> Result<(), StorageError<C>> {
+        tracing::info!(
+            { snapshot_size = snapshot.get_ref().len() },
+            "decoding snapshot for installation"
+        );
+
+        // Deserialize snapshot data
+        let snapshot_data: Vec<(Vec<u8>, Vec<u8>)> = deserialize::<C, _>(snapshot.get_ref())
+            .map_err(|e| StorageError::read_snapshot(Some(meta.signature()), AnyError::new(&e)))?;
+
+        // Clone data for file writing later
+        let snapshot_data_clone = snapshot_data.clone();
+
+        // Prepare metadata to restore
+        let last_applied_bytes = meta.last_log_id.as_ref().map(|log_id| log_id.encode());
+
+        let last_membership_bytes = serialize::<C, _>(&meta.last_membership)
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), AnyError::new(&e)))?;
+
+        // Restore data and metadata atomically to RocksDB
+        let db = self.db.clone();
+        let prefix = self.prefix.clone();
+        let meta_sig = meta.signature();
+
+        spawn_blocking(move || {
+            let cf_data = db.cf_handle(CF_SM_DATA).expect("column family `sm_data` not found");
+            let cf_meta = db.cf_handle(CF_SM_META).expect("column family `sm_meta` not found");
+
+            let mut batch = WriteBatch::default();
+
+            // Clear existing data in sm_data
+            let iter = db.iterator_cf(cf_data, rocksdb::IteratorMode::From(&prefix, Direction::Forward));
+            for item in iter {
+                let (key, _) = item.map_err(|e| StorageError::write_snapshot(Some(meta_sig.clone()), &e))?;
+                if !key.starts_with(&prefix) {
+                    break;
+                }
+                batch.delete_cf(cf_data, &key);
+            }
+
+            // Restore snapshot data to sm_data
+            for (key, value) in snapshot_data {
+                batch.put_cf(cf_data, prefixed(&prefix, &key), &value);
+            }
+
+            // Restore metadata to sm_meta
+            if let Some(bytes) = last_applied_bytes {
+                batch.put_cf(cf_meta, prefixed(&prefix, b"last_applied_log"), bytes);
+            }
+            batch.put_cf(cf_meta, prefixed(&prefix, b"last_membership"), last_membership_bytes);
+
+            // Atomic write of all changes
+            db.write(batch).map_err(|e| StorageError::write_snapshot(Some(meta_sig.clone()), &e))?;
+
+            db.flush_wal(true).map_err(|e| StorageError::<C>::write_snapshot(Some(meta_sig.clone()), &e))
+        })
+        .await
+        .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), &std::io::Error::other(e.to_string())))??;
+
+        // Write snapshot file with metadata for get_current_snapshot
+        let snapshot_file = SnapshotFile {
+            meta: meta.clone(),
+            data: snapshot_data_clone,
+        };
+        let file_bytes = snapshot_file
+            .encode()
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), AnyError::new(&e)))?;
+        let file_bytes = seal(self.encryptor.as_ref(), &file_bytes)
+            .map_err(|e| StorageError::write_snapshot(Some(meta.signature()), &e))?;
+
+        let snapshot_path = self.snapshot_dir.join(&meta.snapshot_id);
+        fs::write(&snapshot_path, &file_bytes).map_err(|e| StorageError::write_snapshot(Some(meta.signature()), &e))?;
+
+        Ok(())
+    }
+
+    async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>> {
+        // Find the latest snapshot file by comparing filenames lexicographically
+        let mut latest_snapshot_id: Option<String> = None;
+
+        for entry in fs::read_dir(&self.snapshot_dir).map_err(|e| StorageError::read_snapshot(None, &e))? {
+            let entry = entry.map_err(|e| StorageError::read_snapshot(None, &e))?;
+            let path = entry.path();
+
+            if !path.is_file() {
+                continue;
+            }
+
+            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                let snapshot_id = filename.to_string();
+
+                // Update latest if this is the first snapshot or if it's newer
+                if latest_snapshot_id.as_ref().is_none_or(|current| snapshot_id > *current) {
+                    latest_snapshot_id = Some(snapshot_id);
+                }
+            }
+        }
+
+        let Some(snapshot_id) = latest_snapshot_id else {
+            return Ok(None);
+        };
+
+        let snapshot_path = self.snapshot_dir.join(&snapshot_id);
+
+        // Read and deserialize snapshot file
+        let file_bytes = fs::read(&snapshot_path).map_err(|e| StorageError::read_snapshot(None, &e))?;
+        let file_bytes =
+            unseal(self.encryptor.as_ref(), &file_bytes).map_err(|e| StorageError::read_snapshot(None, &e))?;
+        let snapshot_file =
+            SnapshotFile::<C>::decode(&file_bytes).map_err(|e| StorageError::read_snapshot(None, AnyError::new(&e)))?;
+
+        // Serialize data for snapshot field
+        let data_bytes =
+            serialize::<C, _>(&snapshot_file.data).map_err(|e| StorageError::read_snapshot(None, AnyError::new(&e)))?;
+
+        Ok(Some(Snapshot {
+            meta: snapshot_file.meta,
+            snapshot: Cursor::new(data_bytes),
+        }))
+    }
+}
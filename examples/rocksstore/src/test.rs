@@ -1,20 +1,56 @@
+use std::io;
+use std::sync::Arc;
+
+use openraft::entry::RaftEntry;
+use openraft::storage::RaftLogStorage;
+use openraft::storage::RaftLogStorageExt;
+use openraft::storage::RaftStateMachine;
+use openraft::testing::blank_ent;
 use openraft::testing::log::StoreBuilder;
 use openraft::testing::log::Suite;
+use openraft::testing::log_id;
+use openraft::RaftLogReader;
+use openraft::RaftSnapshotBuilder;
+use openraft::SnapshotMeta;
 use openraft::StorageError;
+use openraft::Vote;
+use openraft_memstore::MemStateMachine;
+use rocksdb::Options;
+use rocksdb::DB;
 use tempfile::TempDir;
 
+use crate::encryption::Encryptor;
+use crate::encryption::KeyId;
+use crate::encryption::KeyRing;
 use crate::log_store::RocksLogStore;
-use crate::RocksStateMachine;
+use crate::log_store::CF_LOGS;
+use crate::log_store::CF_META;
+use crate::multi_raft::RocksMultiStore;
+use crate::read_only::RocksStore;
+use crate::state_machine::RocksStateMachine;
+use crate::state_machine::SnapshotFile;
+use crate::state_machine::CF_SM_DATA;
+use crate::state_machine::CF_SM_META;
 use crate::TypeConfig;
 
 struct RocksBuilder {}
 
-impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine, TempDir> for RocksBuilder {
-    async fn build(&self) -> Result<(TempDir, RocksLogStore<TypeConfig>, RocksStateMachine), StorageError<TypeConfig>> {
+impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>, TempDir> for RocksBuilder {
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>), StorageError<TypeConfig>> {
         let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
         let (log_store, sm) = crate::new(td.path()).await.map_err(|e| StorageError::read(&e))?;
         Ok((td, log_store, sm))
     }
+
+    async fn reopen(
+        &self,
+        td: &TempDir,
+    ) -> Result<Option<(RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>)>, StorageError<TypeConfig>> {
+        let (log_store, sm) = crate::new(td.path()).await.map_err(|e| StorageError::read(&e))?;
+        Ok(Some((log_store, sm)))
+    }
 }
 
 #[tokio::test]
@@ -22,3 +58,362 @@ pub async fn test_rocks_store() -> Result<(), StorageError<TypeConfig>> {
     Suite::test_all(RocksBuilder {}).await?;
     Ok(())
 }
+
+/// Build a `RocksStateMachine` paired with an in-memory log store.
+struct RocksStateMachineBuilder {}
+
+impl StoreBuilder<TypeConfig, mem_log::LogStore<TypeConfig>, RocksStateMachine<TypeConfig>, TempDir>
+    for RocksStateMachineBuilder
+{
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, mem_log::LogStore<TypeConfig>, RocksStateMachine<TypeConfig>), StorageError<TypeConfig>> {
+        let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+        let sm = RocksStateMachine::open(td.path()).await.map_err(|e| StorageError::read(&e))?;
+        Ok((td, mem_log::LogStore::default(), sm))
+    }
+}
+
+#[tokio::test]
+pub async fn test_rocks_state_machine() -> Result<(), StorageError<TypeConfig>> {
+    Suite::test_all(RocksStateMachineBuilder {}).await?;
+    Ok(())
+}
+
+/// Build a `RocksLogStore` paired with a state machine of another type config.
+struct RocksLogStoreBuilder {}
+
+type MemConfig = openraft_memstore::TypeConfig;
+
+impl StoreBuilder<MemConfig, RocksLogStore<MemConfig>, Arc<MemStateMachine>, TempDir> for RocksLogStoreBuilder {
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, RocksLogStore<MemConfig>, Arc<MemStateMachine>), StorageError<MemConfig>> {
+        let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+        let log_store = RocksLogStore::open(td.path()).map_err(|e| StorageError::read(&e))?;
+        let (_, sm) = openraft_memstore::new_mem_store();
+        Ok((td, log_store, sm))
+    }
+
+    async fn reopen(
+        &self,
+        td: &TempDir,
+    ) -> Result<Option<(RocksLogStore<MemConfig>, Arc<MemStateMachine>)>, StorageError<MemConfig>> {
+        let log_store = RocksLogStore::open(td.path()).map_err(|e| StorageError::read(&e))?;
+        let (_, sm) = openraft_memstore::new_mem_store();
+        Ok(Some((log_store, sm)))
+    }
+}
+
+#[tokio::test]
+pub async fn test_rocks_log_store() -> Result<(), StorageError<MemConfig>> {
+    Suite::test_all(RocksLogStoreBuilder {}).await?;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_open_read_only() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+
+    {
+        let (mut log_store, mut sm) =
+            crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+
+        log_store.save_vote(&Vote::new(2, 1)).await?;
+        log_store.blocking_append([blank_ent(1, 1, 1), blank_ent(1, 1, 2), blank_ent(2, 1, 3)]).await?;
+        log_store.purge(log_id(1, 1, 1)).await?;
+        sm.apply([blank_ent(1, 1, 1), blank_ent(1, 1, 2)]).await?;
+    }
+
+    let store = RocksStore::<TypeConfig>::open_read_only(td.path()).map_err(|e| StorageError::read(&e))?;
+
+    assert_eq!(Some(Vote::new(2, 1)), store.read_vote()?);
+    assert_eq!(Some(log_id(1, 1, 1)), store.read_last_purged_log_id()?);
+    assert_eq!(
+        vec![log_id(1, 1, 2), log_id(2, 1, 3)],
+        store.read_entries(..)?.iter().map(|e| e.log_id).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(log_id(1, 1, 2)), store.read_applied_state()?.0);
+    assert!(store.read_snapshot_metas()?.is_empty());
+
+    let mut out = Vec::new();
+    store.dump(&mut out).map_err(|e| StorageError::read(&e))?;
+    let out = String::from_utf8(out).unwrap();
+    let want = format!("last_purged_log_id: {}", log_id::<TypeConfig>(1, 1, 1));
+    assert!(out.contains(&want), "{}", out);
+
+    Ok(())
+}
+
+/// Log ids, votes and snapshot files written as JSON by an earlier version are still read.
+#[tokio::test]
+pub async fn test_read_json_encoded_values() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+
+    {
+        let (mut log_store, _sm) = crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+        log_store.save_vote(&Vote::new(2, 1)).await?;
+    }
+
+    {
+        let db = DB::open_cf(&Options::default(), td.path(), [
+            CF_META, CF_LOGS, CF_SM_META, CF_SM_DATA,
+        ])
+        .map_err(|e| StorageError::read(&e))?;
+        let put = |cf: &str, key: &str, value: Vec<u8>| db.put_cf(db.cf_handle(cf).unwrap(), key, value);
+
+        put(
+            CF_META,
+            "vote",
+            serde_json::to_vec(&Vote::<TypeConfig>::new(3, 1)).unwrap(),
+        )
+        .map_err(|e| StorageError::read(&e))?;
+        put(
+            CF_META,
+            "last_purged_log_id",
+            serde_json::to_vec(&log_id::<TypeConfig>(1, 1, 1)).unwrap(),
+        )
+        .map_err(|e| StorageError::read(&e))?;
+        put(
+            CF_SM_META,
+            "last_applied_log",
+            serde_json::to_vec(&log_id::<TypeConfig>(1, 1, 2)).unwrap(),
+        )
+        .map_err(|e| StorageError::read(&e))?;
+    }
+
+    let (mut log_store, mut sm) = crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+
+    assert_eq!(Some(Vote::new(3, 1)), log_store.read_vote().await?);
+    assert_eq!(
+        Some(log_id(1, 1, 1)),
+        log_store.get_log_state().await?.last_purged_log_id
+    );
+    assert_eq!(Some(log_id(1, 1, 2)), sm.applied_state().await?.0);
+
+    log_store.save_vote(&Vote::new(4, 1)).await?;
+    assert_eq!(Some(Vote::new(4, 1)), log_store.read_vote().await?);
+
+    // A JSON encoded snapshot file is read, and is written back with the new layout.
+    let meta = SnapshotMeta::<TypeConfig> {
+        last_log_id: Some(log_id(1, 1, 2)),
+        last_membership: Default::default(),
+        snapshot_id: "foo".to_string(),
+    };
+    let data = vec![(b"k".to_vec(), b"v".to_vec())];
+
+    let legacy = serde_json::to_vec(&serde_json::json!({ "meta": meta, "data": data })).unwrap();
+    let file = SnapshotFile::<TypeConfig>::decode(&legacy).map_err(|e| StorageError::read(&e))?;
+    assert_eq!(meta, file.meta);
+    assert_eq!(data, file.data);
+
+    let encoded = file.encode().map_err(|e| StorageError::read(&e))?;
+    assert_ne!(Some(&b'{'), encoded.first());
+    let file = SnapshotFile::<TypeConfig>::decode(&encoded).map_err(|e| StorageError::read(&e))?;
+    assert_eq!(meta, file.meta);
+    assert_eq!(data, file.data);
+
+    Ok(())
+}
+
+/// A toy encryptor for testing, which xor-s every byte with a key.
+#[derive(Debug)]
+struct XorEncryptor(u8);
+
+impl Encryptor for XorEncryptor {
+    fn encrypt(&self, _key_id: KeyId, plain: &[u8]) -> Result<Vec<u8>, io::Error> {
+        Ok(plain.iter().map(|b| b ^ self.0).collect())
+    }
+
+    fn decrypt(&self, key_id: KeyId, cipher: &[u8]) -> Result<Vec<u8>, io::Error> {
+        self.encrypt(key_id, cipher)
+    }
+}
+
+struct EncryptedRocksBuilder {}
+
+impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>, TempDir>
+    for EncryptedRocksBuilder
+{
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>), StorageError<TypeConfig>> {
+        let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+        let (log_store, sm) = crate::new(td.path()).await.map_err(|e| StorageError::read(&e))?;
+        let encryptor = Arc::new(XorEncryptor(0x5a));
+        Ok((
+            td,
+            log_store.with_encryptor(encryptor.clone()),
+            sm.with_encryptor(encryptor),
+        ))
+    }
+}
+
+#[tokio::test]
+pub async fn test_rocks_store_encrypted() -> Result<(), StorageError<TypeConfig>> {
+    Suite::test_all(EncryptedRocksBuilder {}).await?;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_encrypted_entries_are_not_plain() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+    let encryptor = Arc::new(XorEncryptor(0x5a));
+
+    {
+        let (log_store, _sm) = crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+        let mut log_store = log_store.with_encryptor(encryptor.clone());
+        log_store.blocking_append([blank_ent(1, 1, 1)]).await?;
+    }
+
+    let store = RocksStore::<TypeConfig>::open_read_only(td.path()).map_err(|e| StorageError::read(&e))?;
+    assert!(
+        store.read_entries(..).is_err(),
+        "entries can not be decoded without the encryptor"
+    );
+
+    let store = store.with_encryptor(encryptor);
+    assert_eq!(
+        vec![log_id(1, 1, 1)],
+        store.read_entries(..)?.iter().map(|e| e.log_id).collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
+/// Build the stores of one group in a DB that hosts other groups too.
+struct MultiRaftBuilder {}
+
+impl StoreBuilder<TypeConfig, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>, TempDir> for MultiRaftBuilder {
+    async fn build(
+        &self,
+    ) -> Result<(TempDir, RocksLogStore<TypeConfig>, RocksStateMachine<TypeConfig>), StorageError<TypeConfig>> {
+        let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+        let multi = RocksMultiStore::open(td.path()).map_err(|e| StorageError::read(&e))?;
+
+        // Neighbor groups with data must not affect the tested group.
+        for group_id in [1, 2, 3] {
+            multi.create_group(group_id).map_err(|e| StorageError::read(&e))?;
+        }
+        for group_id in [1, 3] {
+            let (mut log_store, _sm) =
+                multi.open_group::<TypeConfig>(group_id).await.map_err(|e| StorageError::read(&e))?;
+            log_store.save_vote(&Vote::new(5, 5)).await?;
+            log_store.blocking_append([blank_ent(5, 5, 1), blank_ent(5, 5, 2)]).await?;
+        }
+
+        let (log_store, sm) = multi.open_group(2).await.map_err(|e| StorageError::read(&e))?;
+        Ok((td, log_store, sm))
+    }
+}
+
+#[tokio::test]
+pub async fn test_multi_raft_group() -> Result<(), StorageError<TypeConfig>> {
+    Suite::test_all(MultiRaftBuilder {}).await?;
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_multi_raft_create_drop_group() -> Result<(), io::Error> {
+    let td = TempDir::new()?;
+    let multi = RocksMultiStore::open(td.path())?;
+
+    assert!(multi.open_group::<TypeConfig>(1).await.is_err());
+
+    multi.create_group(1)?;
+    multi.create_group(2)?;
+    assert_eq!(io::ErrorKind::AlreadyExists, multi.create_group(1).unwrap_err().kind());
+    assert_eq!(vec![1, 2], multi.list_groups()?);
+
+    for group_id in [1, 2] {
+        let (mut log_store, mut sm) = multi.open_group::<TypeConfig>(group_id).await?;
+        log_store.blocking_append([blank_ent(1, 1, 1)]).await.map_err(io::Error::other)?;
+        sm.apply([openraft::Entry::new_normal(log_id(1, 1, 1), crate::RocksRequest::Set {
+            key: "foo".to_string(),
+            value: format!("bar-{}", group_id),
+        })])
+        .await
+        .map_err(io::Error::other)?;
+    }
+
+    multi.drop_group(1)?;
+    assert_eq!(vec![2], multi.list_groups()?);
+    assert_eq!(io::ErrorKind::NotFound, multi.drop_group(1).unwrap_err().kind());
+
+    // A re-created group is empty
+    multi.create_group(1)?;
+    let (mut log_store, sm) = multi.open_group::<TypeConfig>(1).await?;
+    assert!(log_store.get_log_state().await.map_err(io::Error::other)?.last_log_id.is_none());
+    assert_eq!(None, sm.get("foo").map_err(io::Error::other)?);
+
+    // The other group is intact
+    let (mut log_store, sm) = multi.open_group::<TypeConfig>(2).await?;
+    assert_eq!(
+        Some(log_id(1, 1, 1)),
+        log_store.get_log_state().await.map_err(io::Error::other)?.last_log_id
+    );
+    assert_eq!(Some(b"bar-2".to_vec()), sm.get("foo").map_err(io::Error::other)?);
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_log_store_metrics() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+    let (mut log_store, _sm) = crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+
+    let m = log_store.metrics().map_err(|e| StorageError::read(&e))?;
+    assert_eq!(0, m.log_entries);
+
+    let names = m.column_families.iter().map(|cf| cf.name.as_str()).collect::<Vec<_>>();
+    for name in ["meta", "logs", "sm_meta", "sm_data"] {
+        assert!(names.contains(&name), "{} in {:?}", name, names);
+    }
+
+    log_store.blocking_append((1..=10).map(|i| blank_ent(1, 1, i))).await?;
+    log_store.purge(log_id(1, 1, 3)).await?;
+
+    let m = log_store.metrics().map_err(|e| StorageError::read(&e))?;
+    assert_eq!(7, m.log_entries);
+    assert!(m.disk_usage() > 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_key_rotation() -> Result<(), StorageError<TypeConfig>> {
+    let td = TempDir::new().map_err(|e| StorageError::read(&e))?;
+    let key_ring = Arc::new(KeyRing::new(1, Arc::new(XorEncryptor(0x5a))));
+
+    let (log_store, sm) = crate::new::<TypeConfig, _>(td.path()).await.map_err(|e| StorageError::read(&e))?;
+    let mut log_store = log_store.with_encryptor(key_ring.clone());
+    let mut sm = sm.with_encryptor(key_ring.clone());
+
+    log_store.blocking_append((1..=3).map(|i| blank_ent(1, 1, i))).await?;
+    sm.apply([blank_ent(1, 1, 1)]).await?;
+    sm.build_snapshot().await?;
+
+    key_ring.rotate(2, Arc::new(XorEncryptor(0x33)));
+    log_store.blocking_append([blank_ent(1, 1, 4)]).await?;
+
+    assert!(key_ring.remove_key(2).is_err(), "the current key can not be removed");
+
+    assert_eq!(3, log_store.reencrypt()?);
+    assert_eq!(1, sm.reencrypt_snapshots()?);
+
+    assert_eq!(0, log_store.reencrypt()?, "all entries are under the new key");
+    assert_eq!(0, sm.reencrypt_snapshots()?, "all snapshots are under the new key");
+
+    key_ring.remove_key(1).map_err(|e| StorageError::read(&e))?;
+
+    let entries = log_store.try_get_log_entries(..).await?;
+    assert_eq!(
+        vec![log_id(1, 1, 1), log_id(1, 1, 2), log_id(1, 1, 3), log_id(1, 1, 4)],
+        entries.iter().map(|e| e.log_id).collect::<Vec<_>>()
+    );
+
+    let snapshot = sm.get_current_snapshot().await?.unwrap();
+    assert_eq!(Some(log_id(1, 1, 1)), snapshot.meta.last_log_id);
+
+    Ok(())
+}
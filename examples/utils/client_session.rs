@@ -0,0 +1,153 @@
+//! Apply every write request of a client at most once, even if the client retries it.
+//!
+//! A client that does not receive the reply to a write, e.g., because of a timeout or a broken
+//! connection, can not tell whether the write is applied: retrying it may apply it twice. To make
+//! a retry safe, the state machine stores the response of the last request of every client
+//! session, and replies a retry with it instead of applying the request again:
+//!
+//! - The client tags every write with its session id and a sequence number, with
+//!   [`ClientSession::request()`], and retries a write with the same [`SessionRequest`];
+//! - The state machine applies a [`SessionRequest`] with [`SessionTable::apply()`].
+//!
+//! The [`SessionTable`] is part of the state machine: it is updated by applying the log, and is
+//! included in a snapshot. Thus a retry sent to a new leader, or to a node restored from a
+//! snapshot, receives the same response.
+//!
+//! A client sends the next request only after receiving the reply to the previous one, so that
+//! only the response of the last request is kept. The sessions are never removed in this module;
+//! a real application should remove the idle ones deterministically, e.g., by a timestamp the
+//! leader writes in the log, so that every node removes the same sessions.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A write request `request`, sent by client `client` with sequence number `seq`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionRequest<D> {
+    pub client: String,
+    pub seq: u64,
+    pub request: D,
+}
+
+impl<D: fmt::Display> fmt::Display for SessionRequest<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}: {}", self.client, self.seq, self.request)
+    }
+}
+
+/// The client side of a session: assigns increasing sequence numbers to the requests of a client.
+#[derive(Debug, Clone)]
+pub struct ClientSession {
+    client: String,
+    last_seq: u64,
+}
+
+impl ClientSession {
+    /// Create a session with an id unique among all clients of the cluster.
+    pub fn new(client: impl ToString) -> Self {
+        Self {
+            client: client.to_string(),
+            last_seq: 0,
+        }
+    }
+
+    /// Tag `request` with the next sequence number of this session.
+    ///
+    /// Retry the returned request as it is, until receiving a reply.
+    pub fn request<D>(&mut self, request: D) -> SessionRequest<D> {
+        self.last_seq += 1;
+        SessionRequest {
+            client: self.client.clone(),
+            seq: self.last_seq,
+            request,
+        }
+    }
+}
+
+/// The error when a request can not be applied at most once.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    /// The request is older than the last request of the session, whose response is the only one
+    /// kept. The client must have received the reply to this request already.
+    Stale { client: String, seq: u64, last_seq: u64 },
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionError::Stale { client, seq, last_seq } => {
+                write!(f, "stale request {}/{}, the last request is {}", client, seq, last_seq)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// The response of the last request applied for a client.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LastApplied<R> {
+    seq: u64,
+    response: R,
+}
+
+/// The state machine side of the sessions: the response of the last request of every client.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionTable<R> {
+    sessions: BTreeMap<String, LastApplied<R>>,
+}
+
+impl<R> Default for SessionTable<R> {
+    fn default() -> Self {
+        Self {
+            sessions: BTreeMap::new(),
+        }
+    }
+}
+
+impl<R: Clone> SessionTable<R> {
+    /// Apply `req` with `apply`, unless it is already applied.
+    ///
+    /// - A new request, with a sequence number greater than the last one of its session, is
+    ///   applied, and its response is stored;
+    /// - A retry of the last request is not applied again, the stored response is returned;
+    /// - An older request is rejected with [`SessionError::Stale`].
+    pub fn apply<D>(&mut self, req: &SessionRequest<D>, apply: impl FnOnce(&D) -> R) -> Result<R, SessionError> {
+        if let Some(last) = self.sessions.get(&req.client) {
+            if req.seq == last.seq {
+                return Ok(last.response.clone());
+            }
+            if req.seq < last.seq {
+                return Err(SessionError::Stale {
+                    client: req.client.clone(),
+                    seq: req.seq,
+                    last_seq: last.seq,
+                });
+            }
+        }
+
+        let response = apply(&req.request);
+        self.sessions.insert(req.client.clone(), LastApplied {
+            seq: req.seq,
+            response: response.clone(),
+        });
+        Ok(response)
+    }
+
+    /// The sequence number of the last request applied for `client`.
+    pub fn last_seq(&self, client: &str) -> Option<u64> {
+        self.sessions.get(client).map(|last| last.seq)
+    }
+
+    /// The number of client sessions.
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+}
@@ -0,0 +1,183 @@
+use proc_macro2::Ident;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Fields;
+use syn::Path;
+use syn::Type;
+use syn::Variant;
+
+/// The name of the helper attribute of `#[derive(ApplyCommand)]`.
+const ATTR: &str = "apply";
+
+/// Generate the `apply()` method for a command enum.
+pub(crate) fn derive_apply_command(input: DeriveInput) -> Result<TokenStream, syn::Error> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "ApplyCommand can only be derived for enums",
+        ));
+    };
+
+    let args = EnumArgs::parse(&input.attrs, &input.ident)?;
+    let state = &args.state;
+    let response = &args.response;
+
+    let mut arms = Vec::with_capacity(data.variants.len());
+    for variant in data.variants.iter() {
+        arms.push(apply_arm(variant, response)?);
+    }
+
+    // A reference to an empty enum is not uninhabited, match the value instead.
+    let scrutinee = if arms.is_empty() { quote!(*self) } else { quote!(self) };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Apply this command to the state machine `state` and return the response.
+            ///
+            /// Generated by `#[derive(ApplyCommand)]`.
+            #[allow(clippy::useless_conversion)]
+            pub fn apply(&self, state: &mut #state) -> #response {
+                match #scrutinee {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Build the match arm that applies one variant.
+///
+/// The fields are passed by reference, in the order they are declared, to the handler:
+/// `handler(state, field_0, field_1, ...)` if `with` is specified, otherwise to the method of the
+/// state named after the variant in snake case: `state.variant_name(field_0, field_1, ...)`.
+fn apply_arm(variant: &Variant, response: &Type) -> Result<TokenStream, syn::Error> {
+    let args = VariantArgs::parse(&variant.attrs)?;
+    let ident = &variant.ident;
+
+    let (pattern, fields) = match &variant.fields {
+        Fields::Named(named) => {
+            let fields = named.named.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+            (quote!(Self::#ident { #(#fields),* }), fields)
+        }
+        Fields::Unnamed(unnamed) => {
+            let fields = (0..unnamed.unnamed.len()).map(|i| format_ident!("field_{}", i)).collect::<Vec<_>>();
+            (quote!(Self::#ident ( #(#fields),* )), fields)
+        }
+        Fields::Unit => (quote!(Self::#ident), vec![]),
+    };
+
+    let call = match &args.with {
+        Some(handler) => quote!(#handler(state, #(#fields),*)),
+        None => {
+            let method = Ident::new(&snake_case(&ident.to_string()), ident.span());
+            quote!(state.#method(#(#fields),*))
+        }
+    };
+
+    let output = match &args.map {
+        Some(map) => quote!(#map(#call)),
+        None => quote!(<_ as ::core::convert::Into<#response>>::into(#call)),
+    };
+
+    Ok(quote! {
+        #pattern => #output,
+    })
+}
+
+/// The arguments of `#[apply(...)]` on the enum.
+struct EnumArgs {
+    /// The type the commands are applied to.
+    state: Type,
+
+    /// The type every command returns.
+    response: Type,
+}
+
+impl EnumArgs {
+    fn parse(attrs: &[Attribute], ident: &Ident) -> Result<Self, syn::Error> {
+        let mut state = None;
+        let mut response = None;
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident(ATTR)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("state") {
+                    state = Some(meta.value()?.parse::<Type>()?);
+                } else if meta.path.is_ident("response") {
+                    response = Some(meta.value()?.parse::<Type>()?);
+                } else {
+                    return Err(meta.error("Unknown attribute; expected one of: `state`, `response`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let missing = |name: &str| {
+            syn::Error::new(
+                ident.span(),
+                format!("Missing `{}` attribute; add `#[apply(state = S, response = R)]`", name),
+            )
+        };
+
+        Ok(Self {
+            state: state.ok_or_else(|| missing("state"))?,
+            response: response.ok_or_else(|| missing("response"))?,
+        })
+    }
+}
+
+/// The arguments of `#[apply(...)]` on a variant.
+#[derive(Default)]
+struct VariantArgs {
+    /// The function that applies the variant, instead of the method of the state.
+    with: Option<Path>,
+
+    /// The function that converts the output of the handler into the response, instead of `Into`.
+    map: Option<Path>,
+}
+
+impl VariantArgs {
+    fn parse(attrs: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut args = Self::default();
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident(ATTR)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("with") {
+                    args.with = Some(meta.value()?.parse::<Path>()?);
+                } else if meta.path.is_ident("map") {
+                    args.map = Some(meta.value()?.parse::<Path>()?);
+                } else {
+                    return Err(meta.error("Unknown attribute; expected one of: `with`, `map`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(args)
+    }
+}
+
+/// Convert a `CamelCase` variant name to `snake_case`, e.g., `SetTTL` to `set_ttl`.
+fn snake_case(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut res = String::with_capacity(s.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower) {
+                res.push('_');
+            }
+        }
+        res.extend(c.to_lowercase());
+    }
+
+    res
+}
@@ -1,6 +1,7 @@
 #![doc = include_str!("lib_readme.md")]
 #![allow(clippy::uninlined_format_args)]
 
+mod apply;
 mod expand;
 mod since;
 pub(crate) mod utils;
@@ -8,6 +9,7 @@ pub(crate) mod utils;
 use proc_macro::TokenStream;
 use quote::quote;
 use since::Since;
+use syn::DeriveInput;
 use syn::Item;
 use syn::ReturnType;
 use syn::TraitItem;
@@ -220,3 +222,90 @@ pub fn expand(item: TokenStream) -> TokenStream {
     let repeat = parse_macro_input!(item as expand::Expand);
     repeat.render().into()
 }
+
+/// Derive the `apply()` method that dispatches a command to the state machine.
+///
+/// An application usually defines its commands as an enum, and applies a command with a `match`
+/// whose arms call a function and build the response. `#[derive(ApplyCommand)]` generates this
+/// `match`, as an inherent method:
+///
+/// ```ignore
+/// pub fn apply(&self, state: &mut S) -> R;
+/// ```
+///
+/// - `#[apply(state = S, response = R)]` on the enum is required: `S` is the type the commands are
+///   applied to, and `R` is the response of every command.
+/// - A variant is applied by the method of `S` named after the variant in snake case, e.g., `SetTtl
+///   { key, ttl }` calls `state.set_ttl(key, ttl)`. The fields are passed by reference, in the
+///   order they are declared.
+/// - `#[apply(with = path)]` on a variant calls `path(state, field_0, field_1, ...)` instead.
+/// - The output of the call is converted into `R` with `Into`, or with `#[apply(map = path)]` on a
+///   variant, with `path(output)`, e.g., `map = Response::Value` to wrap it in a variant of `R`.
+///
+/// The derive does not add `serde` support: derive `Serialize` and `Deserialize` for the enum as
+/// usual, so that it can be used as the [`AppData`] of a Raft.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use openraft_macros::ApplyCommand;
+///
+/// #[derive(Default)]
+/// struct Data {
+///     kv: BTreeMap<String, String>,
+/// }
+///
+/// impl Data {
+///     fn set(&mut self, key: &str, value: &str) -> Option<String> {
+///         self.kv.insert(key.to_string(), value.to_string())
+///     }
+/// }
+///
+/// fn delete(data: &mut Data, key: &str) -> Option<String> {
+///     data.kv.remove(key)
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Response {
+///     Prev(Option<String>),
+///     Len(usize),
+/// }
+///
+/// #[derive(ApplyCommand)]
+/// #[apply(state = Data, response = Response)]
+/// enum Command {
+///     #[apply(map = Response::Prev)]
+///     Set { key: String, value: String },
+///
+///     #[apply(with = delete, map = Response::Prev)]
+///     Delete { key: String },
+///
+///     #[apply(with = len)]
+///     Len,
+/// }
+///
+/// fn len(data: &mut Data) -> Response {
+///     Response::Len(data.kv.len())
+/// }
+///
+/// let mut data = Data::default();
+///
+/// let set = Command::Set { key: "a".to_string(), value: "1".to_string() };
+/// assert_eq!(Response::Prev(None), set.apply(&mut data));
+/// assert_eq!(Response::Len(1), Command::Len.apply(&mut data));
+///
+/// let delete = Command::Delete { key: "a".to_string() };
+/// assert_eq!(Response::Prev(Some("1".to_string())), delete.apply(&mut data));
+/// ```
+///
+/// [`AppData`]: https://docs.rs/openraft/latest/openraft/trait.AppData.html
+#[proc_macro_derive(ApplyCommand, attributes(apply))]
+pub fn derive_apply_command(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    match apply::derive_apply_command(input) {
+        Ok(x) => x.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
@@ -0,0 +1,25 @@
+#[apply(state = Vec<T>, response = Option<T>)]
+enum Command<T>
+where
+    T: Clone,
+{
+    Push(T),
+    Pop,
+}
+impl<T> Command<T>
+where
+    T: Clone,
+{
+    /// Apply this command to the state machine `state` and return the response.
+    ///
+    /// Generated by `#[derive(ApplyCommand)]`.
+    #[allow(clippy::useless_conversion)]
+    pub fn apply(&self, state: &mut Vec<T>) -> Option<T> {
+        match self {
+            Self::Push(field_0) => {
+                <_ as ::core::convert::Into<Option<T>>>::into(state.push(field_0))
+            }
+            Self::Pop => <_ as ::core::convert::Into<Option<T>>>::into(state.pop()),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Vec<T>, response = Option<T>)]
+enum Command<T>
+where T: Clone
+{
+    Push(T),
+    Pop,
+}
@@ -0,0 +1,23 @@
+#[apply(state = Data, response = Response)]
+enum Command {
+    Set { key: String, value: String },
+    SetTTL(String, u64),
+    Clear,
+}
+impl Command {
+    /// Apply this command to the state machine `state` and return the response.
+    ///
+    /// Generated by `#[derive(ApplyCommand)]`.
+    #[allow(clippy::useless_conversion)]
+    pub fn apply(&self, state: &mut Data) -> Response {
+        match self {
+            Self::Set { key, value } => {
+                <_ as ::core::convert::Into<Response>>::into(state.set(key, value))
+            }
+            Self::SetTTL(field_0, field_1) => {
+                <_ as ::core::convert::Into<Response>>::into(state.set_ttl(field_0, field_1))
+            }
+            Self::Clear => <_ as ::core::convert::Into<Response>>::into(state.clear()),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Data, response = Response)]
+enum Command {
+    Set { key: String, value: String },
+    SetTTL(String, u64),
+    Clear,
+}
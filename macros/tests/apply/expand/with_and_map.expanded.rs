@@ -0,0 +1,24 @@
+#[apply(state = Data, response = Response)]
+enum Command {
+    #[apply(with = set, map = Response::Prev)]
+    Set { key: String, value: String },
+    #[apply(with = store::delete)]
+    Delete(String),
+    #[apply(map = Response::Len)]
+    Len,
+}
+impl Command {
+    /// Apply this command to the state machine `state` and return the response.
+    ///
+    /// Generated by `#[derive(ApplyCommand)]`.
+    #[allow(clippy::useless_conversion)]
+    pub fn apply(&self, state: &mut Data) -> Response {
+        match self {
+            Self::Set { key, value } => Response::Prev(set(state, key, value)),
+            Self::Delete(field_0) => {
+                <_ as ::core::convert::Into<Response>>::into(store::delete(state, field_0))
+            }
+            Self::Len => Response::Len(state.len()),
+        }
+    }
+}
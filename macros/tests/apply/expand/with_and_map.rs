@@ -0,0 +1,12 @@
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Data, response = Response)]
+enum Command {
+    #[apply(with = set, map = Response::Prev)]
+    Set { key: String, value: String },
+
+    #[apply(with = store::delete)]
+    Delete(String),
+
+    #[apply(map = Response::Len)]
+    Len,
+}
@@ -0,0 +1,7 @@
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Vec<u64>)]
+enum Command {
+    Push(u64),
+}
+
+fn main() {}
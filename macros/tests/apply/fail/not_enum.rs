@@ -0,0 +1,7 @@
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Vec<u64>, response = ())]
+struct Command {
+    value: u64,
+}
+
+fn main() {}
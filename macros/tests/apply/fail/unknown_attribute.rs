@@ -0,0 +1,10 @@
+struct Data;
+
+#[derive(openraft_macros::ApplyCommand)]
+#[apply(state = Data, response = ())]
+enum Command {
+    #[apply(handler = push)]
+    Push(u64),
+}
+
+fn main() {}
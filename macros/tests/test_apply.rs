@@ -0,0 +1,10 @@
+#[test]
+fn fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/apply/fail/*.rs");
+}
+
+#[test]
+fn pass() {
+    macrotest::expand("tests/apply/expand/*.rs");
+}
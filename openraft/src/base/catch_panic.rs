@@ -0,0 +1,57 @@
+//! Catch a panic in an internal task and turn it into a [`TaskPanicked`] error.
+
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+
+use futures::FutureExt;
+
+use crate::error::TaskPanicked;
+
+/// Run `fut` to completion and return a [`TaskPanicked`] error instead of unwinding if it panics.
+///
+/// `task` names the task in the returned error.
+pub(crate) async fn catch_panic<F>(task: impl ToString, fut: F) -> Result<F::Output, TaskPanicked>
+where F: Future {
+    AssertUnwindSafe(fut)
+        .catch_unwind()
+        .await
+        .map_err(|payload| TaskPanicked::new(task, panic_message(payload.as_ref())))
+}
+
+/// Extract a human readable message from a panic payload.
+///
+/// `panic!()` produces a `&'static str` or a `String` payload; any other payload is reported as a
+/// placeholder.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::catch_panic;
+    use crate::error::TaskPanicked;
+
+    #[test]
+    fn test_catch_panic() {
+        let res = block_on(catch_panic("foo", async { 3 }));
+        assert_eq!(Ok(3), res);
+
+        let res = block_on(catch_panic("foo", async { panic!("static message") }));
+        assert_eq!(Err(TaskPanicked::new("foo", "static message")), res);
+
+        let res: Result<(), _> = block_on(catch_panic("bar", async { panic!("formatted {}", 5) }));
+        assert_eq!(Err(TaskPanicked::new("bar", "formatted 5")), res);
+
+        let res: Result<(), _> = block_on(catch_panic("baz", async { std::panic::panic_any(5u64) }));
+        assert_eq!(Err(TaskPanicked::new("baz", "<non-string panic payload>")), res);
+    }
+}
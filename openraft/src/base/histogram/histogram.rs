@@ -83,8 +83,13 @@ impl Histogram {
 
     /// Records a value to the histogram.
     pub(crate) fn record(&mut self, value: u64) {
+        self.record_n(value, 1);
+    }
+
+    /// Records a value `n` times to the histogram.
+    pub(crate) fn record_n(&mut self, value: u64, n: u64) {
         let bucket_index = Self::calculate_bucket(value);
-        self.buckets[bucket_index] += 1;
+        self.buckets[bucket_index] += n;
     }
 
     /// Calculates the bucket index for a given value using logarithmic bucketing.
@@ -111,7 +116,6 @@ impl Histogram {
     }
 
     /// Returns the total number of values recorded.
-    #[allow(dead_code)]
     pub(crate) fn total(&self) -> u64 {
         self.buckets.iter().sum()
     }
@@ -132,7 +136,6 @@ impl Histogram {
     ///
     /// This is used internally when calculating multiple percentiles to avoid
     /// recalculating the total multiple times.
-    #[allow(dead_code)]
     fn percentile_with_total(&self, p: f64, total: u64) -> u64 {
         let target = (total as f64 * p).ceil().max(1.0) as u64;
         let mut cumulative = 0u64;
@@ -148,7 +151,6 @@ impl Histogram {
     }
 
     /// Returns common percentile statistics: P50, P90, P99.
-    #[allow(dead_code)]
     pub(crate) fn percentile_stats(&self) -> PercentileStats {
         let total = self.total();
         PercentileStats {
@@ -241,6 +243,18 @@ mod tests {
         assert_eq!(hist.get_bucket(8), 3);
     }
 
+    #[test]
+    fn test_record_n() {
+        let mut hist = Histogram::new();
+
+        hist.record_n(8, 3);
+        hist.record_n(100, 0);
+
+        assert_eq!(hist.total(), 3);
+        assert_eq!(hist.get_bucket(8), 3);
+        assert_eq!(hist.get_bucket(Histogram::calculate_bucket(100)), 0);
+    }
+
     #[test]
     fn test_u64_max_coverage() {
         let max_bucket = Histogram::calculate_bucket(u64::MAX);
@@ -3,4 +3,4 @@ mod histogram;
 mod percentile_stats;
 
 pub(crate) use histogram::Histogram;
-pub(crate) use percentile_stats::PercentileStats;
+pub use percentile_stats::PercentileStats;
@@ -1,14 +1,15 @@
 use std::fmt;
 
 /// Percentile statistics for a histogram.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct PercentileStats {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PercentileStats {
     /// 50th percentile (median)
-    pub(crate) p50: u64,
+    pub p50: u64,
     /// 90th percentile
-    pub(crate) p90: u64,
+    pub p90: u64,
     /// 99th percentile
-    pub(crate) p99: u64,
+    pub p99: u64,
 }
 
 impl fmt::Display for PercentileStats {
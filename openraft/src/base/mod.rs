@@ -27,6 +27,7 @@
 //! Applications rarely need to use these types directly - they're used internally
 //! to make Openraft flexible across different environments.
 
+pub(crate) mod catch_panic;
 pub(crate) mod finalized;
 pub(crate) mod histogram;
 
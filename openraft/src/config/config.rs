@@ -15,6 +15,7 @@ use crate::LogId;
 use crate::LogIdOptionExt;
 use crate::RaftTypeConfig;
 use crate::config::error::ConfigError;
+use crate::config::log_levels::LogLevels;
 use crate::raft_state::LogStateReader;
 
 /// Log compaction and snapshot policy.
@@ -63,6 +64,168 @@ impl SnapshotPolicy {
     }
 }
 
+/// How a node treats the AppendEntries and Vote RPCs sent by a node that is not in its effective
+/// membership.
+///
+/// Such RPCs are usually sent by a node removed from the cluster that does not know it yet, or by
+/// a leader of a membership this node has not seen, e.g., when a node is restored from an old
+/// backup or migrated to another cluster.
+///
+/// The policy applies only when this node is a member itself: a node that is not initialized, or
+/// is being added to the cluster, accepts RPCs from any node.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NonMemberRpcPolicy {
+    /// Reject every RPC from a non-member with
+    /// [`Fatal::NonMemberRejected`](crate::error::Fatal::NonMemberRejected).
+    Reject,
+
+    /// Handle an RPC from a non-member only if it does not change the vote of this node.
+    ///
+    /// A vote request is answered with the current vote of this node, but never granted. An
+    /// AppendEntries is handled only if it is sent by the leader this node already follows;
+    /// otherwise it is rejected as with [`Self::Reject`].
+    AcceptReadOnly,
+
+    /// Handle RPCs from non-members the same way as those from members. This is the default.
+    #[default]
+    Accept,
+}
+
+/// When a newly elected leader appends a blank log.
+///
+/// A leader commits the logs of previous terms only by committing a log of its own term, and it
+/// serves linearizable reads only after it has committed one. The blank log lets it do both right
+/// after the election, without waiting for a client write. The state machine applies it as
+/// [`EntryPayload::Blank`](crate::EntryPayload::Blank).
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum BlankEntryPolicy {
+    /// Always append a blank log. This is the default.
+    #[default]
+    Always,
+
+    /// Append a blank log only if the leader has logs that are not known to be committed.
+    ///
+    /// A leader whose logs are all committed serves linearizable reads at once, without
+    /// appending a log.
+    IfUncommitted,
+
+    /// Never append a blank log.
+    ///
+    /// If the leader has logs that are not known to be committed, they are not committed, and
+    /// linearizable reads are blocked, until the first log the leader proposes is committed. A
+    /// warning is logged when a leader is elected in this situation.
+    Never,
+}
+
+/// What a node does with the client writes it proposed as the leader but that are not yet
+/// committed, when it is no longer the leader.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PendingWritePolicy {
+    /// Keep waiting: a write is responded when its entry is applied, or with
+    /// [`ForwardToLeader`](crate::error::ForwardToLeader) when the new leader truncates it. This
+    /// is the default.
+    #[default]
+    Wait,
+
+    /// Respond at once with [`LeaderChanged`](crate::error::LeaderChanged), which tells whether the
+    /// entry is persisted locally and who the new leader is, so that a client can decide whether
+    /// to retry the write on the new leader.
+    Fail,
+}
+
+/// How [`Raft::read()`] serves a [`ReadGuarantee::Stale`] read on a node that has not discovered
+/// the leader since it started.
+///
+/// A restarted node does not know whether its state machine is far behind the cluster until it
+/// hears from the leader, or becomes the leader itself. Until then, a stale read may return data
+/// that is much older than the application expects.
+///
+/// Linearizable reads are not affected: they are served only by the leader.
+///
+/// [`Raft::read()`]: crate::Raft::read
+/// [`ReadGuarantee::Stale`]: crate::raft::ReadGuarantee::Stale
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ColdStartReadPolicy {
+    /// Serve stale reads with the state this node has applied. This is the default.
+    #[default]
+    ServeStale,
+
+    /// Refuse stale reads with [`ForwardToLeader`](crate::error::ForwardToLeader) until the
+    /// leader is discovered.
+    Refuse,
+
+    /// Wait up to the given number of milliseconds for the leader to be discovered, then serve
+    /// the read; refuse it as with [`Self::Refuse`] if the leader is not discovered in time.
+    WaitForLeader(u64),
+}
+
+/// Which of the logs included in the snapshot are purged.
+///
+/// Logs that are not included in a snapshot are never purged. A follower that needs a purged log
+/// is replicated with a snapshot, which is much more expensive than sending it the logs.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PurgePolicy {
+    /// Keep the last [`max_in_snapshot_log_to_keep`](Config::max_in_snapshot_log_to_keep) logs
+    /// included in the snapshot, and purge the others. This is the default.
+    #[default]
+    KeepInSnapshot,
+
+    /// In addition to [`Self::KeepInSnapshot`], a leader keeps the logs that its followers and
+    /// learners lag behind, plus `margin` more logs, so that a briefly slow member is still
+    /// replicated with logs instead of a snapshot after a purge.
+    ///
+    /// In other words, at least `max(lag) + margin` logs are kept, where `lag` is the number of
+    /// logs a member has not yet replicated. A member lagging more than `max_lag` logs, e.g., one
+    /// that is down, is not taken into account, so that it does not stop the log from being
+    /// purged. The logs it needs may be purged, and it is replicated with a snapshot.
+    ///
+    /// A follower does not know the lag of the other members; it purges the logs as with
+    /// [`Self::KeepInSnapshot`].
+    KeepForLagging {
+        /// The number of logs to keep in addition to the ones the most lagging member needs.
+        margin: u64,
+
+        /// Members lagging more than this number of logs are not taken into account.
+        max_lag: u64,
+    },
+}
+
+/// The unit of a timer value in [`Config`], such as
+/// [`heartbeat_interval`](Config::heartbeat_interval).
+///
+/// Co-located low-latency clusters can use sub-millisecond timers with [`Self::Micros`].
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TimerUnit {
+    /// The value is in milliseconds. This is the default.
+    #[default]
+    Millis,
+
+    /// The value is in microseconds.
+    Micros,
+}
+
+impl TimerUnit {
+    /// Convert a timer value in this unit to a [`Duration`].
+    pub fn to_duration(&self, value: u64) -> Duration {
+        match self {
+            TimerUnit::Millis => Duration::from_millis(value),
+            TimerUnit::Micros => Duration::from_micros(value),
+        }
+    }
+}
+
 /// Parse number with unit such as 5.3 KB
 fn parse_bytes_with_unit(src: &str) -> Result<u64, ConfigError> {
     let res = byte_unit::Byte::from_str(src).map_err(|e| ConfigError::InvalidNumber {
@@ -100,6 +263,103 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
     Ok(SnapshotPolicy::LogsSinceLast(n_logs))
 }
 
+fn parse_non_member_rpc_policy(src: &str) -> Result<NonMemberRpcPolicy, ConfigError> {
+    match src {
+        "reject" => Ok(NonMemberRpcPolicy::Reject),
+        "accept-read-only" => Ok(NonMemberRpcPolicy::AcceptReadOnly),
+        "accept" => Ok(NonMemberRpcPolicy::Accept),
+        _ => Err(ConfigError::InvalidNonMemberRpcPolicy {
+            syntax: "reject|accept-read-only|accept".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
+fn parse_blank_entry_policy(src: &str) -> Result<BlankEntryPolicy, ConfigError> {
+    match src {
+        "always" => Ok(BlankEntryPolicy::Always),
+        "if-uncommitted" => Ok(BlankEntryPolicy::IfUncommitted),
+        "never" => Ok(BlankEntryPolicy::Never),
+        _ => Err(ConfigError::InvalidBlankEntryPolicy {
+            syntax: "always|if-uncommitted|never".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
+fn parse_pending_write_policy(src: &str) -> Result<PendingWritePolicy, ConfigError> {
+    match src {
+        "wait" => Ok(PendingWritePolicy::Wait),
+        "fail" => Ok(PendingWritePolicy::Fail),
+        _ => Err(ConfigError::InvalidPendingWritePolicy {
+            syntax: "wait|fail".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
+fn parse_timer_unit(src: &str) -> Result<TimerUnit, ConfigError> {
+    match src {
+        "ms" => Ok(TimerUnit::Millis),
+        "us" => Ok(TimerUnit::Micros),
+        _ => Err(ConfigError::InvalidTimerUnit {
+            syntax: "ms|us".to_string(),
+            invalid: src.to_string(),
+        }),
+    }
+}
+
+fn parse_cold_start_read_policy(src: &str) -> Result<ColdStartReadPolicy, ConfigError> {
+    let syntax = "serve-stale|refuse|wait-for-leader:<ms>";
+
+    match src {
+        "serve-stale" => return Ok(ColdStartReadPolicy::ServeStale),
+        "refuse" => return Ok(ColdStartReadPolicy::Refuse),
+        _ => {}
+    }
+
+    let Some(ms) = src.strip_prefix("wait-for-leader:") else {
+        return Err(ConfigError::InvalidColdStartReadPolicy {
+            syntax: syntax.to_string(),
+            invalid: src.to_string(),
+        });
+    };
+
+    let ms = ms.parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
+        invalid: src.to_string(),
+        reason: e.to_string(),
+    })?;
+    Ok(ColdStartReadPolicy::WaitForLeader(ms))
+}
+
+fn parse_purge_policy(src: &str) -> Result<PurgePolicy, ConfigError> {
+    let syntax = "keep-in-snapshot|keep-for-lagging:<margin>:<max_lag>";
+
+    if src == "keep-in-snapshot" {
+        return Ok(PurgePolicy::KeepInSnapshot);
+    }
+
+    let elts = src.split(':').collect::<Vec<_>>();
+    if elts.len() != 3 || elts[0] != "keep-for-lagging" {
+        return Err(ConfigError::InvalidPurgePolicy {
+            syntax: syntax.to_string(),
+            invalid: src.to_string(),
+        });
+    }
+
+    let parse = |n: &str| {
+        n.parse::<u64>().map_err(|e| ConfigError::InvalidNumber {
+            invalid: src.to_string(),
+            reason: e.to_string(),
+        })
+    };
+
+    Ok(PurgePolicy::KeepForLagging {
+        margin: parse(elts[1])?,
+        max_lag: parse(elts[2])?,
+    })
+}
+
 /// Runtime configuration for a Raft node.
 ///
 /// `Config` controls tunable parameters for Raft operation including election timeouts, heartbeat
@@ -132,6 +392,10 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
 /// **Rule of thumb**: Set `heartbeat_interval ≈ election_timeout / 3` and ensure election timeout
 /// is 10-20× your typical network round-trip time.
 ///
+/// Timers are in milliseconds by default. A co-located low-latency cluster can set
+/// [`heartbeat_interval_unit`](Self::heartbeat_interval_unit) and
+/// [`election_timeout_unit`](Self::election_timeout_unit) to microseconds.
+///
 /// # See Also
 ///
 /// - [Raft specification §5.6](https://raft.github.io/raft.pdf) for timing guidance
@@ -142,21 +406,62 @@ fn parse_snapshot_policy(src: &str) -> Result<SnapshotPolicy, ConfigError> {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Config {
     /// The application-specific name of this Raft cluster
+    ///
+    /// Every RPC sent by this node carries it, and an RPC from a cluster with another name is
+    /// rejected with [`Fatal::ClusterMismatch`], so that two clusters that reach each other because
+    /// of a misconfigured address do not mix up their logs and votes. Give every cluster a
+    /// distinct name for the check to take effect.
+    ///
+    /// [`Fatal::ClusterMismatch`]: crate::error::Fatal::ClusterMismatch
     #[clap(long, default_value = "foo")]
     pub cluster_name: String,
 
-    /// The minimum election timeout in milliseconds
+    /// The version of the application software running on this node.
+    ///
+    /// It is advertised to other nodes in [`NodeInfo`](crate::raft::NodeInfo), along with the
+    /// version of Openraft, and reported in
+    /// [`RaftMetrics::node_infos`](crate::RaftMetrics::node_infos).
+    #[clap(long, default_value = "")]
+    pub app_version: String,
+
+    /// Application capabilities this node advertises to other nodes, e.g., a new data format it
+    /// can read.
+    ///
+    /// They are advertised in [`NodeInfo::capabilities`](crate::raft::NodeInfo::capabilities),
+    /// along with the capabilities of Openraft. Give them a prefix that does not collide with
+    /// `openraft:`.
+    #[clap(long, value_delimiter = ',')]
+    pub capabilities: Vec<String>,
+
+    /// The minimum election timeout, in [`election_timeout_unit`](Self::election_timeout_unit)
     #[clap(long, default_value = "150")]
     pub election_timeout_min: u64,
 
-    /// The maximum election timeout in milliseconds
+    /// The maximum election timeout, in [`election_timeout_unit`](Self::election_timeout_unit)
     #[clap(long, default_value = "300")]
     pub election_timeout_max: u64,
 
-    /// The heartbeat interval in milliseconds at which leaders will send heartbeats to followers
+    /// The unit of `election_timeout_min` and `election_timeout_max`: `ms` or `us`.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "ms", value_parser = parse_timer_unit)]
+    pub election_timeout_unit: TimerUnit,
+
+    /// The heartbeat interval at which leaders will send heartbeats to followers, in
+    /// [`heartbeat_interval_unit`](Self::heartbeat_interval_unit)
     #[clap(long, default_value = "50")]
     pub heartbeat_interval: u64,
 
+    /// The unit of `heartbeat_interval`: `ms` or `us`.
+    ///
+    /// It can differ from `election_timeout_unit`, e.g., to send a heartbeat every 200
+    /// microseconds with an election timeout of a few milliseconds. The timers are compared in
+    /// the same unit when the config is validated.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "ms", value_parser = parse_timer_unit)]
+    pub heartbeat_interval_unit: TimerUnit,
+
     /// The timeout for sending then installing the last snapshot segment,
     /// in millisecond. It is also used as the timeout for sending a non-last segment if
     /// `send_snapshot_timeout` is 0.
@@ -181,6 +486,97 @@ pub struct Config {
     #[clap(long, default_value = "300")]
     pub max_payload_entries: u64,
 
+    /// The maximum total size in bytes of the log entries in an AppendEntries RPC. `0` means no
+    /// limit.
+    ///
+    /// A batch of at most `max_payload_entries` entries is cut short once its size reaches this
+    /// budget, but always contains at least one entry. The size of an entry is reported by
+    /// [`RaftLogReader::entry_size()`]; entries of unknown size are not counted.
+    ///
+    /// [`RaftLogReader::entry_size()`]: crate::storage::RaftLogReader::entry_size
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0", value_parser=parse_bytes_with_unit)]
+    pub max_payload_bytes: u64,
+
+    /// The compression schemes this node supports for AppendEntries payloads, in order of
+    /// preference, e.g., `zstd,lz4`.
+    ///
+    /// They are advertised to other nodes as capabilities with the
+    /// [`NodeInfo::COMPRESSION`](crate::raft::NodeInfo::COMPRESSION) prefix. A leader uses, for
+    /// every replication target, the first of its schemes the target advertises too, and reports
+    /// it in [`RaftMetrics::compression`](crate::RaftMetrics::compression). Openraft does not
+    /// compress by itself: the scheme is passed to the network implementation with
+    /// [`RPCOption::compression()`](crate::network::RPCOption::compression).
+    ///
+    /// Since: 0.10.0
+    #[clap(long, value_delimiter = ',')]
+    pub compression: Vec<String>,
+
+    /// The minimum total size in bytes of the log entries in an AppendEntries RPC to compress.
+    ///
+    /// Smaller payloads, such as heartbeats, are sent uncompressed, because compressing them
+    /// costs more CPU than the bandwidth it saves. The size of an entry is reported by
+    /// [`RaftLogReader::entry_size()`]; entries of unknown size are not counted.
+    ///
+    /// [`RaftLogReader::entry_size()`]: crate::storage::RaftLogReader::entry_size
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "64KiB", value_parser=parse_bytes_with_unit)]
+    pub compression_threshold: u64,
+
+    /// The maximum number of AppendEntries RPCs in flight to a single follower or learner.
+    ///
+    /// With a value greater than 1, once the last matching log on a target is found, the leader
+    /// sends up to this many AppendEntries RPCs of at most `max_payload_entries` entries each,
+    /// without waiting for the previous ones to be responded, to hide the round-trip time on a
+    /// high-latency link. Every extra in-flight RPC uses another connection created with
+    /// [`RaftNetworkFactory::new_client()`](crate::network::RaftNetworkFactory::new_client).
+    ///
+    /// `1` sends one RPC at a time.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "1")]
+    pub max_append_entries_inflight: u64,
+
+    /// The number of committed but not yet applied logs at which a follower or learner reports it
+    /// is busy to the leader. `0` disables it.
+    ///
+    /// When its disk is saturated, a node falls behind in applying logs. It then responds to an
+    /// AppendEntries with [`AppendEntriesResponse::Busy`], and the leader pauses sending logs to
+    /// it for [`busy_pause`](`Self::busy_pause`), instead of piling up requests that time out.
+    ///
+    /// [`AppendEntriesResponse::Busy`]: crate::raft::AppendEntriesResponse::Busy
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub busy_apply_backlog: u64,
+
+    /// The time in milliseconds a leader pauses sending logs to a target that reported it is busy.
+    ///
+    /// Heartbeats are still sent during the pause.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "100")]
+    pub busy_pause: u64,
+
+    /// The max number of log entries a leader caches for replication. `0` disables the cache.
+    ///
+    /// When many followers lag, their replication streams read the same log ranges from the log
+    /// store. With the cache, a range is read once and shared. An entry is evicted once every
+    /// follower and learner has replicated it, or, when the cache is full, in the order of the
+    /// log index.
+    ///
+    /// Only entries that the log reader can copy with [`RaftLogReader::clone_entry()`] are cached.
+    /// The effectiveness of the cache is reported in [`RaftMetrics::replication_cache`].
+    ///
+    /// [`RaftLogReader::clone_entry()`]: crate::storage::RaftLogReader::clone_entry
+    /// [`RaftMetrics::replication_cache`]: crate::metrics::RaftMetrics::replication_cache
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub replication_cache_entries: u64,
+
     /// The distance behind in log replication a follower must fall before it is considered lagging
     ///
     /// - Followers that fall behind this index are replicated with a snapshot.
@@ -203,6 +599,74 @@ pub struct Config {
     #[clap(long, default_value = "3MiB", value_parser=parse_bytes_with_unit)]
     pub snapshot_max_chunk_size: u64,
 
+    /// The maximum number of log entries to buffer for a target while a snapshot is being sent to
+    /// it.
+    ///
+    /// While a snapshot is transmitted, the leader reads the logs after the snapshot into memory,
+    /// so that they are sent at once when the snapshot is installed, instead of being read from
+    /// storage in a second catch-up phase. `0` disables the buffering.
+    #[clap(long, default_value = "3000")]
+    pub snapshot_tail_buffer_size: u64,
+
+    /// The maximum number of snapshots a leader sends at the same time. `0` means no limit.
+    ///
+    /// When more followers need a snapshot, for example after the leader is restored from a
+    /// backup, the extra transfers wait in a FIFO queue until a running one finishes, instead of
+    /// exhausting the IO of the leader. The transfers are reported in
+    /// [`RaftMetrics::snapshot_transfers`].
+    ///
+    /// [`RaftMetrics::snapshot_transfers`]: crate::metrics::RaftMetrics::snapshot_transfers
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub max_inflight_snapshots: u64,
+
+    /// The interval in milliseconds at which a leader sends the checksum of a range of committed
+    /// log entries to followers. `0` disables it.
+    ///
+    /// Every interval the leader computes the checksum of the next `max_payload_entries` committed
+    /// entries, starting over from the first one after reaching the last, and sends it with the
+    /// first heartbeat after it is computed. A follower that has applied the range compares it
+    /// with the checksum of its own copy, and reports a mismatch in
+    /// [`RaftMetrics::log_checksum_mismatches`]. It detects a silently diverged or corrupted log
+    /// before it shows up as a difference in the state machine. Logs are read for checksums in a
+    /// separate task, so that slow log reads do not delay heartbeats.
+    ///
+    /// The checksum of an entry is returned by [`RaftLogReader::entry_checksum()`].
+    ///
+    /// [`RaftMetrics::log_checksum_mismatches`]: crate::metrics::RaftMetrics::log_checksum_mismatches
+    /// [`RaftLogReader::entry_checksum()`]: crate::storage::RaftLogReader::entry_checksum
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub log_checksum_interval: u64,
+
+    /// Interval in milliseconds for a leader to evaluate the fitness of itself and of the voters,
+    /// and to hand off its leadership to a voter that is significantly fitter.
+    ///
+    /// The fitness of a node is scored by the function set with
+    /// [`Raft::set_leader_fitness()`](crate::Raft::set_leader_fitness), e.g., from its CPU load,
+    /// disk latency or zone. If the best scored voter that has replicated every log of the leader
+    /// scores at least [`leader_fitness_margin`](Self::leader_fitness_margin) more than the
+    /// leader, the leader transfers its leadership to it, and counts it in
+    /// [`RaftMetrics::leader_fitness_handoffs`](crate::RaftMetrics::leader_fitness_handoffs).
+    ///
+    /// `0` disables the evaluation, which is the default.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub leader_fitness_interval: u64,
+
+    /// The least fitness score a voter has to exceed the leader by, for the leader to hand off its
+    /// leadership to it. See [`leader_fitness_interval`](Self::leader_fitness_interval).
+    ///
+    /// A margin keeps the leadership from moving back and forth between nodes with about the same
+    /// score.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "10")]
+    pub leader_fitness_margin: u64,
+
     /// The maximum number of logs to keep that are already included in **snapshot**.
     ///
     /// Logs that are not in a snapshot will never be purged.
@@ -213,6 +677,15 @@ pub struct Config {
     #[clap(long, default_value = "1")]
     pub purge_batch_size: u64,
 
+    /// Which of the logs included in the snapshot are purged: `keep-in-snapshot` or
+    /// `keep-for-lagging:<margin>:<max_lag>`.
+    ///
+    /// See [`PurgePolicy`].
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "keep-in-snapshot", value_parser = parse_purge_policy)]
+    pub purge_policy: PurgePolicy,
+
     /// The size of the bounded API channel for sending messages to RaftCore.
     ///
     /// This controls backpressure for client requests. When the channel is full,
@@ -227,6 +700,45 @@ pub struct Config {
     #[clap(long, default_value = "65536")]
     pub notification_channel_size: Option<u64>,
 
+    /// The maximum number of client writes queued for RaftCore to process. `0` means no limit.
+    ///
+    /// When this many [`Raft::client_write()`](crate::Raft::client_write) calls are queued,
+    /// a new write is rejected at once with
+    /// [`ClientWriteError::Overloaded`](crate::error::ClientWriteError::Overloaded), instead of
+    /// waiting for room in the API channel. It lets a leader under overload shed load and tell
+    /// clients when to retry, rather than piling up pending requests.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub max_queued_client_writes: u64,
+
+    /// The maximum size in bytes of the log entry of a client write. `0` means no limit.
+    ///
+    /// A [`Raft::client_write()`](crate::Raft::client_write) whose entry is larger is rejected
+    /// with [`ClientWriteError::EntryTooLarge`](crate::error::ClientWriteError::EntryTooLarge)
+    /// without being proposed. The size of an entry is reported by
+    /// [`RaftLogReader::entry_size()`]; an entry of unknown size is always accepted.
+    ///
+    /// [`RaftLogReader::entry_size()`]: crate::storage::RaftLogReader::entry_size
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0", value_parser=parse_bytes_with_unit)]
+    pub max_entry_size: u64,
+
+    /// The maximum time in milliseconds a linearizable read waits to be batched with other reads
+    /// before the leader confirms its leadership.
+    ///
+    /// A [`ReadPolicy::ReadIndex`] read requires a round of heartbeats to a quorum. Reads that
+    /// arrive while a batch is open share a single round, instead of sending one round for every
+    /// read. With `0`, only the reads that are already queued when `RaftCore` handles the first
+    /// one are batched, which adds no delay.
+    ///
+    /// [`ReadPolicy::ReadIndex`]: crate::raft::ReadPolicy::ReadIndex
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub read_index_batch_delay: u64,
+
     /// Enable or disable tick.
     ///
     /// If ticking is disabled, timeout-based events are all disabled:
@@ -254,6 +766,25 @@ pub struct Config {
     )]
     pub enable_tick: bool,
 
+    /// Whether ticks are driven by the application instead of an internal timer.
+    ///
+    /// When enabled (`true`), Raft does not spawn a timer task, and the application emits every
+    /// tick by calling [`Raft::tick()`](crate::Raft::tick), e.g., from a game loop, a WASM host
+    /// without timers, or a deterministic test. Elections and heartbeats are evaluated only on a
+    /// tick, so the application should call it about every [`Self::heartbeat_interval`]
+    /// milliseconds.
+    ///
+    /// [`Self::enable_tick`] still applies: a manual tick is ignored while ticking is disabled.
+    ///
+    /// Since: 0.10.0
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub manual_tick: bool,
+
     /// Whether a leader sends heartbeat logs to following nodes, i.e., followers and learners.
     // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
     // https://github.com/clap-rs/clap/discussions/4374
@@ -288,6 +819,219 @@ pub struct Config {
     )]
     pub enable_elect: bool,
 
+    /// Whether to check that the local storage can be read back before starting an election.
+    ///
+    /// Before campaigning, the node reads its vote and its last flushed log entry from the log
+    /// store. If either of them fails to be read or decoded, or the entry is not the expected one,
+    /// the node stays a follower instead of becoming a leader with a corrupted log, and reports
+    /// the failure in [`RaftMetrics::storage_check_failure`] and in
+    /// [`NoElectionReason::StorageCheckFailed`].
+    ///
+    /// The storage is read in a task outside `RaftCore`, which keeps handling messages meanwhile:
+    /// the election is started when the check passes, if it is still due.
+    ///
+    /// [`RaftMetrics::storage_check_failure`]: crate::metrics::RaftMetrics::storage_check_failure
+    /// [`NoElectionReason::StorageCheckFailed`]: crate::raft::NoElectionReason::StorageCheckFailed
+    ///
+    /// Since: 0.10.0
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub check_storage_before_election: bool,
+
+    /// The minimum interval in milliseconds between two vote requests from the same candidate that
+    /// are handled. `0` disables the limiting.
+    ///
+    /// A vote request that arrives within this interval after the previous one from the same
+    /// candidate is rejected without being handled, so that a misbehaving node, or a node removed
+    /// from the cluster that keeps campaigning, can not keep disturbing the cluster. A legitimate
+    /// candidate sends one vote request per election, thus it must be smaller than
+    /// `election_timeout_min`.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub vote_request_min_interval: u64,
+
+    /// How to treat the AppendEntries and Vote RPCs sent by a node that is not in the effective
+    /// membership of this node: `reject`, `accept-read-only` or `accept`.
+    ///
+    /// See [`NonMemberRpcPolicy`].
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "accept", value_parser = parse_non_member_rpc_policy)]
+    pub accept_rpc_from_non_members: NonMemberRpcPolicy,
+
+    /// When a newly elected leader appends a blank log: `always`, `if-uncommitted` or `never`.
+    ///
+    /// See [`BlankEntryPolicy`].
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "always", value_parser = parse_blank_entry_policy)]
+    pub blank_entry_policy: BlankEntryPolicy,
+
+    /// How a stale read is served on a node that has not discovered the leader since it started:
+    /// `serve-stale`, `refuse` or `wait-for-leader:<ms>`.
+    ///
+    /// See [`ColdStartReadPolicy`].
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "serve-stale", value_parser = parse_cold_start_read_policy)]
+    pub cold_start_reads: ColdStartReadPolicy,
+
+    /// What a leader that steps down does with the client writes that are not yet committed:
+    /// `wait` or `fail`.
+    ///
+    /// See [`PendingWritePolicy`].
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "wait", value_parser = parse_pending_write_policy)]
+    pub pending_writes_on_step_down: PendingWritePolicy,
+
+    /// Whether a leader sends the new committed log id to followers at once, when its own log
+    /// flush commits a log.
+    ///
+    /// A leader always re-computes the committed log id when its local append is flushed: if the
+    /// acknowledgements already received from followers and the leader's own flushed log form a
+    /// quorum, the log is committed and client requests are responded without waiting for the next
+    /// replication response. In a 3-node cluster, this saves a round-trip when a follower
+    /// acknowledges a log before the leader flushes it.
+    ///
+    /// When enabled (`true`), the new committed log id is sent to followers at once.
+    ///
+    /// When disabled (`false`), followers learn it with the next AppendEntries or heartbeat. This
+    /// saves one RPC per such commit, at the cost of followers applying the log later. This setting
+    /// is mainly for testing and benchmarking.
+    ///
+    /// Since: 0.10.0
+    // clap 4 requires `num_args = 0..=1`, or it complains about missing arg error
+    // https://github.com/clap-rs/clap/discussions/4374
+    #[clap(long,
+           default_value_t = true,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub broadcast_commit_on_local_flush: bool,
+
+    /// Whether to restart a replication stream that panicked, instead of shutting down Raft.
+    ///
+    /// When disabled (`false`), a panic in a replication stream stops Raft with
+    /// [`Fatal::TaskPanicked`](crate::error::Fatal::TaskPanicked). When enabled, the leader logs
+    /// the panic, spawns a new stream for the same target and resumes replication from the last
+    /// known matching log id.
+    ///
+    /// A panic in any other internal task always stops Raft.
+    ///
+    /// Since: 0.10.0
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub restart_replication_on_panic: bool,
+
+    /// Whether a follower fetches committed log entries it cannot read from the leader, instead
+    /// of shutting down.
+    ///
+    /// When disabled (`false`), an error reading committed logs to apply stops Raft with a
+    /// [`StorageError`](crate::StorageError). When enabled, a follower quarantines the unreadable
+    /// range, e.g., entries that fail to deserialize because of a corrupted disk, and stops
+    /// applying logs until the leader sends a copy of them with
+    /// [`RaftNetworkV2::repair_log()`](crate::network::v2::RaftNetworkV2::repair_log). The copy is
+    /// applied to the state machine, the local log is not rewritten. If no copy is fetched after
+    /// about a hundred heartbeat intervals, e.g., the leader cannot read them either, the follower
+    /// stops with the error.
+    ///
+    /// The leader always stops with the error: there is no other node it can trust.
+    ///
+    /// Since: 0.10.0
+    #[clap(long,
+           default_value_t = false,
+           action = clap::ArgAction::Set,
+           num_args = 0..=1,
+           default_missing_value = "true"
+    )]
+    pub repair_corrupted_log: bool,
+
+    /// The maximum time in milliseconds a follower waits for the results of committed log entries
+    /// computed by the leader, before computing them itself. `0` disables streaming apply results.
+    ///
+    /// When enabled, the leader sends the results its state machine returns with
+    /// [`RaftStateMachine::take_computed_results()`] to every follower with
+    /// [`RaftNetworkV2::apply_results()`], and a follower applies a batch of entries with
+    /// [`RaftStateMachine::apply_computed()`] if the results of all of them arrive in time. It is
+    /// meant for a deterministic state machine that is expensive to compute, but cheap to store a
+    /// result of.
+    ///
+    /// Every result carries a checksum: a corrupted copy is not used, and a follower that computes
+    /// a result itself compares it with the one of the leader, and reports a state machine that is
+    /// not deterministic in [`RaftMetrics::apply_result_mismatches`].
+    ///
+    /// [`RaftStateMachine::take_computed_results()`]: crate::storage::RaftStateMachine::take_computed_results
+    /// [`RaftStateMachine::apply_computed()`]: crate::storage::RaftStateMachine::apply_computed
+    /// [`RaftNetworkV2::apply_results()`]: crate::network::v2::RaftNetworkV2::apply_results
+    /// [`RaftMetrics::apply_result_mismatches`]: crate::metrics::RaftMetrics::apply_result_mismatches
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub apply_results_wait: u64,
+
+    /// The maximum number of engine inputs to record for
+    /// [`Raft::engine_trace()`](crate::Raft::engine_trace). `0` disables the recording.
+    ///
+    /// When enabled, every RPC, tick, client request and IO completion fed to the Raft engine is
+    /// kept in memory, so that the trace can be attached to a bug report and replayed
+    /// deterministically. Inputs after this limit are dropped, and the trace is marked as
+    /// truncated.
+    ///
+    /// Engine trace requires feature `serde`; without it this option is ignored.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub engine_trace_max_inputs: u64,
+
+    /// The number of the last [`RaftMetrics`] samples to keep for
+    /// [`Raft::metrics_history()`](crate::Raft::metrics_history). `0` disables the history.
+    ///
+    /// When enabled, the metrics are sampled on every tick, i.e., about every
+    /// [`heartbeat_interval`](Self::heartbeat_interval), and the oldest sample is dropped for a new
+    /// one. After an incident, the samples show how commit, apply and replication progressed in
+    /// the minutes before, without an external monitoring system.
+    ///
+    /// [`RaftMetrics`]: crate::metrics::RaftMetrics
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub metrics_history_size: u64,
+
+    /// The number of committed logs the state machine may lag behind before an
+    /// [`ApplyLagEvent::Raised`] is sent to [`Raft::apply_lag_events()`]. `0` disables the alarm.
+    ///
+    /// The alarm is raised when `committed - applied` stays greater than this threshold for
+    /// [`apply_lag_alarm_duration`](field@Self::apply_lag_alarm_duration) milliseconds, e.g., when
+    /// the state machine is stuck, and is cleared when the lag drops back. The lag is checked
+    /// on every tick, i.e., about every [`heartbeat_interval`](Self::heartbeat_interval).
+    ///
+    /// [`ApplyLagEvent::Raised`]: crate::metrics::ApplyLagEvent::Raised
+    /// [`Raft::apply_lag_events()`]: crate::Raft::apply_lag_events
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "0")]
+    pub apply_lag_alarm_threshold: u64,
+
+    /// How long in milliseconds the apply lag must stay above
+    /// [`apply_lag_alarm_threshold`](Self::apply_lag_alarm_threshold) before the alarm is raised.
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "5000")]
+    pub apply_lag_alarm_duration: u64,
+
     /// Whether to allow to reset the replication progress to `None`, when the
     /// follower's log is found reverted to an early state. **Do not enable this in production**
     /// unless you know what you are doing.
@@ -308,6 +1052,24 @@ pub struct Config {
     )]
     pub allow_log_reversion: Option<bool>,
 
+    /// The number of log reversions a follower reports before the leader quarantines it.
+    ///
+    /// A follower whose log is found reverted, i.e., it reports a conflict below the index the
+    /// leader has already seen it matching, while log reversion is not allowed, is considered
+    /// diverged. The leader stops replicating to a quarantined follower and lists it in
+    /// [`RaftMetrics::quarantined`], until an operator resyncs it with
+    /// [`Trigger::resync()`](crate::raft::trigger::Trigger::resync) or allows the reversion with
+    /// [`Trigger::allow_next_revert()`](crate::raft::trigger::Trigger::allow_next_revert).
+    ///
+    /// The count is reset when the follower acknowledges replicated logs, so that a single stale
+    /// response does not quarantine a healthy follower. `0` is treated as `1`.
+    ///
+    /// [`RaftMetrics::quarantined`]: crate::metrics::RaftMetrics::quarantined
+    ///
+    /// Since: 0.10.0
+    #[clap(long, default_value = "3")]
+    pub quarantine_after_log_reversions: u64,
+
     /// Allow IO completion notifications to arrive out of order.
     ///
     /// When enabled, storage implementations may report IO completions non-monotonically.
@@ -330,6 +1092,16 @@ pub struct Config {
 pub(crate) struct RuntimeConfig {
     pub(crate) enable_heartbeat: AtomicBool,
     pub(crate) enable_elect: AtomicBool,
+
+    /// Whether the node is in read-only mode, see [`Raft::enter_readonly()`].
+    ///
+    /// [`Raft::enter_readonly()`]: crate::Raft::enter_readonly
+    pub(crate) readonly: AtomicBool,
+
+    /// Log level overrides of subsystems, see [`RuntimeConfigHandle::log_level()`].
+    ///
+    /// [`RuntimeConfigHandle::log_level()`]: crate::raft::RuntimeConfigHandle::log_level
+    pub(crate) log_levels: LogLevels,
 }
 
 impl RuntimeConfig {
@@ -337,6 +1109,8 @@ impl RuntimeConfig {
         Self {
             enable_heartbeat: AtomicBool::from(config.enable_heartbeat),
             enable_elect: AtomicBool::from(config.enable_elect),
+            readonly: AtomicBool::from(false),
+            log_levels: LogLevels::default(),
         }
     }
 }
@@ -348,16 +1122,55 @@ impl Default for Config {
 }
 
 impl Config {
-    /// Generate a new random election timeout within the configured min and max values.
+    /// Generate a new random election timeout within the configured min and max values, in
+    /// [`election_timeout_unit`](Self::election_timeout_unit).
     pub fn new_rand_election_timeout<RT: AsyncRuntime>(&self) -> u64 {
         RT::thread_rng().random_range(self.election_timeout_min..self.election_timeout_max)
     }
 
+    /// Get the minimum election timeout.
+    #[since(version = "0.10.0")]
+    pub fn election_timeout_min(&self) -> Duration {
+        self.election_timeout_unit.to_duration(self.election_timeout_min)
+    }
+
+    /// Get the maximum election timeout.
+    #[since(version = "0.10.0")]
+    pub fn election_timeout_max(&self) -> Duration {
+        self.election_timeout_unit.to_duration(self.election_timeout_max)
+    }
+
+    /// Get the heartbeat interval.
+    #[since(version = "0.10.0")]
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.heartbeat_interval_unit.to_duration(self.heartbeat_interval)
+    }
+
     /// Get the timeout for sending and installing the last snapshot segment.
     pub fn install_snapshot_timeout(&self) -> Duration {
         Duration::from_millis(self.install_snapshot_timeout)
     }
 
+    /// Get the maximum time a follower waits for the apply results computed by the leader.
+    pub(crate) fn apply_results_wait(&self) -> Duration {
+        Duration::from_millis(self.apply_results_wait)
+    }
+
+    /// Get the minimum interval between two handled vote requests from the same candidate.
+    pub(crate) fn vote_request_min_interval(&self) -> Duration {
+        Duration::from_millis(self.vote_request_min_interval)
+    }
+
+    /// Get how long the apply lag must stay above the threshold before the alarm is raised.
+    pub(crate) fn apply_lag_alarm_duration(&self) -> Duration {
+        Duration::from_millis(self.apply_lag_alarm_duration)
+    }
+
+    /// Get the maximum time a linearizable read waits to be batched with other reads.
+    pub(crate) fn read_index_batch_delay(&self) -> Duration {
+        Duration::from_millis(self.read_index_batch_delay)
+    }
+
     /// Get the timeout for sending a non-last snapshot segment.
     #[deprecated(
         since = "0.9.0",
@@ -436,7 +1249,16 @@ impl Config {
             });
         }
 
-        if self.election_timeout_min <= self.heartbeat_interval {
+        if self.election_timeout_min() <= self.heartbeat_interval() {
+            if self.election_timeout_unit != self.heartbeat_interval_unit {
+                return Err(ConfigError::TimerRatio {
+                    shorter: "heartbeat_interval",
+                    shorter_value: self.heartbeat_interval(),
+                    longer: "election_timeout_min",
+                    longer_value: self.election_timeout_min(),
+                });
+            }
+
             return Err(ConfigError::ElectionTimeoutLTHeartBeat {
                 election_timeout_min: self.election_timeout_min,
                 heartbeat_interval: self.heartbeat_interval,
@@ -447,6 +1269,26 @@ impl Config {
             return Err(ConfigError::MaxPayloadIs0);
         }
 
+        if self.max_append_entries_inflight == 0 {
+            return Err(ConfigError::MaxAppendEntriesInflightIs0);
+        }
+
+        if self.vote_request_min_interval() >= self.election_timeout_min() {
+            if self.election_timeout_unit != TimerUnit::Millis {
+                return Err(ConfigError::TimerRatio {
+                    shorter: "vote_request_min_interval",
+                    shorter_value: self.vote_request_min_interval(),
+                    longer: "election_timeout_min",
+                    longer_value: self.election_timeout_min(),
+                });
+            }
+
+            return Err(ConfigError::VoteRequestMinIntervalGEElectionTimeout {
+                vote_request_min_interval: self.vote_request_min_interval,
+                election_timeout_min: self.election_timeout_min,
+            });
+        }
+
         Ok(self)
     }
 }
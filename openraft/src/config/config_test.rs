@@ -1,7 +1,13 @@
 use core::time::Duration;
 
+use crate::BlankEntryPolicy;
+use crate::ColdStartReadPolicy;
 use crate::Config;
+use crate::NonMemberRpcPolicy;
+use crate::PendingWritePolicy;
+use crate::PurgePolicy;
 use crate::SnapshotPolicy;
+use crate::TimerUnit;
 use crate::config::error::ConfigError;
 
 #[test]
@@ -13,9 +19,11 @@ fn test_config_defaults() {
 
     assert_eq!(50, cfg.heartbeat_interval);
     assert_eq!(300, cfg.max_payload_entries);
+    assert_eq!(1, cfg.max_append_entries_inflight);
     assert_eq!(5000, cfg.replication_lag_threshold);
 
     assert_eq!(3 * 1024 * 1024, cfg.snapshot_max_chunk_size);
+    assert_eq!(3000, cfg.snapshot_tail_buffer_size);
     assert_eq!(SnapshotPolicy::LogsSinceLast(5000), cfg.snapshot_policy);
     assert_eq!(Some(65536), cfg.api_channel_size);
     assert_eq!(Some(65536), cfg.notification_channel_size);
@@ -46,6 +54,31 @@ fn test_invalid_election_timeout_config_produces_expected_error() {
         election_timeout_min: 1000,
         heartbeat_interval: 1500
     });
+
+    let config = Config {
+        election_timeout_min: 1000,
+        election_timeout_max: 2000,
+        vote_request_min_interval: 1000,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    let err = res.unwrap_err();
+    assert_eq!(err, ConfigError::VoteRequestMinIntervalGEElectionTimeout {
+        vote_request_min_interval: 1000,
+        election_timeout_min: 1000,
+    });
+}
+
+#[test]
+fn test_invalid_max_append_entries_inflight() {
+    let config = Config {
+        max_append_entries_inflight: 0,
+        ..Default::default()
+    };
+
+    let res = config.validate();
+    assert_eq!(Err(ConfigError::MaxAppendEntriesInflightIs0), res.map(|_| ()));
 }
 
 #[test]
@@ -66,6 +99,9 @@ fn test_build() -> anyhow::Result<()> {
         "--purge-batch-size=207",
         "--api-channel-size=208",
         "--notification-channel-size=209",
+        "--snapshot-tail-buffer-size=210",
+        "--max-append-entries-inflight=211",
+        "--replication-cache-entries=212",
     ])?;
 
     assert_eq!("bar", config.cluster_name);
@@ -86,6 +122,9 @@ fn test_build() -> anyhow::Result<()> {
     assert_eq!(207, config.purge_batch_size);
     assert_eq!(Some(208), config.api_channel_size);
     assert_eq!(Some(209), config.notification_channel_size);
+    assert_eq!(210, config.snapshot_tail_buffer_size);
+    assert_eq!(211, config.max_append_entries_inflight);
+    assert_eq!(212, config.replication_cache_entries);
 
     // Test config methods
     #[allow(deprecated)]
@@ -118,6 +157,23 @@ fn test_config_snapshot_policy() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_accept_rpc_from_non_members() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(NonMemberRpcPolicy::Accept, config.accept_rpc_from_non_members);
+
+    let config = Config::build(&["foo", "--accept-rpc-from-non-members=reject"])?;
+    assert_eq!(NonMemberRpcPolicy::Reject, config.accept_rpc_from_non_members);
+
+    let config = Config::build(&["foo", "--accept-rpc-from-non-members=accept-read-only"])?;
+    assert_eq!(NonMemberRpcPolicy::AcceptReadOnly, config.accept_rpc_from_non_members);
+
+    let res = Config::build(&["foo", "--accept-rpc-from-non-members=bar"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_tick() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-tick=false"])?;
@@ -135,6 +191,23 @@ fn test_config_enable_tick() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_manual_tick() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--manual-tick=false"])?;
+    assert_eq!(false, config.manual_tick);
+
+    let config = Config::build(&["foo", "--manual-tick=true"])?;
+    assert_eq!(true, config.manual_tick);
+
+    let config = Config::build(&["foo", "--manual-tick"])?;
+    assert_eq!(true, config.manual_tick);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.manual_tick);
+
+    Ok(())
+}
+
 #[test]
 fn test_config_enable_heartbeat() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--enable-heartbeat=false"])?;
@@ -169,6 +242,45 @@ fn test_config_enable_elect() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_config_broadcast_commit_on_local_flush() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--broadcast-commit-on-local-flush=false"])?;
+    assert_eq!(false, config.broadcast_commit_on_local_flush);
+
+    let config = Config::build(&["foo", "--broadcast-commit-on-local-flush"])?;
+    assert_eq!(true, config.broadcast_commit_on_local_flush);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(true, config.broadcast_commit_on_local_flush);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_restart_replication_on_panic() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--restart-replication-on-panic=true"])?;
+    assert_eq!(true, config.restart_replication_on_panic);
+
+    let config = Config::build(&["foo", "--restart-replication-on-panic"])?;
+    assert_eq!(true, config.restart_replication_on_panic);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.restart_replication_on_panic);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_engine_trace_max_inputs() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--engine-trace-max-inputs=1000"])?;
+    assert_eq!(1000, config.engine_trace_max_inputs);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.engine_trace_max_inputs);
+
+    Ok(())
+}
+
 #[test]
 fn test_config_allow_log_reversion() -> anyhow::Result<()> {
     let config = Config::build(&["foo", "--allow-log-reversion=false"])?;
@@ -272,3 +384,328 @@ fn test_config_notification_channel_size() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_config_max_queued_client_writes() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--max-queued-client-writes=100"])?;
+    assert_eq!(100, config.max_queued_client_writes);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.max_queued_client_writes);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_max_inflight_snapshots() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--max-inflight-snapshots=2"])?;
+    assert_eq!(2, config.max_inflight_snapshots);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.max_inflight_snapshots);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_log_checksum_interval() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--log-checksum-interval=1000"])?;
+    assert_eq!(1000, config.log_checksum_interval);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.log_checksum_interval);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_max_entry_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--max-entry-size=1KiB", "--max-payload-bytes=2MiB"])?;
+    assert_eq!(1024, config.max_entry_size);
+    assert_eq!(2 * 1024 * 1024, config.max_payload_bytes);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.max_entry_size);
+    assert_eq!(0, config.max_payload_bytes);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_read_index_batch_delay() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--read-index-batch-delay=5"])?;
+    assert_eq!(5, config.read_index_batch_delay);
+    assert_eq!(Duration::from_millis(5), config.read_index_batch_delay());
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.read_index_batch_delay);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_metrics_history_size() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--metrics-history-size=600"])?;
+    assert_eq!(600, config.metrics_history_size);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.metrics_history_size);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_repair_corrupted_log() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--repair-corrupted-log=true"])?;
+    assert_eq!(true, config.repair_corrupted_log);
+
+    let config = Config::build(&["foo", "--repair-corrupted-log"])?;
+    assert_eq!(true, config.repair_corrupted_log);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(false, config.repair_corrupted_log);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_app_version_and_capabilities() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--app-version=1.2.0", "--capabilities=app:a,app:b"])?;
+    assert_eq!("1.2.0", config.app_version);
+    assert_eq!(vec!["app:a".to_string(), "app:b".to_string()], config.capabilities);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!("", config.app_version);
+    assert!(config.capabilities.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_leader_fitness() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--leader-fitness-interval=1000", "--leader-fitness-margin=5"])?;
+    assert_eq!(1000, config.leader_fitness_interval);
+    assert_eq!(5, config.leader_fitness_margin);
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.leader_fitness_interval);
+    assert_eq!(10, config.leader_fitness_margin);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_apply_results_wait() -> anyhow::Result<()> {
+    let config = Config::build(&["foo", "--apply-results-wait=50"])?;
+    assert_eq!(50, config.apply_results_wait);
+    assert_eq!(Duration::from_millis(50), config.apply_results_wait());
+
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.apply_results_wait);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_blank_entry_policy() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(BlankEntryPolicy::Always, config.blank_entry_policy);
+
+    let config = Config::build(&["foo", "--blank-entry-policy=if-uncommitted"])?;
+    assert_eq!(BlankEntryPolicy::IfUncommitted, config.blank_entry_policy);
+
+    let config = Config::build(&["foo", "--blank-entry-policy=never"])?;
+    assert_eq!(BlankEntryPolicy::Never, config.blank_entry_policy);
+
+    let res = Config::build(&["foo", "--blank-entry-policy=bar"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_purge_policy() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(PurgePolicy::KeepInSnapshot, config.purge_policy);
+
+    let config = Config::build(&["foo", "--purge-policy=keep-for-lagging:100:5000"])?;
+    assert_eq!(
+        PurgePolicy::KeepForLagging {
+            margin: 100,
+            max_lag: 5000
+        },
+        config.purge_policy
+    );
+
+    let config = Config::build(&["foo", "--purge-policy=keep-in-snapshot"])?;
+    assert_eq!(PurgePolicy::KeepInSnapshot, config.purge_policy);
+
+    let res = Config::build(&["foo", "--purge-policy=keep-for-lagging:100"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--purge-policy=keep-for-lagging:x:5000"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_cold_start_reads() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(ColdStartReadPolicy::ServeStale, config.cold_start_reads);
+
+    let config = Config::build(&["foo", "--cold-start-reads=refuse"])?;
+    assert_eq!(ColdStartReadPolicy::Refuse, config.cold_start_reads);
+
+    let config = Config::build(&["foo", "--cold-start-reads=wait-for-leader:500"])?;
+    assert_eq!(ColdStartReadPolicy::WaitForLeader(500), config.cold_start_reads);
+
+    let res = Config::build(&["foo", "--cold-start-reads=wait-for-leader"]);
+    assert!(res.is_err());
+
+    let res = Config::build(&["foo", "--cold-start-reads=wait-for-leader:x"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_timer_unit() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(TimerUnit::Millis, config.heartbeat_interval_unit);
+    assert_eq!(TimerUnit::Millis, config.election_timeout_unit);
+    assert_eq!(Duration::from_millis(50), config.heartbeat_interval());
+    assert_eq!(Duration::from_millis(150), config.election_timeout_min());
+
+    let config = Config::build(&[
+        "foo",
+        "--heartbeat-interval=200",
+        "--heartbeat-interval-unit=us",
+        "--election-timeout-min=1",
+        "--election-timeout-max=2",
+    ])?;
+    assert_eq!(Duration::from_micros(200), config.heartbeat_interval());
+    assert_eq!(Duration::from_millis(1), config.election_timeout_min());
+    assert_eq!(Duration::from_millis(2), config.election_timeout_max());
+
+    let config = Config::build(&[
+        "foo",
+        "--election-timeout-min=1500",
+        "--election-timeout-max=3000",
+        "--election-timeout-unit=us",
+        "--heartbeat-interval=500",
+        "--heartbeat-interval-unit=us",
+    ])?;
+    assert_eq!(Duration::from_micros(1500), config.election_timeout_min());
+
+    let res = Config::build(&["foo", "--heartbeat-interval-unit=ns"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_timer_unit_ratio() {
+    // 2ms heartbeat is not shorter than a 1500us election timeout.
+    let config = Config {
+        heartbeat_interval: 2,
+        election_timeout_min: 1500,
+        election_timeout_max: 3000,
+        election_timeout_unit: TimerUnit::Micros,
+        ..Default::default()
+    };
+
+    let err = config.validate().unwrap_err();
+    assert_eq!(err, ConfigError::TimerRatio {
+        shorter: "heartbeat_interval",
+        shorter_value: Duration::from_millis(2),
+        longer: "election_timeout_min",
+        longer_value: Duration::from_micros(1500),
+    });
+
+    let config = Config {
+        heartbeat_interval: 500,
+        election_timeout_min: 1500,
+        election_timeout_max: 3000,
+        election_timeout_unit: TimerUnit::Micros,
+        heartbeat_interval_unit: TimerUnit::Micros,
+        vote_request_min_interval: 2,
+        ..Default::default()
+    };
+
+    let err = config.validate().unwrap_err();
+    assert_eq!(err, ConfigError::TimerRatio {
+        shorter: "vote_request_min_interval",
+        shorter_value: Duration::from_millis(2),
+        longer: "election_timeout_min",
+        longer_value: Duration::from_micros(1500),
+    });
+}
+
+#[test]
+fn test_config_compression() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert!(config.compression.is_empty());
+    assert_eq!(64 * 1024, config.compression_threshold);
+
+    let config = Config::build(&["foo", "--compression=zstd,lz4", "--compression-threshold=1MiB"])?;
+    assert_eq!(vec!["zstd".to_string(), "lz4".to_string()], config.compression);
+    assert_eq!(1024 * 1024, config.compression_threshold);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_busy_apply_backlog() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.busy_apply_backlog);
+    assert_eq!(100, config.busy_pause);
+
+    let config = Config::build(&["foo", "--busy-apply-backlog=1000", "--busy-pause=50"])?;
+    assert_eq!(1000, config.busy_apply_backlog);
+    assert_eq!(50, config.busy_pause);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_pending_writes_on_step_down() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(PendingWritePolicy::Wait, config.pending_writes_on_step_down);
+
+    let config = Config::build(&["foo", "--pending-writes-on-step-down=fail"])?;
+    assert_eq!(PendingWritePolicy::Fail, config.pending_writes_on_step_down);
+
+    let res = Config::build(&["foo", "--pending-writes-on-step-down=forward"]);
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_apply_lag_alarm() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert_eq!(0, config.apply_lag_alarm_threshold);
+    assert_eq!(5000, config.apply_lag_alarm_duration);
+
+    let config = Config::build(&[
+        "foo",
+        "--apply-lag-alarm-threshold=100",
+        "--apply-lag-alarm-duration=300",
+    ])?;
+    assert_eq!(100, config.apply_lag_alarm_threshold);
+    assert_eq!(Duration::from_millis(300), config.apply_lag_alarm_duration());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_check_storage_before_election() -> anyhow::Result<()> {
+    let config = Config::build(&["foo"])?;
+    assert!(config.check_storage_before_election);
+
+    let config = Config::build(&["foo", "--check-storage-before-election=false"])?;
+    assert!(!config.check_storage_before_election);
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use anyerror::AnyError;
 
 /// Error variants related to configuration.
@@ -26,6 +28,10 @@ pub enum ConfigError {
     #[error("max_payload_entries must be > 0")]
     MaxPayloadIs0,
 
+    /// The `max_append_entries_inflight` configuration must be greater than 0.
+    #[error("max_append_entries_inflight must be > 0")]
+    MaxAppendEntriesInflightIs0,
+
     /// Election timeout must be greater than heartbeat interval.
     #[error("election_timeout_min({election_timeout_min}) must be > heartbeat_interval({heartbeat_interval})")]
     ElectionTimeoutLTHeartBeat {
@@ -35,6 +41,41 @@ pub enum ConfigError {
         heartbeat_interval: u64,
     },
 
+    /// A legitimate candidate would be throttled if the vote request interval is not smaller than
+    /// the election timeout.
+    #[error(
+        "vote_request_min_interval({vote_request_min_interval}) must be < election_timeout_min({election_timeout_min})"
+    )]
+    VoteRequestMinIntervalGEElectionTimeout {
+        /// Minimum interval between vote requests from the same candidate.
+        vote_request_min_interval: u64,
+        /// Minimum election timeout value.
+        election_timeout_min: u64,
+    },
+
+    /// A timer must be shorter than another one in a different unit; they are compared as
+    /// durations.
+    #[error("{shorter}({shorter_value:?}) must be < {longer}({longer_value:?})")]
+    TimerRatio {
+        /// The name of the timer that must be shorter.
+        shorter: &'static str,
+        /// The value of the timer that must be shorter.
+        shorter_value: Duration,
+        /// The name of the timer that must be longer.
+        longer: &'static str,
+        /// The value of the timer that must be longer.
+        longer_value: Duration,
+    },
+
+    /// Invalid timer unit string.
+    #[error("timer unit string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidTimerUnit {
+        /// The invalid unit string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
     /// Invalid snapshot policy string format.
     #[error("snapshot policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
     InvalidSnapshotPolicy {
@@ -44,6 +85,51 @@ pub enum ConfigError {
         syntax: String,
     },
 
+    /// Invalid non-member RPC policy string.
+    #[error("non-member RPC policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidNonMemberRpcPolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
+    /// Invalid blank entry policy string.
+    #[error("blank entry policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidBlankEntryPolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
+    /// Invalid pending write policy string.
+    #[error("pending write policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidPendingWritePolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
+    /// Invalid cold start read policy string.
+    #[error("cold start read policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidColdStartReadPolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
+    /// Invalid purge policy string.
+    #[error("purge policy string is invalid: '{invalid:?}' expect: '{syntax}'")]
+    InvalidPurgePolicy {
+        /// The invalid policy string provided.
+        invalid: String,
+        /// The expected syntax format.
+        syntax: String,
+    },
+
     /// Failed to parse a number from string.
     #[error("{reason} when parsing {invalid:?}")]
     InvalidNumber {
@@ -0,0 +1,170 @@
+//! Log level overrides of subsystems, adjustable at runtime.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+
+use tracing::Level;
+
+/// A subsystem of Openraft whose log level can be overridden at runtime with
+/// [`RuntimeConfigHandle::log_level()`].
+///
+/// [`RuntimeConfigHandle::log_level()`]: crate::raft::RuntimeConfigHandle::log_level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogSubsystem {
+    /// Replicating logs and snapshots from the leader to followers and learners.
+    Replication,
+
+    /// Election timeouts and campaigns.
+    Election,
+
+    /// Writing logs to the log store, and applying them to the state machine.
+    StorageIo,
+
+    /// Building, reading, transmitting and installing snapshots.
+    Snapshot,
+}
+
+impl LogSubsystem {
+    fn index(&self) -> usize {
+        match self {
+            LogSubsystem::Replication => 0,
+            LogSubsystem::Election => 1,
+            LogSubsystem::StorageIo => 2,
+            LogSubsystem::Snapshot => 3,
+        }
+    }
+}
+
+impl fmt::Display for LogSubsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogSubsystem::Replication => write!(f, "replication"),
+            LogSubsystem::Election => write!(f, "election"),
+            LogSubsystem::StorageIo => write!(f, "storage-io"),
+            LogSubsystem::Snapshot => write!(f, "snapshot"),
+        }
+    }
+}
+
+/// The log level overrides shared by the components of a Raft node.
+///
+/// An event of an overridden subsystem is emitted if its level is not more verbose than the
+/// override, even if the tracing subscriber filters it out, see `subsystem_log!()`.
+#[derive(Default)]
+pub(crate) struct LogLevels {
+    /// The overridden level of every subsystem, encoded with [`encode()`]; `0` means no override.
+    subsystems: [AtomicU8; 4],
+
+    /// Whether there is any override in `replication_targets`, to skip locking it.
+    has_replication_targets: AtomicBool,
+
+    /// The overridden level of the replication to a single target, keyed by the display of the
+    /// node id.
+    replication_targets: Mutex<BTreeMap<String, Level>>,
+}
+
+impl LogLevels {
+    pub(crate) fn set(&self, subsystem: LogSubsystem, level: Option<Level>) {
+        self.subsystems[subsystem.index()].store(encode(level), Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self, subsystem: LogSubsystem) -> Option<Level> {
+        decode(self.subsystems[subsystem.index()].load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn set_replication_target(&self, target: String, level: Option<Level>) {
+        let mut targets = self.replication_targets.lock().unwrap();
+
+        match level {
+            Some(level) => targets.insert(target, level),
+            None => targets.remove(&target),
+        };
+
+        self.has_replication_targets.store(!targets.is_empty(), Ordering::Relaxed);
+    }
+
+    /// Return `true` if an event at `level` of `subsystem`, about the replication to `target` if
+    /// any, is enabled by an override.
+    ///
+    /// The override of the replication to `target` applies to both replication and snapshot
+    /// events.
+    pub(crate) fn enables(&self, subsystem: LogSubsystem, target: Option<&dyn fmt::Display>, level: Level) -> bool {
+        if self.get(subsystem).is_some_and(|max| level <= max) {
+            return true;
+        }
+
+        let Some(target) = target else {
+            return false;
+        };
+
+        if !self.has_replication_targets.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        let targets = self.replication_targets.lock().unwrap();
+        targets.get(&target.to_string()).is_some_and(|max| level <= *max)
+    }
+}
+
+fn encode(level: Option<Level>) -> u8 {
+    match level {
+        None => 0,
+        Some(Level::ERROR) => 1,
+        Some(Level::WARN) => 2,
+        Some(Level::INFO) => 3,
+        Some(Level::DEBUG) => 4,
+        Some(Level::TRACE) => 5,
+    }
+}
+
+fn decode(v: u8) -> Option<Level> {
+    match v {
+        1 => Some(Level::ERROR),
+        2 => Some(Level::WARN),
+        3 => Some(Level::INFO),
+        4 => Some(Level::DEBUG),
+        5 => Some(Level::TRACE),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::Level;
+
+    use super::LogLevels;
+    use super::LogSubsystem;
+
+    #[test]
+    fn test_log_levels() {
+        let levels = LogLevels::default();
+        assert!(!levels.enables(LogSubsystem::Replication, None, Level::ERROR));
+
+        levels.set(LogSubsystem::Replication, Some(Level::DEBUG));
+        assert_eq!(Some(Level::DEBUG), levels.get(LogSubsystem::Replication));
+        assert!(levels.enables(LogSubsystem::Replication, None, Level::DEBUG));
+        assert!(!levels.enables(LogSubsystem::Replication, None, Level::TRACE));
+        assert!(!levels.enables(LogSubsystem::Election, None, Level::DEBUG));
+
+        levels.set(LogSubsystem::Replication, None);
+        assert!(!levels.enables(LogSubsystem::Replication, None, Level::DEBUG));
+    }
+
+    #[test]
+    fn test_log_levels_replication_target() {
+        let levels = LogLevels::default();
+
+        levels.set_replication_target("2".to_string(), Some(Level::TRACE));
+        assert!(levels.enables(LogSubsystem::Replication, Some(&2), Level::TRACE));
+        assert!(levels.enables(LogSubsystem::Snapshot, Some(&2), Level::TRACE));
+        assert!(!levels.enables(LogSubsystem::Replication, Some(&3), Level::DEBUG));
+        assert!(!levels.enables(LogSubsystem::Replication, None, Level::DEBUG));
+
+        levels.set_replication_target("2".to_string(), None);
+        assert!(!levels.enables(LogSubsystem::Replication, Some(&2), Level::TRACE));
+    }
+}
@@ -6,6 +6,13 @@
 //!
 //! - [`Config`] - Main configuration for Raft runtime behavior
 //! - [`SnapshotPolicy`] - Policy for triggering automatic snapshots
+//! - [`NonMemberRpcPolicy`] - Policy for RPCs from nodes not in the membership
+//! - [`BlankEntryPolicy`] - Policy for the blank log of a newly elected leader
+//! - [`PurgePolicy`] - Policy for the logs to keep when purging
+//! - [`ColdStartReadPolicy`] - Policy for stale reads before a restarted node discovers the leader
+//! - [`PendingWritePolicy`] - Policy for uncommitted client writes when the leader steps down
+//! - [`TimerUnit`] - The unit of the heartbeat and election timers
+//! - [`LogSubsystem`] - A subsystem whose log level can be overridden at runtime
 //! - [`RuntimeConfig`] - Dynamic configuration that can be changed at runtime
 //! - [`ConfigError`] - Configuration validation errors
 //!
@@ -38,11 +45,19 @@
 #[allow(clippy::module_inception)]
 mod config;
 mod error;
+mod log_levels;
 
 #[cfg(test)]
 mod config_test;
 
+pub use config::BlankEntryPolicy;
+pub use config::ColdStartReadPolicy;
 pub use config::Config;
+pub use config::NonMemberRpcPolicy;
+pub use config::PendingWritePolicy;
+pub use config::PurgePolicy;
 pub(crate) use config::RuntimeConfig;
 pub use config::SnapshotPolicy;
+pub use config::TimerUnit;
 pub use error::ConfigError;
+pub use log_levels::LogSubsystem;
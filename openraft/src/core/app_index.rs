@@ -0,0 +1,62 @@
+//! The index from application keys to the log ids they are applied at.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::storage::AppIndexStore;
+use crate::type_config::alias::LogIdOf;
+
+/// A handle to an [`AppIndexStore`] shared by `RaftCore`, which inserts the keys published by the
+/// state machine, and `Raft`, which looks them up.
+#[derive(Clone)]
+pub(crate) struct AppIndex<C>
+where C: RaftTypeConfig
+{
+    store: Arc<Mutex<Box<dyn AppIndexStore<C>>>>,
+}
+
+impl<C> AppIndex<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(store: Box<dyn AppIndexStore<C>>) -> Self {
+        Self {
+            store: Arc::new(Mutex::new(store)),
+        }
+    }
+
+    pub(crate) fn insert(&self, keys: impl IntoIterator<Item = (String, LogIdOf<C>)>) {
+        let mut store = self.store.lock().unwrap();
+        for (key, log_id) in keys {
+            store.insert(key, log_id);
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<LogIdOf<C>> {
+        self.store.lock().unwrap().get(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AppIndex;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::storage::MemAppIndexStore;
+
+    #[test]
+    fn test_insert_get() {
+        let index = AppIndex::<UTConfig>::new(Box::new(MemAppIndexStore::default()));
+        let shared = index.clone();
+
+        assert_eq!(None, shared.get("a"));
+
+        index.insert([("a".to_string(), log_id(1, 1, 3)), ("b".to_string(), log_id(1, 1, 4))]);
+        assert_eq!(Some(log_id(1, 1, 3)), shared.get("a"));
+        assert_eq!(Some(log_id(1, 1, 4)), shared.get("b"));
+
+        index.insert([("a".to_string(), log_id(2, 1, 5))]);
+        assert_eq!(Some(log_id(2, 1, 5)), shared.get("a"));
+        assert_eq!(None, shared.get("c"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Stream the apply results computed by the leader to followers.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use tracing::Instrument;
+
+use crate::RaftNetworkFactory;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscUnboundedReceiver;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::async_runtime::TryRecvError;
+use crate::core::sm::computed_results::MAX_KEPT;
+use crate::network::RPCOption;
+use crate::network::layer::NetworkLayers;
+use crate::network::v2::RaftNetworkV2;
+use crate::raft::ApplyResultsRequest;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+
+/// A task per follower, each of which sends the apply results in log order through one client.
+pub(crate) struct ApplyResultsSenders<C>
+where C: RaftTypeConfig
+{
+    senders: BTreeMap<C::NodeId, MpscUnboundedSenderOf<C, ApplyResultsRequest<C>>>,
+}
+
+impl<C> ApplyResultsSenders<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            senders: BTreeMap::new(),
+        }
+    }
+
+    /// Send `req` to every one of `targets`.
+    ///
+    /// A task is spawned for a target that does not have one yet; the tasks of the other nodes are
+    /// shut down.
+    pub(crate) async fn send<NF>(
+        &mut self,
+        network_factory: &mut NF,
+        network_layers: &NetworkLayers<C>,
+        timeout: Duration,
+        targets: Vec<(C::NodeId, C::Node)>,
+        req: ApplyResultsRequest<C>,
+    ) where
+        NF: RaftNetworkFactory<C>,
+    {
+        self.senders.retain(|id, _| targets.iter().any(|(target, _)| target == id));
+
+        for (target, node) in targets {
+            if !self.senders.contains_key(&target) {
+                let client = network_factory.new_client(target.clone(), &node).await;
+                let (tx, rx) = C::mpsc_unbounded();
+
+                let name = format!("openraft-apply-results->{}", target);
+                let fu = Self::run(target.clone(), client, network_layers.clone(), timeout, rx);
+
+                #[allow(clippy::let_underscore_future)]
+                let _ = C::spawn_named(&name, fu.instrument(tracing::debug_span!("send_apply_results")));
+                self.senders.insert(target.clone(), tx);
+            }
+
+            // Safe unwrap(): inserted above
+            let _ = self.senders.get(&target).unwrap().send(req.clone());
+        }
+    }
+
+    /// Shut down every task, e.g., when this node is no longer the leader.
+    pub(crate) fn shutdown(&mut self) {
+        self.senders.clear();
+    }
+
+    /// Send the queued requests one by one, until the sender is dropped.
+    ///
+    /// Requests queued while sending are merged into one. If more than [`MAX_KEPT`] results are
+    /// queued, the oldest are dropped: the follower does not keep more than that, and computes
+    /// the results of these entries itself.
+    async fn run<N>(
+        target: C::NodeId,
+        mut client: N,
+        layers: NetworkLayers<C>,
+        timeout: Duration,
+        mut rx: MpscUnboundedReceiverOf<C, ApplyResultsRequest<C>>,
+    ) where
+        N: RaftNetworkV2<C>,
+    {
+        while let Some(mut req) = rx.recv().await {
+            loop {
+                match rx.try_recv() {
+                    Ok(next) => {
+                        req.from_leader = next.from_leader;
+                        req.results.extend(next.results);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            }
+
+            if req.results.len() > MAX_KEPT {
+                req.results.drain(..req.results.len() - MAX_KEPT);
+            }
+
+            let res = C::timeout(timeout, layers.call(&target, &mut client, req, RPCOption::new(timeout))).await;
+            match res {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    tracing::debug!(
+                        target = display(&target),
+                        error = display(&e),
+                        "fail to send apply results"
+                    );
+                }
+                Err(_timeout) => {
+                    tracing::debug!(target = display(&target), "timeout sending apply results");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Track the elections started by this node and their outcome.
+
+use crate::RaftTypeConfig;
+use crate::metrics::ElectionMetrics;
+use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// Accumulates [`ElectionMetrics`].
+///
+/// The outcome of an election is not reported by the engine; it is observed from the vote, the
+/// candidate and the leader of the engine whenever metrics are reported.
+pub(crate) struct ElectionStats<C>
+where C: RaftTypeConfig
+{
+    id: C::NodeId,
+
+    metrics: ElectionMetrics,
+
+    /// The vote of the election started by this node that is neither won nor lost yet.
+    pending: Option<VoteOf<C>>,
+
+    /// The greatest term observed.
+    term: C::Term,
+}
+
+impl<C> ElectionStats<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(id: C::NodeId, term: C::Term) -> Self {
+        Self {
+            id,
+            metrics: ElectionMetrics::default(),
+            pending: None,
+            term,
+        }
+    }
+
+    /// Called when this node starts an election with `vote`.
+    ///
+    /// An election that is still pending has timed out without being granted by a quorum.
+    pub(crate) fn on_start(&mut self, reason: &str, vote: VoteOf<C>) {
+        if self.pending.take().is_some() {
+            self.lose("election timed out without being granted by a quorum".to_string());
+        }
+
+        self.metrics.started += 1;
+        self.metrics.last_start_reason = Some(reason.to_string());
+        self.pending = Some(vote);
+    }
+
+    /// Called when a vote response rejects the election of this node.
+    pub(crate) fn on_vote_rejected(&mut self) {
+        self.metrics.votes_rejected += 1;
+    }
+
+    /// Called when the election this node was about to start is vetoed for `reason`.
+    pub(crate) fn on_vetoed(&mut self, reason: String) {
+        self.metrics.vetoed += 1;
+        self.metrics.last_veto_reason = Some(reason);
+    }
+
+    /// Update the outcome of the pending election and the greatest term seen.
+    ///
+    /// `vote` is the current vote of this node; `candidate` and `leader` are the votes of the
+    /// election in progress and of the established leadership of this node, if any.
+    pub(crate) fn observe(&mut self, vote: &VoteOf<C>, candidate: Option<&VoteOf<C>>, leader: Option<&VoteOf<C>>) {
+        let term = vote.term();
+        if term > self.term {
+            self.term = term;
+            if vote.leader_node_id() != Some(&self.id) {
+                self.metrics.higher_term_seen += 1;
+            }
+        }
+
+        let Some(pending) = self.pending.as_ref() else {
+            return;
+        };
+
+        if candidate.map(|c| c.leader_id()) == Some(pending.leader_id()) {
+            return;
+        }
+
+        if leader.map(|l| l.leader_id()) == Some(pending.leader_id()) {
+            self.metrics.won += 1;
+        } else {
+            self.lose(format!("saw a greater vote: {}", vote));
+        }
+        self.pending = None;
+    }
+
+    pub(crate) fn metrics(&self) -> ElectionMetrics {
+        self.metrics.clone()
+    }
+
+    /// Why the last vetoed election was vetoed.
+    pub(crate) fn last_veto_reason(&self) -> Option<&str> {
+        self.metrics.last_veto_reason.as_deref()
+    }
+
+    fn lose(&mut self, reason: String) {
+        self.metrics.lost += 1;
+        self.metrics.last_loss_reason = Some(reason);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ElectionStats;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+
+    #[test]
+    fn test_election_stats_won_and_lost() {
+        let mut s = ElectionStats::<UTConfig>::new(1, 0);
+
+        // Won
+        let v1 = Vote::new(1, 1);
+        s.on_start("election timeout", v1);
+        s.observe(&v1, Some(&v1), None);
+        s.on_vote_rejected();
+        s.observe(&Vote::new_committed(1, 1), None, Some(&Vote::new_committed(1, 1)));
+
+        // Timed out, then lost to a greater vote
+        let v2 = Vote::new(2, 1);
+        s.on_start("election timeout", v2);
+        let v3 = Vote::new(3, 1);
+        s.on_start("triggered by application", v3);
+        s.observe(&Vote::new(4, 2), None, None);
+
+        let m = s.metrics();
+        assert_eq!(3, m.started);
+        assert_eq!(1, m.won);
+        assert_eq!(2, m.lost);
+        assert_eq!(1, m.votes_rejected);
+        assert_eq!(1, m.higher_term_seen);
+        assert_eq!(Some("triggered by application".to_string()), m.last_start_reason);
+        assert_eq!(Some("saw a greater vote: <T4-N2:->".to_string()), m.last_loss_reason);
+    }
+
+    #[test]
+    fn test_election_stats_higher_term() {
+        let mut s = ElectionStats::<UTConfig>::new(1, 2);
+
+        s.observe(&Vote::new(2, 2), None, None);
+        assert_eq!(0, s.metrics().higher_term_seen, "not greater than the initial term");
+
+        s.observe(&Vote::new(3, 1), None, None);
+        assert_eq!(0, s.metrics().higher_term_seen, "the term of this node");
+
+        s.observe(&Vote::new_committed(5, 3), None, None);
+        assert_eq!(1, s.metrics().higher_term_seen);
+    }
+
+    #[test]
+    fn test_election_stats_vetoed() {
+        let mut s = ElectionStats::<UTConfig>::new(1, 0);
+
+        s.on_vetoed("disk unhealthy".to_string());
+        s.on_vetoed("clock not synced".to_string());
+
+        let m = s.metrics();
+        assert_eq!(0, m.started);
+        assert_eq!(2, m.vetoed);
+        assert_eq!(Some("clock not synced".to_string()), m.last_veto_reason);
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt;
 use crate::RaftTypeConfig;
 use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
+use crate::raft::LogChecksum;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
@@ -31,6 +32,10 @@ where C: RaftTypeConfig
     /// When there are no new logs to replicate, the Leader sends a heartbeat to replicate committed
     /// log id to followers to update their committed log id.
     pub(crate) committed: Option<LogIdOf<C>>,
+
+    /// The checksum of a range of committed log entries for followers to verify, if one is
+    /// computed since the last heartbeat.
+    pub(crate) log_checksum: Option<LogChecksum<C>>,
 }
 
 impl<C> HeartbeatEvent<C>
@@ -41,8 +46,14 @@ where C: RaftTypeConfig
             time,
             session_id,
             committed,
+            log_checksum: None,
         }
     }
+
+    pub(crate) fn with_log_checksum(mut self, log_checksum: Option<LogChecksum<C>>) -> Self {
+        self.log_checksum = log_checksum;
+        self
+    }
 }
 
 impl<C> fmt::Display for HeartbeatEvent<C>
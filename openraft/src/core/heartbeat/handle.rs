@@ -12,6 +12,8 @@ use crate::async_runtime::watch::WatchSender;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::heartbeat::worker::HeartbeatWorker;
 use crate::core::notification::Notification;
+use crate::network::NetworkEventBus;
+use crate::network::layer::NetworkLayers;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::JoinHandleOf;
 use crate::type_config::alias::MpscSenderOf;
@@ -63,6 +65,8 @@ where C: RaftTypeConfig
         &mut self,
         network_factory: &mut NF,
         tx_notification: &MpscSenderOf<C, Notification<C>>,
+        network_events: &Arc<NetworkEventBus<C>>,
+        network_layers: &NetworkLayers<C>,
         targets: impl IntoIterator<Item = (C::NodeId, C::Node)>,
     ) where
         NF: RaftNetworkFactory<C>,
@@ -79,13 +83,16 @@ where C: RaftTypeConfig
                 node,
                 config: self.config.clone(),
                 tx_notification: tx_notification.clone(),
+                network_events: network_events.clone(),
+                network_layers: network_layers.clone(),
             };
 
             let span = tracing::span!(parent: &Span::current(), Level::DEBUG, "heartbeat", id=display(&self.id), target=display(&target));
 
             let (tx_shutdown, rx_shutdown) = C::oneshot();
 
-            let worker_handle = C::spawn(worker.run(rx_shutdown).instrument(span));
+            let name = format!("openraft-heartbeat-{}->{}", self.id, target);
+            let worker_handle = C::spawn_named(&name, worker.run(rx_shutdown).instrument(span));
             self.workers.insert(target, (tx_shutdown, worker_handle));
         }
     }
@@ -1,17 +1,22 @@
 use std::fmt;
 use std::sync::Arc;
-use std::time::Duration;
 
 use futures::FutureExt;
 
 use crate::Config;
 use crate::RaftTypeConfig;
 use crate::async_runtime::watch::WatchReceiver;
+use crate::base::catch_panic::catch_panic;
 use crate::core::heartbeat::errors::RaftCoreClosed;
 use crate::core::heartbeat::errors::Stopped;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::notification::Notification;
+use crate::error::RPCError;
+use crate::error::Timeout;
+use crate::network::NetworkEventBus;
 use crate::network::RPCOption;
+use crate::network::RPCTypes;
+use crate::network::layer::NetworkLayers;
 use crate::network::v2::RaftNetworkV2;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
@@ -47,6 +52,12 @@ where
     ///
     /// [`RaftCore`]: crate::core::RaftCore
     pub(crate) tx_notification: MpscSenderOf<C, Notification<C>>,
+
+    /// Delivers connection lifecycle events to the application.
+    pub(crate) network_events: Arc<NetworkEventBus<C>>,
+
+    /// The layers applied to every heartbeat sent to the target.
+    pub(crate) network_layers: NetworkLayers<C>,
 }
 
 impl<C, N> fmt::Display for HeartbeatWorker<C, N>
@@ -65,8 +76,18 @@ where
     N: RaftNetworkV2<C>,
 {
     pub(crate) async fn run(self, rx_shutdown: OneshotReceiverOf<C, ()>) {
-        let res = self.do_run(rx_shutdown).await;
-        tracing::info!("HeartbeatWorker finished with result: {:?}", res);
+        let task = format!("heartbeat(target={})", self.target);
+        let tx_notification = self.tx_notification.clone();
+
+        match catch_panic(task, self.do_run(rx_shutdown)).await {
+            Ok(res) => {
+                tracing::info!("HeartbeatWorker finished with result: {:?}", res);
+            }
+            Err(error) => {
+                tracing::error!("HeartbeatWorker panicked: {}", error);
+                tx_notification.send(Notification::TaskPanicked { error }).await.ok();
+            }
+        }
     }
 
     pub(crate) async fn do_run(mut self, mut rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<(), Stopped> {
@@ -88,7 +109,7 @@ where
                 continue;
             };
 
-            let timeout = Duration::from_millis(self.config.heartbeat_interval);
+            let timeout = self.config.heartbeat_interval();
             let option = RPCOption::new(timeout);
 
             let payload = AppendEntriesRequest {
@@ -98,18 +119,28 @@ where
                 prev_log_id: heartbeat.committed.clone(),
                 leader_commit: heartbeat.committed.clone(),
                 entries: vec![],
+                cluster_name: Some(self.config.cluster_name.clone()),
+                trace_ids: Default::default(),
+                log_checksum: heartbeat.log_checksum.clone(),
             };
 
-            let res = C::timeout(timeout, self.network.append_entries(payload, option)).await;
+            let res = C::timeout(
+                timeout,
+                self.network_layers.call(&self.target, &mut self.network, payload, option),
+            )
+            .await;
             tracing::debug!("{} sent a heartbeat: {}, result: {:?}", self, heartbeat, res);
 
             match res {
                 Ok(Ok(x)) => {
+                    self.network_events.on_success(&self.target);
+
                     let response: AppendEntriesResponse<C> = x;
 
                     match response {
                         AppendEntriesResponse::Success => {}
                         AppendEntriesResponse::PartialSuccess(_matching) => {}
+                        AppendEntriesResponse::Busy { .. } => {}
                         AppendEntriesResponse::HigherVote(vote) => {
                             tracing::debug!(
                                 "seen a higher vote({vote}) from {}; when:(sending heartbeat)",
@@ -148,8 +179,20 @@ where
 
                     self.send_notification(noti, "send HeartbeatProgress").await?;
                 }
-                _ => {
-                    tracing::warn!("{} failed to send a heartbeat: {:?}", self, res);
+                Ok(Err(err)) => {
+                    tracing::warn!("{} failed to send a heartbeat: {}", self, err);
+                    self.network_events.on_error(&self.target, &err);
+                }
+                Err(_elapsed) => {
+                    tracing::warn!("{} timeout sending a heartbeat: {:?}", self, timeout);
+
+                    let err = RPCError::Timeout(Timeout {
+                        action: RPCTypes::AppendEntries,
+                        id: self.id.clone(),
+                        target: self.target.clone(),
+                        timeout,
+                    });
+                    self.network_events.on_error(&self.target, &err);
                 }
             }
         }
@@ -0,0 +1,113 @@
+//! Access node-local entries in a task outside `RaftCore`, so that a slow local entry IO never
+//! delays heartbeats, elections or other messages.
+
+use tracing::Instrument;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::async_runtime::MpscUnboundedReceiver;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::async_runtime::OneshotSender;
+use crate::core::raft_msg::ResultSender;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+
+/// A request to the local entries task, responded to the caller with `tx`.
+pub(crate) enum LocalEntriesRequest<C>
+where C: RaftTypeConfig
+{
+    /// Append a local entry.
+    Append {
+        data: Vec<u8>,
+        tx: ResultSender<C, u64, StorageError<C>>,
+    },
+
+    /// Read at most `max` local entries since sequence number `start`.
+    Read {
+        start: u64,
+        max: u64,
+        tx: ResultSender<C, Vec<(u64, Vec<u8>)>, StorageError<C>>,
+    },
+
+    /// Remove local entries up to sequence number `upto`, inclusive.
+    Purge {
+        upto: u64,
+        tx: ResultSender<C, (), StorageError<C>>,
+    },
+}
+
+/// The handle of the task that accesses local entries.
+///
+/// The task is shut down when the handle is dropped.
+pub(crate) struct LocalEntriesHandle<C>
+where C: RaftTypeConfig
+{
+    tx: MpscUnboundedSenderOf<C, LocalEntriesRequest<C>>,
+}
+
+impl<C> LocalEntriesHandle<C>
+where C: RaftTypeConfig
+{
+    /// Spawn a task that accesses local entries with `log_reader`.
+    ///
+    /// Requests are handled one at a time, in the order they are sent.
+    pub(crate) fn spawn<LR>(log_reader: LR) -> Self
+    where LR: RaftLogReader<C> {
+        let (tx, rx) = C::mpsc_unbounded();
+
+        let worker = LocalEntriesWorker { log_reader, rx };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn_named(
+            "openraft-local-entries",
+            worker.run().instrument(tracing::debug_span!("local_entries")),
+        );
+
+        Self { tx }
+    }
+
+    /// Send a request to the task.
+    ///
+    /// If the task has quit, the request is dropped along with its `tx`.
+    pub(crate) fn send(&self, req: LocalEntriesRequest<C>) {
+        let _ = self.tx.send(req);
+    }
+}
+
+struct LocalEntriesWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    log_reader: LR,
+    rx: MpscUnboundedReceiverOf<C, LocalEntriesRequest<C>>,
+}
+
+impl<C, LR> LocalEntriesWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    async fn run(mut self) {
+        while let Some(req) = self.rx.recv().await {
+            // Local entries do not affect consensus: a storage error is returned to the caller
+            // instead of stopping `RaftCore`.
+            match req {
+                LocalEntriesRequest::Append { data, tx } => {
+                    let res = self.log_reader.append_local(data).await;
+                    let _ = tx.send(res);
+                }
+                LocalEntriesRequest::Read { start, max, tx } => {
+                    let res = self.log_reader.read_local(start, max).await;
+                    let _ = tx.send(res);
+                }
+                LocalEntriesRequest::Purge { upto, tx } => {
+                    let res = self.log_reader.purge_local(upto).await;
+                    let _ = tx.send(res);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Archive logs with the [`LogArchiver`] set by the application, in a task outside `RaftCore`.
+
+use std::time::Duration;
+
+use anyerror::AnyError;
+use tracing::Instrument;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscUnboundedReceiver;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::async_runtime::TryRecvError;
+use crate::core::notification::Notification;
+use crate::storage::LogArchiver;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::MpscSenderOf;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+use crate::type_config::async_runtime::mpsc::MpscSender;
+
+/// How long to wait before archiving again after the archiver fails.
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The handle of the task that archives logs.
+///
+/// The task is shut down when the handle is dropped, after the chunk being archived.
+pub(crate) struct LogArchiveHandle<C>
+where C: RaftTypeConfig
+{
+    tx: MpscUnboundedSenderOf<C, (u64, u64)>,
+
+    /// The end of the last range sent to the task, exclusive.
+    requested_end: u64,
+}
+
+impl<C> LogArchiveHandle<C>
+where C: RaftTypeConfig
+{
+    /// Spawn a task that archives logs with `archiver`, reading them with `log_reader`, in chunks
+    /// of at most `chunk_size` entries.
+    ///
+    /// After a chunk is archived, the task sends [`Notification::LogsArchived`] to `RaftCore`.
+    pub(crate) fn spawn<LR>(
+        archiver: Box<dyn LogArchiver<C>>,
+        log_reader: LR,
+        chunk_size: u64,
+        tx_notification: MpscSenderOf<C, Notification<C>>,
+    ) -> Self
+    where
+        LR: RaftLogReader<C>,
+    {
+        let (tx, rx) = C::mpsc_unbounded();
+
+        let worker = LogArchiveWorker {
+            archiver,
+            log_reader,
+            chunk_size: std::cmp::max(chunk_size, 1),
+            tx_notification,
+            rx,
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn_named(
+            "openraft-log-archive",
+            worker.run().instrument(tracing::debug_span!("log_archive")),
+        );
+
+        Self { tx, requested_end: 0 }
+    }
+
+    /// Ask the task to archive the logs in `[start, end)`, if they are not yet requested.
+    pub(crate) fn archive(&mut self, start: u64, end: u64) {
+        if end <= std::cmp::max(start, self.requested_end) {
+            return;
+        }
+
+        tracing::debug!("request to archive logs [{}, {})", start, end);
+
+        self.requested_end = end;
+        let _ = self.tx.send((start, end));
+    }
+}
+
+struct LogArchiveWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    archiver: Box<dyn LogArchiver<C>>,
+    log_reader: LR,
+    chunk_size: u64,
+    tx_notification: MpscSenderOf<C, Notification<C>>,
+    rx: MpscUnboundedReceiverOf<C, (u64, u64)>,
+}
+
+impl<C, LR> LogArchiveWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    async fn run(mut self) {
+        // The range left to archive.
+        let (mut start, mut end) = (0, 0);
+
+        loop {
+            // Wait for a request if there is nothing to archive, otherwise take the latest one.
+            let req = if start >= end {
+                match self.rx.recv().await {
+                    Some(req) => Some(req),
+                    None => return,
+                }
+            } else {
+                match self.rx.try_recv() {
+                    Ok(req) => Some(req),
+                    Err(TryRecvError::Empty) => None,
+                    Err(TryRecvError::Disconnected) => return,
+                }
+            };
+
+            if let Some((req_start, req_end)) = req {
+                start = std::cmp::max(start, req_start);
+                end = std::cmp::max(end, req_end);
+                continue;
+            }
+
+            let chunk_end = std::cmp::min(end, start + self.chunk_size);
+
+            if let Err(e) = self.archive(start, chunk_end).await {
+                tracing::warn!(
+                    "failed to archive logs [{}, {}), retry in {:?}: {}",
+                    start,
+                    chunk_end,
+                    RETRY_INTERVAL,
+                    e
+                );
+                C::sleep(RETRY_INTERVAL).await;
+                continue;
+            }
+
+            start = chunk_end;
+
+            let notify = Notification::LogsArchived { next: start };
+            if self.tx_notification.send(notify).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Archive the logs in `[start, end)` that are still in the log store.
+    async fn archive(&mut self, start: u64, end: u64) -> Result<(), AnyError> {
+        let entries = self.log_reader.try_get_log_entries(start..end).await.map_err(|e| AnyError::new(&e))?;
+
+        if !entries.is_empty() {
+            self.archiver.archive(entries).await.map_err(|e| AnyError::new(&e))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+//! Compute and verify log checksums in a task outside `RaftCore`, so that a heartbeat or an
+//! AppendEntries request never waits for the log store.
+
+use tracing::Instrument;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscUnboundedReceiver;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::core::notification::Notification;
+use crate::display_ext::DisplayOptionExt;
+use crate::raft::LogChecksum;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::MpscSenderOf;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+use crate::type_config::async_runtime::mpsc::MpscSender;
+
+/// A request to the log checksum task.
+pub(crate) enum LogChecksumRequest<C>
+where C: RaftTypeConfig
+{
+    /// Compute the checksum of the logs in `[start, end)`, to send with a heartbeat.
+    Compute { start: u64, end: u64 },
+
+    /// Compute the checksum of the local copy of the range of a checksum sent by the leader.
+    Verify { expected: LogChecksum<C> },
+}
+
+/// The handle of the task that reads logs to compute their checksums.
+///
+/// The task is shut down when the handle is dropped.
+pub(crate) struct LogChecksumHandle<C>
+where C: RaftTypeConfig
+{
+    tx: MpscUnboundedSenderOf<C, LogChecksumRequest<C>>,
+
+    /// Whether a checksum to send with heartbeats is being computed.
+    computing: bool,
+
+    /// The checksum computed by the task, to send with the next heartbeat.
+    ready: Option<LogChecksum<C>>,
+}
+
+impl<C> LogChecksumHandle<C>
+where C: RaftTypeConfig
+{
+    /// Spawn a task that reads logs with `log_reader` to compute their checksums.
+    ///
+    /// The task sends [`Notification::LogChecksumComputed`] or
+    /// [`Notification::LogChecksumVerified`] to `RaftCore` when a request is done.
+    pub(crate) fn spawn<LR>(log_reader: LR, tx_notification: MpscSenderOf<C, Notification<C>>) -> Self
+    where LR: RaftLogReader<C> {
+        let (tx, rx) = C::mpsc_unbounded();
+
+        let worker = LogChecksumWorker {
+            log_reader,
+            tx_notification,
+            rx,
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn_named(
+            "openraft-log-checksum",
+            worker.run().instrument(tracing::debug_span!("log_checksum")),
+        );
+
+        Self {
+            tx,
+            computing: false,
+            ready: None,
+        }
+    }
+
+    /// Whether a checksum to send with heartbeats is being computed.
+    pub(crate) fn is_computing(&self) -> bool {
+        self.computing
+    }
+
+    /// Take the computed checksum to send with a heartbeat, if any.
+    pub(crate) fn take_ready(&mut self) -> Option<LogChecksum<C>> {
+        self.ready.take()
+    }
+
+    /// Ask the task to compute the checksum of the logs in `[start, end)`.
+    pub(crate) fn compute(&mut self, start: u64, end: u64) {
+        tracing::debug!("request to compute the checksum of logs [{}, {})", start, end);
+
+        self.computing = true;
+        let _ = self.tx.send(LogChecksumRequest::Compute { start, end });
+    }
+
+    /// Store the checksum computed by the task, to send with the next heartbeat.
+    pub(crate) fn on_computed(&mut self, log_checksum: Option<LogChecksum<C>>) {
+        self.computing = false;
+        self.ready = log_checksum;
+    }
+
+    /// Ask the task to verify the local copy of the range of `expected`.
+    pub(crate) fn verify(&self, expected: LogChecksum<C>) {
+        tracing::debug!("request to verify log checksum: {}", expected);
+
+        let _ = self.tx.send(LogChecksumRequest::Verify { expected });
+    }
+}
+
+struct LogChecksumWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    log_reader: LR,
+    tx_notification: MpscSenderOf<C, Notification<C>>,
+    rx: MpscUnboundedReceiverOf<C, LogChecksumRequest<C>>,
+}
+
+impl<C, LR> LogChecksumWorker<C, LR>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    async fn run(mut self) {
+        while let Some(req) = self.rx.recv().await {
+            let notify = match req {
+                LogChecksumRequest::Compute { start, end } => {
+                    let log_checksum = self.checksum(start, end).await;
+                    tracing::debug!(log_checksum = display(log_checksum.display()), "computed log checksum");
+                    Notification::LogChecksumComputed { log_checksum }
+                }
+                LogChecksumRequest::Verify { expected } => {
+                    let got = self.checksum(expected.start, expected.last.index() + 1).await;
+                    Notification::LogChecksumVerified { expected, got }
+                }
+            };
+
+            if self.tx_notification.send(notify).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Compute the checksum of the logs in `[start, end)`.
+    ///
+    /// It returns `None` if any of them cannot be read, e.g., they are purged.
+    async fn checksum(&mut self, start: u64, end: u64) -> Option<LogChecksum<C>> {
+        let entries = match self.log_reader.try_get_log_entries(start..end).await {
+            Ok(x) => x,
+            Err(e) => {
+                tracing::warn!(
+                    error = display(&e),
+                    "failed to read logs [{}, {}) to checksum",
+                    start,
+                    end
+                );
+                return None;
+            }
+        };
+
+        if entries.len() as u64 != end - start {
+            tracing::debug!("logs [{}, {}) to checksum are purged", start, end);
+            return None;
+        }
+
+        let reader = &self.log_reader;
+        LogChecksum::from_entries(&entries, |e| reader.entry_checksum(e))
+    }
+}
@@ -23,21 +23,36 @@
 //! See the [Engine/Runtime architecture guide](crate::docs::components::engine_runtime) for
 //! details.
 
+pub(crate) mod app_index;
+pub(crate) mod apply_results_sender;
 pub(crate) mod balancer;
 pub(crate) mod core_state;
+pub(crate) mod election_stats;
 pub(crate) mod heartbeat;
 pub(crate) mod io_flush_tracking;
+pub(crate) mod local_entries;
+pub(crate) mod log_archive;
+pub(crate) mod log_checksum;
+pub(crate) mod node_infos;
 pub(crate) mod notification;
 mod raft_core;
 pub(crate) mod raft_msg;
+mod read_batch;
 mod replication_state;
 mod server_state;
 pub(crate) mod sm;
+pub(crate) mod storage_check;
 mod tick;
+pub(crate) mod trace_ids;
+pub(crate) mod transition_stats;
+mod vote_rate_limiter;
+pub(crate) mod write_latency;
 
 pub(crate) use raft_core::ApplyResult;
 pub use raft_core::RaftCore;
+pub(crate) use read_batch::ReadBatch;
 pub(crate) use replication_state::replication_lag;
 pub use server_state::ServerState;
 pub(crate) use tick::Tick;
 pub(crate) use tick::TickHandle;
+pub(crate) use vote_rate_limiter::VoteRateLimiter;
@@ -0,0 +1,77 @@
+//! The software version and capabilities of this node and of the nodes it has exchanged
+//! [`HelloRequest`](crate::raft::HelloRequest) with.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::raft::NodeInfo;
+
+/// Node infos shared by `Raft`, which receives hello requests from other nodes, and `RaftCore`,
+/// which sends hello requests to replication targets and reports the infos in metrics.
+#[derive(Clone)]
+pub(crate) struct NodeInfos<C>
+where C: RaftTypeConfig
+{
+    local: Arc<NodeInfo>,
+    peers: Arc<Mutex<BTreeMap<C::NodeId, NodeInfo>>>,
+}
+
+impl<C> NodeInfos<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(local: NodeInfo) -> Self {
+        Self {
+            local: Arc::new(local),
+            peers: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// The info of this node.
+    pub(crate) fn local(&self) -> &NodeInfo {
+        &self.local
+    }
+
+    /// Record the info received from another node, replacing the previous one.
+    pub(crate) fn insert(&self, id: C::NodeId, info: NodeInfo) {
+        self.peers.lock().unwrap().insert(id, info);
+    }
+
+    /// Returns whether the info of node `id` is known.
+    pub(crate) fn contains(&self, id: &C::NodeId) -> bool {
+        self.peers.lock().unwrap().contains_key(id)
+    }
+
+    /// Returns `true` if node `id` is known not to support `capability`.
+    ///
+    /// A node whose info is not known yet, e.g., one that does not implement the hello RPC, is
+    /// assumed to support it, so that the behavior does not change before the infos are exchanged.
+    pub(crate) fn lacks(&self, id: &C::NodeId, capability: &str) -> bool {
+        self.peers.lock().unwrap().get(id).is_some_and(|info| !info.supports(capability))
+    }
+
+    /// Returns the first of the compression `schemes` node `id` supports.
+    ///
+    /// A node whose info is not known yet is not sent compressed payloads.
+    pub(crate) fn compression(&self, id: &C::NodeId, schemes: &[String]) -> Option<String> {
+        let peers = self.peers.lock().unwrap();
+        peers.get(id)?.negotiate_compression(schemes).map(String::from)
+    }
+
+    /// The compression scheme negotiated with every known node that supports one of `schemes`.
+    pub(crate) fn all_compression(&self, schemes: &[String]) -> BTreeMap<C::NodeId, String> {
+        let peers = self.peers.lock().unwrap();
+        peers
+            .iter()
+            .filter_map(|(id, info)| Some((id.clone(), info.negotiate_compression(schemes)?.to_string())))
+            .collect()
+    }
+
+    /// The infos of all known nodes, including this node `self_id`.
+    pub(crate) fn all(&self, self_id: &C::NodeId) -> BTreeMap<C::NodeId, NodeInfo> {
+        let mut all = self.peers.lock().unwrap().clone();
+        all.insert(self_id.clone(), self.local.as_ref().clone());
+        all
+    }
+}
@@ -4,11 +4,16 @@ use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::core::sm;
 use crate::display_ext::DisplayInstantExt;
+use crate::display_ext::DisplayOptionExt;
+use crate::error::TaskPanicked;
+use crate::raft::LogChecksum;
 use crate::raft::VoteResponse;
 use crate::raft_state::IOId;
 use crate::replication;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::VoteOf;
 use crate::vote::committed::CommittedVote;
 use crate::vote::non_committed::NonCommittedVote;
@@ -46,6 +51,48 @@ where C: RaftTypeConfig
     /// and [`RaftCore`](`crate::core::RaftCore`) needs to shutdown.
     StorageError { error: StorageError<C> },
 
+    /// A replication stream panicked.
+    ///
+    /// [`RaftCore`](`crate::core::RaftCore`) either restarts the stream or shuts down, according
+    /// to [`Config::restart_replication_on_panic`](`crate::Config::restart_replication_on_panic`).
+    ReplicationPanicked {
+        target: C::NodeId,
+        session_id: ReplicationSessionId<C>,
+        error: TaskPanicked,
+    },
+
+    /// An internal task other than a replication stream panicked,
+    /// and [`RaftCore`](`crate::core::RaftCore`) needs to shutdown.
+    TaskPanicked { error: TaskPanicked },
+
+    /// The state machine worker cannot read the committed logs in `[first, last]` to apply, and
+    /// asks [`RaftCore`](`crate::core::RaftCore`) to fetch a copy of them from the leader.
+    ///
+    /// `tx` receives `None` if no copy is fetched this time and the worker should ask again. It
+    /// is dropped if the logs are not to be repaired.
+    RepairLog {
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Option<Vec<C::Entry>>>,
+    },
+
+    /// The logs before `next` are archived by the log archiver, and can be purged.
+    LogsArchived { next: u64 },
+
+    /// The checksum of a log range to send with heartbeats is computed, or `None` if the logs
+    /// cannot be read.
+    LogChecksumComputed { log_checksum: Option<LogChecksum<C>> },
+
+    /// The checksum of the local copy of the range of `expected`, sent by the leader, is
+    /// computed, or `None` if the logs cannot be read.
+    LogChecksumVerified {
+        expected: LogChecksum<C>,
+        got: Option<LogChecksum<C>>,
+    },
+
+    /// The storage check before an election is done: `Err` tells why the storage fails it.
+    StorageChecked { result: Result<(), String> },
+
     /// Completion of an IO operation to local store.
     LocalIO { io_id: IOId<C> },
 
@@ -72,6 +119,10 @@ where C: RaftTypeConfig
         /// ith tick
         i: u64,
     },
+
+    /// The open batch of linearizable reads has waited for `Config::read_index_batch_delay`, wake
+    /// up RaftCore to confirm the leadership for them.
+    ReadBatchDue,
 }
 
 impl<C> Notification<C>
@@ -110,6 +161,30 @@ where C: RaftTypeConfig
                 )
             }
             Self::StorageError { error } => write!(f, "StorageError: {}", error),
+            Self::ReplicationPanicked {
+                target,
+                session_id,
+                error,
+            } => {
+                write!(
+                    f,
+                    "ReplicationPanicked: target={}, session_id: {}, {}",
+                    target, session_id, error
+                )
+            }
+            Self::TaskPanicked { error } => write!(f, "TaskPanicked: {}", error),
+            Self::RepairLog { first, last, .. } => write!(f, "RepairLog: [{}, {}]", first, last),
+            Self::LogsArchived { next } => write!(f, "LogsArchived: next: {}", next),
+            Self::LogChecksumComputed { log_checksum } => {
+                write!(f, "LogChecksumComputed: {}", log_checksum.display())
+            }
+            Self::LogChecksumVerified { expected, got } => {
+                write!(f, "LogChecksumVerified: expected: {}, got: {}", expected, got.display())
+            }
+            Self::StorageChecked { result } => match result {
+                Ok(()) => write!(f, "StorageChecked: Ok"),
+                Err(e) => write!(f, "StorageChecked: Err: {}", e),
+            },
             Self::LocalIO { io_id } => write!(f, "IOFlushed: {}", io_id),
             Self::ReplicationProgress { has_payload, progress } => {
                 let payload = if *has_payload { "no-payload" } else { "has-payload" };
@@ -134,6 +209,7 @@ where C: RaftTypeConfig
             Self::Tick { i } => {
                 write!(f, "Tick {}", i)
             }
+            Self::ReadBatchDue => write!(f, "ReadBatchDue"),
         }
     }
 }
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -9,6 +10,7 @@ use anyerror::AnyError;
 use futures::FutureExt;
 use futures::StreamExt;
 use futures::TryFutureExt;
+use futures::future::Either;
 use futures::stream::FuturesUnordered;
 use maplit::btreeset;
 use tracing::Instrument;
@@ -16,6 +18,8 @@ use tracing::Level;
 use tracing::Span;
 
 use crate::ChangeMembers;
+use crate::EffectiveMembership;
+use crate::EntryPayload;
 use crate::Instant;
 use crate::Membership;
 use crate::RaftTypeConfig;
@@ -26,21 +30,40 @@ use crate::async_runtime::OneshotSender;
 use crate::async_runtime::TryRecvError;
 use crate::async_runtime::watch::WatchSender;
 use crate::config::Config;
+use crate::config::PendingWritePolicy;
 use crate::config::RuntimeConfig;
+use crate::core::ReadBatch;
 use crate::core::ServerState;
+use crate::core::VoteRateLimiter;
+use crate::core::app_index::AppIndex;
+use crate::core::apply_results_sender::ApplyResultsSenders;
 use crate::core::balancer::Balancer;
 use crate::core::core_state::CoreState;
+use crate::core::election_stats::ElectionStats;
 use crate::core::heartbeat::event::HeartbeatEvent;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
 use crate::core::io_flush_tracking::IoProgressSender;
+use crate::core::local_entries::LocalEntriesHandle;
+use crate::core::local_entries::LocalEntriesRequest;
+use crate::core::log_archive::LogArchiveHandle;
+use crate::core::log_checksum::LogChecksumHandle;
+use crate::core::node_infos::NodeInfos;
 use crate::core::notification::Notification;
 use crate::core::raft_msg::AppendEntriesTx;
 use crate::core::raft_msg::ClientReadTx;
+use crate::core::raft_msg::ElectionVeto;
+use crate::core::raft_msg::LeaderFitness;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::ResultSender;
 use crate::core::raft_msg::VoteTx;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::sm;
+use crate::core::sm::computed_results::ComputedResults;
+use crate::core::storage_check::ElectionTrigger;
+use crate::core::storage_check::check_storage;
+use crate::core::trace_ids::TraceIds;
+use crate::core::transition_stats::TransitionStats;
+use crate::core::write_latency::WriteLatency;
 use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySliceExt;
@@ -51,33 +74,60 @@ use crate::engine::ReplicationProgress;
 use crate::engine::Respond;
 use crate::entry::RaftEntry;
 use crate::error::AllowNextRevertError;
+use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
+use crate::error::EntryTooLarge;
 use crate::error::Fatal;
 use crate::error::ForwardToLeader;
 use crate::error::Infallible;
 use crate::error::InitializeError;
+use crate::error::LeaderChanged;
 use crate::error::QuorumNotEnough;
 use crate::error::RPCError;
+use crate::error::ResyncError;
 use crate::error::Timeout;
+use crate::error::VersionUnsupported;
 use crate::impls::OneshotResponder;
 use crate::log_id::option_raft_log_id_ext::OptionRaftLogIdExt;
+use crate::metrics::ApplyLagAlarm;
+use crate::metrics::CapacityHint;
 use crate::metrics::HeartbeatMetrics;
+use crate::metrics::MetricsHistory;
+use crate::metrics::MetricsSample;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::ReadReplica;
+use crate::metrics::ReadReplicaMetrics;
 use crate::metrics::ReplicationMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SnapshotReceiveProgress;
+use crate::network::NetworkEventBus;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
 use crate::network::RaftNetworkFactory;
-use crate::network::v2::RaftNetworkV2;
+use crate::network::layer::NetworkLayers;
 use crate::progress::Progress;
 use crate::progress::entry::ProgressEntry;
 use crate::quorum::QuorumSet;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ApplyResultsRequest;
 use crate::raft::ClientWriteResult;
+use crate::raft::ClusterHealth;
+use crate::raft::ComputedResult;
+use crate::raft::DecommissionRequest;
+use crate::raft::HelloRequest;
+use crate::raft::LeadershipInfo;
+use crate::raft::LogChecksum;
+use crate::raft::LogRepairRequest;
+use crate::raft::NoElectionReason;
+use crate::raft::NodeHealth;
+use crate::raft::NodeInfo;
 use crate::raft::ReadPolicy;
+use crate::raft::StateMachineChecksumRequest;
+use crate::raft::StateMachineChecksums;
+use crate::raft::TargetProgress;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft::linearizable_read::Linearizer;
@@ -90,16 +140,27 @@ use crate::raft_state::io_state::io_id::IOId;
 use crate::replication::ReplicationCore;
 use crate::replication::ReplicationHandle;
 use crate::replication::ReplicationSessionId;
+use crate::replication::entry_cache::EntryCache;
 use crate::replication::request::Replicate;
+use crate::replication::snapshot_permits::SnapshotPermits;
 use crate::runtime::RaftRuntime;
 use crate::storage::IOFlushed;
+use crate::storage::RaftLogReader;
 use crate::storage::RaftLogStorage;
+use crate::storage::Snapshot;
+use crate::trace::EngineInput;
+use crate::trace::StateDigest;
+use crate::trace::TraceEntry;
+use crate::trace::recorder::EngineRecorder;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscReceiverOf;
 use crate::type_config::alias::MpscSenderOf;
 use crate::type_config::alias::OneshotReceiverOf;
+use crate::type_config::alias::OneshotSenderOf;
+use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchSenderOf;
 use crate::type_config::alias::WriteResponderOf;
 use crate::type_config::async_runtime::mpsc::MpscSender;
@@ -115,6 +176,12 @@ pub(crate) struct ApplyResult<C: RaftTypeConfig> {
     pub(crate) since: u64,
     pub(crate) end: u64,
     pub(crate) last_applied: LogIdOf<C>,
+
+    /// The application keys published by the state machine for the applied entries.
+    pub(crate) app_keys: Vec<(String, LogIdOf<C>)>,
+
+    /// The results computed by the state machine of the leader, to stream to followers.
+    pub(crate) computed: Vec<ComputedResult<C>>,
 }
 
 impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
@@ -123,6 +190,8 @@ impl<C: RaftTypeConfig> Debug for ApplyResult<C> {
             .field("since", &self.since)
             .field("end", &self.end)
             .field("last_applied", &self.last_applied)
+            .field("app_keys", &self.app_keys.len())
+            .field("computed", &self.computed.len())
             .finish()
     }
 }
@@ -158,9 +227,17 @@ where
     /// The `RaftNetworkFactory` implementation.
     pub(crate) network_factory: NF,
 
+    /// The layers applied to every RPC sent by this node, see
+    /// [`RaftNetworkFactory::network_layers()`](crate::network::RaftNetworkFactory::network_layers).
+    pub(crate) network_layers: NetworkLayers<C>,
+
     /// The [`RaftLogStorage`] implementation.
     pub(crate) log_store: LS,
 
+    /// A reader of the log store, to tell the size of a client write with
+    /// [`RaftLogReader::entry_size()`](crate::storage::RaftLogReader::entry_size).
+    pub(crate) log_reader: LS::LogReader,
+
     /// A controlling handle to the [`RaftStateMachine`] worker.
     ///
     /// [`RaftStateMachine`]: `crate::storage::RaftStateMachine`
@@ -171,6 +248,17 @@ where
     /// Responders to send result back to client when logs are applied.
     pub(crate) client_responders: BTreeMap<u64, CoreResponder<C>>,
 
+    /// The last log id flushed to the log store, to tell whether a pending client write is
+    /// persisted when the leader steps down, and to read it back before an election.
+    pub(crate) flushed_log_id: Option<LogIdOf<C>>,
+
+    /// Why the local storage failed the last check before an election, if it did.
+    pub(crate) storage_check_failure: Option<String>,
+
+    /// What starts an election once the storage check being run passes, if one is being run,
+    /// see [`Notification::StorageChecked`].
+    pub(crate) pending_storage_check: Option<ElectionTrigger<C>>,
+
     /// A mapping of node IDs the replication state of the target node.
     pub(crate) replications: BTreeMap<C::NodeId, ReplicationHandle<C>>,
 
@@ -180,6 +268,10 @@ where
     pub(crate) tx_api: MpscSenderOf<C, RaftMsg<C>>,
     pub(crate) rx_api: MpscReceiverOf<C, RaftMsg<C>>,
 
+    /// The priority lane of `RaftMsg`: vote and transfer-leader messages, see
+    /// [`RaftMsg::is_priority()`]. It is always drained before `rx_api`.
+    pub(crate) rx_api_priority: MpscReceiverOf<C, RaftMsg<C>>,
+
     /// A Sender to send callback by other components to [`RaftCore`], when an action is finished,
     /// such as flushing log to disk, or applying log entries to state machine.
     pub(crate) tx_notification: MpscSenderOf<C, Notification<C>>,
@@ -187,13 +279,121 @@ where
     /// A Receiver to receive callback from other components.
     pub(crate) rx_notification: MpscReceiverOf<C, Notification<C>>,
 
+    /// The priority lane of `Notification`, used by vote requests, so that elections are not
+    /// delayed by a backlog of replication progress or IO completions. It is always drained
+    /// before `rx_notification`.
+    ///
+    /// Replication and heartbeat progress are not sent through it, so that they are handled in
+    /// the order they are sent. Neither are ticks: a tick must not overtake the heartbeats queued
+    /// before it, or a follower would time out and elect while the Leader is alive.
+    pub(crate) tx_notification_priority: MpscSenderOf<C, Notification<C>>,
+    pub(crate) rx_notification_priority: MpscReceiverOf<C, Notification<C>>,
+
     pub(crate) tx_metrics: WatchSenderOf<C, RaftMetrics<C>>,
     pub(crate) tx_data_metrics: WatchSenderOf<C, RaftDataMetrics<C>>,
     pub(crate) tx_server_metrics: WatchSenderOf<C, RaftServerMetrics<C>>,
+    pub(crate) tx_membership: WatchSenderOf<C, Arc<EffectiveMembership<C>>>,
     pub(crate) tx_progress: IoProgressSender<C>,
 
     pub(crate) runtime_stats: RuntimeStats,
 
+    /// Latency of every stage of the writes proposed by this node as a leader.
+    pub(crate) write_latency: WriteLatency<C>,
+
+    /// Tracks the elections started by this node, reported in `RaftMetrics::elections`.
+    pub(crate) election_stats: ElectionStats<C>,
+
+    /// Tracks the time of the last state changes of this node, reported in
+    /// `RaftMetrics::transitions`.
+    pub(crate) transition_stats: TransitionStats<C>,
+
+    /// The time the vote is loaded from storage when this node starts.
+    ///
+    /// A committed vote updated later than this is confirmed by a leader this node has heard
+    /// from.
+    pub(crate) vote_loaded_at: Option<InstantOf<C>>,
+
+    /// Whether this node has heard from a leader, or has become the leader, since it started,
+    /// reported in `RaftMetrics::leader_discovered`.
+    pub(crate) leader_discovered: bool,
+
+    /// Delivers connection lifecycle events of replication streams to the application.
+    pub(crate) network_events: Arc<NetworkEventBus<C>>,
+
+    /// Throttles vote requests from every candidate, see `Config::vote_request_min_interval`.
+    pub(crate) vote_rate_limiter: VoteRateLimiter<C>,
+
+    /// The linearizable reads waiting for a round of heartbeats to confirm the leadership.
+    pub(crate) read_batch: ReadBatch<C>,
+
+    /// Records the inputs fed to `engine`, if `Config::engine_trace_max_inputs` is not 0.
+    pub(crate) engine_recorder: EngineRecorder<C>,
+
+    /// The last samples of metrics, if `Config::metrics_history_size` is not 0.
+    pub(crate) metrics_history: MetricsHistory<C>,
+
+    /// Raises an alarm when applying falls behind committed, see
+    /// `Config::apply_lag_alarm_threshold`.
+    pub(crate) apply_lag_alarm: ApplyLagAlarm<C>,
+
+    /// The progress of the snapshot being received, updated by `Raft` and reported in metrics.
+    pub(crate) snapshot_receive: SnapshotReceiveProgress<C>,
+
+    /// The application keys published by the state machine, shared with `Raft` for lookups.
+    pub(crate) app_index: AppIndex<C>,
+
+    /// The log entries read for replication, shared by every replication stream.
+    pub(crate) entry_cache: EntryCache<C>,
+
+    /// The permits to send a snapshot, shared by every replication stream.
+    pub(crate) snapshot_permits: SnapshotPermits<C>,
+
+    /// The trace ids of client requests by log index, shared by every replication stream.
+    pub(crate) trace_ids: TraceIds,
+
+    /// The task computing log checksums, to send with heartbeats or to verify the ones received.
+    pub(crate) log_checksum: LogChecksumHandle<C>,
+
+    /// The index of the first log entry of the next range to compute the checksum of, as a leader.
+    pub(crate) log_checksum_cursor: u64,
+
+    /// When to compute the checksum of the next log range, as a leader.
+    pub(crate) next_log_checksum_at: Option<InstantOf<C>>,
+
+    /// The range of logs the state machine worker cannot read, and the channel to send a copy of
+    /// them fetched from the leader, see [`Notification::RepairLog`].
+    #[allow(clippy::type_complexity)]
+    pub(crate) pending_log_repair: Option<(LogIdOf<C>, LogIdOf<C>, OneshotSenderOf<C, Option<Vec<C::Entry>>>)>,
+
+    /// The apply results received from the leader, shared with `Raft` and the state machine
+    /// worker. `RaftCore` only reports the statistics of it.
+    pub(crate) computed_results: ComputedResults<C>,
+
+    /// The results computed by the state machine of this leader, to stream to followers.
+    pub(crate) pending_apply_results: Vec<ComputedResult<C>>,
+
+    /// The tasks that stream the apply results to followers, as a leader.
+    pub(crate) apply_results_senders: ApplyResultsSenders<C>,
+
+    /// The infos of this node and of other nodes, shared with `Raft`, which receives hello
+    /// requests.
+    pub(crate) node_infos: NodeInfos<C>,
+
+    /// The hook set by the application, consulted before this node starts an election.
+    pub(crate) election_veto: Option<ElectionVeto>,
+
+    /// The function set by the application to score the fitness of nodes to be the leader.
+    pub(crate) leader_fitness: Option<LeaderFitness<C>>,
+
+    /// The task archiving logs before they are purged, with the hook set by the application.
+    pub(crate) log_archive: Option<LogArchiveHandle<C>>,
+
+    /// The task accessing the node-local entries of the application.
+    pub(crate) local_entries: LocalEntriesHandle<C>,
+
+    /// When to evaluate the fitness of the voters next, as a leader.
+    pub(crate) next_leader_fitness_at: Option<InstantOf<C>>,
+
     pub(crate) span: Span,
 }
 
@@ -238,6 +438,7 @@ where
     async fn do_main(&mut self, rx_shutdown: OneshotReceiverOf<C, ()>) -> Result<Infallible, Fatal<C>> {
         tracing::debug!("raft node is initializing");
 
+        self.engine_recorder.record(|| EngineInput::Startup);
         self.engine.startup();
         // It may not finish running all the commands, if there is a command waiting for a callback.
         self.run_engine_commands().await?;
@@ -250,8 +451,8 @@ where
 
     /// Handle `is_leader` requests.
     ///
-    /// Send heartbeat to all voters. We respond once we have
-    /// a quorum of agreement.
+    /// A [`ReadPolicy::ReadIndex`] request is added to [`ReadBatch`], and is responded once
+    /// [`Self::confirm_read_batch`] receives a quorum of agreement for the batch.
     ///
     /// Why:
     /// To ensure linearizability, a read request proposed at time `T1` confirms this node's
@@ -260,8 +461,15 @@ where
     // TODO: the second condition is such a read request can only read from state machine only when the last log it sees
     //       at `T1` is committed.
     #[tracing::instrument(level = "trace", skip(self, tx))]
-    pub(super) async fn handle_check_is_leader_request(&mut self, read_policy: ReadPolicy, tx: ClientReadTx<C>) {
-        // Setup sentinel values to track when we've received majority confirmation of leadership.
+    pub(super) fn handle_check_is_leader_request(
+        &mut self,
+        read_policy: ReadPolicy,
+        tx: ClientReadTx<C>,
+        trace_id: Option<String>,
+    ) {
+        if let Some(trace_id) = &trace_id {
+            tracing::debug!(trace_id, read_policy = display(&read_policy), "client read");
+        }
 
         let resp = {
             let l = self.engine.leader_handler();
@@ -298,17 +506,57 @@ where
             return;
         }
 
+        // single-node quorum, fast path, return quickly.
+        let eff_mem = self.engine.state.membership_state.effective();
+        if eff_mem.is_quorum(btreeset! {self.id.clone()}.iter()) {
+            let _ = tx.send(Ok(resp));
+            return;
+        }
+
+        let opens_batch = self.read_batch.push(resp, tx, trace_id, C::now());
+        let delay = self.read_batch.delay();
+
+        // With no delay, the batch is confirmed at the end of the current loop of RaftCore.
+        if opens_batch && !delay.is_zero() {
+            let core_tx = self.tx_notification.clone();
+
+            let fu = async move {
+                C::sleep(delay).await;
+                let _ = core_tx.send(Notification::ReadBatchDue).await;
+            };
+
+            // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+            #[allow(clippy::let_underscore_future)]
+            let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_read_batch_timer")));
+        }
+    }
+
+    /// Confirm the leadership for the batched linearizable reads with a single round of heartbeats,
+    /// if the batch is due.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(super) async fn confirm_read_batch(&mut self) {
+        let Some((reads, trace_ids)) = self.read_batch.take_due(C::now()) else {
+            return;
+        };
+
+        if let Err(forward) = self.engine.leader_handler() {
+            ReadBatch::respond(reads, Err(forward.into()));
+            return;
+        }
+
+        tracing::debug!(reads = reads.len(), "confirm leadership for a batch of reads");
+
         let my_id = self.id.clone();
         let my_vote = self.engine.state.vote_ref().clone();
-        let ttl = Duration::from_millis(self.config.heartbeat_interval);
+        let ttl = self.config.heartbeat_interval();
         let eff_mem = self.engine.state.membership_state.effective().clone();
         let core_tx = self.tx_notification.clone();
 
         let mut granted = btreeset! {my_id.clone()};
 
-        // single-node quorum, fast path, return quickly.
+        // The membership may have changed since the reads arrived.
         if eff_mem.is_quorum(granted.iter()) {
-            let _ = tx.send(Ok(resp));
+            ReadBatch::respond(reads, Ok(()));
             return;
         }
 
@@ -317,58 +565,19 @@ where
 
         let voter_progresses = {
             let l = &self.engine.leader.as_ref().unwrap();
-            l.progress.iter().filter(|(id, _v)| l.progress.is_voter(id) == Some(true))
+            l.progress
+                .iter()
+                .filter(|(id, _v)| l.progress.is_voter(id) == Some(true))
+                .map(|(id, p)| (id.clone(), p.matching().cloned()))
+                .collect::<Vec<_>>()
         };
 
-        for (target, progress) in voter_progresses {
-            let target = target.clone();
-
+        for (target, matching) in voter_progresses {
             if target == my_id {
                 continue;
             }
 
-            let rpc = AppendEntriesRequest {
-                vote: my_vote.clone(),
-                prev_log_id: progress.matching().cloned(),
-                entries: vec![],
-                leader_commit: self.engine.state.committed().cloned(),
-            };
-
-            // Safe unwrap(): target is in membership
-            let target_node = eff_mem.get_node(&target).unwrap().clone();
-            let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
-
-            let option = RPCOption::new(ttl);
-
-            let fu = {
-                let my_id = my_id.clone();
-                let target = target.clone();
-
-                async move {
-                    let outer_res = C::timeout(ttl, client.append_entries(rpc, option)).await;
-                    match outer_res {
-                        Ok(append_res) => match append_res {
-                            Ok(x) => Ok((target, x)),
-                            Err(err) => Err((target, err)),
-                        },
-                        Err(_timeout) => {
-                            let timeout_err = Timeout {
-                                action: RPCTypes::AppendEntries,
-                                id: my_id,
-                                target: target.clone(),
-                                timeout: ttl,
-                            };
-
-                            Err((target, RPCError::Timeout(timeout_err)))
-                        }
-                    }
-                }
-            };
-
-            let fu = fu.instrument(tracing::debug_span!("spawn_is_leader", target = target.to_string()));
-            let task = C::spawn(fu).map_err(move |err| (target, err));
-
-            pending.push(task);
+            pending.push(self.spawn_heartbeat_probe(target, matching, ttl, trace_ids.clone()).await);
         }
 
         let waiting_fu = async move {
@@ -410,14 +619,14 @@ where
 
                     // we are no longer leader so error out early
                     let err = ForwardToLeader::empty();
-                    let _ = tx.send(Err(err.into()));
+                    ReadBatch::respond(reads, Err(err.into()));
                     return;
                 }
 
                 granted.insert(target);
 
                 if eff_mem.is_quorum(granted.iter()) {
-                    let _ = tx.send(Ok(resp));
+                    ReadBatch::respond(reads, Ok(()));
                     return;
                 }
             }
@@ -425,11 +634,11 @@ where
             // If we've hit this location, then we've failed to gather needed confirmations due to
             // request failures.
 
-            let _ = tx.send(Err(QuorumNotEnough {
+            let err = QuorumNotEnough {
                 cluster: eff_mem.membership().to_string(),
                 got: granted,
-            }
-            .into()));
+            };
+            ReadBatch::respond(reads, Err(err.into()));
         };
 
         // TODO: do not spawn, manage read requests with a queue by RaftCore
@@ -439,6 +648,287 @@ where
         let _ = C::spawn(waiting_fu.instrument(tracing::debug_span!("spawn_is_leader_waiting")));
     }
 
+    /// Probe every member with an empty `AppendEntries` and report the health of the cluster.
+    ///
+    /// Unlike [`Self::handle_check_is_leader_request`], it waits for every member to respond or
+    /// time out, including learners.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(super) async fn handle_cluster_health_request(
+        &mut self,
+        timeout: Duration,
+        tx: ResultSender<C, ClusterHealth<C>, CheckIsLeaderError<C>>,
+    ) {
+        if let Err(forward) = self.engine.leader_handler() {
+            let _ = tx.send(Err(forward.into()));
+            return;
+        }
+
+        let targets = {
+            let l = &self.engine.leader.as_ref().unwrap();
+            l.progress
+                .iter()
+                .map(|(id, p)| (id.clone(), p.matching().cloned(), l.progress.is_voter(id) == Some(true)))
+                .collect::<Vec<_>>()
+        };
+
+        let my_id = self.id.clone();
+        let my_vote = self.engine.state.vote_ref().clone();
+        let last_log_id = self.engine.state.last_log_id().cloned();
+
+        let mut report = ClusterHealth {
+            leader_id: my_id.clone(),
+            term: my_vote.term(),
+            last_log_id: last_log_id.clone(),
+            committed: self.engine.state.committed().cloned(),
+            nodes: BTreeMap::new(),
+        };
+
+        let mut pending = FuturesUnordered::new();
+
+        for (target, matched, voter) in targets {
+            let is_me = target == my_id;
+
+            report.nodes.insert(target.clone(), NodeHealth {
+                voter,
+                reachable: is_me,
+                lag: last_log_id.next_index().saturating_sub(matched.next_index()),
+                matched: matched.clone(),
+                term: is_me.then(|| my_vote.term()),
+                last_error: None,
+            });
+
+            if !is_me {
+                pending.push(self.spawn_heartbeat_probe(target, matched, timeout, vec![]).await);
+            }
+        }
+
+        let network_events = self.network_events.clone();
+        let core_tx = self.tx_notification.clone();
+
+        let waiting_fu = async move {
+            while let Some(res) = pending.next().await {
+                let (target, append_res) = match res {
+                    Ok(Ok(res)) => res,
+                    Ok(Err((target, err))) => {
+                        tracing::warn!(target=display(&target), error=%err, "cluster health probe failed");
+                        network_events.on_error(&target, &err);
+                        continue;
+                    }
+                    Err((target, err)) => {
+                        tracing::error!(target = display(target), "fail to join task: {}", err);
+                        continue;
+                    }
+                };
+
+                network_events.on_success(&target);
+
+                let term = if let AppendEntriesResponse::HigherVote(vote) = append_res {
+                    let term = vote.term();
+
+                    let send_res = core_tx
+                        .send(Notification::HigherVote {
+                            target: target.clone(),
+                            higher: vote,
+                            leader_vote: my_vote.clone().into_committed(),
+                        })
+                        .await;
+
+                    if let Err(_e) = send_res {
+                        tracing::error!("fail to send HigherVote to RaftCore");
+                    }
+
+                    term
+                } else {
+                    my_vote.term()
+                };
+
+                if let Some(node) = report.nodes.get_mut(&target) {
+                    node.reachable = true;
+                    node.term = Some(term);
+                }
+            }
+
+            for (id, node) in report.nodes.iter_mut() {
+                node.last_error = network_events.last_error(id);
+            }
+
+            let _ = tx.send(Ok(report));
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(waiting_fu.instrument(tracing::debug_span!("spawn_cluster_health_waiting")));
+    }
+
+    /// Collect the checksum of the state machine of every member, right after a blank log entry
+    /// proposed for it is applied.
+    ///
+    /// The blank entry is appended after every log entry, and is not yet applied by any member
+    /// when the requests are sent, thus every member computes the checksum on the same state.
+    #[tracing::instrument(level = "debug", skip(self, tx))]
+    pub(super) async fn handle_state_machine_checksums(
+        &mut self,
+        timeout: Duration,
+        tx: ResultSender<C, StateMachineChecksums<C>, CheckIsLeaderError<C>>,
+    ) {
+        let targets = match self.engine.leader_handler() {
+            Ok(l) => l.leader.progress.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>(),
+            Err(forward) => {
+                let _ = tx.send(Err(forward.into()));
+                return;
+            }
+        };
+
+        let at = self
+            .write_entry(C::Entry::new_blank(LogIdOf::<C>::default()), None)
+            .and_then(|index| self.engine.state.get_log_id(index));
+
+        let mut report = StateMachineChecksums {
+            leader_id: self.id.clone(),
+            at: at.clone(),
+            checksums: targets.iter().map(|id| (id.clone(), None)).collect(),
+        };
+
+        let Some(at) = at else {
+            let _ = tx.send(Ok(report));
+            return;
+        };
+
+        let mut pending = FuturesUnordered::new();
+
+        for target in targets {
+            if target == self.id {
+                let (sm_tx, sm_rx) = C::oneshot();
+                if let Err(e) = self.sm_handle.send(sm::Command::checksum(at.clone(), sm_tx)) {
+                    tracing::error!(error = display(e), "error sending Checksum to sm worker");
+                    continue;
+                }
+
+                let fu = async move {
+                    let res = match C::timeout(timeout, sm_rx).await {
+                        Ok(Ok(checksum)) => Ok(checksum),
+                        _ => Err("no checksum from the local state machine".to_string()),
+                    };
+                    (target, res)
+                };
+                pending.push(Either::Left(fu));
+                continue;
+            }
+
+            let req = StateMachineChecksumRequest::new(self.engine.state.vote_ref().clone(), at.clone())
+                .with_cluster_name(Some(self.config.cluster_name.clone()));
+
+            // Safe unwrap(): target is in membership
+            let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap().clone();
+            let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
+            let layers = self.network_layers.clone();
+            let option = RPCOption::new(timeout);
+
+            let fu = async move {
+                let res = match C::timeout(timeout, layers.call(&target, &mut client, req, option)).await {
+                    Ok(Ok(checksum)) => Ok(checksum),
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(_timeout) => Err(format!("timeout after {:?}", timeout)),
+                };
+                (target, res)
+            };
+            pending.push(Either::Right(fu));
+        }
+
+        let waiting_fu = async move {
+            while let Some((target, res)) = pending.next().await {
+                match res {
+                    Ok(checksum) => {
+                        report.checksums.insert(target, checksum);
+                    }
+                    Err(e) => {
+                        tracing::warn!(target = display(&target), error = display(e), "fail to get checksum");
+                    }
+                }
+            }
+
+            let mismatched = report.mismatched().cloned().collect::<Vec<_>>();
+            if !mismatched.is_empty() {
+                tracing::error!(
+                    "state machine checksums mismatch: {}; mismatched: {}",
+                    report,
+                    mismatched.display()
+                );
+            }
+
+            let _ = tx.send(Ok(report));
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(waiting_fu.instrument(tracing::debug_span!("spawn_state_machine_checksums_waiting")));
+    }
+
+    /// Spawn a task that sends an empty `AppendEntries` to `target`, to check whether it accepts
+    /// the vote of this leader.
+    ///
+    /// `prev_log_id` is the last log id known to be replicated to `target`. `trace_ids` are the
+    /// trace ids of the client reads it is sent for, see [`RPCOption::trace_ids`].
+    async fn spawn_heartbeat_probe(
+        &mut self,
+        target: C::NodeId,
+        prev_log_id: Option<LogIdOf<C>>,
+        ttl: Duration,
+        trace_ids: Vec<String>,
+    ) -> impl Future<
+        Output = Result<
+            Result<(C::NodeId, AppendEntriesResponse<C>), (C::NodeId, RPCError<C>)>,
+            (C::NodeId, JoinErrorOf<C>),
+        >,
+    > + use<C, NF, LS> {
+        let rpc = AppendEntriesRequest {
+            vote: self.engine.state.vote_ref().clone(),
+            prev_log_id,
+            entries: vec![],
+            leader_commit: self.engine.state.committed().cloned(),
+            cluster_name: Some(self.config.cluster_name.clone()),
+            trace_ids: BTreeMap::new(),
+            log_checksum: None,
+        };
+
+        // Safe unwrap(): target is in membership
+        let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap().clone();
+        let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
+
+        let mut option = RPCOption::new(ttl);
+        option.trace_ids = trace_ids;
+
+        let fu = {
+            let my_id = self.id.clone();
+            let target = target.clone();
+            let layers = self.network_layers.clone();
+
+            async move {
+                let outer_res = C::timeout(ttl, layers.call(&target, &mut client, rpc, option)).await;
+                match outer_res {
+                    Ok(append_res) => match append_res {
+                        Ok(x) => Ok((target, x)),
+                        Err(err) => Err((target, err)),
+                    },
+                    Err(_timeout) => {
+                        let timeout_err = Timeout {
+                            action: RPCTypes::AppendEntries,
+                            id: my_id,
+                            target: target.clone(),
+                            timeout: ttl,
+                        };
+
+                        Err((target, RPCError::Timeout(timeout_err)))
+                    }
+                }
+            }
+        };
+
+        let fu = fu.instrument(tracing::debug_span!(
+            "spawn_heartbeat_probe",
+            target = target.to_string()
+        ));
+        C::spawn(fu).map_err(move |err| (target, err))
+    }
+
     /// Submit change-membership by writing a Membership log entry.
     ///
     /// If `retain` is `true`, removed `voter` will becomes `learner`. Otherwise they will
@@ -476,6 +966,23 @@ where
         self.write_entry(ent, Some(CoreResponder::Oneshot(tx)));
     }
 
+    /// Propose a version bump entry if every member, including learners, supports `version`.
+    pub(super) fn bump_version(
+        &mut self,
+        version: u64,
+        supports: impl Fn(&C::NodeId, &C::Node) -> bool,
+        tx: OneshotResponder<C, ClientWriteResult<C>>,
+    ) {
+        let membership = self.engine.state.membership_state.effective().membership();
+        if let Some((id, _)) = membership.nodes().find(|(id, node)| !supports(id, node)) {
+            tx.send(Err(VersionUnsupported::new(version, id.clone()).into()));
+            return;
+        }
+
+        let ent = C::Entry::new(LogIdOf::<C>::default(), EntryPayload::VersionBump(version));
+        self.write_entry(ent, Some(CoreResponder::Oneshot(tx)));
+    }
+
     /// Write a log entry to the cluster through raft protocol.
     ///
     /// I.e.: append the log entry to local store, forward it to a quorum(including the leader),
@@ -488,13 +995,13 @@ where
     /// [`RaftTypeConfig::Responder`] (application-defined) or [`OneshotResponder`]
     /// (general-purpose); the former is for application-defined entries like user data, the
     /// latter is for membership configuration changes.
+    ///
+    /// It returns the log index the entry is written to, or `None` if it is rejected.
     #[tracing::instrument(level = "debug", skip_all, fields(id = display(&self.id)))]
-    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<CoreResponder<C>>) {
+    pub fn write_entry(&mut self, entry: C::Entry, resp_tx: Option<CoreResponder<C>>) -> Option<u64> {
         tracing::debug!(payload = display(&entry), "write_entry");
 
-        let Some((mut lh, tx)) = self.engine.get_leader_handler_or_reject(resp_tx) else {
-            return;
-        };
+        let (mut lh, tx) = self.engine.get_leader_handler_or_reject(resp_tx)?;
 
         // If the leader is transferring leadership, forward writes to the new leader.
         if let Some(to) = lh.leader.get_transfer_to() {
@@ -502,9 +1009,13 @@ where
                 let err = lh.state.new_forward_to_leader(to.clone());
                 tx.send(Err(ClientWriteError::ForwardToLeader(err)));
             }
-            return;
+            return None;
         }
 
+        self.engine_recorder.record(|| EngineInput::WriteEntry {
+            entry: TraceEntry::copy_from(&entry),
+        });
+
         let entries = vec![entry];
         // TODO: it should returns membership config error etc. currently this is done by the
         //       caller.
@@ -515,6 +1026,8 @@ where
         if let Some(tx) = tx {
             self.client_responders.insert(index, tx);
         }
+
+        Some(index)
     }
 
     /// Send a heartbeat message to every follower/learners.
@@ -540,17 +1053,37 @@ where
             return false;
         }
 
+        self.engine_recorder.record(|| EngineInput::Heartbeat);
         lh.send_heartbeat();
 
         tracing::debug!("{} triggered sending heartbeat", emitter);
         true
     }
 
+    /// Evict the cached entries that are replicated to every target.
+    fn evict_replicated_entries(&self) {
+        let Some(leader) = self.engine.leader.as_ref() else {
+            return;
+        };
+
+        let min_next = leader
+            .progress
+            .iter()
+            .filter(|(id, _)| id != &self.id)
+            .map(|(_, p)| p.matching().next_index())
+            .min();
+
+        if let Some(min_next) = min_next {
+            self.entry_cache.evict_before(min_next);
+            self.trace_ids.evict_before(min_next);
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub fn flush_metrics(&mut self) {
         self.tx_progress.send_log_progress(self.engine.state.log_progress().flushed().cloned());
 
-        let (replication, heartbeat) = if let Some(leader) = self.engine.leader.as_ref() {
+        let (replication, heartbeat, read_replicas, quarantined) = if let Some(leader) = self.engine.leader.as_ref() {
             let replication_prog = &leader.progress;
             let replication =
                 Some(replication_prog.iter().map(|(id, p)| (id.clone(), p.matching().cloned())).collect());
@@ -559,12 +1092,58 @@ where
             let heartbeat =
                 Some(clock_prog.iter().map(|(id, opt_t)| (id.clone(), opt_t.map(SerdeInstant::new))).collect());
 
-            (replication, heartbeat)
+            let next_index = self.engine.state.last_log_id().next_index();
+            let read_replicas = Some(
+                replication_prog
+                    .iter()
+                    .filter(|(id, _)| replication_prog.is_voter(id) == Some(false))
+                    .map(|(id, p)| {
+                        let matched = p.matching().cloned();
+                        let replica = ReadReplica {
+                            lag: next_index.saturating_sub(matched.next_index()),
+                            matched,
+                            last_acked: clock_prog.try_get(id).copied().flatten().map(SerdeInstant::new),
+                        };
+                        (id.clone(), replica)
+                    })
+                    .collect(),
+            );
+
+            let quarantined =
+                Some(replication_prog.iter().filter(|(_, p)| p.quarantined).map(|(id, _)| id.clone()).collect());
+
+            (replication, heartbeat, read_replicas, quarantined)
         } else {
-            (None, None)
+            (None, None, None, None)
         };
 
-        self.report_metrics(replication, heartbeat);
+        let capacity = self.capacity_hint();
+
+        self.report_metrics(replication, heartbeat, read_replicas, quarantined, capacity);
+    }
+
+    /// Estimate the write capacity of this node as a leader.
+    ///
+    /// It returns `None` if this node is not a leader.
+    fn capacity_hint(&self) -> Option<CapacityHint> {
+        let leader = self.engine.leader.as_ref()?;
+        let progress = &leader.progress;
+
+        let next_index = self.engine.state.last_log_id().next_index();
+
+        let max_voter_lag = progress
+            .iter()
+            .filter(|(id, _)| progress.is_voter(id) == Some(true))
+            .map(|(_, p)| next_index.saturating_sub(p.matching().next_index()))
+            .max()
+            .unwrap_or_default();
+
+        Some(CapacityHint {
+            uncommitted: next_index.saturating_sub(self.engine.state.committed().next_index()),
+            max_voter_lag,
+            flush_latency_ewma_us: self.write_latency.flush_latency_ewma(),
+            window: self.config.max_payload_entries * self.config.max_append_entries_inflight,
+        })
     }
 
     /// Report a metrics payload on the current state of the Raft node.
@@ -573,15 +1152,42 @@ where
         &mut self,
         replication: Option<ReplicationMetrics<C>>,
         heartbeat: Option<HeartbeatMetrics<C>>,
+        read_replicas: Option<ReadReplicaMetrics<C>>,
+        quarantined: Option<BTreeSet<C::NodeId>>,
+        capacity: Option<CapacityHint>,
     ) {
         let last_quorum_acked = self.last_quorum_acked_time();
         let millis_since_quorum_ack = last_quorum_acked.map(|t| t.elapsed().as_millis() as u64);
 
         let st = &self.engine.state;
 
-        let membership_config = st.membership_state.effective().stored_membership().clone();
+        let effective_membership = st.membership_state.effective();
+        let membership_config = effective_membership.stored_membership().clone();
+        let committed_membership = st.membership_state.committed().stored_membership().clone();
         let current_leader = self.current_leader();
 
+        if !self.leader_discovered {
+            self.leader_discovered = st.server_state == ServerState::Leader
+                || (current_leader.is_some() && st.vote.last_update() > self.vote_loaded_at);
+        }
+
+        let leader_vote = self.engine.leader_ref().map(|l| l.committed_vote_ref().clone().into_vote());
+        self.election_stats.observe(
+            st.vote_ref(),
+            self.engine.candidate_ref().map(|c| c.vote_ref()),
+            leader_vote.as_ref(),
+        );
+
+        let vote = st.log_progress().flushed().map(|io_id| io_id.to_app_vote()).unwrap_or_default();
+        self.transition_stats.observe(
+            st.server_state,
+            &vote,
+            st.io_snapshot_last_log_id(),
+            effective_membership.log_id().as_ref(),
+        );
+
+        let (applied_from_leader_results, apply_result_mismatches) = self.computed_results.stats();
+
         #[allow(deprecated)]
         let m = RaftMetrics {
             running_state: Ok(()),
@@ -589,7 +1195,7 @@ where
 
             // --- data ---
             current_term: st.vote_ref().term(),
-            vote: st.log_progress().flushed().map(|io_id| io_id.to_app_vote()).unwrap_or_default(),
+            vote: vote.clone(),
             last_log_index: st.last_log_id().index(),
             last_applied: st.io_applied().cloned(),
             snapshot: st.io_snapshot_last_log_id().cloned(),
@@ -598,13 +1204,34 @@ where
             // --- cluster ---
             state: st.server_state,
             current_leader: current_leader.clone(),
+            leader_discovered: self.leader_discovered,
             millis_since_quorum_ack,
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             membership_config: membership_config.clone(),
+            committed_membership: committed_membership.clone(),
+            current_term_committed: self.engine.leader_ref().is_some_and(|l| st.committed() >= Some(l.noop_log_id())),
+            split_brain_detected: self.runtime_stats.split_brain_detected,
+            elections: self.election_stats.metrics(),
+            transitions: self.transition_stats.metrics(),
+            leader_fitness_handoffs: self.runtime_stats.leader_fitness_handoffs,
+            log_checksums_verified: self.runtime_stats.log_checksums_verified,
+            log_checksum_mismatches: self.runtime_stats.log_checksum_mismatches,
+            applied_from_leader_results,
+            apply_result_mismatches,
+            storage_check_failure: self.storage_check_failure.clone(),
+            node_infos: self.node_infos.all(&self.id),
+            compression: self.node_infos.all_compression(&self.config.compression),
+            write_latency: self.write_latency.metrics(),
+            replication_cache: self.entry_cache.metrics(),
+            snapshot_transfers: self.snapshot_permits.metrics(),
+            snapshot_receiving: self.snapshot_receive.metrics(),
             heartbeat: heartbeat.clone(),
 
             // --- replication ---
             replication: replication.clone(),
+            read_replicas: read_replicas.clone(),
+            quarantined: quarantined.clone(),
+            capacity,
         };
 
         #[allow(deprecated)]
@@ -617,14 +1244,17 @@ where
             last_quorum_acked: last_quorum_acked.map(SerdeInstant::new),
             replication,
             heartbeat,
+            read_replicas,
+            quarantined,
         };
 
         let server_metrics = RaftServerMetrics {
             id: self.id.clone(),
-            vote: st.log_progress().flushed().map(|io_id| io_id.to_app_vote()).unwrap_or_default(),
+            vote,
             state: st.server_state,
             current_leader,
             membership_config,
+            committed_membership,
         };
 
         // Start to send metrics
@@ -648,6 +1278,14 @@ where
             false
         });
 
+        self.tx_membership.send_if_modified(|membership| {
+            if effective_membership.ne(membership) {
+                *membership = effective_membership.clone();
+                return true;
+            }
+            false
+        });
+
         tracing::debug!("report_metrics: {}", m);
         let res = self.tx_metrics.send(m);
 
@@ -673,7 +1311,11 @@ where
         let membership = Membership::from(member_nodes);
 
         let entry = C::Entry::new_membership(LogIdOf::<C>::default(), membership);
+        self.engine_recorder.record(|| EngineInput::Initialize {
+            entry: TraceEntry::copy_from(&entry),
+        });
         let res = self.engine.initialize(entry);
+        self.record_election_start("initialize");
 
         // If there is an error, respond at once.
         // Otherwise, wait for the initialization log to be applied to state machine.
@@ -702,10 +1344,34 @@ where
         });
     }
 
+    /// Initialize a new cluster from a snapshot, and respond once the snapshot is installed.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) fn handle_initialize_from_snapshot(
+        &mut self,
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, (), InitializeError<C>>,
+    ) {
+        self.engine_recorder.record(|| EngineInput::InitializeFromSnapshot {
+            meta: snapshot.meta.clone(),
+        });
+        let res = self.engine.initialize_from_snapshot(snapshot);
+        self.record_election_start("initialize");
+
+        let (condition, res) = match res {
+            Ok(cond) => (cond, Ok(())),
+            Err(e) => (None, Err(e)),
+        };
+        self.engine.output.push_command(Command::Respond {
+            when: condition,
+            resp: Respond::new(res, tx),
+        });
+    }
+
     /// Trigger a snapshot building(log compaction) job if there is no pending building job.
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn trigger_snapshot(&mut self) {
         tracing::debug!("{}", func_name!());
+        self.engine_recorder.record(|| EngineInput::TriggerSnapshot);
         self.engine.snapshot_handler().trigger_snapshot();
     }
 
@@ -726,14 +1392,26 @@ where
             .snapshot_policy
             .should_snapshot(&self.engine.state, self.core_state.snapshot_tried_at.as_ref())
         {
-            tracing::debug!("snapshot policy triggered at: {}", at);
+            subsystem_log!(
+                self.runtime_config,
+                Snapshot,
+                DEBUG,
+                "snapshot policy triggered at: {}",
+                at
+            );
             self.core_state.snapshot_tried_at = Some(at);
             self.trigger_snapshot();
         }
 
         // Keep replicating to a target if the replication stream to it is idle
         if let Ok(mut lh) = self.engine.leader_handler() {
+            let queued = lh.output.len();
             lh.replication_handler().initiate_replication();
+
+            // Most of the time every stream is busy: record it only when there is something to send.
+            if lh.output.len() != queued {
+                self.engine_recorder.record(|| EngineInput::InitiateReplication);
+            }
         }
     }
 
@@ -812,7 +1490,9 @@ where
         let entry_count = last.index() + 1 - first.index();
         self.runtime_stats.apply_batch.record(entry_count);
 
-        let cmd = sm::Command::apply(first, last.clone(), responders);
+        let wait_leader_results = self.config.apply_results_wait > 0 && self.engine.leader.is_none();
+        let vote = self.engine.state.vote_ref().clone();
+        let cmd = sm::Command::apply(first, last.clone(), responders, vote, wait_leader_results);
         self.sm_handle.send(cmd).map_err(|e| StorageError::apply(last, AnyError::error(e)))?;
 
         Ok(())
@@ -831,6 +1511,10 @@ where
 
         let membership_log_id = self.engine.state.membership_state.effective().log_id();
         let network = self.network_factory.new_client(target.clone(), target_node).await;
+        let mut pipeline_networks = vec![];
+        for _ in 1..self.config.max_append_entries_inflight {
+            pipeline_networks.push(self.network_factory.new_client(target.clone(), target_node).await);
+        }
         let snapshot_network = self.network_factory.new_client(target.clone(), target_node).await;
 
         let leader = self.engine.leader.as_ref().unwrap();
@@ -841,13 +1525,21 @@ where
             target.clone(),
             session_id,
             self.config.clone(),
+            self.runtime_config.clone(),
             self.engine.state.committed().cloned(),
             progress_entry.matching.clone(),
             network,
+            pipeline_networks,
             snapshot_network,
+            self.snapshot_permits.clone(),
             self.log_store.get_log_reader().await,
+            self.entry_cache.clone(),
+            self.trace_ids.clone(),
             self.sm_handle.new_snapshot_reader(),
             self.tx_notification.clone(),
+            self.network_events.clone(),
+            self.network_layers.clone(),
+            self.node_infos.clone(),
             tracing::span!(parent: &self.span, Level::DEBUG, "replication", id=display(&self.id), target=display(&target)),
         )
     }
@@ -858,6 +1550,7 @@ where
         tracing::info!("remove all replication");
 
         self.heartbeat_handle.shutdown();
+        self.apply_results_senders.shutdown();
 
         let nodes = std::mem::take(&mut self.replications);
 
@@ -876,6 +1569,9 @@ where
             let _x = handle.await;
             tracing::info!("Done joining removed replication : {}", target);
         }
+
+        // Clear the cache after every stream is joined, so that none of them fills it again.
+        self.entry_cache.clear();
     }
 
     /// Run as many commands as possible.
@@ -931,9 +1627,66 @@ where
         // Progress driven commands run at last because some command may generate progress changes.
         self.run_progress_driven_command().await?;
 
+        self.fail_pending_writes_on_step_down();
+
+        self.engine_recorder.record(|| EngineInput::RunCommands {
+            digest: StateDigest::new(&self.engine.state),
+        });
+
         Ok(())
     }
 
+    /// Respond [`LeaderChanged`] to the client writes that are not committed, if this node is no
+    /// longer the leader and [`PendingWritePolicy::Fail`] is configured.
+    ///
+    /// The writes that are known to be committed are still responded when they are applied.
+    fn fail_pending_writes_on_step_down(&mut self) {
+        if self.config.pending_writes_on_step_down != PendingWritePolicy::Fail {
+            return;
+        }
+
+        if self.engine.leader.is_some() || self.client_responders.is_empty() {
+            return;
+        }
+
+        let pending = self.client_responders.split_off(&self.engine.state.committed().next_index());
+        if pending.is_empty() {
+            return;
+        }
+
+        let flushed = self.flushed_log_id.clone();
+        let forward = self.engine.state.forward_to_leader();
+
+        let failed = pending
+            .into_iter()
+            .map(|(index, tx)| {
+                let err = match self.engine.state.get_log_id(index) {
+                    Some(log_id) => {
+                        let persisted = flushed.as_ref() >= Some(&log_id);
+                        ClientWriteError::LeaderChanged(LeaderChanged::new(log_id, persisted, forward.clone()))
+                    }
+                    // The log is truncated, it will never be committed.
+                    None => ClientWriteError::ForwardToLeader(forward.clone()),
+                };
+                (tx, err)
+            })
+            .collect::<Vec<_>>();
+
+        tracing::info!(
+            pending = failed.len(),
+            "leader stepped down with pending client writes, respond LeaderChanged"
+        );
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(async move {
+            for (tx, err) in failed {
+                tracing::debug!("sent to pending client write: {}", err);
+                tx.send(Err(err));
+            }
+        });
+    }
+
     /// Run all commands that are automatically generated by progress changes.
     async fn run_progress_driven_command(&mut self) -> Result<(), StorageError<C>> {
         while let Some(cmd) = self.engine.next_progress_driven_command() {
@@ -999,15 +1752,36 @@ where
             // `select!` without `biased` provides a random fairness.
             // We want to check shutdown prior to other channels.
             // See: https://docs.rs/tokio/latest/tokio/macro.select.html#fairness
+            // Priority lanes are polled before the others.
             futures::select_biased! {
                 _ = (&mut rx_shutdown).fuse() => {
                     tracing::info!("recv from rx_shutdown");
                     return Err(Fatal::Stopped);
                 }
 
+                notify_res = self.rx_notification_priority.recv().fuse() => {
+                    match notify_res {
+                        Some(notify) => self.handle_notification(notify).await?,
+                        None => {
+                            tracing::error!("all rx_notification_priority senders are dropped");
+                            return Err(Fatal::Stopped);
+                        }
+                    };
+                }
+
+                msg_res = self.rx_api_priority.recv().fuse() => {
+                    match msg_res {
+                        Some(msg) => self.handle_api_msg(msg).await,
+                        None => {
+                            tracing::info!("all rx_api_priority senders are dropped");
+                            return Err(Fatal::Stopped);
+                        }
+                    };
+                }
+
                 notify_res = self.rx_notification.recv().fuse() => {
                     match notify_res {
-                        Some(notify) => self.handle_notification(notify)?,
+                        Some(notify) => self.handle_notification(notify).await?,
                         None => {
                             tracing::error!("all rx_notify senders are dropped");
                             return Err(Fatal::Stopped);
@@ -1049,6 +1823,48 @@ where
             // Trigger routine actions after processing all messages
             self.trigger_routine_actions();
 
+            // Reads received in this loop share one round of heartbeats.
+            self.confirm_read_batch().await;
+
+            if let Some((first, last, tx)) = self.pending_log_repair.take() {
+                self.spawn_log_repair(first, last, tx).await;
+            }
+
+            if !self.pending_apply_results.is_empty() {
+                self.send_apply_results().await;
+            }
+
+            if let (Some(log_archive), Some(next)) = (self.log_archive.as_mut(), self.engine.state.archive_next) {
+                log_archive.archive(next, self.engine.state.purge_upto().next_index());
+            }
+
+            self.run_engine_commands().await?;
+        }
+    }
+
+    /// Process all queued messages in the priority lanes.
+    ///
+    /// It is called before every message from the other lanes, so that an election or heartbeat
+    /// message waits for at most one other message.
+    /// If an input channel is closed, it returns `Fatal::Stopped`.
+    async fn process_priority(&mut self) -> Result<(), Fatal<C>> {
+        loop {
+            match self.rx_notification_priority.try_recv() {
+                Ok(notify) => self.handle_notification(notify).await?,
+                Err(TryRecvError::Empty) => match self.rx_api_priority.try_recv() {
+                    Ok(msg) => self.handle_api_msg(msg).await,
+                    Err(TryRecvError::Empty) => return Ok(()),
+                    Err(TryRecvError::Disconnected) => {
+                        tracing::debug!("rx_api_priority is disconnected, quit");
+                        return Err(Fatal::Stopped);
+                    }
+                },
+                Err(TryRecvError::Disconnected) => {
+                    tracing::error!("rx_notification_priority is disconnected, quit");
+                    return Err(Fatal::Stopped);
+                }
+            }
+
             self.run_engine_commands().await?;
         }
     }
@@ -1059,6 +1875,8 @@ where
     /// If the input channel is closed, it returns `Fatal::Stopped`.
     async fn process_raft_msg(&mut self, at_most: u64) -> Result<u64, Fatal<C>> {
         for i in 0..at_most {
+            self.process_priority().await?;
+
             let res = self.rx_api.try_recv();
             let msg = match res {
                 Ok(msg) => msg,
@@ -1094,6 +1912,8 @@ where
     /// If the input channel is closed, it returns `Fatal::Stopped`.
     async fn process_notification(&mut self, at_most: u64) -> Result<u64, Fatal<C>> {
         for i in 0..at_most {
+            self.process_priority().await?;
+
             let res = self.rx_notification.try_recv();
             let notify = match res {
                 Ok(msg) => msg,
@@ -1109,7 +1929,7 @@ where
                 },
             };
 
-            self.handle_notification(notify)?;
+            self.handle_notification(notify).await?;
 
             // TODO: does run_engine_commands() run too frequently?
             //       to run many commands in one shot, it is possible to batch more commands to gain
@@ -1138,19 +1958,20 @@ where
                 continue;
             }
 
-            let req = vote_req.clone();
+            let req = vote_req.clone().with_cluster_name(Some(self.config.cluster_name.clone()));
 
             // Safe unwrap(): target must be in membership
             let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap().clone();
             let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
 
-            let tx = self.tx_notification.clone();
+            let tx = self.tx_notification_priority.clone();
 
-            let ttl = Duration::from_millis(self.config.election_timeout_min);
+            let ttl = self.config.election_timeout_min();
             let id = self.id.clone();
             let option = RPCOption::new(ttl);
 
             let vote = vote.clone();
+            let layers = self.network_layers.clone();
 
             // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
             #[allow(clippy::let_underscore_future)]
@@ -1158,7 +1979,7 @@ where
                 {
                     let target = target.clone();
                     async move {
-                        let tm_res = C::timeout(ttl, client.vote(req, option)).await;
+                        let tm_res = C::timeout(ttl, layers.call(&target, &mut client, req, option)).await;
                         let res = match tm_res {
                             Ok(res) => res,
 
@@ -1207,19 +2028,20 @@ where
                 continue;
             }
 
-            let r = req.clone();
+            let r = req.clone().with_cluster_name(Some(self.config.cluster_name.clone()));
 
             // Safe unwrap(): target must be in membership
             let target_node = self.engine.state.membership_state.effective().get_node(&target).unwrap().clone();
             let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
 
-            let ttl = Duration::from_millis(self.config.election_timeout_min);
+            let ttl = self.config.election_timeout_min();
             let option = RPCOption::new(ttl);
 
             let fut = {
                 let target = target.clone();
+                let layers = self.network_layers.clone();
                 async move {
-                    let tm_res = C::timeout(ttl, client.transfer_leader(r, option)).await;
+                    let tm_res = C::timeout(ttl, layers.call(&target, &mut client, r, option)).await;
                     let res = match tm_res {
                         Ok(res) => res,
                         Err(timeout) => {
@@ -1248,11 +2070,70 @@ where
         }
     }
 
+    /// Send a [`DecommissionRequest`] to a node removed from the cluster.
+    ///
+    /// The node is no longer in the membership, thus its `node` info is provided by the caller.
+    async fn send_decommission(
+        &mut self,
+        target: C::NodeId,
+        node: C::Node,
+        timeout: Duration,
+        tx: ResultSender<C, (), RPCError<C>>,
+    ) {
+        let req = DecommissionRequest::new(self.engine.state.vote_ref().clone(), target.clone())
+            .with_cluster_name(Some(self.config.cluster_name.clone()));
+
+        let mut client = self.network_factory.new_client(target.clone(), &node).await;
+        let option = RPCOption::new(timeout);
+        let my_id = self.id.clone();
+
+        let fut = {
+            let target = target.clone();
+            let layers = self.network_layers.clone();
+            async move {
+                let res = match C::timeout(timeout, layers.call(&target, &mut client, req, option)).await {
+                    Ok(res) => res,
+                    Err(_timeout) => Err(RPCError::Timeout(Timeout {
+                        action: RPCTypes::Decommission,
+                        id: my_id,
+                        target: target.clone(),
+                        timeout,
+                    })),
+                };
+
+                if let Err(e) = &res {
+                    tracing::warn!({error = display(e), target = display(&target)}, "error sending decommission");
+                } else {
+                    tracing::info!("Done decommission sent to {}", target);
+                }
+
+                let _ = tx.send(res);
+            }
+        };
+
+        let span = tracing::debug_span!(parent: &Span::current(), "send_decommission", target = display(&target));
+
+        // False positive lint warning(`non-binding `let` on a future`): https://github.com/rust-lang/rust-clippy/issues/9932
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fut.instrument(span));
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(super) fn handle_vote_request(&mut self, req: VoteRequest<C>, tx: VoteTx<C>) {
         tracing::info!(req = display(&req), func = func_name!());
 
-        let resp = self.engine.handle_vote_req(req);
+        let resp = if let Some(reason) = self.vote_request_rejection(&req) {
+            tracing::info!(req = display(&req), "reject vote-request: {}", reason);
+            VoteResponse::new(
+                self.engine.state.vote_ref(),
+                self.engine.state.last_log_id().cloned(),
+                false,
+            )
+        } else {
+            self.engine_recorder.record(|| EngineInput::VoteRequest { req: req.clone() });
+            self.engine.handle_vote_req(req)
+        };
+
         let condition = Some(Condition::IOFlushed {
             io_id: IOId::new(self.engine.state.vote_ref()),
         });
@@ -1262,10 +2143,33 @@ where
         });
     }
 
+    /// Returns the reason to reject a vote request before it is fed to the engine, if any.
+    ///
+    /// The engine does not see such a request, thus it is not recorded in the engine trace.
+    fn vote_request_rejection(&mut self, req: &VoteRequest<C>) -> Option<&'static str> {
+        let candidate = req.vote.to_leader_node_id()?;
+
+        if !self.vote_rate_limiter.try_acquire(&candidate, C::now()) {
+            return Some("too many vote requests from the candidate");
+        }
+
+        None
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(super) fn handle_append_entries_request(&mut self, req: AppendEntriesRequest<C>, tx: AppendEntriesTx<C>) {
         tracing::debug!(req = display(&req), func = func_name!());
 
+        self.detect_split_brain(&req.vote);
+        self.trace_ids.extend(&req.trace_ids);
+
+        self.engine_recorder.record(|| EngineInput::AppendEntries {
+            vote: req.vote.clone(),
+            prev_log_id: req.prev_log_id.clone(),
+            entries: req.entries.iter().map(TraceEntry::copy_from).collect(),
+            leader_commit: req.leader_commit.clone(),
+        });
+
         let is_ok = self.engine.handle_append_entries(&req.vote, req.prev_log_id, req.entries, tx);
 
         if is_ok {
@@ -1273,14 +2177,349 @@ where
         }
     }
 
+    /// Take the checksum of a range of applied logs to send with a heartbeat, and start computing
+    /// the next one if [`Config::log_checksum_interval`] has passed since the last one.
+    ///
+    /// The checksum is computed by a task outside `RaftCore` and sent with the first heartbeat
+    /// after it is done, so that a heartbeat never waits for the log store. The ranges cover the
+    /// logs from the first to the last applied one, and then start over.
+    fn next_log_checksum(&mut self) -> Option<LogChecksum<C>> {
+        let interval = self.config.log_checksum_interval;
+        if interval == 0 {
+            return None;
+        }
+
+        let ready = self.log_checksum.take_ready();
+
+        if self.log_checksum.is_computing() {
+            return ready;
+        }
+
+        let now = C::now();
+        if self.next_log_checksum_at.as_ref().is_some_and(|t| now < *t) {
+            return ready;
+        }
+        self.next_log_checksum_at = Some(now + Duration::from_millis(interval));
+
+        let first = self.engine.state.purge_upto().next_index();
+        let end = self.engine.state.io_applied().next_index();
+
+        let mut start = std::cmp::max(self.log_checksum_cursor, first);
+        if start >= end {
+            start = first;
+        }
+        if start >= end {
+            return ready;
+        }
+        let end = std::cmp::min(end, start + self.config.max_payload_entries);
+
+        self.log_checksum_cursor = end;
+        self.log_checksum.compute(start, end);
+
+        ready
+    }
+
+    /// Verify the local copy of the log range of a checksum sent by the leader.
+    ///
+    /// Only a range that is applied on this node, and not purged, is verified: the applied logs
+    /// are committed and must be the same as the leader's. The logs are read by a task outside
+    /// `RaftCore`, which reports the result with [`Notification::LogChecksumVerified`].
+    fn verify_log_checksum(&mut self, expected: LogChecksum<C>) {
+        if self.engine.state.io_applied() < Some(&expected.last) {
+            return;
+        }
+        if expected.start < self.engine.state.purge_upto().next_index() {
+            return;
+        }
+
+        self.log_checksum.verify(expected);
+    }
+
+    /// Record the result of verifying the local copy of the log range of `expected`.
+    ///
+    /// `got` is `None` if the logs cannot be read, e.g., they are purged since the verification
+    /// started, in which case nothing is verified.
+    fn on_log_checksum_verified(&mut self, expected: LogChecksum<C>, got: Option<LogChecksum<C>>) {
+        let Some(got) = got else {
+            return;
+        };
+
+        self.runtime_stats.log_checksums_verified += 1;
+
+        if got != expected {
+            self.runtime_stats.log_checksum_mismatches += 1;
+            tracing::error!(
+                expected = display(&expected),
+                got = display(&got),
+                "log checksum mismatch: the local log diverges from the leader"
+            );
+        }
+    }
+
+    /// Read the logs in `[first, last]` in a spawned task, for a follower that cannot read them
+    /// from its own log store, and send them to `tx`.
+    ///
+    /// The logs are read outside `RaftCore`, so that a slow log store does not delay heartbeats.
+    /// An empty `Vec` is sent if this node does not have all of these logs.
+    async fn spawn_read_logs_to_repair(
+        &mut self,
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Vec<C::Entry>>,
+    ) {
+        if first.index() < self.engine.state.purge_upto().next_index() || !self.engine.state.has_log_id(&last) {
+            tracing::info!(
+                first = display(&first),
+                last = display(&last),
+                "no logs to repair a follower"
+            );
+            let _ = tx.send(vec![]);
+            return;
+        }
+
+        let mut log_reader = self.log_store.get_log_reader().await;
+
+        let fu = async move {
+            let entries = match log_reader.try_get_log_entries(first.index()..last.index() + 1).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    tracing::error!(error = display(&e), "failed to read logs to repair a follower");
+                    vec![]
+                }
+            };
+            let _ = tx.send(entries);
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_read_logs_to_repair")));
+    }
+
+    /// Fetch a copy of the committed logs in `[first, last]` from the leader, for the state machine
+    /// worker that cannot read them from the local log store.
+    ///
+    /// `tx` is dropped, and the worker stops with the read error, if
+    /// [`Config::repair_corrupted_log`] is disabled or this node is the leader. If the leader is
+    /// unknown or does not respond, `None` is sent after a heartbeat interval, and the worker asks
+    /// again.
+    async fn spawn_log_repair(
+        &mut self,
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Option<Vec<C::Entry>>>,
+    ) {
+        if !self.config.repair_corrupted_log {
+            return;
+        }
+
+        let leader_id = self.current_leader();
+        if leader_id.as_ref() == Some(&self.id) {
+            tracing::error!("the leader cannot repair its own logs: [{}, {}]", first, last);
+            return;
+        }
+
+        let retry_interval = self.config.heartbeat_interval();
+
+        let client = match self.get_leader_node(leader_id.clone()) {
+            Some(node) => {
+                // Safe unwrap(): the node of the leader is found
+                let leader_id = leader_id.unwrap();
+                let client = self.network_factory.new_client(leader_id.clone(), &node).await;
+                Some((leader_id, client))
+            }
+            None => None,
+        };
+        let layers = self.network_layers.clone();
+
+        let n = last.index() + 1 - first.index();
+        let req = LogRepairRequest::new(self.id.clone(), first, last)
+            .with_cluster_name(Some(self.config.cluster_name.clone()));
+        let option = RPCOption::new(retry_interval);
+
+        let fu = async move {
+            let entries = match client {
+                Some((leader_id, mut client)) => {
+                    match layers.call(&leader_id, &mut client, req.clone(), option).await {
+                        Ok(entries) => {
+                            let first = entries.first().map(|e| e.log_id());
+                            let last = entries.last().map(|e| e.log_id());
+
+                            if entries.len() as u64 == n
+                                && first.as_ref() == Some(req.first())
+                                && last.as_ref() == Some(req.last())
+                            {
+                                Some(entries)
+                            } else {
+                                tracing::warn!(
+                                    first = display(first.display()),
+                                    last = display(last.display()),
+                                    n = entries.len(),
+                                    "the leader does not have the logs to repair: {}",
+                                    req
+                                );
+                                None
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = display(&e), "failed to fetch logs to repair from the leader");
+                            None
+                        }
+                    }
+                }
+                None => {
+                    tracing::info!("no leader to repair logs from: {}", req);
+                    None
+                }
+            };
+
+            if entries.is_none() {
+                C::sleep(retry_interval).await;
+            }
+            let _ = tx.send(entries);
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_log_repair")));
+    }
+
+    /// Send the results computed by the state machine of this leader to every follower and
+    /// learner, see [`Config::apply_results_wait`].
+    ///
+    /// A result that fails to be sent is not sent again: the follower computes it itself.
+    async fn send_apply_results(&mut self) {
+        let results = std::mem::take(&mut self.pending_apply_results);
+
+        let targets = match self.engine.leader_handler() {
+            Ok(l) => l
+                .leader
+                .progress
+                .iter()
+                .map(|(id, _)| id.clone())
+                .filter(|id| id != &self.id)
+                .filter(|id| !self.node_infos.lacks(id, NodeInfo::APPLY_RESULTS))
+                .collect::<Vec<_>>(),
+            Err(_) => return,
+        };
+
+        let membership = self.engine.state.membership_state.effective();
+        // Safe unwrap(): target is in membership
+        let targets = targets.into_iter().map(|id| (id.clone(), membership.get_node(&id).unwrap().clone())).collect();
+
+        let req = ApplyResultsRequest::new(self.engine.state.vote_ref().clone(), results)
+            .with_cluster_name(Some(self.config.cluster_name.clone()));
+
+        self.apply_results_senders
+            .send(
+                &mut self.network_factory,
+                &self.network_layers,
+                self.config.heartbeat_interval(),
+                targets,
+                req,
+            )
+            .await;
+    }
+
+    /// Send this node's info to `target` and keep the info it replies with, see
+    /// [`RaftNetworkV2::hello`](crate::network::v2::RaftNetworkV2::hello).
+    ///
+    /// A target that fails to reply is asked again when the replication streams are rebuilt.
+    async fn send_hello(&mut self, target: C::NodeId, target_node: C::Node) {
+        let req = HelloRequest::new(self.id.clone(), self.node_infos.local().clone())
+            .with_cluster_name(Some(self.config.cluster_name.clone()));
+        let timeout = self.config.heartbeat_interval();
+
+        let mut client = self.network_factory.new_client(target.clone(), &target_node).await;
+        let node_infos = self.node_infos.clone();
+        let layers = self.network_layers.clone();
+
+        let fu = async move {
+            let res = C::timeout(timeout, layers.call(&target, &mut client, req, RPCOption::new(timeout))).await;
+            match res {
+                Ok(Ok(info)) => {
+                    tracing::info!(target = display(&target), info = display(&info), "recv hello reply");
+                    node_infos.insert(target, info);
+                }
+                Ok(Err(e)) => {
+                    tracing::debug!(target = display(&target), error = display(&e), "fail to send hello");
+                }
+                Err(_timeout) => {
+                    tracing::debug!(target = display(&target), "timeout sending hello");
+                }
+            }
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("spawn_send_hello")));
+    }
+
+    /// Detect two leaders that are active in the same term.
+    ///
+    /// When an `AppendEntries` from another leader of the same term is received while the lease
+    /// of the current leader has not expired, both leaders are sending heartbeats within one lease
+    /// window. A leader of a smaller term is just a stale leader that has not yet seen the new
+    /// term, and is not reported. Raft safety is not affected, because at most one of them can
+    /// commit, but it indicates clock or configuration problems that operators should know about.
+    fn detect_split_brain(&mut self, req_vote: &VoteOf<C>) {
+        let my_vote = self.engine.state.vote_ref();
+
+        if !req_vote.is_committed() || !my_vote.is_committed() {
+            return;
+        }
+
+        if req_vote.term() != my_vote.term() || req_vote.leader_node_id() == my_vote.leader_node_id() {
+            return;
+        }
+
+        let Some(last_update) = self.engine.state.vote_last_modified() else {
+            return;
+        };
+
+        let lease = self.engine.config.timer_config.leader_lease;
+        let now = C::now();
+        if now > last_update + lease {
+            return;
+        }
+
+        self.runtime_stats.split_brain_detected += 1;
+
+        tracing::warn!(
+            id = display(&self.id),
+            current_leader_vote = display(my_vote),
+            other_leader_vote = display(req_vote),
+            since_current_leader_seen = debug(now - last_update),
+            lease = debug(lease),
+            "split-brain detected: received AppendEntries from another leader of the same term within the lease of the current leader; check clock drift and timeout configuration"
+        );
+    }
+
+    /// Describe an input for the message of a violated invariant, see `Engine::check_invariants()`.
+    ///
+    /// The input is formatted only when it is also logged, at DEBUG level: formatting every input
+    /// would cost an allocation per message on the hot path of debug builds.
+    #[cfg(debug_assertions)]
+    fn invariant_input(input: &impl fmt::Display) -> std::borrow::Cow<'static, str> {
+        if tracing::enabled!(Level::DEBUG) {
+            std::borrow::Cow::Owned(input.to_string())
+        } else {
+            std::borrow::Cow::Borrowed("<not recorded, enable DEBUG level logging to record it>")
+        }
+    }
+
     // TODO: Make this method non-async. It does not need to run any async command in it.
     #[tracing::instrument(level = "debug", skip(self, msg), fields(state = debug(self.engine.state.server_state), id=display(&self.id)))]
     pub(crate) async fn handle_api_msg(&mut self, msg: RaftMsg<C>) {
         tracing::debug!("RAFT_event id={:<2}  input: {}", self.id, msg);
 
+        #[cfg(debug_assertions)]
+        let (prev_vote, input) = (self.engine.state.vote_ref().clone(), Self::invariant_input(&msg));
+
         match msg {
             RaftMsg::AppendEntries { rpc, tx } => {
+                let log_checksum = rpc.log_checksum.clone();
                 self.handle_append_entries_request(rpc, tx);
+
+                if let Some(log_checksum) = log_checksum {
+                    self.verify_log_checksum(log_checksum);
+                }
             }
             RaftMsg::RequestVote { rpc, tx } => {
                 let now = C::now();
@@ -1294,25 +2533,109 @@ where
                 self.handle_vote_request(rpc, tx);
             }
             RaftMsg::BeginReceivingSnapshot { tx } => {
+                self.engine_recorder.record(|| EngineInput::BeginReceivingSnapshot);
                 self.engine.handle_begin_receiving_snapshot(tx);
             }
             RaftMsg::InstallFullSnapshot { vote, snapshot, tx } => {
+                self.engine_recorder.record(|| EngineInput::InstallFullSnapshot {
+                    vote: vote.clone(),
+                    meta: snapshot.meta.clone(),
+                });
                 self.engine.handle_install_full_snapshot(vote, snapshot, tx);
             }
-            RaftMsg::CheckIsLeaderRequest { read_policy, tx } => {
-                self.handle_check_is_leader_request(read_policy, tx).await;
+            RaftMsg::CheckIsLeaderRequest {
+                read_policy,
+                tx,
+                trace_id,
+            } => {
+                self.handle_check_is_leader_request(read_policy, tx, trace_id);
+            }
+            RaftMsg::ClientWriteRequest {
+                app_data,
+                responder,
+                trace_id,
+                permit,
+            } => {
+                drop(permit);
+                let entry = C::Entry::new_normal(LogIdOf::<C>::default(), app_data);
+
+                let max = self.config.max_entry_size;
+                let too_large = self.log_reader.entry_size(&entry).filter(|size| max > 0 && *size > max);
+                if let Some(size) = too_large {
+                    tracing::debug!(size, max, "client write rejected: entry too large");
+                    if let Some(tx) = responder {
+                        tx.send(Err(EntryTooLarge::new(size, max).into()));
+                    }
+                    return;
+                }
+
+                let index = self.write_entry(entry, responder);
+
+                if let (Some(index), Some(trace_id)) = (index, trace_id) {
+                    tracing::debug!(trace_id, index, "client write");
+                    self.trace_ids.insert(index, trace_id);
+                }
+            }
+            RaftMsg::ClusterHealth { timeout, tx } => {
+                self.handle_cluster_health_request(timeout, tx).await;
+            }
+            RaftMsg::StateMachineChecksums { timeout, tx } => {
+                self.handle_state_machine_checksums(timeout, tx).await;
+            }
+            RaftMsg::StateMachineChecksum { at, tx } => {
+                let res = self.sm_handle.send(sm::Command::checksum(at, tx));
+                if let Err(e) = res {
+                    tracing::error!(error = display(e), "error sending Checksum to sm worker");
+                }
+            }
+            RaftMsg::RepairLog { first, last, tx } => {
+                self.spawn_read_logs_to_repair(first, last, tx).await;
+            }
+            RaftMsg::AppendLocalEntry { data, tx } => {
+                self.local_entries.send(LocalEntriesRequest::Append { data, tx });
+            }
+            RaftMsg::ReadLocalEntries { start, max, tx } => {
+                self.local_entries.send(LocalEntriesRequest::Read { start, max, tx });
+            }
+            RaftMsg::PurgeLocalEntries { upto, tx } => {
+                self.local_entries.send(LocalEntriesRequest::Purge { upto, tx });
+            }
+            RaftMsg::ReplicationProgress { tx } => {
+                let res = match self.engine.leader_handler() {
+                    Ok(lh) => {
+                        let progress = &lh.leader.progress;
+                        Ok(progress
+                            .iter()
+                            .map(|(id, p)| {
+                                let voter = progress.is_voter(id) == Some(true);
+                                (id.clone(), TargetProgress::new(voter, p))
+                            })
+                            .collect())
+                    }
+                    Err(forward) => Err(forward.into()),
+                };
+                let _ = tx.send(res);
             }
-            RaftMsg::ClientWriteRequest { app_data, responder } => {
-                self.write_entry(C::Entry::new_normal(LogIdOf::<C>::default(), app_data), responder);
+            RaftMsg::LeadershipInfo { tx } => {
+                let _ = tx.send(self.leadership_info());
             }
             RaftMsg::Initialize { members, tx } => {
                 tracing::info!(
-                    members = debug(&members),
-                    "received RaftMsg::Initialize: {}",
+                    members = debug(&members),
+                    "received RaftMsg::Initialize: {}",
+                    func_name!()
+                );
+
+                self.handle_initialize(members, tx);
+            }
+            RaftMsg::InitializeFromSnapshot { snapshot, tx } => {
+                tracing::info!(
+                    snapshot = display(&snapshot),
+                    "received RaftMsg::InitializeFromSnapshot: {}",
                     func_name!()
                 );
 
-                self.handle_initialize(members, tx);
+                self.handle_initialize_from_snapshot(snapshot, tx);
             }
             RaftMsg::ChangeMembership { changes, retain, tx } => {
                 tracing::info!(
@@ -1324,6 +2647,50 @@ where
 
                 self.change_membership(changes, retain, tx);
             }
+            RaftMsg::BumpVersion { version, supports, tx } => {
+                tracing::info!(version, "received RaftMsg::BumpVersion: {}", func_name!());
+
+                self.bump_version(version, supports, tx);
+            }
+            RaftMsg::SetElectionVeto { veto, tx } => {
+                tracing::info!(
+                    "received RaftMsg::SetElectionVeto: {}: is_set: {}",
+                    func_name!(),
+                    veto.is_some()
+                );
+
+                self.election_veto = veto;
+                let _ = tx.send(());
+            }
+            RaftMsg::SetLeaderFitness { fitness, tx } => {
+                tracing::info!(
+                    "received RaftMsg::SetLeaderFitness: {}: is_set: {}",
+                    func_name!(),
+                    fitness.is_some()
+                );
+
+                self.leader_fitness = fitness;
+                let _ = tx.send(());
+            }
+            RaftMsg::SetLogArchiver { archiver, tx } => {
+                tracing::info!(
+                    "received RaftMsg::SetLogArchiver: {}: is_set: {}",
+                    func_name!(),
+                    archiver.is_some()
+                );
+
+                self.log_archive = match archiver {
+                    Some(archiver) => Some(LogArchiveHandle::spawn(
+                        archiver,
+                        self.log_store.get_log_reader().await,
+                        self.config.max_payload_entries,
+                        self.tx_notification.clone(),
+                    )),
+                    None => None,
+                };
+                self.engine.set_log_archive(self.log_archive.is_some());
+                let _ = tx.send(());
+            }
             RaftMsg::ExternalCoreRequest { req } => {
                 req(&self.engine.state);
             }
@@ -1331,12 +2698,27 @@ where
                 from: current_leader_vote,
                 to,
             } => {
-                if self.engine.state.vote_ref() == &current_leader_vote {
+                if self.id == to && self.runtime_config.readonly.load(Ordering::Relaxed) {
+                    tracing::info!(
+                        "read-only, refuse to take over leadership from: {}",
+                        current_leader_vote
+                    );
+                } else if self.id == to && self.is_election_vetoed("leadership is transferred to this node") {
+                    tracing::info!(
+                        "election vetoed, refuse to take over leadership from: {}",
+                        current_leader_vote
+                    );
+                } else if self.engine.state.vote_ref() == &current_leader_vote {
                     tracing::info!("Transfer Leader from: {}, to {}", current_leader_vote, to);
 
-                    self.engine.state.vote.disable_lease();
                     if self.id == to {
-                        self.engine.elect();
+                        self.elect_after_storage_check(ElectionTrigger::TransferLeader {
+                            leader_vote: current_leader_vote,
+                        })
+                        .await;
+                    } else {
+                        self.engine_recorder.record(|| EngineInput::HandleTransferLeader { to: to.clone() });
+                        self.engine.state.vote.disable_lease();
                     }
                 }
             }
@@ -1345,12 +2727,16 @@ where
 
                 match cmd {
                     ExternalCommand::Elect => {
-                        if self.engine.state.membership_state.effective().is_voter(&self.id) {
+                        if self.runtime_config.readonly.load(Ordering::Relaxed) {
+                            tracing::info!("ExternalCommand: read-only, refuse to elect");
+                        } else if !self.engine.state.membership_state.effective().is_voter(&self.id) {
+                            // Node is switched to learner.
+                        } else if self.is_election_vetoed("triggered by application") {
+                            tracing::info!("ExternalCommand: election vetoed, refuse to elect");
+                        } else {
                             // TODO: reject if it is already a leader?
-                            self.engine.elect();
+                            self.elect_after_storage_check(ElectionTrigger::External).await;
                             tracing::debug!("ExternalCommand: triggered election");
-                        } else {
-                            // Node is switched to learner.
                         }
                     }
                     ExternalCommand::Heartbeat => {
@@ -1358,20 +2744,31 @@ where
                     }
                     ExternalCommand::Snapshot => self.trigger_snapshot(),
                     ExternalCommand::GetSnapshot { tx } => {
-                        let cmd = sm::Command::get_snapshot(tx);
+                        let cmd = sm::Command::get_snapshot(None, tx);
                         let res = self.sm_handle.send(cmd);
                         if let Err(e) = res {
                             tracing::error!(error = display(e), "error sending GetSnapshot to sm worker");
                         }
                     }
                     ExternalCommand::PurgeLog { upto } => {
+                        self.engine_recorder.record(|| EngineInput::PurgeLog { upto });
                         self.engine.trigger_purge_log(upto);
                     }
                     ExternalCommand::TriggerTransferLeader { to } => {
+                        self.engine_recorder.record(|| EngineInput::TriggerTransferLeader { to: to.clone() });
                         self.engine.trigger_transfer_leader(to);
                     }
+                    ExternalCommand::SendDecommission {
+                        target,
+                        node,
+                        timeout,
+                        tx,
+                    } => {
+                        self.send_decommission(target, node, timeout, tx).await;
+                    }
                     ExternalCommand::AllowNextRevert { to, allow, tx } => {
-                        //
+                        self.engine_recorder.record(|| EngineInput::AllowNextRevert { to: to.clone(), allow });
+
                         let res = match self.engine.leader_handler() {
                             Ok(mut l) => {
                                 let res = l.replication_handler().allow_next_revert(to, allow);
@@ -1384,6 +2781,18 @@ where
                         };
                         let _ = tx.send(res);
                     }
+                    ExternalCommand::Resync { to, tx } => {
+                        self.engine_recorder.record(|| EngineInput::Resync { to: to.clone() });
+
+                        let res = match self.engine.leader_handler() {
+                            Ok(mut l) => l.replication_handler().resync(to).map_err(ResyncError::from),
+                            Err(e) => {
+                                tracing::warn!("Resync: current node is not a Leader");
+                                Err(ResyncError::from(e))
+                            }
+                        };
+                        let _ = tx.send(res);
+                    }
                     ExternalCommand::StateMachineCommand { sm_cmd } => {
                         let res = self.sm_handle.send(sm_cmd);
                         if let Err(e) = res {
@@ -1393,12 +2802,18 @@ where
                 }
             }
         };
+
+        #[cfg(debug_assertions)]
+        self.engine.check_invariants(&prev_vote, input);
     }
 
     #[tracing::instrument(level = "debug", skip_all, fields(state = debug(self.engine.state.server_state), id=display(&self.id)))]
-    pub(crate) fn handle_notification(&mut self, notify: Notification<C>) -> Result<(), Fatal<C>> {
+    pub(crate) async fn handle_notification(&mut self, notify: Notification<C>) -> Result<(), Fatal<C>> {
         tracing::debug!("RAFT_event id={:<2} notify: {}", self.id, notify);
 
+        #[cfg(debug_assertions)]
+        let (prev_vote, input) = (self.engine.state.vote_ref().clone(), Self::invariant_input(&notify));
+
         match notify {
             Notification::VoteResponse {
                 target,
@@ -1417,6 +2832,13 @@ where
                 #[allow(clippy::collapsible_if)]
                 if self.engine.candidate.is_some() {
                     if self.does_candidate_vote_match(&candidate_vote, "VoteResponse") {
+                        self.engine_recorder.record(|| EngineInput::VoteResponse {
+                            target: target.clone(),
+                            resp: resp.clone(),
+                        });
+                        if !resp.vote_granted {
+                            self.election_stats.on_vote_rejected();
+                        }
                         self.engine.handle_vote_resp(target, resp);
                     }
                 }
@@ -1436,18 +2858,32 @@ where
                 );
 
                 if self.does_leader_vote_match(&leader_vote, "HigherVote") {
+                    self.engine_recorder.record(|| EngineInput::HigherVote { higher: higher.clone() });
+
                     // Rejected vote change is ok.
                     let _ = self.engine.vote_handler().update_vote(&higher);
                 }
             }
 
+            Notification::ReadBatchDue => {
+                // The due batch is confirmed at the end of the current loop.
+                tracing::debug!("read batch is due");
+            }
+
             Notification::Tick { i } => {
                 // check every timer
 
                 let now = C::now();
                 tracing::debug!("received tick: {}, now: {}", i, now.display());
 
-                self.handle_tick_election();
+                self.handle_tick_election().await;
+
+                self.metrics_history.record(|| MetricsSample {
+                    at: now.into(),
+                    metrics: self.tx_metrics.borrow_watched().clone(),
+                });
+
+                self.apply_lag_alarm.check(now, self.engine.state.committed(), self.engine.state.io_applied());
 
                 // TODO: test: fixture: make isolated_nodes a single-way isolating.
 
@@ -1462,10 +2898,12 @@ where
 
                     // Install next heartbeat
                     if let Some(l) = self.engine.leader_mut() {
-                        l.next_heartbeat = C::now() + Duration::from_millis(self.config.heartbeat_interval);
+                        l.next_heartbeat = C::now() + self.config.heartbeat_interval();
                     }
                 }
 
+                self.check_leader_fitness();
+
                 // When a membership that removes the leader is committed,
                 // the leader continue to work for a short while before reverting to a learner.
                 // This way, let the leader replicate the `membership-log-is-committed` message to
@@ -1485,7 +2923,13 @@ where
                 //       ---
                 //       A better way is to make leader step down a command that waits for the log to be applied.
                 if self.engine.state.io_applied() >= self.engine.state.membership_state.effective().log_id().as_ref() {
+                    let before = (self.engine.state.server_state, self.engine.output.len());
                     self.engine.leader_step_down();
+
+                    // It is checked on every tick: record it only when the leader did step down.
+                    if (self.engine.state.server_state, self.engine.output.len()) != before {
+                        self.engine_recorder.record(|| EngineInput::LeaderStepDown);
+                    }
                 }
             }
 
@@ -1494,17 +2938,77 @@ where
                 return Err(Fatal::StorageError(error));
             }
 
+            Notification::ReplicationPanicked {
+                target,
+                session_id,
+                error,
+            } => {
+                tracing::error!(
+                    target = display(&target),
+                    session_id = display(&session_id),
+                    "RaftCore received Notification::ReplicationPanicked: {}",
+                    error
+                );
+
+                // A stream of a previous leader or membership is already gone.
+                if !self.does_replication_session_match(&session_id, "ReplicationPanicked") {
+                    return Ok(());
+                }
+
+                if !self.config.restart_replication_on_panic {
+                    return Err(Fatal::TaskPanicked(error));
+                }
+
+                tracing::warn!(target = display(&target), "restart replication stream after panic");
+                self.engine_recorder.record(|| EngineInput::RestartReplication { target: target.clone() });
+                self.engine.replication_handler().restart_replication_stream(target);
+            }
+
+            Notification::TaskPanicked { error } => {
+                tracing::error!("RaftCore received Notification::TaskPanicked: {}", error);
+                return Err(Fatal::TaskPanicked(error));
+            }
+
+            Notification::RepairLog { first, last, tx } => {
+                // The leader is connected in the runtime loop, which can await.
+                self.pending_log_repair = Some((first, last, tx));
+            }
+
+            Notification::LogsArchived { next } => {
+                self.engine.on_logs_archived(next);
+            }
+
+            Notification::LogChecksumComputed { log_checksum } => {
+                self.log_checksum.on_computed(log_checksum);
+            }
+
+            Notification::LogChecksumVerified { expected, got } => {
+                self.on_log_checksum_verified(expected, got);
+            }
+
+            Notification::StorageChecked { result } => {
+                self.on_storage_checked(result);
+            }
+
             Notification::LocalIO { io_id } => {
+                self.engine_recorder.record(|| EngineInput::LocalIO {
+                    io_id: io_id.clone().into(),
+                });
                 self.engine.state.log_progress_mut().flush(io_id.clone());
 
                 match io_id {
                     IOId::Log(log_io_id) => {
+                        self.flushed_log_id = log_io_id.log_id.clone();
+
                         // No need to check against membership change,
                         // because not like removing-then-adding a remote node,
                         // local log wont revert when membership changes.
                         #[allow(clippy::collapsible_if)]
                         if self.engine.leader.is_some() {
                             if self.does_leader_vote_match(&log_io_id.committed_vote, "LocalIO Notification") {
+                                if let Some(log_id) = &log_io_id.log_id {
+                                    self.write_latency.on_flush(log_id.index(), C::now());
+                                }
                                 self.engine.replication_handler().update_local_progress(log_io_id.log_id);
                             }
                         }
@@ -1523,7 +3027,13 @@ where
 
                     // replication_handler() won't panic because:
                     // The leader is still valid because progress.session_id.leader_vote does not change.
+                    self.engine_recorder.record(|| EngineInput::ReplicationProgress {
+                        target: progress.target.clone(),
+                        result: progress.result.clone().map(|x| x.0),
+                        has_payload,
+                    });
                     self.engine.replication_handler().update_progress(progress.target, progress.result, has_payload);
+                    self.evict_replicated_entries();
                 }
             }
 
@@ -1541,6 +3051,7 @@ where
                     );
                     // replication_handler() won't panic because:
                     // The leader is still valid because progress.session_id.leader_vote does not change.
+                    self.engine_recorder.record_leader_clock(&target, sending_time);
                     self.engine.replication_handler().update_leader_clock(target, sending_time);
                 }
             }
@@ -1558,6 +3069,7 @@ where
                             func_name!()
                         );
 
+                        self.engine_recorder.record(|| EngineInput::SnapshotBuilt { meta: meta.clone() });
                         self.engine.on_building_snapshot_done(meta);
                     }
                     sm::Response::InstallSnapshot((log_io_id, meta)) => {
@@ -1568,6 +3080,10 @@ where
                             func_name!()
                         );
 
+                        self.engine_recorder.record(|| EngineInput::SnapshotInstalled {
+                            io_id: IOId::Log(log_io_id.clone()).into(),
+                            meta: meta.clone(),
+                        });
                         self.engine.state.log_progress_mut().flush(IOId::Log(log_io_id));
 
                         if let Some(meta) = meta {
@@ -1579,41 +3095,92 @@ where
                         }
                     }
                     sm::Response::Apply(res) => {
+                        self.engine_recorder.record(|| EngineInput::Applied {
+                            last_applied: res.last_applied.clone(),
+                        });
+                        self.write_latency.on_apply(res.last_applied.index(), C::now());
+                        self.app_index.insert(res.app_keys);
+                        if self.engine.leader.is_some() {
+                            self.pending_apply_results.extend(res.computed);
+                        }
                         self.engine.state.apply_progress_mut().flush(res.last_applied);
                     }
                 }
             }
         };
+
+        #[cfg(debug_assertions)]
+        self.engine.check_invariants(&prev_vote, input);
+
         Ok(())
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
-    fn handle_tick_election(&mut self) {
+    async fn handle_tick_election(&mut self) {
         let now = C::now();
 
-        tracing::debug!("try to trigger election by tick, now: {}", now.display());
+        subsystem_log!(
+            self.runtime_config,
+            Election,
+            DEBUG,
+            "try to trigger election by tick, now: {}",
+            now.display()
+        );
+
+        if !self.is_election_timeout_passed(now) {
+            return;
+        }
+
+        if self.is_election_vetoed("election timeout") {
+            return;
+        }
+
+        self.elect_after_storage_check(ElectionTrigger::ElectionTimeout).await;
+    }
 
+    /// Whether this node is due to start an election because the election timeout passed.
+    fn is_election_timeout_passed(&self, now: InstantOf<C>) -> bool {
         // TODO: leader lease should be extended. Or it has to examine if it is leader
         //       before electing.
         if self.engine.state.server_state == ServerState::Leader {
-            tracing::debug!("already a leader, do not elect again");
-            return;
+            subsystem_log!(
+                self.runtime_config,
+                Election,
+                DEBUG,
+                "already a leader, do not elect again"
+            );
+            return false;
         }
 
         if !self.engine.state.membership_state.effective().is_voter(&self.id) {
-            tracing::debug!("this node is not a voter");
-            return;
+            subsystem_log!(self.runtime_config, Election, DEBUG, "this node is not a voter");
+            return false;
         }
 
         if !self.runtime_config.enable_elect.load(Ordering::Relaxed) {
-            tracing::debug!("election is disabled");
-            return;
+            subsystem_log!(self.runtime_config, Election, DEBUG, "election is disabled");
+            return false;
+        }
+
+        if self.runtime_config.readonly.load(Ordering::Relaxed) {
+            subsystem_log!(self.runtime_config, Election, DEBUG, "read-only, do not elect");
+            return false;
         }
 
         if self.engine.state.membership_state.effective().voter_ids().count() == 1 {
-            tracing::debug!("this is the only voter, do election at once");
+            subsystem_log!(
+                self.runtime_config,
+                Election,
+                DEBUG,
+                "this is the only voter, do election at once"
+            );
         } else {
-            tracing::debug!("there are multiple voter, check election timeout");
+            subsystem_log!(
+                self.runtime_config,
+                Election,
+                DEBUG,
+                "there are multiple voter, check election timeout"
+            );
 
             let local_vote = &self.engine.state.vote;
             let timer_config = &self.engine.config.timer_config;
@@ -1624,21 +3191,314 @@ where
                 election_timeout += timer_config.smaller_log_timeout;
             }
 
-            tracing::debug!("local vote: {}, election_timeout: {:?}", local_vote, election_timeout,);
+            subsystem_log!(
+                self.runtime_config,
+                Election,
+                DEBUG,
+                "local vote: {}, election_timeout: {:?}",
+                local_vote,
+                election_timeout,
+            );
 
             if local_vote.is_expired(now, election_timeout) {
                 tracing::info!("election timeout passed, about to elect");
             } else {
-                tracing::debug!("election timeout has not yet passed",);
-                return;
+                subsystem_log!(
+                    self.runtime_config,
+                    Election,
+                    DEBUG,
+                    "election timeout has not yet passed",
+                );
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Build the [`LeadershipInfo`] of this node.
+    ///
+    /// The reason for not starting an election is determined with the same checks as
+    /// [`Self::handle_tick_election()`], without consulting the election veto hook.
+    fn leadership_info(&self) -> LeadershipInfo<C> {
+        let now = C::now();
+        let st = &self.engine.state;
+        let vote = st.vote_ref();
+
+        LeadershipInfo {
+            id: self.id.clone(),
+            state: st.server_state,
+            vote: vote.clone(),
+            vote_committed: vote.is_committed(),
+            current_leader: self.current_leader(),
+            since_last_heard: st.vote.last_update().map(|t| now.saturating_duration_since(t)),
+            no_election_reason: self.no_election_reason(now),
+        }
+    }
+
+    /// Why this node does not start an election at the next election tick.
+    fn no_election_reason(&self, now: InstantOf<C>) -> Option<NoElectionReason> {
+        let st = &self.engine.state;
+        let membership = st.membership_state.effective();
+
+        match st.server_state {
+            ServerState::Leader => return Some(NoElectionReason::Leader),
+            ServerState::Candidate => return Some(NoElectionReason::Campaigning),
+            _ => {}
+        }
+
+        if membership.voter_ids().next().is_none() {
+            return Some(NoElectionReason::Uninitialized);
+        }
+
+        if !membership.is_voter(&self.id) {
+            return Some(NoElectionReason::NotVoter);
+        }
+
+        if !self.runtime_config.enable_elect.load(Ordering::Relaxed) {
+            return Some(NoElectionReason::ElectionDisabled);
+        }
+
+        if self.runtime_config.readonly.load(Ordering::Relaxed) {
+            return Some(NoElectionReason::ReadOnly);
+        }
+
+        if let Some(reason) = &self.storage_check_failure {
+            return Some(NoElectionReason::StorageCheckFailed { reason: reason.clone() });
+        }
+
+        // The only voter elects at once, regardless of the vote.
+        if membership.voter_ids().count() > 1 {
+            let timer_config = &self.engine.config.timer_config;
+
+            let mut election_timeout = timer_config.election_timeout;
+            if self.engine.is_there_greater_log() {
+                election_timeout += timer_config.smaller_log_timeout;
+            }
+
+            let (last_update, lease, _) = st.vote.lease_info();
+            if let Some(last_update) = last_update {
+                let expire_at = last_update + lease + election_timeout;
+                if now <= expire_at {
+                    return Some(NoElectionReason::LeaseNotExpired {
+                        remaining: expire_at.saturating_duration_since(now),
+                    });
+                }
             }
         }
 
-        // Every time elect, reset this flag.
-        self.engine.reset_greater_log();
+        if self.election_veto.is_some()
+            && let Some(reason) = self.election_stats.last_veto_reason()
+        {
+            return Some(NoElectionReason::Vetoed {
+                reason: reason.to_string(),
+            });
+        }
+
+        None
+    }
 
-        tracing::info!("do trigger election");
+    /// Start an election and record it in the election metrics.
+    fn elect(&mut self, reason: &str) {
         self.engine.elect();
+        self.record_election_start(reason);
+    }
+
+    /// Hand off the leadership to the fittest up-to-date voter if it is significantly fitter than
+    /// this leader, once every [`Config::leader_fitness_interval`].
+    fn check_leader_fitness(&mut self) {
+        let interval = self.config.leader_fitness_interval;
+        if interval == 0 {
+            return;
+        }
+
+        let Some(fitness) = self.leader_fitness.as_ref() else {
+            return;
+        };
+
+        let Some(leader) = self.engine.leader_ref() else {
+            return;
+        };
+
+        if leader.get_transfer_to().is_some() {
+            return;
+        }
+
+        let now = C::now();
+        if self.next_leader_fitness_at.as_ref().is_some_and(|t| now < *t) {
+            return;
+        }
+        self.next_leader_fitness_at = Some(now + Duration::from_millis(interval));
+
+        let membership = self.engine.state.membership_state.effective();
+        let last_log_id = leader.last_log_id();
+
+        let Some(my_node) = membership.get_node(&self.id) else {
+            return;
+        };
+        let my_score = fitness(&self.id, my_node);
+
+        let fittest = leader
+            .progress
+            .iter()
+            .filter(|(id, _)| id != &self.id)
+            .filter(|(id, _)| leader.progress.is_voter(id) == Some(true))
+            .filter(|(_, p)| p.matching() == last_log_id)
+            .filter_map(|(id, _)| membership.get_node(id).map(|node| (id, fitness(id, node))))
+            .max_by_key(|(_, score)| *score);
+
+        let Some((to, score)) = fittest else {
+            return;
+        };
+
+        if score < my_score.saturating_add(self.config.leader_fitness_margin) {
+            return;
+        }
+
+        let to = to.clone();
+        tracing::info!(
+            to = display(&to),
+            score,
+            my_score,
+            "hand off leadership to a fitter voter"
+        );
+
+        self.runtime_stats.leader_fitness_handoffs += 1;
+        self.engine_recorder.record(|| EngineInput::TriggerTransferLeader { to: to.clone() });
+        self.engine.trigger_transfer_leader(to);
+    }
+
+    /// Consult the hook set with [`Raft::set_election_veto()`] before starting an election for
+    /// `reason`, and record a veto in the election metrics.
+    ///
+    /// [`Raft::set_election_veto()`]: crate::Raft::set_election_veto
+    fn is_election_vetoed(&mut self, reason: &str) -> bool {
+        let Some(veto) = self.election_veto.as_ref() else {
+            return false;
+        };
+
+        match veto(reason) {
+            Ok(()) => false,
+            Err(veto_reason) => {
+                subsystem_log!(
+                    self.runtime_config,
+                    Election,
+                    DEBUG,
+                    "election for {} is vetoed: {}",
+                    reason,
+                    veto_reason
+                );
+                self.election_stats.on_vetoed(veto_reason);
+                true
+            }
+        }
+    }
+
+    /// Start an election for `trigger`, after reading back the vote and the last flushed log
+    /// entry from the log store, if [`Config::check_storage_before_election`] is enabled.
+    ///
+    /// The storage is read in a spawned task, so that a slow or hung disk does not block
+    /// `RaftCore`. The election is started when the result is received, see
+    /// [`Self::on_storage_checked()`]. If a check is already being run, `trigger` replaces the one
+    /// waiting for it.
+    async fn elect_after_storage_check(&mut self, trigger: ElectionTrigger<C>) {
+        if !self.config.check_storage_before_election {
+            self.elect_by(trigger);
+            return;
+        }
+
+        if self.pending_storage_check.replace(trigger).is_some() {
+            tracing::debug!("storage check is being run, election is started when it passes");
+            return;
+        }
+
+        // The last log is purged, only the snapshot is left.
+        let last = self.flushed_log_id.clone().filter(|last| Some(last) > self.engine.state.purge_upto());
+
+        let log_reader = self.log_store.get_log_reader().await;
+        let tx = self.tx_notification.clone();
+
+        let fu = async move {
+            let result = check_storage(log_reader, last).await;
+            let _ = tx.send(Notification::StorageChecked { result }).await;
+        };
+
+        #[allow(clippy::let_underscore_future)]
+        let _ = C::spawn(fu.instrument(tracing::debug_span!("check_storage")));
+    }
+
+    /// Start the election waiting for the storage check, if the storage passes it and the
+    /// election is still due.
+    ///
+    /// A node that can not read its own log must not become a leader and replicate it. The
+    /// failure is kept until a later check passes, and is reported in the metrics.
+    fn on_storage_checked(&mut self, result: Result<(), String>) {
+        let Some(trigger) = self.pending_storage_check.take() else {
+            return;
+        };
+
+        match result {
+            Ok(()) => {
+                if let Some(failure) = self.storage_check_failure.take() {
+                    tracing::info!("storage check passed, previous failure: {}", failure);
+                }
+            }
+            Err(failure) => {
+                tracing::error!("storage check failed, refuse to elect for {}: {}", trigger, failure);
+                self.storage_check_failure = Some(failure);
+                return;
+            }
+        }
+
+        // The state may have changed while the storage is being checked.
+        let due = match &trigger {
+            ElectionTrigger::ElectionTimeout => self.is_election_timeout_passed(C::now()),
+            ElectionTrigger::External | ElectionTrigger::TransferLeader { .. } => {
+                !self.runtime_config.readonly.load(Ordering::Relaxed)
+                    && self.engine.state.membership_state.effective().is_voter(&self.id)
+            }
+        };
+
+        if due {
+            self.elect_by(trigger);
+        } else {
+            tracing::info!("election is no longer due after storage check: {}", trigger);
+        }
+    }
+
+    /// Start an election for `trigger`.
+    fn elect_by(&mut self, trigger: ElectionTrigger<C>) {
+        match &trigger {
+            ElectionTrigger::ElectionTimeout => {
+                self.engine_recorder.record(|| EngineInput::ElectionTimeout);
+
+                // Every time elect, reset this flag.
+                self.engine.reset_greater_log();
+
+                tracing::info!("do trigger election");
+            }
+            ElectionTrigger::External => {
+                self.engine_recorder.record(|| EngineInput::Elect);
+            }
+            ElectionTrigger::TransferLeader { leader_vote } => {
+                if self.engine.state.vote_ref() != leader_vote {
+                    tracing::info!("vote changed, ignore leadership transfer from: {}", leader_vote);
+                    return;
+                }
+
+                self.engine_recorder.record(|| EngineInput::HandleTransferLeader { to: self.id.clone() });
+                self.engine.state.vote.disable_lease();
+            }
+        }
+
+        self.elect(trigger.reason());
+    }
+
+    /// Record the election just started by the engine, if any, in the election metrics.
+    fn record_election_start(&mut self, reason: &str) {
+        if let Some(candidate) = self.engine.candidate_ref() {
+            self.election_stats.on_start(reason, candidate.vote_ref().clone());
+        }
     }
 
     /// If a message is sent by a previous Candidate but is received by current Candidate,
@@ -1755,12 +3615,24 @@ where
                 committed_vote: vote,
                 entries,
             } => {
+                let first_index = entries.first().unwrap().index();
                 let last_log_id = entries.last().unwrap().log_id();
-                tracing::debug!("AppendEntries: {}", entries.display_n(10));
+                let last_index = last_log_id.index();
+                subsystem_log!(
+                    self.runtime_config,
+                    StorageIo,
+                    DEBUG,
+                    "AppendEntries: {}",
+                    entries.display_n(10)
+                );
 
                 let entry_count = entries.len() as u64;
                 self.runtime_stats.append_batch.record(entry_count);
 
+                if self.engine.leader.is_some() {
+                    self.write_latency.on_append(&vote.clone().into_vote(), first_index, last_index, C::now());
+                }
+
                 let io_id = IOId::new_log_io(vote, Some(last_log_id));
                 let notify = Notification::LocalIO { io_id: io_id.clone() };
                 let callback = IOFlushed::new(notify, self.tx_notification.downgrade());
@@ -1775,12 +3647,33 @@ where
                 // because `append()` may call the callback before returning.
                 self.engine.state.log_progress_mut().submit(io_id);
 
+                let trace_ids = self.trace_ids.range(first_index, last_index + 1);
+                let span = tracing::debug_span!("append_to_log_store", trace_ids = debug(trace_ids.values()));
+
+                fail_point!("before_append_log", |_| {
+                    Err(StorageError::write_logs(AnyError::error(
+                        "failpoint: before_append_log",
+                    )))
+                });
+
                 // Submit IO request, do not wait for the response.
-                self.log_store.append(entries, callback).await?;
+                self.log_store.append(entries, callback).instrument(span).await?;
+
+                // A follower does not send the entries, and neither does a leader without a target.
+                if self.engine.leader.is_none() || self.replications.is_empty() {
+                    self.trace_ids.evict_before(last_index + 1);
+                }
             }
             Command::SaveVote { vote } => {
                 self.engine.state.log_progress_mut().submit(IOId::new(&vote));
+
+                fail_point!("before_save_vote", |_| {
+                    Err(StorageError::write_vote(AnyError::error("failpoint: before_save_vote")))
+                });
                 self.log_store.save_vote(&vote).await?;
+                fail_point!("after_save_vote", |_| {
+                    Err(StorageError::write_vote(AnyError::error("failpoint: after_save_vote")))
+                });
 
                 let _ = self
                     .tx_notification
@@ -1808,8 +3701,18 @@ where
                 self.engine.state.io_state_mut().update_purged(Some(upto));
             }
             Command::TruncateLog { since } => {
+                // The truncation is not waited to be persisted:
+                // the callback of the next `append()` is the barrier for both.
+                // See `RaftLogStorage::truncate()`.
                 self.log_store.truncate(since.clone()).await?;
 
+                // A crash here leaves the log truncated but not yet appended.
+                fail_point!("after_truncate_log", |_| {
+                    Err(StorageError::write_logs(AnyError::error(
+                        "failpoint: after_truncate_log",
+                    )))
+                });
+
                 // Inform clients waiting for logs to be applied.
                 let removed = self.client_responders.split_off(&since.index());
                 if !removed.is_empty() {
@@ -1839,12 +3742,22 @@ where
                 }
             }
             Command::BroadcastHeartbeat { session_id, committed } => {
-                self.heartbeat_handle.broadcast(HeartbeatEvent::new(C::now(), session_id, committed))
+                let log_checksum = self.next_log_checksum();
+                let event = HeartbeatEvent::new(C::now(), session_id, committed).with_log_checksum(log_checksum);
+                self.heartbeat_handle.broadcast(event)
             }
             Command::SaveCommittedAndApply {
                 already_applied: already_committed,
                 upto,
             } => {
+                // Every commit is applied, including one that is not sent to followers at once,
+                // see `Config::broadcast_commit_on_local_flush`.
+                self.write_latency.on_commit(upto.index(), C::now());
+
+                fail_point!("before_save_committed", |_| {
+                    Err(StorageError::write(AnyError::error("failpoint: before_save_committed")))
+                });
+
                 self.engine.state.apply_progress_mut().submit(upto.clone());
 
                 self.log_store.save_committed(Some(upto.clone())).await?;
@@ -1868,12 +3781,38 @@ where
 
                 let effective = self.engine.state.membership_state.effective().clone();
 
+                for ReplicationProgress(target, _) in targets.iter() {
+                    if !self.node_infos.contains(target) {
+                        self.send_hello(target.clone(), effective.get_node(target).unwrap().clone()).await;
+                    }
+                }
+
                 let nodes = targets.into_iter().map(|p| {
                     let node_id = p.0;
                     (node_id.clone(), effective.get_node(&node_id).unwrap().clone())
                 });
 
-                self.heartbeat_handle.spawn_workers(&mut self.network_factory, &self.tx_notification, nodes).await;
+                self.heartbeat_handle
+                    .spawn_workers(
+                        &mut self.network_factory,
+                        &self.tx_notification,
+                        &self.network_events,
+                        &self.network_layers,
+                        nodes,
+                    )
+                    .await;
+            }
+            Command::RestartReplicationStream {
+                target: ReplicationProgress(target, progress),
+            } => {
+                if let Some(old) = self.replications.remove(&target) {
+                    // Drop sender to notify the task to shutdown
+                    drop(old.tx_repl);
+                    let _x = old.join_handle.await;
+                }
+
+                let handle = self.spawn_replication_stream(target.clone(), progress).await;
+                self.replications.insert(target, handle);
             }
             Command::StateMachine { command } => {
                 let io_id = command.get_log_progress();
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// A place in the queue of client writes, taken by `Raft` before it sends a write to `RaftCore`.
+///
+/// The place is released when the permit is dropped: when `RaftCore` receives the write, or when
+/// the write never reaches it, e.g., the caller is cancelled while waiting for room in the
+/// channel, or `RaftCore` has quit.
+pub(crate) struct ClientWritePermit {
+    queued: Arc<AtomicU64>,
+}
+
+impl ClientWritePermit {
+    /// Take a place in the queue counted by `queued`, if fewer than `max` places are taken, or if
+    /// `max` is `0`.
+    ///
+    /// Otherwise, it returns the number of places taken.
+    pub(crate) fn try_acquire(queued: &Arc<AtomicU64>, max: u64) -> Result<Self, u64> {
+        let taken = queued.fetch_add(1, Ordering::Relaxed);
+
+        if max > 0 && taken >= max {
+            queued.fetch_sub(1, Ordering::Relaxed);
+            return Err(taken);
+        }
+
+        Ok(Self { queued: queued.clone() })
+    }
+}
+
+impl Drop for ClientWritePermit {
+    fn drop(&mut self) {
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::Ordering;
+
+    use super::ClientWritePermit;
+
+    #[test]
+    fn test_client_write_permit() -> anyhow::Result<()> {
+        let queued = Arc::new(AtomicU64::new(0));
+
+        let p1 = ClientWritePermit::try_acquire(&queued, 2).unwrap();
+        let p2 = ClientWritePermit::try_acquire(&queued, 2).unwrap();
+        assert_eq!(Some(2), ClientWritePermit::try_acquire(&queued, 2).err());
+        assert_eq!(2, queued.load(Ordering::Relaxed));
+
+        drop(p1);
+        assert_eq!(1, queued.load(Ordering::Relaxed));
+
+        let p3 = ClientWritePermit::try_acquire(&queued, 2).unwrap();
+        drop(p2);
+        drop(p3);
+        assert_eq!(0, queued.load(Ordering::Relaxed));
+
+        // No limit.
+        let _p = ClientWritePermit::try_acquire(&queued, 0).unwrap();
+        let _p = ClientWritePermit::try_acquire(&queued, 0).unwrap();
+        assert_eq!(2, queued.load(Ordering::Relaxed));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
 //! This mod defines external command sent by application to Raft.
 
 use std::fmt;
+use std::time::Duration;
 
 use crate::RaftTypeConfig;
 use crate::Snapshot;
 use crate::core::raft_msg::ResultSender;
 use crate::core::sm;
 use crate::error::AllowNextRevertError;
+use crate::error::RPCError;
+use crate::error::ResyncError;
 use crate::type_config::alias::OneshotSenderOf;
 
 /// Application-triggered Raft actions for testing and administration.
@@ -47,6 +50,20 @@ pub(crate) enum ExternalCommand<C: RaftTypeConfig> {
         tx: ResultSender<C, (), AllowNextRevertError<C>>,
     },
 
+    /// Resync a follower or learner with a full snapshot.
+    Resync {
+        to: C::NodeId,
+        tx: ResultSender<C, (), ResyncError<C>>,
+    },
+
+    /// Ask a node removed from the cluster to shut down, and send back whether it acknowledged.
+    SendDecommission {
+        target: C::NodeId,
+        node: C::Node,
+        timeout: Duration,
+        tx: ResultSender<C, (), RPCError<C>>,
+    },
+
     /// Send a [`sm::Command`] to [`sm::worker::Worker`].
     /// This command is run in the sm task.
     StateMachineCommand { sm_cmd: sm::Command<C> },
@@ -91,6 +108,12 @@ where C: RaftTypeConfig
                     to
                 )
             }
+            ExternalCommand::Resync { to, .. } => {
+                write!(f, "Resync: to {}", to)
+            }
+            ExternalCommand::SendDecommission { target, timeout, .. } => {
+                write!(f, "SendDecommission: to {}, timeout: {:?}", target, timeout)
+            }
             ExternalCommand::StateMachineCommand { sm_cmd } => {
                 write!(f, "StateMachineCommand: {}", sm_cmd)
             }
@@ -1,10 +1,13 @@
 use std::collections::BTreeMap;
 use std::fmt;
+use std::time::Duration;
 
 use crate::ChangeMembers;
 use crate::RaftState;
 use crate::RaftTypeConfig;
+use crate::StorageError;
 use crate::base::BoxOnce;
+use crate::core::raft_msg::client_write_permit::ClientWritePermit;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::display_ext::DisplayBTreeMapDebugValueExt;
 use crate::error::CheckIsLeaderError;
@@ -14,19 +17,49 @@ use crate::impls::OneshotResponder;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::raft::ClientWriteResult;
+use crate::raft::ClusterHealth;
+use crate::raft::LeadershipInfo;
 use crate::raft::ReadPolicy;
 use crate::raft::SnapshotResponse;
+use crate::raft::StateMachineChecksums;
+use crate::raft::TargetProgress;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft::linearizable_read::Linearizer;
 use crate::raft::responder::core_responder::CoreResponder;
+use crate::storage::LogArchiver;
 use crate::storage::Snapshot;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::type_config::alias::VoteOf;
 
+pub(crate) mod client_write_permit;
 pub(crate) mod external_command;
 
+/// Returns whether a member, with its id and node metadata, supports a version to bump to.
+#[cfg(not(feature = "singlethreaded"))]
+pub(crate) type SupportsVersion<C> =
+    Box<dyn Fn(&<C as RaftTypeConfig>::NodeId, &<C as RaftTypeConfig>::Node) -> bool + Send + 'static>;
+#[cfg(feature = "singlethreaded")]
+pub(crate) type SupportsVersion<C> =
+    Box<dyn Fn(&<C as RaftTypeConfig>::NodeId, &<C as RaftTypeConfig>::Node) -> bool + 'static>;
+
+/// Decides whether this node may start an election for the given reason: `Err(reason)` vetoes it.
+#[cfg(not(feature = "singlethreaded"))]
+pub(crate) type ElectionVeto = Box<dyn Fn(&str) -> Result<(), String> + Send + 'static>;
+#[cfg(feature = "singlethreaded")]
+pub(crate) type ElectionVeto = Box<dyn Fn(&str) -> Result<(), String> + 'static>;
+
+/// Scores the fitness of a node to be the leader, with its id and node metadata: the greater the
+/// fitter.
+#[cfg(not(feature = "singlethreaded"))]
+pub(crate) type LeaderFitness<C> =
+    Box<dyn Fn(&<C as RaftTypeConfig>::NodeId, &<C as RaftTypeConfig>::Node) -> u64 + Send + 'static>;
+#[cfg(feature = "singlethreaded")]
+pub(crate) type LeaderFitness<C> =
+    Box<dyn Fn(&<C as RaftTypeConfig>::NodeId, &<C as RaftTypeConfig>::Node) -> u64 + 'static>;
+
 /// A oneshot TX to send result from `RaftCore` to external caller, e.g. `Raft::append_entries`.
 pub(crate) type ResultSender<C, T, E = Infallible> = OneshotSenderOf<C, Result<T, E>>;
 
@@ -74,11 +107,78 @@ where C: RaftTypeConfig
     ClientWriteRequest {
         app_data: C::D,
         responder: Option<CoreResponder<C>>,
+
+        /// The trace id of the client request, attached to the log entry it is written to.
+        trace_id: Option<String>,
+
+        /// The place this write takes in the queue of client writes, released when `RaftCore`
+        /// receives it.
+        permit: ClientWritePermit,
     },
 
     CheckIsLeaderRequest {
         read_policy: ReadPolicy,
         tx: ClientReadTx<C>,
+
+        /// The trace id of the client read, sent along with the heartbeats that confirm the
+        /// leadership for it.
+        trace_id: Option<String>,
+    },
+
+    /// Probe every member and report the health of the cluster.
+    ClusterHealth {
+        timeout: Duration,
+        tx: ResultSender<C, ClusterHealth<C>, CheckIsLeaderError<C>>,
+    },
+
+    /// Collect the state machine checksums of every member at the last applied log id.
+    StateMachineChecksums {
+        timeout: Duration,
+        tx: ResultSender<C, StateMachineChecksums<C>, CheckIsLeaderError<C>>,
+    },
+
+    /// Get the checksum of the local state machine right after `at` is applied.
+    StateMachineChecksum {
+        at: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Option<u64>>,
+    },
+
+    /// Read the log entries in `[first, last]` for a follower that cannot read them locally.
+    RepairLog {
+        first: LogIdOf<C>,
+        last: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Vec<C::Entry>>,
+    },
+
+    /// Append a node-local entry to the log store.
+    AppendLocalEntry {
+        data: Vec<u8>,
+        tx: ResultSender<C, u64, StorageError<C>>,
+    },
+
+    /// Read at most `max` node-local entries since sequence number `start`.
+    ReadLocalEntries {
+        start: u64,
+        max: u64,
+        tx: ResultSender<C, Vec<(u64, Vec<u8>)>, StorageError<C>>,
+    },
+
+    /// Remove node-local entries up to sequence number `upto`, inclusive.
+    PurgeLocalEntries {
+        upto: u64,
+        tx: ResultSender<C, (), StorageError<C>>,
+    },
+
+    /// Report the replication progress of every target tracked by the leader.
+    ReplicationProgress {
+        tx: ResultSender<C, BTreeMap<C::NodeId, TargetProgress<C>>, CheckIsLeaderError<C>>,
+    },
+
+    /// Explain the leadership this node believes in, see [`Raft::leadership_info()`].
+    ///
+    /// [`Raft::leadership_info()`]: crate::Raft::leadership_info
+    LeadershipInfo {
+        tx: OneshotSenderOf<C, LeadershipInfo<C>>,
     },
 
     Initialize {
@@ -86,6 +186,14 @@ where C: RaftTypeConfig
         tx: ResultSender<C, (), InitializeError<C>>,
     },
 
+    /// Initialize a new cluster from a snapshot, see [`Raft::initialize_from_snapshot()`].
+    ///
+    /// [`Raft::initialize_from_snapshot()`]: crate::Raft::initialize_from_snapshot
+    InitializeFromSnapshot {
+        snapshot: Snapshot<C>,
+        tx: ResultSender<C, (), InitializeError<C>>,
+    },
+
     ChangeMembership {
         changes: ChangeMembers<C>,
 
@@ -96,6 +204,34 @@ where C: RaftTypeConfig
         tx: OneshotResponder<C, ClientWriteResult<C>>,
     },
 
+    /// Propose a version bump if every member supports `version`.
+    BumpVersion {
+        version: u64,
+
+        /// Returns whether a member supports the version, with its id and node metadata.
+        supports: SupportsVersion<C>,
+
+        tx: OneshotResponder<C, ClientWriteResult<C>>,
+    },
+
+    /// Set, or remove with `None`, the hook consulted before this node starts an election.
+    SetElectionVeto {
+        veto: Option<ElectionVeto>,
+        tx: OneshotSenderOf<C, ()>,
+    },
+
+    /// Set, or remove with `None`, the function scoring the fitness of nodes to be the leader.
+    SetLeaderFitness {
+        fitness: Option<LeaderFitness<C>>,
+        tx: OneshotSenderOf<C, ()>,
+    },
+
+    /// Set, or remove with `None`, the hook that archives logs before they are purged.
+    SetLogArchiver {
+        archiver: Option<Box<dyn LogArchiver<C>>>,
+        tx: OneshotSenderOf<C, ()>,
+    },
+
     ExternalCoreRequest {
         req: BoxOnce<'static, RaftState<C>>,
     },
@@ -116,6 +252,26 @@ where C: RaftTypeConfig
     },
 }
 
+impl<C> RaftMsg<C>
+where C: RaftTypeConfig
+{
+    /// Whether this message is sent through the priority lane, which `RaftCore` processes ahead
+    /// of the others.
+    ///
+    /// Vote and transfer-leader messages are small and time-sensitive: waiting behind a backlog of
+    /// client writes or log replication may let an election timeout elapse. They do not depend on
+    /// the order of other messages, while an AppendEntries, even without entries, must not
+    /// overtake the AppendEntries sent before it by the same leader.
+    pub(crate) fn is_priority(&self) -> bool {
+        match self {
+            RaftMsg::RequestVote { .. } => true,
+            RaftMsg::HandleTransferLeader { .. } => true,
+            RaftMsg::ExternalCommand { cmd } => matches!(cmd, ExternalCommand::Elect),
+            _ => false,
+        }
+    }
+}
+
 impl<C> fmt::Display for RaftMsg<C>
 where C: RaftTypeConfig
 {
@@ -137,12 +293,42 @@ where C: RaftTypeConfig
             RaftMsg::CheckIsLeaderRequest { read_policy, .. } => {
                 write!(f, "CheckIsLeaderRequest with read policy: {}", read_policy)
             }
+            RaftMsg::ClusterHealth { timeout, .. } => {
+                write!(f, "ClusterHealth: timeout: {:?}", timeout)
+            }
+            RaftMsg::StateMachineChecksums { timeout, .. } => {
+                write!(f, "StateMachineChecksums: timeout: {:?}", timeout)
+            }
+            RaftMsg::StateMachineChecksum { at, .. } => {
+                write!(f, "StateMachineChecksum: at: {}", at)
+            }
+            RaftMsg::RepairLog { first, last, .. } => {
+                write!(f, "RepairLog: [{}, {}]", first, last)
+            }
+            RaftMsg::AppendLocalEntry { data, .. } => {
+                write!(f, "AppendLocalEntry: {} bytes", data.len())
+            }
+            RaftMsg::ReadLocalEntries { start, max, .. } => {
+                write!(f, "ReadLocalEntries: start: {}, max: {}", start, max)
+            }
+            RaftMsg::PurgeLocalEntries { upto, .. } => {
+                write!(f, "PurgeLocalEntries: upto: {}", upto)
+            }
+            RaftMsg::ReplicationProgress { .. } => write!(f, "ReplicationProgress"),
+            RaftMsg::LeadershipInfo { .. } => write!(f, "LeadershipInfo"),
             RaftMsg::Initialize { members, .. } => {
                 write!(f, "Initialize: {}", members.display())
             }
+            RaftMsg::InitializeFromSnapshot { snapshot, .. } => {
+                write!(f, "InitializeFromSnapshot: {}", snapshot)
+            }
             RaftMsg::ChangeMembership { changes, retain, .. } => {
                 write!(f, "ChangeMembership: {}, retain: {}", changes, retain)
             }
+            RaftMsg::BumpVersion { version, .. } => write!(f, "BumpVersion: {}", version),
+            RaftMsg::SetElectionVeto { veto, .. } => write!(f, "SetElectionVeto: {}", veto.is_some()),
+            RaftMsg::SetLeaderFitness { fitness, .. } => write!(f, "SetLeaderFitness: {}", fitness.is_some()),
+            RaftMsg::SetLogArchiver { archiver, .. } => write!(f, "SetLogArchiver: {}", archiver.is_some()),
             RaftMsg::ExternalCoreRequest { .. } => write!(f, "External Request"),
             RaftMsg::HandleTransferLeader { from, to } => {
                 write!(f, "TransferLeader: from_leader: vote={}, to: {}", from, to)
@@ -153,3 +339,48 @@ where C: RaftTypeConfig
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Vote;
+    use crate::core::raft_msg::RaftMsg;
+    use crate::core::raft_msg::external_command::ExternalCommand;
+    use crate::engine::testing::UTConfig;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::VoteRequest;
+    use crate::testing::blank_ent;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_raft_msg_is_priority() -> anyhow::Result<()> {
+        let append = |n: u64| {
+            let (tx, _rx) = UTConfig::<()>::oneshot();
+            RaftMsg::<UTConfig>::AppendEntries {
+                rpc: AppendEntriesRequest::new(
+                    Vote::new_committed(1, 1),
+                    None,
+                    (0..n).map(|i| blank_ent(1, 1, i)),
+                    None,
+                ),
+                tx,
+            }
+        };
+
+        assert!(!append(0).is_priority(), "heartbeat keeps the order of replication");
+        assert!(!append(1).is_priority(), "replication");
+
+        let (tx, _rx) = UTConfig::<()>::oneshot();
+        let vote = RaftMsg::<UTConfig>::RequestVote {
+            rpc: VoteRequest::new(Vote::new(2, 2), None),
+            tx,
+        };
+        assert!(vote.is_priority());
+
+        let cmd = |cmd| RaftMsg::<UTConfig>::ExternalCommand { cmd };
+        assert!(cmd(ExternalCommand::Elect).is_priority());
+        assert!(!cmd(ExternalCommand::Heartbeat).is_priority());
+        assert!(!cmd(ExternalCommand::Snapshot).is_priority());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! Batch linearizable reads so that one round of heartbeats confirms the leadership for all of
+//! them.
+
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::async_runtime::OneshotSender;
+use crate::core::raft_msg::ClientReadTx;
+use crate::error::CheckIsLeaderError;
+use crate::raft::linearizable_read::Linearizer;
+use crate::type_config::alias::InstantOf;
+
+/// The [`ReadPolicy::ReadIndex`] reads waiting for the leader to confirm its leadership.
+///
+/// A read joins the open batch when it arrives. The batch is closed `delay` after its first read
+/// arrives, and a single round of heartbeats is sent for every read in it. A read only joins a
+/// batch whose round has not started yet, thus the leadership is always confirmed after the read
+/// arrives.
+///
+/// [`ReadPolicy::ReadIndex`]: crate::raft::ReadPolicy::ReadIndex
+pub(crate) struct ReadBatch<C>
+where C: RaftTypeConfig
+{
+    /// How long the batch stays open after its first read arrives, see
+    /// `Config::read_index_batch_delay`.
+    delay: Duration,
+
+    /// The time the first read in the batch arrived, `None` if the batch is empty.
+    since: Option<InstantOf<C>>,
+
+    reads: Vec<(Linearizer<C>, ClientReadTx<C>)>,
+
+    /// The trace ids of the reads in the batch, sent along with the heartbeats.
+    trace_ids: Vec<String>,
+}
+
+impl<C> ReadBatch<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            since: None,
+            reads: Vec::new(),
+            trace_ids: Vec::new(),
+        }
+    }
+
+    pub(crate) fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Add a read received at `now` to the open batch.
+    ///
+    /// It returns `true` if the read opens a new batch.
+    pub(crate) fn push(
+        &mut self,
+        linearizer: Linearizer<C>,
+        tx: ClientReadTx<C>,
+        trace_id: Option<String>,
+        now: InstantOf<C>,
+    ) -> bool {
+        self.reads.push((linearizer, tx));
+        self.trace_ids.extend(trace_id);
+
+        if self.since.is_none() {
+            self.since = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Close the batch and return its reads and their trace ids if the batch has been open for
+    /// `delay` at `now`.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn take_due(
+        &mut self,
+        now: InstantOf<C>,
+    ) -> Option<(Vec<(Linearizer<C>, ClientReadTx<C>)>, Vec<String>)> {
+        let since = self.since?;
+
+        if now < since + self.delay {
+            return None;
+        }
+
+        self.since = None;
+        Some((std::mem::take(&mut self.reads), std::mem::take(&mut self.trace_ids)))
+    }
+
+    /// Send the result of confirming the leadership to every read in a batch.
+    pub(crate) fn respond(reads: Vec<(Linearizer<C>, ClientReadTx<C>)>, res: Result<(), CheckIsLeaderError<C>>) {
+        for (linearizer, tx) in reads {
+            let _ = tx.send(res.clone().map(|_| linearizer));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ReadBatch;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::raft::linearizable_read::Linearizer;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_read_batch() {
+        let mut b = ReadBatch::<UTConfig>::new(Duration::from_millis(10));
+        let now = UTConfig::<()>::now();
+        let ms = |n| now + Duration::from_millis(n);
+
+        let read = || {
+            let (tx, _rx) = UTConfig::<()>::oneshot();
+            (Linearizer::new(1, log_id(1, 1, 2), None), tx)
+        };
+
+        assert!(b.take_due(ms(100)).is_none(), "empty batch is never due");
+
+        let (l, tx) = read();
+        assert!(b.push(l, tx, Some("a".to_string()), now));
+        let (l, tx) = read();
+        assert!(!b.push(l, tx, None, ms(5)), "joins the open batch");
+
+        assert!(b.take_due(ms(9)).is_none());
+        let (reads, trace_ids) = b.take_due(ms(10)).unwrap();
+        assert_eq!(2, reads.len());
+        assert_eq!(vec!["a".to_string()], trace_ids);
+        assert!(b.take_due(ms(20)).is_none());
+
+        let (l, tx) = read();
+        assert!(b.push(l, tx, None, ms(20)), "opens a new batch");
+        let (reads, trace_ids) = b.take_due(ms(30)).unwrap();
+        assert_eq!(1, reads.len());
+        assert!(trace_ids.is_empty());
+    }
+
+    #[test]
+    fn test_read_batch_no_delay() {
+        let mut b = ReadBatch::<UTConfig>::new(Duration::ZERO);
+        let now = UTConfig::<()>::now();
+
+        let (tx, _rx) = UTConfig::<()>::oneshot();
+        assert!(b.push(Linearizer::new(1, log_id(1, 1, 2), None), tx, None, now));
+        assert_eq!(1, b.take_due(now).unwrap().0.len());
+    }
+}
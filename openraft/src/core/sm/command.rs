@@ -13,6 +13,7 @@ use crate::storage::Snapshot;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::alias::SnapshotDataOf;
+use crate::type_config::alias::VoteOf;
 
 /// The payload of a state machine command.
 pub(crate) enum Command<C>
@@ -23,6 +24,11 @@ where C: RaftTypeConfig
 
     /// Get the latest built snapshot.
     GetSnapshot {
+        /// The last log id of the snapshot the caller expects, if any.
+        ///
+        /// If the snapshot kept by the worker is at it, a copy of it is returned without getting
+        /// the current snapshot from the state machine again.
+        last_log_id: Option<LogIdOf<C>>,
         tx: OneshotSenderOf<C, Option<Snapshot<C>>>,
     },
 
@@ -48,6 +54,23 @@ where C: RaftTypeConfig
         last: LogIdOf<C>,
 
         client_resp_channels: BTreeMap<u64, CoreResponder<C>>,
+
+        /// The vote of this node when the command is sent, passed to the state machine as the
+        /// fencing token of the batch.
+        vote: VoteOf<C>,
+
+        /// Whether to wait for the results computed by the leader, i.e., this node is not the
+        /// leader and [`Config::apply_results_wait`](crate::Config::apply_results_wait) is enabled.
+        wait_leader_results: bool,
+    },
+
+    /// Get the checksum of the state machine right after `at` is applied.
+    ///
+    /// If `at` is not applied yet, the worker responds once it is applied. It responds `None` if
+    /// the state machine has already applied a later log entry, or does not support checksums.
+    Checksum {
+        at: LogIdOf<C>,
+        tx: OneshotSenderOf<C, Option<u64>>,
     },
 
     /// Apply a custom function to the state machine.
@@ -71,8 +94,8 @@ where C: RaftTypeConfig
         Command::BuildSnapshot
     }
 
-    pub(crate) fn get_snapshot(tx: OneshotSenderOf<C, Option<Snapshot<C>>>) -> Self {
-        Command::GetSnapshot { tx }
+    pub(crate) fn get_snapshot(last_log_id: Option<LogIdOf<C>>, tx: OneshotSenderOf<C, Option<Snapshot<C>>>) -> Self {
+        Command::GetSnapshot { last_log_id, tx }
     }
 
     pub(crate) fn begin_receiving_snapshot(tx: OneshotSenderOf<C, SnapshotDataOf<C>>) -> Self {
@@ -83,16 +106,24 @@ where C: RaftTypeConfig
         Command::InstallFullSnapshot { log_io_id, snapshot }
     }
 
+    pub(crate) fn checksum(at: LogIdOf<C>, tx: OneshotSenderOf<C, Option<u64>>) -> Self {
+        Command::Checksum { at, tx }
+    }
+
     /// Applies log ids within the inclusive range `[first, last]`.
     pub(crate) fn apply(
         first: LogIdOf<C>,
         last: LogIdOf<C>,
         client_resp_channels: BTreeMap<u64, CoreResponder<C>>,
+        vote: VoteOf<C>,
+        wait_leader_results: bool,
     ) -> Self {
         Command::Apply {
             first,
             last,
             client_resp_channels,
+            vote,
+            wait_leader_results,
         }
     }
 
@@ -107,6 +138,7 @@ where C: RaftTypeConfig
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { log_io_id, .. } => Some(IOId::Log(log_io_id.clone())),
             Command::Apply { .. } => None,
+            Command::Checksum { .. } => None,
             Command::Func { .. } => None,
         }
     }
@@ -123,6 +155,7 @@ where C: RaftTypeConfig
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { log_io_id, .. } => log_io_id.last_log_id().cloned(),
             Command::Apply { last, .. } => Some(last.clone()),
+            Command::Checksum { .. } => None,
             Command::Func { .. } => None,
         }
     }
@@ -141,6 +174,7 @@ where C: RaftTypeConfig
             Command::BeginReceivingSnapshot { .. } => None,
             Command::InstallFullSnapshot { snapshot, .. } => snapshot.meta.last_log_id.clone(),
             Command::Apply { .. } => None,
+            Command::Checksum { .. } => None,
             Command::Func { .. } => None,
         }
     }
@@ -163,6 +197,7 @@ where C: RaftTypeConfig
                 write!(f, "BeginReceivingSnapshot")
             }
             Command::Apply { first, last, .. } => write!(f, "Apply: [{},{}]", first, last),
+            Command::Checksum { at, .. } => write!(f, "Checksum: at: {}", at),
             Command::Func { .. } => write!(f, "Func"),
         }
     }
@@ -185,6 +220,7 @@ where C: RaftTypeConfig
                 write!(f, "BeginReceivingSnapshot")
             }
             Command::Apply { first, last, .. } => write!(f, "Apply: [{},{}]", first, last),
+            Command::Checksum { at, .. } => write!(f, "Checksum: at: {}", at),
             Command::Func { .. } => write!(f, "Func"),
         }
     }
@@ -217,6 +253,7 @@ where C: RaftTypeConfig
                     ..
                 },
             ) => first == first2 && last == last2,
+            (Command::Checksum { at, .. }, Command::Checksum { at: at2, .. }) => at == at2,
             (Command::Func { .. }, Command::Func { .. }) => false,
             _ => false,
         }
@@ -0,0 +1,284 @@
+//! The apply results computed by the leader, received by a follower to apply.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
+use crate::async_runtime::watch::WatchSender;
+use crate::raft::ComputedResult;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::WatchReceiverOf;
+use crate::type_config::alias::WatchSenderOf;
+
+/// The maximum number of results kept, received from the leader or computed locally, so that a
+/// follower that does not apply, or a leader that does not stream results, does not exhaust the
+/// memory.
+pub(crate) const MAX_KEPT: usize = 4096;
+
+/// Results received from the leader, shared by `Raft`, which receives them, the state machine
+/// worker, which applies them, and `RaftCore`, which reports the statistics in metrics.
+///
+/// A result computed locally is compared with the one from the leader, whichever arrives
+/// later, to detect a state machine that is not deterministic.
+#[derive(Clone)]
+pub(crate) struct ComputedResults<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Inner<C>>>,
+
+    /// Bumped when results are received, to wake up the worker waiting for them.
+    tx: Arc<Mutex<WatchSenderOf<C, u64>>>,
+    rx: WatchReceiverOf<C, u64>,
+}
+
+struct Inner<C>
+where C: RaftTypeConfig
+{
+    /// The index of the last applied entry, results of entries up to it are not kept.
+    applied: Option<u64>,
+
+    /// Results received from the leader for entries that are not yet applied.
+    received: BTreeMap<u64, ComputedResult<C>>,
+
+    /// Checksums of the results computed locally, waiting for the results of the leader.
+    computed: BTreeMap<u64, (LogIdOf<C>, u64)>,
+
+    /// Number of entries applied with results received from the leader.
+    applied_from_leader: u64,
+
+    /// Number of results computed locally that differ from the ones of the leader.
+    mismatches: u64,
+}
+
+impl<C> ComputedResults<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        let (tx, rx) = C::watch_channel(0);
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                applied: None,
+                received: BTreeMap::new(),
+                computed: BTreeMap::new(),
+                applied_from_leader: 0,
+                mismatches: 0,
+            })),
+            tx: Arc::new(Mutex::new(tx)),
+            rx,
+        }
+    }
+
+    /// Keep results received from the leader, or compare them with the ones computed locally if
+    /// the entries are already applied.
+    pub(crate) fn insert(&self, results: Vec<ComputedResult<C>>) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+
+            let mut upto = None;
+            for result in results {
+                let index = result.log_id.index();
+                upto = Some(index);
+
+                if Some(index) <= inner.applied {
+                    if let Some((log_id, checksum)) = inner.computed.remove(&index)
+                        && log_id == result.log_id
+                        && result.is_intact()
+                    {
+                        inner.compare(&log_id, checksum, result.checksum);
+                    }
+                } else if inner.received.len() < MAX_KEPT {
+                    inner.received.insert(index, result);
+                }
+            }
+
+            // The leader sends results in log order: a local result before them will not be
+            // compared.
+            if let Some(upto) = upto {
+                inner.computed = inner.computed.split_off(&(upto + 1));
+            }
+        }
+
+        self.tx.lock().unwrap().send_if_modified(|v| {
+            *v += 1;
+            true
+        });
+    }
+
+    /// Wait at most `timeout` for the results of the entries with the consecutive `log_ids` and
+    /// take them.
+    ///
+    /// It returns `None` if any of them is missing, corrupted, or is the result of another entry
+    /// at the same index, when timeout.
+    pub(crate) async fn wait(&mut self, log_ids: &[LogIdOf<C>], timeout: Duration) -> Option<Vec<Vec<u8>>> {
+        let deadline = C::now() + timeout;
+
+        loop {
+            if let Some(results) = self.take(log_ids) {
+                return Some(results);
+            }
+
+            let res = C::timeout_at(deadline, self.rx.changed()).await;
+            if !matches!(res, Ok(Ok(_))) {
+                return None;
+            }
+        }
+    }
+
+    fn take(&self, log_ids: &[LogIdOf<C>]) -> Option<Vec<Vec<u8>>> {
+        let (first, last) = (log_ids.first()?, log_ids.last()?);
+
+        let mut inner = self.inner.lock().unwrap();
+
+        let range = first.index()..=last.index();
+        let n = inner.received.range(range.clone()).count() as u64;
+        if n != last.index() + 1 - first.index() || n != log_ids.len() as u64 {
+            return None;
+        }
+
+        let matched = inner.received.range(range.clone()).zip(log_ids).all(|((_, r), log_id)| &r.log_id == log_id);
+        if !matched {
+            return None;
+        }
+
+        if let Some((_, corrupted)) = inner.received.range(range.clone()).find(|(_, r)| !r.is_intact()) {
+            tracing::warn!("received a corrupted apply result: {}", corrupted);
+            let index = corrupted.log_id.index();
+            inner.received.remove(&index);
+            return None;
+        }
+
+        let rest = inner.received.split_off(&(last.index() + 1));
+        let taken = std::mem::replace(&mut inner.received, rest);
+
+        inner.applied = Some(last.index());
+        inner.applied_from_leader += n;
+
+        Some(taken.into_values().map(|r| r.data).collect())
+    }
+
+    /// Compare the results computed locally for the entries up to `last` with the ones of the
+    /// leader, or keep their checksums until the results of the leader arrive.
+    pub(crate) fn on_computed(&self, last: &LogIdOf<C>, computed: Vec<ComputedResult<C>>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        for result in computed {
+            let index = result.log_id.index();
+            match inner.received.remove(&index) {
+                Some(received) if received.log_id == result.log_id && received.is_intact() => {
+                    inner.compare(&result.log_id, result.checksum, received.checksum);
+                }
+                _ => {
+                    if inner.computed.len() < MAX_KEPT {
+                        inner.computed.insert(index, (result.log_id, result.checksum));
+                    }
+                }
+            }
+        }
+
+        inner.skip_upto(last.index());
+    }
+
+    /// Discard the results of entries up to `last`, e.g., that are installed with a snapshot.
+    pub(crate) fn skip_upto(&self, last: &LogIdOf<C>) {
+        self.inner.lock().unwrap().skip_upto(last.index());
+    }
+
+    /// Returns the number of entries applied with the results of the leader, and the number of
+    /// results computed locally that differ from the ones of the leader.
+    pub(crate) fn stats(&self) -> (u64, u64) {
+        let inner = self.inner.lock().unwrap();
+        (inner.applied_from_leader, inner.mismatches)
+    }
+}
+
+impl<C> Inner<C>
+where C: RaftTypeConfig
+{
+    fn compare(&mut self, log_id: &LogIdOf<C>, local: u64, leader: u64) {
+        if local != leader {
+            self.mismatches += 1;
+            tracing::error!(
+                "apply result of {} differs from the leader: local: {:016x}, leader: {:016x}; \
+                 the state machine is not deterministic",
+                log_id,
+                local,
+                leader
+            );
+        }
+    }
+
+    fn skip_upto(&mut self, index: u64) {
+        if Some(index) > self.applied {
+            self.applied = Some(index);
+        }
+        self.received = self.received.split_off(&(index + 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ComputedResults;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::raft::ComputedResult;
+    use crate::type_config::alias::LogIdOf;
+
+    fn result(index: u64, data: &str) -> ComputedResult<UTConfig> {
+        ComputedResult::new(log_id(1, 1, index), data.as_bytes().to_vec())
+    }
+
+    fn log_ids(first: u64, last: u64) -> Vec<LogIdOf<UTConfig>> {
+        (first..=last).map(|i| log_id(1, 1, i)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_computed_results_wait() {
+        let mut r = ComputedResults::<UTConfig>::new();
+
+        r.insert(vec![result(3, "a"), result(4, "b")]);
+
+        let got = r.wait(&log_ids(3, 5), Duration::from_millis(10)).await;
+        assert_eq!(None, got, "result of 5 is missing");
+
+        let got = r.wait(&[log_id(2, 1, 3), log_id(1, 1, 4)], Duration::from_millis(10)).await;
+        assert_eq!(None, got, "log id mismatch");
+
+        let got = r.wait(&[log_id(1, 1, 3), log_id(2, 1, 4)], Duration::from_millis(10)).await;
+        assert_eq!(None, got, "log id mismatch in the middle of the batch");
+
+        let got = r.wait(&log_ids(3, 4), Duration::from_millis(10)).await;
+        assert_eq!(Some(vec![b"a".to_vec(), b"b".to_vec()]), got);
+        assert_eq!((2, 0), r.stats());
+
+        r.insert(vec![result(4, "b")]);
+        let got = r.wait(&log_ids(4, 4), Duration::from_millis(10)).await;
+        assert_eq!(None, got, "applied entries are not kept");
+
+        let mut corrupted = result(5, "c");
+        corrupted.data = b"x".to_vec();
+        r.insert(vec![corrupted]);
+        let got = r.wait(&log_ids(5, 5), Duration::from_millis(10)).await;
+        assert_eq!(None, got, "corrupted");
+    }
+
+    #[test]
+    fn test_computed_results_compare() {
+        let r = ComputedResults::<UTConfig>::new();
+
+        // The leader's result arrives before computed locally
+        r.insert(vec![result(3, "a")]);
+        r.on_computed(&log_id(1, 1, 3), vec![result(3, "x")]);
+        assert_eq!((0, 1), r.stats());
+
+        // The leader's result arrives after computed locally
+        r.on_computed(&log_id(1, 1, 5), vec![result(4, "b"), result(5, "c")]);
+        r.insert(vec![result(4, "b"), result(5, "y")]);
+        assert_eq!((0, 2), r.stats());
+    }
+}
@@ -8,6 +8,7 @@ use crate::core::sm;
 use crate::storage::Snapshot;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::JoinHandleOf;
+use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::MpscUnboundedSenderOf;
 use crate::type_config::alias::MpscUnboundedWeakSenderOf;
 
@@ -54,12 +55,18 @@ where C: RaftTypeConfig
 {
     /// Get a snapshot from the state machine.
     ///
+    /// `last_log_id` is the last log id of the snapshot expected. If the state machine worker
+    /// already got the snapshot at it, e.g., for another follower, a copy of it is returned.
+    ///
     /// If the state machine worker has shutdown, it will return an error.
     /// If there is no snapshot available, it will return `Ok(None)`.
-    pub(crate) async fn get_snapshot(&self) -> Result<Option<Snapshot<C>>, &'static str> {
+    pub(crate) async fn get_snapshot(
+        &self,
+        last_log_id: Option<LogIdOf<C>>,
+    ) -> Result<Option<Snapshot<C>>, &'static str> {
         let (tx, rx) = C::oneshot();
 
-        let cmd = sm::Command::get_snapshot(tx);
+        let cmd = sm::Command::get_snapshot(last_log_id, tx);
         tracing::debug!("SnapshotReader sending command to sm::Worker: {:?}", cmd);
 
         let Some(cmd_tx) = self.cmd_tx.upgrade() else {
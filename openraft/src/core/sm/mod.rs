@@ -5,6 +5,7 @@
 //! to the RaftCore.
 
 pub(crate) mod command;
+pub(crate) mod computed_results;
 pub(crate) mod handle;
 pub(crate) mod response;
 pub(crate) mod worker;
@@ -1,4 +1,6 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyerror::AnyError;
 use tracing_futures::Instrument;
@@ -9,17 +11,21 @@ use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::async_runtime::MpscUnboundedReceiver;
 use crate::async_runtime::OneshotSender;
+use crate::base::catch_panic::catch_panic;
+use crate::config::RuntimeConfig;
 use crate::core::ApplyResult;
 use crate::core::notification::Notification;
 use crate::core::sm::Command;
 use crate::core::sm::CommandResult;
 use crate::core::sm::Response;
+use crate::core::sm::computed_results::ComputedResults;
 use crate::core::sm::handle::Handle;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySliceExt;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
 use crate::raft::ClientWriteResponse;
+use crate::raft::ComputedResult;
 use crate::raft::responder::Responder;
 use crate::raft::responder::core_responder::CoreResponder;
 #[cfg(doc)]
@@ -34,12 +40,22 @@ use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::OneshotSenderOf;
 use crate::type_config::async_runtime::mpsc::MpscSender;
 
+/// How many times the worker asks RaftCore for a copy of the logs it cannot read, before it stops
+/// with the read error.
+///
+/// RaftCore answers an attempt that fetches no copy after a heartbeat interval, thus the worker
+/// waits for about this many heartbeat intervals, long enough for a new leader to be elected.
+pub(crate) const MAX_LOG_REPAIR_ATTEMPTS: u64 = 100;
+
 pub(crate) struct Worker<C, SM, LR>
 where
     C: RaftTypeConfig,
     SM: RaftStateMachine<C>,
     LR: RaftLogReader<C>,
 {
+    /// The id of this node, to name the spawned tasks.
+    id: C::NodeId,
+
     /// The application state machine implementation.
     state_machine: SM,
 
@@ -51,6 +67,28 @@ where
 
     /// Send back the result of the command to RaftCore.
     resp_tx: MpscSenderOf<C, Notification<C>>,
+
+    /// The config that can be changed at runtime, e.g., log level overrides.
+    runtime_config: Arc<RuntimeConfig>,
+
+    /// The snapshot last got from the state machine for replication, if it can be copied.
+    ///
+    /// Followers that need the same snapshot are sent copies of it, see
+    /// [`RaftStateMachine::clone_snapshot`].
+    shared_snapshot: Option<Snapshot<C>>,
+
+    /// The last log id applied to the state machine, including by installing a snapshot.
+    last_applied: Option<LogIdOf<C>>,
+
+    /// Checksum requests waiting for a log id to be applied, keyed by the log index.
+    pending_checksums: BTreeMap<u64, Vec<(LogIdOf<C>, OneshotSenderOf<C, Option<u64>>)>>,
+
+    /// The apply results computed by the leader, received by this node.
+    computed_results: ComputedResults<C>,
+
+    /// How long to wait for the apply results computed by the leader, `0` if they are not
+    /// streamed, see [`Config::apply_results_wait`](crate::Config::apply_results_wait).
+    apply_results_wait: Duration,
 }
 
 impl<C, SM, LR> Worker<C, SM, LR>
@@ -61,18 +99,30 @@ where
 {
     /// Spawn a new state machine worker, return a controlling handle.
     pub(crate) fn spawn(
+        id: C::NodeId,
         state_machine: SM,
         log_reader: LR,
+        last_applied: Option<LogIdOf<C>>,
         resp_tx: MpscSenderOf<C, Notification<C>>,
+        runtime_config: Arc<RuntimeConfig>,
+        computed_results: ComputedResults<C>,
+        apply_results_wait: Duration,
         span: tracing::Span,
     ) -> Handle<C> {
         let (cmd_tx, cmd_rx) = C::mpsc_unbounded();
 
         let worker = Worker {
+            id,
             state_machine,
             log_reader,
             cmd_rx,
             resp_tx,
+            runtime_config,
+            shared_snapshot: None,
+            last_applied,
+            pending_checksums: BTreeMap::new(),
+            computed_results,
+            apply_results_wait,
         };
 
         let join_handle = worker.do_spawn(span);
@@ -81,8 +131,16 @@ where
     }
 
     fn do_spawn(mut self, span: tracing::Span) -> JoinHandleOf<C, ()> {
+        let name = format!("openraft-sm-worker-{}", self.id);
         let fu = async move {
-            let res = self.worker_loop().await;
+            let res = match catch_panic("state-machine", self.worker_loop()).await {
+                Ok(res) => res,
+                Err(error) => {
+                    tracing::error!("state machine worker panicked: {}", error);
+                    let _ = self.resp_tx.send(Notification::TaskPanicked { error }).await;
+                    return;
+                }
+            };
 
             if let Err(err) = res {
                 tracing::error!("{} while execute state machine command", err,);
@@ -95,7 +153,7 @@ where
                     .await;
             }
         };
-        C::spawn(fu.instrument(span))
+        C::spawn_named(&name, fu.instrument(span))
     }
 
     #[tracing::instrument(level = "debug", skip_all)]
@@ -119,10 +177,10 @@ where
                     // It is a read operation and is spawned, and it responds in another task
                     self.build_snapshot(self.resp_tx.clone()).await;
                 }
-                Command::GetSnapshot { tx } => {
+                Command::GetSnapshot { last_log_id, tx } => {
                     tracing::info!("{}: get snapshot", func_name!());
 
-                    self.get_snapshot(tx).await?;
+                    self.get_snapshot(last_log_id, tx).await?;
                     // GetSnapshot does not respond to RaftCore
                 }
                 Command::InstallFullSnapshot {
@@ -132,7 +190,13 @@ where
                     tracing::info!("{}: install complete snapshot", func_name!());
 
                     let meta = snapshot.meta.clone();
+                    self.shared_snapshot = None;
                     self.state_machine.install_snapshot(&meta, snapshot.snapshot).await?;
+                    self.last_applied = meta.last_log_id.clone();
+                    if let Some(last) = &meta.last_log_id {
+                        self.computed_results.skip_upto(last);
+                    }
+                    self.respond_checksums().await?;
 
                     tracing::info!("Done install complete snapshot, meta: {}", meta);
 
@@ -151,11 +215,21 @@ where
                     first,
                     last,
                     mut client_resp_channels,
+                    vote,
+                    wait_leader_results,
                 } => {
-                    let resp = self.apply(first, last, &mut client_resp_channels).await?;
+                    self.state_machine.set_fencing_token(vote);
+                    let resp = self.apply(first, last, &mut client_resp_channels, wait_leader_results).await?;
                     let res = CommandResult::new(Ok(Response::Apply(resp)));
                     self.resp_tx.send(Notification::sm(res)).await.ok();
                 }
+                Command::Checksum { at, tx } => {
+                    tracing::debug!("{}: checksum at {}", func_name!(), at);
+
+                    self.pending_checksums.entry(at.index()).or_default().push((at, tx));
+                    self.respond_checksums().await?;
+                    // Checksum does not respond to RaftCore
+                }
                 Command::Func { func, input_sm_type } => {
                     tracing::debug!("{}: run user defined Func", func_name!());
 
@@ -180,6 +254,7 @@ where
         first: LogIdOf<C>,
         last: LogIdOf<C>,
         client_resp_channels: &mut BTreeMap<u64, CoreResponder<C>>,
+        wait_leader_results: bool,
     ) -> Result<ApplyResult<C>, StorageError<C>> {
         // TODO: prepare response before apply,
         //       so that an Entry does not need to be Clone,
@@ -188,7 +263,7 @@ where
         let since = first.index();
         let end = last.index() + 1;
 
-        let entries = self.log_reader.try_get_log_entries(since..end).await?;
+        let entries = self.read_logs_to_apply(&first, &last).await?;
         if entries.len() != (end - since) as usize {
             return Err(StorageError::read_logs(AnyError::error(format!(
                 "returned log entries count({}) does not match the input([{}, {}]))",
@@ -197,7 +272,13 @@ where
                 end
             ))));
         }
-        tracing::debug!(entries = display(entries.display()), "about to apply");
+        subsystem_log!(
+            self.runtime_config,
+            StorageIo,
+            DEBUG,
+            entries = display(entries.display()),
+            "about to apply"
+        );
 
         let last_applied = last;
 
@@ -207,7 +288,20 @@ where
 
         let n_entries = end - since;
 
-        let apply_results = self.state_machine.apply(entries).await?;
+        let leader_results = if wait_leader_results {
+            let log_ids = applying_entries.iter().map(|(log_id, _)| log_id.clone()).collect::<Vec<_>>();
+            self.computed_results.wait(&log_ids, self.apply_results_wait).await
+        } else {
+            None
+        };
+
+        let (apply_results, app_keys, mut computed) = self.apply_entries(entries, leader_results).await?;
+
+        // A follower compares the results it computed with the ones of the leader; the leader
+        // streams them to followers.
+        if wait_leader_results {
+            self.computed_results.on_computed(&last_applied, std::mem::take(&mut computed));
+        }
 
         let n_replies = apply_results.len() as u64;
 
@@ -223,7 +317,10 @@ where
             let (log_id, membership) = applying_entries.next().unwrap();
             let resp = results.next().unwrap();
             let tx = client_resp_channels.remove(&log_index);
-            tracing::debug!(
+            subsystem_log!(
+                self.runtime_config,
+                StorageIo,
+                DEBUG,
                 log_id = debug(&log_id),
                 membership = debug(&membership),
                 "send_response"
@@ -246,11 +343,160 @@ where
             since,
             end,
             last_applied,
+            app_keys,
+            computed,
         };
 
         Ok(resp)
     }
 
+    /// Read the committed logs in `[first, last]` to apply.
+    ///
+    /// If they cannot be read, e.g., an entry fails to deserialize, the range is quarantined: no
+    /// log is applied until RaftCore fetches a copy of it from the leader, see
+    /// [`Config::repair_corrupted_log`](crate::Config::repair_corrupted_log). The read error is
+    /// returned if the logs are not to be repaired, or if no copy is fetched after
+    /// [`MAX_LOG_REPAIR_ATTEMPTS`] attempts, e.g., the leader does not have them either.
+    async fn read_logs_to_apply(
+        &mut self,
+        first: &LogIdOf<C>,
+        last: &LogIdOf<C>,
+    ) -> Result<Vec<C::Entry>, StorageError<C>> {
+        let error = match self.log_reader.try_get_log_entries(first.index()..last.index() + 1).await {
+            Ok(entries) => return Ok(entries),
+            Err(e) => e,
+        };
+
+        tracing::error!(
+            error = display(&error),
+            "cannot read logs to apply, quarantine [{}, {}] until they are repaired",
+            first,
+            last
+        );
+
+        for attempt in 1..=MAX_LOG_REPAIR_ATTEMPTS {
+            let (tx, rx) = C::oneshot();
+            let notification = Notification::RepairLog {
+                first: first.clone(),
+                last: last.clone(),
+                tx,
+            };
+
+            if self.resp_tx.send(notification).await.is_err() {
+                return Err(error);
+            }
+
+            match rx.await {
+                Ok(Some(entries)) => {
+                    tracing::info!("repaired logs [{}, {}] with a copy from the leader", first, last);
+                    return Ok(entries);
+                }
+                Ok(None) => {
+                    tracing::warn!(
+                        "no copy of logs [{}, {}] fetched, attempt {}/{}",
+                        first,
+                        last,
+                        attempt,
+                        MAX_LOG_REPAIR_ATTEMPTS
+                    );
+                }
+                Err(_) => return Err(error),
+            }
+        }
+
+        tracing::error!(
+            "give up repairing logs [{}, {}] after {} attempts",
+            first,
+            last,
+            MAX_LOG_REPAIR_ATTEMPTS
+        );
+        Err(error)
+    }
+
+    /// Apply `entries` to the state machine, return the apply results, the published application
+    /// keys and the results computed by the state machine.
+    ///
+    /// The entries are applied with `leader_results` if they are given, i.e., the results
+    /// computed by the leader for every entry, instead of computing them.
+    ///
+    /// The entries are applied in several batches if a checksum is requested at any of them, so
+    /// that the checksum is computed right after that entry is applied.
+    #[allow(clippy::type_complexity)]
+    async fn apply_entries(
+        &mut self,
+        mut entries: Vec<C::Entry>,
+        mut leader_results: Option<Vec<Vec<u8>>>,
+    ) -> Result<(Vec<C::R>, Vec<(String, LogIdOf<C>)>, Vec<ComputedResult<C>>), StorageError<C>> {
+        let mut apply_results = Vec::with_capacity(entries.len());
+        let mut app_keys = Vec::new();
+        let mut computed = Vec::new();
+
+        while let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+            let (first, last) = (first.index(), last.index());
+
+            let n = match self.pending_checksums.range(first..=last).next() {
+                Some((index, _)) => (index - first + 1) as usize,
+                None => entries.len(),
+            };
+
+            let rest = entries.split_off(n);
+            let last_log_id = entries[n - 1].log_id();
+
+            let batch_results = leader_results.as_mut().map(|results| {
+                let rest = results.split_off(n);
+                std::mem::replace(results, rest)
+            });
+
+            match batch_results {
+                Some(results) => {
+                    apply_results.extend(self.state_machine.apply_computed(entries, results).await?);
+                }
+                None => {
+                    apply_results.extend(self.state_machine.apply(entries).await?);
+
+                    if !self.apply_results_wait.is_zero() {
+                        let results = self.state_machine.take_computed_results();
+                        computed.extend(results.into_iter().map(|(log_id, data)| ComputedResult::new(log_id, data)));
+                    }
+                }
+            }
+            app_keys.extend(self.state_machine.take_app_index_keys());
+
+            self.last_applied = Some(last_log_id);
+            self.respond_checksums().await?;
+
+            entries = rest;
+        }
+
+        Ok((apply_results, app_keys, computed))
+    }
+
+    /// Respond to the checksum requests at log ids that are not after the last applied log id.
+    ///
+    /// The checksum is computed if the request is at the last applied log id, otherwise the state
+    /// it is requested at is gone and `None` is sent back.
+    async fn respond_checksums(&mut self) -> Result<(), StorageError<C>> {
+        let Some(last_applied) = self.last_applied.clone() else {
+            return Ok(());
+        };
+
+        let waiting = self.pending_checksums.split_off(&(last_applied.index() + 1));
+        let ready = std::mem::replace(&mut self.pending_checksums, waiting);
+
+        for (at, tx) in ready.into_values().flatten() {
+            let checksum = if at == last_applied {
+                self.state_machine.checksum(&at).await?
+            } else {
+                None
+            };
+
+            tracing::debug!(at = display(&at), checksum = debug(checksum), "respond checksum");
+            let _ = tx.send(checksum);
+        }
+
+        Ok(())
+    }
+
     /// Build a snapshot by requesting a builder from the state machine.
     ///
     /// This method calls
@@ -278,21 +524,56 @@ where
             return;
         };
 
-        let _handle = C::spawn(async move {
-            let res = builder.build_snapshot().await;
+        let name = format!("openraft-snapshot-builder-{}", self.id);
+        let fu = async move {
+            let res = match catch_panic("build-snapshot", builder.build_snapshot()).await {
+                Ok(res) => res,
+                Err(error) => {
+                    tracing::error!("building snapshot panicked: {}", error);
+                    resp_tx.send(Notification::TaskPanicked { error }).await.ok();
+                    return;
+                }
+            };
             let res = res.map(|snap| Response::BuildSnapshotDone(Some(snap.meta)));
             let cmd_res = CommandResult::new(res);
             resp_tx.send(Notification::sm(cmd_res)).await.ok();
-        });
+        };
+        let _handle = C::spawn_named(&name, fu.instrument(tracing::debug_span!("build_snapshot")));
         tracing::info!("{} returning; spawned building snapshot task", func_name!());
     }
 
+    /// Get the current snapshot, or a copy of the shared one if it is at `last_log_id`.
+    ///
+    /// A snapshot got for an expected `last_log_id` is kept to be shared, if it can be copied.
     #[tracing::instrument(level = "info", skip_all)]
-    async fn get_snapshot(&mut self, tx: OneshotSenderOf<C, Option<Snapshot<C>>>) -> Result<(), StorageError<C>> {
-        tracing::info!("{}", func_name!());
+    async fn get_snapshot(
+        &mut self,
+        last_log_id: Option<LogIdOf<C>>,
+        tx: OneshotSenderOf<C, Option<Snapshot<C>>>,
+    ) -> Result<(), StorageError<C>> {
+        tracing::info!(last_log_id = display(last_log_id.display()), "{}", func_name!());
+
+        let shared =
+            self.shared_snapshot.as_ref().filter(|s| last_log_id.is_some() && s.meta.last_log_id == last_log_id);
+        if let Some(copy) = shared.and_then(|s| self.state_machine.clone_snapshot(s)) {
+            tracing::info!("sending back a copy of shared snapshot: meta: {}", copy.meta);
+            let _ = tx.send(Some(copy));
+            return Ok(());
+        }
 
         let snapshot = self.state_machine.get_current_snapshot().await?;
 
+        let snapshot = match snapshot {
+            Some(snapshot) if last_log_id.is_some() => match self.state_machine.clone_snapshot(&snapshot) {
+                Some(copy) => {
+                    self.shared_snapshot = Some(snapshot);
+                    Some(copy)
+                }
+                None => Some(snapshot),
+            },
+            _ => snapshot,
+        };
+
         tracing::info!(
             "sending back snapshot: meta: {}",
             snapshot.as_ref().map(|s| &s.meta).display()
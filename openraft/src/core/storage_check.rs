@@ -0,0 +1,83 @@
+//! Check that the local storage can be read back before starting an election, in a task outside
+//! `RaftCore`, so that a slow or hung disk does not block it.
+//!
+//! See [`Config::check_storage_before_election`](crate::Config::check_storage_before_election).
+
+use std::fmt;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// What starts an election once the storage check before it passes.
+pub(crate) enum ElectionTrigger<C>
+where C: RaftTypeConfig
+{
+    /// The election timeout passed.
+    ElectionTimeout,
+
+    /// The application asked this node to elect.
+    External,
+
+    /// The leader with `leader_vote` transfers its leadership to this node.
+    TransferLeader { leader_vote: VoteOf<C> },
+}
+
+impl<C> ElectionTrigger<C>
+where C: RaftTypeConfig
+{
+    /// The reason of the election, as recorded in the election metrics.
+    pub(crate) fn reason(&self) -> &'static str {
+        match self {
+            Self::ElectionTimeout => "election timeout",
+            Self::External => "triggered by application",
+            Self::TransferLeader { .. } => "leadership is transferred to this node",
+        }
+    }
+}
+
+impl<C> fmt::Display for ElectionTrigger<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransferLeader { leader_vote } => write!(f, "{}, from: {}", self.reason(), leader_vote),
+            _ => write!(f, "{}", self.reason()),
+        }
+    }
+}
+
+/// Check that the vote and the last flushed log entry `last` can be read and decoded.
+///
+/// `last` is `None` if there is no log entry to read back, e.g., it is purged and only the
+/// snapshot is left.
+pub(crate) async fn check_storage<C, LR>(mut log_reader: LR, last: Option<LogIdOf<C>>) -> Result<(), String>
+where
+    C: RaftTypeConfig,
+    LR: RaftLogReader<C>,
+{
+    log_reader.read_vote().await.map_err(|e| format!("failed to read vote: {}", e))?;
+
+    let Some(last) = last else {
+        return Ok(());
+    };
+
+    let index = last.index();
+    let entries = log_reader
+        .try_get_log_entries(index..index + 1)
+        .await
+        .map_err(|e| format!("failed to read last log {}: {}", last, e))?;
+
+    let Some(entry) = entries.first() else {
+        return Err(format!("last log {} is missing", last));
+    };
+
+    let got = entry.log_id();
+    if got != last {
+        return Err(format!("last log is {}, expect {}", got, last));
+    }
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
-//! tick emitter emits a `RaftMsg::Tick` event at a certain interval.
+//! tick emitter emits a `RaftMsg::Tick` event at a certain interval, or when the application calls
+//! `Raft::tick()` in manual tick mode.
 
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
@@ -31,12 +33,23 @@ where C: RaftTypeConfig
 
     /// Emit event or not
     enabled: Arc<AtomicBool>,
+
+    /// The number of ticks emitted, shared with [`TickHandle`] emitting manual ticks.
+    ticks: Arc<AtomicU64>,
 }
 
 pub(crate) struct TickHandle<C>
 where C: RaftTypeConfig
 {
     enabled: Arc<AtomicBool>,
+    ticks: Arc<AtomicU64>,
+
+    /// For emitting a tick with [`Self::tick`].
+    tx: MpscSenderOf<C, Notification<C>>,
+
+    /// Whether a tick loop is spawned, `false` in manual tick mode.
+    spawned: bool,
+
     shutdown: Mutex<Option<OneshotSenderOf<C, ()>>>,
     join_handle: Mutex<Option<JoinHandleOf<C, ()>>>,
 }
@@ -46,7 +59,7 @@ where C: RaftTypeConfig
 {
     /// Signal the tick loop to stop, without waiting for it to stop.
     fn drop(&mut self) {
-        if self.shutdown.lock().unwrap().is_none() {
+        if !self.spawned || self.shutdown.lock().unwrap().is_none() {
             return;
         }
         let _ = self.shutdown();
@@ -56,34 +69,55 @@ where C: RaftTypeConfig
 impl<C> Tick<C>
 where C: RaftTypeConfig
 {
-    pub(crate) fn spawn(interval: Duration, tx: MpscSenderOf<C, Notification<C>>, enabled: bool) -> TickHandle<C> {
+    pub(crate) fn spawn(
+        id: &C::NodeId,
+        interval: Duration,
+        tx: MpscSenderOf<C, Notification<C>>,
+        enabled: bool,
+    ) -> TickHandle<C> {
         let enabled = Arc::new(AtomicBool::from(enabled));
+        let ticks = Arc::new(AtomicU64::new(0));
         let this = Self {
             interval,
             enabled: enabled.clone(),
-            tx,
+            tx: tx.clone(),
+            ticks: ticks.clone(),
         };
 
         let (shutdown, shutdown_rx) = C::oneshot();
 
         let shutdown = Mutex::new(Some(shutdown));
 
-        let join_handle = C::spawn(this.tick_loop(shutdown_rx).instrument(tracing::span!(
-            parent: &Span::current(),
-            Level::DEBUG,
-            "tick"
-        )));
+        let join_handle = C::spawn_named(
+            &format!("openraft-tick-{}", id),
+            this.tick_loop(shutdown_rx)
+                .instrument(tracing::span!(parent: &Span::current(), Level::DEBUG, "tick")),
+        );
 
         TickHandle {
             enabled,
+            ticks,
+            tx,
+            spawned: true,
             shutdown,
             join_handle: Mutex::new(Some(join_handle)),
         }
     }
 
-    pub(crate) async fn tick_loop(self, cancel_rx: OneshotReceiverOf<C, ()>) {
-        let mut i = 0;
+    /// Create a handle without spawning a tick loop: a tick is emitted only by
+    /// [`TickHandle::tick`].
+    pub(crate) fn manual(tx: MpscSenderOf<C, Notification<C>>, enabled: bool) -> TickHandle<C> {
+        TickHandle {
+            enabled: Arc::new(AtomicBool::from(enabled)),
+            ticks: Arc::new(AtomicU64::new(0)),
+            tx,
+            spawned: false,
+            shutdown: Mutex::new(None),
+            join_handle: Mutex::new(None),
+        }
+    }
 
+    pub(crate) async fn tick_loop(self, cancel_rx: OneshotReceiverOf<C, ()>) {
         let mut cancel = std::pin::pin!(cancel_rx);
 
         loop {
@@ -105,7 +139,7 @@ where C: RaftTypeConfig
                 continue;
             }
 
-            i += 1;
+            let i = self.ticks.fetch_add(1, Ordering::Relaxed) + 1;
 
             let send_res = self.tx.send(Notification::Tick { i }).await;
             if let Err(_e) = send_res {
@@ -125,10 +159,30 @@ where C: RaftTypeConfig
         self.enabled.store(enabled, Ordering::Relaxed);
     }
 
+    /// Emit a tick at once, unless ticking is disabled.
+    ///
+    /// It returns an error if `RaftCore` has quit.
+    pub(crate) async fn tick(&self) -> Result<(), ()> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            tracing::debug!("tick is disabled, skip manual tick");
+            return Ok(());
+        }
+
+        let i = self.ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        self.tx.send(Notification::Tick { i }).await.map_err(|_| ())?;
+
+        tracing::debug!("Manual tick sent: {}", i);
+        Ok(())
+    }
+
     /// Signal the tick loop to stop. And return a JoinHandle to wait for the loop to stop.
     ///
-    /// If it is called twice, the second call will return None.
+    /// If it is called twice, or no loop is spawned, it returns None.
     pub(crate) fn shutdown(&self) -> Option<JoinHandleOf<C, ()>> {
+        if !self.spawned {
+            return None;
+        }
+
         {
             let shutdown = {
                 let mut x = self.shutdown.lock().unwrap();
@@ -188,11 +242,12 @@ mod tests {
     #[tokio::test]
     async fn test_shutdown() -> anyhow::Result<()> {
         let (tx, mut rx) = TickUTConfig::mpsc(1024);
-        let th = Tick::<TickUTConfig>::spawn(Duration::from_millis(100), tx, true);
+        let th = Tick::<TickUTConfig>::spawn(&0, Duration::from_millis(100), tx, true);
 
         TickUTConfig::sleep(Duration::from_millis(500)).await;
         let _ = th.shutdown().unwrap().await;
         TickUTConfig::sleep(Duration::from_millis(500)).await;
+        drop(th);
 
         let mut received = vec![];
         while let Some(x) = rx.recv().await {
@@ -207,4 +262,31 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_manual() -> anyhow::Result<()> {
+        let (tx, mut rx) = TickUTConfig::mpsc(1024);
+        let th = Tick::<TickUTConfig>::manual(tx, true);
+
+        TickUTConfig::sleep(Duration::from_millis(200)).await;
+        assert!(rx.try_recv().is_err(), "no tick without calling tick()");
+
+        th.tick().await.unwrap();
+        th.tick().await.unwrap();
+
+        th.enable(false);
+        th.tick().await.unwrap();
+
+        assert!(th.shutdown().is_none(), "no tick loop to shut down");
+        drop(th);
+
+        let mut received = vec![];
+        while let Some(x) = rx.recv().await {
+            received.push(x.to_string());
+        }
+
+        assert_eq!(vec!["Tick 1", "Tick 2"], received, "disabled tick is ignored");
+
+        Ok(())
+    }
 }
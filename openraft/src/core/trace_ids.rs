@@ -0,0 +1,82 @@
+//! The trace ids of client requests, indexed by the log entries they are written to.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// The trace ids attached to log entries by [`Raft::client_write_with_trace_id()`], or received
+/// with the entries in an `AppendEntries` RPC.
+///
+/// The leader looks them up to send them along with the entries it replicates, and every node
+/// records them in the tracing span of appending the entries to its log store, so that a client
+/// request can be followed across nodes in the logs.
+///
+/// It is a handle shared by `RaftCore`, which inserts and evicts trace ids, and every
+/// `ReplicationCore`, which reads them.
+///
+/// [`Raft::client_write_with_trace_id()`]: crate::Raft::client_write_with_trace_id
+#[derive(Clone, Default)]
+pub(crate) struct TraceIds {
+    inner: Arc<Mutex<BTreeMap<u64, String>>>,
+}
+
+impl TraceIds {
+    /// Attach a trace id to the log entry at `index`, replacing the one of a truncated entry.
+    pub(crate) fn insert(&self, index: u64, trace_id: String) {
+        self.inner.lock().unwrap().insert(index, trace_id);
+    }
+
+    /// Attach the trace ids received with log entries.
+    pub(crate) fn extend(&self, trace_ids: &BTreeMap<u64, String>) {
+        if trace_ids.is_empty() {
+            return;
+        }
+        self.inner.lock().unwrap().extend(trace_ids.iter().map(|(k, v)| (*k, v.clone())));
+    }
+
+    /// Return the trace ids of the log entries in `[start, end)`.
+    pub(crate) fn range(&self, start: u64, end: u64) -> BTreeMap<u64, String> {
+        if start >= end {
+            return BTreeMap::new();
+        }
+
+        let inner = self.inner.lock().unwrap();
+        inner.range(start..end).map(|(k, v)| (*k, v.clone())).collect()
+    }
+
+    /// Evict the trace ids before `index`, which are no longer sent or written.
+    pub(crate) fn evict_before(&self, index: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner = inner.split_off(&index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use maplit::btreemap;
+
+    use super::TraceIds;
+
+    fn s(x: &str) -> String {
+        x.to_string()
+    }
+
+    #[test]
+    fn test_trace_ids() {
+        let t = TraceIds::default();
+
+        t.insert(2, s("a"));
+        t.extend(&btreemap! {4 => s("b"), 5 => s("c")});
+
+        assert_eq!(btreemap! {2 => s("a"), 4 => s("b")}, t.range(1, 5));
+        assert_eq!(BTreeMap::new(), t.range(5, 5));
+
+        t.insert(4, s("d"));
+        assert_eq!(btreemap! {4 => s("d"), 5 => s("c")}, t.range(3, 10), "replaced");
+
+        t.evict_before(5);
+        assert_eq!(btreemap! {5 => s("c")}, t.range(0, 10));
+    }
+}
@@ -0,0 +1,123 @@
+//! Track the time of the last change of the state of this node.
+
+use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::metrics::SerdeInstant;
+use crate::metrics::TransitionMetrics;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// Accumulates [`TransitionMetrics`].
+///
+/// The changes are not reported by the engine; they are observed by comparing the values with the
+/// ones seen last time, whenever metrics are reported.
+pub(crate) struct TransitionStats<C>
+where C: RaftTypeConfig
+{
+    metrics: TransitionMetrics<C>,
+
+    server_state: ServerState,
+    vote: VoteOf<C>,
+    snapshot: Option<LogIdOf<C>>,
+    membership: Option<LogIdOf<C>>,
+}
+
+impl<C> TransitionStats<C>
+where C: RaftTypeConfig
+{
+    /// Create with the values this node starts with; they are not counted as changes.
+    pub(crate) fn new(
+        server_state: ServerState,
+        vote: VoteOf<C>,
+        snapshot: Option<LogIdOf<C>>,
+        membership: Option<LogIdOf<C>>,
+    ) -> Self {
+        Self {
+            metrics: TransitionMetrics::default(),
+            server_state,
+            vote,
+            snapshot,
+            membership,
+        }
+    }
+
+    /// Record the current time for every value that differs from the one seen last time.
+    ///
+    /// `vote` is the persisted vote, `snapshot` is the last log id of the persisted snapshot and
+    /// `membership` is the log id of the effective membership config.
+    pub(crate) fn observe(
+        &mut self,
+        server_state: ServerState,
+        vote: &VoteOf<C>,
+        snapshot: Option<&LogIdOf<C>>,
+        membership: Option<&LogIdOf<C>>,
+    ) {
+        let now = SerdeInstant::new(C::now());
+
+        if self.server_state != server_state {
+            self.server_state = server_state;
+            self.metrics.server_state_changed = Some(now);
+        }
+
+        if &self.vote != vote {
+            self.vote = vote.clone();
+            self.metrics.vote_persisted = Some(now);
+        }
+
+        if self.snapshot.as_ref() != snapshot {
+            self.snapshot = snapshot.cloned();
+            self.metrics.snapshot_updated = Some(now);
+        }
+
+        if self.membership.as_ref() != membership {
+            self.membership = membership.cloned();
+            self.metrics.membership_changed = Some(now);
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> TransitionMetrics<C> {
+        self.metrics.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransitionStats;
+    use crate::ServerState;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::metrics::TransitionMetrics;
+
+    #[test]
+    fn test_transition_stats() {
+        let v1 = Vote::new_committed(1, 1);
+        let mut s = TransitionStats::<UTConfig>::new(ServerState::Learner, v1, None, Some(log_id(1, 1, 1)));
+
+        s.observe(ServerState::Learner, &v1, None, Some(&log_id(1, 1, 1)));
+        assert_eq!(
+            TransitionMetrics::default(),
+            s.metrics(),
+            "the initial values are not changes"
+        );
+
+        s.observe(ServerState::Follower, &v1, None, Some(&log_id(1, 1, 1)));
+        let m = s.metrics();
+        assert!(m.server_state_changed.is_some());
+        assert!(m.vote_persisted.is_none());
+
+        let v2 = Vote::new(2, 2);
+        s.observe(
+            ServerState::Follower,
+            &v2,
+            Some(&log_id(1, 1, 3)),
+            Some(&log_id(2, 2, 4)),
+        );
+        let m2 = s.metrics();
+        assert_eq!(m.server_state_changed, m2.server_state_changed);
+        assert!(m2.vote_persisted.is_some());
+        assert!(m2.snapshot_updated.is_some());
+        assert!(m2.membership_changed.is_some());
+    }
+}
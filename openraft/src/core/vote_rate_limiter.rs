@@ -0,0 +1,90 @@
+//! Limit the rate of vote requests handled for every candidate.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::type_config::alias::InstantOf;
+
+/// Allows at most one vote request from a candidate in every `min_interval`.
+///
+/// A legitimate candidate sends one vote request to a node per election, and elections are at
+/// least `election_timeout_min` apart. A node that campaigns more often than that, such as a node
+/// removed from the cluster, is throttled so that it can not keep disturbing the cluster.
+pub(crate) struct VoteRateLimiter<C>
+where C: RaftTypeConfig
+{
+    /// The minimum interval between two handled vote requests from the same candidate. Zero
+    /// disables the limiting.
+    min_interval: Duration,
+
+    /// The time the last vote request from a candidate is handled.
+    ///
+    /// Entries older than `min_interval` are removed, so that it does not grow with the number of
+    /// candidates seen.
+    last_handled: BTreeMap<C::NodeId, InstantOf<C>>,
+}
+
+impl<C> VoteRateLimiter<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_handled: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `true` if a vote request from `candidate` received at `now` should be handled.
+    pub(crate) fn try_acquire(&mut self, candidate: &C::NodeId, now: InstantOf<C>) -> bool {
+        if self.min_interval.is_zero() {
+            return true;
+        }
+
+        self.last_handled.retain(|_, t| now.saturating_duration_since(*t) < self.min_interval);
+
+        if self.last_handled.contains_key(candidate) {
+            return false;
+        }
+
+        self.last_handled.insert(candidate.clone(), now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::VoteRateLimiter;
+    use crate::engine::testing::UTConfig;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_try_acquire() {
+        let mut l = VoteRateLimiter::<UTConfig>::new(Duration::from_millis(100));
+        let now = UTConfig::<()>::now();
+
+        assert!(l.try_acquire(&1, now));
+        assert!(!l.try_acquire(&1, now + Duration::from_millis(99)));
+        assert!(
+            l.try_acquire(&2, now + Duration::from_millis(99)),
+            "other candidates are not limited"
+        );
+
+        assert!(l.try_acquire(&1, now + Duration::from_millis(100)));
+
+        assert!(l.try_acquire(&3, now + Duration::from_millis(300)));
+        assert_eq!(1, l.last_handled.len(), "expired entries are removed");
+    }
+
+    #[test]
+    fn test_disabled() {
+        let mut l = VoteRateLimiter::<UTConfig>::new(Duration::ZERO);
+        let now = UTConfig::<()>::now();
+
+        assert!(l.try_acquire(&1, now));
+        assert!(l.try_acquire(&1, now));
+    }
+}
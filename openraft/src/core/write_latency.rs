@@ -0,0 +1,283 @@
+//! Track the latency of every stage of a write on a leader.
+
+use std::collections::VecDeque;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::base::histogram::Histogram;
+use crate::metrics::WriteLatencyMetrics;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::VoteOf;
+
+/// The maximum number of append batches to track.
+///
+/// Batches are removed when they are applied. If a leader can not commit, the oldest batches are
+/// dropped to bound the memory.
+const MAX_PENDING: usize = 10_000;
+
+/// The weight of a new sample in the moving average of the flush latency is `1 / EWMA_WEIGHT`.
+const EWMA_WEIGHT: u64 = 8;
+
+/// A batch of log entries submitted to the local log store by a leader.
+struct PendingAppend<C>
+where C: RaftTypeConfig
+{
+    vote: VoteOf<C>,
+
+    /// The index range of the entries, inclusive.
+    first: u64,
+    last: u64,
+
+    appended_at: InstantOf<C>,
+    committed_at: Option<InstantOf<C>>,
+}
+
+impl<C> PendingAppend<C>
+where C: RaftTypeConfig
+{
+    fn len(&self) -> u64 {
+        self.last + 1 - self.first
+    }
+}
+
+/// Tracks log entries appended by the leader until they are applied, and records the latency of
+/// each stage into histograms, in microseconds.
+///
+/// Latencies are recorded once a whole batch reaches a stage, for every entry in the batch.
+///
+/// Entries reach every stage in index order, thus the batches are kept in index order, and the
+/// batches that have not yet reached a stage are those after a cursor: a stage only visits the
+/// batches it records.
+pub(crate) struct WriteLatency<C>
+where C: RaftTypeConfig
+{
+    pending: VecDeque<PendingAppend<C>>,
+
+    /// The position in `pending` of the first batch that is not yet flushed.
+    next_flush: usize,
+
+    /// The position in `pending` of the first batch that is not yet committed.
+    next_commit: usize,
+
+    flush: Histogram,
+    commit: Histogram,
+    apply: Histogram,
+
+    /// Exponentially weighted moving average of the flush latency of every batch, in microseconds.
+    flush_ewma: Option<u64>,
+}
+
+impl<C> Default for WriteLatency<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            next_flush: 0,
+            next_commit: 0,
+            flush: Histogram::new(),
+            commit: Histogram::new(),
+            apply: Histogram::new(),
+            flush_ewma: None,
+        }
+    }
+}
+
+impl<C> WriteLatency<C>
+where C: RaftTypeConfig
+{
+    /// Entries `[first, last]` are submitted to the local log store by the leader of `vote`.
+    ///
+    /// Batches appended by a previous leader are discarded, since they may never be committed.
+    pub(crate) fn on_append(&mut self, vote: &VoteOf<C>, first: u64, last: u64, now: InstantOf<C>) {
+        if self.pending.back().is_some_and(|p| &p.vote != vote) {
+            self.pending.clear();
+            self.next_flush = 0;
+            self.next_commit = 0;
+        }
+
+        if self.pending.len() >= MAX_PENDING {
+            self.pop_front();
+        }
+
+        self.pending.push_back(PendingAppend {
+            vote: vote.clone(),
+            first,
+            last,
+            appended_at: now,
+            committed_at: None,
+        });
+    }
+
+    /// Entries up to `upto`(inclusive) are flushed to the local log store.
+    pub(crate) fn on_flush(&mut self, upto: u64, now: InstantOf<C>) {
+        while let Some(p) = self.pending.get(self.next_flush).filter(|p| p.last <= upto) {
+            let latency = micros::<C>(p.appended_at, now);
+            self.flush.record_n(latency, p.len());
+            self.flush_ewma = Some(match self.flush_ewma {
+                None => latency,
+                Some(avg) => (avg * (EWMA_WEIGHT - 1) + latency) / EWMA_WEIGHT,
+            });
+
+            self.next_flush += 1;
+        }
+    }
+
+    /// Entries up to `upto`(inclusive) are committed.
+    pub(crate) fn on_commit(&mut self, upto: u64, now: InstantOf<C>) {
+        while let Some(p) = self.pending.get_mut(self.next_commit).filter(|p| p.last <= upto) {
+            p.committed_at = Some(now);
+            self.commit.record_n(micros::<C>(p.appended_at, now), p.len());
+
+            self.next_commit += 1;
+        }
+    }
+
+    /// Entries up to `upto`(inclusive) are applied to the state machine.
+    pub(crate) fn on_apply(&mut self, upto: u64, now: InstantOf<C>) {
+        while self.pending.front().is_some_and(|p| p.last <= upto) {
+            let p = self.pop_front().unwrap();
+
+            // Not committed by this leader.
+            let Some(committed_at) = p.committed_at else {
+                continue;
+            };
+
+            self.apply.record_n(micros::<C>(committed_at, now), p.len());
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<PendingAppend<C>> {
+        let p = self.pending.pop_front()?;
+        self.next_flush = self.next_flush.saturating_sub(1);
+        self.next_commit = self.next_commit.saturating_sub(1);
+        Some(p)
+    }
+
+    /// Moving average of the flush latency in microseconds, `0` if nothing has been flushed.
+    pub(crate) fn flush_latency_ewma(&self) -> u64 {
+        self.flush_ewma.unwrap_or_default()
+    }
+
+    pub(crate) fn metrics(&self) -> WriteLatencyMetrics {
+        WriteLatencyMetrics {
+            entries: self.apply.total(),
+            flush: self.flush.percentile_stats(),
+            commit: self.commit.percentile_stats(),
+            apply: self.apply.percentile_stats(),
+        }
+    }
+}
+
+fn micros<C>(since: InstantOf<C>, now: InstantOf<C>) -> u64
+where C: RaftTypeConfig {
+    now.saturating_duration_since(since).as_micros() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::WriteLatency;
+    use crate::Vote;
+    use crate::base::histogram::Histogram;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::PercentileStats;
+    use crate::type_config::TypeConfigExt;
+
+    /// Build the expected stats of `(micros, count)` pairs.
+    fn stats(values: &[(u64, u64)]) -> PercentileStats {
+        let mut h = Histogram::new();
+        for (v, n) in values {
+            h.record_n(*v, *n);
+        }
+        h.percentile_stats()
+    }
+
+    #[test]
+    fn test_write_latency_stages() {
+        let mut w = WriteLatency::<UTConfig>::default();
+        let vote = Vote::new_committed(1, 1);
+        let t0 = UTConfig::<()>::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+
+        w.on_append(&vote, 1, 2, t0);
+        w.on_append(&vote, 3, 3, ms(1));
+
+        w.on_flush(2, ms(2));
+        w.on_commit(3, ms(3));
+        w.on_apply(2, ms(7));
+
+        let m = w.metrics();
+        assert_eq!(2, m.entries);
+        assert_eq!(stats(&[(2000, 2)]), m.flush);
+        assert_eq!(stats(&[(3000, 2), (2000, 1)]), m.commit);
+        assert_eq!(stats(&[(4000, 2)]), m.apply);
+
+        // The batch of entry 3 is still pending
+        assert_eq!(1, w.pending.len());
+    }
+
+    #[test]
+    fn test_write_latency_each_batch_recorded_once() {
+        let mut w = WriteLatency::<UTConfig>::default();
+        let vote = Vote::new_committed(1, 1);
+        let t0 = UTConfig::<()>::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+
+        w.on_append(&vote, 1, 1, t0);
+        w.on_append(&vote, 2, 2, t0);
+        w.on_append(&vote, 3, 3, t0);
+
+        w.on_flush(1, ms(1));
+        w.on_commit(1, ms(1));
+        w.on_flush(2, ms(2));
+        w.on_commit(2, ms(2));
+        w.on_apply(2, ms(2));
+
+        // Applied batches are removed, the remaining one is recorded when it reaches a stage.
+        w.on_flush(3, ms(3));
+        w.on_flush(3, ms(4));
+        w.on_commit(3, ms(3));
+        w.on_apply(3, ms(3));
+
+        let m = w.metrics();
+        assert_eq!(3, m.entries);
+        assert_eq!(stats(&[(1000, 1), (2000, 1), (3000, 1)]), m.flush);
+        assert_eq!(stats(&[(1000, 1), (2000, 1), (3000, 1)]), m.commit);
+        assert!(w.pending.is_empty());
+    }
+
+    #[test]
+    fn test_write_latency_flush_ewma() {
+        let mut w = WriteLatency::<UTConfig>::default();
+        let vote = Vote::new_committed(1, 1);
+        let t0 = UTConfig::<()>::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+
+        assert_eq!(0, w.flush_latency_ewma());
+
+        w.on_append(&vote, 1, 1, t0);
+        w.on_flush(1, ms(8));
+        assert_eq!(8000, w.flush_latency_ewma());
+
+        w.on_append(&vote, 2, 2, ms(8));
+        w.on_flush(2, ms(24));
+        assert_eq!((8000 * 7 + 16000) / 8, w.flush_latency_ewma());
+    }
+
+    #[test]
+    fn test_write_latency_new_leader_discards_pending() {
+        let mut w = WriteLatency::<UTConfig>::default();
+        let t0 = UTConfig::<()>::now();
+
+        w.on_append(&Vote::new_committed(1, 1), 1, 2, t0);
+        w.on_append(&Vote::new_committed(2, 1), 3, 3, t0);
+        assert_eq!(1, w.pending.len());
+
+        // Not committed by this leader, not recorded.
+        w.on_apply(2, t0);
+        assert_eq!(0, w.metrics().entries);
+        assert_eq!(1, w.pending.len());
+    }
+}
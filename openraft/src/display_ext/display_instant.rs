@@ -42,7 +42,7 @@ where T: Instant
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Convert Instant to SystemTime
         let sys_t = {
-            let sys_now = std::time::SystemTime::now();
+            let sys_now = T::wall_clock_now();
             let now = T::now();
 
             if &now >= self.0 {
@@ -88,7 +88,6 @@ where T: Instant
 
 #[cfg(test)]
 mod tests {
-
     use crate::TokioInstant;
     use crate::display_ext::DisplayInstantExt;
 
@@ -104,4 +103,15 @@ mod tests {
         println!("now: {}", now.display().utc().full());
         Ok(())
     }
+
+    /// The wall clock time is obtained with [`crate::Instant::wall_clock_now`].
+    #[test]
+    fn test_display_instant_wall_clock_now() -> anyhow::Result<()> {
+        let before = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        let got = TokioInstant::now().display().utc().full().to_string();
+        let after = chrono::Utc::now().format("%Y-%m-%d").to_string();
+
+        assert!(got.starts_with(&before) || got.starts_with(&after), "got: {}", got);
+        Ok(())
+    }
 }
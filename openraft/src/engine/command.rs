@@ -111,6 +111,14 @@ where C: RaftTypeConfig
         targets: Vec<ReplicationProgress<C>>,
     },
 
+    /// Replace the replication stream to a single target, e.g., after the stream panicked.
+    ///
+    /// The Runtime has to close the old stream if it is still running and start a new one.
+    RestartReplicationStream {
+        /// The target to replicate to.
+        target: ReplicationProgress<C>,
+    },
+
     /// Save vote to storage
     SaveVote { vote: VoteOf<C> },
 
@@ -173,6 +181,7 @@ where C: RaftTypeConfig
             Command::RebuildReplicationStreams { targets } => {
                 write!(f, "RebuildReplicationStreams: {}", targets.display_n(10))
             }
+            Command::RestartReplicationStream { target } => write!(f, "RestartReplicationStream: {}", target),
             Command::SaveVote { vote } => write!(f, "SaveVote: {}", vote),
             Command::SendVote { vote_req } => write!(f, "SendVote: {}", vote_req),
             Command::PurgeLog { upto } => write!(f, "PurgeLog: upto: {}", upto),
@@ -208,6 +217,7 @@ where
             (Command::Replicate { target, req },               Command::Replicate { target: b_target, req: other_req, }, )           => target == b_target && req == other_req,
             (Command::BroadcastTransferLeader { req },         Command::BroadcastTransferLeader { req: b, }, )                       => req == b,
             (Command::RebuildReplicationStreams { targets },   Command::RebuildReplicationStreams { targets: b }, )                  => targets == b,
+            (Command::RestartReplicationStream { target },    Command::RestartReplicationStream { target: b }, )                    => target == b,
             (Command::SaveVote { vote },                       Command::SaveVote { vote: b })                                        => vote == b,
             (Command::SendVote { vote_req },                   Command::SendVote { vote_req: b }, )                                  => vote_req == b,
             (Command::PurgeLog { upto },                       Command::PurgeLog { upto: b })                                        => upto == b,
@@ -227,6 +237,7 @@ where C: RaftTypeConfig
     pub(crate) fn kind(&self) -> CommandKind {
         match self {
             Command::RebuildReplicationStreams { .. } => CommandKind::Main,
+            Command::RestartReplicationStream { .. }  => CommandKind::Main,
             Command::Respond { .. }                   => CommandKind::Respond,
             // Apply is firstly handled by RaftCore, then forwarded to state machine worker.
             // TODO: Apply also write `committed` to log-store, which should be run in CommandKind::Log
@@ -254,6 +265,7 @@ where C: RaftTypeConfig
     pub(crate) fn condition(&self) -> Option<Condition<C>> {
         match self {
             Command::RebuildReplicationStreams { .. } => None,
+            Command::RestartReplicationStream { .. }  => None,
             Command::Respond { when, .. }             => when.clone(),
 
             Command::UpdateIOProgress { when, .. }    => when.clone(),
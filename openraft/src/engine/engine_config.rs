@@ -1,6 +1,6 @@
-use std::time::Duration;
-
+use crate::BlankEntryPolicy;
 use crate::Config;
+use crate::PurgePolicy;
 use crate::RaftTypeConfig;
 use crate::engine::time_state;
 use crate::type_config::alias::AsyncRuntimeOf;
@@ -8,6 +8,7 @@ use crate::type_config::alias::AsyncRuntimeOf;
 /// Config for Engine
 #[derive(Clone, Debug)]
 #[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
 pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The id of this node.
     pub(crate) id: C::NodeId,
@@ -18,11 +19,30 @@ pub(crate) struct EngineConfig<C: RaftTypeConfig> {
     /// The minimal number of applied logs to purge in a batch.
     pub(crate) purge_batch_size: u64,
 
+    /// Which of the logs included in the snapshot are purged.
+    pub(crate) purge_policy: PurgePolicy,
+
     /// The maximum number of entries per payload allowed to be transmitted during replication
     pub(crate) max_payload_entries: u64,
 
+    /// The maximum number of AppendEntries RPCs in flight to a single target.
+    pub(crate) max_append_entries_inflight: u64,
+
+    /// The number of committed but not applied logs at which to respond with `Busy`; `0` disables.
+    pub(crate) busy_apply_backlog: u64,
+
     pub(crate) allow_log_reversion: bool,
 
+    /// The number of log reversions reported by a follower before it is quarantined.
+    pub(crate) quarantine_after_log_reversions: u64,
+
+    /// Whether to send the committed log id to followers at once when the leader's own flush
+    /// commits a log.
+    pub(crate) broadcast_commit_on_local_flush: bool,
+
+    /// When a newly elected leader appends a blank log.
+    pub(crate) blank_entry_policy: BlankEntryPolicy,
+
     pub(crate) timer_config: time_state::Config,
 }
 
@@ -30,18 +50,25 @@ impl<C> EngineConfig<C>
 where C: RaftTypeConfig
 {
     pub(crate) fn new(id: C::NodeId, config: &Config) -> Self {
-        let election_timeout = Duration::from_millis(config.new_rand_election_timeout::<AsyncRuntimeOf<C>>());
+        let election_timeout =
+            config.election_timeout_unit.to_duration(config.new_rand_election_timeout::<AsyncRuntimeOf<C>>());
         Self {
             id,
             max_in_snapshot_log_to_keep: config.max_in_snapshot_log_to_keep,
             purge_batch_size: config.purge_batch_size,
+            purge_policy: config.purge_policy,
             max_payload_entries: config.max_payload_entries,
+            max_append_entries_inflight: config.max_append_entries_inflight,
+            busy_apply_backlog: config.busy_apply_backlog,
             allow_log_reversion: config.get_allow_log_reversion(),
+            quarantine_after_log_reversions: config.quarantine_after_log_reversions,
+            broadcast_commit_on_local_flush: config.broadcast_commit_on_local_flush,
+            blank_entry_policy: config.blank_entry_policy,
 
             timer_config: time_state::Config {
                 election_timeout,
-                smaller_log_timeout: Duration::from_millis(config.election_timeout_max * 2),
-                leader_lease: Duration::from_millis(config.election_timeout_max),
+                smaller_log_timeout: config.election_timeout_max() * 2,
+                leader_lease: config.election_timeout_max(),
             },
         }
     }
@@ -52,8 +79,14 @@ where C: RaftTypeConfig
             id,
             max_in_snapshot_log_to_keep: 1000,
             purge_batch_size: 256,
+            purge_policy: PurgePolicy::KeepInSnapshot,
             max_payload_entries: 300,
+            max_append_entries_inflight: 1,
+            busy_apply_backlog: 0,
             allow_log_reversion: false,
+            quarantine_after_log_reversions: 3,
+            broadcast_commit_on_local_flush: true,
+            blank_entry_policy: BlankEntryPolicy::Always,
             timer_config: time_state::Config::default(),
         }
     }
@@ -206,6 +206,32 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Initialize a new cluster from a snapshot, instead of from a membership log entry.
+    ///
+    /// The snapshot is rewritten to start at the very first log id, and its membership becomes the
+    /// initial membership. It returns the condition to wait for the snapshot to be installed.
+    pub(crate) fn initialize_from_snapshot(
+        &mut self,
+        mut snapshot: Snapshot<C>,
+    ) -> Result<Option<Condition<C>>, InitializeError<C>> {
+        self.check_initialize()?;
+
+        let m = snapshot.meta.last_membership.membership().clone();
+        self.check_members_contain_me(&m)?;
+
+        snapshot.meta = snapshot.meta.into_bootstrap(m);
+
+        // FollowingHandler requires vote to be committed.
+        let vote = <VoteOf<C> as RaftVote<C>>::from_leader_id(Default::default(), true);
+        self.state.vote.update(C::now(), Duration::default(), vote);
+        let cond = self.following_handler().install_full_snapshot(snapshot);
+
+        // With the new config, start to elect to become leader
+        self.elect();
+
+        Ok(cond)
+    }
+
     /// Start to elect this node as leader
     #[tracing::instrument(level = "debug", skip(self))]
     pub(crate) fn elect(&mut self) {
@@ -420,7 +446,15 @@ where C: RaftTypeConfig
         let res = self.append_entries(vote, prev_log_id, entries);
         let is_ok = res.is_ok();
 
-        let resp: AppendEntriesResponse<C> = res.into();
+        let mut resp: AppendEntriesResponse<C> = res.into();
+
+        if is_ok {
+            let apply_backlog = self.apply_backlog();
+            if self.config.busy_apply_backlog > 0 && apply_backlog >= self.config.busy_apply_backlog {
+                tracing::info!(apply_backlog, "busy applying logs, respond with Busy");
+                resp = AppendEntriesResponse::Busy { apply_backlog };
+            }
+        }
 
         let condition = if is_ok {
             Some(Condition::IOFlushed {
@@ -438,6 +472,11 @@ where C: RaftTypeConfig
         is_ok
     }
 
+    /// The number of logs that are committed but not yet applied.
+    fn apply_backlog(&self) -> u64 {
+        self.state.committed().next_index().saturating_sub(self.state.io_applied().next_index())
+    }
+
     pub(crate) fn append_entries(
         &mut self,
         vote: &VoteOf<C>,
@@ -574,8 +613,28 @@ where C: RaftTypeConfig
             return;
         }
 
-        self.log_handler().schedule_policy_based_purge();
+        self.schedule_policy_based_purge();
         self.try_purge_log();
+
+        // A follower waiting for a resync is sent the new snapshot.
+        if let Ok(mut lh) = self.leader_handler() {
+            let mut rh = lh.replication_handler();
+            if rh.has_pending_resync() {
+                rh.initiate_replication();
+            }
+        }
+    }
+
+    /// Schedule purging the logs according to the purge policy.
+    ///
+    /// A leader keeps the logs its lagging members need, if the policy is
+    /// [`PurgePolicy::KeepForLagging`](crate::PurgePolicy::KeepForLagging).
+    pub(crate) fn schedule_policy_based_purge(&mut self) {
+        if self.leader.is_some() {
+            self.replication_handler().schedule_policy_based_purge();
+        } else {
+            self.log_handler().schedule_policy_based_purge(None);
+        }
     }
 
     /// Try to purge logs up to the expected position.
@@ -602,6 +661,35 @@ where C: RaftTypeConfig
         }
     }
 
+    /// Keep logs that are not yet archived from being purged, if `enabled`.
+    ///
+    /// When a log archiver is set, archiving starts from the first log not yet purged. When it is
+    /// removed, logs scheduled to purge are purged without being archived.
+    pub(crate) fn set_log_archive(&mut self, enabled: bool) {
+        if enabled {
+            if self.state.archive_next.is_none() {
+                self.state.archive_next = Some(self.state.last_purged_log_id().next_index());
+            }
+        } else {
+            self.state.archive_next = None;
+            self.try_purge_log();
+        }
+    }
+
+    /// The logs before `next` are archived, purge them if they are scheduled to purge.
+    pub(crate) fn on_logs_archived(&mut self, next: u64) {
+        let Some(archive_next) = self.state.archive_next.as_mut() else {
+            return;
+        };
+
+        if next <= *archive_next {
+            return;
+        }
+
+        *archive_next = next;
+        self.try_purge_log();
+    }
+
     /// This is a to user API that triggers log purging up to `index`, inclusive.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn trigger_purge_log(&mut self, mut index: u64) {
@@ -710,6 +798,39 @@ where C: RaftTypeConfig
 
         None
     }
+
+    /// Check the invariants of the engine state after processing an input, in debug builds.
+    ///
+    /// `prev_vote` is the vote before `input` is processed. Besides the invariants of
+    /// [`RaftState`], such as `purged <= snapshot <= applied <= committed <= last_log_id`, the vote
+    /// must never decrease.
+    ///
+    /// A violation panics with the input that caused it and the state, so that a corrupted state
+    /// is caught where it is introduced instead of becoming a wrong decision later.
+    #[cfg(debug_assertions)]
+    pub(crate) fn check_invariants(&mut self, prev_vote: &VoteOf<C>, input: impl std::fmt::Display) {
+        // Disable the validation on access, which panics without context.
+        let enabled = self.state.is_enabled();
+        self.state.enable_validation(false);
+
+        let res = self.validate_after_input(prev_vote);
+
+        self.state.enable_validation(enabled);
+
+        if let Err(e) = res {
+            panic!(
+                "RaftState invariant violated after input: {}; error: {}; prev_vote: {}; state: {:?}",
+                input, e, prev_vote, self.state
+            );
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    fn validate_after_input(&self, prev_vote: &VoteOf<C>) -> Result<(), Box<dyn std::error::Error>> {
+        validit::Validate::validate(&*self.state)?;
+        validit::less_equal!(prev_vote.as_ref_vote(), self.state.vote_ref().as_ref_vote());
+        Ok(())
+    }
 }
 
 /// Supporting util
@@ -738,14 +859,17 @@ where C: RaftTypeConfig
         let _res = self.vote_handler().update_vote(&vote.clone().into_vote());
         debug_assert!(_res.is_ok(), "commit vote cannot fail but: {:?}", _res);
 
-        self.state.accept_log_io(IOId::new_log_io(vote, last_log_id));
+        self.state.accept_log_io(IOId::new_log_io(vote.clone(), last_log_id.clone()));
 
-        // No need to submit UpdateIOProgress command,
+        // No need to submit UpdateIOProgress command if a blank log is appended:
         // IO progress is updated by the new blank log
-
-        self.leader_handler()
-            .unwrap()
-            .leader_append_entries(vec![C::Entry::new_blank(LogIdOf::<C>::default())]);
+        if !self.leader_handler().unwrap().append_blank_log() {
+            self.output.push_command(Command::UpdateIOProgress {
+                when: None,
+                io_id: IOId::new_log_io(vote, last_log_id),
+            });
+            self.replication_handler().initiate_replication();
+        }
     }
 
     /// Check if a raft node is in a state that allows to initialize.
@@ -314,6 +314,11 @@ where C: RaftTypeConfig
 
         self.output.push_command(Command::from(sm::Command::install_full_snapshot(snapshot, log_io_id)));
 
+        // Logs covered by the installed snapshot are not archived.
+        if let Some(archive_next) = self.state.archive_next.as_mut() {
+            *archive_next = std::cmp::max(*archive_next, snap_last_log_id.index() + 1);
+        }
+
         self.state.purge_upto = Some(snap_last_log_id.clone());
         self.log_handler().purge_log();
 
@@ -66,3 +66,25 @@ fn test_get_read_log_id() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_get_read_log_id_without_blank_log() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    // The blank log is not appended: the last log is not proposed by this leader.
+    eng.leader.as_mut().unwrap().noop_log_id = log_id(3, 1, 4);
+
+    eng.state.apply_progress_mut().accept(log_id(1, 1, 1));
+    let got = eng.leader_handler()?.get_read_log_id();
+    assert_eq!(
+        log_id(3, 1, 4),
+        got,
+        "not all logs are committed, wait for a log of this leader"
+    );
+
+    eng.state.apply_progress_mut().accept(log_id(2, 1, 3));
+    let got = eng.leader_handler()?.get_read_log_id();
+    assert_eq!(log_id(2, 1, 3), got, "all logs are committed");
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
+use crate::BlankEntryPolicy;
 use crate::RaftState;
 use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOptionExt;
 use crate::engine::Command;
 use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
@@ -11,6 +13,7 @@ use crate::proposer::Leader;
 use crate::proposer::LeaderQuorumSet;
 use crate::raft::message::TransferLeaderRequest;
 use crate::raft_state::IOId;
+use crate::raft_state::LogStateReader;
 use crate::replication::ReplicationSessionId;
 use crate::type_config::alias::LogIdOf;
 
@@ -95,6 +98,35 @@ where C: RaftTypeConfig
         rh.initiate_replication();
     }
 
+    /// Append a blank log for a newly elected leader, according to the
+    /// [`BlankEntryPolicy`](crate::BlankEntryPolicy).
+    ///
+    /// Returns whether a blank log is appended, which also initiates replication.
+    pub(crate) fn append_blank_log(&mut self) -> bool {
+        let uncommitted = self.state.last_log_id() > self.state.committed();
+
+        let append = match self.config.blank_entry_policy {
+            BlankEntryPolicy::Always => true,
+            BlankEntryPolicy::IfUncommitted => uncommitted,
+            BlankEntryPolicy::Never => {
+                if uncommitted {
+                    tracing::warn!(
+                        last_log_id = display(self.state.last_log_id().display()),
+                        committed = display(self.state.committed().display()),
+                        "leader does not append a blank log: \
+                        logs are not committed and linearizable reads are blocked until a new log is committed"
+                    );
+                }
+                false
+            }
+        };
+
+        if append {
+            self.leader_append_entries(vec![C::Entry::new_blank(LogIdOf::<C>::default())]);
+        }
+        append
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn send_heartbeat(&mut self) {
         let membership_log_id = self.state.membership_state.effective().log_id();
@@ -115,6 +147,12 @@ where C: RaftTypeConfig
             return self.leader.noop_log_id.clone();
         };
 
+        // A leader that has not appended a blank log and whose logs are all committed reads at the
+        // committed log id: every log committed in the cluster is in its log.
+        if self.leader.last_log_id() < Some(&self.leader.noop_log_id) && self.leader.last_log_id() == Some(&committed) {
+            return committed;
+        }
+
         // noop log id is the first log this leader proposed.
         std::cmp::max(self.leader.noop_log_id.clone(), committed)
     }
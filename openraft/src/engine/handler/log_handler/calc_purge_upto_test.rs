@@ -69,7 +69,7 @@ fn test_calc_purge_upto() -> anyhow::Result<()> {
             eng.state.purged_next = last_purged.index() + 1;
         }
         eng.state.snapshot_meta.last_log_id = snapshot_last_log_id;
-        let got = eng.log_handler().calc_purge_upto();
+        let got = eng.log_handler().calc_purge_upto(None);
 
         assert_eq!(
             want, got,
@@ -80,3 +80,30 @@ fn test_calc_purge_upto() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_calc_purge_upto_keep_from() -> anyhow::Result<()> {
+    // keep_from, want
+    let cases = vec![
+        //
+        (None, Some(log_id(3, 4))),
+        (Some(6), Some(log_id(3, 4))),
+        (Some(5), Some(log_id(3, 4))),
+        (Some(4), Some(log_id(3, 3))),
+        (Some(1), Some(log_id(0, 0))),
+        (Some(0), None),
+    ];
+
+    for (keep_from, want) in cases {
+        let mut eng = eng();
+        eng.config.max_in_snapshot_log_to_keep = 0;
+        eng.config.purge_batch_size = 1;
+        eng.state.snapshot_meta.last_log_id = Some(log_id(3, 4));
+
+        let got = eng.log_handler().calc_purge_upto(keep_from);
+
+        assert_eq!(want, got, "case: keep_from: {:?}", keep_from);
+    }
+
+    Ok(())
+}
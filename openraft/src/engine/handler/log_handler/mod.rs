@@ -27,22 +27,32 @@ impl<C> LogHandler<'_, C>
 where C: RaftTypeConfig
 {
     /// Purge log entries up to `RaftState.purge_upto()`, inclusive.
+    ///
+    /// If a log archiver is set, logs that are not yet archived are kept, and are purged when they
+    /// are archived.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn purge_log(&mut self) {
         let st = &mut self.state;
-        let purge_upto = st.purge_upto();
+        let mut purge_upto = st.purge_upto().cloned();
 
         tracing::info!(
             last_purged_log_id = display(st.last_purged_log_id().display()),
-            purge_upto = display(purge_upto.display()),
+            purge_upto = display(purge_upto.as_ref().display()),
+            archive_next = debug(st.archive_next),
             "purge_log"
         );
 
-        if purge_upto <= st.last_purged_log_id() {
+        if let Some(archive_next) = st.archive_next
+            && purge_upto.next_index() > archive_next
+        {
+            purge_upto = archive_next.checked_sub(1).and_then(|i| st.get_log_id(i));
+        }
+
+        if purge_upto.as_ref() <= st.last_purged_log_id() {
             return;
         }
 
-        let upto = purge_upto.unwrap().clone();
+        let upto = purge_upto.unwrap();
 
         st.purge_log(&upto);
         self.output.push_command(Command::PurgeLog { upto });
@@ -53,9 +63,14 @@ where C: RaftTypeConfig
     ///
     /// This method is called after building a snapshot because openraft only purges logs that are
     /// already included in the snapshot.
+    ///
+    /// `keep_from` is the index of the first log that must not be purged, e.g., the first log a
+    /// lagging member needs.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn schedule_policy_based_purge(&mut self) {
-        if let Some(purge_upto) = self.calc_purge_upto() {
+    pub(crate) fn schedule_policy_based_purge(&mut self, keep_from: Option<u64>) {
+        if let Some(purge_upto) = self.calc_purge_upto(keep_from)
+            && Some(&purge_upto) > self.state.purge_upto()
+        {
             self.update_purge_upto(purge_upto);
         }
     }
@@ -74,17 +89,23 @@ where C: RaftTypeConfig
     ///
     /// `max_keep` specifies the number of applied logs to keep.
     /// `max_keep==0` means every applied log can be purged.
+    ///
+    /// Logs at or after `keep_from` are not purged.
     #[tracing::instrument(level = "debug", skip_all)]
-    pub(crate) fn calc_purge_upto(&self) -> Option<LogIdOf<C>> {
+    pub(crate) fn calc_purge_upto(&self, keep_from: Option<u64>) -> Option<LogIdOf<C>> {
         let st = &self.state;
         let max_keep = self.config.max_in_snapshot_log_to_keep;
         let batch_size = self.config.purge_batch_size;
 
-        let purge_end = self.state.snapshot_meta.last_log_id.next_index().saturating_sub(max_keep);
+        let mut purge_end = self.state.snapshot_meta.last_log_id.next_index().saturating_sub(max_keep);
+        if let Some(keep_from) = keep_from {
+            purge_end = std::cmp::min(purge_end, keep_from);
+        }
 
         tracing::debug!(
             snapshot_last_log_id = debug(self.state.snapshot_meta.last_log_id.clone()),
             max_keep,
+            keep_from = debug(keep_from),
             "try purge: (-oo, {})",
             purge_end
         );
@@ -166,3 +166,42 @@ fn test_purge_log_to_higher_leader_lgo() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_purge_log_upto_archived() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let mut lh = eng.log_handler();
+    lh.state.purge_upto = Some(log_id(4, 1, 6));
+    lh.state.archive_next = Some(5);
+    lh.purge_log();
+
+    assert_eq!(Some(&log_id(4, 1, 4)), lh.state.last_purged_log_id());
+    assert_eq!(
+        Some(&log_id(4, 1, 6)),
+        lh.state.purge_upto(),
+        "the rest is purged when archived"
+    );
+
+    assert_eq!(
+        vec![Command::PurgeLog { upto: log_id(4, 1, 4) }],
+        lh.output.take_commands()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_purge_log_none_archived() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let mut lh = eng.log_handler();
+    lh.state.purge_upto = Some(log_id(4, 1, 6));
+    lh.state.archive_next = Some(3);
+    lh.purge_log();
+
+    assert_eq!(Some(&log_id(2, 1, 2)), lh.state.last_purged_log_id());
+    assert_eq!(0, lh.output.take_commands().len());
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::EffectiveMembership;
 use crate::LogIdOptionExt;
 use crate::Membership;
+use crate::PurgePolicy;
 use crate::RaftState;
 use crate::RaftTypeConfig;
 use crate::ServerState;
@@ -12,6 +13,7 @@ use crate::engine::EngineConfig;
 use crate::engine::EngineOutput;
 use crate::engine::ReplicationProgress;
 use crate::engine::handler::log_handler::LogHandler;
+use crate::engine::handler::snapshot_handler::SnapshotHandler;
 use crate::error::NodeNotFound;
 use crate::error::Operation;
 use crate::progress;
@@ -30,6 +32,14 @@ use crate::vote::raft_vote::RaftVoteExt;
 #[cfg(test)]
 mod append_membership_test;
 #[cfg(test)]
+mod restart_replication_stream_test;
+#[cfg(test)]
+mod resync_test;
+#[cfg(test)]
+mod update_leader_clock_test;
+#[cfg(test)]
+mod update_local_progress_test;
+#[cfg(test)]
 mod update_matching_test;
 
 /// Handle replication operations.
@@ -117,11 +127,12 @@ where C: RaftTypeConfig
     pub(crate) fn update_leader_clock(&mut self, node_id: C::NodeId, t: InstantOf<C>) {
         tracing::debug!(target = display(&node_id), t = display(t.display()), "{}", func_name!());
 
-        let granted = *self
-            .leader
-            .clock_progress
-            .increase_to(&node_id, Some(t))
-            .expect("it should always update existing progress");
+        // A heartbeat response may arrive after the target is removed from the membership.
+        let Ok(granted) = self.leader.clock_progress.increase_to(&node_id, Some(t)) else {
+            tracing::debug!(target = display(&node_id), "ignore clock of a removed target");
+            return;
+        };
+        let granted = *granted;
 
         tracing::debug!(
             granted = display(granted.as_ref().map(|x| x.display()).display()),
@@ -183,18 +194,25 @@ where C: RaftTypeConfig
     /// In raft a log that is granted and in the leader term is committed.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) fn try_commit_quorum_accepted(&mut self, granted: Option<LogIdOf<C>>) {
+        if self.commit_quorum_accepted(granted) {
+            self.output.push_command(Command::ReplicateCommitted {
+                committed: self.state.committed().cloned(),
+            });
+        }
+    }
+
+    /// Update the committed log id without sending it to followers.
+    ///
+    /// It returns `true` if the committed log id is updated.
+    fn commit_quorum_accepted(&mut self, granted: Option<LogIdOf<C>>) -> bool {
         // Only when the log id is proposed by the current leader, it is committed.
         if let Some(ref c) = granted
             && !self.state.vote_ref().is_same_leader(c.committed_leader_id())
         {
-            return;
+            return false;
         }
 
-        if let Some(_prev_committed) = self.state.update_committed(&granted) {
-            self.output.push_command(Command::ReplicateCommitted {
-                committed: self.state.committed().cloned(),
-            });
-        }
+        self.state.update_committed(&granted).is_some()
     }
 
     /// Update progress when replicated data(logs or snapshot) does not match the follower/learner
@@ -225,6 +243,8 @@ where C: RaftTypeConfig
     ///   progress tracker.
     /// - This flag will be consumed upon the next log reversion detection, allowing for a one-time
     ///   reset.
+    /// - If the node is quarantined for log reversions, the quarantine is lifted so that the
+    ///   replication is resumed and the reversion is detected again.
     /// - If the node is not found in the progress tracker, this method ignore it.
     pub(crate) fn allow_next_revert(&mut self, target: C::NodeId, allow: bool) -> Result<(), NodeNotFound<C>> {
         let Some(prog_entry) = self.leader.progress.get_mut(&target) else {
@@ -238,6 +258,12 @@ where C: RaftTypeConfig
 
         prog_entry.allow_log_reversion = allow;
 
+        if allow && prog_entry.quarantined {
+            prog_entry.quarantined = false;
+            prog_entry.reversions = 0;
+            self.initiate_replication();
+        }
+
         Ok(())
     }
 
@@ -259,10 +285,10 @@ where C: RaftTypeConfig
         match repl_res {
             Ok(p) => match p.0 {
                 Ok(matching) => {
-                    self.update_matching(target, matching);
+                    self.update_matching(target.clone(), matching);
                 }
                 Err(conflict) => {
-                    self.update_conflicting(target, conflict, has_payload);
+                    self.update_conflicting(target.clone(), conflict, has_payload);
                 }
             },
             Err(err_str) => {
@@ -274,6 +300,8 @@ where C: RaftTypeConfig
             }
         };
 
+        self.try_reset_for_resync(&target);
+
         // The purge job may be postponed because a replication task is using them.
         // Thus, we just try again to purge when progress is updated.
         self.try_purge_log();
@@ -296,6 +324,95 @@ where C: RaftTypeConfig
         self.output.push_command(Command::RebuildReplicationStreams { targets });
     }
 
+    /// Replace the replication stream to `target`, e.g., after the stream panicked.
+    ///
+    /// The in-flight data is discarded and will be resent by the new stream.
+    pub(crate) fn restart_replication_stream(&mut self, target: C::NodeId) {
+        if target == self.config.id {
+            return;
+        }
+
+        let Some(prog_entry) = self.leader.progress.get_mut(&target) else {
+            tracing::warn!(
+                target = display(&target),
+                "no progress for target, skip restarting replication"
+            );
+            return;
+        };
+
+        prog_entry.inflight = Inflight::None;
+        let progress = ReplicationProgress(target, prog_entry.clone());
+
+        self.output.push_command(Command::RestartReplicationStream { target: progress });
+        self.initiate_replication();
+    }
+
+    /// Resync `target` with a full snapshot, discarding what is known about its log.
+    ///
+    /// The progress of `target` is reset, so that a reverted log found on it does not need
+    /// `allow_log_reversion`, and the last snapshot is sent to it. If there is no snapshot yet,
+    /// one is built, and it is sent once built.
+    ///
+    /// Resyncing the leader itself is ignored.
+    pub(crate) fn resync(&mut self, target: C::NodeId) -> Result<(), NodeNotFound<C>> {
+        if target == self.config.id {
+            tracing::warn!("{}: target {} is the leader itself, ignored", func_name!(), target);
+            return Ok(());
+        }
+
+        let Some(prog_entry) = self.leader.progress.get_mut(&target) else {
+            tracing::warn!(
+                "target node {} not found in progress tracker, when {}",
+                target,
+                func_name!()
+            );
+            return Err(NodeNotFound::new(target, Operation::Resync));
+        };
+
+        prog_entry.force_snapshot = true;
+
+        if self.state.snapshot_last_log_id().is_none() {
+            let mut snapshot_handler = SnapshotHandler {
+                state: self.state,
+                output: self.output,
+            };
+            snapshot_handler.trigger_snapshot();
+        }
+
+        self.try_reset_for_resync(&target);
+        self.initiate_replication();
+        Ok(())
+    }
+
+    /// Reset the progress of `target` for a requested resync, once no data is in flight to it.
+    ///
+    /// The response to data sent before the reset reports a state of the target that is no longer
+    /// trusted, thus the reset waits for it.
+    fn try_reset_for_resync(&mut self, target: &C::NodeId) {
+        let Some(prog_entry) = self.leader.progress.try_get(target) else {
+            return;
+        };
+
+        if !prog_entry.force_snapshot || !prog_entry.inflight.is_none() {
+            return;
+        }
+
+        let searching_end = self.state.last_log_id().next_index();
+
+        self.leader.progress.reset_with(target, |prog_entry| {
+            *prog_entry = ProgressEntry::empty(searching_end);
+            prog_entry.force_snapshot = true;
+        });
+    }
+
+    /// Whether a resync is waiting for a snapshot to be built.
+    pub(crate) fn has_pending_resync(&self) -> bool {
+        self.leader
+            .progress
+            .iter()
+            .any(|(_, prog_entry)| prog_entry.force_snapshot && prog_entry.inflight.is_none())
+    }
+
     /// Initiate replication for every target that is not sending data in flight.
     ///
     /// `send_none` specifies whether to force to send a message even when there is no data to send.
@@ -310,7 +427,9 @@ where C: RaftTypeConfig
                 continue;
             }
 
-            let t = prog_entry.next_send(self.state, self.config.max_payload_entries);
+            let max_entries = prog_entry
+                .max_entries_to_send(self.config.max_payload_entries, self.config.max_append_entries_inflight);
+            let t = prog_entry.next_send(self.state, max_entries);
             tracing::debug!(target = display(&*id), send = debug(&t), "next send");
 
             match t {
@@ -337,6 +456,37 @@ where C: RaftTypeConfig
         });
     }
 
+    /// Schedule purging the logs according to the purge policy, keeping the logs the lagging
+    /// members need.
+    pub(crate) fn schedule_policy_based_purge(&mut self) {
+        let keep_from = self.lagging_keep_from();
+        self.log_handler().schedule_policy_based_purge(keep_from);
+    }
+
+    /// Return the index of the first log to keep for the lagging members, if the purge policy is
+    /// [`PurgePolicy::KeepForLagging`].
+    ///
+    /// It is `margin` logs before the first log the most lagging member needs. Members lagging more
+    /// than `max_lag` logs are ignored.
+    pub(crate) fn lagging_keep_from(&self) -> Option<u64> {
+        let PurgePolicy::KeepForLagging { margin, max_lag } = self.config.purge_policy else {
+            return None;
+        };
+
+        let last_next = self.state.last_log_id().next_index();
+
+        let min_next = self
+            .leader
+            .progress
+            .iter()
+            .filter(|(id, _)| id != &self.config.id)
+            .map(|(_, p)| p.matching().next_index())
+            .filter(|next| last_next.saturating_sub(*next) <= max_lag)
+            .min()?;
+
+        Some(min_next.saturating_sub(margin))
+    }
+
     /// Try to run a pending purge job if no tasks are using the logs to be purged.
     ///
     /// Purging logs involves concurrent log accesses by replication tasks and purging tasks.
@@ -352,6 +502,11 @@ where C: RaftTypeConfig
             "try_purge_log"
         );
 
+        // The logs the lagging members need change as they make progress.
+        if matches!(self.config.purge_policy, PurgePolicy::KeepForLagging { .. }) {
+            self.schedule_policy_based_purge();
+        }
+
         if self.state.purge_upto() <= self.state.last_purged_log_id() {
             tracing::debug!("no need to purge, return");
             return;
@@ -406,7 +561,22 @@ where C: RaftTypeConfig
             // TODO: It should be self.state.last_log_id() but None is ok.
             prog_entry.inflight = Inflight::logs(None, upto.clone());
 
-            self.update_matching(id, upto);
+            if self.config.broadcast_commit_on_local_flush {
+                self.update_matching(id, upto);
+            } else {
+                // Commit at once, but followers learn the new committed log id with the next
+                // AppendEntries or heartbeat.
+                let quorum_accepted = self
+                    .leader
+                    .progress
+                    .update_with(&id, |prog_entry| {
+                        prog_entry.new_updater(&*self.config).update_matching(upto)
+                    })
+                    .expect("it should always update existing progress")
+                    .clone();
+
+                self.commit_quorum_accepted(quorum_accepted);
+            }
         }
     }
 
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::ReplicationProgress;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::log_id_range::LogIdRange;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::replication::request::Replicate;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+
+fn m123() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.log_ids.append(log_id(2, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m123())),
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m123())),
+    );
+
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    for id in [2, 3] {
+        let prog_entry = eng.leader.as_mut().unwrap().progress.get_mut(&id).unwrap();
+        prog_entry.matching = Some(log_id(2, 1, 1));
+        prog_entry.inflight = Inflight::logs(Some(log_id(2, 1, 1)), Some(log_id(2, 1, 3)));
+    }
+
+    eng
+}
+
+#[test]
+fn test_restart_replication_stream() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    let mut want_progress = eng.leader.as_ref().unwrap().progress.get(&2).clone();
+    want_progress.inflight = Inflight::None;
+
+    eng.replication_handler().restart_replication_stream(2);
+
+    assert_eq!(
+        vec![
+            Command::RestartReplicationStream {
+                target: ReplicationProgress(2, want_progress),
+            },
+            Command::Replicate {
+                target: 2,
+                req: Replicate::logs(LogIdRange::new(Some(log_id(2, 1, 1)), Some(log_id(2, 1, 3)))),
+            },
+        ],
+        eng.output.take_commands()
+    );
+
+    // The inflight data to other targets is untouched.
+    assert_eq!(
+        &Inflight::logs(Some(log_id(2, 1, 1)), Some(log_id(2, 1, 3))),
+        &eng.leader.as_ref().unwrap().progress.get(&3).inflight
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_restart_replication_stream_to_self_or_unknown() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().restart_replication_stream(1);
+    eng.replication_handler().restart_replication_stream(5);
+
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::core::sm;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::error::NodeNotFound;
+use crate::error::Operation;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::progress::entry::ProgressEntry;
+use crate::replication::request::Replicate;
+use crate::replication::response::ReplicationResult;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+
+fn m123() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.log_ids.append(log_id(2, 1, 1));
+    eng.state.log_ids.append(log_id(2, 1, 3));
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m123())),
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m123())),
+    );
+
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    for id in [2, 3] {
+        let prog_entry = eng.leader.as_mut().unwrap().progress.get_mut(&id).unwrap();
+        prog_entry.matching = Some(log_id(2, 1, 1));
+        prog_entry.inflight = Inflight::logs(Some(log_id(2, 1, 1)), Some(log_id(2, 1, 3)));
+    }
+
+    eng
+}
+
+fn want_progress() -> ProgressEntry<UTConfig> {
+    let mut p = ProgressEntry::empty(4);
+    p.force_snapshot = true;
+    p
+}
+
+#[test]
+fn test_resync_with_snapshot() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.snapshot_meta.last_log_id = Some(log_id(2, 1, 1));
+    eng.leader.as_mut().unwrap().progress.get_mut(&2).unwrap().inflight = Inflight::None;
+
+    eng.replication_handler().resync(2)?;
+
+    assert_eq!(
+        vec![Command::Replicate {
+            target: 2,
+            req: Replicate::snapshot(Some(log_id(2, 1, 1))),
+        }],
+        eng.output.take_commands()
+    );
+
+    let mut want = want_progress();
+    want.inflight = Inflight::snapshot(Some(log_id(2, 1, 1)));
+    assert_eq!(&want, eng.leader.as_ref().unwrap().progress.get(&2));
+
+    // The progress of other targets is untouched.
+    assert_eq!(
+        Some(&log_id(2, 1, 1)),
+        eng.leader.as_ref().unwrap().progress.get(&3).matching()
+    );
+
+    tracing::info!("--- the flag is cleared once the snapshot is acknowledged");
+    {
+        eng.replication_handler().update_matching(2, Some(log_id(2, 1, 1)));
+        assert!(!eng.leader.as_ref().unwrap().progress.get(&2).force_snapshot);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_with_inflight_data() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.state.snapshot_meta.last_log_id = Some(log_id(2, 1, 1));
+
+    eng.replication_handler().resync(2)?;
+
+    assert_eq!(0, eng.output.take_commands().len(), "wait for the inflight data");
+    assert_eq!(
+        Some(&log_id(2, 1, 1)),
+        eng.leader.as_ref().unwrap().progress.get(&2).matching()
+    );
+
+    tracing::info!("--- the progress is reset once the inflight data is acknowledged");
+    {
+        eng.replication_handler().update_progress(2, Ok(ReplicationResult(Ok(Some(log_id(2, 1, 3))))), true);
+        assert_eq!(&want_progress(), eng.leader.as_ref().unwrap().progress.get(&2));
+
+        eng.replication_handler().initiate_replication();
+        assert_eq!(
+            vec![Command::Replicate {
+                target: 2,
+                req: Replicate::snapshot(Some(log_id(2, 1, 1))),
+            }],
+            eng.output.take_commands()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_without_snapshot() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.leader.as_mut().unwrap().progress.get_mut(&2).unwrap().inflight = Inflight::None;
+
+    eng.replication_handler().resync(2)?;
+
+    assert_eq!(
+        vec![Command::from(sm::Command::build_snapshot())],
+        eng.output.take_commands()
+    );
+    assert_eq!(&want_progress(), eng.leader.as_ref().unwrap().progress.get(&2));
+    assert!(eng.replication_handler().has_pending_resync());
+
+    tracing::info!("--- the snapshot is sent once built");
+    {
+        eng.state.snapshot_meta.last_log_id = Some(log_id(2, 1, 3));
+        eng.replication_handler().initiate_replication();
+
+        assert_eq!(
+            vec![Command::Replicate {
+                target: 2,
+                req: Replicate::snapshot(Some(log_id(2, 1, 3))),
+            }],
+            eng.output.take_commands()
+        );
+        assert!(!eng.replication_handler().has_pending_resync());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_resync_self_or_unknown() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.replication_handler().resync(1)?;
+    assert_eq!(0, eng.output.take_commands().len());
+
+    let res = eng.replication_handler().resync(5);
+    assert_eq!(Err(NodeNotFound::new(5, Operation::Resync)), res);
+    assert_eq!(0, eng.output.take_commands().len());
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::progress::Progress;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+
+fn m123() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m123())),
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 3)), m123())),
+    );
+
+    eng
+}
+
+#[test]
+fn test_update_leader_clock() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    let now = UTConfig::<()>::now();
+    let mut rh = eng.replication_handler();
+
+    rh.update_leader_clock(2, now);
+    assert_eq!(Some(&Some(now)), rh.leader.clock_progress.try_get(&2));
+
+    Ok(())
+}
+
+#[test]
+fn test_update_leader_clock_removed_target() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.testing_new_leader();
+
+    let mut rh = eng.replication_handler();
+
+    // A heartbeat response from node-4, which is not in the membership, is ignored.
+    rh.update_leader_clock(4, UTConfig::<()>::now());
+    assert_eq!(None, rh.leader.clock_progress.try_get(&4));
+
+    Ok(())
+}
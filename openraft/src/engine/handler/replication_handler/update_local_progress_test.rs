@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::EffectiveMembership;
+use crate::Membership;
+use crate::MembershipState;
+use crate::Vote;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::progress::Inflight;
+use crate::progress::Progress;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+
+fn m1() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1}], [])
+}
+
+fn m123() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}], [])
+}
+
+fn eng(m: Membership<UTConfig>) -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.config.id = 1;
+    eng.state.vote = Leased::new(
+        UTConfig::<()>::now(),
+        Duration::from_millis(500),
+        Vote::new_committed(2, 1),
+    );
+    eng.state.membership_state = MembershipState::new(
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m.clone())),
+        Arc::new(EffectiveMembership::new(Some(log_id(2, 1, 1)), m)),
+    );
+
+    eng.testing_new_leader();
+    eng.output.take_commands();
+
+    for id in [2, 3] {
+        if let Some(prog_entry) = eng.leader.as_mut().unwrap().progress.get_mut(&id) {
+            prog_entry.inflight = Inflight::logs(None, Some(log_id(2, 1, 3)));
+        }
+    }
+
+    eng
+}
+
+#[test]
+fn test_update_local_progress_commit_at_once() -> anyhow::Result<()> {
+    for broadcast in [true, false] {
+        let mut eng = eng(m123());
+        eng.config.broadcast_commit_on_local_flush = broadcast;
+        let mut rh = eng.replication_handler();
+
+        // A follower acknowledged before the leader flushed; no quorum yet.
+        rh.update_matching(2, Some(log_id(2, 1, 3)));
+        assert_eq!(None, rh.state.committed());
+        assert_eq!(0, rh.output.take_commands().len());
+
+        // The leader's flush forms a quorum with the acknowledgement: it commits without waiting
+        // for another replication response, whether or not the commit is broadcast.
+        rh.update_local_progress(Some(log_id(2, 1, 3)));
+        assert_eq!(Some(&log_id(2, 1, 3)), rh.leader.progress.get(&1).matching());
+        assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+        let want = if broadcast {
+            vec![Command::ReplicateCommitted {
+                committed: Some(log_id(2, 1, 3)),
+            }]
+        } else {
+            // Followers learn the committed log id with the next AppendEntries or heartbeat.
+            vec![]
+        };
+        assert_eq!(want, rh.output.take_commands(), "broadcast: {}", broadcast);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_update_local_progress_only_voter() -> anyhow::Result<()> {
+    let mut eng = eng(m1());
+    eng.config.broadcast_commit_on_local_flush = false;
+    let mut rh = eng.replication_handler();
+
+    // No replication response will come, the leader commits at once.
+    rh.update_local_progress(Some(log_id(2, 1, 3)));
+    assert_eq!(Some(&log_id(2, 1, 3)), rh.state.committed());
+
+    Ok(())
+}
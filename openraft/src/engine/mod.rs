@@ -44,9 +44,11 @@ pub(crate) mod time_state;
 #[cfg(test)]
 mod tests {
     mod append_entries_test;
+    mod check_invariants_test;
     mod elect_test;
     mod handle_vote_req_test;
     mod handle_vote_resp_test;
+    mod initialize_from_snapshot_test;
     mod initialize_test;
     mod install_full_snapshot_test;
     mod log_id_list_test;
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use crate::Vote;
+use crate::engine::Engine;
+use crate::engine::LogIdList;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::type_config::TypeConfigExt;
+use crate::utime::Leased;
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::testing_default(0);
+    eng.state.enable_validation(false);
+    eng.config.id = 2;
+    eng.state.vote = Leased::new(UTConfig::<()>::now(), Duration::from_millis(500), Vote::new(2, 2));
+    eng.state.log_ids = LogIdList::new([log_id(0, 0, 0), log_id(2, 1, 3)]);
+    eng
+}
+
+#[test]
+fn test_check_invariants_ok() -> anyhow::Result<()> {
+    let mut eng = eng();
+
+    eng.check_invariants(&Vote::new(1, 1), "input");
+    eng.check_invariants(&Vote::new(2, 2), "input");
+
+    // Validation on access is restored.
+    assert!(!eng.state.is_enabled());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "RaftState invariant violated after input: decrease-vote")]
+fn test_check_invariants_vote_decreased() {
+    let mut eng = eng();
+
+    eng.check_invariants(&Vote::new_committed(2, 2), "decrease-vote");
+}
+
+#[test]
+#[should_panic(expected = "RaftState invariant violated after input: commit-beyond-last-log")]
+fn test_check_invariants_committed_greater_than_last_log() {
+    let mut eng = eng();
+    eng.state.apply_progress_mut().accept(log_id(2, 1, 5));
+
+    eng.check_invariants(&Vote::new(2, 2), "commit-beyond-last-log");
+}
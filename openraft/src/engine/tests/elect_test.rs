@@ -59,6 +59,7 @@ fn test_elect_single_node() -> anyhow::Result<()> {
                     vote_req: VoteRequest {
                         vote: Vote::new(1, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
+                        cluster_name: None,
                     },
                 },
             ],
@@ -105,6 +106,7 @@ fn test_elect_single_node_elect_again() -> anyhow::Result<()> {
                     vote_req: VoteRequest {
                         vote: Vote::new(2, 1),
                         last_log_id: Some(log_id(0, 0, 0)),
+                        cluster_name: None,
                     },
                 },
             ],
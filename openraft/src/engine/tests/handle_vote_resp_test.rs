@@ -5,6 +5,7 @@ use std::time::Duration;
 use maplit::btreeset;
 use pretty_assertions::assert_eq;
 
+use crate::BlankEntryPolicy;
 use crate::EffectiveMembership;
 use crate::Entry;
 use crate::Membership;
@@ -23,6 +24,7 @@ use crate::raft::VoteResponse;
 use crate::raft_state::IOId;
 use crate::replication::request::Replicate;
 use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
 use crate::utime::Leased;
 use crate::vote::raft_vote::RaftVoteExt;
 
@@ -227,3 +229,78 @@ fn test_handle_vote_resp_equal_vote() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_handle_vote_resp_blank_entry_policy() -> anyhow::Result<()> {
+    let become_leader = |policy: BlankEntryPolicy, committed: Option<LogIdOf<UTConfig>>| {
+        let mut eng = eng();
+        eng.config.id = 1;
+        eng.config.blank_entry_policy = policy;
+        eng.state.vote = Leased::new(UTConfig::<()>::now(), Duration::from_millis(500), Vote::new(2, 1));
+        eng.state
+            .membership_state
+            .set_effective(Arc::new(EffectiveMembership::new(Some(log_id(1, 1, 1)), m12())));
+        if let Some(committed) = committed {
+            eng.state.apply_progress_mut().accept(committed);
+        }
+
+        let voting = eng.new_candidate(*eng.state.vote_ref());
+        voting.grant_by(&1);
+        eng.state.server_state = ServerState::Candidate;
+
+        eng.handle_vote_resp(2, VoteResponse::new(Vote::new(2, 1), Some(log_id(2, 1, 2)), true));
+        assert_eq!(ServerState::Leader, eng.state.server_state);
+        eng
+    };
+
+    let no_blank_log = vec![
+        Command::RebuildReplicationStreams {
+            targets: vec![ReplicationProgress(2, ProgressEntry::empty(1))],
+        },
+        Command::SaveVote {
+            vote: Vote::new_committed(2, 1),
+        },
+        Command::UpdateIOProgress {
+            when: None,
+            io_id: IOId::new_log_io(Vote::new(2, 1).into_committed(), Some(log_id(0, 0, 0))),
+        },
+        Command::Replicate {
+            target: 2,
+            req: Replicate::logs(LogIdRange::new(None, Some(log_id(0, 0, 0)))),
+        },
+    ];
+
+    tracing::info!("--- if-uncommitted: all logs are committed, no blank log");
+    {
+        let mut eng = become_leader(BlankEntryPolicy::IfUncommitted, Some(log_id(0, 0, 0)));
+
+        assert_eq!(
+            Some(log_id(0, 0, 0)),
+            eng.leader.as_ref().unwrap().last_log_id().copied()
+        );
+        assert_eq!(no_blank_log, eng.output.take_commands());
+    }
+
+    tracing::info!("--- if-uncommitted: logs are not committed, append a blank log");
+    {
+        let eng = become_leader(BlankEntryPolicy::IfUncommitted, None);
+
+        assert_eq!(
+            Some(log_id(2, 1, 1)),
+            eng.leader.as_ref().unwrap().last_log_id().copied()
+        );
+    }
+
+    tracing::info!("--- never: logs are not committed, no blank log");
+    {
+        let mut eng = become_leader(BlankEntryPolicy::Never, None);
+
+        assert_eq!(
+            Some(log_id(0, 0, 0)),
+            eng.leader.as_ref().unwrap().last_log_id().copied()
+        );
+        assert_eq!(no_blank_log, eng.output.take_commands());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::io::Cursor;
+
+use maplit::btreeset;
+use pretty_assertions::assert_eq;
+
+use crate::Membership;
+use crate::StoredMembership;
+use crate::Vote;
+use crate::core::ServerState;
+use crate::engine::Command;
+use crate::engine::Condition;
+use crate::engine::Engine;
+use crate::engine::testing::UTConfig;
+use crate::engine::testing::log_id;
+use crate::error::InitializeError;
+use crate::error::NotInMembers;
+use crate::raft::VoteRequest;
+use crate::raft_state::LogStateReader;
+use crate::storage::Snapshot;
+use crate::storage::SnapshotMeta;
+
+fn m12() -> Membership<UTConfig> {
+    Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2}], [])
+}
+
+fn eng() -> Engine<UTConfig> {
+    let mut eng = Engine::<UTConfig>::testing_default(0);
+    eng.state.enable_validation(false); // Disable validation for incomplete state
+
+    eng.state.server_state = eng.calc_server_state();
+    eng
+}
+
+/// A snapshot of another cluster, with its own log ids.
+fn snapshot() -> Snapshot<UTConfig> {
+    Snapshot {
+        meta: SnapshotMeta {
+            last_log_id: Some(log_id(5, 7, 100)),
+            last_membership: StoredMembership::new(Some(log_id(3, 7, 50)), m12()),
+            snapshot_id: "5-100".to_string(),
+        },
+        snapshot: Cursor::new(vec![0u8]),
+    }
+}
+
+#[test]
+fn test_initialize_from_snapshot() -> anyhow::Result<()> {
+    let log_id0 = log_id(0, 0, 0);
+
+    let mut eng = eng();
+    eng.config.id = 1;
+
+    let cond = eng.initialize_from_snapshot(snapshot())?;
+
+    assert_eq!(Some(Condition::Snapshot { log_id: log_id0 }), cond);
+
+    assert_eq!(
+        SnapshotMeta {
+            last_log_id: Some(log_id0),
+            last_membership: StoredMembership::new(Some(log_id0), m12()),
+            snapshot_id: "5-100".to_string(),
+        },
+        eng.state.snapshot_meta
+    );
+    assert_eq!(Some(&log_id0), eng.state.last_log_id());
+    assert_eq!(Some(&log_id0), eng.state.purge_upto());
+
+    assert_eq!(ServerState::Candidate, eng.state.server_state);
+    assert_eq!(&m12(), eng.state.membership_state.effective().membership());
+
+    let last = eng.output.take_commands().pop();
+    assert_eq!(
+        Some(Command::SendVote {
+            vote_req: VoteRequest::new(Vote::new(1, 1), Some(log_id0))
+        }),
+        last
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_initialize_from_snapshot_not_in_members() -> anyhow::Result<()> {
+    let mut eng = eng();
+    eng.config.id = 3;
+
+    assert_eq!(
+        Err(InitializeError::NotInMembers(NotInMembers {
+            node_id: 3,
+            membership: m12()
+        })),
+        eng.initialize_from_snapshot(snapshot())
+    );
+
+    Ok(())
+}
@@ -116,7 +116,8 @@ fn test_initialize() -> anyhow::Result<()> {
                 Command::SendVote {
                     vote_req: VoteRequest {
                         vote: Vote::new(1, 1),
-                        last_log_id: Some(log_id(0, 0, 0))
+                        last_log_id: Some(log_id(0, 0, 0)),
+                        cluster_name: None,
                     },
                 },
             ],
@@ -80,6 +80,9 @@ fn test_startup_as_leader_without_logs() -> anyhow::Result<()> {
                     inflight: Inflight::None,
                     searching_end: 4,
                     allow_log_reversion: false,
+                    force_snapshot: false,
+                    reversions: 0,
+                    quarantined: false,
                 })]
             },
             Command::AppendEntries {
@@ -132,6 +135,9 @@ fn test_startup_as_leader_with_proposed_logs() -> anyhow::Result<()> {
                     inflight: Inflight::None,
                     searching_end: 7,
                     allow_log_reversion: false,
+                    force_snapshot: false,
+                    reversions: 0,
+                    quarantined: false,
                 })]
             },
             Command::Replicate {
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 #[derive(Clone, Debug)]
 #[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub(crate) struct Config {
     /// The time interval after which the next election will be initiated once the current lease has
     /// expired.
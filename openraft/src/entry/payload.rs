@@ -19,6 +19,13 @@ pub enum EntryPayload<C: RaftTypeConfig> {
 
     /// A change-membership log entry.
     Membership(Membership<C>),
+
+    /// Bump the version of the application apply logic to the given version.
+    ///
+    /// It is proposed with [`Raft::bump_version()`](crate::Raft::bump_version) once every member
+    /// supports the version. It is a barrier for a rolling upgrade: the state machine applies the
+    /// entries before it with the old logic and the entries after it with the new one.
+    VersionBump(u64),
 }
 
 impl<C> Clone for EntryPayload<C>
@@ -31,6 +38,7 @@ where
             EntryPayload::Blank => EntryPayload::Blank,
             EntryPayload::Normal(n) => EntryPayload::Normal(n.clone()),
             EntryPayload::Membership(m) => EntryPayload::Membership(m.clone()),
+            EntryPayload::VersionBump(v) => EntryPayload::VersionBump(*v),
         }
     }
 }
@@ -45,6 +53,7 @@ where C: RaftTypeConfig
             EntryPayload::Membership(c) => {
                 write!(f, "membership:{:?}", c)?;
             }
+            EntryPayload::VersionBump(v) => write!(f, "version-bump:{}", v)?,
         }
 
         Ok(())
@@ -61,6 +70,7 @@ where C: RaftTypeConfig
             EntryPayload::Membership(c) => {
                 write!(f, "membership:{}", c)?;
             }
+            EntryPayload::VersionBump(v) => write!(f, "version-bump:{}", v)?,
         }
 
         Ok(())
@@ -75,6 +85,7 @@ where C: RaftTypeConfig
             EntryPayload::Blank => "Blank",
             EntryPayload::Normal(_) => "Normal",
             EntryPayload::Membership(_) => "Membership",
+            EntryPayload::VersionBump(_) => "VersionBump",
         }
     }
 }
@@ -114,6 +125,9 @@ mod tests {
             format!("{:?}", membership),
             "membership:Membership { configs: [{1, 2}], nodes: {1: (), 2: ()} }"
         );
+
+        let bump = EntryPayload::<UTConfig>::VersionBump(2);
+        assert_eq!(format!("{:?}", bump), "version-bump:2");
     }
 
     #[test]
@@ -132,5 +146,8 @@ mod tests {
             format!("{}", membership),
             "membership:{voters:[{1:(),2:()}], learners:[]}"
         );
+
+        let bump = EntryPayload::<UTConfig>::VersionBump(2);
+        assert_eq!(format!("{}", bump), "version-bump:2");
     }
 }
@@ -13,6 +13,11 @@ use crate::type_config::alias::CommittedLeaderIdOf;
 use crate::type_config::alias::LogIdOf;
 
 /// Defines operations on an entry.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as the `Entry` of `{C}`",
+    label = "`RaftEntry<{C}>` is not implemented for `{Self}`",
+    note = "the `Entry` must be built for the type config that declares it, e.g., `Entry = openraft::impls::Entry<Self>`, which is the default of `declare_raft_types!`"
+)]
 pub trait RaftEntry<C>
 where
     C: RaftTypeConfig,
@@ -1,15 +1,26 @@
 //! Error types exposed by this crate.
 
 mod allow_next_revert_error;
+mod cluster_mismatch;
+mod decommission_rejected;
 pub mod decompose;
+mod entry_too_large;
 pub mod into_ok;
 pub(crate) mod into_raft_result;
 mod invalid_sm;
+mod leader_changed;
 mod membership_error;
 mod node_not_found;
+mod non_member_rejected;
 mod operation;
+mod overloaded;
+mod read_only;
 mod replication_closed;
+mod resync_error;
+mod storage_stamp_mismatch;
 mod streaming_error;
+mod task_panicked;
+mod version_unsupported;
 
 use std::collections::BTreeSet;
 use std::error::Error;
@@ -21,12 +32,23 @@ use anyerror::AnyError;
 use openraft_macros::since;
 
 pub use self::allow_next_revert_error::AllowNextRevertError;
+pub use self::cluster_mismatch::ClusterMismatch;
+pub use self::decommission_rejected::DecommissionRejected;
+pub use self::entry_too_large::EntryTooLarge;
 pub use self::invalid_sm::InvalidStateMachineType;
+pub use self::leader_changed::LeaderChanged;
 pub use self::membership_error::MembershipError;
 pub use self::node_not_found::NodeNotFound;
+pub use self::non_member_rejected::NonMemberRejected;
 pub use self::operation::Operation;
+pub use self::overloaded::Overloaded;
+pub use self::read_only::ReadOnly;
 pub use self::replication_closed::ReplicationClosed;
+pub use self::resync_error::ResyncError;
+pub use self::storage_stamp_mismatch::StorageStampMismatch;
 pub use self::streaming_error::StreamingError;
+pub use self::task_panicked::TaskPanicked;
+pub use self::version_unsupported::VersionUnsupported;
 use crate::Membership;
 use crate::RaftTypeConfig;
 use crate::StorageError;
@@ -163,12 +185,17 @@ where C: RaftTypeConfig
 ///
 /// When a `Fatal` error occurs, the Raft node stops processing requests and enters a stopped state.
 /// Applications should monitor for fatal errors and initiate graceful shutdown when detected.
+/// The only exceptions are `ClusterMismatch` and `NonMemberRejected`, which reject a single RPC.
 ///
 /// # Variants
 ///
 /// - `StorageError`: Underlying storage (log or state machine) encountered an error
 /// - `Panicked`: Raft core task panicked due to a programming error
+/// - `TaskPanicked`: An internal task, such as a replication stream, panicked
 /// - `Stopped`: Raft was explicitly shut down via [`Raft::shutdown`]
+/// - `StorageStampMismatch`: The storage belongs to another node or cluster
+/// - `ClusterMismatch`: An RPC from another cluster is rejected; this node keeps running
+/// - `NonMemberRejected`: An RPC from a non-member is rejected; this node keeps running
 ///
 /// [`Raft::shutdown`]: crate::Raft::shutdown
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
@@ -184,9 +211,30 @@ where C: RaftTypeConfig
     #[error("panicked")]
     Panicked,
 
+    /// An internal task spawned by Raft panicked, and Raft stopped.
+    #[error(transparent)]
+    TaskPanicked(#[from] TaskPanicked),
+
     /// Raft stopped normally.
     #[error("raft stopped")]
     Stopped,
+
+    /// The storage was stamped by another node or cluster, and Raft refused to start.
+    #[error(transparent)]
+    StorageStampMismatch(#[from] StorageStampMismatch<C>),
+
+    /// An RPC from another cluster is rejected.
+    ///
+    /// Unlike other variants, this node does not stop: it is returned to the sender, which treats
+    /// this node as unreachable.
+    #[error(transparent)]
+    ClusterMismatch(#[from] ClusterMismatch),
+
+    /// An RPC from a node that is not a member is rejected.
+    ///
+    /// Like `ClusterMismatch`, this node does not stop.
+    #[error(transparent)]
+    NonMemberRejected(#[from] NonMemberRejected<C>),
 }
 
 /// Error related to installing a snapshot.
@@ -240,6 +288,32 @@ where C: RaftTypeConfig
     /// When writing a change-membership entry.
     #[error(transparent)]
     ChangeMembershipError(#[from] ChangeMembershipError<C>),
+
+    /// Too many client writes are queued on this node; the write is rejected without being
+    /// proposed.
+    #[error(transparent)]
+    Overloaded(#[from] Overloaded),
+
+    /// The log entry of the write is larger than [`Config::max_entry_size`]; the write is rejected
+    /// without being proposed.
+    ///
+    /// [`Config::max_entry_size`]: crate::Config::max_entry_size
+    #[error(transparent)]
+    EntryTooLarge(#[from] EntryTooLarge),
+
+    /// This node is in read-only mode for maintenance; the write is rejected without being
+    /// proposed.
+    #[error(transparent)]
+    ReadOnly(#[from] ReadOnly<C>),
+
+    /// A member does not support the version of a version bump; the version bump is rejected
+    /// without being proposed.
+    #[error(transparent)]
+    VersionUnsupported(#[from] VersionUnsupported<C>),
+
+    /// The node stepped down before the write is committed; the outcome of the write is unknown.
+    #[error(transparent)]
+    LeaderChanged(#[from] LeaderChanged<C>),
 }
 
 impl<C> TryAsRef<ForwardToLeader<C>> for ClientWriteError<C>
@@ -548,6 +622,21 @@ impl fmt::Display for PayloadTooLarge {
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader rpc should not have payload")
             }
+            RPCTypes::Decommission => {
+                unreachable!("Decommission rpc should not have payload")
+            }
+            RPCTypes::StateMachineChecksum => {
+                unreachable!("StateMachineChecksum rpc should not have payload")
+            }
+            RPCTypes::RepairLog => {
+                unreachable!("RepairLog rpc should not have payload")
+            }
+            RPCTypes::ApplyResults => {
+                write!(f, "entries:{}", self.entries_hint)?;
+            }
+            RPCTypes::Hello => {
+                unreachable!("Hello rpc should not have payload")
+            }
         }
         write!(f, ")")?;
 
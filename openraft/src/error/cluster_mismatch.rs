@@ -0,0 +1,25 @@
+/// An RPC is sent from a node of another cluster.
+///
+/// It is returned by the RPC handlers of [`Raft`](crate::Raft) when the
+/// [`Config::cluster_name`](crate::Config::cluster_name) of this node differs from the cluster name
+/// carried in the request. The request is not handled and this node keeps running.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("reject RPC from cluster {got}, this node is in cluster {expected}")]
+pub struct ClusterMismatch {
+    /// The cluster name of this node.
+    pub expected: String,
+
+    /// The cluster name carried in the request.
+    pub got: String,
+}
+
+impl ClusterMismatch {
+    /// Create a new ClusterMismatch error.
+    pub fn new(expected: impl ToString, got: impl ToString) -> Self {
+        Self {
+            expected: expected.to_string(),
+            got: got.to_string(),
+        }
+    }
+}
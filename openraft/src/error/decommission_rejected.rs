@@ -0,0 +1,26 @@
+use crate::RaftTypeConfig;
+use crate::type_config::alias::VoteOf;
+
+/// A decommission request is rejected and this node keeps running.
+///
+/// It is returned by [`Raft::handle_decommission()`](crate::Raft::handle_decommission) when the
+/// request is addressed to another node, or is sent by a leader whose vote is smaller than the
+/// vote of this node, e.g., a stale leader that has not yet seen a newer term.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("decommission of node {target} by leader {from_leader} is rejected by node {node_id} with vote {vote}")]
+pub struct DecommissionRejected<C>
+where C: RaftTypeConfig
+{
+    /// The node this node is.
+    pub node_id: C::NodeId,
+
+    /// The vote of this node.
+    pub vote: VoteOf<C>,
+
+    /// The node the request asks to shut down.
+    pub target: C::NodeId,
+
+    /// The vote of the leader that sent the request.
+    pub from_leader: VoteOf<C>,
+}
@@ -0,0 +1,24 @@
+/// A client write is rejected because its log entry is larger than [`Config::max_entry_size`].
+///
+/// The size is reported by [`RaftLogReader::entry_size()`]. The write is not proposed, and
+/// retrying it does not help.
+///
+/// [`Config::max_entry_size`]: crate::Config::max_entry_size
+/// [`RaftLogReader::entry_size()`]: crate::storage::RaftLogReader::entry_size
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("entry too large: {size} bytes, max: {max} bytes")]
+pub struct EntryTooLarge {
+    /// The size of the entry in bytes.
+    pub size: u64,
+
+    /// The max size of an entry in bytes.
+    pub max: u64,
+}
+
+impl EntryTooLarge {
+    /// Create a new EntryTooLarge error.
+    pub fn new(size: u64, max: u64) -> Self {
+        Self { size, max }
+    }
+}
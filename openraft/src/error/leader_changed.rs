@@ -0,0 +1,53 @@
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOptionExt;
+use crate::error::ForwardToLeader;
+use crate::type_config::alias::LogIdOf;
+
+/// A client write is not yet committed when the node that proposed it is no longer the leader.
+///
+/// It is returned only with [`PendingWritePolicy::Fail`]. The outcome of the write is unknown: it
+/// may still be committed by the new leader, or be discarded.
+///
+/// `persisted` tells whether the entry is flushed to the local log store of this node. If it is
+/// not, the entry is less likely to be committed, but it may have been replicated to a follower
+/// nevertheless. A client should only retry the write on the new leader, see
+/// [`forward_to_leader()`](Self::forward_to_leader), if the write is idempotent or de-duplicated
+/// by the application.
+///
+/// [`PendingWritePolicy::Fail`]: crate::config::PendingWritePolicy::Fail
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error(
+    "leader changed before log {log_id} is committed, persisted locally: {persisted}, new leader: {}",
+    self.forward.leader_id.display()
+)]
+pub struct LeaderChanged<C>
+where C: RaftTypeConfig
+{
+    /// The log id of the entry of the write.
+    pub log_id: LogIdOf<C>,
+
+    /// Whether the entry is flushed to the local log store.
+    pub persisted: bool,
+
+    /// The new leader, if it is known.
+    pub forward: ForwardToLeader<C>,
+}
+
+impl<C> LeaderChanged<C>
+where C: RaftTypeConfig
+{
+    /// Create a new LeaderChanged error.
+    pub fn new(log_id: LogIdOf<C>, persisted: bool, forward: ForwardToLeader<C>) -> Self {
+        Self {
+            log_id,
+            persisted,
+            forward,
+        }
+    }
+
+    /// The new leader to retry the write on, if it is known.
+    pub fn forward_to_leader(&self) -> &ForwardToLeader<C> {
+        &self.forward
+    }
+}
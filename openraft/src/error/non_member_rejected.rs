@@ -0,0 +1,25 @@
+use crate::RaftTypeConfig;
+
+/// An RPC is sent from a node that is not in the membership of this node.
+///
+/// It is returned by the RPC handlers of [`Raft`](crate::Raft) according to
+/// [`Config::accept_rpc_from_non_members`](crate::Config::accept_rpc_from_non_members). The
+/// request is not handled and this node keeps running.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("reject RPC from node {node_id}, which is not a member")]
+pub struct NonMemberRejected<C>
+where C: RaftTypeConfig
+{
+    /// The node that sent the RPC.
+    pub node_id: C::NodeId,
+}
+
+impl<C> NonMemberRejected<C>
+where C: RaftTypeConfig
+{
+    /// Create a new NonMemberRejected error.
+    pub fn new(node_id: C::NodeId) -> Self {
+        Self { node_id }
+    }
+}
@@ -14,6 +14,9 @@ pub enum Operation {
     /// Set a flag to allow a target replication state to revert to a previous state for one time.
     AllowNextRevert,
 
+    /// Resync a follower or learner with a full snapshot.
+    Resync,
+
     /// Transfer leadership to the specified node.
     TransferLeader,
 
@@ -41,6 +44,7 @@ impl fmt::Display for Operation {
         match self {
             Operation::None => write!(f, "(unknown operation)"),
             Operation::AllowNextRevert => write!(f, "set flag to allow replication revert for once"),
+            Operation::Resync => write!(f, "resync with a snapshot"),
             Operation::TransferLeader => write!(f, "transfer leadership"),
             Operation::SendHeartbeat => write!(f, "send heartbeat"),
             Operation::ReceiveSnapshot => write!(f, "receive snapshot"),
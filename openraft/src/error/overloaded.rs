@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// A client write is rejected because too many client writes are already queued for `RaftCore`.
+///
+/// It is returned only when [`Config::max_queued_client_writes`] is set. The write is not
+/// proposed, and the client may retry it, after `retry_after`, on the same node.
+///
+/// [`Config::max_queued_client_writes`]: crate::Config::max_queued_client_writes
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("overloaded: {queued} client writes are queued; retry after {retry_after:?}")]
+pub struct Overloaded {
+    /// The number of client writes that are queued when this write is rejected.
+    pub queued: u64,
+
+    /// How long the client is suggested to wait before retrying.
+    pub retry_after: Duration,
+}
+
+impl Overloaded {
+    /// Create a new Overloaded error.
+    pub fn new(queued: u64, retry_after: Duration) -> Self {
+        Self { queued, retry_after }
+    }
+}
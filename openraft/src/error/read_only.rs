@@ -0,0 +1,20 @@
+use crate::RaftTypeConfig;
+
+/// A client write is rejected because the node is in read-only mode for maintenance.
+///
+/// The write is not proposed. The client should send it to another node. See
+/// [`Raft::enter_readonly()`](crate::Raft::enter_readonly).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} is read-only for maintenance")]
+pub struct ReadOnly<C: RaftTypeConfig> {
+    /// The id of the read-only node.
+    pub node_id: C::NodeId,
+}
+
+impl<C: RaftTypeConfig> ReadOnly<C> {
+    /// Create a new ReadOnly error.
+    pub fn new(node_id: C::NodeId) -> Self {
+        Self { node_id }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::RaftTypeConfig;
+use crate::error::ForwardToLeader;
+use crate::error::NodeNotFound;
+
+/// Error related to resyncing a follower with a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum ResyncError<C: RaftTypeConfig> {
+    /// The target node was not found.
+    #[error("cannot resync; error: {0}")]
+    NodeNotFound(#[from] NodeNotFound<C>),
+    /// Request must be forwarded to the leader.
+    #[error("cannot resync; error: {0}")]
+    ForwardToLeader(#[from] ForwardToLeader<C>),
+}
@@ -0,0 +1,29 @@
+use crate::RaftTypeConfig;
+use crate::storage::StorageStamp;
+
+/// The storage was stamped by another node or another cluster.
+///
+/// It is returned by [`Raft::new()`](crate::Raft::new) when the [`StorageStamp`] read from the
+/// log store does not match the node id and the cluster name this node is started with, e.g., a
+/// data directory was copied to the wrong node.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("storage belongs to {found}, but this node is {expected}")]
+pub struct StorageStampMismatch<C>
+where C: RaftTypeConfig
+{
+    /// The stamp of this node.
+    pub expected: StorageStamp<C>,
+
+    /// The stamp found in the storage.
+    pub found: StorageStamp<C>,
+}
+
+impl<C> StorageStampMismatch<C>
+where C: RaftTypeConfig
+{
+    /// Create a new StorageStampMismatch error.
+    pub fn new(expected: StorageStamp<C>, found: StorageStamp<C>) -> Self {
+        Self { expected, found }
+    }
+}
@@ -0,0 +1,24 @@
+/// An internal task, such as a replication stream or the state machine worker, panicked.
+///
+/// It carries the name of the task and the panic payload, so that the cause of a
+/// [`Fatal`](crate::error::Fatal) error does not have to be dug out of the logs.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[error("task {task} panicked: {message}")]
+pub struct TaskPanicked {
+    /// The name of the task that panicked, e.g., `replication(target=2)`.
+    pub task: String,
+
+    /// The panic payload, if it is a string; otherwise a placeholder.
+    pub message: String,
+}
+
+impl TaskPanicked {
+    /// Create a new TaskPanicked error.
+    pub fn new(task: impl ToString, message: impl ToString) -> Self {
+        Self {
+            task: task.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::RaftTypeConfig;
+
+/// A version bump is rejected because a member does not support the version.
+///
+/// Whether a member supports it is decided by the predicate passed to
+/// [`Raft::bump_version()`](crate::Raft::bump_version), with the node metadata of the member. The
+/// version bump is not proposed. The client should upgrade the member and retry.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[error("node {node_id} does not support version {version}")]
+pub struct VersionUnsupported<C: RaftTypeConfig> {
+    /// The version to bump to.
+    pub version: u64,
+
+    /// The id of the first member that does not support the version.
+    pub node_id: C::NodeId,
+}
+
+impl<C: RaftTypeConfig> VersionUnsupported<C> {
+    /// Create a new VersionUnsupported error.
+    pub fn new(version: u64, node_id: C::NodeId) -> Self {
+        Self { version, node_id }
+    }
+}
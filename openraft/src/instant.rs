@@ -8,6 +8,7 @@ use std::ops::SubAssign;
 use std::panic::RefUnwindSafe;
 use std::panic::UnwindSafe;
 use std::time::Duration;
+use std::time::SystemTime;
 
 use crate::OptionalSend;
 use crate::OptionalSync;
@@ -36,6 +37,18 @@ pub trait Instant:
     /// Return the current instant.
     fn now() -> Self;
 
+    /// Return the current wall-clock time.
+    ///
+    /// It is only used to display or serialize an instant as a date time, by comparing it with
+    /// [`Self::now()`].
+    ///
+    /// The default implementation calls [`SystemTime::now()`], which panics on a target without a
+    /// system clock, such as `wasm32-unknown-unknown`. An instant type for such a target should
+    /// override it, e.g., with the time from `Date.now()` of the JavaScript host.
+    fn wall_clock_now() -> SystemTime {
+        SystemTime::now()
+    }
+
     /// Return the amount of time since the instant.
     ///
     /// The returned duration is guaranteed to be non-negative.
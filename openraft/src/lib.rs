@@ -26,6 +26,56 @@ macro_rules! func_name {
     }};
 }
 
+/// Emit a tracing event of a subsystem whose level may be overridden at runtime.
+///
+/// The event is emitted as usual if the subscriber enables its level. Otherwise, it is emitted at
+/// `INFO` level if the overrides in `RuntimeConfig::log_levels` enable it, with two extra fields:
+/// `subsystem` and `level`.
+///
+/// Usage:
+/// - `subsystem_log!(runtime_config, Election, DEBUG, "msg: {}", x)`;
+/// - `subsystem_log!(runtime_config, Replication(target), DEBUG, "msg: {}", x)`, for an event about
+///   the replication to `target`.
+macro_rules! subsystem_log {
+    ($runtime_config:expr, $subsystem:ident($target:expr), $lvl:ident, $($arg:tt)+) => {
+        subsystem_log!(@emit $runtime_config, $subsystem, Some(&$target as &dyn std::fmt::Display), $lvl, $($arg)+)
+    };
+    ($runtime_config:expr, $subsystem:ident, $lvl:ident, $($arg:tt)+) => {
+        subsystem_log!(@emit $runtime_config, $subsystem, None, $lvl, $($arg)+)
+    };
+    (@emit $runtime_config:expr, $subsystem:ident, $target:expr, $lvl:ident, $($arg:tt)+) => {
+        if tracing::enabled!(tracing::Level::$lvl) {
+            tracing::event!(tracing::Level::$lvl, $($arg)+);
+        } else if $runtime_config.log_levels.enables(
+            $crate::config::LogSubsystem::$subsystem,
+            $target,
+            tracing::Level::$lvl,
+        ) {
+            let subsystem = $crate::config::LogSubsystem::$subsystem;
+            tracing::info!(subsystem = %subsystem, level = %tracing::Level::$lvl, $($arg)+);
+        }
+    };
+}
+
+/// Define a failpoint named `openraft::<name>`, which is a no-op unless feature `failpoints` is
+/// enabled.
+///
+/// Usage:
+/// - `fail_point!("before_save_vote")`: the failpoint can `panic`, `sleep` or `print`, but not
+///   `return`;
+/// - `fail_point!("before_save_vote", |_arg| Err(err))`: the `return` action makes the enclosing
+///   function return the value built by the closure from the optional action argument.
+macro_rules! fail_point {
+    ($name:literal) => {
+        #[cfg(feature = "failpoints")]
+        fail::fail_point!(concat!("openraft::", $name));
+    };
+    ($name:literal, $e:expr) => {
+        #[cfg(feature = "failpoints")]
+        fail::fail_point!(concat!("openraft::", $name), $e);
+    };
+}
+
 #[cfg(feature = "loosen-follower-log-revert")]
 compile_error!(
     "The feature flag `loosen-follower-log-revert` is removed since `0.10.0`. \
@@ -34,13 +84,14 @@ compile_error!(
 
 pub extern crate openraft_macros;
 
+use openraft_quorum as quorum;
+
 mod change_members;
 mod config;
 mod core;
 mod display_ext;
 mod node;
 mod progress;
-mod quorum;
 mod raft_types;
 mod replication;
 mod runtime;
@@ -69,6 +120,7 @@ pub mod network;
 pub mod raft;
 pub mod storage;
 pub mod testing;
+pub mod trace;
 pub mod type_config;
 pub mod vote;
 
@@ -79,6 +131,7 @@ use std::fmt;
 
 pub use anyerror;
 pub use anyerror::AnyError;
+pub use openraft_macros::ApplyCommand;
 pub use openraft_macros::add_async_trait;
 pub use type_config::AsyncRuntime;
 pub use type_config::async_runtime;
@@ -96,9 +149,16 @@ pub use crate::base::OptionalSend;
 pub use crate::base::OptionalSerde;
 pub use crate::base::OptionalSync;
 pub use crate::change_members::ChangeMembers;
+pub use crate::config::BlankEntryPolicy;
+pub use crate::config::ColdStartReadPolicy;
 pub use crate::config::Config;
 pub use crate::config::ConfigError;
+pub use crate::config::LogSubsystem;
+pub use crate::config::NonMemberRpcPolicy;
+pub use crate::config::PendingWritePolicy;
+pub use crate::config::PurgePolicy;
 pub use crate::config::SnapshotPolicy;
+pub use crate::config::TimerUnit;
 pub use crate::core::ServerState;
 pub use crate::entry::Entry;
 pub use crate::entry::EntryPayload;
@@ -120,6 +180,7 @@ pub use crate::node::EmptyNode;
 pub use crate::node::Node;
 pub use crate::node::NodeId;
 pub use crate::raft::Raft;
+pub use crate::raft::ReadGuarantee;
 pub use crate::raft::ReadPolicy;
 pub use crate::raft_state::MembershipState;
 pub use crate::raft_state::RaftState;
@@ -34,12 +34,15 @@ pub(crate) mod ref_log_id;
 
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::str::FromStr;
 
 pub use log_id_option_ext::LogIdOptionExt;
 pub use log_index_option_ext::LogIndexOptionExt;
 
 use crate::RaftTypeConfig;
 use crate::log_id::raft_log_id::RaftLogId;
+use crate::storage::codec::CodecError;
+use crate::storage::codec::parse;
 use crate::type_config::alias::CommittedLeaderIdOf;
 
 /// The identity of a raft log.
@@ -90,6 +93,21 @@ where C: RaftTypeConfig
     }
 }
 
+/// Parse the `Display` output, such as `T1-N2.3`.
+impl<C> FromStr for LogId<C>
+where
+    C: RaftTypeConfig,
+    CommittedLeaderIdOf<C>: FromStr<Err = CodecError>,
+{
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (leader_id, index) =
+            s.rsplit_once('.').ok_or_else(|| CodecError::invalid(format_args!("log id: {:?}", s)))?;
+        Ok(Self::new(leader_id.parse()?, parse(index, "index")?))
+    }
+}
+
 impl<C> LogId<C>
 where C: RaftTypeConfig
 {
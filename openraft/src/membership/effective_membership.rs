@@ -112,9 +112,14 @@ where C: RaftTypeConfig
 impl<C> EffectiveMembership<C>
 where C: RaftTypeConfig
 {
-    #[allow(dead_code)]
-    pub(crate) fn is_voter(&self, nid: &C::NodeId) -> bool {
-        self.membership().is_voter(nid)
+    /// Return true if the given node id is a voter in any of the joint configs.
+    pub fn is_voter(&self, nid: &C::NodeId) -> bool {
+        self.voter_ids.contains(nid)
+    }
+
+    /// Return true if the given node id is either a voter or a learner.
+    pub fn contains(&self, nid: &C::NodeId) -> bool {
+        self.membership().contains(nid)
     }
 
     /// Returns an Iterator of all voter node ids. Learners are not included.
@@ -123,7 +128,7 @@ where C: RaftTypeConfig
     }
 
     /// Returns an Iterator of all learner node ids. Voters are not included.
-    pub(crate) fn learner_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
+    pub fn learner_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
         self.membership().learner_ids()
     }
 
@@ -33,3 +33,23 @@ fn test_effective_membership_majority() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_effective_membership_voter_and_learner() -> anyhow::Result<()> {
+    let m123_345 = Membership::<UTConfig>::new_with_defaults(vec![btreeset! {1,2,3}, btreeset! {3,4,5}], btreeset! {6});
+    let m = EffectiveMembership::<UTConfig>::new(None, m123_345);
+
+    assert!(m.is_voter(&1));
+    assert!(m.is_voter(&5));
+    assert!(!m.is_voter(&6));
+    assert!(!m.is_voter(&7));
+
+    assert!(m.contains(&5));
+    assert!(m.contains(&6));
+    assert!(!m.contains(&7));
+
+    assert_eq!(btreeset! {1,2,3,4,5}, m.voter_ids().collect());
+    assert_eq!(btreeset! {6}, m.learner_ids().collect());
+
+    Ok(())
+}
@@ -183,18 +183,14 @@ where C: RaftTypeConfig
     pub fn learner_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
         self.nodes.keys().filter(|x| !self.is_voter(x)).cloned()
     }
-}
 
-impl<C> Membership<C>
-where C: RaftTypeConfig
-{
     /// Return true if the given node id is either a voter or a learner.
-    pub(crate) fn contains(&self, node_id: &C::NodeId) -> bool {
+    pub fn contains(&self, node_id: &C::NodeId) -> bool {
         self.nodes.contains_key(node_id)
     }
 
-    /// Check if the given `NodeId` exists and is a voter.
-    pub(crate) fn is_voter(&self, node_id: &C::NodeId) -> bool {
+    /// Return true if the given node id is a voter in any of the joint configs.
+    pub fn is_voter(&self, node_id: &C::NodeId) -> bool {
         for c in self.configs.iter() {
             if c.contains(node_id) {
                 return true;
@@ -202,7 +198,11 @@ where C: RaftTypeConfig
         }
         false
     }
+}
 
+impl<C> Membership<C>
+where C: RaftTypeConfig
+{
     /// Create a new Membership the same as [`Self::new()`], but does not add the default
     /// value `Node::default()` if a voter id is not in `nodes`. Thus, it may create an invalid
     /// instance.
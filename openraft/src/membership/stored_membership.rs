@@ -50,6 +50,21 @@ where C: RaftTypeConfig
         self.membership.voter_ids()
     }
 
+    /// Get an iterator over the learner node IDs.
+    pub fn learner_ids(&self) -> impl Iterator<Item = C::NodeId> + '_ {
+        self.membership.learner_ids()
+    }
+
+    /// Return true if the given node id is a voter.
+    pub fn is_voter(&self, node_id: &C::NodeId) -> bool {
+        self.membership.is_voter(node_id)
+    }
+
+    /// Return true if the given node id is either a voter or a learner.
+    pub fn contains(&self, node_id: &C::NodeId) -> bool {
+        self.membership.contains(node_id)
+    }
+
     /// Get an iterator over all nodes (ID and node information).
     pub fn nodes(&self) -> impl Iterator<Item = (&C::NodeId, &C::Node)> {
         self.membership.nodes()
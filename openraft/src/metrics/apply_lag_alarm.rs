@@ -0,0 +1,239 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::LogIdOptionExt;
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+
+/// An alarm about the state machine lagging behind the committed logs.
+///
+/// Events are received by the application with
+/// [`Raft::apply_lag_events()`](crate::Raft::apply_lag_events), for example to page an operator
+/// when the state machine is stuck, without an external monitoring system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyLagEvent<C>
+where C: RaftTypeConfig
+{
+    /// `committed - applied` has been greater than
+    /// [`Config::apply_lag_alarm_threshold`](crate::Config::apply_lag_alarm_threshold) for
+    /// `duration`.
+    Raised {
+        committed: LogIdOf<C>,
+        applied: Option<LogIdOf<C>>,
+        lag: u64,
+        duration: Duration,
+    },
+
+    /// The lag dropped back to the threshold or below, after the alarm was raised.
+    Cleared { applied: Option<LogIdOf<C>>, lag: u64 },
+}
+
+impl<C> fmt::Display for ApplyLagEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApplyLagEvent::Raised {
+                committed,
+                applied,
+                lag,
+                duration,
+            } => write!(
+                f,
+                "Raised(committed: {}, applied: {}, lag: {}, duration: {:?})",
+                committed,
+                applied.display(),
+                lag,
+                duration
+            ),
+            ApplyLagEvent::Cleared { applied, lag } => {
+                write!(f, "Cleared(applied: {}, lag: {})", applied.display(), lag)
+            }
+        }
+    }
+}
+
+/// The subscribers of [`ApplyLagEvent`], shared by `RaftCore` and `Raft`.
+///
+/// A subscriber is removed when its receiver is dropped.
+#[derive(Clone)]
+pub(crate) struct ApplyLagEvents<C>
+where C: RaftTypeConfig
+{
+    subscribers: Arc<Mutex<Vec<MpscUnboundedSenderOf<C, ApplyLagEvent<C>>>>>,
+}
+
+impl<C> ApplyLagEvents<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            subscribers: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Add a subscriber that receives all events emitted after this call.
+    pub(crate) fn subscribe(&self) -> MpscUnboundedReceiverOf<C, ApplyLagEvent<C>> {
+        let (tx, rx) = C::mpsc_unbounded();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    fn emit(&self, event: ApplyLagEvent<C>) {
+        tracing::warn!("apply lag event: {}", event);
+        self.subscribers.lock().unwrap().retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+/// Watches `committed - applied` and emits an [`ApplyLagEvent`] when the lag stays above
+/// `threshold` for `duration`, and when it drops back.
+pub(crate) struct ApplyLagAlarm<C>
+where C: RaftTypeConfig
+{
+    threshold: u64,
+    duration: Duration,
+
+    /// Since when the lag is above the threshold.
+    lagging_since: Option<InstantOf<C>>,
+
+    raised: bool,
+
+    events: ApplyLagEvents<C>,
+}
+
+impl<C> ApplyLagAlarm<C>
+where C: RaftTypeConfig
+{
+    /// Create an alarm; a `threshold` of 0 disables it.
+    pub(crate) fn new(threshold: u64, duration: Duration, events: ApplyLagEvents<C>) -> Self {
+        Self {
+            threshold,
+            duration,
+            lagging_since: None,
+            raised: false,
+            events,
+        }
+    }
+
+    /// Check the lag at `now`, and emit an event if the alarm is raised or cleared.
+    pub(crate) fn check(&mut self, now: InstantOf<C>, committed: Option<&LogIdOf<C>>, applied: Option<&LogIdOf<C>>) {
+        if self.threshold == 0 {
+            return;
+        }
+
+        let lag = committed.next_index().saturating_sub(applied.next_index());
+
+        if lag <= self.threshold {
+            self.lagging_since = None;
+
+            if self.raised {
+                self.raised = false;
+                self.events.emit(ApplyLagEvent::Cleared {
+                    applied: applied.cloned(),
+                    lag,
+                });
+            }
+            return;
+        }
+
+        let since = self.lagging_since.get_or_insert(now);
+        let duration = now - *since;
+
+        if !self.raised && duration >= self.duration {
+            self.raised = true;
+            self.events.emit(ApplyLagEvent::Raised {
+                // Safe unwrap(): lag > 0 implies committed is not None
+                committed: committed.cloned().unwrap(),
+                applied: applied.cloned(),
+                lag,
+                duration,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::ApplyLagAlarm;
+    use super::ApplyLagEvent;
+    use super::ApplyLagEvents;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::type_config::TypeConfigExt;
+
+    #[test]
+    fn test_apply_lag_alarm() {
+        let events = ApplyLagEvents::<UTConfig>::new();
+        let mut rx = events.subscribe();
+
+        let mut alarm = ApplyLagAlarm::new(2, Duration::from_millis(100), events);
+        let t0 = UTConfig::<()>::now();
+
+        // Lag 2 is not above the threshold
+        alarm.check(t0, Some(&log_id(1, 1, 5)), Some(&log_id(1, 1, 3)));
+        assert!(rx.try_recv().is_err());
+
+        // Lag 3, not for long enough
+        alarm.check(t0, Some(&log_id(1, 1, 6)), Some(&log_id(1, 1, 3)));
+        alarm.check(
+            t0 + Duration::from_millis(50),
+            Some(&log_id(1, 1, 6)),
+            Some(&log_id(1, 1, 3)),
+        );
+        assert!(rx.try_recv().is_err());
+
+        alarm.check(
+            t0 + Duration::from_millis(100),
+            Some(&log_id(1, 1, 7)),
+            Some(&log_id(1, 1, 3)),
+        );
+        assert_eq!(
+            ApplyLagEvent::Raised {
+                committed: log_id(1, 1, 7),
+                applied: Some(log_id(1, 1, 3)),
+                lag: 4,
+                duration: Duration::from_millis(100),
+            },
+            rx.try_recv().unwrap()
+        );
+
+        // Raised only once
+        alarm.check(
+            t0 + Duration::from_millis(200),
+            Some(&log_id(1, 1, 7)),
+            Some(&log_id(1, 1, 3)),
+        );
+        assert!(rx.try_recv().is_err());
+
+        alarm.check(
+            t0 + Duration::from_millis(300),
+            Some(&log_id(1, 1, 7)),
+            Some(&log_id(1, 1, 7)),
+        );
+        assert_eq!(
+            ApplyLagEvent::Cleared {
+                applied: Some(log_id(1, 1, 7)),
+                lag: 0,
+            },
+            rx.try_recv().unwrap()
+        );
+
+        // Cleared only once
+        alarm.check(
+            t0 + Duration::from_millis(400),
+            Some(&log_id(1, 1, 7)),
+            Some(&log_id(1, 1, 7)),
+        );
+        assert!(rx.try_recv().is_err());
+    }
+}
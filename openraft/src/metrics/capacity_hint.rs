@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// An estimate of how much more write load a leader can take, for admission control before
+/// proposing.
+///
+/// The estimate is based on the entries in flight, i.e., appended but not yet committed, and on the
+/// lag of the slowest voter, compared with the number of entries replication can keep in flight to
+/// a target. [`flush_latency_ewma_us`](Self::flush_latency_ewma_us) tells whether the local storage
+/// is the bottleneck.
+///
+/// It is a hint computed when metrics are reported, not a guarantee: a write may still be
+/// rejected or delayed when [`available()`](Self::available) is not zero.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CapacityHint {
+    /// Number of log entries appended by the leader but not yet committed.
+    pub uncommitted: u64,
+
+    /// Number of log entries the slowest voter, including the leader itself, is behind the
+    /// leader's last log.
+    pub max_voter_lag: u64,
+
+    /// Exponentially weighted moving average of the latency from appending log entries to the local
+    /// log store to flushing them, in microseconds. `0` if nothing has been flushed yet.
+    pub flush_latency_ewma_us: u64,
+
+    /// Number of log entries replication keeps in flight to a single target:
+    /// [`max_payload_entries`] * [`max_append_entries_inflight`].
+    ///
+    /// [`max_payload_entries`]: crate::Config::max_payload_entries
+    /// [`max_append_entries_inflight`]: crate::Config::max_append_entries_inflight
+    pub window: u64,
+}
+
+impl CapacityHint {
+    /// Estimated number of log entries that can be proposed before replication to the slowest
+    /// voter is saturated.
+    ///
+    /// `0` means the application should hold back new writes until the backlog is drained.
+    pub fn available(&self) -> u64 {
+        self.window.saturating_sub(self.backlog())
+    }
+
+    /// The fraction of [`window`](Self::window) in use, which may exceed `1.0` when the backlog is
+    /// larger than the window.
+    pub fn load(&self) -> f64 {
+        if self.window == 0 {
+            return 0.0;
+        }
+        self.backlog() as f64 / self.window as f64
+    }
+
+    fn backlog(&self) -> u64 {
+        std::cmp::max(self.uncommitted, self.max_voter_lag)
+    }
+}
+
+impl fmt::Display for CapacityHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{available:{}/{}, uncommitted:{}, max_voter_lag:{}, flush_latency_ewma:{}us}}",
+            self.available(),
+            self.window,
+            self.uncommitted,
+            self.max_voter_lag,
+            self.flush_latency_ewma_us,
+        )
+    }
+}
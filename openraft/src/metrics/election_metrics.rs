@@ -0,0 +1,61 @@
+use std::fmt;
+
+use crate::display_ext::DisplayOptionExt;
+
+/// Counters of the elections this node took part in, accumulated since the node started.
+///
+/// A growing `started` with few `won` indicates an election storm, e.g., caused by an unstable
+/// network or an election timeout shorter than the round trip time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ElectionMetrics {
+    /// Number of elections this node started as a candidate.
+    pub started: u64,
+
+    /// Number of elections this node won and became the leader.
+    pub won: u64,
+
+    /// Number of elections this node lost: it saw a greater vote, or it timed out without being
+    /// granted by a quorum, e.g., a split vote, and started another one.
+    pub lost: u64,
+
+    /// Number of vote responses that rejected the election of this node.
+    pub votes_rejected: u64,
+
+    /// Number of times this node learned a greater term from another node.
+    pub higher_term_seen: u64,
+
+    /// Why this node started its last election.
+    pub last_start_reason: Option<String>,
+
+    /// Why this node lost its last lost election.
+    pub last_loss_reason: Option<String>,
+
+    /// Number of times an election this node was about to start was vetoed by the hook set with
+    /// [`Raft::set_election_veto()`](crate::Raft::set_election_veto).
+    ///
+    /// The hook is consulted again on every following attempt, e.g., on every tick once the
+    /// election timeout has passed, and each vetoed attempt is counted.
+    pub vetoed: u64,
+
+    /// Why the last vetoed election was vetoed.
+    pub last_veto_reason: Option<String>,
+}
+
+impl fmt::Display for ElectionMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{started: {}, won: {}, lost: {}, votes_rejected: {}, higher_term_seen: {}, last_start_reason: {}, last_loss_reason: {}, vetoed: {}, last_veto_reason: {}}}",
+            self.started,
+            self.won,
+            self.lost,
+            self.votes_rejected,
+            self.higher_term_seen,
+            self.last_start_reason.display(),
+            self.last_loss_reason.display(),
+            self.vetoed,
+            self.last_veto_reason.display(),
+        )
+    }
+}
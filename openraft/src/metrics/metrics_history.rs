@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::metrics::RaftMetrics;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// A snapshot of [`RaftMetrics`] taken at a point in time, returned by
+/// [`Raft::metrics_history()`](crate::Raft::metrics_history).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct MetricsSample<C: RaftTypeConfig> {
+    /// When the sample is taken.
+    pub at: SerdeInstantOf<C>,
+
+    /// The metrics at that time.
+    pub metrics: RaftMetrics<C>,
+}
+
+impl<C> fmt::Display for MetricsSample<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.at, self.metrics)
+    }
+}
+
+/// The last samples of metrics, shared by `RaftCore` and `Raft`, or a no-op if the history is
+/// disabled.
+///
+/// Once `capacity` samples are kept, the oldest one is dropped for a new one.
+#[derive(Clone)]
+pub(crate) struct MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    capacity: usize,
+    inner: Option<Arc<Mutex<VecDeque<MetricsSample<C>>>>>,
+}
+
+impl<C> MetricsHistory<C>
+where C: RaftTypeConfig
+{
+    /// Create a history that keeps at most `capacity` samples, or a disabled one if `capacity` is
+    /// 0.
+    pub(crate) fn new(capacity: u64) -> Self {
+        let capacity = capacity as usize;
+        let inner = if capacity == 0 {
+            None
+        } else {
+            Some(Arc::new(Mutex::new(VecDeque::with_capacity(capacity))))
+        };
+
+        Self { capacity, inner }
+    }
+
+    /// Record a sample built by `f`, which is called only if the history is enabled.
+    pub(crate) fn record(&self, f: impl FnOnce() -> MetricsSample<C>) {
+        if let Some(inner) = &self.inner {
+            let sample = f();
+
+            let mut samples = inner.lock().unwrap();
+            if samples.len() >= self.capacity {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+        }
+    }
+
+    /// Returns a copy of the samples from the oldest to the newest, or `None` if the history is
+    /// disabled.
+    pub(crate) fn samples(&self) -> Option<Vec<MetricsSample<C>>> {
+        self.inner.as_ref().map(|x| x.lock().unwrap().iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetricsHistory;
+    use super::MetricsSample;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::RaftMetrics;
+    use crate::type_config::TypeConfigExt;
+
+    fn sample(id: u64) -> MetricsSample<UTConfig> {
+        MetricsSample {
+            at: UTConfig::<()>::now().into(),
+            metrics: RaftMetrics::new_initial(id),
+        }
+    }
+
+    #[test]
+    fn test_metrics_history_disabled() {
+        let h = MetricsHistory::<UTConfig>::new(0);
+        h.record(|| unreachable!("not called if disabled"));
+        assert_eq!(None, h.samples());
+    }
+
+    #[test]
+    fn test_metrics_history_drop_oldest() {
+        let h = MetricsHistory::<UTConfig>::new(2);
+
+        h.record(|| sample(1));
+        h.record(|| sample(2));
+        h.record(|| sample(3));
+
+        let ids = h.samples().unwrap().into_iter().map(|s| s.metrics.id).collect::<Vec<_>>();
+        assert_eq!(vec![2, 3], ids);
+    }
+}
@@ -26,9 +26,28 @@
 //! Metrics is not a stream thus it only guarantees to provide the latest state but
 //! not every change of the state.
 //! Because internally, `watch::channel()` only stores one last state.
+//! To look back at how metrics evolved, e.g., after an incident, enable
+//! [`Config::metrics_history_size`](crate::Config::metrics_history_size) and dump the samples
+//! with [`Raft::metrics_history()`](crate::Raft::metrics_history).
+//!
+//! ## [`StableRaftMetrics`]
+//!
+//! Tools that read metrics across openraft upgrades, such as admin UIs, should serialize
+//! [`StableRaftMetrics`], obtained with [`RaftMetrics::to_stable()`], instead of [`RaftMetrics`]:
+//! its representation carries an explicit schema version and only changes compatibly within it.
 
+mod apply_lag_alarm;
+mod capacity_hint;
+mod election_metrics;
 mod metric;
+mod metrics_history;
 mod raft_metrics;
+mod read_replica;
+mod replication_cache_metrics;
+mod snapshot_receive_metrics;
+mod snapshot_transfer_metrics;
+mod stable_metrics;
+mod transition_metrics;
 mod wait;
 
 mod metric_display;
@@ -36,18 +55,36 @@ mod serde_instant;
 mod wait_condition;
 #[cfg(test)]
 mod wait_test;
+mod write_latency_metrics;
 
 use std::collections::BTreeMap;
 
+pub(crate) use apply_lag_alarm::ApplyLagAlarm;
+pub use apply_lag_alarm::ApplyLagEvent;
+pub(crate) use apply_lag_alarm::ApplyLagEvents;
+pub use capacity_hint::CapacityHint;
+pub use election_metrics::ElectionMetrics;
 pub use metric::Metric;
+pub(crate) use metrics_history::MetricsHistory;
+pub use metrics_history::MetricsSample;
 pub use raft_metrics::RaftDataMetrics;
 pub use raft_metrics::RaftMetrics;
 pub use raft_metrics::RaftServerMetrics;
+pub use read_replica::ReadReplica;
+pub use replication_cache_metrics::ReplicationCacheMetrics;
 pub use serde_instant::SerdeInstant;
+pub use snapshot_receive_metrics::SnapshotReceiveMetrics;
+pub use snapshot_receive_metrics::SnapshotReceivePhase;
+pub(crate) use snapshot_receive_metrics::SnapshotReceiveProgress;
+pub use snapshot_transfer_metrics::SnapshotTransferMetrics;
+pub use stable_metrics::StableRaftMetrics;
+pub use transition_metrics::TransitionMetrics;
 pub use wait::Wait;
 pub use wait::WaitError;
 pub(crate) use wait_condition::Condition;
+pub use write_latency_metrics::WriteLatencyMetrics;
 
+pub use crate::base::histogram::PercentileStats;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::NodeIdOf;
 use crate::type_config::alias::SerdeInstantOf;
@@ -56,3 +93,4 @@ pub(crate) type ReplicationMetrics<C> = BTreeMap<NodeIdOf<C>, Option<LogIdOf<C>>
 /// Heartbeat metrics, a mapping between a node's ID and the time of the last
 /// acknowledged heartbeat or replication to this node.
 pub(crate) type HeartbeatMetrics<C> = BTreeMap<NodeIdOf<C>, Option<SerdeInstantOf<C>>>;
+pub(crate) type ReadReplicaMetrics<C> = BTreeMap<NodeIdOf<C>, ReadReplica<C>>;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::sync::Arc;
 
@@ -5,12 +7,27 @@ use crate::Instant;
 use crate::RaftTypeConfig;
 use crate::StoredMembership;
 use crate::core::ServerState;
+use crate::display_ext::DisplayBTreeMap;
 use crate::display_ext::DisplayBTreeMapOptValue;
+use crate::display_ext::DisplayBTreeSet;
 use crate::display_ext::DisplayOption;
+use crate::display_ext::DisplayOptionExt;
 use crate::error::Fatal;
+use crate::error::InProgress;
+use crate::metrics::CapacityHint;
+use crate::metrics::ElectionMetrics;
 use crate::metrics::HeartbeatMetrics;
+#[cfg(doc)]
+use crate::metrics::ReadReplica;
+use crate::metrics::ReadReplicaMetrics;
+use crate::metrics::ReplicationCacheMetrics;
 use crate::metrics::ReplicationMetrics;
 use crate::metrics::SerdeInstant;
+use crate::metrics::SnapshotReceiveMetrics;
+use crate::metrics::SnapshotTransferMetrics;
+use crate::metrics::TransitionMetrics;
+use crate::metrics::WriteLatencyMetrics;
+use crate::raft::NodeInfo;
 use crate::type_config::alias::InstantOf;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::SerdeInstantOf;
@@ -28,7 +45,8 @@ use crate::type_config::alias::VoteOf;
 /// - **Node State**: `id`, `state`, `current_leader`, `running_state`
 /// - **Log State**: `last_log_index`, `last_applied`, `snapshot`, `purged`
 /// - **Voting State**: `current_term`, `vote`
-/// - **Leader Metrics** (only when leader): `heartbeat`, `replication`, `last_quorum_acked`
+/// - **Leader Metrics** (only when leader): `heartbeat`, `replication`, `read_replicas`,
+///   `last_quorum_acked`
 /// - **Cluster Config**: `membership_config`
 ///
 /// # Usage
@@ -55,6 +73,7 @@ use crate::type_config::alias::VoteOf;
 ///
 /// - `heartbeat`: Last acknowledged time for each node (for detecting offline nodes)
 /// - `replication`: Replication state including `matched` log index for each node
+/// - `read_replicas`: Lag and last acknowledged time of each learner
 ///
 /// These fields are `None` when the node is a follower or candidate.
 ///
@@ -109,6 +128,13 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The current cluster leader.
     pub current_leader: Option<C::NodeId>,
 
+    /// Whether this node has heard from a leader, or has become the leader, since it started.
+    ///
+    /// Right after a restart, `current_leader` may be loaded from the persisted vote, and the
+    /// state machine may be far behind the cluster. See
+    /// [`Config::cold_start_reads`](crate::Config::cold_start_reads).
+    pub leader_discovered: bool,
+
     /// For a leader, it is the elapsed time in milliseconds since the most recently acknowledged
     /// timestamp by a quorum.
     ///
@@ -146,6 +172,96 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     /// The current membership config of the cluster.
     pub membership_config: Arc<StoredMembership<C>>,
 
+    /// The last membership config known to be committed.
+    ///
+    /// It differs from `membership_config` while a membership change is in progress, i.e., the
+    /// config in `membership_config` is proposed but not yet committed. Another membership change
+    /// is rejected with [`InProgress`] until then; see [`Self::membership_in_progress()`].
+    pub committed_membership: Arc<StoredMembership<C>>,
+
+    /// Whether this node is the leader and has committed a log of its own term.
+    ///
+    /// Until then, the logs of previous terms are not committed by this leader. With the default
+    /// [`Config::blank_entry_policy`](crate::Config::blank_entry_policy), it becomes `true` once
+    /// the blank log appended on election is committed.
+    pub current_term_committed: bool,
+
+    /// Number of times two leaders of the same term are observed to be active at the same time.
+    ///
+    /// It is increased when an `AppendEntries` is received from another leader of the current term
+    /// while the lease of the current leader has not yet expired. A non-zero value usually
+    /// indicates clock drift or misconfigured timeouts, such as a leader lease longer than the
+    /// election timeout of other nodes. A stale leader of a smaller term is not counted.
+    pub split_brain_detected: u64,
+
+    /// Elections started by this node and their outcome, to alert on election storms.
+    pub elections: ElectionMetrics,
+
+    /// The time of the last change of the server state, the vote, the snapshot and the
+    /// membership of this node, e.g., to show how long this node has been the leader.
+    pub transitions: TransitionMetrics<C>,
+
+    /// Number of times this node as a leader handed off its leadership to a voter that is
+    /// significantly fitter. See
+    /// [`Config::leader_fitness_interval`](crate::Config::leader_fitness_interval).
+    pub leader_fitness_handoffs: u64,
+
+    /// Number of log ranges this node as a follower verified against the checksum sent by the
+    /// leader. See [`Config::log_checksum_interval`](crate::Config::log_checksum_interval).
+    pub log_checksums_verified: u64,
+
+    /// Number of log ranges whose checksum on this node as a follower differs from the one sent
+    /// by the leader.
+    ///
+    /// A non-zero value indicates a diverged or corrupted log on this node.
+    pub log_checksum_mismatches: u64,
+
+    /// Number of log entries this node as a follower applied with the results computed by the
+    /// leader, instead of computing them. See
+    /// [`Config::apply_results_wait`](crate::Config::apply_results_wait).
+    pub applied_from_leader_results: u64,
+
+    /// Number of results computed by this node as a follower that differ from the ones computed
+    /// by the leader for the same log entries.
+    ///
+    /// A non-zero value indicates a state machine that is not deterministic.
+    pub apply_result_mismatches: u64,
+
+    /// Why the local storage failed the check before the last attempt to start an election, or
+    /// `None` if it passed. See
+    /// [`Config::check_storage_before_election`](crate::Config::check_storage_before_election).
+    ///
+    /// A node with a failed storage check does not campaign until its storage is repaired.
+    pub storage_check_failure: Option<String>,
+
+    /// The software version and capabilities of this node and of every node it has exchanged
+    /// [`HelloRequest`](crate::raft::HelloRequest) with, keyed by node id.
+    ///
+    /// A Leader sends a hello request to each replication target it does not know yet.
+    pub node_infos: BTreeMap<C::NodeId, NodeInfo>,
+
+    /// The compression scheme negotiated with every node that supports one of
+    /// [`Config::compression`](crate::Config::compression), keyed by node id.
+    ///
+    /// A leader compresses the AppendEntries payloads to a node with this scheme once they reach
+    /// [`Config::compression_threshold`](crate::Config::compression_threshold). A node that is not
+    /// listed is sent uncompressed payloads.
+    pub compression: BTreeMap<C::NodeId, String>,
+
+    /// Latency of log entries written by this node as a leader, split into storage, quorum
+    /// acknowledgement and apply stages.
+    pub write_latency: WriteLatencyMetrics,
+
+    /// Effectiveness of the cache of log entries read for replication by this node as a leader.
+    pub replication_cache: ReplicationCacheMetrics,
+
+    /// Snapshots being sent, and waiting to be sent, by this node as a leader.
+    pub snapshot_transfers: SnapshotTransferMetrics,
+
+    /// Progress of the snapshot this node as a follower or learner is receiving and installing,
+    /// or `None` if there is none.
+    pub snapshot_receiving: Option<SnapshotReceiveMetrics<C>>,
+
     /// Heartbeat metrics. It is Some() only when this node is leader.
     ///
     /// This field records a mapping between a node's ID and the time of the
@@ -161,6 +277,26 @@ pub struct RaftMetrics<C: RaftTypeConfig> {
     // ---
     /// The replication states. It is Some() only when this node is leader.
     pub replication: Option<ReplicationMetrics<C>>,
+
+    /// Data freshness of every learner. It is Some() only when this node is leader.
+    ///
+    /// Learners do not vote, and can be used as read replicas for reads that tolerate stale data.
+    /// See [`ReadReplica::is_fresh`].
+    pub read_replicas: Option<ReadReplicaMetrics<C>>,
+
+    /// Followers and learners quarantined for diverged logs. It is Some() only when this node is
+    /// leader.
+    ///
+    /// Nothing is replicated to a quarantined node until it is resynced or its log reversion is
+    /// allowed. See [`Config::quarantine_after_log_reversions`].
+    ///
+    /// [`Config::quarantine_after_log_reversions`]: crate::Config::quarantine_after_log_reversions
+    pub quarantined: Option<BTreeSet<C::NodeId>>,
+
+    /// Estimated write capacity. It is Some() only when this node is leader.
+    ///
+    /// See [`Raft::capacity_hint()`](crate::Raft::capacity_hint).
+    pub capacity: Option<CapacityHint>,
 }
 
 impl<C> fmt::Display for RaftMetrics<C>
@@ -195,12 +331,16 @@ where C: RaftTypeConfig
         write!(f, ", ")?;
         write!(
             f,
-            "membership:{}, snapshot:{}, purged:{}, replication:{{{}}}, heartbeat:{{{}}}",
+            "membership:{}, committed_membership:{}, snapshot:{}, purged:{}, replication:{{{}}}, heartbeat:{{{}}}, read_replicas:{{{}}}, quarantined:{}, capacity:{}",
             self.membership_config,
+            self.committed_membership.log_id().display(),
             DisplayOption(&self.snapshot),
             DisplayOption(&self.purged),
             DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
             DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
+            DisplayOption(&self.read_replicas.as_ref().map(DisplayBTreeMap)),
+            DisplayOption(&self.quarantined.as_ref().map(DisplayBTreeSet)),
+            self.capacity.display(),
         )?;
 
         write!(f, "}}")?;
@@ -227,12 +367,49 @@ where C: RaftTypeConfig
 
             state: ServerState::Follower,
             current_leader: None,
+            leader_discovered: false,
             millis_since_quorum_ack: None,
             last_quorum_acked: None,
             membership_config: Arc::new(StoredMembership::default()),
+            committed_membership: Arc::new(StoredMembership::default()),
+            current_term_committed: false,
+            split_brain_detected: 0,
+            elections: ElectionMetrics::default(),
+            transitions: TransitionMetrics::default(),
+            leader_fitness_handoffs: 0,
+            log_checksums_verified: 0,
+            log_checksum_mismatches: 0,
+            applied_from_leader_results: 0,
+            apply_result_mismatches: 0,
+            storage_check_failure: None,
+            node_infos: BTreeMap::new(),
+            compression: BTreeMap::new(),
+            write_latency: WriteLatencyMetrics::default(),
+            replication_cache: ReplicationCacheMetrics::default(),
+            snapshot_transfers: SnapshotTransferMetrics::default(),
+            snapshot_receiving: None,
             replication: None,
             heartbeat: None,
+            read_replicas: None,
+            quarantined: None,
+            capacity: None,
+        }
+    }
+
+    /// Returns the membership change in progress, i.e., the membership config that is proposed
+    /// but not yet committed, or `None` if the last membership config is committed.
+    ///
+    /// The returned [`InProgress`] is the same error a `change_membership()` on the leader fails
+    /// with at this point.
+    pub fn membership_in_progress(&self) -> Option<InProgress<C>> {
+        if self.membership_config.log_id() == self.committed_membership.log_id() {
+            return None;
         }
+
+        Some(InProgress {
+            committed: self.committed_membership.log_id().clone(),
+            membership_log_id: self.membership_config.log_id().clone(),
+        })
     }
 }
 
@@ -291,6 +468,21 @@ pub struct RaftDataMetrics<C: RaftTypeConfig> {
     /// guess if a follower/learner node is offline, longer duration suggests
     /// a higher possibility of that.
     pub heartbeat: Option<HeartbeatMetrics<C>>,
+
+    /// Data freshness of every learner. It is Some() only when this node is leader.
+    ///
+    /// Learners do not vote, and can be used as read replicas for reads that tolerate stale data.
+    /// See [`ReadReplica::is_fresh`].
+    pub read_replicas: Option<ReadReplicaMetrics<C>>,
+
+    /// Followers and learners quarantined for diverged logs. It is Some() only when this node is
+    /// leader.
+    ///
+    /// Nothing is replicated to a quarantined node until it is resynced or its log reversion is
+    /// allowed. See [`Config::quarantine_after_log_reversions`].
+    ///
+    /// [`Config::quarantine_after_log_reversions`]: crate::Config::quarantine_after_log_reversions
+    pub quarantined: Option<BTreeSet<C::NodeId>>,
 }
 
 impl<C> fmt::Display for RaftDataMetrics<C>
@@ -321,9 +513,11 @@ where C: RaftTypeConfig
 
         write!(
             f,
-            ", replication:{{{}}}, heartbeat:{{{}}}",
+            ", replication:{{{}}}, heartbeat:{{{}}}, read_replicas:{{{}}}, quarantined:{}",
             DisplayOption(&self.replication.as_ref().map(DisplayBTreeMapOptValue)),
             DisplayOption(&self.heartbeat.as_ref().map(DisplayBTreeMapOptValue)),
+            DisplayOption(&self.read_replicas.as_ref().map(DisplayBTreeMap)),
+            DisplayOption(&self.quarantined.as_ref().map(DisplayBTreeSet)),
         )?;
 
         write!(f, "}}")?;
@@ -346,6 +540,9 @@ pub struct RaftServerMetrics<C: RaftTypeConfig> {
 
     /// The current membership configuration.
     pub membership_config: Arc<StoredMembership<C>>,
+
+    /// The last membership configuration known to be committed.
+    pub committed_membership: Arc<StoredMembership<C>>,
 }
 
 impl<C> fmt::Display for RaftServerMetrics<C>
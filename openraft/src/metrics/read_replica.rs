@@ -0,0 +1,61 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOption;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// Data freshness of a learner, i.e., a non-voter that can serve stale-tolerant reads, as seen by
+/// the leader.
+///
+/// A load balancer can use it to route reads that tolerate staleness only to replicas that are
+/// fresh enough, see [`ReadReplica::is_fresh`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ReadReplica<C: RaftTypeConfig> {
+    /// The last log id replicated to this learner.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// Number of log entries the learner is behind the leader's last log.
+    pub lag: u64,
+
+    /// The time the leader last received an acknowledgement from this learner, by replication or
+    /// heartbeat.
+    ///
+    /// The learner had all the logs up to `matched` at this time.
+    pub last_acked: Option<SerdeInstantOf<C>>,
+}
+
+impl<C> ReadReplica<C>
+where C: RaftTypeConfig
+{
+    /// The estimated staleness of the data on this learner: the time elapsed since it was last
+    /// known to have the logs up to `matched`.
+    ///
+    /// It returns `None` if the learner has never acknowledged the leader.
+    pub fn staleness(&self) -> Option<Duration> {
+        self.last_acked.as_ref().map(|t| t.elapsed())
+    }
+
+    /// Returns `true` if the learner is at most `max_lag` entries behind the leader and was
+    /// acknowledged within `max_staleness`.
+    pub fn is_fresh(&self, max_lag: u64, max_staleness: Duration) -> bool {
+        self.lag <= max_lag && self.staleness().is_some_and(|d| d <= max_staleness)
+    }
+}
+
+impl<C> fmt::Display for ReadReplica<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{matched:{}, lag:{}, last_acked:{}}}",
+            DisplayOption(&self.matched),
+            self.lag,
+            DisplayOption(&self.last_acked),
+        )
+    }
+}
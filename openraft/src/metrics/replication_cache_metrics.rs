@@ -0,0 +1,28 @@
+use std::fmt;
+
+/// Effectiveness of the cache of log entries shared by the replication streams of a leader.
+///
+/// Counters are accumulated since the node started. See
+/// [`Config::replication_cache_entries`](crate::Config::replication_cache_entries).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ReplicationCacheMetrics {
+    /// Number of log entries in the cache.
+    pub entries: u64,
+
+    /// Number of log reads for replication served by the cache.
+    pub hits: u64,
+
+    /// Number of log reads for replication that had to read the log store.
+    pub misses: u64,
+}
+
+impl fmt::Display for ReplicationCacheMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{entries: {}, hits: {}, misses: {}}}",
+            self.entries, self.hits, self.misses
+        )
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::SnapshotId;
+use crate::display_ext::DisplayOptionExt;
+use crate::metrics::SerdeInstant;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// The phase of a snapshot being received by a follower or learner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SnapshotReceivePhase {
+    /// The snapshot data is being received from the leader.
+    Receiving,
+
+    /// All data is received, and the snapshot is being installed to the state machine.
+    Installing,
+}
+
+impl fmt::Display for SnapshotReceivePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotReceivePhase::Receiving => write!(f, "Receiving"),
+            SnapshotReceivePhase::Installing => write!(f, "Installing"),
+        }
+    }
+}
+
+/// Progress of the snapshot a follower or learner is receiving and installing.
+///
+/// A `last_updated` that does not advance while in [`SnapshotReceivePhase::Receiving`] tells
+/// that the transfer is stuck.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct SnapshotReceiveMetrics<C: RaftTypeConfig> {
+    /// The id of the snapshot being received.
+    pub snapshot_id: SnapshotId,
+
+    /// Whether the snapshot is being received or installed.
+    pub phase: SnapshotReceivePhase,
+
+    /// Number of bytes received so far.
+    pub received: u64,
+
+    /// The size of the snapshot in bytes, if the transport knows it.
+    ///
+    /// The chunked transport used by [`Raft::install_snapshot()`](crate::Raft::install_snapshot)
+    /// does not; an application defined transport may report it with
+    /// [`Raft::report_snapshot_receiving()`](crate::Raft::report_snapshot_receiving).
+    pub total: Option<u64>,
+
+    /// When the progress is updated for the last time.
+    pub last_updated: SerdeInstantOf<C>,
+}
+
+impl<C> fmt::Display for SnapshotReceiveMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{id: {}, {}, received: {}, total: {}, last_updated: {}}}",
+            self.snapshot_id,
+            self.phase,
+            self.received,
+            self.total.display(),
+            self.last_updated
+        )
+    }
+}
+
+/// The progress of the snapshot being received, shared by `Raft`, which updates it, and
+/// `RaftCore`, which reports it in the metrics.
+#[derive(Clone)]
+pub(crate) struct SnapshotReceiveProgress<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Option<SnapshotReceiveMetrics<C>>>>,
+}
+
+impl<C> SnapshotReceiveProgress<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Record that `received` bytes of snapshot `snapshot_id` are received.
+    ///
+    /// A known `total` is kept for the following updates of the same snapshot.
+    pub(crate) fn receiving(&self, snapshot_id: &SnapshotId, received: u64, total: Option<u64>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        let total = match inner.as_ref() {
+            Some(m) if &m.snapshot_id == snapshot_id => total.or(m.total),
+            _ => total,
+        };
+
+        *inner = Some(SnapshotReceiveMetrics {
+            snapshot_id: snapshot_id.clone(),
+            phase: SnapshotReceivePhase::Receiving,
+            received,
+            total,
+            last_updated: SerdeInstant::new(C::now()),
+        });
+    }
+
+    /// Record that snapshot `snapshot_id` is received, and is being installed.
+    pub(crate) fn installing(&self, snapshot_id: &SnapshotId) {
+        let mut inner = self.inner.lock().unwrap();
+
+        let (received, total) = match inner.as_ref() {
+            Some(m) if &m.snapshot_id == snapshot_id => (m.received, m.total),
+            _ => (0, None),
+        };
+
+        *inner = Some(SnapshotReceiveMetrics {
+            snapshot_id: snapshot_id.clone(),
+            phase: SnapshotReceivePhase::Installing,
+            received,
+            total,
+            last_updated: SerdeInstant::new(C::now()),
+        });
+    }
+
+    /// Clear the progress of snapshot `snapshot_id` once it is installed, or failed to.
+    ///
+    /// The progress of another snapshot that started since then is kept.
+    pub(crate) fn finish(&self, snapshot_id: &SnapshotId) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.as_ref().is_some_and(|m| &m.snapshot_id == snapshot_id) {
+            *inner = None;
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> Option<SnapshotReceiveMetrics<C>> {
+        self.inner.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotReceivePhase;
+    use super::SnapshotReceiveProgress;
+    use crate::engine::testing::UTConfig;
+
+    #[test]
+    fn test_snapshot_receive_progress() {
+        let p = SnapshotReceiveProgress::<UTConfig>::new();
+        let id = "s1".to_string();
+
+        assert!(p.metrics().is_none());
+
+        p.receiving(&id, 10, Some(100));
+        p.receiving(&id, 20, None);
+        let m = p.metrics().unwrap();
+        assert_eq!(SnapshotReceivePhase::Receiving, m.phase);
+        assert_eq!(20, m.received);
+        assert_eq!(Some(100), m.total, "total is kept");
+
+        p.installing(&id);
+        let m = p.metrics().unwrap();
+        assert_eq!(SnapshotReceivePhase::Installing, m.phase);
+        assert_eq!(20, m.received);
+        assert_eq!(Some(100), m.total);
+
+        // Another snapshot replaces it, and is not cleared by the former one.
+        let id2 = "s2".to_string();
+        p.receiving(&id2, 5, None);
+        p.finish(&id);
+        let m = p.metrics().unwrap();
+        assert_eq!(id2, m.snapshot_id);
+        assert_eq!(None, m.total);
+
+        p.finish(&id2);
+        assert!(p.metrics().is_none());
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Snapshots being sent by a leader, and those waiting for a permit to be sent.
+///
+/// See [`Config::max_inflight_snapshots`](crate::Config::max_inflight_snapshots).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SnapshotTransferMetrics {
+    /// Number of snapshots being sent.
+    pub sending: u64,
+
+    /// Number of snapshots waiting to be sent until another transfer finishes.
+    pub queued: u64,
+
+    /// Number of snapshot transfers that had to wait, since the node started.
+    pub total_queued: u64,
+}
+
+impl fmt::Display for SnapshotTransferMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{sending: {}, queued: {}, total_queued: {}}}",
+            self.sending, self.queued, self.total_queued
+        )
+    }
+}
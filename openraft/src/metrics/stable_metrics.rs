@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use openraft_macros::since;
+
+use crate::LogIdOptionExt;
+use crate::RaftMetrics;
+use crate::RaftTypeConfig;
+use crate::core::ServerState;
+
+/// A stable representation of [`RaftMetrics`] for external tooling, such as admin UIs and CLI
+/// tools.
+///
+/// Unlike [`RaftMetrics`], whose fields follow the internals of openraft, the serialized form of
+/// this struct only changes in a backward compatible way within a [`schema_version`]:
+///
+/// - New fields are only added with a default value, so that a newer tool can read the metrics of
+///   an older node.
+/// - Unknown fields are ignored when deserializing, so that an older tool can read the metrics of a
+///   newer node.
+/// - A field is never removed or changes its meaning without bumping
+///   [`SCHEMA_VERSION`](Self::SCHEMA_VERSION).
+///
+/// Log ids are represented by their index only, and the membership by node ids.
+///
+/// ```ignore
+/// let stable = raft.metrics().borrow_watched().to_stable();
+/// let json = serde_json::to_string(&stable)?;
+/// ```
+///
+/// [`schema_version`]: Self::schema_version
+#[since(version = "0.10.0")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct StableRaftMetrics<C: RaftTypeConfig> {
+    /// The schema version of this representation, i.e., [`SCHEMA_VERSION`](Self::SCHEMA_VERSION)
+    /// of the node that produced it.
+    pub schema_version: u32,
+
+    /// The ID of the Raft node.
+    pub id: C::NodeId,
+
+    /// The state of the Raft node.
+    pub state: ServerState,
+
+    /// The error message if the Raft node stopped with a fatal error, or `None` if it is running.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fatal: Option<String>,
+
+    /// The current term of the Raft node.
+    pub current_term: C::Term,
+
+    /// The current cluster leader.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub current_leader: Option<C::NodeId>,
+
+    /// The index of the last log entry appended to this Raft node's log.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_log_index: Option<u64>,
+
+    /// The index of the last log entry applied to the state machine.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_applied_index: Option<u64>,
+
+    /// The index of the last log entry included in the current snapshot.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub snapshot_index: Option<u64>,
+
+    /// The index of the last purged log entry.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub purged_index: Option<u64>,
+
+    /// The index of the log entry of the effective membership config.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub membership_index: Option<u64>,
+
+    /// The voter ids of every config in the effective membership; more than one config means a
+    /// joint config.
+    pub voters: Vec<BTreeSet<C::NodeId>>,
+
+    /// The learner ids in the effective membership.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub learners: BTreeSet<C::NodeId>,
+
+    /// For a leader, the elapsed time in milliseconds since the most recently acknowledged
+    /// timestamp by a quorum. `None` on other nodes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub millis_since_quorum_ack: Option<u64>,
+
+    /// For a leader, the index of the last log replicated to each node. `None` on other nodes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub replication: Option<BTreeMap<C::NodeId, Option<u64>>>,
+}
+
+impl<C> StableRaftMetrics<C>
+where C: RaftTypeConfig
+{
+    /// The current schema version, bumped on every incompatible change of the representation.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Returns `true` if this representation can be read by this version of openraft, i.e., it is
+    /// not produced with a newer schema version.
+    pub fn is_supported(&self) -> bool {
+        self.schema_version <= Self::SCHEMA_VERSION
+    }
+}
+
+impl<C> From<&RaftMetrics<C>> for StableRaftMetrics<C>
+where C: RaftTypeConfig
+{
+    fn from(m: &RaftMetrics<C>) -> Self {
+        #[allow(deprecated)]
+        let millis_since_quorum_ack = m.millis_since_quorum_ack;
+
+        Self {
+            schema_version: Self::SCHEMA_VERSION,
+            id: m.id.clone(),
+            state: m.state,
+            fatal: m.running_state.as_ref().err().map(|e| e.to_string()),
+            current_term: m.current_term,
+            current_leader: m.current_leader.clone(),
+            last_log_index: m.last_log_index,
+            last_applied_index: m.last_applied.index(),
+            snapshot_index: m.snapshot.index(),
+            purged_index: m.purged.index(),
+            membership_index: m.membership_config.log_id().index(),
+            voters: m.membership_config.membership().get_joint_config().clone(),
+            learners: m.membership_config.learner_ids().collect(),
+            millis_since_quorum_ack,
+            replication: m.replication.as_ref().map(|r| r.iter().map(|(id, l)| (id.clone(), l.index())).collect()),
+        }
+    }
+}
+
+impl<C> From<RaftMetrics<C>> for StableRaftMetrics<C>
+where C: RaftTypeConfig
+{
+    fn from(m: RaftMetrics<C>) -> Self {
+        Self::from(&m)
+    }
+}
+
+impl<C> RaftMetrics<C>
+where C: RaftTypeConfig
+{
+    /// Convert to the [`StableRaftMetrics`] representation for external tooling.
+    #[since(version = "0.10.0")]
+    pub fn to_stable(&self) -> StableRaftMetrics<C> {
+        StableRaftMetrics::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::btreemap;
+    use maplit::btreeset;
+
+    use super::StableRaftMetrics;
+    use crate::Membership;
+    use crate::RaftMetrics;
+    use crate::StoredMembership;
+    use crate::core::ServerState;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+
+    fn metrics() -> RaftMetrics<UTConfig> {
+        let mut m = RaftMetrics::new_initial(1);
+        m.state = ServerState::Leader;
+        m.current_term = 2;
+        m.current_leader = Some(1);
+        m.last_log_index = Some(10);
+        m.last_applied = Some(log_id(2, 1, 9));
+        m.purged = Some(log_id(1, 1, 3));
+        m.membership_config = std::sync::Arc::new(StoredMembership::new(
+            Some(log_id(1, 1, 1)),
+            Membership::new_with_defaults(vec![btreeset! {1,2,3}], btreeset! {4}),
+        ));
+        m.replication = Some(btreemap! {2 => Some(log_id(2, 1, 8)), 3 => None});
+        m
+    }
+
+    #[test]
+    fn test_from_raft_metrics() {
+        let stable = metrics().to_stable();
+
+        assert_eq!(
+            StableRaftMetrics::<UTConfig> {
+                schema_version: 1,
+                id: 1,
+                state: ServerState::Leader,
+                fatal: None,
+                current_term: 2,
+                current_leader: Some(1),
+                last_log_index: Some(10),
+                last_applied_index: Some(9),
+                snapshot_index: None,
+                purged_index: Some(3),
+                membership_index: Some(1),
+                voters: vec![btreeset! {1,2,3}],
+                learners: btreeset! {4},
+                millis_since_quorum_ack: None,
+                replication: Some(btreemap! {2 => Some(8), 3 => None}),
+            },
+            stable
+        );
+        assert!(stable.is_supported());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_compatible() -> anyhow::Result<()> {
+        let stable = metrics().to_stable();
+
+        let json = serde_json::to_value(&stable)?;
+        assert_eq!(1, json["schema_version"]);
+        assert_eq!(serde_json::from_value::<StableRaftMetrics<UTConfig>>(json)?, stable);
+
+        // Produced by a newer node: unknown fields are ignored.
+        let newer = serde_json::json!({
+            "schema_version": 2,
+            "id": 1,
+            "state": "Follower",
+            "current_term": 3,
+            "voters": [[1, 2, 3]],
+            "some_new_field": {"a": 1},
+        });
+        let got = serde_json::from_value::<StableRaftMetrics<UTConfig>>(newer)?;
+        assert_eq!(2, got.schema_version);
+        assert_eq!(Some(3), got.voters.first().map(|v| v.len()));
+        assert_eq!(None, got.last_applied_index);
+        assert!(!got.is_supported());
+
+        Ok(())
+    }
+}
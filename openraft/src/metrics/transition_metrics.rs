@@ -0,0 +1,57 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::SerdeInstantOf;
+
+/// The time of the last change of the server state, the vote, the snapshot and the membership of
+/// this node, since the node started.
+///
+/// A time is `None` if the value has not changed since the node started. A time is serialized and
+/// displayed as a wall-clock date time, e.g., a dashboard can show how long a node has been the
+/// leader with `server_state_changed.elapsed()`, and alert on a leader that changes too often.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct TransitionMetrics<C: RaftTypeConfig> {
+    /// The time the server state of this node last changed, e.g., from `Follower` to `Leader`.
+    ///
+    /// The change to the server state this node starts with is included.
+    pub server_state_changed: Option<SerdeInstantOf<C>>,
+
+    /// The time a different vote was last persisted by this node.
+    pub vote_persisted: Option<SerdeInstantOf<C>>,
+
+    /// The time a snapshot was last built or installed by this node.
+    pub snapshot_updated: Option<SerdeInstantOf<C>>,
+
+    /// The time the effective membership config of this node last changed.
+    pub membership_changed: Option<SerdeInstantOf<C>>,
+}
+
+impl<C> Default for TransitionMetrics<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            server_state_changed: None,
+            vote_persisted: None,
+            snapshot_updated: None,
+            membership_changed: None,
+        }
+    }
+}
+
+impl<C> fmt::Display for TransitionMetrics<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{server_state_changed: {}, vote_persisted: {}, snapshot_updated: {}, membership_changed: {}}}",
+            self.server_state_changed.display(),
+            self.vote_persisted.display(),
+            self.snapshot_updated.display(),
+            self.membership_changed.display(),
+        )
+    }
+}
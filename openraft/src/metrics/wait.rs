@@ -212,6 +212,21 @@ where C: RaftTypeConfig
         .await
     }
 
+    /// Block until no membership change is in progress, i.e., the last membership config is
+    /// committed, or timeout.
+    ///
+    /// A `change_membership()` rejected with [`InProgress`] can be retried after this returns.
+    ///
+    /// [`InProgress`]: crate::error::InProgress
+    #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
+    pub async fn membership_committed(&self, msg: impl ToString) -> Result<RaftMetrics<C>, WaitError> {
+        self.metrics(
+            |m| m.membership_in_progress().is_none(),
+            &format!("{} .membership is committed", msg.to_string()),
+        )
+        .await
+    }
+
     /// Wait for `snapshot` to become `snapshot_last_log_id` or timeout.
     #[tracing::instrument(level = "trace", skip(self), fields(msg=msg.to_string().as_str()))]
     pub async fn snapshot(
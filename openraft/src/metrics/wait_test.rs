@@ -258,13 +258,34 @@ where C: RaftTypeConfig {
         purged: None,
 
         current_leader: None,
+        leader_discovered: false,
         millis_since_quorum_ack: None,
         last_quorum_acked: None,
         membership_config: Arc::new(StoredMembership::new(None, Membership::default())),
+        committed_membership: Arc::new(StoredMembership::new(None, Membership::default())),
+        current_term_committed: false,
+        split_brain_detected: 0,
+        elections: Default::default(),
+        transitions: Default::default(),
+        leader_fitness_handoffs: 0,
+        log_checksums_verified: 0,
+        log_checksum_mismatches: 0,
+        applied_from_leader_results: 0,
+        apply_result_mismatches: 0,
+        storage_check_failure: None,
+        node_infos: Default::default(),
+        compression: Default::default(),
+        write_latency: Default::default(),
+        replication_cache: Default::default(),
+        snapshot_transfers: Default::default(),
+        snapshot_receiving: None,
         heartbeat: None,
 
         snapshot: None,
         replication: None,
+        read_replicas: None,
+        quarantined: None,
+        capacity: None,
     };
     let (tx, rx) = C::watch_channel(init.clone());
     let w = Wait {
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use crate::metrics::PercentileStats;
+
+/// Latency distribution of log entries written on a leader, in microseconds.
+///
+/// The time a log entry spends from being proposed to being applied is split into stages, so that
+/// a slow write can be attributed to the storage, the network, or the state machine:
+///
+/// - `flush`: from submitting the entry to the local log store to it being flushed.
+/// - `commit`: from submitting the entry to the local log store to it being accepted by a quorum,
+///   i.e., committed. It includes the replication to followers.
+/// - `apply`: from the entry being committed to it being applied to the state machine.
+///
+/// Latencies are recorded for every entry appended by this node as a leader, and are accumulated
+/// since the node started.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WriteLatencyMetrics {
+    /// Number of log entries whose apply latency is recorded.
+    pub entries: u64,
+
+    /// Latency from local append to local flush.
+    pub flush: PercentileStats,
+
+    /// Latency from local append to quorum acknowledgement.
+    pub commit: PercentileStats,
+
+    /// Latency from commit to apply.
+    pub apply: PercentileStats,
+}
+
+impl fmt::Display for WriteLatencyMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{entries: {}, flush: {}, commit: {}, apply: {}}}",
+            self.entries, self.flush, self.commit, self.apply
+        )
+    }
+}
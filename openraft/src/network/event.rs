@@ -0,0 +1,186 @@
+//! Connection lifecycle events of the replication streams.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::async_runtime::MpscUnboundedSender;
+use crate::error::RPCError;
+use crate::network::RPCTypes;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
+use crate::type_config::alias::MpscUnboundedSenderOf;
+
+/// A change of the connection from the leader to a replication target.
+///
+/// Events are emitted by the replication streams and heartbeat workers on a leader, and are
+/// received by the
+/// application with [`Raft::network_events()`](crate::Raft::network_events), for example to
+/// show "node 2 unreachable since 12:03" in a UI, without scraping logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkEvent<C>
+where C: RaftTypeConfig
+{
+    /// An RPC to `target` succeeded, after the replication stream is started or after the target
+    /// was disconnected.
+    Connected { target: C::NodeId },
+
+    /// An RPC to `target` failed with a network error, after the target was connected.
+    Disconnected { target: C::NodeId, reason: String },
+
+    /// The target is [`Unreachable`](crate::error::Unreachable) and no RPC will be sent to it until
+    /// the backoff interval, as specified by
+    /// [`RaftNetworkV2::backoff()`](crate::network::v2::RaftNetworkV2::backoff), expires.
+    BackoffStarted { target: C::NodeId },
+
+    /// An RPC to `target` did not finish within `timeout`.
+    RpcTimeout {
+        target: C::NodeId,
+        rpc_type: RPCTypes,
+        timeout: Duration,
+    },
+}
+
+impl<C> NetworkEvent<C>
+where C: RaftTypeConfig
+{
+    /// The replication target this event is about.
+    pub fn target(&self) -> &C::NodeId {
+        match self {
+            NetworkEvent::Connected { target } => target,
+            NetworkEvent::Disconnected { target, .. } => target,
+            NetworkEvent::BackoffStarted { target } => target,
+            NetworkEvent::RpcTimeout { target, .. } => target,
+        }
+    }
+}
+
+impl<C> fmt::Display for NetworkEvent<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkEvent::Connected { target } => write!(f, "Connected(target: {})", target),
+            NetworkEvent::Disconnected { target, reason } => {
+                write!(f, "Disconnected(target: {}, reason: {})", target, reason)
+            }
+            NetworkEvent::BackoffStarted { target } => write!(f, "BackoffStarted(target: {})", target),
+            NetworkEvent::RpcTimeout {
+                target,
+                rpc_type,
+                timeout,
+            } => write!(
+                f,
+                "RpcTimeout(target: {}, rpc: {}, timeout: {:?})",
+                target, rpc_type, timeout
+            ),
+        }
+    }
+}
+
+/// Tracks the connection state of every target and delivers [`NetworkEvent`]s to every
+/// subscriber.
+///
+/// It is shared by the replication streams and the heartbeat workers, so that `Connected` and
+/// `Disconnected` are emitted only when the state of a target changes, no matter which of them
+/// sent the RPC, or whether a replication stream is rebuilt.
+/// A subscriber is removed when its receiver is dropped.
+pub(crate) struct NetworkEventBus<C>
+where C: RaftTypeConfig
+{
+    inner: Mutex<BusInner<C>>,
+}
+
+struct BusInner<C>
+where C: RaftTypeConfig
+{
+    /// Whether the last RPC to a target succeeded.
+    connected: BTreeMap<C::NodeId, bool>,
+
+    /// The error of the last RPC to a target, removed when an RPC to it succeeds.
+    last_error: BTreeMap<C::NodeId, String>,
+    subscribers: Vec<MpscUnboundedSenderOf<C, NetworkEvent<C>>>,
+}
+
+impl<C> NetworkEventBus<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Mutex::new(BusInner {
+                connected: BTreeMap::new(),
+                last_error: BTreeMap::new(),
+                subscribers: vec![],
+            }),
+        }
+    }
+
+    /// Add a subscriber that receives all events emitted after this call.
+    pub(crate) fn subscribe(&self) -> MpscUnboundedReceiverOf<C, NetworkEvent<C>> {
+        let (tx, rx) = C::mpsc_unbounded();
+        self.inner.lock().unwrap().subscribers.push(tx);
+        rx
+    }
+
+    /// An RPC to `target` succeeded.
+    pub(crate) fn on_success(&self, target: &C::NodeId) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.last_error.remove(target);
+
+        let prev = inner.connected.insert(target.clone(), true);
+        if prev != Some(true) {
+            inner.emit(NetworkEvent::Connected { target: target.clone() });
+        }
+    }
+
+    /// An RPC to `target` failed with `err`.
+    pub(crate) fn on_error(&self, target: &C::NodeId, err: &RPCError<C>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        inner.last_error.insert(target.clone(), err.to_string());
+
+        match err {
+            RPCError::Timeout(timeout) => {
+                inner.emit(NetworkEvent::RpcTimeout {
+                    target: target.clone(),
+                    rpc_type: timeout.action,
+                    timeout: timeout.timeout,
+                });
+            }
+            RPCError::Unreachable(_) | RPCError::Network(_) => {
+                let prev = inner.connected.insert(target.clone(), false);
+                if prev == Some(true) {
+                    inner.emit(NetworkEvent::Disconnected {
+                        target: target.clone(),
+                        reason: err.to_string(),
+                    });
+                }
+            }
+            // The remote received the RPC
+            RPCError::PayloadTooLarge(_) | RPCError::RemoteError(_) => {}
+        }
+    }
+
+    /// The error of the last RPC to `target`, `None` if it succeeded or no RPC failed.
+    pub(crate) fn last_error(&self, target: &C::NodeId) -> Option<String> {
+        self.inner.lock().unwrap().last_error.get(target).cloned()
+    }
+
+    /// The replication to `target` starts to back off.
+    pub(crate) fn on_backoff(&self, target: &C::NodeId) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.emit(NetworkEvent::BackoffStarted { target: target.clone() });
+    }
+}
+
+impl<C> BusInner<C>
+where C: RaftTypeConfig
+{
+    fn emit(&mut self, event: NetworkEvent<C>) {
+        tracing::info!("network event: {}", event);
+        self.subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
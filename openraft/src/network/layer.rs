@@ -0,0 +1,437 @@
+//! Compose middleware around the RPCs sent by a [`RaftNetworkV2`] implementation.
+//!
+//! A [`NetworkLayer`] intercepts every RPC that Openraft sends to a target node, so that
+//! cross-cutting concerns, such as retry, timeout, metrics, tracing or authentication, are
+//! implemented once and shared by all network implementations, instead of being re-implemented in
+//! every method of every [`RaftNetworkV2`].
+//!
+//! Layers are added to a [`RaftNetworkFactory`] with [`LayeredNetworkFactory`]. The layer added
+//! last is the outermost one and sees an RPC first:
+//!
+//! ```ignore
+//! let network = LayeredNetworkFactory::new(MyNetworkFactory::new())
+//!     .layer(MetricsLayer::default())
+//!     .layer(TracingLayer);
+//!
+//! let raft = Raft::new(id, config, network, log_store, state_machine).await?;
+//! ```
+//!
+//! A layer passes an RPC on to the next layer, or to the base network, with
+//! [`PendingRpc::send()`]:
+//!
+//! ```ignore
+//! impl<C: RaftTypeConfig> NetworkLayer<C> for MetricsLayer {
+//!     fn call<'a>(&'a self, rpc: &'a mut dyn PendingRpc<C>, option: RPCOption)
+//!         -> BoxFuture<'a, Result<(), RPCError<C>>> {
+//!         Box::pin(async move {
+//!             let start = Instant::now();
+//!             let res = rpc.send(option).await;
+//!             self.record(rpc.target(), rpc.rpc_type(), start.elapsed(), res.is_ok());
+//!             res
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! [`RaftNetworkV2`]: crate::network::v2::RaftNetworkV2
+
+use std::future::Future;
+use std::sync::Arc;
+
+use anyerror::AnyError;
+use openraft_macros::add_async_trait;
+use openraft_macros::since;
+
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::base::BoxFuture;
+use crate::error::NetworkError;
+use crate::error::RPCError;
+use crate::error::ReplicationClosed;
+use crate::error::StreamingError;
+use crate::error::Unreachable;
+use crate::network::RPCOption;
+use crate::network::RPCTypes;
+use crate::network::RaftNetworkFactory;
+use crate::network::v2::RaftNetworkV2;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::ApplyResultsRequest;
+use crate::raft::DecommissionRequest;
+use crate::raft::HelloRequest;
+use crate::raft::LogRepairRequest;
+use crate::raft::NodeInfo;
+use crate::raft::SnapshotResponse;
+use crate::raft::StateMachineChecksumRequest;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::raft::message::TransferLeaderRequest;
+use crate::storage::Snapshot;
+use crate::type_config::alias::VoteOf;
+
+/// A middleware that intercepts every RPC sent to a target node.
+///
+/// An implementation usually does something before and after passing the RPC on with
+/// [`PendingRpc::send()`], such as recording metrics, sends it again if it fails and
+/// [`PendingRpc::can_send()`] still returns `true`, or rejects it without sending, such as when a
+/// rate limit is reached.
+///
+/// Openraft applies its own timeout to every RPC, which also bounds the time spent in all layers.
+#[since(version = "0.10.0")]
+pub trait NetworkLayer<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Send `rpc` to the next layer, or to the base network, and return the result.
+    ///
+    /// Returning `Ok(())` without a successful [`PendingRpc::send()`] is reported as an
+    /// [`Unreachable`] error to Openraft.
+    fn call<'a>(&'a self, rpc: &'a mut dyn PendingRpc<C>, option: RPCOption) -> BoxFuture<'a, Result<(), RPCError<C>>>;
+}
+
+/// An RPC passed through [`NetworkLayer`]s before it is sent by the base network.
+#[since(version = "0.10.0")]
+pub trait PendingRpc<C>: OptionalSend
+where C: RaftTypeConfig
+{
+    /// The node this RPC is sent to.
+    fn target(&self) -> &C::NodeId;
+
+    /// The type of this RPC.
+    fn rpc_type(&self) -> RPCTypes;
+
+    /// Returns whether [`send()`](Self::send) can still be called.
+    ///
+    /// An RPC whose request can not be copied, such as an `AppendEntries` with log entries or a
+    /// snapshot, can only be sent once. Other RPCs can be sent again, for example by a retry
+    /// layer.
+    fn can_send(&self) -> bool;
+
+    /// Send this RPC with the next layer, or with the base network.
+    ///
+    /// The response is kept by Openraft and is not visible to a layer. An error that is not an
+    /// [`RPCError`], such as a closed snapshot transmission, is reported as a [`NetworkError`].
+    fn send(&mut self, option: RPCOption) -> BoxFuture<'_, Result<(), RPCError<C>>>;
+}
+
+/// A [`RaftNetworkFactory`] that applies [`NetworkLayer`]s to every RPC sent by the networks
+/// built by another factory.
+#[since(version = "0.10.0")]
+pub struct LayeredNetworkFactory<C, F>
+where C: RaftTypeConfig
+{
+    inner: F,
+    layers: Vec<Arc<dyn NetworkLayer<C>>>,
+}
+
+impl<C, F> LayeredNetworkFactory<C, F>
+where C: RaftTypeConfig
+{
+    /// Create a factory without any layer, that builds networks with `inner`.
+    pub fn new(inner: F) -> Self {
+        Self { inner, layers: vec![] }
+    }
+
+    /// Add a layer outside of all the layers added before.
+    pub fn layer(mut self, layer: impl NetworkLayer<C>) -> Self {
+        self.layers.insert(0, Arc::new(layer));
+        self
+    }
+}
+
+impl<C, F> RaftNetworkFactory<C> for LayeredNetworkFactory<C, F>
+where
+    C: RaftTypeConfig,
+    F: RaftNetworkFactory<C>,
+{
+    type Network = F::Network;
+
+    async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network {
+        self.inner.new_client(target, node).await
+    }
+
+    fn network_layers(&self) -> Vec<Arc<dyn NetworkLayer<C>>> {
+        let mut layers = self.layers.clone();
+        layers.extend(self.inner.network_layers());
+        layers
+    }
+}
+
+/// The layers applied to every RPC sent by this node, the outermost first.
+#[derive(Clone)]
+pub(crate) struct NetworkLayers<C>
+where C: RaftTypeConfig
+{
+    layers: Arc<[Arc<dyn NetworkLayer<C>>]>,
+}
+
+impl<C> NetworkLayers<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(layers: Vec<Arc<dyn NetworkLayer<C>>>) -> Self {
+        Self { layers: layers.into() }
+    }
+
+    /// Send `rpc` to `target` with `network`, through all the layers.
+    pub(crate) async fn call<N, R>(
+        &self,
+        target: &C::NodeId,
+        network: &mut N,
+        rpc: R,
+        option: RPCOption,
+    ) -> Result<R::Response, R::Error>
+    where
+        N: RaftNetworkV2<C>,
+        R: NetworkRpc<C>,
+    {
+        if self.layers.is_empty() {
+            return rpc.send(network, option).await;
+        }
+
+        let mut base = BaseRpc {
+            target,
+            network,
+            rpc: Some(rpc),
+            result: None,
+        };
+
+        let res = {
+            let mut chain = Chain {
+                layers: &self.layers,
+                inner: &mut base,
+            };
+            chain.send(option).await
+        };
+
+        match (res, base.result) {
+            (Ok(()), Some(result)) => result,
+            (Ok(()), None) => Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+                "network layer returned without sending the RPC",
+            )))
+            .into()),
+            (Err(_), Some(Err(e))) => Err(e),
+            (Err(e), _) => Err(e.into()),
+        }
+    }
+}
+
+/// Passes an RPC to the first of `layers`, which then passes it to the rest of them.
+struct Chain<'a, C>
+where C: RaftTypeConfig
+{
+    layers: &'a [Arc<dyn NetworkLayer<C>>],
+    inner: &'a mut dyn PendingRpc<C>,
+}
+
+impl<C> PendingRpc<C> for Chain<'_, C>
+where C: RaftTypeConfig
+{
+    fn target(&self) -> &C::NodeId {
+        self.inner.target()
+    }
+
+    fn rpc_type(&self) -> RPCTypes {
+        self.inner.rpc_type()
+    }
+
+    fn can_send(&self) -> bool {
+        self.inner.can_send()
+    }
+
+    fn send(&mut self, option: RPCOption) -> BoxFuture<'_, Result<(), RPCError<C>>> {
+        match self.layers.split_first() {
+            None => self.inner.send(option),
+            Some((first, rest)) => Box::pin(async move {
+                let mut next = Chain {
+                    layers: rest,
+                    inner: &mut *self.inner,
+                };
+                first.call(&mut next, option).await
+            }),
+        }
+    }
+}
+
+/// The innermost [`PendingRpc`] that sends the request with the base network and keeps the
+/// result.
+struct BaseRpc<'a, C, N, R>
+where
+    C: RaftTypeConfig,
+    R: NetworkRpc<C>,
+{
+    target: &'a C::NodeId,
+    network: &'a mut N,
+    rpc: Option<R>,
+    result: Option<Result<R::Response, R::Error>>,
+}
+
+impl<C, N, R> PendingRpc<C> for BaseRpc<'_, C, N, R>
+where
+    C: RaftTypeConfig,
+    N: RaftNetworkV2<C>,
+    R: NetworkRpc<C>,
+{
+    fn target(&self) -> &C::NodeId {
+        self.target
+    }
+
+    fn rpc_type(&self) -> RPCTypes {
+        R::RPC_TYPE
+    }
+
+    fn can_send(&self) -> bool {
+        self.rpc.is_some()
+    }
+
+    fn send(&mut self, option: RPCOption) -> BoxFuture<'_, Result<(), RPCError<C>>> {
+        Box::pin(async move {
+            let Some(rpc) = self.rpc.take() else {
+                return Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+                    "RPC can not be sent again",
+                ))));
+            };
+            self.rpc = rpc.try_clone();
+
+            let result = rpc.send(self.network, option).await;
+            let res = match &result {
+                Ok(_) => Ok(()),
+                Err(e) => Err(R::to_rpc_error(e)),
+            };
+            self.result = Some(result);
+            res
+        })
+    }
+}
+
+/// A request of a [`RaftNetworkV2`] method.
+#[add_async_trait]
+pub(crate) trait NetworkRpc<C>: OptionalSend + Sized + 'static
+where C: RaftTypeConfig
+{
+    type Response: OptionalSend;
+    type Error: From<RPCError<C>> + OptionalSend;
+
+    const RPC_TYPE: RPCTypes;
+
+    /// Returns a copy to send this request again, or `None` if it can not be copied.
+    fn try_clone(&self) -> Option<Self>;
+
+    /// Convert an error of this request to the one seen by a [`NetworkLayer`].
+    fn to_rpc_error(e: &Self::Error) -> RPCError<C>;
+
+    async fn send<N>(self, network: &mut N, option: RPCOption) -> Result<Self::Response, Self::Error>
+    where N: RaftNetworkV2<C>;
+}
+
+/// The arguments of [`RaftNetworkV2::full_snapshot()`].
+pub(crate) struct FullSnapshotRpc<C, F>
+where C: RaftTypeConfig
+{
+    pub(crate) vote: VoteOf<C>,
+    pub(crate) snapshot: Snapshot<C>,
+    pub(crate) cancel: F,
+}
+
+impl<C, F> NetworkRpc<C> for FullSnapshotRpc<C, F>
+where
+    C: RaftTypeConfig,
+    F: Future<Output = ReplicationClosed> + OptionalSend + 'static,
+{
+    type Response = SnapshotResponse<C>;
+    type Error = StreamingError<C>;
+
+    const RPC_TYPE: RPCTypes = RPCTypes::InstallSnapshot;
+
+    fn try_clone(&self) -> Option<Self> {
+        None
+    }
+
+    fn to_rpc_error(e: &Self::Error) -> RPCError<C> {
+        match e {
+            StreamingError::Timeout(e) => RPCError::Timeout(e.clone()),
+            StreamingError::Unreachable(e) => RPCError::Unreachable(e.clone()),
+            StreamingError::Network(e) => RPCError::Network(e.clone()),
+            StreamingError::Closed(_) | StreamingError::StorageError(_) => RPCError::Network(NetworkError::new(e)),
+        }
+    }
+
+    async fn send<N>(self, network: &mut N, option: RPCOption) -> Result<Self::Response, Self::Error>
+    where N: RaftNetworkV2<C> {
+        network.full_snapshot(self.vote, self.snapshot, self.cancel, option).await
+    }
+}
+
+impl<C> NetworkRpc<C> for AppendEntriesRequest<C>
+where C: RaftTypeConfig
+{
+    type Response = AppendEntriesResponse<C>;
+    type Error = RPCError<C>;
+
+    const RPC_TYPE: RPCTypes = RPCTypes::AppendEntries;
+
+    /// Only a heartbeat can be copied, because log entries are not required to be `Clone`.
+    fn try_clone(&self) -> Option<Self> {
+        if !self.entries.is_empty() {
+            return None;
+        }
+
+        Some(AppendEntriesRequest {
+            vote: self.vote.clone(),
+            prev_log_id: self.prev_log_id.clone(),
+            entries: vec![],
+            leader_commit: self.leader_commit.clone(),
+            cluster_name: self.cluster_name.clone(),
+            trace_ids: self.trace_ids.clone(),
+            log_checksum: self.log_checksum.clone(),
+        })
+    }
+
+    fn to_rpc_error(e: &Self::Error) -> RPCError<C> {
+        e.clone()
+    }
+
+    async fn send<N>(self, network: &mut N, option: RPCOption) -> Result<Self::Response, Self::Error>
+    where N: RaftNetworkV2<C> {
+        network.append_entries(self, option).await
+    }
+}
+
+/// Implement [`NetworkRpc`] for a `Clone` request sent with the method `$method`.
+macro_rules! impl_network_rpc {
+    ($req:ident, $resp:ty, $rpc_type:ident, $method:ident) => {
+        impl<C> NetworkRpc<C> for $req<C>
+        where C: RaftTypeConfig
+        {
+            type Response = $resp;
+            type Error = RPCError<C>;
+
+            const RPC_TYPE: RPCTypes = RPCTypes::$rpc_type;
+
+            fn try_clone(&self) -> Option<Self> {
+                Some(self.clone())
+            }
+
+            fn to_rpc_error(e: &Self::Error) -> RPCError<C> {
+                e.clone()
+            }
+
+            async fn send<N>(self, network: &mut N, option: RPCOption) -> Result<Self::Response, Self::Error>
+            where N: RaftNetworkV2<C> {
+                network.$method(self, option).await
+            }
+        }
+    };
+}
+
+impl_network_rpc!(VoteRequest, VoteResponse<C>, Vote, vote);
+impl_network_rpc!(TransferLeaderRequest, (), TransferLeader, transfer_leader);
+impl_network_rpc!(DecommissionRequest, (), Decommission, decommission);
+impl_network_rpc!(
+    StateMachineChecksumRequest,
+    Option<u64>,
+    StateMachineChecksum,
+    state_machine_checksum
+);
+impl_network_rpc!(LogRepairRequest, Vec<C::Entry>, RepairLog, repair_log);
+impl_network_rpc!(ApplyResultsRequest, (), ApplyResults, apply_results);
+impl_network_rpc!(HelloRequest, NodeInfo, Hello, hello);
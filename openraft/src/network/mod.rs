@@ -7,12 +7,15 @@
 //! - [`RaftNetwork`] - Protocol for sending Raft RPCs (AppendEntries, Vote, InstallSnapshot)
 //! - [`RaftNetworkFactory`] - Factory for creating network connections to target nodes
 //! - [`v2::RaftNetworkV2`] - Alternative protocol with full snapshot support
+//! - [`layer::NetworkLayer`] - Middleware around every RPC sent to a target node
 //!
 //! ## Key Types
 //!
 //! - [`Backoff`] - Backoff strategy for retrying failed network operations
+//! - [`NetworkEvent`] - Connection lifecycle events of replication streams
 //! - [`RPCOption`] - Options for configuring RPC behavior
 //! - [`RPCTypes`] - Type definitions for RPC requests and responses
+//! - `auth::Signed` - Signed RPC payloads for authenticating peers, with feature `serde`
 //!
 //! ## Usage
 //!
@@ -24,15 +27,22 @@
 //! details and examples.
 
 mod backoff;
+mod event;
 mod rpc_option;
 mod rpc_type;
 
+pub mod layer;
 pub mod v1;
 pub mod v2;
 
+#[cfg(feature = "serde")]
+pub mod auth;
+
 pub mod snapshot_transport;
 
 pub use backoff::Backoff;
+pub use event::NetworkEvent;
+pub(crate) use event::NetworkEventBus;
 pub use rpc_option::RPCOption;
 pub use rpc_type::RPCTypes;
 pub use v1::RaftNetwork;
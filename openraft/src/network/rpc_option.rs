@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use openraft_macros::since;
+
 /// An additional argument to the [`RaftNetwork`] methods to allow applications to customize
 /// networking behaviors.
 ///
@@ -13,6 +15,15 @@ pub struct RPCOption {
 
     /// The size of the snapshot chunk.
     pub(crate) snapshot_chunk_size: Option<usize>,
+
+    /// The cluster name of the sender.
+    pub(crate) cluster_name: Option<String>,
+
+    /// The trace ids of the client requests this RPC is sent for.
+    pub(crate) trace_ids: Vec<String>,
+
+    /// The compression scheme to send the payload with.
+    pub(crate) compression: Option<String>,
 }
 
 impl RPCOption {
@@ -21,6 +32,9 @@ impl RPCOption {
         Self {
             hard_ttl,
             snapshot_chunk_size: None,
+            cluster_name: None,
+            trace_ids: vec![],
+            compression: None,
         }
     }
 
@@ -52,4 +66,40 @@ impl RPCOption {
     pub fn snapshot_chunk_size(&self) -> Option<usize> {
         self.snapshot_chunk_size
     }
+
+    /// Get the [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// It is set when sending a snapshot. An implementation of
+    /// [`RaftNetworkV2::full_snapshot`](crate::network::v2::RaftNetworkV2::full_snapshot) that
+    /// uses its own transport should send it along, so that the receiver can check it with
+    /// [`Raft::check_cluster_name`](crate::Raft::check_cluster_name).
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+
+    /// Get the trace ids of the client requests this RPC is sent for.
+    ///
+    /// They are the ids passed to [`Raft::client_write_with_trace_id`] for the entries an
+    /// `AppendEntries` RPC replicates, or to [`Raft::read_with_trace_id`] for the reads a heartbeat
+    /// confirms the leadership for. A network implementation may send them along, e.g., in a
+    /// request header, so that a client request can be followed across nodes in the logs.
+    ///
+    /// [`Raft::client_write_with_trace_id`]: crate::Raft::client_write_with_trace_id
+    /// [`Raft::read_with_trace_id`]: crate::Raft::read_with_trace_id
+    pub fn trace_ids(&self) -> &[String] {
+        &self.trace_ids
+    }
+
+    /// Get the compression scheme to send the AppendEntries payload with, if it should be
+    /// compressed.
+    ///
+    /// It is one of the [`Config::compression`](crate::Config::compression) schemes that the
+    /// target node supports too, and it is set only if the size of the entries reaches
+    /// [`Config::compression_threshold`](crate::Config::compression_threshold). The network
+    /// implementation compresses the encoded payload with it, and the target decompresses it
+    /// before passing it to [`Raft::append_entries()`](crate::Raft::append_entries).
+    #[since(version = "0.10.0")]
+    pub fn compression(&self) -> Option<&str> {
+        self.compression.as_deref()
+    }
 }
@@ -14,6 +14,16 @@ pub enum RPCTypes {
     InstallSnapshot,
     /// TransferLeader request RPC.
     TransferLeader,
+    /// Decommission request RPC.
+    Decommission,
+    /// State machine checksum request RPC.
+    StateMachineChecksum,
+    /// Log repair request RPC.
+    RepairLog,
+    /// Apply results streaming RPC.
+    ApplyResults,
+    /// Node identification RPC.
+    Hello,
 }
 
 impl fmt::Display for RPCTypes {
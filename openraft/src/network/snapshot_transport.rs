@@ -92,6 +92,7 @@ mod tokio_rt {
                     offset,
                     data: buf,
                     done,
+                    cluster_name: option.cluster_name().map(|x| x.to_string()),
                 };
 
                 // Send the RPC over to the target.
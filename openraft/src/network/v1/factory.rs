@@ -1,8 +1,12 @@
+use std::sync::Arc;
+
 use openraft_macros::add_async_trait;
+use openraft_macros::since;
 
 use crate::OptionalSend;
 use crate::OptionalSync;
 use crate::RaftTypeConfig;
+use crate::network::layer::NetworkLayer;
 use crate::network::v2::RaftNetworkV2;
 
 /// A trait defining the interface for a Raft network factory to create connections between cluster
@@ -36,4 +40,16 @@ where C: RaftTypeConfig
     /// The method is intentionally async to give the implementation a chance to use asynchronous
     /// sync primitives to serialize access to the common internal object, if needed.
     async fn new_client(&mut self, target: C::NodeId, node: &C::Node) -> Self::Network;
+
+    /// The [`NetworkLayer`]s applied to every RPC sent by the networks of this factory, the
+    /// outermost first.
+    ///
+    /// It is called once when the [`Raft`](crate::Raft) is created. By default, there is no layer.
+    /// Use [`LayeredNetworkFactory`] to add layers to a factory.
+    ///
+    /// [`LayeredNetworkFactory`]: crate::network::layer::LayeredNetworkFactory
+    #[since(version = "0.10.0")]
+    fn network_layers(&self) -> Vec<Arc<dyn NetworkLayer<C>>> {
+        vec![]
+    }
 }
@@ -16,7 +16,13 @@ use crate::network::Backoff;
 use crate::network::RPCOption;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::ApplyResultsRequest;
+use crate::raft::DecommissionRequest;
+use crate::raft::HelloRequest;
+use crate::raft::LogRepairRequest;
+use crate::raft::NodeInfo;
 use crate::raft::SnapshotResponse;
+use crate::raft::StateMachineChecksumRequest;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
 use crate::raft::message::TransferLeaderRequest;
@@ -100,6 +106,105 @@ where C: RaftTypeConfig
         ))))
     }
 
+    /// Send Decommission message to a node that has been removed from the cluster.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_decommission()`], which
+    /// shuts the node down, and return an error if the request is rejected, so that the leader
+    /// does not report the node as shut down.
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error to
+    /// ignore it. In case the application did not implement it, the application has to stop the
+    /// removed node by itself.
+    ///
+    /// [`Raft::handle_decommission()`]: crate::raft::Raft::handle_decommission
+    #[since(version = "0.10.0")]
+    async fn decommission(&mut self, _req: DecommissionRequest<C>, _option: RPCOption) -> Result<(), RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "decommission not implemented",
+        ))))
+    }
+
+    /// Send a request for the checksum of the state machine to a member of the cluster.
+    ///
+    /// The node received this message should pass it to
+    /// [`Raft::handle_state_machine_checksum()`] and return the checksum it returns.
+    ///
+    /// This method provides a default implementation that just returns [`Unreachable`] error. In
+    /// case the application did not implement it, only the checksum of the leader is reported by
+    /// [`Raft::state_machine_checksums()`].
+    ///
+    /// [`Raft::handle_state_machine_checksum()`]: crate::raft::Raft::handle_state_machine_checksum
+    /// [`Raft::state_machine_checksums()`]: crate::raft::Raft::state_machine_checksums
+    #[since(version = "0.10.0")]
+    async fn state_machine_checksum(
+        &mut self,
+        _req: StateMachineChecksumRequest<C>,
+        _option: RPCOption,
+    ) -> Result<Option<u64>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "state_machine_checksum not implemented",
+        ))))
+    }
+
+    /// Send a request for a copy of committed log entries to the Leader, when this follower cannot
+    /// read them from its own log store.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_repair_log()`] and return
+    /// the entries it returns.
+    ///
+    /// It is only called if [`Config::repair_corrupted_log`] is enabled. This method provides a
+    /// default implementation that just returns [`Unreachable`] error, and the follower keeps
+    /// retrying until it is shut down.
+    ///
+    /// [`Raft::handle_repair_log()`]: crate::raft::Raft::handle_repair_log
+    /// [`Config::repair_corrupted_log`]: crate::Config::repair_corrupted_log
+    #[since(version = "0.10.0")]
+    async fn repair_log(
+        &mut self,
+        _req: LogRepairRequest<C>,
+        _option: RPCOption,
+    ) -> Result<Vec<C::Entry>, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "repair_log not implemented",
+        ))))
+    }
+
+    /// Send the results computed by the state machine of the Leader to a follower.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_apply_results()`].
+    ///
+    /// It is only called if [`Config::apply_results_wait`] is enabled. This method provides a
+    /// default implementation that just returns [`Unreachable`] error, and followers compute the
+    /// results themselves after waiting for them.
+    ///
+    /// [`Raft::handle_apply_results()`]: crate::raft::Raft::handle_apply_results
+    /// [`Config::apply_results_wait`]: crate::Config::apply_results_wait
+    #[since(version = "0.10.0")]
+    async fn apply_results(&mut self, _req: ApplyResultsRequest<C>, _option: RPCOption) -> Result<(), RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "apply_results not implemented",
+        ))))
+    }
+
+    /// Send the software version and the capabilities of this node to the target node, and
+    /// receive the ones of the target node.
+    ///
+    /// The node received this message should pass it to [`Raft::handle_hello()`].
+    ///
+    /// The Leader sends it to every replication target whose [`NodeInfo`] is not known yet, and
+    /// reports the result in [`RaftMetrics::node_infos`]. This method provides a default
+    /// implementation that just returns [`Unreachable`] error, and the info of the target stays
+    /// unknown.
+    ///
+    /// [`Raft::handle_hello()`]: crate::raft::Raft::handle_hello
+    /// [`RaftMetrics::node_infos`]: crate::RaftMetrics::node_infos
+    #[since(version = "0.10.0")]
+    async fn hello(&mut self, _req: HelloRequest<C>, _option: RPCOption) -> Result<NodeInfo, RPCError<C>> {
+        Err(RPCError::Unreachable(Unreachable::new(&AnyError::error(
+            "hello not implemented",
+        ))))
+    }
+
     /// Build a backoff instance if the target node is temporarily(or permanently) unreachable.
     ///
     /// When a [`Unreachable`](`crate::error::Unreachable`) error is returned from the `Network`
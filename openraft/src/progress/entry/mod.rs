@@ -42,6 +42,21 @@ where C: RaftTypeConfig
     ///
     /// This flag will be cleared after the progress entry is reset.
     pub(crate) allow_log_reversion: bool,
+
+    /// If true, replicate by snapshot even if the logs the follower needs are not purged.
+    ///
+    /// It is set by a resync requested by the application, and cleared once the follower
+    /// acknowledges a snapshot.
+    pub(crate) force_snapshot: bool,
+
+    /// The number of log reversions reported by the target since it last acknowledged logs.
+    pub(crate) reversions: u64,
+
+    /// If true, the target is considered diverged and nothing is replicated to it.
+    ///
+    /// It is set when the target reports too many log reversions while reversion is not allowed,
+    /// and cleared by a resync or by allowing the next reversion.
+    pub(crate) quarantined: bool,
 }
 
 impl<C> ProgressEntry<C>
@@ -54,6 +69,9 @@ where C: RaftTypeConfig
             inflight: Inflight::None,
             searching_end: matching.next_index(),
             allow_log_reversion: false,
+            force_snapshot: false,
+            reversions: 0,
+            quarantined: false,
         }
     }
 
@@ -66,6 +84,9 @@ where C: RaftTypeConfig
             inflight: Inflight::None,
             searching_end: end,
             allow_log_reversion: false,
+            force_snapshot: false,
+            reversions: 0,
+            quarantined: false,
         }
     }
 
@@ -117,6 +138,10 @@ where C: RaftTypeConfig
             return Err(&self.inflight);
         }
 
+        if self.quarantined {
+            return Err(&self.inflight);
+        }
+
         let last_next = log_state.last_log_id().next_index();
         debug_assert!(
             self.searching_end <= last_next,
@@ -132,6 +157,15 @@ where C: RaftTypeConfig
 
         // `searching_end` is the max value for `start`.
 
+        // A resync is requested. Wait for a snapshot to be built if there is none.
+        if self.force_snapshot {
+            let Some(snapshot_last) = log_state.snapshot_last_log_id() else {
+                return Err(&self.inflight);
+            };
+            self.inflight = Inflight::snapshot(Some(snapshot_last.clone()));
+            return Ok(&self.inflight);
+        }
+
         // The log the follower needs is purged.
         // Replicate by snapshot.
         if self.searching_end < purge_upto_next {
@@ -162,6 +196,20 @@ where C: RaftTypeConfig
         Ok(&self.inflight)
     }
 
+    /// Return the max number of log entries to send to the target in one replication action.
+    ///
+    /// Once the last matching log id is found, the replication stream splits the logs into up to
+    /// `max_inflight` AppendEntries RPCs of `max_payload_entries` each and sends them without
+    /// waiting for responses. While still searching, a conflict would waste the pipelined
+    /// payloads, thus only one RPC is sent.
+    pub(crate) fn max_entries_to_send(&self, max_payload_entries: u64, max_inflight: u64) -> u64 {
+        if self.matching().next_index() < self.searching_end {
+            max_payload_entries
+        } else {
+            max_payload_entries * max_inflight
+        }
+    }
+
     /// Return the index range (`[start,end]`) of the first log in the next AppendEntries.
     ///
     /// The returned range is left close and right close.
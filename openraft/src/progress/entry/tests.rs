@@ -88,6 +88,59 @@ fn test_update_conflicting() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_update_conflicting_reverted_quarantine() -> anyhow::Result<()> {
+    let mut pe = ProgressEntry::<UTConfig>::empty(20);
+    pe.matching = Some(log_id(10));
+
+    let engine_config = EngineConfig::new_default(1);
+
+    for i in 1..=2 {
+        pe.inflight = inflight_logs(10, 11);
+        pe.new_updater(&engine_config).update_conflicting(5, true);
+
+        assert_eq!(&Some(log_id(10)), pe.borrow(), "matching is kept");
+        assert_eq!(11, pe.searching_end);
+        assert_eq!(i, pe.reversions);
+        assert!(!pe.quarantined);
+    }
+
+    // An ack resets the count
+    pe.inflight = inflight_logs(10, 11);
+    pe.new_updater(&engine_config).update_matching(Some(log_id(11)));
+    assert_eq!(0, pe.reversions);
+
+    for _ in 0..3 {
+        pe.inflight = inflight_logs(11, 12);
+        pe.new_updater(&engine_config).update_conflicting(5, true);
+    }
+    assert_eq!(3, pe.reversions);
+    assert!(pe.quarantined);
+
+    // Nothing is sent to a quarantined target
+    let res = pe.next_send(&LogState::new(6, 10, 20), 100);
+    assert_eq!(Err(&Inflight::None), res);
+
+    Ok(())
+}
+
+#[test]
+fn test_max_entries_to_send() -> anyhow::Result<()> {
+    // Searching for the matching log id
+    let mut pe = ProgressEntry::<UTConfig>::empty(20);
+    assert_eq!(10, pe.max_entries_to_send(10, 4));
+
+    pe.matching = Some(log_id(5));
+    assert_eq!(10, pe.max_entries_to_send(10, 4));
+
+    // The matching log id is found
+    pe.searching_end = 6;
+    assert_eq!(40, pe.max_entries_to_send(10, 4));
+    assert_eq!(10, pe.max_entries_to_send(10, 1));
+
+    Ok(())
+}
+
 /// LogStateReader impl for testing
 struct LogState {
     last: Option<LogIdOf<UTConfig>>,
@@ -32,9 +32,11 @@ where C: RaftTypeConfig
     /// when follower data is intact. However, for testing purposes, a follower may clean its
     /// data and require the leader to replicate all data from the beginning.
     ///
-    /// To allow follower log reversion, enable [`Config::allow_log_reversion`].
+    /// To allow follower log reversion, enable [`Config::allow_log_reversion`]. Otherwise, the
+    /// follower is quarantined after [`Config::quarantine_after_log_reversions`] reversions.
     ///
     /// [`Config::allow_log_reversion`]: `crate::config::Config::allow_log_reversion`
+    /// [`Config::quarantine_after_log_reversions`]: `crate::config::Config::quarantine_after_log_reversions`
     pub(crate) fn update_conflicting(&mut self, conflict: u64, has_payload: bool) {
         tracing::debug!(
             "update_conflict: current progress_entry: {}; conflict: {}",
@@ -61,32 +63,48 @@ where C: RaftTypeConfig
         // An already matching log id is found lost:
         //
         // - If log reversion is allowed, just restart the binary search from the beginning.
-        // - Otherwise, panic it.
+        // - Otherwise, the follower is diverged: keep the progress and quarantine the follower if it keeps
+        //   reporting reversions.
 
         let allow_reset = self.entry.allow_log_reversion || self.engine_config.allow_log_reversion;
 
+        if conflict >= self.entry.matching().next_index() {
+            return;
+        }
+
         if allow_reset {
-            if conflict < self.entry.matching().next_index() {
-                tracing::warn!(
-                    "conflict {} < last matching {}: \
-                    follower log is reverted; \
-                    with 'allow_log_reversion' enabled, this is allowed.",
-                    conflict,
-                    self.entry.matching().display(),
-                );
+            tracing::warn!(
+                "conflict {} < last matching {}: \
+                follower log is reverted; \
+                with 'allow_log_reversion' enabled, this is allowed.",
+                conflict,
+                self.entry.matching().display(),
+            );
 
-                self.entry.matching = None;
-                self.entry.allow_log_reversion = false;
-            }
+            self.entry.matching = None;
+            self.entry.allow_log_reversion = false;
         } else {
-            debug_assert!(
-                conflict >= self.entry.matching().next_index(),
-                "follower log reversion is not allowed \
-                without `allow_log_reversion` enabled; \
-                matching: {}; conflict: {}",
+            // The matching log can not be searched again, keep the search range valid.
+            self.entry.searching_end = self.entry.matching().next_index();
+            self.entry.reversions += 1;
+
+            tracing::warn!(
+                "conflict {} < last matching {}: \
+                follower log is reverted, which is not allowed \
+                without `allow_log_reversion` enabled; reversions: {}",
+                conflict,
                 self.entry.matching().display(),
-                conflict
+                self.entry.reversions,
             );
+
+            if self.entry.reversions >= self.engine_config.quarantine_after_log_reversions {
+                tracing::error!(
+                    "follower is diverged after {} log reversions, quarantine it; \
+                    resync it or allow the next reversion to resume replication",
+                    self.entry.reversions
+                );
+                self.entry.quarantined = true;
+            }
         }
     }
 
@@ -97,7 +115,11 @@ where C: RaftTypeConfig
             matching.display()
         );
 
+        if self.entry.inflight.is_sending_snapshot() {
+            self.entry.force_snapshot = false;
+        }
         self.entry.inflight.ack(matching.clone());
+        self.entry.reversions = 0;
 
         debug_assert!(matching.as_ref() >= self.entry.matching());
         self.entry.matching = matching;
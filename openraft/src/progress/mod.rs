@@ -259,6 +259,50 @@ where
         0
     }
 
+    /// Move an element at `index` down so that all the values greater than `committed` stay
+    /// sorted in front of the others, after its value is decreased.
+    #[inline(always)]
+    fn move_down(&mut self, index: usize) -> usize
+    where P: PartialOrd {
+        self.stat.move_count += 1;
+        for i in index..self.voter_count.saturating_sub(1) {
+            if self.vector[i].1.borrow() < self.vector[i + 1].1.borrow() {
+                self.vector.swap(i, i + 1);
+            } else {
+                return i;
+            }
+        }
+
+        self.voter_count.saturating_sub(1).max(index)
+    }
+
+    /// Update the value of `id` with `f`, which is allowed to decrease it, e.g., when the progress
+    /// of a follower is reset.
+    ///
+    /// The granted value is not decreased: it is already committed.
+    ///
+    /// It returns `None` if the `id` is not found.
+    pub(crate) fn reset_with<F>(&mut self, id: &ID, f: F) -> Option<&V>
+    where
+        ID: PartialEq,
+        P: PartialOrd,
+        F: FnOnce(&mut V),
+    {
+        self.stat.update_count += 1;
+
+        let index = self.index(id)?;
+        f(&mut self.vector[index].1);
+
+        // Learner elements are always still.
+        let index = if index < self.voter_count {
+            self.move_down(index)
+        } else {
+            index
+        };
+
+        Some(&self.vector[index].1)
+    }
+
     pub(crate) fn iter_mut(&mut self) -> IterMut<'_, (ID, V)> {
         self.vector.iter_mut()
     }
@@ -575,6 +619,37 @@ mod t {
         Ok(())
     }
 
+    #[test]
+    fn vec_progress_reset_with() -> anyhow::Result<()> {
+        let quorum_set: Vec<u64> = vec![0, 1, 2, 3, 4];
+        let mut progress = VecProgress::<u64, u64, u64, _>::new(quorum_set, [6], || 0);
+
+        let _ = progress.update(&0, 12);
+        let _ = progress.update(&1, 11);
+        let _ = progress.update(&2, 10);
+        let _ = progress.update(&3, 5);
+        let _ = progress.update(&6, 7);
+        assert_eq!(&10, progress.granted());
+
+        assert_eq!(Some(&0), progress.reset_with(&0, |x| *x = 0));
+        assert_eq!(
+            vec![(1, 11), (2, 10), (3, 5), (0, 0), (4, 0), (6, 7)],
+            progress.vector,
+            "values greater than granted are in front"
+        );
+        assert_eq!(&10, progress.granted(), "granted is not decreased");
+
+        // Without the reset moving 0 down, 0 would be counted in the quorum {0,1,3}.
+        assert_eq!(Ok(&10), progress.update(&3, 11));
+
+        assert_eq!(Some(&0), progress.reset_with(&6, |x| *x = 0));
+        assert_eq!(Some(5), progress.index(&6), "learner is not moved");
+
+        assert_eq!(None, progress.reset_with(&9, |x| *x = 0));
+
+        Ok(())
+    }
+
     /// Progress entry for testing
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     struct ProgressEntry {
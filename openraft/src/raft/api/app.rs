@@ -1,16 +1,21 @@
+use std::sync::atomic::Ordering;
+
 use openraft_macros::since;
 
 use crate::RaftTypeConfig;
 use crate::ReadPolicy;
+use crate::StorageError;
 use crate::core::raft_msg::RaftMsg;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
 use crate::error::Fatal;
+use crate::error::ReadOnly;
 use crate::impls::OneshotResponder;
 use crate::raft::ClientWriteResponse;
 use crate::raft::ClientWriteResult;
 use crate::raft::linearizable_read::Linearizer;
 use crate::raft::raft_inner::RaftInner;
+use crate::raft::responder::Responder;
 use crate::raft::responder::core_responder::CoreResponder;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::WriteResponderOf;
@@ -38,9 +43,15 @@ where C: RaftTypeConfig
     pub(crate) async fn get_read_linearizer(
         &self,
         read_policy: ReadPolicy,
+        trace_id: Option<String>,
     ) -> Result<Result<Linearizer<C>, CheckIsLeaderError<C>>, Fatal<C>> {
         let (tx, rx) = C::oneshot();
-        self.inner.call_core(RaftMsg::CheckIsLeaderRequest { read_policy, tx }, rx).await
+        let msg = RaftMsg::CheckIsLeaderRequest {
+            read_policy,
+            tx,
+            trace_id,
+        };
+        self.inner.call_core(msg, rx).await
     }
 
     #[since(version = "0.10.0")]
@@ -48,12 +59,13 @@ where C: RaftTypeConfig
     pub(crate) async fn client_write(
         &self,
         app_data: C::D,
+        trace_id: Option<String>,
         // TODO: ClientWriteError can only be ForwardToLeader Error
     ) -> Result<Result<ClientWriteResponse<C>, ClientWriteError<C>>, Fatal<C>> {
         let (tx, rx) = C::oneshot();
         let responder = OneshotResponder::new(tx);
 
-        self.do_client_write_ff(app_data, Some(CoreResponder::Oneshot(responder))).await?;
+        self.do_client_write_ff(app_data, Some(CoreResponder::Oneshot(responder)), trace_id).await?;
 
         let res: ClientWriteResult<C> = self.inner.recv_msg(rx).await?;
 
@@ -67,13 +79,72 @@ where C: RaftTypeConfig
         app_data: C::D,
         responder: Option<WriteResponderOf<C>>,
     ) -> Result<(), Fatal<C>> {
-        self.do_client_write_ff(app_data, responder.map(|r| CoreResponder::UserDefined(r))).await
+        self.do_client_write_ff(app_data, responder.map(|r| CoreResponder::UserDefined(r)), None).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, data))]
+    pub(crate) async fn append_local_entry(&self, data: Vec<u8>) -> Result<Result<u64, StorageError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::AppendLocalEntry { data, tx }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn read_local_entries(
+        &self,
+        start: u64,
+        max: u64,
+    ) -> Result<Result<Vec<(u64, Vec<u8>)>, StorageError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::ReadLocalEntries { start, max, tx }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn purge_local_entries(&self, upto: u64) -> Result<Result<(), StorageError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::PurgeLocalEntries { upto, tx }, rx).await
     }
 
     /// Fire-and-forget version of `client_write`, accept a generic responder.
     #[since(version = "0.10.0")]
-    async fn do_client_write_ff(&self, app_data: C::D, responder: Option<CoreResponder<C>>) -> Result<(), Fatal<C>> {
-        self.inner.send_msg(RaftMsg::ClientWriteRequest { app_data, responder }).await?;
+    async fn do_client_write_ff(
+        &self,
+        app_data: C::D,
+        responder: Option<CoreResponder<C>>,
+        trace_id: Option<String>,
+    ) -> Result<(), Fatal<C>> {
+        if self.inner.runtime_config.readonly.load(Ordering::Relaxed) {
+            let read_only = ReadOnly::new(self.inner.id().clone());
+            tracing::debug!("reject client write: {}", read_only);
+
+            if let Some(responder) = responder {
+                responder.send(Err(read_only.into()));
+            }
+            return Ok(());
+        }
+
+        let permit = match self.inner.reserve_client_write() {
+            Ok(permit) => permit,
+            Err(overloaded) => {
+                tracing::debug!("reject client write: {}", overloaded);
+
+                if let Some(responder) = responder {
+                    responder.send(Err(overloaded.into()));
+                }
+                return Ok(());
+            }
+        };
+
+        self.inner
+            .send_msg(RaftMsg::ClientWriteRequest {
+                app_data,
+                responder,
+                trace_id,
+                permit,
+            })
+            .await?;
 
         Ok(())
     }
@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::time::Duration;
 
 use maplit::btreemap;
+use maplit::btreeset;
 use openraft_macros::since;
 
 use crate::ChangeMembers;
@@ -8,16 +11,28 @@ use crate::LogIdOptionExt;
 use crate::OptionalSend;
 use crate::RaftMetrics;
 use crate::RaftTypeConfig;
+use crate::async_runtime::watch::WatchReceiver;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::replication_lag;
 use crate::display_ext::DisplayResult;
 use crate::display_ext::DisplayResultExt;
+use crate::error::CheckIsLeaderError;
+use crate::error::ClientWriteError;
 use crate::error::Fatal;
+use crate::error::ForwardToLeader;
 use crate::error::InitializeError;
+use crate::error::LearnerNotFound;
 use crate::impls::OneshotResponder;
 use crate::membership::IntoNodes;
+use crate::raft::AddLearnerResponse;
 use crate::raft::ClientWriteResult;
+use crate::raft::ClusterHealth;
+use crate::raft::DecommissionResponse;
+use crate::raft::StateMachineChecksums;
+use crate::raft::TargetProgress;
 use crate::raft::raft_inner::RaftInner;
+use crate::storage::Snapshot;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::OneshotReceiverOf;
@@ -56,6 +71,16 @@ where C: RaftTypeConfig
             .await
     }
 
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub(crate) async fn initialize_from_snapshot(
+        &self,
+        snapshot: Snapshot<C>,
+    ) -> Result<Result<(), InitializeError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::InitializeFromSnapshot { snapshot, tx }, rx).await
+    }
+
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "info", skip_all)]
     pub(crate) async fn change_membership(
@@ -124,6 +149,15 @@ where C: RaftTypeConfig
         Ok(client_write_result)
     }
 
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "info", skip(self, supports))]
+    pub(crate) async fn bump_version<F>(&self, version: u64, supports: F) -> Result<ClientWriteResult<C>, Fatal<C>>
+    where F: Fn(&C::NodeId, &C::Node) -> bool + OptionalSend + 'static {
+        let (tx, rx) = oneshot_channel::<C, _>();
+        let supports = Box::new(supports);
+        self.inner.call_core(RaftMsg::BumpVersion { version, supports, tx }, rx).await
+    }
+
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip(self, id), fields(target=display(&id)))]
     pub(crate) async fn add_learner(
@@ -132,39 +166,241 @@ where C: RaftTypeConfig
         node: C::Node,
         blocking: bool,
     ) -> Result<ClientWriteResult<C>, Fatal<C>> {
-        let (tx, rx) = oneshot_channel::<C, _>();
-
-        let msg = RaftMsg::ChangeMembership {
-            changes: ChangeMembers::AddNodes(btreemap! {id.clone()=>node}),
-            retain: true,
-            tx,
+        let resp = match self.propose_learner(id.clone(), node).await? {
+            Ok(x) => x,
+            Err(e) => return Ok(Err(e)),
         };
 
-        let client_write_result = self.inner.call_core(msg, rx).await?;
+        if !blocking {
+            return Ok(Ok(resp));
+        }
 
-        let resp = match client_write_result {
+        // Blocks until the replication to the new learner becomes up to date.
+        self.wait_learner(&id, &resp.log_id, None).await;
+
+        Ok(Ok(resp))
+    }
+
+    /// Probe every member of the cluster and report its health, waiting at most `timeout` for each
+    /// member to respond.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn cluster_health(
+        &self,
+        timeout: Duration,
+    ) -> Result<Result<ClusterHealth<C>, CheckIsLeaderError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::ClusterHealth { timeout, tx }, rx).await
+    }
+
+    /// Collect the state machine checksums of every member at the same log id, waiting at most
+    /// `timeout` for each member to respond.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn state_machine_checksums(
+        &self,
+        timeout: Duration,
+    ) -> Result<Result<StateMachineChecksums<C>, CheckIsLeaderError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::StateMachineChecksums { timeout, tx }, rx).await
+    }
+
+    /// Return the replication progress of every target tracked by the leader.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub(crate) async fn target_progress(
+        &self,
+    ) -> Result<Result<BTreeMap<C::NodeId, TargetProgress<C>>, CheckIsLeaderError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::ReplicationProgress { tx }, rx).await
+    }
+
+    /// Add a learner and wait at most `timeout` for it to catch up, then report its progress.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, id), fields(target=display(&id)))]
+    pub(crate) async fn add_learner_and_wait(
+        &self,
+        id: C::NodeId,
+        node: C::Node,
+        timeout: Duration,
+    ) -> Result<Result<AddLearnerResponse<C>, ClientWriteError<C>>, Fatal<C>> {
+        let write = match self.propose_learner(id.clone(), node).await? {
             Ok(x) => x,
             Err(e) => return Ok(Err(e)),
         };
 
-        if !blocking {
-            return Ok(Ok(resp));
+        let (caught_up, progress) = self.wait_learner(&id, &write.log_id, Some(timeout)).await;
+
+        let (matched, lag) = match progress {
+            Some((matched, lag)) => (matched, Some(lag)),
+            None => (None, None),
+        };
+
+        let resp = AddLearnerResponse {
+            write,
+            caught_up,
+            matched,
+            lag,
+        };
+
+        tracing::info!("add_learner_and_wait: {}", resp);
+
+        Ok(Ok(resp))
+    }
+
+    /// Remove `id` from the cluster and ask it to shut down.
+    ///
+    /// If `id` is this leader, the leadership is transferred to another voter instead, and a
+    /// [`ForwardToLeader`] error is returned for the caller to retry on the new leader.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self, id), fields(target=display(&id)))]
+    pub(crate) async fn decommission(
+        &self,
+        id: C::NodeId,
+        timeout: Duration,
+    ) -> Result<Result<DecommissionResponse<C>, ClientWriteError<C>>, Fatal<C>> {
+        let metrics = self.inner.rx_metrics.borrow_watched().clone();
+        let membership = metrics.membership_config.membership();
+
+        if metrics.current_leader.as_ref() != Some(&self.inner.id) {
+            let forward = match metrics.current_leader {
+                Some(leader_id) => match membership.get_node(&leader_id) {
+                    Some(node) => ForwardToLeader::new(leader_id, node.clone()),
+                    None => ForwardToLeader::empty(),
+                },
+                None => ForwardToLeader::empty(),
+            };
+            return Ok(Err(forward.into()));
         }
 
-        if self.inner.id == id {
-            return Ok(Ok(resp));
+        let Some(node) = membership.get_node(&id).cloned() else {
+            return Ok(Err(ClientWriteError::ChangeMembershipError(
+                LearnerNotFound { node_id: id }.into(),
+            )));
+        };
+
+        if id == self.inner.id {
+            let forward = self.transfer_leader_away(&metrics, timeout).await?;
+            return Ok(Err(forward.into()));
         }
 
-        // Otherwise, blocks until the replication to the new learner becomes up to date.
+        // A learner is removed at once; a voter is removed via joint consensus.
+        let is_voter = membership.voter_ids().any(|x| x == id);
+        let changes = if is_voter {
+            ChangeMembers::RemoveVoters(btreeset! {id.clone()})
+        } else {
+            ChangeMembers::RemoveNodes(btreeset! {id.clone()})
+        };
+
+        let write = match self.change_membership(changes, false).await? {
+            Ok(x) => x,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        // The removed node no longer receives logs and does not know it is removed.
+        let (tx, rx) = C::oneshot();
+        let cmd = ExternalCommand::SendDecommission {
+            target: id,
+            node,
+            timeout,
+            tx,
+        };
+        let shutdown_res = self.inner.call_core(RaftMsg::ExternalCommand { cmd }, rx).await?;
+
+        let resp = DecommissionResponse {
+            write,
+            shutdown_signaled: shutdown_res.is_ok(),
+        };
+
+        tracing::info!("decommission: {}", resp);
 
-        // The log id of the membership that contains the added learner.
-        let membership_log_id = &resp.log_id;
+        Ok(Ok(resp))
+    }
 
+    /// Transfer the leadership to the voter with the most logs, and wait at most `timeout` for
+    /// the new leader to be elected.
+    ///
+    /// It returns the error pointing to the new leader, if it is known.
+    async fn transfer_leader_away(
+        &self,
+        metrics: &RaftMetrics<C>,
+        timeout: Duration,
+    ) -> Result<ForwardToLeader<C>, Fatal<C>> {
+        let membership = metrics.membership_config.membership();
+        let replication = metrics.replication.clone().unwrap_or_default();
+
+        let successor = membership
+            .voter_ids()
+            .filter(|id| id != &self.inner.id)
+            .max_by_key(|id| replication.get(id).cloned().flatten());
+
+        let Some(successor) = successor else {
+            tracing::warn!("no other voter to transfer leadership to");
+            return Ok(ForwardToLeader::empty());
+        };
+
+        tracing::info!("decommission leader: transfer leadership to {}", successor);
+
+        let cmd = ExternalCommand::TriggerTransferLeader { to: successor };
+        self.inner.send_msg(RaftMsg::ExternalCommand { cmd }).await?;
+
+        let my_id = self.inner.id.clone();
         let wait_res = self
             .inner
-            .wait(None)
+            .wait(Some(timeout))
             .metrics(
-                |metrics| match self.check_replication_upto_date(metrics, &id, Some(membership_log_id)) {
+                |m| m.current_leader.is_some() && m.current_leader.as_ref() != Some(&my_id),
+                "wait for the new leader",
+            )
+            .await;
+
+        let Ok(m) = wait_res else {
+            return Ok(ForwardToLeader::empty());
+        };
+
+        let leader_id = m.current_leader.clone().unwrap();
+        let forward = match m.membership_config.membership().get_node(&leader_id) {
+            Some(node) => ForwardToLeader::new(leader_id, node.clone()),
+            None => ForwardToLeader::empty(),
+        };
+
+        Ok(forward)
+    }
+
+    /// Propose a membership that adds `id` as a learner.
+    async fn propose_learner(&self, id: C::NodeId, node: C::Node) -> Result<ClientWriteResult<C>, Fatal<C>> {
+        let (tx, rx) = oneshot_channel::<C, _>();
+
+        let msg = RaftMsg::ChangeMembership {
+            changes: ChangeMembers::AddNodes(btreemap! {id=>node}),
+            retain: true,
+            tx,
+        };
+
+        self.inner.call_core(msg, rx).await
+    }
+
+    /// Wait until the replication lag of learner `id` is no more than
+    /// [`Config::replication_lag_threshold`](crate::Config::replication_lag_threshold).
+    ///
+    /// It returns whether the learner caught up, and the latest known progress of the
+    /// replication to it: the matching log id and the lag.
+    /// It returns at once if `id` is this node.
+    async fn wait_learner(
+        &self,
+        id: &C::NodeId,
+        membership_log_id: &LogIdOf<C>,
+        timeout: Option<Duration>,
+    ) -> (bool, Option<(Option<LogIdOf<C>>, u64)>) {
+        if &self.inner.id == id {
+            return (true, None);
+        }
+
+        let wait_res = self
+            .inner
+            .wait(timeout)
+            .metrics(
+                |metrics| match self.check_replication_upto_date(metrics, id, Some(membership_log_id)) {
                     Ok(_matching) => true,
                     // keep waiting
                     Err(_) => false,
@@ -178,7 +414,16 @@ where C: RaftTypeConfig
             "waiting for replication to new learner"
         );
 
-        Ok(Ok(resp))
+        let caught_up = wait_res.is_ok();
+
+        let metrics = match wait_res {
+            Ok(m) => m,
+            Err(_) => self.inner.rx_metrics.borrow_watched().clone(),
+        };
+
+        let progress = self.replication_progress(&metrics, id, Some(membership_log_id)).ok().flatten();
+
+        (caught_up, progress)
     }
 
     #[since(version = "0.10.0")]
@@ -188,6 +433,30 @@ where C: RaftTypeConfig
         node_id: &C::NodeId,
         membership_log_id: Option<&LogIdOf<C>>,
     ) -> Result<Option<LogIdOf<C>>, ()> {
+        let Some((matched, distance)) = self.replication_progress(metrics, node_id, membership_log_id)? else {
+            // The learner is removed or this node is no longer a leader.
+            return Ok(None);
+        };
+
+        if distance <= self.inner.config.replication_lag_threshold {
+            // replication became up to date.
+            return Ok(matched);
+        }
+
+        // Not up to date, keep waiting.
+        Err(())
+    }
+
+    /// Returns the matching log id of the replication to `node_id` and how far it is behind.
+    ///
+    /// It returns `Ok(None)` if the replication no longer exists, and `Err(())` if it is not
+    /// reported in `metrics` yet.
+    fn replication_progress(
+        &self,
+        metrics: &RaftMetrics<C>,
+        node_id: &C::NodeId,
+        membership_log_id: Option<&LogIdOf<C>>,
+    ) -> Result<Option<(Option<LogIdOf<C>>, u64)>, ()> {
         if metrics.membership_config.log_id().as_ref() < membership_log_id {
             // Waiting for the latest metrics to report.
             return Err(());
@@ -219,13 +488,7 @@ where C: RaftTypeConfig
 
         let distance = replication_lag(&matched.index(), &metrics.last_log_index);
 
-        if distance <= self.inner.config.replication_lag_threshold {
-            // replication became up to date.
-            return Ok(matched);
-        }
-
-        // Not up to date, keep waiting.
-        Err(())
+        Ok(Some((matched, distance)))
     }
 }
 
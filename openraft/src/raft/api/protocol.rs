@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use openraft_macros::since;
 
 use crate::LogIdOptionExt;
@@ -15,7 +13,9 @@ use crate::error::Fatal;
 use crate::error::into_raft_result::IntoRaftResult;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
+use crate::raft::LogRepairRequest;
 use crate::raft::SnapshotResponse;
+use crate::raft::StateMachineChecksumRequest;
 use crate::raft::TransferLeaderRequest;
 use crate::raft::VoteRequest;
 use crate::raft::VoteResponse;
@@ -124,6 +124,27 @@ where C: RaftTypeConfig
     }
 
     #[since(version = "0.10.0")]
+    /// Get the checksum of the local state machine right after the requested log id is applied.
+    #[since(version = "0.10.0")]
+    pub(crate) async fn handle_state_machine_checksum(
+        &self,
+        req: StateMachineChecksumRequest<C>,
+    ) -> Result<Option<u64>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::StateMachineChecksum { at: req.at, tx }, rx).await
+    }
+
+    #[since(version = "0.10.0")]
+    pub(crate) async fn handle_repair_log(&self, req: LogRepairRequest<C>) -> Result<Vec<C::Entry>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        let raft_msg = RaftMsg::RepairLog {
+            first: req.first,
+            last: req.last,
+            tx,
+        };
+        self.inner.call_core(raft_msg, rx).await
+    }
+
     pub(crate) async fn handle_transfer_leader(&self, req: TransferLeaderRequest<C>) -> Result<(), Fatal<C>> {
         // Reset the Leader lease at once and quit if this is not the assigned next leader.
         // Only the assigned next Leader waits for the log to be flushed.
@@ -156,7 +177,7 @@ where C: RaftTypeConfig
         #[allow(clippy::neg_cmp_op_on_partial_ord)]
         let fail = |m: &RaftMetrics<C>| !(req.from_leader.as_ref_vote() >= m.vote.as_ref_vote());
 
-        let timeout = Some(Duration::from_millis(self.inner.config().election_timeout_min));
+        let timeout = Some(self.inner.config().election_timeout_min());
         let metrics_res =
             self.inner.wait(timeout).metrics(|st| ok(st) || fail(st), "transfer_leader await flushed log").await;
 
@@ -23,8 +23,7 @@ declare_raft_types!(
         Vote = crate::impls::Vote<Self>,
         SnapshotData = Cursor<Vec<u8>>,
         AsyncRuntime = TokioRuntime,
-        // Responder<T> is not supported by  declare_raft_types
-        // Responder<T> = crate::impls::OneshotResponder<Self, T> where T: OptionalSend + 'static,
+        Responder<T> = crate::impls::OneshotResponder<Self, T>,
 );
 
 declare_raft_types!(
@@ -47,6 +46,30 @@ declare_raft_types!(
         AsyncRuntime = TokioRuntime,
 );
 
+declare_raft_types!(
+    WithResponder:
+        /// A responder that is not the default one.
+        Responder<T> = crate::impls::OneshotResponder<Self, T>,
+        D = u64,
+);
+
+declare_raft_types!(
+    pub(crate) WithTypes, pub(crate) mod with_types:
+        D = u64,
+        R = (),
+);
+
+declare_raft_types!(EmptyWithTypes, mod empty_with_types);
+
 declare_raft_types!(EmptyWithColon:);
 
 declare_raft_types!(Empty);
+
+/// The alias module is specialized for its type config.
+fn _alias_types(raft: with_types::Raft, _: empty_with_types::Raft) {
+    let _entry: with_types::Entry = crate::Entry::<WithTypes>::default();
+    let _log_id: Option<with_types::LogId> = None;
+    let _err: with_types::RaftError<with_types::ClientWriteError> =
+        crate::error::RaftError::Fatal(crate::error::Fatal::Stopped);
+    drop(raft);
+}
@@ -2,7 +2,10 @@
 //! Blocking-mode write API blocks until the write operation is completed,
 //! where [`RaftTypeConfig::Responder`] is a [`OneshotResponder`].
 
+use std::time::Duration;
+
 use crate::ChangeMembers;
+use crate::OptionalSend;
 use crate::Raft;
 use crate::RaftTypeConfig;
 use crate::error::ClientWriteError;
@@ -10,7 +13,9 @@ use crate::error::RaftError;
 use crate::error::into_raft_result::IntoRaftResult;
 #[cfg(doc)]
 use crate::impls::OneshotResponder;
+use crate::raft::AddLearnerResponse;
 use crate::raft::ClientWriteResponse;
+use crate::raft::DecommissionResponse;
 #[cfg(doc)]
 use crate::raft::ManagementApi;
 
@@ -49,6 +54,16 @@ where C: RaftTypeConfig
     /// If it loses leadership or crashed before committing the second **uniform** config log, the
     /// cluster is left in the **joint** config.
     ///
+    /// Only one membership change can be in flight: if the last membership config is not yet
+    /// committed, it fails at once with [`ChangeMembershipError::InProgress`], carrying the log
+    /// id of the pending config. The pending change is reported by
+    /// [`RaftMetrics::membership_in_progress()`]; wait for it to commit with
+    /// [`Wait::membership_committed()`] before retrying.
+    ///
+    /// [`ChangeMembershipError::InProgress`]: crate::error::ChangeMembershipError::InProgress
+    /// [`RaftMetrics::membership_in_progress()`]: crate::RaftMetrics::membership_in_progress
+    /// [`Wait::membership_committed()`]: crate::metrics::Wait::membership_committed
+    ///
     /// # Examples
     ///
     /// ```ignore
@@ -71,6 +86,41 @@ where C: RaftTypeConfig
         self.management_api().change_membership(members, retain).await.into_raft_result()
     }
 
+    /// Propose a version bump entry, once every member supports `version`.
+    ///
+    /// It coordinates a rolling upgrade of the application apply logic: upgrade every node to a
+    /// binary that can apply with both the old and the new logic, then bump the version. The state
+    /// machine receives an [`EntryPayload::VersionBump`] entry as a barrier: it applies the entries
+    /// before it with the old logic and the entries after it with the new one, on every node.
+    ///
+    /// `supports` is called with the id and the node metadata of every voter and learner in the
+    /// effective membership, e.g., to check a version advertised in an application-defined
+    /// [`Node`](crate::Node). If any of them returns `false`, the version bump is not proposed and
+    /// it fails with [`ClientWriteError::VersionUnsupported`].
+    ///
+    /// Openraft does not keep the current version: the state machine persists it, and decides
+    /// what to do with a version that is not greater.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // The node metadata has a `max_version` field, set by each node when it is added.
+    /// raft.bump_version(2, |_id, node: &MyNode| node.max_version >= 2).await?;
+    /// ```
+    ///
+    /// [`EntryPayload::VersionBump`]: crate::EntryPayload::VersionBump
+    #[tracing::instrument(level = "info", skip(self, supports))]
+    pub async fn bump_version<F>(
+        &self,
+        version: u64,
+        supports: F,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>>
+    where
+        F: Fn(&C::NodeId, &C::Node) -> bool + OptionalSend + 'static,
+    {
+        self.management_api().bump_version(version, supports).await.into_raft_result()
+    }
+
     /// Add a new learner raft node, optionally, blocking until up-to-speed.
     ///
     /// - Add a node as learner into the cluster.
@@ -110,4 +160,78 @@ where C: RaftTypeConfig
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
         self.management_api().add_learner(id, node, blocking).await.into_raft_result()
     }
+
+    /// Add a new learner raft node and wait at most `timeout` for it to catch up.
+    ///
+    /// It is the same as [`add_learner()`](Self::add_learner) with `blocking` set to `true`,
+    /// except that waiting for the learner ends when `timeout` expires, and the replication
+    /// progress of the learner is returned in [`AddLearnerResponse`].
+    ///
+    /// The learner is considered caught up when it is no more than
+    /// [`Config::replication_lag_threshold`](crate::Config::replication_lag_threshold) entries
+    /// behind the leader. If the timeout expires first, the learner stays in the cluster, and
+    /// [`AddLearnerResponse::caught_up`] is `false`. An application should not promote such a
+    /// learner to a voter, since it can not vote for or accept new logs in time.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use std::time::Duration;
+    ///
+    /// let resp = raft.add_learner_and_wait(4, node, Duration::from_secs(60)).await?;
+    /// if resp.caught_up {
+    ///     raft.change_membership(ChangeMembers::AddVoterIds(btreeset! {4}), false).await?;
+    /// } else {
+    ///     println!("learner 4 is still {:?} logs behind", resp.lag);
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip(self, id), fields(target=display(&id)))]
+    pub async fn add_learner_and_wait(
+        &self,
+        id: C::NodeId,
+        node: C::Node,
+        timeout: Duration,
+    ) -> Result<AddLearnerResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.management_api().add_learner_and_wait(id, node, timeout).await.into_raft_result()
+    }
+
+    /// Gracefully remove a node from the cluster and ask it to shut down.
+    ///
+    /// It must be called on the leader, and runs the following steps:
+    ///
+    /// - If `id` is this leader, the leadership is transferred to the voter with the most logs, and
+    ///   it returns a [`ForwardToLeader`] error with the new leader once it is elected, or when
+    ///   `timeout` expires. Call this method again on the new leader to continue.
+    /// - Otherwise, `id` is removed from the membership: a voter is removed via joint consensus,
+    ///   and a learner is removed at once. It blocks until the last membership log is committed,
+    ///   the same as [`change_membership()`](Self::change_membership). From then on, `id` is no
+    ///   longer reported in [`RaftMetrics::membership_config`] or [`RaftMetrics::read_replicas`],
+    ///   and an application routing reads by these metrics stops routing reads to it.
+    /// - Finally, a [`DecommissionRequest`] is sent to `id`, waiting at most `timeout` for it to
+    ///   respond. The node receiving it shuts down, see [`Raft::handle_decommission()`].
+    ///
+    /// The node is removed even if it can not be reached or rejects the request, in which case
+    /// [`DecommissionResponse::shutdown_signaled`] is `false` and the application should stop it.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let resp = raft.decommission(3, Duration::from_secs(1)).await?;
+    /// if !resp.shutdown_signaled {
+    ///     println!("node 3 is removed but not stopped");
+    /// }
+    /// ```
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    /// [`RaftMetrics::membership_config`]: crate::metrics::RaftMetrics::membership_config
+    /// [`RaftMetrics::read_replicas`]: crate::metrics::RaftMetrics::read_replicas
+    /// [`DecommissionRequest`]: crate::raft::DecommissionRequest
+    #[tracing::instrument(level = "info", skip(self, id), fields(target=display(&id)))]
+    pub async fn decommission(
+        &self,
+        id: C::NodeId,
+        timeout: Duration,
+    ) -> Result<DecommissionResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        self.management_api().decommission(id, timeout).await.into_raft_result()
+    }
 }
@@ -0,0 +1,63 @@
+use std::fmt;
+use std::fmt::Debug;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOptionExt;
+use crate::raft::ClientWriteResponse;
+use crate::type_config::alias::LogIdOf;
+
+/// The response to [`Raft::add_learner_and_wait()`](crate::Raft::add_learner_and_wait).
+///
+/// It contains the response of writing the membership log that adds the learner, and the
+/// replication progress of the learner when the waiting ends.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::R: crate::AppDataResponse")
+)]
+pub struct AddLearnerResponse<C: RaftTypeConfig> {
+    /// The response of writing the membership log that contains the new learner.
+    pub write: ClientWriteResponse<C>,
+
+    /// Whether the replication lag of the learner dropped to
+    /// [`Config::replication_lag_threshold`](crate::Config::replication_lag_threshold) or below
+    /// before the timeout.
+    pub caught_up: bool,
+
+    /// The last log id known to be replicated to the learner.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// The number of log entries the learner is behind the leader when the waiting ends.
+    ///
+    /// It is `None` if the lag is unknown: the learner is the leader itself, it has been removed,
+    /// this node is no longer the leader, or the replication to it has not been reported yet.
+    pub lag: Option<u64>,
+}
+
+impl<C: RaftTypeConfig> Debug for AddLearnerResponse<C>
+where C::R: Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddLearnerResponse")
+            .field("write", &self.write)
+            .field("caught_up", &self.caught_up)
+            .field("matched", &self.matched)
+            .field("lag", &self.lag)
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for AddLearnerResponse<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AddLearnerResponse{{write:{}, caught_up:{}, matched:{}, lag:{}}}",
+            self.write,
+            self.caught_up,
+            self.matched.display(),
+            self.lag.display()
+        )
+    }
+}
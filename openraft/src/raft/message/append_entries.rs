@@ -1,8 +1,10 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::RaftTypeConfig;
 use crate::display_ext::DisplayOptionExt;
 use crate::display_ext::DisplaySlice;
+use crate::raft::LogChecksum;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 
@@ -13,8 +15,12 @@ use crate::type_config::alias::VoteOf;
 /// which is always valid. Because `prev_log_id` is used to assert `entries` to be consecutive with
 /// the previous log entries, and `prev_log_id=None` is the very beginning position and there are no
 /// previous log entries.
+///
+/// It is `#[non_exhaustive]`: build it with [`AppendEntriesRequest::new()`] or
+/// [`AppendEntriesRequest::heartbeat()`], so that adding a field does not break the application.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[non_exhaustive]
 pub struct AppendEntriesRequest<C: RaftTypeConfig> {
     /// The leader's current vote.
     pub vote: VoteOf<C>,
@@ -30,6 +36,104 @@ pub struct AppendEntriesRequest<C: RaftTypeConfig> {
 
     /// The leader's committed log id.
     pub leader_commit: Option<LogIdOf<C>>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name. It is `None` in
+    /// a request from a version that does not send it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+
+    /// The trace ids of the client requests written to `entries`, indexed by the log index.
+    ///
+    /// The receiver records them in the tracing span of appending the entries to its log store.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "BTreeMap::is_empty"))]
+    pub(crate) trace_ids: BTreeMap<u64, String>,
+
+    /// The checksum of a range of committed log entries, for the receiver to verify its copy.
+    ///
+    /// It is only sent with a heartbeat, see [`Config::log_checksum_interval`].
+    ///
+    /// [`Config::log_checksum_interval`]: crate::Config::log_checksum_interval
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub(crate) log_checksum: Option<LogChecksum<C>>,
+}
+
+impl<C> AppendEntriesRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a request to append `entries` after `prev_log_id`.
+    pub fn new(
+        vote: VoteOf<C>,
+        prev_log_id: Option<LogIdOf<C>>,
+        entries: impl IntoIterator<Item = C::Entry>,
+        leader_commit: Option<LogIdOf<C>>,
+    ) -> Self {
+        Self {
+            vote,
+            prev_log_id,
+            entries: entries.into_iter().collect(),
+            leader_commit,
+            cluster_name: None,
+            trace_ids: BTreeMap::new(),
+            log_checksum: None,
+        }
+    }
+
+    /// Create a heartbeat request that carries no entries.
+    pub fn heartbeat(vote: VoteOf<C>, prev_log_id: Option<LogIdOf<C>>, leader_commit: Option<LogIdOf<C>>) -> Self {
+        Self::new(vote, prev_log_id, [], leader_commit)
+    }
+
+    /// Replace the entries to append.
+    pub fn with_entries(mut self, entries: impl IntoIterator<Item = C::Entry>) -> Self {
+        self.entries = entries.into_iter().collect();
+        self
+    }
+
+    /// Replace the leader's committed log id.
+    pub fn with_leader_commit(mut self, leader_commit: Option<LogIdOf<C>>) -> Self {
+        self.leader_commit = leader_commit;
+        self
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+
+    /// Set the trace ids of the client requests written to the entries, indexed by the log index.
+    pub fn with_trace_ids(mut self, trace_ids: BTreeMap<u64, String>) -> Self {
+        self.trace_ids = trace_ids;
+        self
+    }
+
+    /// The trace ids of the client requests written to the entries, indexed by the log index.
+    pub fn trace_ids(&self) -> &BTreeMap<u64, String> {
+        &self.trace_ids
+    }
+
+    /// Set the checksum of a range of committed log entries for the receiver to verify.
+    pub fn with_log_checksum(mut self, log_checksum: Option<LogChecksum<C>>) -> Self {
+        self.log_checksum = log_checksum;
+        self
+    }
+
+    /// The checksum of a range of committed log entries for the receiver to verify, if any.
+    pub fn log_checksum(&self) -> Option<&LogChecksum<C>> {
+        self.log_checksum.as_ref()
+    }
+
+    /// Returns true if this request carries no entries.
+    pub fn is_heartbeat(&self) -> bool {
+        self.entries.is_empty()
+    }
 }
 
 impl<C: RaftTypeConfig> fmt::Debug for AppendEntriesRequest<C> {
@@ -101,6 +205,18 @@ pub enum AppendEntriesResponse<C: RaftTypeConfig> {
     /// And a leader's vote(committed vote) must be total order with other votes.
     /// Therefore, it has to be a higher vote: `mine_vote < v`
     HigherVote(VoteOf<C>),
+
+    /// Successfully replicated all log entries, as [`Success`](Self::Success) does, but the
+    /// target is busy applying logs.
+    ///
+    /// `apply_backlog` is the number of logs that are committed but not yet applied on the target.
+    /// The leader pauses sending logs to the target for [`Config::busy_pause`] milliseconds.
+    ///
+    /// It is sent when the backlog reaches [`Config::busy_apply_backlog`].
+    ///
+    /// [`Config::busy_pause`]: crate::Config::busy_pause
+    /// [`Config::busy_apply_backlog`]: crate::Config::busy_apply_backlog
+    Busy { apply_backlog: u64 },
 }
 
 impl<C> AppendEntriesResponse<C>
@@ -108,7 +224,15 @@ where C: RaftTypeConfig
 {
     /// Returns true if the response indicates a successful replication.
     pub fn is_success(&self) -> bool {
-        matches!(*self, AppendEntriesResponse::Success)
+        matches!(
+            *self,
+            AppendEntriesResponse::Success | AppendEntriesResponse::Busy { .. }
+        )
+    }
+
+    /// Returns true if the target reports it is busy applying logs.
+    pub fn is_busy(&self) -> bool {
+        matches!(*self, AppendEntriesResponse::Busy { .. })
     }
 
     /// Returns true if the response indicates a log conflict.
@@ -128,6 +252,7 @@ where C: RaftTypeConfig
             }
             AppendEntriesResponse::HigherVote(vote) => write!(f, "Higher vote, {}", vote),
             AppendEntriesResponse::Conflict => write!(f, "Conflict"),
+            AppendEntriesResponse::Busy { apply_backlog } => write!(f, "Busy(apply_backlog={})", apply_backlog),
         }
     }
 }
@@ -0,0 +1,150 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplaySliceExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// The result of applying a log entry, computed by the state machine of the leader.
+///
+/// It is opaque to Openraft: the state machine of the leader returns it with
+/// [`RaftStateMachine::take_computed_results()`], and the state machine of a follower stores it
+/// with [`RaftStateMachine::apply_computed()`] instead of computing it again. See
+/// [`Config::apply_results_wait`].
+///
+/// [`RaftStateMachine::take_computed_results()`]: crate::storage::RaftStateMachine::take_computed_results
+/// [`RaftStateMachine::apply_computed()`]: crate::storage::RaftStateMachine::apply_computed
+/// [`Config::apply_results_wait`]: crate::Config::apply_results_wait
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ComputedResult<C>
+where C: RaftTypeConfig
+{
+    /// The log id of the applied entry.
+    pub log_id: LogIdOf<C>,
+
+    /// The result computed by the state machine.
+    pub data: Vec<u8>,
+
+    /// The checksum of `data`, to detect a corrupted copy, and a state machine that is not
+    /// deterministic.
+    pub checksum: u64,
+}
+
+impl<C> ComputedResult<C>
+where C: RaftTypeConfig
+{
+    /// Create a result of the entry at `log_id`, with the checksum of `data`.
+    pub fn new(log_id: LogIdOf<C>, data: Vec<u8>) -> Self {
+        let checksum = Self::checksum_of(&data);
+        Self { log_id, data, checksum }
+    }
+
+    /// Returns `true` if `data` matches the checksum.
+    pub fn is_intact(&self) -> bool {
+        Self::checksum_of(&self.data) == self.checksum
+    }
+
+    /// FNV-1a of the data.
+    fn checksum_of(data: &[u8]) -> u64 {
+        data.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+            (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+}
+
+impl<C> fmt::Display for ComputedResult<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} bytes, {:016x}", self.log_id, self.data.len(), self.checksum)
+    }
+}
+
+/// A request sent by the Leader to stream the results its state machine computed to a follower.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ApplyResultsRequest<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the Leader that sends the results.
+    pub(crate) from_leader: VoteOf<C>,
+
+    /// The results of consecutive log entries, in log order.
+    pub(crate) results: Vec<ComputedResult<C>>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+}
+
+impl<C> ApplyResultsRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new request to send `results`.
+    pub fn new(from: VoteOf<C>, results: Vec<ComputedResult<C>>) -> Self {
+        Self {
+            from_leader: from,
+            results,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The Leader that sends the results.
+    pub fn from_leader(&self) -> &VoteOf<C> {
+        &self.from_leader
+    }
+
+    /// The computed results, in log order.
+    pub fn results(&self) -> &[ComputedResult<C>] {
+        &self.results
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
+impl<C> fmt::Display for ApplyResultsRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(from_leader={}, results={})",
+            self.from_leader,
+            self.results.display()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ComputedResult;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+
+    #[test]
+    fn test_computed_result_is_intact() {
+        let mut r = ComputedResult::<UTConfig>::new(log_id(1, 1, 3), b"foo".to_vec());
+        assert!(r.is_intact());
+        assert_eq!(r, ComputedResult::new(log_id(1, 1, 3), b"foo".to_vec()));
+        assert_ne!(
+            r.checksum,
+            ComputedResult::<UTConfig>::new(log_id(1, 1, 3), b"bar".to_vec()).checksum
+        );
+
+        r.data = b"bar".to_vec();
+        assert!(!r.is_intact());
+    }
+}
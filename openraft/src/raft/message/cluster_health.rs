@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayBTreeMapExt;
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::LogIdOf;
+
+/// The health of every member of a cluster, as seen by the leader.
+///
+/// Returned by [`Raft::cluster_health()`](crate::Raft::cluster_health).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct ClusterHealth<C: RaftTypeConfig> {
+    /// The id of the leader that built this report.
+    pub leader_id: C::NodeId,
+
+    /// The term of the leader.
+    pub term: C::Term,
+
+    /// The last log id on the leader.
+    pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The last committed log id.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The status of every voter and learner, including the leader itself.
+    pub nodes: BTreeMap<C::NodeId, NodeHealth<C>>,
+}
+
+impl<C> ClusterHealth<C>
+where C: RaftTypeConfig
+{
+    /// Returns `true` if every voter responded to the probe and is at most `max_lag` log entries
+    /// behind the leader.
+    ///
+    /// Learners are not considered.
+    pub fn voters_healthy(&self, max_lag: u64) -> bool {
+        self.nodes.values().filter(|n| n.voter).all(|n| n.reachable && n.lag <= max_lag)
+    }
+
+    /// The ids of the members that did not respond to the probe.
+    pub fn unreachable(&self) -> impl Iterator<Item = &C::NodeId> + '_ {
+        self.nodes.iter().filter(|(_, n)| !n.reachable).map(|(id, _)| id)
+    }
+}
+
+impl<C> fmt::Display for ClusterHealth<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ClusterHealth{{leader:{}, term:{}, last_log_id:{}, committed:{}, nodes:{{{}}}}}",
+            self.leader_id,
+            self.term,
+            self.last_log_id.display(),
+            self.committed.display(),
+            self.nodes.display()
+        )
+    }
+}
+
+/// The status of a single member in a [`ClusterHealth`] report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct NodeHealth<C: RaftTypeConfig> {
+    /// Whether this node is a voter, otherwise a learner.
+    pub voter: bool,
+
+    /// Whether the node responded to the probe within the timeout.
+    pub reachable: bool,
+
+    /// The last log id known to be replicated to this node.
+    pub matched: Option<LogIdOf<C>>,
+
+    /// The number of log entries this node is behind the leader.
+    pub lag: u64,
+
+    /// The term of the node, learned from its response.
+    ///
+    /// It is `None` if the node did not respond.
+    pub term: Option<C::Term>,
+
+    /// The error of the last RPC from the leader to this node, including replication, heartbeat
+    /// and probe, if it failed.
+    ///
+    /// It is cleared when an RPC to this node succeeds.
+    pub last_error: Option<String>,
+}
+
+impl<C> fmt::Display for NodeHealth<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{voter:{}, reachable:{}, matched:{}, lag:{}, term:{}, last_error:{}}}",
+            self.voter,
+            self.reachable,
+            self.matched.display(),
+            self.lag,
+            self.term.display(),
+            self.last_error.display()
+        )
+    }
+}
@@ -0,0 +1,170 @@
+//! Encode and decode the RPC messages sent between Raft nodes.
+//!
+//! A custom transport, such as QUIC or a message queue, sends the bytes returned by `encode()`
+//! and rebuilds the message on the receiver with `decode()`:
+//!
+//! ```text
+//! version: u8 | JSON encoded message
+//! ```
+//!
+//! The leading version byte lets a receiver reject a message encoded in a format it does not
+//! understand, instead of misinterpreting it.
+
+use crate::RaftTypeConfig;
+use crate::raft::message::AppendEntriesRequest;
+use crate::raft::message::AppendEntriesResponse;
+use crate::raft::message::InstallSnapshotRequest;
+use crate::raft::message::InstallSnapshotResponse;
+use crate::raft::message::VoteRequest;
+use crate::raft::message::VoteResponse;
+
+/// The version of the format produced by `encode()`.
+pub const MESSAGE_FORMAT_VERSION: u8 = 1;
+
+/// Error returned when a Raft RPC message can not be encoded into bytes.
+///
+/// It happens only if a user defined type in the message, such as the application data of an
+/// entry, fails to serialize.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("failed to encode message: {reason}")]
+pub struct MessageEncodeError {
+    /// The reason the message can not be encoded.
+    pub reason: String,
+}
+
+/// Error returned when bytes can not be decoded into a Raft RPC message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MessageDecodeError {
+    /// The input is empty.
+    #[error("empty message")]
+    Empty,
+
+    /// The message is encoded in a format version this node does not support.
+    #[error("unsupported message format version: {version}, expected: {expected}")]
+    UnsupportedVersion { version: u8, expected: u8 },
+
+    /// The message body can not be decoded.
+    #[error("invalid message: {reason}")]
+    Invalid { reason: String },
+}
+
+macro_rules! impl_codec {
+    ($($typ:ident),* $(,)?) => {
+        $(
+            impl<C> $typ<C>
+            where C: RaftTypeConfig
+            {
+                #[doc = concat!("Encode this `", stringify!($typ), "` into bytes to send over a custom transport.")]
+                ///
+                /// Decode it with `decode()`.
+                pub fn encode(&self) -> Result<Vec<u8>, MessageEncodeError> {
+                    encode(self)
+                }
+
+                #[doc = concat!("Decode a `", stringify!($typ), "` encoded by `encode()`.")]
+                pub fn decode(bytes: &[u8]) -> Result<Self, MessageDecodeError> {
+                    decode(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_codec!(
+    AppendEntriesRequest,
+    AppendEntriesResponse,
+    VoteRequest,
+    VoteResponse,
+    InstallSnapshotRequest,
+    InstallSnapshotResponse,
+);
+
+fn encode<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, MessageEncodeError> {
+    let mut buf = vec![MESSAGE_FORMAT_VERSION];
+    serde_json::to_writer(&mut buf, value).map_err(|e| MessageEncodeError { reason: e.to_string() })?;
+    Ok(buf)
+}
+
+fn decode<T>(bytes: &[u8]) -> Result<T, MessageDecodeError>
+where T: serde::de::DeserializeOwned {
+    let Some((version, body)) = bytes.split_first() else {
+        return Err(MessageDecodeError::Empty);
+    };
+
+    if *version != MESSAGE_FORMAT_VERSION {
+        return Err(MessageDecodeError::UnsupportedVersion {
+            version: *version,
+            expected: MESSAGE_FORMAT_VERSION,
+        });
+    }
+
+    serde_json::from_slice(body).map_err(|e| MessageDecodeError::Invalid { reason: e.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Entry;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::RaftEntry;
+    use crate::raft::message::AppendEntriesRequest;
+    use crate::raft::message::AppendEntriesResponse;
+    use crate::raft::message::InstallSnapshotRequest;
+    use crate::raft::message::MessageDecodeError;
+    use crate::raft::message::VoteRequest;
+    use crate::raft::message::VoteResponse;
+    use crate::storage::SnapshotMeta;
+
+    #[test]
+    fn test_encode_decode() -> anyhow::Result<()> {
+        let vote = Vote::<UTConfig>::new_committed(2, 1);
+
+        let req = AppendEntriesRequest::<UTConfig>::new(
+            vote,
+            Some(log_id(1, 1, 3)),
+            [Entry::new_blank(log_id(2, 1, 4))],
+            Some(log_id(1, 1, 2)),
+        );
+        let got = AppendEntriesRequest::<UTConfig>::decode(&req.encode()?)?;
+        assert_eq!(req.to_string(), got.to_string());
+
+        let resp = AppendEntriesResponse::<UTConfig>::PartialSuccess(Some(log_id(2, 1, 4)));
+        assert_eq!(resp, AppendEntriesResponse::decode(&resp.encode()?)?);
+
+        let req = VoteRequest::<UTConfig>::new(vote, Some(log_id(1, 1, 3)));
+        assert_eq!(req, VoteRequest::decode(&req.encode()?)?);
+
+        let resp = VoteResponse::<UTConfig>::new(vote, None, true);
+        assert_eq!(resp, VoteResponse::decode(&resp.encode()?)?);
+
+        let req = InstallSnapshotRequest::<UTConfig>::new(vote, SnapshotMeta::default(), 3, b"foo".to_vec(), true);
+        assert_eq!(req, InstallSnapshotRequest::decode(&req.encode()?)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_error() -> anyhow::Result<()> {
+        let req = VoteRequest::<UTConfig>::new(Vote::new(2, 1), None);
+        let mut bytes = req.encode()?;
+
+        assert_eq!(Err(MessageDecodeError::Empty), VoteRequest::<UTConfig>::decode(&[]));
+
+        assert!(matches!(
+            VoteRequest::<UTConfig>::decode(&bytes[..bytes.len() - 1]),
+            Err(MessageDecodeError::Invalid { .. })
+        ));
+
+        bytes[0] = 9;
+        assert_eq!(
+            Err(MessageDecodeError::UnsupportedVersion {
+                version: 9,
+                expected: 1
+            }),
+            VoteRequest::<UTConfig>::decode(&bytes)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+use std::fmt::Debug;
+
+use crate::RaftTypeConfig;
+use crate::raft::ClientWriteResponse;
+use crate::type_config::alias::VoteOf;
+
+/// A request sent by the Leader to a node that has been removed from the cluster, to ask it to
+/// shut down.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct DecommissionRequest<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the Leader that removed the node.
+    pub(crate) from_leader: VoteOf<C>,
+
+    /// The node that is removed and should shut down.
+    pub(crate) node_id: C::NodeId,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name. It is `None` in
+    /// a request from a version that does not send it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+}
+
+impl<C> DecommissionRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new decommission request.
+    pub fn new(from: VoteOf<C>, node_id: C::NodeId) -> Self {
+        Self {
+            from_leader: from,
+            node_id,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The Leader that removed the node.
+    pub fn from_leader(&self) -> &VoteOf<C> {
+        &self.from_leader
+    }
+
+    /// The node that should shut down.
+    pub fn node_id(&self) -> &C::NodeId {
+        &self.node_id
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
+impl<C> fmt::Display for DecommissionRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from_leader={}, node_id={})", self.from_leader, self.node_id)
+    }
+}
+
+/// The response to [`Raft::decommission()`](crate::Raft::decommission).
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::R: crate::AppDataResponse")
+)]
+pub struct DecommissionResponse<C: RaftTypeConfig> {
+    /// The response of writing the last membership log that removes the node.
+    pub write: ClientWriteResponse<C>,
+
+    /// Whether the removed node acknowledged the request to shut down.
+    ///
+    /// It is `false` if the node is unreachable, or the network does not implement
+    /// [`RaftNetworkV2::decommission()`](crate::network::v2::RaftNetworkV2::decommission). The
+    /// node is removed from the cluster anyway, and the application should stop it.
+    pub shutdown_signaled: bool,
+}
+
+impl<C: RaftTypeConfig> Debug for DecommissionResponse<C>
+where C::R: Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecommissionResponse")
+            .field("write", &self.write)
+            .field("shutdown_signaled", &self.shutdown_signaled)
+            .finish()
+    }
+}
+
+impl<C> fmt::Display for DecommissionResponse<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DecommissionResponse{{write:{}, shutdown_signaled:{}}}",
+            self.write, self.shutdown_signaled
+        )
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::Config;
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayBTreeSetExt;
+
+/// The software version and the capabilities a node advertises to other nodes.
+///
+/// It is exchanged with [`HelloRequest`] and reported in
+/// [`RaftMetrics::node_infos`](crate::RaftMetrics::node_infos), so that a mixed-version cluster
+/// is visible at a glance, e.g., during a rolling upgrade.
+#[derive(Debug, Clone, Default)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct NodeInfo {
+    /// The version of the Openraft crate the node is built with.
+    pub openraft_version: String,
+
+    /// The version of the application, see [`Config::app_version`].
+    pub app_version: String,
+
+    /// The capabilities of Openraft that the node supports, such as
+    /// [`NodeInfo::APPLY_RESULTS`], and the ones of the application, see
+    /// [`Config::capabilities`].
+    pub capabilities: BTreeSet<String>,
+}
+
+impl NodeInfo {
+    /// The node handles [`ApplyResultsRequest`](crate::raft::ApplyResultsRequest).
+    pub const APPLY_RESULTS: &'static str = "openraft:apply-results";
+
+    /// The node handles [`LogRepairRequest`](crate::raft::LogRepairRequest).
+    pub const REPAIR_LOG: &'static str = "openraft:repair-log";
+
+    /// The node handles [`StateMachineChecksumRequest`](crate::raft::StateMachineChecksumRequest).
+    pub const STATE_MACHINE_CHECKSUM: &'static str = "openraft:state-machine-checksum";
+
+    /// The prefix of the capability for a compression scheme the node supports for AppendEntries
+    /// payloads, see [`Config::compression`].
+    pub const COMPRESSION: &'static str = "openraft:compression:";
+
+    /// Build the info of this node, with the capabilities of this version of Openraft and the
+    /// ones configured by the application.
+    pub(crate) fn local(config: &Config) -> Self {
+        let mut capabilities: BTreeSet<String> = [Self::APPLY_RESULTS, Self::REPAIR_LOG, Self::STATE_MACHINE_CHECKSUM]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        capabilities.extend(config.compression.iter().map(|scheme| format!("{}{}", Self::COMPRESSION, scheme)));
+        capabilities.extend(config.capabilities.iter().cloned());
+
+        Self {
+            openraft_version: env!("CARGO_PKG_VERSION").to_string(),
+            app_version: config.app_version.clone(),
+            capabilities,
+        }
+    }
+
+    /// Returns whether the node advertises `capability`.
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.contains(capability)
+    }
+
+    /// Returns the first of the compression `schemes` the node supports.
+    pub fn negotiate_compression<'a>(&self, schemes: &'a [String]) -> Option<&'a str> {
+        schemes
+            .iter()
+            .find(|scheme| self.supports(&format!("{}{}", Self::COMPRESSION, scheme)))
+            .map(|scheme| scheme.as_str())
+    }
+}
+
+impl fmt::Display for NodeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{openraft: {}, app: {}, capabilities: {}}}",
+            self.openraft_version,
+            self.app_version,
+            self.capabilities.display()
+        )
+    }
+}
+
+/// A request a node sends to identify itself to another node, which replies with its own
+/// [`NodeInfo`].
+///
+/// The Leader sends it to every replication target it does not know yet.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct HelloRequest<C>
+where C: RaftTypeConfig
+{
+    /// The id of the node that sends the request.
+    pub(crate) from: C::NodeId,
+
+    /// The info of the node that sends the request.
+    pub(crate) info: NodeInfo,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+}
+
+impl<C> HelloRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new request carrying the info of the sender.
+    pub fn new(from: C::NodeId, info: NodeInfo) -> Self {
+        Self {
+            from,
+            info,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The id of the node that sends the request.
+    pub fn from(&self) -> &C::NodeId {
+        &self.from
+    }
+
+    /// The info of the node that sends the request.
+    pub fn info(&self) -> &NodeInfo {
+        &self.info
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
+impl<C> fmt::Display for HelloRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from={}, info={})", self.from, self.info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+    use crate::raft::NodeInfo;
+
+    #[test]
+    fn test_node_info_local() {
+        let config = Config {
+            app_version: "1.2.0".to_string(),
+            capabilities: vec!["app:v2-codec".to_string()],
+            ..Default::default()
+        };
+
+        let info = NodeInfo::local(&config);
+        assert_eq!(env!("CARGO_PKG_VERSION"), info.openraft_version);
+        assert_eq!("1.2.0", info.app_version);
+        assert!(info.supports(NodeInfo::APPLY_RESULTS));
+        assert!(info.supports("app:v2-codec"));
+        assert!(!info.supports("app:v3-codec"));
+    }
+
+    #[test]
+    fn test_node_info_negotiate_compression() {
+        let config = Config {
+            compression: vec!["lz4".to_string(), "zstd".to_string()],
+            ..Default::default()
+        };
+
+        let info = NodeInfo::local(&config);
+        assert!(info.supports("openraft:compression:lz4"));
+
+        let schemes = |s: &[&str]| s.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(Some("zstd"), info.negotiate_compression(&schemes(&["zstd", "lz4"])));
+        assert_eq!(Some("lz4"), info.negotiate_compression(&schemes(&["snappy", "lz4"])));
+        assert_eq!(None, info.negotiate_compression(&schemes(&["snappy"])));
+        assert_eq!(None, info.negotiate_compression(&[]));
+    }
+}
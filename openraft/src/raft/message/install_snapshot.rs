@@ -22,6 +22,13 @@ pub struct InstallSnapshotRequest<C: RaftTypeConfig> {
 
     /// Will be `true` if this is the last chunk in the snapshot.
     pub done: bool,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name. It is `None` in
+    /// a request from a version that does not send it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
 }
 
 impl<C: RaftTypeConfig> fmt::Display for InstallSnapshotRequest<C> {
@@ -38,6 +45,35 @@ impl<C: RaftTypeConfig> fmt::Display for InstallSnapshotRequest<C> {
     }
 }
 
+impl<C> InstallSnapshotRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a request that sends a chunk of the snapshot described by `meta`.
+    ///
+    /// `data` is the chunk starting at `offset`, and `done` is `true` if it is the last chunk.
+    pub fn new(vote: VoteOf<C>, meta: SnapshotMeta<C>, offset: u64, data: impl Into<Vec<u8>>, done: bool) -> Self {
+        Self {
+            vote,
+            meta,
+            offset,
+            data: data.into(),
+            done,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
 /// The response to an `InstallSnapshotRequest`.
 #[derive(Debug)]
 #[derive(PartialEq, Eq)]
@@ -60,6 +96,13 @@ pub struct SnapshotResponse<C: RaftTypeConfig> {
     pub vote: VoteOf<C>,
 }
 
+impl<C: RaftTypeConfig> InstallSnapshotResponse<C> {
+    /// Create a new install snapshot response with the given vote.
+    pub fn new(vote: VoteOf<C>) -> Self {
+        Self { vote }
+    }
+}
+
 impl<C: RaftTypeConfig> SnapshotResponse<C> {
     /// Create a new snapshot response with the given vote.
     pub fn new(vote: VoteOf<C>) -> Self {
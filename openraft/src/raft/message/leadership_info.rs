@@ -0,0 +1,136 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::VoteOf;
+
+/// What a node believes about the leadership of the cluster, and why it does not start an
+/// election.
+///
+/// Returned by [`Raft::leadership_info()`](crate::Raft::leadership_info).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LeadershipInfo<C: RaftTypeConfig> {
+    /// The id of this node.
+    pub id: C::NodeId,
+
+    /// The server state of this node.
+    pub state: ServerState,
+
+    /// The current vote of this node, which may not be persisted yet.
+    pub vote: VoteOf<C>,
+
+    /// Whether the vote is granted by a quorum, i.e., the node it votes for is an established
+    /// leader.
+    pub vote_committed: bool,
+
+    /// The leader this node believes in: the node of the committed vote, if it is a voter.
+    pub current_leader: Option<C::NodeId>,
+
+    /// The time elapsed since the vote was last updated or confirmed, e.g., by a message from the
+    /// leader, or by granting a vote to a candidate.
+    ///
+    /// It is `None` if the vote has never been updated since this node started.
+    pub since_last_heard: Option<Duration>,
+
+    /// Why this node does not start an election, or `None` if nothing prevents it, in which case
+    /// it starts one at the next election tick.
+    pub no_election_reason: Option<NoElectionReason>,
+}
+
+impl<C> fmt::Display for LeadershipInfo<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LeadershipInfo{{id:{}, state:{:?}, vote:{}, vote_committed:{}, current_leader:{}, since_last_heard:{}, no_election_reason:{}}}",
+            self.id,
+            self.state,
+            self.vote,
+            self.vote_committed,
+            self.current_leader.display(),
+            self.since_last_heard.map(|d| format!("{:?}", d)).display(),
+            self.no_election_reason.display()
+        )
+    }
+}
+
+/// Why a node does not start an election, in [`LeadershipInfo`].
+///
+/// The reasons are checked in the order they are declared, the first one that applies is
+/// reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum NoElectionReason {
+    /// This node is the leader.
+    Leader,
+
+    /// This node is a candidate running an election.
+    ///
+    /// If it is not granted by a quorum, it starts another one after the election timeout.
+    Campaigning,
+
+    /// The cluster is not initialized: there is no voter in the membership this node knows.
+    ///
+    /// Call [`Raft::initialize()`](crate::Raft::initialize) on one of the nodes.
+    Uninitialized,
+
+    /// This node is not a voter in the effective membership: it is a learner, or it is removed.
+    NotVoter,
+
+    /// Elections are disabled, by [`Config::enable_elect`] or by
+    /// [`RuntimeConfigHandle::elect()`].
+    ///
+    /// [`Config::enable_elect`]: crate::Config::enable_elect
+    /// [`RuntimeConfigHandle::elect()`]: crate::raft::RuntimeConfigHandle::elect
+    ElectionDisabled,
+
+    /// This node is in read-only mode, see [`Raft::enter_readonly()`](crate::Raft::enter_readonly).
+    ReadOnly,
+
+    /// The vote is still valid: this node heard from the leader, or granted a vote to a candidate,
+    /// within the leader lease and the election timeout.
+    ///
+    /// This node starts an election if it does not hear from a leader in `remaining`.
+    LeaseNotExpired {
+        /// The time left before the vote expires.
+        remaining: Duration,
+    },
+
+    /// The last attempt to start an election is vetoed by the hook set with
+    /// [`Raft::set_election_veto()`](crate::Raft::set_election_veto).
+    Vetoed {
+        /// The reason returned by the hook.
+        reason: String,
+    },
+
+    /// The local storage failed the check before the last attempt to start an election, see
+    /// [`Config::check_storage_before_election`](crate::Config::check_storage_before_election).
+    StorageCheckFailed {
+        /// Why the storage check failed.
+        reason: String,
+    },
+}
+
+impl fmt::Display for NoElectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NoElectionReason::Leader => write!(f, "this node is the leader"),
+            NoElectionReason::Campaigning => write!(f, "this node is running an election"),
+            NoElectionReason::Uninitialized => write!(f, "the cluster is not initialized"),
+            NoElectionReason::NotVoter => write!(f, "this node is not a voter"),
+            NoElectionReason::ElectionDisabled => write!(f, "election is disabled"),
+            NoElectionReason::ReadOnly => write!(f, "this node is read-only"),
+            NoElectionReason::LeaseNotExpired { remaining } => {
+                write!(f, "the vote expires in {:?}", remaining)
+            }
+            NoElectionReason::Vetoed { reason } => write!(f, "election is vetoed: {}", reason),
+            NoElectionReason::StorageCheckFailed { reason } => {
+                write!(f, "storage check failed: {}", reason)
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::type_config::alias::LogIdOf;
+
+/// The checksum of a range of committed log entries on the leader, sent with a heartbeat.
+///
+/// A follower that has applied the same range computes the checksum of its own copy and reports
+/// a mismatch in [`RaftMetrics::log_checksum_mismatches`], revealing a silently diverged or
+/// corrupted log before it shows up as a difference in the state machine. See
+/// [`Config::log_checksum_interval`].
+///
+/// [`RaftMetrics::log_checksum_mismatches`]: crate::metrics::RaftMetrics::log_checksum_mismatches
+/// [`Config::log_checksum_interval`]: crate::Config::log_checksum_interval
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LogChecksum<C>
+where C: RaftTypeConfig
+{
+    /// The index of the first log entry in the range.
+    pub start: u64,
+
+    /// The log id of the last log entry in the range.
+    pub last: LogIdOf<C>,
+
+    /// The checksum combined from the checksum of every entry in the range, in log order.
+    pub checksum: u64,
+}
+
+impl<C> LogChecksum<C>
+where C: RaftTypeConfig
+{
+    /// Build the checksum of consecutive `entries`, with the checksum of every entry returned by
+    /// `entry_checksum`.
+    ///
+    /// It returns `None` if `entries` is empty, or if the checksum of any entry is unknown.
+    pub(crate) fn from_entries(
+        entries: &[C::Entry],
+        entry_checksum: impl Fn(&C::Entry) -> Option<u64>,
+    ) -> Option<Self> {
+        let first = entries.first()?;
+        let last = entries.last()?;
+
+        let mut checksum = 0xcbf2_9ce4_8422_2325u64;
+        for entry in entries {
+            checksum = (checksum ^ entry_checksum(entry)?).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+
+        Some(Self {
+            start: first.index(),
+            last: last.log_id(),
+            checksum,
+        })
+    }
+}
+
+impl<C> fmt::Display for LogChecksum<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]: {:016x}", self.start, self.last, self.checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogChecksum;
+    use crate::Entry;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::RaftEntry;
+
+    fn entries(start: u64, end: u64) -> Vec<Entry<UTConfig>> {
+        (start..end).map(|i| Entry::new_blank(log_id(1, 1, i))).collect()
+    }
+
+    #[test]
+    fn test_from_entries() {
+        let c = LogChecksum::<UTConfig>::from_entries(&entries(3, 6), |e| Some(e.index())).unwrap();
+        assert_eq!(3, c.start);
+        assert_eq!(log_id(1, 1, 5), c.last);
+
+        let same = LogChecksum::<UTConfig>::from_entries(&entries(3, 6), |e| Some(e.index())).unwrap();
+        assert_eq!(c, same);
+
+        let other = LogChecksum::<UTConfig>::from_entries(&entries(3, 6), |e| Some(e.index() + 1)).unwrap();
+        assert_ne!(c.checksum, other.checksum);
+
+        let reordered = LogChecksum::<UTConfig>::from_entries(&entries(3, 6), |e| Some(8 - e.index())).unwrap();
+        assert_ne!(c.checksum, reordered.checksum, "order matters");
+
+        assert_eq!(None, LogChecksum::<UTConfig>::from_entries(&[], |e| Some(e.index())));
+        assert_eq!(
+            None,
+            LogChecksum::<UTConfig>::from_entries(&entries(3, 6), |e| (e.index() != 4).then_some(1))
+        );
+    }
+}
@@ -3,20 +3,55 @@
 //! Request and response types for an application to talk to the Raft,
 //! and are also used by network layer to talk to other Raft nodes.
 
+mod add_learner;
 mod append_entries;
+mod apply_results;
+mod cluster_health;
+#[cfg(feature = "serde")]
+mod codec;
+mod decommission;
+mod hello;
 mod install_snapshot;
+mod leadership_info;
+mod log_checksum;
+mod repair_log;
+mod replication_progress;
+mod sm_checksum;
 mod transfer_leader;
 mod vote;
 
 mod client_write;
 
+pub use add_learner::AddLearnerResponse;
 pub use append_entries::AppendEntriesRequest;
 pub use append_entries::AppendEntriesResponse;
+pub use apply_results::ApplyResultsRequest;
+pub use apply_results::ComputedResult;
 pub use client_write::ClientWriteResponse;
 pub use client_write::ClientWriteResult;
+pub use cluster_health::ClusterHealth;
+pub use cluster_health::NodeHealth;
+#[cfg(feature = "serde")]
+pub use codec::MESSAGE_FORMAT_VERSION;
+#[cfg(feature = "serde")]
+pub use codec::MessageDecodeError;
+#[cfg(feature = "serde")]
+pub use codec::MessageEncodeError;
+pub use decommission::DecommissionRequest;
+pub use decommission::DecommissionResponse;
+pub use hello::HelloRequest;
+pub use hello::NodeInfo;
 pub use install_snapshot::InstallSnapshotRequest;
 pub use install_snapshot::InstallSnapshotResponse;
 pub use install_snapshot::SnapshotResponse;
+pub use leadership_info::LeadershipInfo;
+pub use leadership_info::NoElectionReason;
+pub use log_checksum::LogChecksum;
+pub use repair_log::LogRepairRequest;
+pub use replication_progress::InflightData;
+pub use replication_progress::TargetProgress;
+pub use sm_checksum::StateMachineChecksumRequest;
+pub use sm_checksum::StateMachineChecksums;
 pub use transfer_leader::TransferLeaderRequest;
 pub use vote::VoteRequest;
 pub use vote::VoteResponse;
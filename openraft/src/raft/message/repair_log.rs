@@ -0,0 +1,78 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// A request sent by a follower to ask the Leader for a copy of committed log entries that the
+/// follower cannot read from its own log store.
+///
+/// See [`Config::repair_corrupted_log`](crate::Config::repair_corrupted_log).
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct LogRepairRequest<C>
+where C: RaftTypeConfig
+{
+    /// The id of the follower that sends the request.
+    pub(crate) from: C::NodeId,
+
+    /// The first log id of the range to repair, inclusive.
+    pub(crate) first: LogIdOf<C>,
+
+    /// The last log id of the range to repair, inclusive.
+    pub(crate) last: LogIdOf<C>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+}
+
+impl<C> LogRepairRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new request for the log entries in `[first, last]`.
+    pub fn new(from: C::NodeId, first: LogIdOf<C>, last: LogIdOf<C>) -> Self {
+        Self {
+            from,
+            first,
+            last,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The id of the follower that sends the request.
+    pub fn from(&self) -> &C::NodeId {
+        &self.from
+    }
+
+    /// The first log id of the range to repair, inclusive.
+    pub fn first(&self) -> &LogIdOf<C> {
+        &self.first
+    }
+
+    /// The last log id of the range to repair, inclusive.
+    pub fn last(&self) -> &LogIdOf<C> {
+        &self.last
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
+impl<C> fmt::Display for LogRepairRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from={}, range=[{}, {}])", self.from, self.first, self.last)
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayOptionExt;
+use crate::progress::Inflight;
+use crate::progress::entry::ProgressEntry;
+use crate::type_config::alias::LogIdOf;
+
+/// The replication progress of a single target, as tracked by the leader.
+///
+/// It is a read-only snapshot of the state the leader uses to find the last matching log id on
+/// the target and to decide what to send next. Returned by
+/// [`Raft::replication_progress()`](crate::Raft::replication_progress).
+///
+/// See: [Algorithm to find the last matching log id on a Follower][algo].
+///
+/// [algo]: crate::docs::protocol::replication::log_replication#algorithm-to-find-the-last-matching-log-id-on-a-follower
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct TargetProgress<C: RaftTypeConfig> {
+    /// Whether the target is a voter, otherwise a learner.
+    pub voter: bool,
+
+    /// The id of the last log known to match on the target.
+    pub matching: Option<LogIdOf<C>>,
+
+    /// One plus the max log index on the target that might match the leader log.
+    ///
+    /// When it is greater than `matching.next_index()`, the leader is still searching for the last
+    /// matching log id.
+    pub searching_end: u64,
+
+    /// The data being sent to the target and waiting for a response.
+    pub inflight: InflightData<C>,
+}
+
+impl<C> TargetProgress<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(voter: bool, entry: &ProgressEntry<C>) -> Self {
+        Self {
+            voter,
+            matching: entry.matching().cloned(),
+            searching_end: entry.searching_end,
+            inflight: InflightData::from(&entry.inflight),
+        }
+    }
+}
+
+impl<C> fmt::Display for TargetProgress<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{voter:{}, matching:{}, searching_end:{}, inflight:{}}}",
+            self.voter,
+            self.matching.display(),
+            self.searching_end,
+            self.inflight
+        )
+    }
+}
+
+/// The data being sent to a target, in a [`TargetProgress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum InflightData<C: RaftTypeConfig> {
+    /// Nothing is being sent.
+    None,
+
+    /// Log entries in the range `(prev, last]` are being sent.
+    Logs {
+        prev: Option<LogIdOf<C>>,
+        last: Option<LogIdOf<C>>,
+    },
+
+    /// A snapshot including logs up to `last_log_id` is being sent.
+    Snapshot { last_log_id: Option<LogIdOf<C>> },
+}
+
+impl<C> From<&Inflight<C>> for InflightData<C>
+where C: RaftTypeConfig
+{
+    fn from(inflight: &Inflight<C>) -> Self {
+        match inflight {
+            Inflight::None => InflightData::None,
+            Inflight::Logs { log_id_range } => InflightData::Logs {
+                prev: log_id_range.prev.clone(),
+                last: log_id_range.last.clone(),
+            },
+            Inflight::Snapshot { last_log_id } => InflightData::Snapshot {
+                last_log_id: last_log_id.clone(),
+            },
+        }
+    }
+}
+
+impl<C> fmt::Display for InflightData<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InflightData::None => write!(f, "None"),
+            InflightData::Logs { prev, last } => write!(f, "Logs:({}, {}]", prev.display(), last.display()),
+            InflightData::Snapshot { last_log_id } => write!(f, "Snapshot:{}", last_log_id.display()),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::display_ext::DisplayBtreeMapOptValueExt;
+use crate::display_ext::DisplayOptionExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// A request sent by the Leader to ask a node for the checksum of its state machine right after
+/// a log entry is applied.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct StateMachineChecksumRequest<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the Leader that collects the checksums.
+    pub(crate) from_leader: VoteOf<C>,
+
+    /// The log id to compute the checksum at.
+    pub(crate) at: LogIdOf<C>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
+}
+
+impl<C> StateMachineChecksumRequest<C>
+where C: RaftTypeConfig
+{
+    /// Create a new checksum request at log id `at`.
+    pub fn new(from: VoteOf<C>, at: LogIdOf<C>) -> Self {
+        Self {
+            from_leader: from,
+            at,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The Leader that collects the checksums.
+    pub fn from_leader(&self) -> &VoteOf<C> {
+        &self.from_leader
+    }
+
+    /// The log id to compute the checksum at.
+    pub fn at(&self) -> &LogIdOf<C> {
+        &self.at
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
+}
+
+impl<C> fmt::Display for StateMachineChecksumRequest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(from_leader={}, at={})", self.from_leader, self.at)
+    }
+}
+
+/// The checksums of the state machines of every member, computed at the same log id.
+///
+/// Returned by [`Raft::state_machine_checksums()`](crate::Raft::state_machine_checksums).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct StateMachineChecksums<C: RaftTypeConfig> {
+    /// The id of the leader that collected the checksums.
+    pub leader_id: C::NodeId,
+
+    /// The log id the checksums are computed at, of a blank log entry proposed by the leader for
+    /// it.
+    ///
+    /// It is `None` if the blank entry cannot be proposed, e.g., the leadership is being
+    /// transferred, and no checksum is collected.
+    pub at: Option<LogIdOf<C>>,
+
+    /// The checksum of every voter and learner, including the leader itself.
+    ///
+    /// It is `None` if the node did not respond in time, e.g., it did not apply the blank entry
+    /// within the timeout, or its state machine does not support checksums.
+    pub checksums: BTreeMap<C::NodeId, Option<u64>>,
+}
+
+impl<C> StateMachineChecksums<C>
+where C: RaftTypeConfig
+{
+    /// The ids of the members whose checksum differs from the checksum of the leader.
+    ///
+    /// Members without a checksum are not considered.
+    pub fn mismatched(&self) -> impl Iterator<Item = &C::NodeId> + '_ {
+        let expected = self.checksums.get(&self.leader_id).copied().flatten();
+
+        self.checksums
+            .iter()
+            .filter(move |(_, c)| expected.is_some() && c.is_some() && **c != expected)
+            .map(|(id, _)| id)
+    }
+
+    /// The ids of the members that did not report a checksum.
+    pub fn unknown(&self) -> impl Iterator<Item = &C::NodeId> + '_ {
+        self.checksums.iter().filter(|(_, c)| c.is_none()).map(|(id, _)| id)
+    }
+}
+
+impl<C> fmt::Display for StateMachineChecksums<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "StateMachineChecksums{{leader:{}, at:{}, checksums:{{{}}}}}",
+            self.leader_id,
+            self.at.display(),
+            self.checksums.display()
+        )
+    }
+}
@@ -20,6 +20,13 @@ where C: RaftTypeConfig
 
     /// The last log id the `to_node_id` node should at least have to become Leader.
     pub(crate) last_log_id: Option<LogIdOf<C>>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name. It is `None` in
+    /// a request from a version that does not send it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
 }
 
 impl<C> TransferLeaderRequest<C>
@@ -31,9 +38,16 @@ where C: RaftTypeConfig
             from_leader: from,
             to_node_id: to,
             last_log_id,
+            cluster_name: None,
         }
     }
 
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
     /// From which Leader the leadership is transferred.
     pub fn from_leader(&self) -> &VoteOf<C> {
         &self.from_leader
@@ -50,6 +64,11 @@ where C: RaftTypeConfig
     pub fn last_log_id(&self) -> Option<&LogIdOf<C>> {
         self.last_log_id.as_ref()
     }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
+    }
 }
 
 impl<C> fmt::Display for TransferLeaderRequest<C>
@@ -14,6 +14,13 @@ pub struct VoteRequest<C: RaftTypeConfig> {
     pub vote: VoteOf<C>,
     /// The candidate's last log id.
     pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the sender.
+    ///
+    /// The receiver rejects the request if it is in a cluster with another name. It is `None` in
+    /// a request from a version that does not send it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cluster_name: Option<String>,
 }
 
 impl<C> fmt::Display for VoteRequest<C>
@@ -29,7 +36,22 @@ where C: RaftTypeConfig
 {
     /// Create a new vote request.
     pub fn new(vote: VoteOf<C>, last_log_id: Option<LogIdOf<C>>) -> Self {
-        Self { vote, last_log_id }
+        Self {
+            vote,
+            last_log_id,
+            cluster_name: None,
+        }
+    }
+
+    /// Set the cluster name of the sender.
+    pub fn with_cluster_name(mut self, cluster_name: Option<String>) -> Self {
+        self.cluster_name = cluster_name;
+        self
+    }
+
+    /// The cluster name of the sender, if it is sent.
+    pub fn cluster_name(&self) -> Option<&str> {
+        self.cluster_name.as_deref()
     }
 }
 
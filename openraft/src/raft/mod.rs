@@ -15,6 +15,7 @@ mod impl_raft_blocking_write;
 pub mod linearizable_read;
 pub(crate) mod message;
 mod raft_inner;
+mod read_only_raft;
 pub mod responder;
 mod runtime_config_handle;
 pub mod trigger;
@@ -30,18 +31,43 @@ pub(in crate::raft) mod core_state;
 
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use core_state::CoreState;
 use derive_more::Display;
 use linearizable_read::Linearizer;
+pub use message::AddLearnerResponse;
 pub use message::AppendEntriesRequest;
 pub use message::AppendEntriesResponse;
+pub use message::ApplyResultsRequest;
 pub use message::ClientWriteResponse;
 pub use message::ClientWriteResult;
+pub use message::ClusterHealth;
+pub use message::ComputedResult;
+pub use message::DecommissionRequest;
+pub use message::DecommissionResponse;
+pub use message::HelloRequest;
+pub use message::InflightData;
 pub use message::InstallSnapshotRequest;
 pub use message::InstallSnapshotResponse;
+pub use message::LeadershipInfo;
+pub use message::LogChecksum;
+pub use message::LogRepairRequest;
+#[cfg(feature = "serde")]
+pub use message::MESSAGE_FORMAT_VERSION;
+#[cfg(feature = "serde")]
+pub use message::MessageDecodeError;
+#[cfg(feature = "serde")]
+pub use message::MessageEncodeError;
+pub use message::NoElectionReason;
+pub use message::NodeHealth;
+pub use message::NodeInfo;
 pub use message::SnapshotResponse;
+pub use message::StateMachineChecksumRequest;
+pub use message::StateMachineChecksums;
+pub use message::TargetProgress;
 pub use message::TransferLeaderRequest;
 pub use message::VoteRequest;
 pub use message::VoteResponse;
@@ -50,57 +76,105 @@ use tracing::Instrument;
 use tracing::Level;
 use tracing::trace_span;
 
+use crate::LogIdOptionExt;
+use crate::Membership;
 use crate::OptionalSend;
 use crate::RaftNetworkFactory;
 use crate::RaftState;
 pub use crate::RaftTypeConfig;
+use crate::SnapshotId;
+use crate::StorageError;
 use crate::StorageHelper;
 use crate::async_runtime::OneshotSender;
 use crate::async_runtime::watch::WatchReceiver;
 use crate::base::BoxFuture;
 use crate::base::BoxMaybeAsyncOnceMut;
 use crate::base::BoxOnce;
+use crate::config::ColdStartReadPolicy;
 use crate::config::Config;
+use crate::config::NonMemberRpcPolicy;
 use crate::config::RuntimeConfig;
 use crate::core::RaftCore;
+use crate::core::ReadBatch;
 use crate::core::Tick;
+use crate::core::VoteRateLimiter;
+use crate::core::app_index::AppIndex;
+use crate::core::apply_results_sender::ApplyResultsSenders;
+use crate::core::election_stats::ElectionStats;
 use crate::core::heartbeat::handle::HeartbeatWorkersHandle;
 pub use crate::core::io_flush_tracking::FlushPoint;
 use crate::core::io_flush_tracking::IoProgressWatcher;
 use crate::core::io_flush_tracking::LogProgress;
 use crate::core::io_flush_tracking::VoteProgress;
+use crate::core::local_entries::LocalEntriesHandle;
+use crate::core::log_checksum::LogChecksumHandle;
+use crate::core::node_infos::NodeInfos;
 use crate::core::raft_msg::RaftMsg;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::core::sm;
+use crate::core::sm::computed_results::ComputedResults;
 use crate::core::sm::worker;
+use crate::core::transition_stats::TransitionStats;
 use crate::engine::Engine;
 use crate::engine::EngineConfig;
+use crate::entry::RaftEntry;
+use crate::error::AllowNextRevertError;
 use crate::error::CheckIsLeaderError;
 use crate::error::ClientWriteError;
+use crate::error::ClusterMismatch;
+use crate::error::DecommissionRejected;
 use crate::error::Fatal;
+use crate::error::ForwardToLeader;
 use crate::error::InitializeError;
 use crate::error::InvalidStateMachineType;
+use crate::error::NonMemberRejected;
 use crate::error::RaftError;
 use crate::error::into_raft_result::IntoRaftResult;
+use crate::membership::EffectiveMembership;
 use crate::membership::IntoNodes;
+use crate::metrics::ApplyLagAlarm;
+use crate::metrics::ApplyLagEvent;
+use crate::metrics::ApplyLagEvents;
+use crate::metrics::CapacityHint;
+use crate::metrics::MetricsHistory;
+use crate::metrics::MetricsSample;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::SnapshotReceiveProgress;
 use crate::metrics::Wait;
+use crate::metrics::WaitError;
+use crate::network::NetworkEvent;
+use crate::network::NetworkEventBus;
+use crate::network::layer::NetworkLayers;
 use crate::raft::raft_inner::RaftInner;
+pub use crate::raft::read_only_raft::ReadOnlyRaft;
 pub use crate::raft::runtime_config_handle::RuntimeConfigHandle;
 use crate::raft::trigger::Trigger;
+use crate::raft_state::LogStateReader;
 use crate::raft_state::RuntimeStats;
+use crate::replication::entry_cache::EntryCache;
+use crate::replication::snapshot_permits::SnapshotPermits;
+use crate::storage::LogArchiver;
+use crate::storage::RaftLogReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
+use crate::storage::RecoveryReport;
 use crate::storage::Snapshot;
+use crate::storage::StorageStamp;
+use crate::trace::EngineTrace;
+use crate::trace::initial_state::InitialState;
+use crate::trace::recorder::EngineRecorder;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::JoinErrorOf;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::MpscUnboundedReceiverOf;
 use crate::type_config::alias::SnapshotDataOf;
 use crate::type_config::alias::VoteOf;
 use crate::type_config::alias::WatchReceiverOf;
 use crate::type_config::alias::WriteResponderOf;
+use crate::vote::RaftVote;
+use crate::vote::raft_vote::RaftVoteExt;
 
 /// Define types for a Raft type configuration.
 ///
@@ -120,11 +194,11 @@ use crate::type_config::alias::WriteResponderOf;
 ///        Node         = openraft::BasicNode,
 ///        Term         = u64,
 ///        LeaderId     = openraft::impls::leader_id_adv::LeaderId<Self>,
-///        Vote           = openraft::impls::Vote<Self>,
-///        Entry          = openraft::Entry<Self>,
-///        SnapshotData   = Cursor<Vec<u8>>,
-///        Responder<T>   = openraft::impls::OneshotResponder<Self, T>,
-///        AsyncRuntime   = openraft::TokioRuntime,
+///        Vote         = openraft::impls::Vote<Self>,
+///        Entry        = openraft::Entry<Self>,
+///        SnapshotData = Cursor<Vec<u8>>,
+///        Responder<T> = openraft::impls::OneshotResponder<Self, T>,
+///        AsyncRuntime = openraft::TokioRuntime,
 /// );
 /// ```
 ///
@@ -135,11 +209,11 @@ use crate::type_config::alias::WriteResponderOf;
 /// - `Node`:         `::openraft::impls::BasicNode`
 /// - `Term`:         `u64`
 /// - `LeaderId`:     `::openraft::impls::leader_id_adv::LeaderId<Self>`
-/// - `Vote`:           `::openraft::impls::Vote<Self>`
-/// - `Entry`:          `::openraft::impls::Entry<Self>`
-/// - `SnapshotData`:   `Cursor<Vec<u8>>`
-/// - `Responder<T>`:   `::openraft::impls::OneshotResponder<Self, T>`
-/// - `AsyncRuntime`:   `::openraft::impls::TokioRuntime`
+/// - `Vote`:         `::openraft::impls::Vote<Self>`
+/// - `Entry`:        `::openraft::impls::Entry<Self>`
+/// - `SnapshotData`: `Cursor<Vec<u8>>`
+/// - `Responder<T>`: `::openraft::impls::OneshotResponder<Self, T>`
+/// - `AsyncRuntime`: `::openraft::impls::TokioRuntime`
 ///
 /// For example, to declare with only `D` and `R` types:
 /// ```ignore
@@ -154,8 +228,101 @@ use crate::type_config::alias::WriteResponderOf;
 /// ```ignore
 /// openraft::declare_raft_types!(pub TypeConfig);
 /// ```
+///
+/// `Responder<T>` is generic over the value it sends: the `where T: OptionalSend + 'static` bound
+/// required by [`RaftTypeConfig::Responder`] is added by this macro:
+/// ```ignore
+/// openraft::declare_raft_types!(
+///    pub TypeConfig:
+///        Responder<T> = MyResponder<T>,
+/// );
+/// ```
+///
+/// To also declare a module of type aliases specialized for the type config, such as `Raft`,
+/// `LogId`, `Entry`, `StorageError` or `RaftError<E>`, name the module after the type config:
+/// ```ignore
+/// openraft::declare_raft_types!(
+///    pub TypeConfig, pub mod typ:
+///        D = ClientRequest,
+///        R = ClientResponse,
+/// );
+///
+/// fn last_log_id(raft: &typ::Raft) -> Option<typ::LogId> { /* ... */ }
+/// ```
+///
+/// The alias module refers to the type config with `super::TypeConfig`, thus it must be declared
+/// at module level, not in a function body.
+///
+/// A type that does not satisfy the bound of its associated type fails to compile, with an error
+/// that names the associated type and its default, for example:
+/// ```text
+/// error[E0277]: `String` can not be used as the `Term` of a `RaftTypeConfig`
+/// ```
 #[macro_export]
 macro_rules! declare_raft_types {
+    // Declare the type config, and a module of type aliases specialized for it.
+    (
+        $(#[$outer:meta])* $visibility:vis $id:ident, $types_visibility:vis mod $types:ident
+        $(: $($type_defs:tt)*)?
+    ) => {
+        $crate::declare_raft_types!($(#[$outer])* $visibility $id: $($($type_defs)*)?);
+
+        /// Type aliases specialized for
+        #[doc = concat!("[`", stringify!($id), "`].")]
+        #[allow(dead_code)]
+        #[allow(missing_docs)]
+        $types_visibility mod $types {
+            use super::$id as C;
+
+            pub type Raft = $crate::Raft<C>;
+
+            pub type NodeId = <C as $crate::RaftTypeConfig>::NodeId;
+            pub type Node = <C as $crate::RaftTypeConfig>::Node;
+            pub type Term = <C as $crate::RaftTypeConfig>::Term;
+
+            pub type Vote = <C as $crate::RaftTypeConfig>::Vote;
+            pub type LeaderId = <C as $crate::RaftTypeConfig>::LeaderId;
+            pub type LogId = $crate::LogId<C>;
+            pub type Entry = <C as $crate::RaftTypeConfig>::Entry;
+            pub type EntryPayload = $crate::EntryPayload<C>;
+            pub type Membership = $crate::membership::Membership<C>;
+            pub type StoredMembership = $crate::StoredMembership<C>;
+
+            pub type LogState = $crate::storage::LogState<C>;
+
+            pub type SnapshotMeta = $crate::SnapshotMeta<C>;
+            pub type Snapshot = $crate::Snapshot<C>;
+            pub type SnapshotData = <C as $crate::RaftTypeConfig>::SnapshotData;
+
+            pub type IOFlushed = $crate::storage::IOFlushed<C>;
+
+            pub type Infallible = $crate::error::Infallible;
+            pub type Fatal = $crate::error::Fatal<C>;
+            pub type RaftError<E = $crate::error::Infallible> = $crate::error::RaftError<C, E>;
+            pub type RPCError<E = $crate::error::Infallible> = $crate::error::RPCError<C, E>;
+
+            pub type ErrorSubject = $crate::ErrorSubject<C>;
+            pub type StorageError = $crate::StorageError<C>;
+            pub type StreamingError = $crate::error::StreamingError<C>;
+
+            pub type RaftMetrics = $crate::RaftMetrics<C>;
+
+            pub type ClientWriteError = $crate::error::ClientWriteError<C>;
+            pub type CheckIsLeaderError = $crate::error::CheckIsLeaderError<C>;
+            pub type ForwardToLeader = $crate::error::ForwardToLeader<C>;
+            pub type InitializeError = $crate::error::InitializeError<C>;
+
+            pub type VoteRequest = $crate::raft::VoteRequest<C>;
+            pub type VoteResponse = $crate::raft::VoteResponse<C>;
+            pub type AppendEntriesRequest = $crate::raft::AppendEntriesRequest<C>;
+            pub type AppendEntriesResponse = $crate::raft::AppendEntriesResponse<C>;
+            pub type InstallSnapshotRequest = $crate::raft::InstallSnapshotRequest<C>;
+            pub type InstallSnapshotResponse = $crate::raft::InstallSnapshotResponse<C>;
+            pub type SnapshotResponse = $crate::raft::SnapshotResponse<C>;
+            pub type ClientWriteResponse = $crate::raft::ClientWriteResponse<C>;
+        }
+    };
+
     // Add a trailing colon to    `declare_raft_types(MyType)`,
     // Make it the standard form: `declare_raft_types(MyType:)`.
     ($(#[$outer:meta])* $visibility:vis $id:ident) => {
@@ -163,7 +330,9 @@ macro_rules! declare_raft_types {
     };
 
     // The main entry of this macro
-    ($(#[$outer:meta])* $visibility:vis $id:ident: $($(#[$inner:meta])* $type_id:ident = $type:ty),* $(,)? ) => {
+    ($(#[$outer:meta])* $visibility:vis $id:ident:
+        $($(#[$inner:meta])* $type_id:ident $(<$type_param:ident>)? = $type:ty),* $(,)?
+    ) => {
         $(#[$outer])*
         #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
         $visibility struct $id {}
@@ -175,7 +344,13 @@ macro_rules! declare_raft_types {
             $crate::openraft_macros::expand!(
                 KEYED,
                 (T, ATTR, V) => {ATTR type T = V;},
-                $(($type_id, $(#[$inner])*, $type),)*
+                $(
+                    (
+                        $type_id $(<$type_param>)?,
+                        $(#[$inner])*,
+                        $type $(where $type_param: $crate::OptionalSend + 'static)?
+                    ),
+                )*
 
                 // Default types:
                 (D            , , String                                       ),
@@ -184,11 +359,11 @@ macro_rules! declare_raft_types {
                 (Node         , , $crate::impls::BasicNode                     ),
                 (Term         , , u64                                          ),
                 (LeaderId     , , $crate::impls::leader_id_adv::LeaderId<Self> ),
-                (Vote           , , $crate::impls::Vote<Self>                    ),
-                (Entry          , , $crate::impls::Entry<Self>                   ),
-                (SnapshotData   , , std::io::Cursor<Vec<u8>>                     ),
-                (Responder<T>   , , $crate::impls::OneshotResponder<Self, T> where T: $crate::OptionalSend + 'static     ),
-                (AsyncRuntime   , , $crate::impls::TokioRuntime                  ),
+                (Vote         , , $crate::impls::Vote<Self>                    ),
+                (Entry        , , $crate::impls::Entry<Self>                   ),
+                (SnapshotData , , std::io::Cursor<Vec<u8>>                     ),
+                (Responder<T> , , $crate::impls::OneshotResponder<Self, T> where T: $crate::OptionalSend + 'static ),
+                (AsyncRuntime , , $crate::impls::TokioRuntime                  ),
             );
 
         }
@@ -221,6 +396,35 @@ pub enum ReadPolicy {
     ReadIndex,
 }
 
+/// The consistency guarantee of a read served by [`Raft::read()`].
+///
+/// From the strongest to the weakest:
+/// - [`ReadIndex`](Self::ReadIndex) and [`LeaderLease`](Self::LeaderLease) are linearizable: the
+///   read observes every write committed before it started.
+/// - [`Stale`](Self::Stale) observes whatever this node has applied, and may miss recent writes.
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum ReadGuarantee {
+    /// Linearizable read that confirms leadership with a quorum, see [`ReadPolicy::ReadIndex`].
+    ReadIndex,
+
+    /// Linearizable read that relies on the leader lease, see [`ReadPolicy::LeaseRead`].
+    LeaderLease,
+
+    /// Read the local state machine as it is, on any node, without contacting other nodes.
+    Stale,
+}
+
+impl ReadGuarantee {
+    /// The policy used to ensure leadership, or `None` if leadership is not required.
+    pub fn read_policy(&self) -> Option<ReadPolicy> {
+        match self {
+            ReadGuarantee::ReadIndex => Some(ReadPolicy::ReadIndex),
+            ReadGuarantee::LeaderLease => Some(ReadPolicy::LeaseRead),
+            ReadGuarantee::Stale => None,
+        }
+    }
+}
+
 /// Primary interface to a Raft node.
 ///
 /// `Raft` provides the complete implementation of the Raft consensus protocol and serves as the
@@ -239,7 +443,7 @@ pub enum ReadPolicy {
 /// 2. **Initialization**: Call [`initialize`](Raft::initialize) on pristine nodes to form a cluster
 /// 3. **Operation**: Use various methods to interact with the node:
 ///    - Protocol RPCs: [`append_entries`](Raft::append_entries), [`vote`](Raft::vote)
-///    - Client operations: [`client_write`](Raft::client_write),
+///    - Client operations: [`client_write`](Raft::client_write), [`read`](Raft::read),
 ///      [`ensure_linearizable`](Raft::ensure_linearizable)
 ///    - Management: [`trigger`](Raft::trigger), [`metrics`](Raft::metrics)
 /// 4. **Shutdown**: Call [`shutdown`](Raft::shutdown) to gracefully stop the node
@@ -308,6 +512,11 @@ where C: RaftTypeConfig
     /// ### `storage`
     /// An implementation of the [`RaftLogStorage`] and [`RaftStateMachine`] trait which will be
     /// used by Raft for data storage.
+    ///
+    /// On the first start, the storage is stamped with `id` and
+    /// [`Config::cluster_name`](crate::Config::cluster_name). On a later start with a different
+    /// `id` or cluster name it returns [`Fatal::StorageStampMismatch`], if the log store
+    /// implements [`RaftLogStorage::read_stamp`].
     #[tracing::instrument(level="debug", skip_all, fields(cluster=%config.cluster_name))]
     pub async fn new<LS, N, SM>(
         id: C::NodeId,
@@ -325,18 +534,25 @@ where C: RaftTypeConfig
         let notification_channel_size = config.notification_channel_size();
 
         let (tx_api, rx_api) = C::mpsc(api_channel_size);
+        let (tx_api_priority, rx_api_priority) = C::mpsc(api_channel_size);
         let (tx_notify, rx_notify) = C::mpsc(notification_channel_size);
+        let (tx_notify_priority, rx_notify_priority) = C::mpsc(notification_channel_size);
         let (tx_metrics, rx_metrics) = C::watch_channel(RaftMetrics::new_initial(id.clone()));
         let (tx_data_metrics, rx_data_metrics) = C::watch_channel(RaftDataMetrics::default());
         let (tx_server_metrics, rx_server_metrics) = C::watch_channel(RaftServerMetrics::default());
         let (tx_progress, progress_watcher) = IoProgressWatcher::new();
         let (tx_shutdown, rx_shutdown) = C::oneshot();
 
-        let tick_handle = Tick::spawn(
-            Duration::from_millis(config.heartbeat_interval * 3 / 2),
-            tx_notify.clone(),
-            config.enable_tick,
-        );
+        let tick_handle = if config.manual_tick {
+            Tick::manual(tx_notify.clone(), config.enable_tick)
+        } else {
+            Tick::spawn(
+                &id,
+                config.heartbeat_interval() * 3 / 2,
+                tx_notify.clone(),
+                config.enable_tick,
+            )
+        };
 
         let runtime_config = Arc::new(RuntimeConfig::new(&config));
 
@@ -350,57 +566,157 @@ where C: RaftTypeConfig
 
         let eng_config = EngineConfig::new(id.clone(), config.as_ref());
 
-        let state = {
+        let (state, recovery_report) = {
             let mut helper = StorageHelper::new(&mut log_store, &mut state_machine)
                 .with_allow_io_notification_reorder(config.get_allow_io_notification_reorder())
                 .with_id(id.clone());
-            helper.get_initial_state().await?
+            helper.check_stamp(StorageStamp::new(&config.cluster_name, id.clone())).await?;
+            helper.recover().await?
         };
 
+        // Without entry sizes, the byte limits do not apply: do not let them be silently ignored.
+        if config.max_entry_size > 0 || config.max_payload_bytes > 0 {
+            let blank = C::Entry::new_blank(LogIdOf::<C>::default());
+            if log_store.get_log_reader().await.entry_size(&blank).is_none() {
+                tracing::warn!(
+                    max_entry_size = config.max_entry_size,
+                    max_payload_bytes = config.max_payload_bytes,
+                    "RaftLogReader::entry_size() is not implemented: max_entry_size and max_payload_bytes do not apply"
+                );
+            }
+        }
+
+        let (tx_membership, rx_membership) = C::watch_channel(state.membership_state.effective().clone());
+
+        let engine_recorder = EngineRecorder::new(config.engine_trace_max_inputs, || {
+            let initial_state = InitialState::new(&state, config.get_allow_io_notification_reorder());
+            EngineTrace::new(eng_config.clone(), initial_state)
+        });
+
+        let last_applied = state.io_applied().cloned();
+        let flushed_log_id = state.last_log_id().cloned();
+        let election_stats = ElectionStats::new(id.clone(), state.vote_ref().term());
+        let transition_stats = TransitionStats::new(
+            state.server_state,
+            state.log_progress().flushed().map(|io_id| io_id.to_app_vote()).unwrap_or_default(),
+            state.io_snapshot_last_log_id().cloned(),
+            state.membership_state.effective().log_id().clone(),
+        );
+        let vote_loaded_at = state.vote.last_update();
         let engine = Engine::new(state, eng_config);
 
+        let queued_client_writes = Arc::new(AtomicU64::new(0));
+
+        let metrics_history = MetricsHistory::new(config.metrics_history_size);
+
+        let network_events = Arc::new(NetworkEventBus::new());
+
+        let apply_lag_events = ApplyLagEvents::new();
+
+        let snapshot_receive = SnapshotReceiveProgress::new();
+
+        let app_index = AppIndex::new(state_machine.app_index_store());
+
+        let computed_results = ComputedResults::new();
+
+        let node_infos = NodeInfos::new(NodeInfo::local(&config));
+
         let sm_span = tracing::span!(parent: &core_span, Level::DEBUG, "sm_worker");
 
         let sm_handle = worker::Worker::spawn(
+            id.clone(),
             state_machine,
             log_store.get_log_reader().await,
+            last_applied,
             tx_notify.clone(),
+            runtime_config.clone(),
+            computed_results.clone(),
+            config.apply_results_wait(),
             sm_span,
         );
 
+        let log_checksum = LogChecksumHandle::spawn(log_store.get_log_reader().await, tx_notify.clone());
+
+        let local_entries = LocalEntriesHandle::spawn(log_store.get_log_reader().await);
+
         let core: RaftCore<C, N, LS> = RaftCore {
             id: id.clone(),
             config: config.clone(),
             runtime_config: runtime_config.clone(),
             core_state: Default::default(),
+            network_layers: NetworkLayers::new(network.network_layers()),
             network_factory: network,
+            log_reader: log_store.get_log_reader().await,
             log_store,
             sm_handle,
 
             engine,
 
             client_responders: BTreeMap::new(),
+            flushed_log_id,
+            storage_check_failure: None,
+            pending_storage_check: None,
 
             replications: Default::default(),
 
             heartbeat_handle: HeartbeatWorkersHandle::new(id.clone(), config.clone()),
             tx_api: tx_api.clone(),
             rx_api,
+            rx_api_priority,
 
             tx_notification: tx_notify,
             rx_notification: rx_notify,
+            tx_notification_priority: tx_notify_priority,
+            rx_notification_priority: rx_notify_priority,
 
             tx_metrics,
             tx_data_metrics,
             tx_server_metrics,
+            tx_membership,
             tx_progress,
 
             runtime_stats: RuntimeStats::new(),
+            write_latency: Default::default(),
+            election_stats,
+            transition_stats,
+            vote_loaded_at,
+            leader_discovered: false,
+            network_events: network_events.clone(),
+            vote_rate_limiter: VoteRateLimiter::new(config.vote_request_min_interval()),
+            read_batch: ReadBatch::new(config.read_index_batch_delay()),
+            engine_recorder: engine_recorder.clone(),
+            metrics_history: metrics_history.clone(),
+            apply_lag_alarm: ApplyLagAlarm::new(
+                config.apply_lag_alarm_threshold,
+                config.apply_lag_alarm_duration(),
+                apply_lag_events.clone(),
+            ),
+            snapshot_receive: snapshot_receive.clone(),
+            app_index: app_index.clone(),
+            entry_cache: EntryCache::new(config.replication_cache_entries),
+            snapshot_permits: SnapshotPermits::new(config.max_inflight_snapshots),
+            trace_ids: Default::default(),
+            log_checksum,
+            log_checksum_cursor: 0,
+            next_log_checksum_at: None,
+            pending_log_repair: None,
+            computed_results: computed_results.clone(),
+            pending_apply_results: Vec::new(),
+            apply_results_senders: ApplyResultsSenders::new(),
+            node_infos: node_infos.clone(),
+            election_veto: None,
+            leader_fitness: None,
+            log_archive: None,
+            local_entries,
+            next_leader_fitness_at: None,
 
             span: core_span,
         };
 
-        let core_handle = C::spawn(core.main(rx_shutdown).instrument(trace_span!("spawn").or_current()));
+        let core_handle = C::spawn_named(
+            &format!("openraft-core-{}", id),
+            core.main(rx_shutdown).instrument(trace_span!("spawn").or_current()),
+        );
 
         let inner = RaftInner {
             id,
@@ -408,10 +724,22 @@ where C: RaftTypeConfig
             runtime_config,
             tick_handle,
             tx_api,
+            tx_api_priority,
             rx_metrics,
             rx_data_metrics,
             rx_server_metrics,
+            rx_membership,
             progress_watcher,
+            network_events,
+            engine_recorder,
+            metrics_history,
+            apply_lag_events,
+            snapshot_receive,
+            recovery_report,
+            app_index,
+            computed_results,
+            node_infos,
+            queued_client_writes,
             tx_shutdown: std::sync::Mutex::new(Some(tx_shutdown)),
             core_state: std::sync::Mutex::new(CoreState::Running(core_handle)),
 
@@ -436,11 +764,221 @@ where C: RaftTypeConfig
         RuntimeConfigHandle::new(self.inner.as_ref())
     }
 
+    /// Put this node in read-only mode for maintenance.
+    ///
+    /// In read-only mode, the node rejects every client write with [`ClientWriteError::ReadOnly`]
+    /// and does not campaign: it does not start an election when its leader lease times out, nor
+    /// when it is asked to with [`Trigger::elect()`] or to take over the leadership from a
+    /// [`Trigger::transfer_leader()`]. It still accepts replication from the leader, votes for
+    /// other candidates, and serves reads. This lets an operator drain traffic from a node before
+    /// restarting it, without a membership change:
+    ///
+    /// ```ignore
+    /// raft.enter_readonly();
+    /// // Move the clients to other nodes, restart this node...
+    /// raft.exit_readonly();
+    /// ```
+    ///
+    /// If this node is the leader, it keeps the leadership, so transfer it away first with
+    /// [`Trigger::transfer_leader()`]. A client write that was already accepted before this call
+    /// is still proposed. The mode is not persisted: a restarted node is writable.
+    ///
+    /// [`ClientWriteError::ReadOnly`]: crate::error::ClientWriteError::ReadOnly
+    /// [`Trigger::elect()`]: crate::raft::trigger::Trigger::elect
+    /// [`Trigger::transfer_leader()`]: crate::raft::trigger::Trigger::transfer_leader
+    #[since(version = "0.10.0")]
+    pub fn enter_readonly(&self) {
+        tracing::info!("enter read-only mode");
+        self.inner.runtime_config.readonly.store(true, Ordering::Relaxed);
+    }
+
+    /// Leave the read-only mode entered with [`Raft::enter_readonly()`].
+    #[since(version = "0.10.0")]
+    pub fn exit_readonly(&self) {
+        tracing::info!("exit read-only mode");
+        self.inner.runtime_config.readonly.store(false, Ordering::Relaxed);
+    }
+
+    /// Return `true` if this node is in read-only mode, see [`Raft::enter_readonly()`].
+    #[since(version = "0.10.0")]
+    pub fn is_readonly(&self) -> bool {
+        self.inner.runtime_config.readonly.load(Ordering::Relaxed)
+    }
+
+    /// Set a hook that is consulted before this node starts an election, replacing the previous
+    /// one.
+    ///
+    /// The hook is called with why the election is about to start, such as `"election timeout"`,
+    /// `"triggered by application"` or `"leadership is transferred to this node"`. If it returns
+    /// `Err(reason)`, the node does not start the election and stays a follower, and the veto is
+    /// recorded in [`ElectionMetrics::vetoed`] and [`ElectionMetrics::last_veto_reason`]. It lets
+    /// the application keep an unfit node from becoming the leader, e.g., one with an unhealthy
+    /// disk, an unsynchronized clock, or one that an orchestrator is about to restart:
+    ///
+    /// ```ignore
+    /// let disk = disk_health.clone();
+    /// raft.set_election_veto(move |_reason| {
+    ///     if disk.is_healthy() { Ok(()) } else { Err("disk is unhealthy".to_string()) }
+    /// }).await?;
+    /// ```
+    ///
+    /// The hook runs in `RaftCore` and must return at once: check a status that is maintained
+    /// elsewhere instead of doing IO in it. It does not affect the votes this node grants to other
+    /// candidates. The hook is not persisted: a restarted node has none.
+    ///
+    /// [`ElectionMetrics::vetoed`]: crate::metrics::ElectionMetrics::vetoed
+    /// [`ElectionMetrics::last_veto_reason`]: crate::metrics::ElectionMetrics::last_veto_reason
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn set_election_veto<F>(&self, veto: F) -> Result<(), Fatal<C>>
+    where F: Fn(&str) -> Result<(), String> + OptionalSend + 'static {
+        let (tx, rx) = C::oneshot();
+        self.inner
+            .call_core(
+                RaftMsg::SetElectionVeto {
+                    veto: Some(Box::new(veto)),
+                    tx,
+                },
+                rx,
+            )
+            .await
+    }
+
+    /// Remove the hook set with [`Raft::set_election_veto()`], so that elections are no longer
+    /// vetoed.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn remove_election_veto(&self) -> Result<(), Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::SetElectionVeto { veto: None, tx }, rx).await
+    }
+
+    /// Set the function that scores the fitness of a node to be the leader, replacing the
+    /// previous one.
+    ///
+    /// The function is called with the id and the node metadata of this node and of every voter,
+    /// and returns a score: the greater the fitter. When this node is the leader, it evaluates the
+    /// scores every [`Config::leader_fitness_interval`] milliseconds, and transfers its leadership
+    /// to the best scored voter that has replicated every log, if it scores at least
+    /// [`Config::leader_fitness_margin`] more than this node. It keeps the leadership on healthy
+    /// hardware:
+    ///
+    /// ```ignore
+    /// let health = cluster_health.clone();
+    /// raft.set_leader_fitness(move |id, _node| {
+    ///     let h = health.get(id);
+    ///     100 - h.cpu_load_percent - h.disk_latency_penalty
+    /// }).await?;
+    /// ```
+    ///
+    /// The function runs in `RaftCore` and must return at once: score from a status that is
+    /// maintained elsewhere instead of doing IO in it. It is not persisted: a restarted node has
+    /// none.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn set_leader_fitness<F>(&self, fitness: F) -> Result<(), Fatal<C>>
+    where F: Fn(&C::NodeId, &C::Node) -> u64 + OptionalSend + 'static {
+        let (tx, rx) = C::oneshot();
+        let fitness = Some(Box::new(fitness) as _);
+        self.inner.call_core(RaftMsg::SetLeaderFitness { fitness, tx }, rx).await
+    }
+
+    /// Remove the function set with [`Raft::set_leader_fitness()`], so that the leadership is no
+    /// longer handed off by fitness.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn remove_leader_fitness(&self) -> Result<(), Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::SetLeaderFitness { fitness: None, tx }, rx).await
+    }
+
+    /// Set the hook that archives logs before they are purged, replacing the previous one.
+    ///
+    /// Before purging logs, this node passes the entries about to be purged to
+    /// [`LogArchiver::archive()`], and purges them only after they are archived, so that an
+    /// application can retain the full history in external storage, e.g., for auditing or
+    /// point-in-time recovery. See [`LogArchiver`] for how a failure is handled.
+    ///
+    /// The hook is not persisted: set it right after creating the `Raft`, before logs are purged.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn set_log_archiver<A>(&self, archiver: A) -> Result<(), Fatal<C>>
+    where A: LogArchiver<C> {
+        let (tx, rx) = C::oneshot();
+        let archiver = Some(Box::new(archiver) as _);
+        self.inner.call_core(RaftMsg::SetLogArchiver { archiver, tx }, rx).await
+    }
+
+    /// Remove the hook set with [`Raft::set_log_archiver()`], so that logs are purged without
+    /// being archived.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn remove_log_archiver(&self) -> Result<(), Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::SetLogArchiver { archiver: None, tx }, rx).await
+    }
+
+    /// Emit a tick, which lets this node check its election timeout and send a heartbeat if it is
+    /// due.
+    ///
+    /// With [`Config::manual_tick`] enabled, no internal timer runs and this is the only source
+    /// of ticks: the application calls it about every [`Config::heartbeat_interval()`].
+    /// Otherwise, it emits an extra tick in addition to the internal timer.
+    ///
+    /// A tick is ignored if ticking is disabled by [`Config::enable_tick`] or
+    /// [`RuntimeConfigHandle::tick`].
+    ///
+    /// Example:
+    /// ```ignore
+    /// loop {
+    ///     raft.tick().await?;
+    ///     sleep(config.heartbeat_interval()).await;
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn tick(&self) -> Result<(), Fatal<C>> {
+        if self.inner.tick_handle.tick().await.is_err() {
+            return Err(self.inner.get_core_stop_error().await);
+        }
+        Ok(())
+    }
+
     /// Return the config of this Raft node.
     pub fn config(&self) -> &Arc<Config> {
         &self.inner.config
     }
 
+    /// Return the inputs fed to the Raft engine of this node so far, or `None` if
+    /// [`Config::engine_trace_max_inputs`] is 0 or feature `serde` is not enabled.
+    ///
+    /// The trace is still available after Raft stopped on a [`Fatal`] error, so that it can be
+    /// attached to a bug report and replayed with [`EngineTrace::replay()`]. See [`crate::trace`].
+    #[since(version = "0.10.0")]
+    pub fn engine_trace(&self) -> Option<EngineTrace<C>> {
+        self.inner.engine_recorder.trace()
+    }
+
+    /// Return the last samples of [`RaftMetrics`] from the oldest to the newest, or `None` if
+    /// [`Config::metrics_history_size`] is 0.
+    ///
+    /// The history is still available after Raft stopped on a [`Fatal`] error, so that it can be
+    /// dumped for a post-mortem.
+    #[since(version = "0.10.0")]
+    pub fn metrics_history(&self) -> Option<Vec<MetricsSample<C>>> {
+        self.inner.metrics_history.samples()
+    }
+
+    /// Return what this node recovered from its storage when it started.
+    ///
+    /// An application may log it as the starting state of the node, and shut the node down if
+    /// the recovered state is not what it expects, e.g., an empty log on a node that is expected
+    /// to have data.
+    #[since(version = "0.10.0")]
+    pub fn recovery_report(&self) -> &RecoveryReport<C> {
+        &self.inner.recovery_report
+    }
+
     /// Create a new [`ProtocolApi`] to handle Raft protocal RPCs received by this Raft node.
     ///
     /// [`ProtocolApi`] provides the following protocol APIs:
@@ -474,12 +1012,32 @@ where C: RaftTypeConfig
         Trigger::new(self.inner.as_ref())
     }
 
+    /// Return a [`ReadOnlyRaft`] handle that exposes only the metrics and read APIs of this node.
+    ///
+    /// It is meant to be handed to subsystems that only observe the node, such as an HTTP admin
+    /// UI or a metrics exporter, so that they cannot write to the cluster or change the
+    /// membership by mistake.
+    #[since(version = "0.10.0")]
+    pub fn read_only_handle(&self) -> ReadOnlyRaft<C> {
+        ReadOnlyRaft::new(self.clone())
+    }
+
     /// Submit an AppendEntries RPC to this Raft node.
     ///
     /// These RPCs are sent by the cluster leader to replicate log entries (§5.3), and are also
     /// used as heartbeats (§5.2).
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn append_entries(&self, rpc: AppendEntriesRequest<C>) -> Result<AppendEntriesResponse<C>, RaftError<C>> {
+        self.check_cluster_name(rpc.cluster_name())?;
+
+        if let Some((policy, sender, my_vote)) = self.non_member_sender(&rpc.vote) {
+            // A read-only non-member can only keep replicating as the leader this node follows.
+            if !(policy == NonMemberRpcPolicy::AcceptReadOnly && rpc.vote == my_vote) {
+                tracing::warn!(req = display(&rpc), "reject append-entries from non-member");
+                return Err(Fatal::from(NonMemberRejected::new(sender)).into());
+            }
+        }
+
         self.protocol_api().append_entries(rpc).await.into_raft_result()
     }
 
@@ -489,9 +1047,67 @@ where C: RaftTypeConfig
     /// (§5.2).
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn vote(&self, rpc: VoteRequest<C>) -> Result<VoteResponse<C>, RaftError<C>> {
+        self.check_cluster_name(rpc.cluster_name())?;
+
+        if let Some((policy, sender, my_vote)) = self.non_member_sender(&rpc.vote) {
+            tracing::warn!(
+                req = display(&rpc),
+                policy = debug(policy),
+                "vote request from non-member"
+            );
+            if policy == NonMemberRpcPolicy::AcceptReadOnly {
+                return Ok(VoteResponse::new(my_vote, None, false));
+            }
+            return Err(Fatal::from(NonMemberRejected::new(sender)).into());
+        }
+
         self.protocol_api().vote(rpc).await.into_raft_result()
     }
 
+    /// Returns the policy, the sender and the current vote of this node, if an RPC sent with
+    /// `vote` is from a non-member and [`Config::accept_rpc_from_non_members`] does not accept it
+    /// unconditionally.
+    ///
+    /// The policy does not apply if this node is not a member itself, e.g., it is being added.
+    fn non_member_sender(&self, vote: &VoteOf<C>) -> Option<(NonMemberRpcPolicy, C::NodeId, VoteOf<C>)> {
+        let policy = self.inner.config.accept_rpc_from_non_members;
+        if policy == NonMemberRpcPolicy::Accept {
+            return None;
+        }
+
+        let sender = vote.to_leader_node_id()?;
+
+        let membership = self.inner.rx_membership.borrow_watched().clone();
+        if !membership.contains(self.inner.id()) || membership.contains(&sender) {
+            return None;
+        }
+
+        let my_vote = self.metrics().borrow_watched().vote.clone();
+        Some((policy, sender, my_vote))
+    }
+
+    /// Check the cluster name carried by an RPC against [`Config::cluster_name`] of this node.
+    ///
+    /// It returns [`Fatal::ClusterMismatch`] if they differ. A request without a cluster name,
+    /// e.g., from a version that does not send it, is accepted. The RPC handlers of `Raft` call
+    /// it for every request. An application that sends the snapshot with its own transport
+    /// calls it before [`Self::install_full_snapshot`], with the cluster name sent along, see
+    /// [`RPCOption::cluster_name`](crate::network::RPCOption::cluster_name).
+    #[since(version = "0.10.0")]
+    pub fn check_cluster_name(&self, cluster_name: Option<&str>) -> Result<(), Fatal<C>> {
+        let expected = self.inner.config.cluster_name.as_str();
+        let Some(got) = cluster_name else {
+            return Ok(());
+        };
+
+        if expected != got {
+            tracing::warn!(expected, got, "reject RPC from another cluster");
+            return Err(ClusterMismatch::new(expected, got).into());
+        }
+
+        Ok(())
+    }
+
     /// Get the latest snapshot from the state machine.
     ///
     /// It returns error only when `RaftCore` fails to serve the request, e.g., Encountering a
@@ -520,7 +1136,24 @@ where C: RaftTypeConfig
         vote: VoteOf<C>,
         snapshot: Snapshot<C>,
     ) -> Result<SnapshotResponse<C>, Fatal<C>> {
-        self.protocol_api().install_full_snapshot(vote, snapshot).await
+        let snapshot_id = snapshot.meta.snapshot_id.clone();
+
+        self.inner.snapshot_receive.installing(&snapshot_id);
+        let res = self.protocol_api().install_full_snapshot(vote, snapshot).await;
+        self.inner.snapshot_receive.finish(&snapshot_id);
+
+        res
+    }
+
+    /// Report the progress of a snapshot being received by an application defined transport.
+    ///
+    /// `received` is the number of bytes received so far, and `total` the size of the snapshot,
+    /// if known. The progress is shown in [`RaftMetrics::snapshot_receiving`] until the snapshot
+    /// is installed with [`Self::install_full_snapshot()`]. The chunked transport used by
+    /// [`Self::install_snapshot()`] reports its progress by itself.
+    #[since(version = "0.10.0")]
+    pub fn report_snapshot_receiving(&self, snapshot_id: &SnapshotId, received: u64, total: Option<u64>) {
+        self.inner.snapshot_receive.receiving(snapshot_id, received, total);
     }
 
     /// Receive an `InstallSnapshotRequest`.
@@ -544,6 +1177,8 @@ where C: RaftTypeConfig
 
         tracing::debug!(req = display(&req), "Raft::install_snapshot()");
 
+        self.check_cluster_name(req.cluster_name())?;
+
         let req_vote = req.vote.clone();
         let my_vote = self.with_raft_state(|state| state.vote_ref().clone()).await?;
         let resp = InstallSnapshotResponse { vote: my_vote.clone() };
@@ -560,6 +1195,9 @@ where C: RaftTypeConfig
             }
         }
 
+        let snapshot_id = req.meta.snapshot_id.clone();
+        let received = req.offset + req.data.len() as u64;
+
         let finished_snapshot = {
             use crate::network::snapshot_transport::Chunked;
             use crate::network::snapshot_transport::SnapshotTransport;
@@ -568,6 +1206,8 @@ where C: RaftTypeConfig
             Chunked::receive_snapshot(&mut *streaming, self, req).await?
         };
 
+        self.inner.snapshot_receive.receiving(&snapshot_id, received, None);
+
         if let Some(snapshot) = finished_snapshot {
             let resp = self.install_full_snapshot(req_vote, snapshot).await?;
             return Ok(resp.into());
@@ -585,6 +1225,29 @@ where C: RaftTypeConfig
         self.metrics().borrow_watched().current_leader.clone()
     }
 
+    /// Explain the leadership this node believes in, and why it does not start an election.
+    ///
+    /// The returned [`LeadershipInfo`] is built by `RaftCore` in a single step: the vote of this
+    /// node, whether it is committed, the leader it believes in, how long ago it last heard from
+    /// it, and a [`NoElectionReason`]. It is meant to answer "why is there no leader", e.g., by
+    /// collecting it from every node:
+    ///
+    /// ```ignore
+    /// let info = raft.leadership_info().await?;
+    /// if info.current_leader.is_none() {
+    ///     println!("no leader: {:?}", info.no_election_reason);
+    /// }
+    /// ```
+    ///
+    /// The election veto hook set with [`Raft::set_election_veto()`] is not consulted: an election
+    /// is reported as vetoed if the last attempt was vetoed.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn leadership_info(&self) -> Result<LeadershipInfo<C>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.inner.call_core(RaftMsg::LeadershipInfo { tx }, rx).await
+    }
+
     /// Ensures reads performed after this method are linearizable across the cluster
     /// using an explicitly provided policy. This method is just a shorthand for calling
     /// [`get_read_log_id()`](Raft::get_read_log_id) and then calling [Raft::wait].
@@ -625,7 +1288,7 @@ where C: RaftTypeConfig
         &self,
         read_policy: ReadPolicy,
     ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
-        let linearizer = self.app_api().get_read_linearizer(read_policy).await.into_raft_result()?;
+        let linearizer = self.app_api().get_read_linearizer(read_policy, None).await.into_raft_result()?;
 
         // Safe unwrap: it never times out.
         let state = linearizer.await_ready(self).await?;
@@ -645,7 +1308,7 @@ where C: RaftTypeConfig
         &self,
         read_policy: ReadPolicy,
     ) -> Result<(Option<LogIdOf<C>>, Option<LogIdOf<C>>), RaftError<C, CheckIsLeaderError<C>>> {
-        let linearizer = self.app_api().get_read_linearizer(read_policy).await.into_raft_result()?;
+        let linearizer = self.app_api().get_read_linearizer(read_policy, None).await.into_raft_result()?;
 
         let read_log_id = linearizer.read_log_id();
         let applied = linearizer.applied();
@@ -703,7 +1366,102 @@ where C: RaftTypeConfig
         &self,
         read_policy: ReadPolicy,
     ) -> Result<Linearizer<C>, RaftError<C, CheckIsLeaderError<C>>> {
-        self.app_api().get_read_linearizer(read_policy).await.into_raft_result()
+        self.app_api().get_read_linearizer(read_policy, None).await.into_raft_result()
+    }
+
+    /// Prepare a read of the state machine with the given guarantee, and return the log id the
+    /// read is serialized after.
+    ///
+    /// This is the single entry point for reads; it chooses the read path by [`ReadGuarantee`]:
+    /// - [`ReadIndex`](ReadGuarantee::ReadIndex) and [`LeaderLease`](ReadGuarantee::LeaderLease)
+    ///   are served by [`ensure_linearizable()`](Self::ensure_linearizable): it fails unless this
+    ///   node is the leader, and returns once the state machine has applied up to the read log id.
+    /// - [`Stale`](ReadGuarantee::Stale) succeeds on any node at once, and returns the last log id
+    ///   applied to the local state machine, or `None` if nothing is applied yet. On a node that
+    ///   has not discovered the leader since it started, it is served according to
+    ///   [`Config::cold_start_reads`].
+    ///
+    /// A read of the state machine performed after this method returns observes at least all the
+    /// entries up to the returned log id.
+    ///
+    /// # Examples
+    /// ```ignore
+    /// let log_id = my_raft.read(ReadGuarantee::ReadIndex).await?;
+    /// let val = my_raft.with_state_machine(|sm| { sm.read("foo") }).await?;
+    /// ```
+    ///
+    /// See: [Read Operation](crate::docs::protocol::read)
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn read(
+        &self,
+        guarantee: ReadGuarantee,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.do_read(guarantee, None).await
+    }
+
+    /// Same as [`Self::read`], with a trace id to follow the read across nodes in the logs.
+    ///
+    /// The trace id is logged by this node, and is passed to [`RaftNetworkV2::append_entries`]
+    /// with [`RPCOption::trace_ids`] of the heartbeats that confirm the leadership for the read.
+    ///
+    /// [`RaftNetworkV2::append_entries`]: crate::network::v2::RaftNetworkV2::append_entries
+    /// [`RPCOption::trace_ids`]: crate::network::RPCOption::trace_ids
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all, fields(trace_id))]
+    pub async fn read_with_trace_id(
+        &self,
+        guarantee: ReadGuarantee,
+        trace_id: impl ToString,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        let trace_id = trace_id.to_string();
+        tracing::Span::current().record("trace_id", trace_id.as_str());
+
+        self.do_read(guarantee, Some(trace_id)).await
+    }
+
+    async fn do_read(
+        &self,
+        guarantee: ReadGuarantee,
+        trace_id: Option<String>,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        if let Some(read_policy) = guarantee.read_policy() {
+            let linearizer = self.app_api().get_read_linearizer(read_policy, trace_id).await.into_raft_result()?;
+
+            let state = linearizer.await_ready(self).await?;
+            return Ok(Some(state.read_log_id().clone()));
+        }
+
+        if !self.inner.is_core_running() {
+            let fatal = self.inner.get_core_stop_error().await;
+            return Err(RaftError::Fatal(fatal));
+        }
+
+        if !self.inner.rx_metrics.borrow_watched().leader_discovered {
+            self.cold_start_read().await?;
+        }
+
+        let applied = self.inner.rx_metrics.borrow_watched().last_applied.clone();
+        Ok(applied)
+    }
+
+    /// Apply [`Config::cold_start_reads`] to a stale read on a node that has not discovered the
+    /// leader since it started.
+    async fn cold_start_read(&self) -> Result<(), RaftError<C, CheckIsLeaderError<C>>> {
+        let not_discovered = || RaftError::APIError(CheckIsLeaderError::ForwardToLeader(ForwardToLeader::empty()));
+
+        let timeout = match self.inner.config.cold_start_reads {
+            ColdStartReadPolicy::ServeStale => return Ok(()),
+            ColdStartReadPolicy::Refuse => return Err(not_discovered()),
+            ColdStartReadPolicy::WaitForLeader(ms) => Duration::from_millis(ms),
+        };
+
+        let res = self.wait(Some(timeout)).metrics(|m| m.leader_discovered, "discover the leader").await;
+        match res {
+            Ok(_) => Ok(()),
+            Err(WaitError::Timeout(_, _)) => Err(not_discovered()),
+            Err(WaitError::ShuttingDown) => Err(RaftError::Fatal(self.inner.get_core_stop_error().await)),
+        }
     }
 
     /// Submit a mutating client request to Raft to update the state of the system (§5.1).
@@ -725,6 +1483,9 @@ where C: RaftTypeConfig
     /// These are application specific requirements, and must be implemented by the application
     /// which is being built on top of Raft.
     ///
+    /// If [`Config::max_queued_client_writes`] writes are already queued on this node, the write
+    /// is rejected at once with [`ClientWriteError::Overloaded`], which suggests when to retry.
+    ///
     /// # Examples
     ///
     /// ```ignore
@@ -738,7 +1499,37 @@ where C: RaftTypeConfig
         &self,
         app_data: C::D,
     ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
-        self.app_api().client_write(app_data).await.into_raft_result()
+        self.app_api().client_write(app_data, None).await.into_raft_result()
+    }
+
+    /// Same as [`Self::client_write`], with a trace id to follow the write across nodes in the
+    /// logs.
+    ///
+    /// The trace id is attached to the log entry the write is appended to. The leader passes it
+    /// to [`RaftNetworkV2::append_entries`] with [`RPCOption::trace_ids`] and sends it along
+    /// with the entry, and every node records it in the tracing span of appending the entry to
+    /// its log store.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let request_id = "req-1a2b3c";
+    /// let response = raft.client_write_with_trace_id(request, request_id).await?;
+    /// ```
+    ///
+    /// [`RaftNetworkV2::append_entries`]: crate::network::v2::RaftNetworkV2::append_entries
+    /// [`RPCOption::trace_ids`]: crate::network::RPCOption::trace_ids
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all, fields(trace_id))]
+    pub async fn client_write_with_trace_id(
+        &self,
+        app_data: C::D,
+        trace_id: impl ToString,
+    ) -> Result<ClientWriteResponse<C>, RaftError<C, ClientWriteError<C>>> {
+        let trace_id = trace_id.to_string();
+        tracing::Span::current().record("trace_id", trace_id.as_str());
+
+        self.app_api().client_write(app_data, Some(trace_id)).await.into_raft_result()
     }
 
     /// Submit a mutating client request to Raft to update the state machine, returns an application
@@ -773,9 +1564,145 @@ where C: RaftTypeConfig
     #[since(version = "0.10.0")]
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn handle_transfer_leader(&self, req: TransferLeaderRequest<C>) -> Result<(), Fatal<C>> {
+        self.check_cluster_name(req.cluster_name())?;
         self.protocol_api().handle_transfer_leader(req).await
     }
 
+    /// Handle the decommission request sent by the Leader with
+    /// [`RaftNetworkV2::decommission`] after this node is removed from the cluster.
+    ///
+    /// This node shuts down if the request is addressed to it, and is sent by a Leader of the same
+    /// cluster whose vote is not smaller than the vote of this node. Otherwise the request is
+    /// rejected with [`DecommissionRejected`], which should be sent back to the Leader, so that it
+    /// does not report the node as shut down.
+    ///
+    /// [`RaftNetworkV2::decommission`]: crate::network::v2::RaftNetworkV2::decommission
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_decommission(
+        &self,
+        req: DecommissionRequest<C>,
+    ) -> Result<(), RaftError<C, DecommissionRejected<C>>> {
+        let cluster_mismatch = self.check_cluster_name(req.cluster_name()).is_err();
+
+        let metrics = self.metrics().borrow_watched().clone();
+
+        #[allow(clippy::neg_cmp_op_on_partial_ord)]
+        if cluster_mismatch
+            || req.node_id() != self.inner.id()
+            || !(req.from_leader().as_ref_vote() >= metrics.vote.as_ref_vote())
+        {
+            tracing::warn!(
+                "reject decommission request: {}; id: {}, vote: {}",
+                req,
+                self.inner.id(),
+                metrics.vote
+            );
+            return Err(RaftError::APIError(DecommissionRejected {
+                node_id: self.inner.id().clone(),
+                vote: metrics.vote,
+                target: req.node_id().clone(),
+                from_leader: req.from_leader().clone(),
+            }));
+        }
+
+        tracing::info!("decommissioned by leader: {}, shutting down", req.from_leader());
+
+        if let Err(e) = self.shutdown().await {
+            tracing::error!("error shutting down after decommission: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Handle the state machine checksum request sent by the Leader with
+    /// [`RaftNetworkV2::state_machine_checksum`] for [`Raft::state_machine_checksums`].
+    ///
+    /// It returns the checksum of the state machine computed right after the requested log id is
+    /// applied, see [`RaftStateMachine::checksum`]. If the log id is not applied yet, it waits
+    /// until it is. It returns `None` if a later log entry has already been applied, or the state
+    /// machine does not support checksums.
+    ///
+    /// [`RaftNetworkV2::state_machine_checksum`]: crate::network::v2::RaftNetworkV2::state_machine_checksum
+    /// [`RaftStateMachine::checksum`]: crate::storage::RaftStateMachine::checksum
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_state_machine_checksum(
+        &self,
+        req: StateMachineChecksumRequest<C>,
+    ) -> Result<Option<u64>, Fatal<C>> {
+        self.check_cluster_name(req.cluster_name())?;
+        self.protocol_api().handle_state_machine_checksum(req).await
+    }
+
+    /// Handle the log repair request sent by a follower with [`RaftNetworkV2::repair_log`], when
+    /// [`Config::repair_corrupted_log`] is enabled.
+    ///
+    /// It returns the requested log entries read from the local log store. It returns an empty
+    /// `Vec` if this node does not have all of them, e.g., they are purged, so that the follower
+    /// retries later, possibly with another leader.
+    ///
+    /// [`RaftNetworkV2::repair_log`]: crate::network::v2::RaftNetworkV2::repair_log
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_repair_log(&self, req: LogRepairRequest<C>) -> Result<Vec<C::Entry>, Fatal<C>> {
+        self.check_cluster_name(req.cluster_name())?;
+        self.protocol_api().handle_repair_log(req).await
+    }
+
+    /// Handle the apply results sent by the Leader with [`RaftNetworkV2::apply_results`], when
+    /// [`Config::apply_results_wait`] is enabled.
+    ///
+    /// The results are kept until the entries are applied, with
+    /// [`RaftStateMachine::apply_computed`] if the results of all entries of a batch arrive in
+    /// time. Results of entries that are already applied are compared with the ones computed
+    /// locally.
+    ///
+    ///
+    /// The results are accepted only if they are sent by the current Leader: the vote of the
+    /// request has to be the committed vote of this node. Results from another node are ignored.
+    ///
+    /// [`RaftNetworkV2::apply_results`]: crate::network::v2::RaftNetworkV2::apply_results
+    /// [`RaftStateMachine::apply_computed`]: crate::storage::RaftStateMachine::apply_computed
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_apply_results(&self, req: ApplyResultsRequest<C>) -> Result<(), Fatal<C>> {
+        self.check_cluster_name(req.cluster_name())?;
+
+        let computed_results = self.inner.computed_results.clone();
+
+        // Check the vote in RaftCore, so that the results are not accepted after the vote changes.
+        self.with_raft_state(move |st| {
+            let vote = st.vote_ref();
+            if vote.is_committed() && vote == &req.from_leader {
+                computed_results.insert(req.results);
+            } else {
+                tracing::info!(
+                    from_leader = display(&req.from_leader),
+                    vote = display(vote),
+                    "ignore apply results not sent by the current leader"
+                );
+            }
+        })
+        .await
+    }
+
+    /// Handle a hello request sent with [`RaftNetworkV2::hello`], and reply with the
+    /// [`NodeInfo`] of this node.
+    ///
+    /// The info of the sender is kept and reported in [`RaftMetrics::node_infos`].
+    ///
+    /// [`RaftNetworkV2::hello`]: crate::network::v2::RaftNetworkV2::hello
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn handle_hello(&self, req: HelloRequest<C>) -> Result<NodeInfo, Fatal<C>> {
+        tracing::debug!(req = display(&req), "recv hello");
+
+        self.check_cluster_name(req.cluster_name())?;
+        self.inner.node_infos.insert(req.from, req.info);
+        Ok(self.inner.node_infos.local().clone())
+    }
+
     /// Return `true` if this node is already initialized and cannot be initialized again with
     /// [`Raft::initialize`]
     #[since(version = "0.10.0")]
@@ -833,6 +1760,60 @@ where C: RaftTypeConfig
         self.management_api().initialize(members).await.into_raft_result()
     }
 
+    /// Export the current snapshot as a bundle to seed a brand new cluster of `members`, e.g., to
+    /// fork the data of this cluster into a cluster with other node ids.
+    ///
+    /// The returned snapshot has the state machine data of the current snapshot of this node,
+    /// with `members` as the membership, and starts at the very first log id, the same as a
+    /// cluster created with [`Raft::initialize()`]. A node of the new cluster initializes from it
+    /// with [`Raft::initialize_from_snapshot()`]:
+    ///
+    /// ```ignore
+    /// let bundle = old_raft.export_bootstrap(btreemap! {10 => node10, 11 => node11}).await?.unwrap();
+    /// // Ship `bundle.meta` and `bundle.snapshot` to node-10 of the new cluster, then:
+    /// new_raft.initialize_from_snapshot(bundle).await?;
+    /// ```
+    ///
+    /// It returns `None` if no snapshot is built yet; build one with [`Trigger::snapshot()`]
+    /// first. The entries after the snapshot are not exported. This cluster is not affected.
+    ///
+    /// [`Trigger::snapshot()`]: crate::raft::trigger::Trigger::snapshot
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn export_bootstrap<T>(&self, members: T) -> Result<Option<Snapshot<C>>, RaftError<C>>
+    where T: IntoNodes<C::NodeId, C::Node> + Debug {
+        let Some(mut snapshot) = self.get_snapshot().await? else {
+            return Ok(None);
+        };
+
+        let membership = Membership::from(members.into_nodes());
+        let mut meta = snapshot.meta.into_bootstrap(membership);
+        meta.snapshot_id = format!("bootstrap-{}", meta.snapshot_id);
+        snapshot.meta = meta;
+
+        Ok(Some(snapshot))
+    }
+
+    /// Initialize a brand new cluster from a snapshot, such as one exported with
+    /// [`Raft::export_bootstrap()`], instead of from an empty state.
+    ///
+    /// It works like [`Raft::initialize()`]: it is called on one uninitialized node, which must be
+    /// a voter in the membership of the snapshot. The node installs the snapshot to its state
+    /// machine, with the membership of the snapshot as the initial membership, and starts an
+    /// election. The other members receive the snapshot from the leader by replication.
+    ///
+    /// The last log id of the snapshot is reset to the very first log id, so that the new cluster
+    /// starts its terms and log indexes over. It returns once the snapshot is installed, or an
+    /// [`InitializeError`] if this node is already initialized or is not in the membership.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn initialize_from_snapshot(
+        &self,
+        snapshot: Snapshot<C>,
+    ) -> Result<(), RaftError<C, InitializeError<C>>> {
+        self.management_api().initialize_from_snapshot(snapshot).await.into_raft_result()
+    }
+
     /// Provides read-only access to [`RaftState`] through a user-provided function.
     ///
     /// The function `func` is applied to the current [`RaftState`]. The result of this function,
@@ -991,6 +1972,17 @@ where C: RaftTypeConfig
         self.inner.send_msg(raft_msg).await
     }
 
+    /// Return an estimate of the current write capacity of this node, or `None` if it is not the
+    /// leader.
+    ///
+    /// Applications can use it for admission control before proposing, e.g., hold back new writes
+    /// while [`CapacityHint::available()`] is `0`. It is read from the latest
+    /// [`RaftMetrics::capacity`] and does not wait for `RaftCore`.
+    #[since(version = "0.10.0")]
+    pub fn capacity_hint(&self) -> Option<CapacityHint> {
+        self.inner.rx_metrics.borrow_watched().capacity.clone()
+    }
+
     /// Get a handle to the metrics channel.
     ///
     /// # Examples
@@ -1015,6 +2007,77 @@ where C: RaftTypeConfig
         self.inner.rx_server_metrics.clone()
     }
 
+    /// Get the membership config currently in effect on this node.
+    ///
+    /// It is the last membership config seen in the log, which may not be committed yet. It is
+    /// cheap to call, without communicating with `RaftCore`, and convenient for routing and
+    /// authorization:
+    ///
+    /// ```ignore
+    /// let membership = raft.effective_membership();
+    /// if membership.is_voter(&node_id) {
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// To watch for membership changes, use [`server_metrics()`](Self::server_metrics).
+    #[since(version = "0.10.0")]
+    pub fn effective_membership(&self) -> Arc<EffectiveMembership<C>> {
+        self.inner.rx_membership.borrow_watched().clone()
+    }
+
+    /// Look up the log id an application key is applied at.
+    ///
+    /// The keys are published by the state machine with
+    /// [`RaftStateMachine::take_app_index_keys`], such as transaction ids. A returned log id is
+    /// committed and applied on this node, so it answers "is transaction X committed, and at what
+    /// index" without going through `RaftCore` or reading the state machine. `None` is returned if
+    /// the key is not applied on this node yet, or not kept by the
+    /// [`AppIndexStore`](crate::storage::AppIndexStore).
+    #[since(version = "0.10.0")]
+    pub fn lookup_app_key(&self, key: &str) -> Option<LogIdOf<C>> {
+        self.inner.app_index.get(key)
+    }
+
+    /// Append a node-local entry to the log store and return its sequence number.
+    ///
+    /// A local entry is for bookkeeping that belongs to this node only, e.g., a local index, so
+    /// that an application does not need a second storage engine for it. It is written with
+    /// [`RaftLogReader::append_local`] in a task outside `RaftCore`, so that it never delays the
+    /// Raft IO of this node. It is not replicated, it does not take a log index and it never
+    /// affects consensus.
+    ///
+    /// Local entry requests are handled one at a time, in the order they are received.
+    ///
+    /// A storage error is returned as `RaftError::APIError`, and does not stop the node.
+    ///
+    /// [`RaftLogReader::append_local`]: crate::storage::RaftLogReader::append_local
+    #[since(version = "0.10.0")]
+    pub async fn append_local_entry(&self, data: Vec<u8>) -> Result<u64, RaftError<C, StorageError<C>>> {
+        self.app_api().append_local_entry(data).await.into_raft_result()
+    }
+
+    /// Read at most `max` node-local entries with a sequence number no less than `start`, as
+    /// `(seq, data)` in sequence order.
+    ///
+    /// See [`Self::append_local_entry`].
+    #[since(version = "0.10.0")]
+    pub async fn read_local_entries(
+        &self,
+        start: u64,
+        max: u64,
+    ) -> Result<Vec<(u64, Vec<u8>)>, RaftError<C, StorageError<C>>> {
+        self.app_api().read_local_entries(start, max).await.into_raft_result()
+    }
+
+    /// Remove the node-local entries with a sequence number up to `upto`, inclusive.
+    ///
+    /// See [`Self::append_local_entry`].
+    #[since(version = "0.10.0")]
+    pub async fn purge_local_entries(&self, upto: u64) -> Result<(), RaftError<C, StorageError<C>>> {
+        self.app_api().purge_local_entries(upto).await.into_raft_result()
+    }
+
     /// Get a handle to watch log I/O flush progress.
     ///
     /// Tracks when log entries and votes are durably written to storage.
@@ -1058,6 +2121,149 @@ where C: RaftTypeConfig
         self.inner.progress_watcher.vote_progress()
     }
 
+    /// Subscribe to connection lifecycle events of the replication streams.
+    ///
+    /// When this node is the leader, every replication stream emits a [`NetworkEvent`] when the
+    /// target becomes connected or disconnected, when it starts to back off from an unreachable
+    /// target, or when an RPC times out.
+    /// The returned receiver gets all events emitted after this call. Dropping it unsubscribes.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut events = raft.network_events();
+    ///
+    /// while let Some(ev) = events.recv().await {
+    ///     if let NetworkEvent::Disconnected { target, .. } = ev {
+    ///         println!("node {} unreachable since {}", target, chrono::Local::now());
+    ///     }
+    /// }
+    /// ```
+    #[since(version = "0.10.0")]
+    pub fn network_events(&self) -> MpscUnboundedReceiverOf<C, NetworkEvent<C>> {
+        self.inner.network_events.subscribe()
+    }
+
+    /// Subscribe to the alarm about the state machine lagging behind the committed logs.
+    ///
+    /// An [`ApplyLagEvent::Raised`] is emitted when `committed - applied` stays above
+    /// [`Config::apply_lag_alarm_threshold`] for [`Config::apply_lag_alarm_duration`], and an
+    /// [`ApplyLagEvent::Cleared`] when it drops back. The lag is checked on every tick, thus no
+    /// event is emitted if [`Config::enable_tick`] is `false`.
+    /// The returned receiver gets all events emitted after this call. Dropping it unsubscribes.
+    #[since(version = "0.10.0")]
+    pub fn apply_lag_events(&self) -> MpscUnboundedReceiverOf<C, ApplyLagEvent<C>> {
+        self.inner.apply_lag_events.subscribe()
+    }
+
+    /// Probe every member of the cluster and return a consolidated health report.
+    ///
+    /// It must be called on the leader. The leader sends an empty `AppendEntries` to every voter
+    /// and learner, and waits at most `timeout` for each of them to respond. The returned
+    /// [`ClusterHealth`] tells, for every member, whether it responded, how far its log is behind
+    /// the leader, its term, and the last RPC error the leader got from it.
+    ///
+    /// It is meant to back the readiness probe of an orchestrator, for example:
+    ///
+    /// ```ignore
+    /// let health = raft.cluster_health(Duration::from_millis(500)).await?;
+    /// let ready = health.voters_healthy(100);
+    /// ```
+    ///
+    /// Returns `Err(RaftError<CheckIsLeaderError>)` with a [`ForwardToLeader`] error if this node
+    /// is not the leader.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn cluster_health(
+        &self,
+        timeout: Duration,
+    ) -> Result<ClusterHealth<C>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.management_api().cluster_health(timeout).await.into_raft_result()
+    }
+
+    /// Collect the checksum of the state machine of every member at the same log id, to audit the
+    /// consistency of the replicas.
+    ///
+    /// It must be called on the leader. The leader proposes a blank log entry, and asks every
+    /// voter and learner for the checksum of its state machine right after applying it, see
+    /// [`RaftStateMachine::checksum`]. No member has applied the blank entry when it is asked,
+    /// thus every one of them computes the checksum on the same state. It waits at most `timeout`
+    /// for each of them to respond, so that a member that is far behind does not block the audit.
+    /// The returned [`StateMachineChecksums`] lists the members whose checksum differs from the
+    /// leader's:
+    ///
+    /// ```ignore
+    /// let checksums = raft.state_machine_checksums(Duration::from_secs(1)).await?;
+    /// for id in checksums.mismatched() {
+    ///     alert(format!("state machine of node {id} diverged"));
+    /// }
+    /// ```
+    ///
+    /// The checksums of the other members are requested with
+    /// [`RaftNetworkV2::state_machine_checksum`].
+    ///
+    /// Returns `Err(RaftError<CheckIsLeaderError>)` with a [`ForwardToLeader`] error if this node
+    /// is not the leader.
+    ///
+    /// [`RaftStateMachine::checksum`]: crate::storage::RaftStateMachine::checksum
+    /// [`RaftNetworkV2::state_machine_checksum`]: crate::network::v2::RaftNetworkV2::state_machine_checksum
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn state_machine_checksums(
+        &self,
+        timeout: Duration,
+    ) -> Result<StateMachineChecksums<C>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.management_api().state_machine_checksums(timeout).await.into_raft_result()
+    }
+
+    /// Allow, or disallow, the log of `node_id` to revert to an earlier state for one time.
+    ///
+    /// It must be called on the leader. It permits exactly one expected reversion, e.g., after
+    /// the data of a test follower is wiped, without enabling [`Config::allow_log_reversion`]
+    /// for every node permanently: the leader replicates logs to the node from the beginning
+    /// once it detects the reversion, and then resets the flag. If the node has already been
+    /// quarantined for log reversions, the replication to it is resumed.
+    ///
+    /// ```ignore
+    /// raft.allow_next_revert(&3, true).await?;
+    /// // Wipe the data of node-3 and restart it.
+    /// ```
+    ///
+    /// It is a shortcut of [`Trigger::allow_next_revert()`]. Returns
+    /// `Err(RaftError<AllowNextRevertError>)` if `node_id` is not replicated to by this node, or
+    /// if this node is not the leader.
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn allow_next_revert(
+        &self,
+        node_id: &C::NodeId,
+        allow: bool,
+    ) -> Result<(), RaftError<C, AllowNextRevertError<C>>> {
+        self.trigger().allow_next_revert(node_id, allow).await.into_raft_result()
+    }
+
+    /// Return the replication progress of every target, as tracked by the leader.
+    ///
+    /// It must be called on the leader. The returned map contains every voter and learner,
+    /// including the leader itself, with the exact state of the search for the last matching log
+    /// id and the data in flight. It is meant for custom replication schedulers and debugging
+    /// tools; for monitoring, use [`RaftMetrics::replication`] instead.
+    ///
+    /// Returns `Err(RaftError<CheckIsLeaderError>)` with a [`ForwardToLeader`] error if this node
+    /// is not the leader.
+    ///
+    /// [`ForwardToLeader`]: crate::error::ForwardToLeader
+    #[since(version = "0.10.0")]
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn replication_progress(
+        &self,
+    ) -> Result<BTreeMap<C::NodeId, TargetProgress<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.management_api().target_progress().await.into_raft_result()
+    }
+
     /// Get a handle to wait for the metrics to satisfy some condition.
     ///
     /// If `timeout` is `None`, then it will wait forever(10 years).
@@ -1082,6 +2288,43 @@ where C: RaftTypeConfig
         self.inner.wait(timeout)
     }
 
+    /// Wait until the log entry at `log_id` is applied to the local state machine.
+    ///
+    /// It works on a leader, a follower or a learner. A client that carries the log id returned by
+    /// its last write, e.g., [`ClientWriteResponse::log_id`], as a consistency token, calls it
+    /// before reading from a node's state machine, to read its own writes on any node:
+    ///
+    /// ```ignore
+    /// let resp = leader.client_write(req).await?;
+    ///
+    /// // On another node:
+    /// follower.wait_applied(resp.log_id, Some(Duration::from_secs(1))).await?;
+    /// let value = read_from_state_machine(key);
+    /// ```
+    ///
+    /// `log_id` must be committed, such as one returned by a successful write: it is regarded as
+    /// applied once an entry at the same index is applied, or once a snapshot including it is
+    /// installed. It returns the last applied log id, which is at least `log_id`.
+    ///
+    /// If `timeout` is `None`, it waits forever. It returns [`WaitError::Timeout`] if the entry
+    /// is not applied in time, or [`WaitError::ShuttingDown`] if Raft is shut down.
+    ///
+    /// [`ClientWriteResponse::log_id`]: crate::raft::ClientWriteResponse::log_id
+    #[since(version = "0.10.0")]
+    pub async fn wait_applied(&self, log_id: LogIdOf<C>, timeout: Option<Duration>) -> Result<LogIdOf<C>, WaitError> {
+        let index = log_id.index();
+        let metrics = self
+            .wait(timeout)
+            .metrics(
+                |m| m.last_applied.index() >= Some(index),
+                format!("wait for {} to be applied", log_id),
+            )
+            .await?;
+
+        // Safe unwrap(): last_applied is at least `log_id`.
+        Ok(metrics.last_applied.unwrap())
+    }
+
     /// Shutdown this Raft node.
     ///
     /// It sends a shutdown signal and waits until `RaftCore` returns.
@@ -1,10 +1,12 @@
 use std::future::Future;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 use std::time::Duration;
 
 use tracing::Level;
 
 use crate::Config;
+use crate::EffectiveMembership;
 use crate::OptionalSend;
 use crate::RaftMetrics;
 use crate::RaftTypeConfig;
@@ -13,15 +15,26 @@ use crate::async_runtime::watch::WatchReceiver;
 use crate::async_runtime::watch::WatchSender;
 use crate::config::RuntimeConfig;
 use crate::core::TickHandle;
+use crate::core::app_index::AppIndex;
 use crate::core::io_flush_tracking::IoProgressWatcher;
+use crate::core::node_infos::NodeInfos;
 use crate::core::raft_msg::RaftMsg;
+use crate::core::raft_msg::client_write_permit::ClientWritePermit;
 use crate::core::raft_msg::external_command::ExternalCommand;
+use crate::core::sm::computed_results::ComputedResults;
 use crate::display_ext::DisplayOptionExt;
 use crate::error::Fatal;
+use crate::error::Overloaded;
+use crate::metrics::ApplyLagEvents;
+use crate::metrics::MetricsHistory;
 use crate::metrics::RaftDataMetrics;
 use crate::metrics::RaftServerMetrics;
+use crate::metrics::SnapshotReceiveProgress;
 use crate::metrics::Wait;
+use crate::network::NetworkEventBus;
 use crate::raft::core_state::CoreState;
+use crate::storage::RecoveryReport;
+use crate::trace::recorder::EngineRecorder;
 use crate::type_config::AsyncRuntime;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::AsyncRuntimeOf;
@@ -41,10 +54,43 @@ where C: RaftTypeConfig
     pub(in crate::raft) runtime_config: Arc<RuntimeConfig>,
     pub(in crate::raft) tick_handle: TickHandle<C>,
     pub(in crate::raft) tx_api: MpscSenderOf<C, RaftMsg<C>>,
+
+    /// The priority lane for [`RaftMsg::is_priority()`] messages, processed ahead of `tx_api`.
+    pub(in crate::raft) tx_api_priority: MpscSenderOf<C, RaftMsg<C>>,
     pub(in crate::raft) rx_metrics: WatchReceiverOf<C, RaftMetrics<C>>,
     pub(in crate::raft) rx_data_metrics: WatchReceiverOf<C, RaftDataMetrics<C>>,
     pub(in crate::raft) rx_server_metrics: WatchReceiverOf<C, RaftServerMetrics<C>>,
+    pub(in crate::raft) rx_membership: WatchReceiverOf<C, Arc<EffectiveMembership<C>>>,
     pub(in crate::raft) progress_watcher: IoProgressWatcher<C>,
+    pub(in crate::raft) network_events: Arc<NetworkEventBus<C>>,
+
+    /// Shared with `RaftCore`, which records engine inputs into it.
+    pub(in crate::raft) engine_recorder: EngineRecorder<C>,
+
+    /// Shared with `RaftCore`, which samples metrics into it.
+    pub(in crate::raft) metrics_history: MetricsHistory<C>,
+
+    /// Shared with `RaftCore`, which emits apply lag events to the subscribers.
+    pub(in crate::raft) apply_lag_events: ApplyLagEvents<C>,
+
+    /// Shared with `RaftCore`, which reports the progress of the snapshot being received.
+    pub(in crate::raft) snapshot_receive: SnapshotReceiveProgress<C>,
+
+    /// The state recovered from storage when this node started.
+    pub(in crate::raft) recovery_report: RecoveryReport<C>,
+
+    /// Shared with `RaftCore`, which inserts the application keys published by the state machine.
+    pub(in crate::raft) app_index: AppIndex<C>,
+
+    /// Shared with the state machine worker, which applies the results received from the leader.
+    pub(in crate::raft) computed_results: ComputedResults<C>,
+
+    /// Shared with `RaftCore`, which sends hello requests and reports the infos in metrics.
+    pub(in crate::raft) node_infos: NodeInfos<C>,
+
+    /// The number of client writes sent to `RaftCore` but not yet received by it, counted by the
+    /// [`ClientWritePermit`]s alive.
+    pub(in crate::raft) queued_client_writes: Arc<AtomicU64>,
 
     pub(in crate::raft) tx_shutdown: std::sync::Mutex<Option<OneshotSenderOf<C, ()>>>,
     pub(in crate::raft) core_state: std::sync::Mutex<CoreState<C>>,
@@ -67,7 +113,13 @@ where C: RaftTypeConfig
     }
 
     pub(crate) async fn send_msg(&self, mes: RaftMsg<C>) -> Result<(), Fatal<C>> {
-        let send_res = self.tx_api.send(mes).await;
+        let tx = if mes.is_priority() {
+            &self.tx_api_priority
+        } else {
+            &self.tx_api
+        };
+
+        let send_res = tx.send(mes).await;
 
         if let Err(e) = send_res {
             let msg = e.0;
@@ -79,6 +131,22 @@ where C: RaftTypeConfig
         Ok(())
     }
 
+    /// Reserve a place in the queue for a client write, or return [`Overloaded`] if
+    /// [`Config::max_queued_client_writes`] writes are already queued.
+    ///
+    /// The place is released when the returned permit is dropped. It is sent along with the
+    /// write, and dropped by `RaftCore` when it receives the write.
+    pub(crate) fn reserve_client_write(&self) -> Result<ClientWritePermit, Overloaded> {
+        let max = self.config.max_queued_client_writes;
+
+        ClientWritePermit::try_acquire(&self.queued_client_writes, max).map_err(|queued| {
+            // How fast the queue drains is unknown here; the heartbeat interval is a short hint
+            // that scales with the expected latency of the cluster.
+            let retry_after = self.config.heartbeat_interval();
+            Overloaded::new(queued, retry_after)
+        })
+    }
+
     /// Invoke RaftCore by sending a RaftMsg and blocks waiting for response.
     #[tracing::instrument(level = "debug", skip_all)]
     pub(crate) async fn call_core<T>(&self, mes: RaftMsg<C>, rx: OneshotReceiverOf<C, T>) -> Result<T, Fatal<C>>
@@ -118,7 +186,14 @@ where C: RaftTypeConfig
     ///
     /// It returns at once.
     pub(in crate::raft) async fn send_external_command(&self, cmd: ExternalCommand<C>) -> Result<(), Fatal<C>> {
-        let send_res = self.tx_api.send(RaftMsg::ExternalCommand { cmd }).await;
+        let msg = RaftMsg::ExternalCommand { cmd };
+        let tx = if msg.is_priority() {
+            &self.tx_api_priority
+        } else {
+            &self.tx_api
+        };
+
+        let send_res = tx.send(msg).await;
 
         if send_res.is_err() {
             let fatal = self.get_core_stop_error().await;
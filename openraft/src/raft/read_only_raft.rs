@@ -0,0 +1,154 @@
+//! A handle to a Raft node that can only observe it.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::EffectiveMembership;
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::core::io_flush_tracking::LogProgress;
+use crate::core::io_flush_tracking::VoteProgress;
+use crate::error::CheckIsLeaderError;
+use crate::error::Fatal;
+use crate::error::RaftError;
+use crate::metrics::CapacityHint;
+use crate::metrics::MetricsSample;
+use crate::metrics::RaftDataMetrics;
+use crate::metrics::RaftMetrics;
+use crate::metrics::RaftServerMetrics;
+use crate::metrics::Wait;
+use crate::metrics::WaitError;
+use crate::raft::ClusterHealth;
+use crate::raft::ReadGuarantee;
+use crate::raft::ReadPolicy;
+use crate::storage::RecoveryReport;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::WatchReceiverOf;
+
+/// A handle to a Raft node that exposes only the metrics and read APIs.
+///
+/// It is created with [`Raft::read_only_handle()`], and is as cheap to clone as [`Raft`]. An
+/// application hands it to subsystems that only observe the node, such as an HTTP admin UI or a
+/// metrics exporter, so that they cannot write to the cluster, change the membership or trigger
+/// an action by mistake:
+///
+/// ```ignore
+/// let exporter = MetricsExporter::new(raft.read_only_handle());
+/// ```
+///
+/// Every method behaves the same as the [`Raft`] method of the same name.
+#[derive(Clone)]
+pub struct ReadOnlyRaft<C>
+where C: RaftTypeConfig
+{
+    raft: Raft<C>,
+}
+
+impl<C> ReadOnlyRaft<C>
+where C: RaftTypeConfig
+{
+    pub(in crate::raft) fn new(raft: Raft<C>) -> Self {
+        Self { raft }
+    }
+
+    /// See [`Raft::metrics`].
+    pub fn metrics(&self) -> WatchReceiverOf<C, RaftMetrics<C>> {
+        self.raft.metrics()
+    }
+
+    /// See [`Raft::data_metrics`].
+    pub fn data_metrics(&self) -> WatchReceiverOf<C, RaftDataMetrics<C>> {
+        self.raft.data_metrics()
+    }
+
+    /// See [`Raft::server_metrics`].
+    pub fn server_metrics(&self) -> WatchReceiverOf<C, RaftServerMetrics<C>> {
+        self.raft.server_metrics()
+    }
+
+    /// See [`Raft::metrics_history`].
+    pub fn metrics_history(&self) -> Option<Vec<MetricsSample<C>>> {
+        self.raft.metrics_history()
+    }
+
+    /// See [`Raft::recovery_report`].
+    pub fn recovery_report(&self) -> &RecoveryReport<C> {
+        self.raft.recovery_report()
+    }
+
+    /// See [`Raft::capacity_hint`].
+    pub fn capacity_hint(&self) -> Option<CapacityHint> {
+        self.raft.capacity_hint()
+    }
+
+    /// See [`Raft::effective_membership`].
+    pub fn effective_membership(&self) -> Arc<EffectiveMembership<C>> {
+        self.raft.effective_membership()
+    }
+
+    /// See [`Raft::lookup_app_key`].
+    pub fn lookup_app_key(&self, key: &str) -> Option<LogIdOf<C>> {
+        self.raft.lookup_app_key(key)
+    }
+
+    /// See [`Raft::is_readonly`].
+    pub fn is_readonly(&self) -> bool {
+        self.raft.is_readonly()
+    }
+
+    /// See [`Raft::watch_log_progress`].
+    #[must_use = "progress handle should be stored to track I/O progress"]
+    pub fn watch_log_progress(&self) -> LogProgress<C> {
+        self.raft.watch_log_progress()
+    }
+
+    /// See [`Raft::watch_vote_progress`].
+    #[must_use = "progress handle should be stored to track I/O progress"]
+    pub fn watch_vote_progress(&self) -> VoteProgress<C> {
+        self.raft.watch_vote_progress()
+    }
+
+    /// See [`Raft::wait`].
+    pub fn wait(&self, timeout: Option<Duration>) -> Wait<C> {
+        self.raft.wait(timeout)
+    }
+
+    /// See [`Raft::wait_applied`].
+    pub async fn wait_applied(&self, log_id: LogIdOf<C>, timeout: Option<Duration>) -> Result<LogIdOf<C>, WaitError> {
+        self.raft.wait_applied(log_id, timeout).await
+    }
+
+    /// See [`Raft::current_leader`].
+    pub async fn current_leader(&self) -> Option<C::NodeId> {
+        self.raft.current_leader().await
+    }
+
+    /// See [`Raft::is_initialized`].
+    pub async fn is_initialized(&self) -> Result<bool, Fatal<C>> {
+        self.raft.is_initialized().await
+    }
+
+    /// See [`Raft::ensure_linearizable`].
+    pub async fn ensure_linearizable(
+        &self,
+        read_policy: ReadPolicy,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.raft.ensure_linearizable(read_policy).await
+    }
+
+    /// See [`Raft::read`].
+    pub async fn read(
+        &self,
+        guarantee: ReadGuarantee,
+    ) -> Result<Option<LogIdOf<C>>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.raft.read(guarantee).await
+    }
+
+    /// See [`Raft::cluster_health`].
+    pub async fn cluster_health(
+        &self,
+        timeout: Duration,
+    ) -> Result<ClusterHealth<C>, RaftError<C, CheckIsLeaderError<C>>> {
+        self.raft.cluster_health(timeout).await
+    }
+}
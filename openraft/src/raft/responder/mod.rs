@@ -21,6 +21,11 @@ use crate::OptionalSend;
 /// # Type Parameters
 ///
 /// - `T`: The type of value to send through this responder
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used to send a `{T}`",
+    label = "`Responder<{T}>` is not implemented for `{Self}`",
+    note = "the `Responder<T>` of a `RaftTypeConfig` must be able to send any `T`, e.g., `Responder<T> = openraft::impls::OneshotResponder<Self, T>`, which is the default of `declare_raft_types!`"
+)]
 pub trait Responder<T>: OptionalSend + 'static {
     /// Send result when the request has been completed.
     ///
@@ -2,6 +2,9 @@
 
 use std::sync::atomic::Ordering;
 
+use tracing::Level;
+
+use crate::LogSubsystem;
 use crate::RaftTypeConfig;
 use crate::raft::RaftInner;
 
@@ -42,4 +45,24 @@ where C: RaftTypeConfig
     pub fn elect(&self, enabled: bool) {
         self.raft_inner.runtime_config.enable_elect.store(enabled, Ordering::Relaxed);
     }
+
+    /// Override the log level of a subsystem, or remove the override with `None`.
+    ///
+    /// Events of the subsystem that are not more verbose than `level` are emitted even if the
+    /// tracing subscriber filters them out by level. Such an event is emitted at `INFO` level
+    /// with two extra fields: `subsystem` and `level`, the original level of the event.
+    ///
+    /// It does not silence events that are enabled by the subscriber.
+    pub fn log_level(&self, subsystem: LogSubsystem, level: Option<Level>) {
+        self.raft_inner.runtime_config.log_levels.set(subsystem, level);
+    }
+
+    /// Override the log level of the replication and snapshot transmission to a single target,
+    /// or remove the override with `None`.
+    ///
+    /// It is used to debug the replication to one follower or learner without enabling verbose
+    /// logs for every target. See [`Self::log_level()`].
+    pub fn replication_log_level(&self, target: &C::NodeId, level: Option<Level>) {
+        self.raft_inner.runtime_config.log_levels.set_replication_target(target.to_string(), level);
+    }
 }
@@ -1,9 +1,12 @@
 //! Trigger an action to RaftCore by an external caller.
 
+use openraft_macros::since;
+
 use crate::RaftTypeConfig;
 use crate::core::raft_msg::external_command::ExternalCommand;
 use crate::error::AllowNextRevertError;
 use crate::error::Fatal;
+use crate::error::ResyncError;
 use crate::raft::RaftInner;
 use crate::type_config::TypeConfigExt;
 
@@ -92,7 +95,9 @@ where C: RaftTypeConfig
     ///
     /// - `allow=true`: This method instructs the RaftCore to allow the target node's log to revert
     ///   to a previous state for one time.
-    /// - `allow=false`: This method instructs the RaftCore to panic if the target node's log revert
+    /// - `allow=false`: This method instructs the RaftCore to treat a reversion of the target
+    ///   node's log as divergence, and to quarantine the node, see
+    ///   [`Config::quarantine_after_log_reversions`].
     ///
     /// This method returns a [`Fatal`] error if it failed to send the request to RaftCore, e.g.,
     /// when RaftCore is shut down.
@@ -106,6 +111,7 @@ where C: RaftTypeConfig
     ///   beginning.
     /// - If this node is not the Leader, the request is ignored.
     /// - If the target node is not found, the request is ignored.
+    /// - If the target node is quarantined, the replication to it is resumed.
     ///
     /// ### Automatic Replication Reset
     ///
@@ -125,6 +131,7 @@ where C: RaftTypeConfig
     /// - Restart the target node.
     ///
     /// [`Config::allow_log_reversion`]: `crate::Config::allow_log_reversion`
+    /// [`Config::quarantine_after_log_reversions`]: `crate::Config::quarantine_after_log_reversions`
     pub async fn allow_next_revert(
         &self,
         to: &C::NodeId,
@@ -143,4 +150,31 @@ where C: RaftTypeConfig
 
         Ok(res)
     }
+
+    /// Request the RaftCore to resync a follower or learner with a full snapshot.
+    ///
+    /// It is used when the target's log is known to be unreliable, e.g., after an operator detects
+    /// divergence, or restores the target from an old backup. Instead of searching for the last
+    /// matching log id by conflicts, the Leader forgets the target's replication progress and
+    /// sends it the last snapshot, building one if there is none, then replicates the logs after
+    /// it.
+    ///
+    /// The target installs the snapshot only if it is newer than the target's committed log id,
+    /// as it does for any snapshot; to rebuild the state of a target that is ahead of the
+    /// snapshot, clear its data before resyncing it.
+    ///
+    /// This method returns a [`Fatal`] error if it failed to send the request to RaftCore, e.g.,
+    /// when RaftCore is shut down.
+    /// Otherwise, it returns an `Ok(Result<_,_>)`, the inner result is:
+    /// - `Ok(())` if the resync is started; resyncing the Leader itself is ignored,
+    /// - or `Err(ResyncError)` if this node is not the Leader, or the target is not found.
+    #[since(version = "0.10.0")]
+    pub async fn resync(&self, to: &C::NodeId) -> Result<Result<(), ResyncError<C>>, Fatal<C>> {
+        let (tx, rx) = C::oneshot();
+        self.raft_inner.send_external_command(ExternalCommand::Resync { to: to.clone(), tx }).await?;
+
+        let res: Result<(), ResyncError<C>> = self.raft_inner.recv_msg(rx).await?;
+
+        Ok(res)
+    }
 }
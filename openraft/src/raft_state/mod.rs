@@ -87,6 +87,15 @@ where C: RaftTypeConfig
     /// If a log is in use by a replication task, the purge is postponed and is stored in this
     /// field.
     pub(crate) purge_upto: Option<LogIdOf<C>>,
+
+    /// The index of the first log not yet archived, if a log archiver is set with
+    /// [`Raft::set_log_archiver()`].
+    ///
+    /// Logs at or after it are not purged, except those covered by a snapshot installed from the
+    /// Leader.
+    ///
+    /// [`Raft::set_log_archiver()`]: crate::Raft::set_log_archiver
+    pub(crate) archive_next: Option<u64>,
 }
 
 impl<C> Default for RaftState<C>
@@ -102,6 +111,7 @@ where C: RaftTypeConfig
             server_state: ServerState::default(),
             io_state: Valid::new(IOState::default()),
             purge_upto: None,
+            archive_next: None,
         }
     }
 }
@@ -18,6 +18,19 @@ pub(crate) struct RuntimeStats {
     /// submitted to the storage layer, helping identify write batch patterns and storage I/O
     /// efficiency.
     pub(crate) append_batch: Histogram,
+
+    /// Number of `AppendEntries` received from a leader with a smaller vote while the lease of
+    /// the current leader is still valid.
+    pub(crate) split_brain_detected: u64,
+
+    /// Number of log ranges verified against the checksum sent by the leader.
+    pub(crate) log_checksums_verified: u64,
+
+    /// Number of log ranges whose checksum differs from the one sent by the leader.
+    pub(crate) log_checksum_mismatches: u64,
+
+    /// Number of times this leader handed off its leadership to a fitter voter.
+    pub(crate) leader_fitness_handoffs: u64,
 }
 
 impl Default for RuntimeStats {
@@ -31,6 +44,10 @@ impl RuntimeStats {
         Self {
             apply_batch: Histogram::new(),
             append_batch: Histogram::new(),
+            split_brain_detected: 0,
+            log_checksums_verified: 0,
+            log_checksum_mismatches: 0,
+            leader_fitness_handoffs: 0,
         }
     }
 }
@@ -0,0 +1,258 @@
+//! A cache of log entries shared by the replication streams of a leader.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::metrics::ReplicationCacheMetrics;
+use crate::vote::committed::CommittedVote;
+
+/// Log entries recently read from the log store for replication.
+///
+/// When several followers lag, their replication streams read the same log ranges. The first
+/// read is cached so that the others are served without reading the storage again.
+///
+/// An entry is evicted once every target has replicated it, or, when the cache is full, in the
+/// order of the log index. Entries are copied in and out of the cache with the `clone` function
+/// provided by the caller, usually [`RaftLogReader::clone_entry`]; an entry that can not be
+/// copied is not cached.
+///
+/// [`RaftLogReader::clone_entry`]: crate::storage::RaftLogReader::clone_entry
+///
+/// The cache belongs to one leadership: it records the vote of the leader whose replication
+/// streams filled it. Reads and inserts from the streams of another leader are ignored, and
+/// inserting for a greater vote discards the entries of the older one. A stream of a deposed
+/// leader that is still running therefore can not serve its entries to the streams of a later
+/// leadership of this node, whose log may differ at the same indexes after a truncation.
+///
+/// It is a handle shared by `RaftCore`, which evicts entries, and every `ReplicationCore`, which
+/// reads and inserts entries.
+#[derive(Clone)]
+pub(crate) struct EntryCache<C>
+where C: RaftTypeConfig
+{
+    /// The max number of entries to keep. `0` disables the cache.
+    capacity: u64,
+
+    inner: Arc<Mutex<Inner<C>>>,
+}
+
+struct Inner<C>
+where C: RaftTypeConfig
+{
+    /// The leader the cached entries are read for.
+    leader_vote: Option<CommittedVote<C>>,
+
+    entries: BTreeMap<u64, C::Entry>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<C> EntryCache<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(capacity: u64) -> Self {
+        Self {
+            capacity,
+            inner: Arc::new(Mutex::new(Inner {
+                leader_vote: None,
+                entries: BTreeMap::new(),
+                hits: 0,
+                misses: 0,
+            })),
+        }
+    }
+
+    /// Return copies of the cached entries in `[start, end)`, stopping at the first one not cached.
+    ///
+    /// It returns `None` if the entry at `start` is not cached, or the cache is not filled for
+    /// `leader_vote`.
+    pub(crate) fn get(
+        &self,
+        leader_vote: &CommittedVote<C>,
+        start: u64,
+        end: u64,
+        clone: impl Fn(&C::Entry) -> Option<C::Entry>,
+    ) -> Option<Vec<C::Entry>> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+
+        let mut entries = vec![];
+        if inner.leader_vote.as_ref() != Some(leader_vote) {
+            inner.misses += 1;
+            return None;
+        }
+
+        for index in start..end {
+            let Some(entry) = inner.entries.get(&index).and_then(&clone) else {
+                break;
+            };
+            entries.push(entry);
+        }
+
+        if entries.is_empty() {
+            inner.misses += 1;
+            None
+        } else {
+            inner.hits += 1;
+            Some(entries)
+        }
+    }
+
+    /// Cache copies of `entries` read from the log store by a replication stream of `leader_vote`.
+    ///
+    /// Entries read for a leader older than the one the cache is filled for are discarded.
+    pub(crate) fn insert(
+        &self,
+        leader_vote: &CommittedVote<C>,
+        entries: &[C::Entry],
+        clone: impl Fn(&C::Entry) -> Option<C::Entry>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+
+        match inner.leader_vote.as_ref() {
+            Some(v) if v == leader_vote => {}
+            Some(v) if v > leader_vote => return,
+            _ => {
+                inner.leader_vote = Some(leader_vote.clone());
+                inner.entries.clear();
+            }
+        }
+
+        for entry in entries {
+            let Some(copy) = clone(entry) else {
+                break;
+            };
+            inner.entries.insert(entry.index(), copy);
+        }
+
+        while inner.entries.len() as u64 > self.capacity {
+            inner.entries.pop_first();
+        }
+    }
+
+    /// Evict the entries before `index`, which are replicated to every target.
+    pub(crate) fn evict_before(&self, index: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries = inner.entries.split_off(&index);
+    }
+
+    /// Discard every entry, e.g., when this node is no longer the leader.
+    pub(crate) fn clear(&self) {
+        self.inner.lock().unwrap().entries.clear();
+    }
+
+    pub(crate) fn metrics(&self) -> ReplicationCacheMetrics {
+        let inner = self.inner.lock().unwrap();
+        ReplicationCacheMetrics {
+            entries: inner.entries.len() as u64,
+            hits: inner.hits,
+            misses: inner.misses,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntryCache;
+    use crate::Entry;
+    use crate::Vote;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::RaftEntry;
+    use crate::vote::committed::CommittedVote;
+    use crate::vote::raft_vote::RaftVoteExt;
+
+    fn entries(term: u64, start: u64, end: u64) -> Vec<Entry<UTConfig>> {
+        (start..end).map(|i| Entry::new_blank(log_id(term, 1, i))).collect()
+    }
+
+    fn clone(entry: &Entry<UTConfig>) -> Option<Entry<UTConfig>> {
+        Some(entry.clone())
+    }
+
+    fn indexes(entries: Option<Vec<Entry<UTConfig>>>) -> Option<Vec<u64>> {
+        entries.map(|x| x.iter().map(|e| e.index()).collect())
+    }
+
+    fn vote(term: u64) -> CommittedVote<UTConfig> {
+        Vote::new(term, 1).into_committed()
+    }
+
+    #[test]
+    fn test_get_insert_evict() {
+        let c = EntryCache::<UTConfig>::new(5);
+        let v = vote(1);
+
+        assert_eq!(None, indexes(c.get(&v, 1, 3, clone)));
+
+        c.insert(&v, &entries(1, 1, 4), clone);
+        assert_eq!(Some(vec![1, 2]), indexes(c.get(&v, 1, 3, clone)));
+        assert_eq!(
+            Some(vec![2, 3]),
+            indexes(c.get(&v, 2, 10, clone)),
+            "stop at the first missing entry"
+        );
+
+        c.insert(&v, &entries(1, 4, 8), clone);
+        assert_eq!(None, indexes(c.get(&v, 1, 3, clone)), "evicted by capacity");
+        assert_eq!(Some(vec![3, 4, 5, 6, 7]), indexes(c.get(&v, 3, 8, clone)));
+
+        c.evict_before(6);
+        assert_eq!(None, indexes(c.get(&v, 5, 8, clone)));
+        assert_eq!(Some(vec![6, 7]), indexes(c.get(&v, 6, 8, clone)));
+
+        let m = c.metrics();
+        assert_eq!((2, 4, 3), (m.entries, m.hits, m.misses));
+
+        c.clear();
+        assert_eq!(0, c.metrics().entries);
+    }
+
+    /// The leader steps down, truncates its log as a follower and is elected again: entries read
+    /// by a stream of the old leadership must not be served to the new one.
+    #[test]
+    fn test_step_down_and_re_elected() {
+        let c = EntryCache::<UTConfig>::new(5);
+        let (v1, v3) = (vote(1), vote(3));
+
+        c.insert(&v1, &entries(1, 1, 4), clone);
+        c.clear();
+
+        // A stream of the old leadership, not yet joined, inserts after the clear.
+        c.insert(&v1, &entries(1, 1, 4), clone);
+
+        assert_eq!(None, indexes(c.get(&v3, 1, 4, clone)), "filled by another leader");
+
+        c.insert(&v3, &entries(2, 1, 3), clone);
+        let got = c.get(&v3, 1, 4, clone).unwrap();
+        assert_eq!(
+            vec![log_id(2, 1, 1), log_id(2, 1, 2)],
+            got.iter().map(|e| e.log_id()).collect::<Vec<_>>(),
+            "entries of the old leadership are discarded"
+        );
+
+        c.insert(&v1, &entries(1, 3, 4), clone);
+        assert_eq!(Some(vec![1, 2]), indexes(c.get(&v3, 1, 4, clone)), "stale insert is ignored");
+        assert_eq!(None, indexes(c.get(&v1, 1, 4, clone)));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let c = EntryCache::<UTConfig>::new(0);
+        let v = vote(1);
+
+        c.insert(&v, &entries(1, 1, 4), clone);
+        assert_eq!(None, indexes(c.get(&v, 1, 3, clone)));
+        assert_eq!((0, 0, 0), (c.metrics().entries, c.metrics().hits, c.metrics().misses));
+    }
+}
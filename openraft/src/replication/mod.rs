@@ -1,15 +1,20 @@
 //! Replication stream.
 
 pub(crate) mod callbacks;
+pub(crate) mod entry_cache;
 pub(crate) mod hint;
 mod replication_session_id;
 pub(crate) mod request;
 pub(crate) mod response;
+pub(crate) mod snapshot_permits;
+mod tail_buffer;
 
+use std::pin::pin;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyerror::AnyError;
+use futures::future::Either;
 use futures::future::FutureExt;
 pub(crate) use replication_session_id::ReplicationSessionId;
 use request::Data;
@@ -24,9 +29,13 @@ use crate::StorageError;
 use crate::async_runtime::MpscUnboundedReceiver;
 use crate::async_runtime::MpscUnboundedSender;
 use crate::async_runtime::MpscUnboundedWeakSender;
+use crate::base::catch_panic::catch_panic;
 use crate::config::Config;
+use crate::config::RuntimeConfig;
+use crate::core::node_infos::NodeInfos;
 use crate::core::notification::Notification;
 use crate::core::sm::handle::SnapshotReader;
+use crate::core::trace_ids::TraceIds;
 use crate::display_ext::DisplayInstantExt;
 use crate::display_ext::DisplayOptionExt;
 use crate::entry::RaftEntry;
@@ -40,13 +49,19 @@ use crate::error::Timeout;
 use crate::log_id::LogIdOptionExt;
 use crate::log_id_range::LogIdRange;
 use crate::network::Backoff;
+use crate::network::NetworkEventBus;
 use crate::network::RPCOption;
 use crate::network::RPCTypes;
+use crate::network::layer::FullSnapshotRpc;
+use crate::network::layer::NetworkLayers;
 use crate::network::v2::RaftNetworkV2;
 use crate::raft::AppendEntriesRequest;
 use crate::raft::AppendEntriesResponse;
 use crate::replication::callbacks::SnapshotCallback;
+use crate::replication::entry_cache::EntryCache;
 use crate::replication::hint::ReplicationHint;
+use crate::replication::snapshot_permits::SnapshotPermits;
+use crate::replication::tail_buffer::TailBuffer;
 use crate::storage::RaftLogReader;
 use crate::storage::RaftLogStorage;
 use crate::storage::Snapshot;
@@ -81,7 +96,8 @@ where C: RaftTypeConfig
 ///
 /// NOTE: we do not stack replication requests to targets because this could result in
 /// out-of-order delivery. We always buffer until we receive a success response, then send the
-/// next payload from the buffer.
+/// next payload from the buffer. The only exception is the pipelined AppendEntries RPCs sent for
+/// one replication action, see [`Self::send_log_entries_pipelined`].
 pub(crate) struct ReplicationCore<C, N, LS>
 where
     C: RaftTypeConfig,
@@ -111,11 +127,20 @@ where
     /// The `RaftNetwork` interface for replicating logs and heartbeat.
     network: N::Network,
 
+    /// Extra `RaftNetwork` clients to send pipelined AppendEntries RPCs concurrently with the
+    /// one sent by [`Self::network`].
+    ///
+    /// There are [`Config::max_append_entries_inflight`] - 1 of them.
+    pipeline_networks: Vec<N::Network>,
+
     /// Another `RaftNetwork` specific for snapshot replication.
     ///
     /// Snapshot transmitting is a long-running task and is processed in a separate task.
     snapshot_network: Arc<MutexOf<C, N::Network>>,
 
+    /// The permits to send a snapshot, shared by every replication stream of the leader.
+    snapshot_permits: SnapshotPermits<C>,
+
     /// The current snapshot replication state.
     ///
     /// It includes a cancel signaler and the join handle of the snapshot replication task.
@@ -123,10 +148,34 @@ where
     /// to quit.
     snapshot_state: Option<(OneshotSenderOf<C, ()>, JoinHandleOf<C, ()>)>,
 
+    /// The logs following the snapshot being sent.
+    ///
+    /// They are read while the snapshot is transmitted, and are sent without reading the storage
+    /// once the snapshot is installed.
+    tail_buffer: TailBuffer<C>,
+
+    /// The log entries read for replication, shared by every replication stream of the leader.
+    entry_cache: EntryCache<C>,
+
+    /// The trace ids of client requests by log index, sent along with the entries.
+    trace_ids: TraceIds,
+
     /// The backoff policy if an [`Unreachable`](`crate::error::Unreachable`) error is returned.
     /// It will be reset to `None` when a successful response is received.
     backoff: Option<Backoff>,
 
+    /// Do not send logs until this time, because the target reported it is busy applying logs.
+    busy_until: Option<InstantOf<C>>,
+
+    /// Delivers connection lifecycle events to the application.
+    network_events: Arc<NetworkEventBus<C>>,
+
+    /// The layers applied to every RPC sent to the target.
+    network_layers: NetworkLayers<C>,
+
+    /// The infos of the nodes, to negotiate the compression scheme with the target.
+    node_infos: NodeInfos<C>,
+
     /// The [`RaftLogStorage::LogReader`] interface.
     log_reader: LS::LogReader,
 
@@ -136,6 +185,9 @@ where
     /// The Raft's runtime config.
     config: Arc<Config>,
 
+    /// The config that can be changed at runtime, e.g., log level overrides.
+    runtime_config: Arc<RuntimeConfig>,
+
     /// The log id of the highest log entry which is known to be committed in the cluster.
     committed: Option<LogIdOf<C>>,
 
@@ -164,13 +216,21 @@ where
         target: C::NodeId,
         session_id: ReplicationSessionId<C>,
         config: Arc<Config>,
+        runtime_config: Arc<RuntimeConfig>,
         committed: Option<LogIdOf<C>>,
         matching: Option<LogIdOf<C>>,
         network: N::Network,
+        pipeline_networks: Vec<N::Network>,
         snapshot_network: N::Network,
+        snapshot_permits: SnapshotPermits<C>,
         log_reader: LS::LogReader,
+        entry_cache: EntryCache<C>,
+        trace_ids: TraceIds,
         snapshot_reader: SnapshotReader<C>,
         tx_raft_core: MpscSenderOf<C, Notification<C>>,
+        network_events: Arc<NetworkEventBus<C>>,
+        network_layers: NetworkLayers<C>,
+        node_infos: NodeInfos<C>,
         span: tracing::Span,
     ) -> ReplicationHandle<C> {
         tracing::debug!(
@@ -188,12 +248,22 @@ where
             target,
             session_id,
             network,
+            pipeline_networks,
             snapshot_network: Arc::new(C::mutex(snapshot_network)),
+            snapshot_permits,
             snapshot_state: None,
+            tail_buffer: TailBuffer::default(),
+            entry_cache,
+            trace_ids,
             backoff: None,
+            busy_until: None,
+            network_events,
+            network_layers,
+            node_infos,
             log_reader,
             snapshot_reader,
             config,
+            runtime_config,
             committed,
             matching,
             tx_raft_core,
@@ -203,7 +273,17 @@ where
             entries_hint: Default::default(),
         };
 
-        let join_handle = C::spawn(this.main().instrument(span));
+        let target = this.target.clone();
+        let session_id = this.session_id.clone();
+        let tx_raft_core = this.tx_raft_core.clone();
+
+        let name = Self::task_name("replication", &session_id, &target);
+
+        let fu = async move {
+            let res = Self::catch_panic(target, session_id, tx_raft_core, this.main()).await;
+            res.unwrap_or_else(|| Err(ReplicationClosed::new("replication panicked")))
+        };
+        let join_handle = C::spawn_named(&name, fu.instrument(span));
 
         ReplicationHandle {
             join_handle,
@@ -211,9 +291,51 @@ where
         }
     }
 
+    /// The name of a `kind` of task of the replication stream from the leader of `session_id` to
+    /// `target`, such as `openraft-replication-1->2`.
+    fn task_name(kind: &str, session_id: &ReplicationSessionId<C>, target: &C::NodeId) -> String {
+        let vote = session_id.vote();
+        format!("openraft-{}-{}->{}", kind, vote.leader_node_id().display(), target)
+    }
+
+    /// Run `fut`, a task of the replication stream to `target`, and report it to RaftCore if it
+    /// panics.
+    ///
+    /// It returns `None` if `fut` panicked.
+    async fn catch_panic<F>(
+        target: C::NodeId,
+        session_id: ReplicationSessionId<C>,
+        tx_raft_core: MpscSenderOf<C, Notification<C>>,
+        fut: F,
+    ) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        let task = format!("replication(target={})", target);
+
+        match catch_panic(task, fut).await {
+            Ok(x) => Some(x),
+            Err(error) => {
+                tracing::error!(error = display(&error), "replication task panicked");
+
+                let notify = Notification::ReplicationPanicked {
+                    target,
+                    session_id,
+                    error,
+                };
+                tx_raft_core.send(notify).await.ok();
+                None
+            }
+        }
+    }
+
     #[tracing::instrument(level="debug", skip(self), fields(session=%self.session_id, target=display(&self.target), cluster=%self.config.cluster_name))]
     async fn main(mut self) -> Result<(), ReplicationClosed> {
         loop {
+            if self.snapshot_state.is_some() {
+                self.buffer_log_tail().await;
+            }
+
             let action = self.next_action.take();
 
             let Some(d) = action else {
@@ -224,7 +346,14 @@ where
             // Backup the log data for retrying.
             let mut log_data = None;
 
-            tracing::debug!(replication_data = display(&d), "{} send replication RPC", func_name!());
+            subsystem_log!(
+                self.runtime_config,
+                Replication(self.target),
+                DEBUG,
+                replication_data = display(&d),
+                "{} send replication RPC",
+                func_name!()
+            );
 
             // If an RPC response is expected by RaftCore
             let need_notify = d.has_payload();
@@ -239,19 +368,27 @@ where
                 }
                 Data::Logs(log) => {
                     log_data = Some(log.clone());
-                    self.send_log_entries(log, true).await
+                    self.send_log_entries_pipelined(log).await
                 }
                 Data::Snapshot(snap) => self.stream_snapshot(snap).await,
                 Data::SnapshotCallback(resp) => self.handle_snapshot_callback(resp).await,
             };
 
-            tracing::debug!(res = debug(&res), "replication action done");
+            subsystem_log!(
+                self.runtime_config,
+                Replication(self.target),
+                DEBUG,
+                res = debug(&res),
+                "replication action done"
+            );
 
             match res {
                 Ok(next) => {
                     // reset backoff at once if replication succeeds
                     self.backoff = None;
 
+                    self.network_events.on_success(&self.target);
+
                     // If the RPC was successful but not finished, continue.
                     if let Some(next) = next {
                         self.next_action = Some(next);
@@ -284,6 +421,8 @@ where
                         ReplicationError::RPCError(err) => {
                             tracing::error!(err = display(&err), "RPCError");
 
+                            self.network_events.on_error(&self.target, &err);
+
                             let retry = match &err {
                                 RPCError::Timeout(_) => false,
                                 RPCError::Unreachable(_unreachable) => {
@@ -292,6 +431,7 @@ where
                                     // successful RPC is sent.
                                     if self.backoff.is_none() {
                                         self.backoff = Some(self.network.backoff());
+                                        self.network_events.on_backoff(&self.target);
                                     }
                                     false
                                 }
@@ -335,6 +475,12 @@ where
             self.backoff_drain_events(C::now() + duration).await?;
         }
 
+        if let Some(until) = self.busy_until.take()
+            && until > C::now()
+        {
+            self.backoff_drain_events(until).await?;
+        }
+
         self.drain_events().await?;
         Ok(())
     }
@@ -349,7 +495,13 @@ where
             }
             RPCTypes::AppendEntries => {
                 self.entries_hint = ReplicationHint::new(too_large.entries_hint(), DEFAULT_ENTRIES_HINT_TTL);
-                tracing::debug!(entries_hint = debug(&self.entries_hint), "updated entries hint");
+                subsystem_log!(
+                    self.runtime_config,
+                    Replication(self.target),
+                    DEBUG,
+                    entries_hint = debug(&self.entries_hint),
+                    "updated entries hint"
+                );
             }
             RPCTypes::InstallSnapshot => {
                 // TODO: handle too large
@@ -358,6 +510,21 @@ where
             RPCTypes::TransferLeader => {
                 unreachable!("TransferLeader RPC should not be too large")
             }
+            RPCTypes::Decommission => {
+                unreachable!("Decommission RPC should not be too large")
+            }
+            RPCTypes::StateMachineChecksum => {
+                unreachable!("StateMachineChecksum RPC should not be too large")
+            }
+            RPCTypes::RepairLog => {
+                unreachable!("RepairLog RPC should not be too large")
+            }
+            RPCTypes::ApplyResults => {
+                unreachable!("ApplyResults RPC is not sent by replication")
+            }
+            RPCTypes::Hello => {
+                unreachable!("Hello RPC is not sent by replication")
+            }
         }
     }
 
@@ -376,7 +543,13 @@ where
         log_ids: LogIdRange<C>,
         has_payload: bool,
     ) -> Result<Option<Data<C>>, ReplicationError<C>> {
-        tracing::debug!(log_id_range = display(&log_ids), "send_log_entries",);
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
+            log_id_range = display(&log_ids),
+            "send_log_entries",
+        );
 
         // Series of logs to send, and the last log id to send
         let (logs, sending_range) = {
@@ -400,8 +573,23 @@ where
                 let r = LogIdRange::new(rng.prev.clone(), rng.prev.clone());
                 (vec![], r)
             } else {
-                // limited_get_log_entries will return logs smaller than the range [start, end).
-                let logs = self.log_reader.limited_get_log_entries(start, end).await?;
+                let buffered_end = std::cmp::min(end, start + self.config.max_payload_entries);
+
+                let mut logs = match self.tail_buffer.take(start, buffered_end) {
+                    Some(logs) => {
+                        subsystem_log!(
+                            self.runtime_config,
+                            Replication(self.target),
+                            DEBUG,
+                            start,
+                            n = logs.len(),
+                            "send logs from tail buffer"
+                        );
+                        logs
+                    }
+                    None => self.read_log_entries(start, end).await?,
+                };
+                logs.truncate(self.payload_bytes_len(&self.payload_prefix_bytes(&logs)));
 
                 let first = logs.first().map(|ent| ent.ref_log_id()).unwrap();
                 let last = logs.last().map(|ent| ent.log_id()).unwrap();
@@ -423,27 +611,52 @@ where
 
         let leader_time = C::now();
 
+        let trace_ids = self.trace_ids.range(sending_range.prev.next_index(), sending_range.last.next_index());
+
         // Build the heartbeat frame to be sent to the follower.
         let payload = AppendEntriesRequest {
             vote: self.session_id.vote(),
             prev_log_id: sending_range.prev.clone(),
             leader_commit: self.committed.clone(),
             entries: logs,
+            cluster_name: Some(self.config.cluster_name.clone()),
+            trace_ids,
+            log_checksum: None,
         };
 
         // Send the payload.
-        tracing::debug!(
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
             payload = display(&payload),
             now = display(leader_time.display()),
             "start sending append_entries, timeout: {:?}",
-            self.config.heartbeat_interval
+            self.config.heartbeat_interval()
         );
 
-        let the_timeout = Duration::from_millis(self.config.heartbeat_interval);
-        let option = RPCOption::new(the_timeout);
-        let res = C::timeout(the_timeout, self.network.append_entries(payload, option)).await;
-
-        tracing::debug!("append_entries res: {:?}", res);
+        let the_timeout = self.config.heartbeat_interval();
+        let mut option = RPCOption::new(the_timeout);
+        option.trace_ids = payload.trace_ids.values().cloned().collect();
+        option.compression = self.compression_for(&payload.entries);
+        fail_point!("before_send_append_entries", |_| {
+            let err = AnyError::error("failpoint: before_send_append_entries");
+            Err(RPCError::Network(crate::error::NetworkError::new(&err)).into())
+        });
+
+        let res = C::timeout(
+            the_timeout,
+            self.network_layers.call(&self.target, &mut self.network, payload, option),
+        )
+        .await;
+
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
+            "append_entries res: {:?}",
+            res
+        );
 
         let append_res = res.map_err(|_e| {
             let to = Timeout {
@@ -457,7 +670,10 @@ where
 
         let append_resp = append_res?;
 
-        tracing::debug!(
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
             req = display(&sending_range),
             resp = display(&append_resp),
             "append_entries resp"
@@ -494,7 +710,7 @@ where
                     vote,
                     self.session_id.vote(),
                 );
-                tracing::debug!(%vote, "append entries failed. converting to follower");
+                subsystem_log!(self.runtime_config, Replication(self.target), DEBUG, %vote, "append entries failed. converting to follower");
 
                 Err(ReplicationError::HigherVote(HigherVote {
                     higher: vote,
@@ -516,7 +732,296 @@ where
 
                 Ok(None)
             }
+            AppendEntriesResponse::Busy { apply_backlog } => {
+                self.notify_heartbeat_progress(leader_time).await;
+                self.pause_for_busy(apply_backlog);
+
+                let matching = &sending_range.last;
+                if has_payload {
+                    self.notify_progress(ReplicationResult(Ok(matching.clone())), has_payload).await;
+                    Ok(self.next_action_to_send(matching.clone(), log_ids))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    /// The target is busy applying logs: do not send logs to it for `busy_pause`.
+    fn pause_for_busy(&mut self, apply_backlog: u64) {
+        let pause = Duration::from_millis(self.config.busy_pause);
+
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            INFO,
+            apply_backlog,
+            pause = debug(pause),
+            "target is busy applying logs, pause replication"
+        );
+
+        self.busy_until = Some(C::now() + pause);
+    }
+
+    /// Read the logs in `[start, end)` to send, from the shared [`EntryCache`] if they are cached.
+    ///
+    /// Like [`RaftLogReader::limited_get_log_entries`], it may return fewer logs than requested.
+    async fn read_log_entries(&mut self, start: u64, end: u64) -> Result<Vec<C::Entry>, StorageError<C>> {
+        let reader = &self.log_reader;
+        if let Some(logs) = self.entry_cache.get(&self.session_id.leader_vote, start, end, |e| reader.clone_entry(e)) {
+            subsystem_log!(
+                self.runtime_config,
+                Replication(self.target),
+                DEBUG,
+                start,
+                n = logs.len(),
+                "send logs from entry cache"
+            );
+            return Ok(logs);
+        }
+
+        let logs = self.log_reader.limited_get_log_entries(start, end).await?;
+        let reader = &self.log_reader;
+        self.entry_cache.insert(&self.session_id.leader_vote, &logs, |e| reader.clone_entry(e));
+        Ok(logs)
+    }
+
+    /// Returns the total sizes of the leading entries of `logs`: the `i`-th element is the total
+    /// size of the first `i` entries.
+    ///
+    /// The size of an entry is reported by [`RaftLogReader::entry_size`]; an entry of unknown size
+    /// is not counted.
+    fn payload_prefix_bytes(&self, logs: &[C::Entry]) -> Vec<u64> {
+        let mut prefix = Vec::with_capacity(logs.len() + 1);
+        let mut total = 0;
+        prefix.push(total);
+
+        for ent in logs {
+            total += self.log_reader.entry_size(ent).unwrap_or_default();
+            prefix.push(total);
+        }
+        prefix
+    }
+
+    /// Returns the number of the leading entries whose total size fits in
+    /// [`Config::max_payload_bytes`], but at least one.
+    ///
+    /// `prefix` is a slice of the result of [`Self::payload_prefix_bytes`] that starts at the first
+    /// of these entries.
+    fn payload_bytes_len(&self, prefix: &[u64]) -> usize {
+        let n = prefix.len() - 1;
+
+        let max = self.config.max_payload_bytes;
+        if max == 0 {
+            return n;
+        }
+
+        let base = prefix[0];
+        let fit = prefix[1..].partition_point(|total| total - base <= max);
+        std::cmp::min(std::cmp::max(fit, 1), n)
+    }
+
+    /// Returns the compression scheme negotiated with the target, if the total size of `logs`
+    /// reaches [`Config::compression_threshold`].
+    fn compression_for(&self, logs: &[C::Entry]) -> Option<String> {
+        if self.config.compression.is_empty() || logs.is_empty() {
+            return None;
+        }
+
+        let size: u64 = logs.iter().map(|ent| self.log_reader.entry_size(ent).unwrap_or_default()).sum();
+        if size < self.config.compression_threshold {
+            return None;
+        }
+
+        self.node_infos.compression(&self.target, &self.config.compression)
+    }
+
+    /// Send the logs in `log_ids` with up to [`Config::max_append_entries_inflight`] AppendEntries
+    /// RPCs at a time, without waiting for the previous one to be responded.
+    ///
+    /// The RPCs are sent concurrently, each by its own network client, and the responses are
+    /// handled in log order no matter in which order they arrive. The follower may receive an RPC
+    /// before the previous one, and reject it with a conflict because its `prev_log_id` is not
+    /// there yet: such a conflict is not a divergence, the logs after the last acknowledged RPC
+    /// are just sent again.
+    ///
+    /// It falls back to [`Self::send_log_entries`] if the logs fit in a single RPC.
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn send_log_entries_pipelined(
+        &mut self,
+        log_ids: LogIdRange<C>,
+    ) -> Result<Option<Data<C>>, ReplicationError<C>> {
+        let max_payload = self.config.max_payload_entries;
+        let start = log_ids.prev.next_index();
+        let end = log_ids.last.next_index();
+
+        if self.pipeline_networks.is_empty() || end - start <= max_payload || self.entries_hint.get().is_some() {
+            return self.send_log_entries(log_ids, true).await;
+        }
+
+        let logs = match self.tail_buffer.take(start, end) {
+            Some(logs) => logs,
+            None => self.read_log_entries(start, end).await?,
+        };
+
+        // Split the logs into one RPC per network client.
+        let mut ranges = vec![];
+        let mut payloads = vec![];
+        {
+            let window = self.pipeline_networks.len() + 1;
+            let mut prev = log_ids.prev.clone();
+            let mut logs = logs;
+
+            let prefix = self.payload_prefix_bytes(&logs);
+            let mut split = 0;
+
+            while !logs.is_empty() && payloads.len() < window {
+                let n = std::cmp::min(max_payload as usize, self.payload_bytes_len(&prefix[split..]));
+                split += n;
+                let entries = logs.drain(..n).collect::<Vec<_>>();
+                let last = entries.last().map(|ent| ent.log_id());
+
+                ranges.push(LogIdRange::new(prev.clone(), last.clone()));
+                payloads.push(AppendEntriesRequest {
+                    vote: self.session_id.vote(),
+                    prev_log_id: prev.clone(),
+                    leader_commit: self.committed.clone(),
+                    entries,
+                    cluster_name: Some(self.config.cluster_name.clone()),
+                    trace_ids: self.trace_ids.range(prev.next_index(), last.next_index()),
+                    log_checksum: None,
+                });
+                prev = last;
+            }
+        }
+
+        let leader_time = C::now();
+        let the_timeout = self.config.heartbeat_interval();
+
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
+            ranges = debug(&ranges),
+            now = display(leader_time.display()),
+            "start sending pipelined append_entries, timeout: {:?}",
+            the_timeout
+        );
+
+        fail_point!("before_send_append_entries", |_| {
+            let err = AnyError::error("failpoint: before_send_append_entries");
+            Err(RPCError::Network(crate::error::NetworkError::new(&err)).into())
+        });
+
+        let compressions = payloads.iter().map(|p| self.compression_for(&p.entries)).collect::<Vec<_>>();
+
+        let networks = std::iter::once(&mut self.network).chain(self.pipeline_networks.iter_mut());
+        let calls = networks.zip(payloads).zip(compressions).map(|((network, payload), compression)| {
+            let mut option = RPCOption::new(the_timeout);
+            option.trace_ids = payload.trace_ids.values().cloned().collect();
+            option.compression = compression;
+            C::timeout(
+                the_timeout,
+                self.network_layers.call(&self.target, network, payload, option),
+            )
+        });
+        let results = futures::future::join_all(calls).await;
+
+        let mut matching = log_ids.prev.clone();
+
+        for (i, (sending_range, res)) in ranges.into_iter().zip(results).enumerate() {
+            let append_res = res.map_err(|_e| {
+                RPCError::Timeout(Timeout {
+                    action: RPCTypes::AppendEntries,
+                    id: self.session_id.vote().to_leader_node_id().unwrap(),
+                    target: self.target.clone(),
+                    timeout: the_timeout,
+                })
+            });
+
+            let append_resp = match append_res.and_then(|r| r) {
+                Ok(x) => x,
+                Err(err) => {
+                    if i == 0 {
+                        return Err(err.into());
+                    }
+                    // The logs after the acknowledged ones are sent again.
+                    subsystem_log!(
+                        self.runtime_config,
+                        Replication(self.target),
+                        DEBUG,
+                        err = display(&err),
+                        "pipelined append_entries failed"
+                    );
+                    break;
+                }
+            };
+
+            subsystem_log!(
+                self.runtime_config,
+                Replication(self.target),
+                DEBUG,
+                req = display(&sending_range),
+                resp = display(&append_resp),
+                "pipelined append_entries resp"
+            );
+
+            match append_resp {
+                AppendEntriesResponse::Success => {
+                    self.notify_heartbeat_progress(leader_time).await;
+
+                    matching = sending_range.last;
+                    self.notify_progress(ReplicationResult(Ok(matching.clone())), true).await;
+                }
+                AppendEntriesResponse::Busy { apply_backlog } => {
+                    self.notify_heartbeat_progress(leader_time).await;
+                    self.pause_for_busy(apply_backlog);
+
+                    matching = sending_range.last;
+                    self.notify_progress(ReplicationResult(Ok(matching.clone())), true).await;
+                }
+                AppendEntriesResponse::PartialSuccess(partial) => {
+                    Self::debug_assert_partial_success(&sending_range, &partial);
+
+                    self.notify_heartbeat_progress(leader_time).await;
+
+                    matching = partial;
+                    self.notify_progress(ReplicationResult(Ok(matching.clone())), true).await;
+                    break;
+                }
+                AppendEntriesResponse::HigherVote(vote) => {
+                    subsystem_log!(self.runtime_config, Replication(self.target), DEBUG, %vote, "append entries failed. converting to follower");
+
+                    return Err(ReplicationError::HigherVote(HigherVote {
+                        higher: vote,
+                        sender_vote: self.session_id.vote(),
+                    }));
+                }
+                AppendEntriesResponse::Conflict => {
+                    if i > 0 {
+                        // This RPC arrived at the follower before the previous one.
+                        subsystem_log!(
+                            self.runtime_config,
+                            Replication(self.target),
+                            DEBUG,
+                            req = display(&sending_range),
+                            "pipelined append_entries arrived out of order"
+                        );
+                        break;
+                    }
+
+                    // Safe unwrap(): prev_log_id=None never conflict
+                    let conflict = sending_range.prev.unwrap();
+
+                    self.notify_heartbeat_progress(leader_time).await;
+                    self.notify_progress(ReplicationResult(Err(conflict)), true).await;
+
+                    return Ok(None);
+                }
+            }
         }
+
+        Ok(self.next_action_to_send(matching, log_ids))
     }
 
     /// Send the error result to RaftCore.
@@ -555,7 +1060,10 @@ where
 
     /// Notify RaftCore with the success replication result (log matching or conflict).
     async fn notify_progress(&mut self, replication_result: ReplicationResult<C>, has_payload: bool) {
-        tracing::debug!(
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
             target = display(self.target.clone()),
             curr_matching = display(self.matching.display()),
             result = display(&replication_result),
@@ -607,11 +1115,17 @@ where
 
             let recv = self.rx_event.recv();
 
-            tracing::debug!("backoff timeout: {:?}", sleep_duration);
+            subsystem_log!(
+                self.runtime_config,
+                Replication(self.target),
+                DEBUG,
+                "backoff timeout: {:?}",
+                sleep_duration
+            );
 
             futures::select! {
                 _ = sleep.fuse() => {
-                    tracing::debug!("backoff timeout");
+                    subsystem_log!(self.runtime_config, Replication(self.target), DEBUG, "backoff timeout");
                     return Ok(());
                 }
                 recv_res = recv.fuse() => {
@@ -627,7 +1141,7 @@ where
     /// It blocks until at least one event is received.
     #[tracing::instrument(level = "trace", skip_all)]
     pub async fn drain_events(&mut self) -> Result<(), ReplicationClosed> {
-        tracing::debug!("drain_events");
+        subsystem_log!(self.runtime_config, Replication(self.target), DEBUG, "drain_events");
 
         // If there is next action to run, do not block waiting for events,
         // instead, just try the best to drain all events.
@@ -646,7 +1160,7 @@ where
 
     #[tracing::instrument(level = "trace", skip(self))]
     pub async fn try_drain_events(&mut self) -> Result<(), ReplicationClosed> {
-        tracing::debug!("{}", func_name!());
+        subsystem_log!(self.runtime_config, Replication(self.target), DEBUG, "{}", func_name!());
 
         // Just drain all events in the channel.
         // There should NOT be more than one `Replicate::Data` event in the channel.
@@ -667,7 +1181,13 @@ where
 
     #[tracing::instrument(level = "trace", skip_all)]
     pub fn process_event(&mut self, event: Replicate<C>) {
-        tracing::debug!(event = display(&event), "process_event");
+        subsystem_log!(
+            self.runtime_config,
+            Replication(self.target),
+            DEBUG,
+            event = display(&event),
+            "process_event"
+        );
 
         match event {
             Replicate::Committed(c) => {
@@ -720,11 +1240,11 @@ where
     #[tracing::instrument(level = "info", skip_all)]
     async fn stream_snapshot(
         &mut self,
-        _snapshot_req: Option<LogIdOf<C>>,
+        snapshot_req: Option<LogIdOf<C>>,
     ) -> Result<Option<Data<C>>, ReplicationError<C>> {
         tracing::info!("{}", func_name!());
 
-        let snapshot = self.snapshot_reader.get_snapshot().await.map_err(|reason| {
+        let snapshot = self.snapshot_reader.get_snapshot(snapshot_req).await.map_err(|reason| {
             tracing::warn!(error = display(&reason), "failed to get snapshot from state machine");
             ReplicationClosed::new(reason)
         })?;
@@ -742,30 +1262,91 @@ where
             Some(x) => x,
         };
 
+        if self.config.snapshot_tail_buffer_size > 0 {
+            self.tail_buffer.reset(snapshot.meta.last_log_id.next_index());
+        }
+
         let mut option = RPCOption::new(self.config.install_snapshot_timeout());
         option.snapshot_chunk_size = Some(self.config.snapshot_max_chunk_size as usize);
+        option.cluster_name = Some(self.config.cluster_name.clone());
 
         let (tx_cancel, rx_cancel) = C::oneshot();
 
-        let jh = C::spawn(Self::send_snapshot(
+        let send = Self::send_snapshot(
+            self.target.clone(),
             self.snapshot_network.clone(),
+            self.network_layers.clone(),
+            self.snapshot_permits.clone(),
             self.session_id.vote(),
             snapshot,
             option,
             rx_cancel,
             self.weak_tx_event.clone(),
-        ));
+        );
+        let supervised = Self::catch_panic(
+            self.target.clone(),
+            self.session_id.clone(),
+            self.tx_raft_core.clone(),
+            send,
+        );
+        let name = Self::task_name("snapshot-sender", &self.session_id, &self.target);
+        let span = tracing::debug_span!("send_snapshot", target = display(&self.target));
+        let jh = C::spawn_named(&name, supervised.map(|_| ()).instrument(span));
 
         // When self.rx_event is dropped:
         // 1) ReplicationCore will return from the main loop;
         // 2) and tx_cancel is dropped;
         // 3) and the snapshot task will be notified.
         self.snapshot_state = Some((tx_cancel, jh));
+
+        // Read the logs after the snapshot while it is being sent.
+        self.buffer_log_tail().await;
+
         Ok(None)
     }
 
+    /// Read more logs after the snapshot being sent into the tail buffer, up to
+    /// [`Config::snapshot_tail_buffer_size`] entries.
+    ///
+    /// A failure to read is not an error: the logs will be read again when they are sent.
+    async fn buffer_log_tail(&mut self) {
+        let Some(start) = self.tail_buffer.next_index() else {
+            return;
+        };
+
+        let limit = self.config.snapshot_tail_buffer_size;
+        let buffered = self.tail_buffer.len() as u64;
+        if buffered >= limit {
+            return;
+        }
+
+        let end = start + (limit - buffered);
+
+        match self.log_reader.try_get_log_entries(start..end).await {
+            Ok(entries) => {
+                subsystem_log!(
+                    self.runtime_config,
+                    Snapshot(self.target),
+                    DEBUG,
+                    start,
+                    n = entries.len(),
+                    "buffer log tail while sending snapshot"
+                );
+                self.tail_buffer.extend(entries);
+            }
+            Err(e) => {
+                tracing::warn!(error = display(&e), "failed to buffer log tail, stop buffering");
+                self.tail_buffer.clear();
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn send_snapshot(
+        target: C::NodeId,
         network: Arc<MutexOf<C, N::Network>>,
+        layers: NetworkLayers<C>,
+        permits: SnapshotPermits<C>,
         vote: VoteOf<C>,
         snapshot: Snapshot<C>,
         option: RPCOption,
@@ -774,16 +1355,29 @@ where
     ) {
         let meta = snapshot.meta.clone();
 
-        let mut net = network.lock().await;
-
-        let start_time = C::now();
-
-        let cancel = async move {
+        let mut cancel = Box::pin(async move {
             let _ = cancel.await;
             ReplicationClosed::new("ReplicationCore is dropped")
+        });
+
+        // Wait in the queue for a permit; give up the place if the replication is closed.
+        let _permit = {
+            let acquire = pin!(permits.acquire());
+            match futures::future::select(acquire, &mut cancel).await {
+                Either::Left((permit, _)) => permit,
+                Either::Right((closed, _)) => {
+                    tracing::info!(error = display(&closed), "stop waiting for a permit to send snapshot");
+                    return;
+                }
+            }
         };
 
-        let res = net.full_snapshot(vote, snapshot, cancel, option).await;
+        let mut net = network.lock().await;
+
+        let start_time = C::now();
+
+        let rpc = FullSnapshotRpc { vote, snapshot, cancel };
+        let res = layers.call(&target, &mut *net, rpc, option).await;
         if let Err(e) = &res {
             tracing::warn!(error = display(e), "failed to send snapshot");
         }
@@ -803,7 +1397,10 @@ where
         &mut self,
         callback: SnapshotCallback<C>,
     ) -> Result<Option<Data<C>>, ReplicationError<C>> {
-        tracing::debug!(
+        subsystem_log!(
+            self.runtime_config,
+            Snapshot(self.target),
+            DEBUG,
             response = display(&callback),
             matching = display(self.matching.display()),
             "handle_snapshot_response"
@@ -817,7 +1414,17 @@ where
             snapshot_meta,
         } = callback;
 
-        let resp = result?;
+        let resp = match result {
+            Ok(x) => x,
+            Err(e) => {
+                self.tail_buffer.clear();
+                return Err(e.into());
+            }
+        };
+
+        // Stop buffering, the buffered logs will be sent in the next AppendEntries RPC.
+        tracing::info!(buffered = self.tail_buffer.len(), "snapshot is sent");
+        self.tail_buffer.stop();
 
         // Handle response conditions.
         let sender_vote = self.session_id.vote();
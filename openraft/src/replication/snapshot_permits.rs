@@ -0,0 +1,243 @@
+//! A limit on the number of snapshots a leader sends at the same time.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::RaftTypeConfig;
+use crate::async_runtime::OneshotSender;
+use crate::metrics::SnapshotTransferMetrics;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::OneshotSenderOf;
+
+/// Permits to send a snapshot, granted in FIFO order.
+///
+/// When many followers need a snapshot at once, sending all of them concurrently may exhaust the
+/// IO of the leader. A replication stream acquires a permit before sending a snapshot and releases
+/// it when the transfer finishes; when all permits are in use, it waits in a queue until a
+/// running transfer finishes.
+///
+/// It is a handle shared by every `ReplicationCore` of a leader. See
+/// [`Config::max_inflight_snapshots`](crate::Config::max_inflight_snapshots).
+#[derive(Clone)]
+pub(crate) struct SnapshotPermits<C>
+where C: RaftTypeConfig
+{
+    /// The max number of snapshots to send at the same time. `0` means no limit.
+    max: u64,
+
+    inner: Arc<Mutex<Inner<C>>>,
+}
+
+struct Inner<C>
+where C: RaftTypeConfig
+{
+    /// Number of granted permits.
+    sending: u64,
+
+    /// Transfers waiting for a permit, in the order they arrived, identified by a sequence number.
+    waiting: VecDeque<(u64, OneshotSenderOf<C, ()>)>,
+
+    /// The sequence number of the last transfer queued.
+    last_seq: u64,
+
+    /// Number of transfers that had to wait for a permit since the node started.
+    total_queued: u64,
+}
+
+/// A granted permit to send a snapshot, released when dropped.
+pub(crate) struct SnapshotPermit<C>
+where C: RaftTypeConfig
+{
+    inner: Arc<Mutex<Inner<C>>>,
+}
+
+impl<C> SnapshotPermits<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(max: u64) -> Self {
+        Self {
+            max,
+            inner: Arc::new(Mutex::new(Inner {
+                sending: 0,
+                waiting: VecDeque::new(),
+                last_seq: 0,
+                total_queued: 0,
+            })),
+        }
+    }
+
+    /// Wait until a permit is granted.
+    ///
+    /// If the returned future is dropped while waiting, the place in the queue is given up.
+    pub(crate) async fn acquire(&self) -> SnapshotPermit<C> {
+        let (seq, rx) = {
+            let mut inner = self.inner.lock().unwrap();
+
+            if self.max == 0 || inner.sending < self.max {
+                inner.sending += 1;
+                return self.permit();
+            }
+
+            let (tx, rx) = C::oneshot();
+            inner.last_seq += 1;
+            let seq = inner.last_seq;
+            inner.waiting.push_back((seq, tx));
+            inner.total_queued += 1;
+            (seq, rx)
+        };
+
+        tracing::info!(max = self.max, "too many snapshots are being sent, wait for a permit");
+
+        let mut waiter = Waiter {
+            inner: &self.inner,
+            seq,
+            granted: false,
+        };
+
+        // The sender is only dropped by a releasing permit after handing it over.
+        let _ = rx.await;
+        waiter.granted = true;
+
+        self.permit()
+    }
+
+    fn permit(&self) -> SnapshotPermit<C> {
+        SnapshotPermit {
+            inner: self.inner.clone(),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> SnapshotTransferMetrics {
+        let inner = self.inner.lock().unwrap();
+
+        SnapshotTransferMetrics {
+            sending: inner.sending,
+            queued: inner.waiting.len() as u64,
+            total_queued: inner.total_queued,
+        }
+    }
+}
+
+impl<C> Inner<C>
+where C: RaftTypeConfig
+{
+    /// Release a permit, handing it over to the first transfer that is still waiting.
+    fn release(&mut self) {
+        while let Some((_seq, tx)) = self.waiting.pop_front() {
+            if tx.send(()).is_ok() {
+                return;
+            }
+        }
+
+        self.sending -= 1;
+    }
+}
+
+impl<C> Drop for SnapshotPermit<C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        self.inner.lock().unwrap().release();
+    }
+}
+
+/// Gives up the place in the queue if a transfer stops waiting for a permit.
+struct Waiter<'a, C>
+where C: RaftTypeConfig
+{
+    inner: &'a Arc<Mutex<Inner<C>>>,
+    seq: u64,
+    granted: bool,
+}
+
+impl<C> Drop for Waiter<'_, C>
+where C: RaftTypeConfig
+{
+    fn drop(&mut self) {
+        if self.granted {
+            return;
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+
+        if let Some(pos) = inner.waiting.iter().position(|(seq, _)| *seq == self.seq) {
+            inner.waiting.remove(pos);
+        } else {
+            // The permit is handed over but not taken.
+            inner.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::SnapshotPermits;
+    use crate::engine::testing::UTConfig;
+    use crate::metrics::SnapshotTransferMetrics;
+
+    fn metrics(sending: u64, queued: u64, total_queued: u64) -> SnapshotTransferMetrics {
+        SnapshotTransferMetrics {
+            sending,
+            queued,
+            total_queued,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_acquire_in_fifo_order() {
+        let p = SnapshotPermits::<UTConfig>::new(1);
+
+        let p1 = p.acquire().await;
+
+        let mut w2 = Box::pin(p.acquire());
+        let mut w3 = Box::pin(p.acquire());
+        assert!((&mut w2).now_or_never().is_none());
+        assert!((&mut w3).now_or_never().is_none());
+        assert_eq!(metrics(1, 2, 2), p.metrics());
+
+        drop(p1);
+        assert!((&mut w3).now_or_never().is_none(), "w2 comes first");
+        let p2 = w2.await;
+        assert_eq!(metrics(1, 1, 2), p.metrics());
+
+        drop(p2);
+        let p3 = w3.await;
+        drop(p3);
+        assert_eq!(metrics(0, 0, 2), p.metrics());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_waiting() {
+        let p = SnapshotPermits::<UTConfig>::new(1);
+
+        let p1 = p.acquire().await;
+
+        let mut w2 = Box::pin(p.acquire());
+        assert!((&mut w2).now_or_never().is_none());
+        drop(w2);
+        assert_eq!(metrics(1, 0, 1), p.metrics(), "cancelled waiter leaves the queue");
+
+        let mut w3 = Box::pin(p.acquire());
+        assert!((&mut w3).now_or_never().is_none());
+
+        drop(p1);
+        drop(w3);
+        assert_eq!(
+            metrics(0, 0, 2),
+            p.metrics(),
+            "permit handed to a cancelled waiter is released"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_limit() {
+        let p = SnapshotPermits::<UTConfig>::new(0);
+
+        let _p1 = p.acquire().await;
+        let _p2 = p.acquire().await;
+        assert_eq!(metrics(2, 0, 0), p.metrics());
+    }
+}
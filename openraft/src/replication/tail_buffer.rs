@@ -0,0 +1,161 @@
+//! Buffers the logs after a snapshot while the snapshot is being sent.
+
+use std::collections::VecDeque;
+
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+
+/// Log entries following the snapshot being sent to a target.
+///
+/// The leader's log is append-only during its term, so the buffered entries stay valid until the
+/// replication stream is closed.
+pub(crate) struct TailBuffer<C>
+where C: RaftTypeConfig
+{
+    /// The index of the next entry to buffer. `None` if buffering is disabled.
+    next_index: Option<u64>,
+
+    entries: VecDeque<C::Entry>,
+}
+
+impl<C> Default for TailBuffer<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            next_index: None,
+            entries: VecDeque::new(),
+        }
+    }
+}
+
+impl<C> TailBuffer<C>
+where C: RaftTypeConfig
+{
+    /// Start buffering entries since `start`, discarding any buffered entries.
+    pub(crate) fn reset(&mut self, start: u64) {
+        self.next_index = Some(start);
+        self.entries.clear();
+    }
+
+    /// Discard all buffered entries and stop buffering.
+    pub(crate) fn clear(&mut self) {
+        self.next_index = None;
+        self.entries.clear();
+    }
+
+    /// Stop buffering, but keep the buffered entries to be taken.
+    pub(crate) fn stop(&mut self) {
+        self.next_index = None;
+    }
+
+    /// The index of the next entry to buffer, or `None` if buffering is stopped.
+    pub(crate) fn next_index(&self) -> Option<u64> {
+        self.next_index
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Append entries read from the log.
+    ///
+    /// If they do not follow the last buffered entry, for example, the expected entry is
+    /// purged, the buffer is cleared and buffering stops.
+    pub(crate) fn extend(&mut self, entries: Vec<C::Entry>) {
+        let Some(first) = entries.first() else {
+            return;
+        };
+
+        if Some(first.index()) != self.next_index {
+            tracing::debug!(
+                expect = debug(self.next_index),
+                got = display(first.index()),
+                "log tail is not contiguous, stop buffering"
+            );
+            self.clear();
+            return;
+        }
+
+        self.next_index = Some(first.index() + entries.len() as u64);
+        self.entries.extend(entries);
+    }
+
+    /// Take the buffered entries in `[start, end)`.
+    ///
+    /// Entries before `start` are discarded because they are already replicated.
+    /// It returns `None` if the entry at `start` is not buffered.
+    pub(crate) fn take(&mut self, start: u64, end: u64) -> Option<Vec<C::Entry>> {
+        while self.entries.front().is_some_and(|e| e.index() < start) {
+            self.entries.pop_front();
+        }
+
+        if self.entries.front()?.index() != start {
+            return None;
+        }
+
+        let n = std::cmp::min(self.entries.len() as u64, end.saturating_sub(start)) as usize;
+        if n == 0 {
+            return None;
+        }
+
+        Some(self.entries.drain(..n).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TailBuffer;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::entry::RaftEntry;
+    use crate::impls::Entry;
+
+    fn blanks(term: u64, indexes: std::ops::Range<u64>) -> Vec<Entry<UTConfig>> {
+        indexes.map(|i| Entry::new_blank(log_id(term, 1, i))).collect()
+    }
+
+    fn indexes(entries: &[Entry<UTConfig>]) -> Vec<u64> {
+        entries.iter().map(|e| e.index()).collect()
+    }
+
+    #[test]
+    fn test_tail_buffer_take() {
+        let mut b = TailBuffer::<UTConfig>::default();
+
+        // Not started
+        b.extend(blanks(1, 5..8));
+        assert_eq!(0, b.len());
+        assert_eq!(None, b.next_index());
+
+        b.reset(5);
+        b.extend(blanks(1, 5..8));
+        b.extend(blanks(1, 8..10));
+        assert_eq!(5, b.len());
+        assert_eq!(Some(10), b.next_index());
+
+        // The start entry is not buffered
+        assert!(b.take(4, 10).is_none());
+        assert_eq!(5, b.len());
+
+        assert_eq!(vec![5, 6], indexes(&b.take(5, 7).unwrap()));
+
+        // Entries before start are discarded
+        assert_eq!(vec![8, 9], indexes(&b.take(8, 20).unwrap()));
+        assert_eq!(0, b.len());
+        assert!(b.take(10, 20).is_none());
+    }
+
+    #[test]
+    fn test_tail_buffer_not_contiguous() {
+        let mut b = TailBuffer::<UTConfig>::default();
+
+        b.reset(5);
+        b.extend(blanks(1, 5..8));
+
+        // Entry 8 is missing
+        b.extend(blanks(1, 9..10));
+        assert_eq!(0, b.len());
+        assert_eq!(None, b.next_index());
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use crate::OptionalSend;
+use crate::OptionalSync;
+use crate::RaftTypeConfig;
+use crate::type_config::alias::LogIdOf;
+
+/// Stores the application keys published by the state machine and the log ids they are applied
+/// at, so that a client can look up whether a key, such as a transaction id, is committed.
+///
+/// Openraft gets the store with [`RaftStateMachine::app_index_store`], inserts the keys returned
+/// by [`RaftStateMachine::take_app_index_keys`] after every apply, and serves lookups with
+/// [`Raft::lookup_app_key`].
+///
+/// The store decides how long a key is kept. The default [`MemAppIndexStore`] keeps every key in
+/// memory and loses them on restart.
+///
+/// [`RaftStateMachine::app_index_store`]: crate::storage::RaftStateMachine::app_index_store
+/// [`RaftStateMachine::take_app_index_keys`]: crate::storage::RaftStateMachine::take_app_index_keys
+/// [`Raft::lookup_app_key`]: crate::Raft::lookup_app_key
+pub trait AppIndexStore<C>: OptionalSend + OptionalSync + 'static
+where C: RaftTypeConfig
+{
+    /// Record that `key` is applied at `log_id`.
+    fn insert(&mut self, key: String, log_id: LogIdOf<C>);
+
+    /// Return the log id `key` is applied at, or `None` if it is unknown.
+    fn get(&self, key: &str) -> Option<LogIdOf<C>>;
+}
+
+/// An in-memory [`AppIndexStore`] that keeps every inserted key.
+///
+/// Inserting a key that is already present replaces its log id.
+#[derive(Debug, Clone)]
+pub struct MemAppIndexStore<C>
+where C: RaftTypeConfig
+{
+    keys: BTreeMap<String, LogIdOf<C>>,
+}
+
+impl<C> Default for MemAppIndexStore<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self { keys: BTreeMap::new() }
+    }
+}
+
+impl<C> AppIndexStore<C> for MemAppIndexStore<C>
+where C: RaftTypeConfig
+{
+    fn insert(&mut self, key: String, log_id: LogIdOf<C>) {
+        self.keys.insert(key, log_id);
+    }
+
+    fn get(&self, key: &str) -> Option<LogIdOf<C>> {
+        self.keys.get(key).cloned()
+    }
+}
@@ -89,10 +89,18 @@ where C: RaftTypeConfig
             }
             Notification::HigherVote { .. }
             | Notification::StorageError { .. }
+            | Notification::ReplicationPanicked { .. }
+            | Notification::TaskPanicked { .. }
+            | Notification::RepairLog { .. }
+            | Notification::LogsArchived { .. }
+            | Notification::LogChecksumComputed { .. }
+            | Notification::LogChecksumVerified { .. }
+            | Notification::StorageChecked { .. }
             | Notification::ReplicationProgress { .. }
             | Notification::HeartbeatProgress { .. }
             | Notification::StateMachine { .. }
-            | Notification::Tick { .. } => {
+            | Notification::Tick { .. }
+            | Notification::ReadBatchDue => {
                 unreachable!("Unexpected notification: {}", self.notification)
             }
         }
@@ -0,0 +1,517 @@
+//! Encode the Raft types a storage persists, so that storage implementations do not need to invent
+//! their own encodings.
+//!
+//! - [`FixedWidthCodec`] encodes [`LogId`], [`Vote`] and the leader ids into a fixed number of
+//!   bytes. Integers are encoded in big-endian, thus encoded log ids of a totally ordered leader
+//!   id, such as the default [`leader_id_adv::LeaderId`], sort in the same order as the log ids,
+//!   and can be used as keys of an ordered key-value store.
+//! - `SnapshotMeta::encode()` and `SnapshotMeta::decode()` encode a snapshot meta, which is not
+//!   fixed-width, because it contains a membership config. Requires the `serde` feature.
+//!
+//! The text form is a round trip too: `LogId`, `Vote` and the leader ids are parsed back from their
+//! `Display` output with `FromStr`:
+//!
+//! ```ignore
+//! let log_id: LogId<C> = "T1-N2.3".parse()?;
+//! assert_eq!("T1-N2.3", log_id.to_string());
+//!
+//! let bytes = log_id.encode();
+//! assert_eq!(log_id, LogId::<C>::decode(&bytes)?);
+//! ```
+//!
+//! [`leader_id_adv::LeaderId`]: crate::impls::leader_id_adv::LeaderId
+
+use std::fmt::Display;
+use std::mem::size_of;
+use std::str::FromStr;
+
+use crate::LogId;
+use crate::RaftTypeConfig;
+#[cfg(feature = "serde")]
+use crate::SnapshotMeta;
+#[cfg(feature = "serde")]
+use crate::StoredMembership;
+use crate::Vote;
+use crate::type_config::alias::CommittedLeaderIdOf;
+#[cfg(feature = "serde")]
+use crate::type_config::alias::LogIdOf;
+use crate::vote::leader_id::leader_id_adv;
+use crate::vote::leader_id::leader_id_std;
+
+/// Error returned when bytes or a string can not be decoded into a value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CodecError {
+    /// The input does not have the expected length.
+    #[error("invalid length: expected {expected} bytes, got {got}")]
+    Length { expected: usize, got: usize },
+
+    /// The input is malformed.
+    #[error("invalid input: {reason}")]
+    Invalid { reason: String },
+}
+
+impl CodecError {
+    pub(crate) fn invalid(reason: impl ToString) -> Self {
+        Self::Invalid {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Parse `s` into a `T`, the part of a `what` being parsed.
+pub(crate) fn parse<T>(s: &str, what: &str) -> Result<T, CodecError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    s.parse().map_err(|e| CodecError::invalid(format_args!("{}: {:?}: {}", what, s, e)))
+}
+
+/// Parse a `T{term}-N{node_id}` leader id into its term and node id parts.
+pub(crate) fn split_leader_id(s: &str) -> Result<(&str, &str), CodecError> {
+    s.strip_prefix('T')
+        .and_then(|x| x.split_once("-N"))
+        .ok_or_else(|| CodecError::invalid(format_args!("leader id: {:?}", s)))
+}
+
+/// A value encoded into exactly [`ENCODED_LEN`](Self::ENCODED_LEN) bytes.
+pub trait FixedWidthCodec: Sized {
+    /// The number of bytes of an encoded value.
+    const ENCODED_LEN: usize;
+
+    /// Append the encoded value to `buf`.
+    fn encode_to(&self, buf: &mut Vec<u8>);
+
+    /// Decode a value from exactly [`ENCODED_LEN`](Self::ENCODED_LEN) bytes.
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError>;
+
+    /// Encode the value into a new buffer.
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::ENCODED_LEN);
+        self.encode_to(&mut buf);
+        buf
+    }
+}
+
+/// Split `bytes` into the encoding of `T` and the rest.
+fn split<T: FixedWidthCodec>(bytes: &[u8]) -> (&[u8], &[u8]) {
+    bytes.split_at(T::ENCODED_LEN)
+}
+
+fn check_len<T: FixedWidthCodec>(bytes: &[u8]) -> Result<(), CodecError> {
+    if bytes.len() != T::ENCODED_LEN {
+        return Err(CodecError::Length {
+            expected: T::ENCODED_LEN,
+            got: bytes.len(),
+        });
+    }
+    Ok(())
+}
+
+macro_rules! impl_uint {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl FixedWidthCodec for $t {
+                const ENCODED_LEN: usize = size_of::<$t>();
+
+                fn encode_to(&self, buf: &mut Vec<u8>) {
+                    buf.extend_from_slice(&self.to_be_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+                    check_len::<Self>(bytes)?;
+                    // Safe unwrap(): the length is checked.
+                    Ok(<$t>::from_be_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_uint!(u8, u16, u32, u64, u128);
+
+impl FixedWidthCodec for bool {
+    const ENCODED_LEN: usize = 1;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        match u8::decode(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(CodecError::invalid(format_args!("bool byte: {}", b))),
+        }
+    }
+}
+
+/// `None` is encoded as a zero byte followed by zeros, so that it sorts before any `Some`.
+impl<T> FixedWidthCodec for Option<T>
+where T: FixedWidthCodec
+{
+    const ENCODED_LEN: usize = 1 + T::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        match self {
+            None => buf.resize(buf.len() + Self::ENCODED_LEN, 0),
+            Some(v) => {
+                buf.push(1);
+                v.encode_to(buf);
+            }
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len::<Self>(bytes)?;
+        let (is_some, v) = split::<bool>(bytes);
+        if bool::decode(is_some)? {
+            Ok(Some(T::decode(v)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<C> FixedWidthCodec for leader_id_adv::LeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FixedWidthCodec,
+    C::NodeId: FixedWidthCodec,
+{
+    const ENCODED_LEN: usize = C::Term::ENCODED_LEN + C::NodeId::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.term.encode_to(buf);
+        self.node_id.encode_to(buf);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len::<Self>(bytes)?;
+        let (term, node_id) = split::<C::Term>(bytes);
+        Ok(Self {
+            term: C::Term::decode(term)?,
+            node_id: C::NodeId::decode(node_id)?,
+        })
+    }
+}
+
+impl<C> FixedWidthCodec for leader_id_std::LeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FixedWidthCodec,
+    C::NodeId: FixedWidthCodec,
+{
+    const ENCODED_LEN: usize = C::Term::ENCODED_LEN + Option::<C::NodeId>::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.term.encode_to(buf);
+        self.voted_for.encode_to(buf);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len::<Self>(bytes)?;
+        let (term, voted_for) = split::<C::Term>(bytes);
+        Ok(Self {
+            term: C::Term::decode(term)?,
+            voted_for: Option::<C::NodeId>::decode(voted_for)?,
+        })
+    }
+}
+
+impl<C> FixedWidthCodec for leader_id_std::CommittedLeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FixedWidthCodec,
+{
+    const ENCODED_LEN: usize = C::Term::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.term.encode_to(buf);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        Ok(Self::new(C::Term::decode(bytes)?, C::NodeId::default()))
+    }
+}
+
+/// Encoded as the committed leader id followed by the index.
+impl<C> FixedWidthCodec for LogId<C>
+where
+    C: RaftTypeConfig,
+    CommittedLeaderIdOf<C>: FixedWidthCodec,
+{
+    const ENCODED_LEN: usize = CommittedLeaderIdOf::<C>::ENCODED_LEN + u64::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.leader_id.encode_to(buf);
+        self.index.encode_to(buf);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len::<Self>(bytes)?;
+        let (leader_id, index) = split::<CommittedLeaderIdOf<C>>(bytes);
+        Ok(Self::new(
+            CommittedLeaderIdOf::<C>::decode(leader_id)?,
+            u64::decode(index)?,
+        ))
+    }
+}
+
+/// Encoded as the leader id followed by the `committed` flag.
+impl<C> FixedWidthCodec for Vote<C>
+where
+    C: RaftTypeConfig,
+    C::LeaderId: FixedWidthCodec,
+{
+    const ENCODED_LEN: usize = C::LeaderId::ENCODED_LEN + bool::ENCODED_LEN;
+
+    fn encode_to(&self, buf: &mut Vec<u8>) {
+        self.leader_id.encode_to(buf);
+        self.committed.encode_to(buf);
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        check_len::<Self>(bytes)?;
+        let (leader_id, committed) = split::<C::LeaderId>(bytes);
+        Ok(Self {
+            leader_id: C::LeaderId::decode(leader_id)?,
+            committed: bool::decode(committed)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C> SnapshotMeta<C>
+where
+    C: RaftTypeConfig,
+    LogIdOf<C>: FixedWidthCodec,
+{
+    /// Encode this snapshot meta into bytes, to store it along with the snapshot.
+    ///
+    /// The layout is:
+    ///
+    /// ```text
+    /// last_log_id: Option<LogId> | last_membership.log_id: Option<LogId>
+    /// | snapshot_id length: u32 | snapshot_id | JSON encoded membership
+    /// ```
+    ///
+    /// Decode it with [`SnapshotMeta::decode()`].
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.last_log_id.encode_to(&mut buf);
+        self.last_membership.log_id().encode_to(&mut buf);
+
+        (self.snapshot_id.len() as u32).encode_to(&mut buf);
+        buf.extend_from_slice(self.snapshot_id.as_bytes());
+
+        // Safe unwrap(): serializing to a Vec<u8> with the derived Serialize never fails.
+        serde_json::to_writer(&mut buf, self.last_membership.membership()).unwrap();
+        buf
+    }
+
+    /// Decode a snapshot meta encoded by [`SnapshotMeta::encode()`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
+        let mut rest = bytes;
+
+        let last_log_id = decode_next::<Option<LogIdOf<C>>>(&mut rest)?;
+        let membership_log_id = decode_next::<Option<LogIdOf<C>>>(&mut rest)?;
+
+        let len = decode_next::<u32>(&mut rest)? as usize;
+        if rest.len() < len {
+            return Err(CodecError::Length {
+                expected: bytes.len() - rest.len() + len,
+                got: bytes.len(),
+            });
+        }
+        let (snapshot_id, membership) = rest.split_at(len);
+        let snapshot_id = String::from_utf8(snapshot_id.to_vec()).map_err(CodecError::invalid)?;
+
+        let membership = serde_json::from_slice(membership).map_err(CodecError::invalid)?;
+
+        Ok(Self {
+            last_log_id,
+            last_membership: StoredMembership::new(membership_log_id, membership),
+            snapshot_id,
+        })
+    }
+}
+
+/// Decode a `T` from the beginning of `bytes`, and advance `bytes` past it.
+#[cfg(feature = "serde")]
+fn decode_next<T: FixedWidthCodec>(bytes: &mut &[u8]) -> Result<T, CodecError> {
+    if bytes.len() < T::ENCODED_LEN {
+        return Err(CodecError::Length {
+            expected: T::ENCODED_LEN,
+            got: bytes.len(),
+        });
+    }
+    let (v, rest) = split::<T>(bytes);
+    *bytes = rest;
+    T::decode(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::CodecError;
+    use super::FixedWidthCodec;
+    use crate::LogId;
+    use crate::Vote;
+    use crate::declare_raft_types;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::vote::leader_id::leader_id_std;
+
+    declare_raft_types!(
+        StdConfig:
+            D = u64,
+            R = (),
+            LeaderId = leader_id_std::LeaderId<Self>,
+    );
+
+    #[test]
+    fn test_log_id_codec() -> anyhow::Result<()> {
+        let lid = log_id(3, 2, 5);
+
+        let bytes = lid.encode();
+        assert_eq!(LogId::<UTConfig>::ENCODED_LEN, bytes.len());
+        assert_eq!(lid, LogId::<UTConfig>::decode(&bytes)?);
+
+        assert_eq!(
+            Err(CodecError::Length { expected: 24, got: 23 }),
+            LogId::<UTConfig>::decode(&bytes[1..])
+        );
+
+        let lid = LogId::<StdConfig>::new(leader_id_std::CommittedLeaderId::new(3, 0), 5);
+        let bytes = lid.encode();
+        assert_eq!(16, bytes.len());
+        assert_eq!(lid, LogId::<StdConfig>::decode(&bytes)?);
+
+        Ok(())
+    }
+
+    /// Encoded log ids sort in the same order as log ids.
+    #[test]
+    fn test_log_id_codec_order() -> anyhow::Result<()> {
+        let log_ids = [
+            None,
+            Some(log_id(0, 0, 0)),
+            Some(log_id(0, 0, 256)),
+            Some(log_id(0, 1, 1)),
+            Some(log_id(1, 0, 0)),
+            Some(log_id(256, 0, 0)),
+        ];
+
+        let encoded = log_ids.iter().map(|x| x.encode()).collect::<BTreeSet<_>>();
+        assert_eq!(
+            log_ids.iter().map(|x| x.encode()).collect::<Vec<_>>(),
+            encoded.into_iter().collect::<Vec<_>>()
+        );
+
+        for lid in log_ids {
+            assert_eq!(lid, Option::<LogId<UTConfig>>::decode(&lid.encode())?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_parse() -> anyhow::Result<()> {
+        let lid = log_id(3, 2, 5);
+        assert_eq!("T3-N2.5", lid.to_string());
+        assert_eq!(lid, "T3-N2.5".parse()?);
+
+        let lid = LogId::<StdConfig>::new(leader_id_std::CommittedLeaderId::new(3, 0), 5);
+        assert_eq!("3.5", lid.to_string());
+        assert_eq!(lid, "3.5".parse()?);
+
+        for vote in [Vote::<UTConfig>::new(3, 2), Vote::new_committed(3, 2)] {
+            assert_eq!(vote, vote.to_string().parse()?);
+        }
+
+        let vote = Vote::<StdConfig> {
+            leader_id: leader_id_std::LeaderId {
+                term: 3,
+                voted_for: None,
+            },
+            committed: false,
+        };
+        assert_eq!("<T3-NNone:->", vote.to_string());
+        assert_eq!(vote, vote.to_string().parse()?);
+
+        assert_eq!(
+            Err(CodecError::invalid(r#"index: "x": invalid digit found in string"#)),
+            "T3-N2.x".parse::<LogId<UTConfig>>()
+        );
+        assert_eq!(
+            Err(CodecError::invalid(r#"leader id: "3-N2""#)),
+            "3-N2.5".parse::<LogId<UTConfig>>()
+        );
+        assert_eq!(
+            Err(CodecError::invalid(r#"vote: "<T3-N2:X>""#)),
+            "<T3-N2:X>".parse::<Vote<UTConfig>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vote_codec() -> anyhow::Result<()> {
+        for vote in [Vote::<UTConfig>::new(3, 2), Vote::new_committed(3, 2)] {
+            assert_eq!(vote, Vote::decode(&vote.encode())?);
+        }
+
+        for vote in [Vote::<StdConfig>::new(3, 2), Vote::new_committed(3, 2), Vote {
+            leader_id: leader_id_std::LeaderId {
+                term: 3,
+                voted_for: None,
+            },
+            committed: false,
+        }] {
+            assert_eq!(vote, Vote::decode(&vote.encode())?);
+        }
+
+        let mut bytes = Vote::<UTConfig>::new(3, 2).encode();
+        *bytes.last_mut().unwrap() = 2;
+        assert_eq!(
+            Err(CodecError::invalid("bool byte: 2")),
+            Vote::<UTConfig>::decode(&bytes)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_snapshot_meta_codec() -> anyhow::Result<()> {
+        use maplit::btreeset;
+
+        use crate::Membership;
+        use crate::StoredMembership;
+        use crate::storage::SnapshotMeta;
+
+        let meta = SnapshotMeta::<UTConfig> {
+            last_log_id: Some(log_id(3, 2, 5)),
+            last_membership: StoredMembership::new(
+                Some(log_id(1, 2, 3)),
+                Membership::new_with_defaults(vec![btreeset! {1,2,3}], btreeset! {4}),
+            ),
+            snapshot_id: "snap-1".to_string(),
+        };
+
+        let bytes = meta.encode();
+        assert_eq!(meta, SnapshotMeta::decode(&bytes)?);
+
+        assert!(matches!(
+            SnapshotMeta::<UTConfig>::decode(&bytes[..55]),
+            Err(CodecError::Length { .. })
+        ));
+
+        let meta = SnapshotMeta::<UTConfig>::default();
+        assert_eq!(meta, SnapshotMeta::decode(&meta.encode())?);
+
+        Ok(())
+    }
+}
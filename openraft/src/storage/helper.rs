@@ -19,9 +19,14 @@ use crate::display_ext::DisplayOptionExt;
 use crate::engine::LogIdList;
 use crate::entry::RaftEntry;
 use crate::entry::RaftPayload;
+use crate::error::Fatal;
+use crate::error::StorageStampMismatch;
 use crate::raft_state::IOState;
+use crate::storage::MembershipSource;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
+use crate::storage::RecoveryReport;
+use crate::storage::StorageStamp;
 use crate::storage::log_reader_ext::RaftLogReaderExt;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::LogIdOf;
@@ -77,20 +82,49 @@ where
         self
     }
 
-    // TODO: let RaftStore store node-id.
-    //       To achieve this, RaftLogStorage must store node-id
-    //       To achieve this, RaftLogStorage has to provide API to initialize with a node id and API to
-    //       read node-id
+    /// Check that the storage belongs to the node identified by `stamp`.
+    ///
+    /// If the storage has no stamp yet, `stamp` is saved. Otherwise it returns
+    /// [`Fatal::StorageStampMismatch`] if the saved stamp differs from `stamp`.
+    #[since(version = "0.10.0")]
+    pub async fn check_stamp(&mut self, stamp: StorageStamp<C>) -> Result<(), Fatal<C>> {
+        let found = self.log_store.read_stamp().await?;
+
+        let Some(found) = found else {
+            tracing::info!(stamp = display(&stamp), "save storage stamp");
+            self.log_store.save_stamp(&stamp).await?;
+            return Ok(());
+        };
+
+        if found != stamp {
+            tracing::error!(
+                expected = display(&stamp),
+                found = display(&found),
+                "storage stamp mismatch"
+            );
+            return Err(StorageStampMismatch::new(stamp, found).into());
+        }
+
+        Ok(())
+    }
+
     /// Get Raft's state information from storage.
     ///
     /// When the Raft node is first started, it will call this interface to fetch the last known
     /// state from stable storage.
     pub async fn get_initial_state(&mut self) -> Result<RaftState<C>, StorageError<C>> {
+        let (state, _report) = self.recover().await?;
+        Ok(state)
+    }
+
+    /// Get Raft's state information from storage, and a report of what is recovered.
+    pub(crate) async fn recover(&mut self) -> Result<(RaftState<C>, RecoveryReport<C>), StorageError<C>> {
         let mut log_reader = self.log_store.get_log_reader().await;
         let vote = log_reader.read_vote().await?;
         let vote = vote.unwrap_or_default();
 
         let mut committed = self.log_store.read_committed().await?;
+        let saved_committed = committed.clone();
 
         let st = self.log_store.get_log_state().await?;
         let mut last_purged_log_id = st.last_purged_log_id;
@@ -114,9 +148,11 @@ where
         }
 
         // For transient state machines: install persistent snapshot to restore state efficiently.
-        self.restore_from_snapshot().await?;
+        let snapshot_installed = self.restore_from_snapshot().await?;
         let (mut last_applied, _) = self.state_machine.applied_state().await?;
 
+        let mut reapplied_logs = 0;
+
         // Re-apply log entries to recover SM to latest state.
         // For transient state machines, this re-applies logs from snapshot position to committed.
         if last_applied < committed {
@@ -140,6 +176,7 @@ where
             );
 
             self.reapply_committed(start, end).await?;
+            reapplied_logs = end - start;
 
             last_applied = committed.clone();
         }
@@ -169,6 +206,7 @@ where
         let log_id_list = self.get_key_log_ids(last_purged_log_id.clone(), last_log_id.clone()).await?;
 
         let snapshot = self.state_machine.get_current_snapshot().await?;
+        let snapshot_rebuilt = snapshot.is_none() && last_purged_log_id.is_some();
 
         // If there is not a snapshot and there are logs purged, which means the snapshot is not persisted,
         // we just rebuild it so that replication can use it.
@@ -184,7 +222,32 @@ where
             }
             s @ Some(_) => s,
         };
-        let snapshot_meta = snapshot.map(|x| x.meta).unwrap_or_default();
+        let snapshot = snapshot.map(|x| x.meta);
+
+        let membership = mem_state.effective().stored_membership().as_ref().clone();
+        let membership_source = if membership.log_id().is_none() {
+            MembershipSource::Empty
+        } else if membership.log_id() > &last_applied {
+            MembershipSource::Log
+        } else {
+            MembershipSource::StateMachine
+        };
+
+        let report = RecoveryReport {
+            vote: vote.clone(),
+            committed: saved_committed,
+            last_log_id: last_log_id.clone(),
+            purged: last_purged_log_id.clone(),
+            last_applied: last_applied.clone(),
+            snapshot_installed,
+            reapplied_logs,
+            snapshot: snapshot.clone(),
+            snapshot_rebuilt,
+            membership,
+            membership_source,
+        };
+
+        let snapshot_meta = snapshot.unwrap_or_default();
 
         let io_state = IOState::new(
             &self.id,
@@ -197,7 +260,7 @@ where
 
         let now = C::now();
 
-        Ok(RaftState {
+        let state = RaftState {
             // The initial value for `vote` is the minimal possible value.
             // See: [Conditions for initialization][precondition]
             //
@@ -217,19 +280,24 @@ where
             server_state: Default::default(),
             io_state: Valid::new(io_state),
             purge_upto: last_purged_log_id,
-        })
+            archive_next: None,
+        };
+
+        Ok((state, report))
     }
 
     /// Restore state machine by installing snapshot if available and newer than last_applied.
     ///
     /// For transient state machines, this installs the last persistent snapshot to efficiently
     /// restore the state machine to a recent position.
-    async fn restore_from_snapshot(&mut self) -> Result<(), StorageError<C>> {
+    ///
+    /// Returns `true` if the snapshot is installed.
+    async fn restore_from_snapshot(&mut self) -> Result<bool, StorageError<C>> {
         let (last_applied, _) = self.state_machine.applied_state().await?;
         let snapshot = self.state_machine.get_current_snapshot().await?;
 
         let Some(snap) = snapshot else {
-            return Ok(());
+            return Ok(false);
         };
 
         if snap.meta.last_log_id > last_applied {
@@ -245,9 +313,10 @@ where
                 new_last_applied = display(snap.meta.last_log_id.display()),
                 "Snapshot installed, state machine restored to snapshot position"
             );
+            return Ok(true);
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Read log entries from [`RaftLogReader`] in chunks and apply them to the state machine.
@@ -0,0 +1,27 @@
+use std::io;
+
+use crate::OptionalSend;
+use crate::RaftTypeConfig;
+use crate::base::BoxFuture;
+
+/// Copies the log entries that are about to be purged to external storage, such as an object
+/// store, so that the full history is retained for auditing or point-in-time recovery.
+///
+/// It is set with [`Raft::set_log_archiver()`]. A task outside `RaftCore` reads the entries
+/// scheduled to purge from the log store and passes them to [`archive()`](Self::archive), in
+/// index order and in chunks of at most [`Config::max_payload_entries`] entries, so that a slow
+/// archiver does not block `RaftCore`. A log is purged only after it is archived. If archiving
+/// fails, the logs are kept, and the chunk is archived again after a short delay. Thus an entry
+/// may be passed more than once, and the archiver should overwrite an entry it already has.
+///
+/// Entries that are not in the local log store, e.g., those covered by a snapshot installed from
+/// the leader, are not archived.
+///
+/// [`Raft::set_log_archiver()`]: crate::Raft::set_log_archiver
+/// [`Config::max_payload_entries`]: crate::Config::max_payload_entries
+pub trait LogArchiver<C>: OptionalSend + 'static
+where C: RaftTypeConfig
+{
+    /// Copy `entries`, which are consecutive and not empty, to external storage.
+    fn archive(&mut self, entries: Vec<C::Entry>) -> BoxFuture<'_, Result<(), io::Error>>;
+}
@@ -14,6 +14,10 @@
 //! - [`LogState`] - Current state of log storage (first/last log IDs)
 //! - [`Snapshot`] - Container for snapshot data and metadata
 //! - [`SnapshotMeta`] - Snapshot metadata (last log ID, membership)
+//! - [`StorageStamp`] - The cluster and node a storage belongs to
+//! - [`AppIndexStore`] - Application keys published by the state machine and their log ids
+//!
+//! The [`codec`] module encodes log ids, votes and snapshot metas for persisting them.
 //!
 //! ## Usage
 //!
@@ -25,25 +29,40 @@
 //! [State Machine Component](crate::docs::components::state_machine) documentation
 //! for implementation details and examples.
 
+mod app_index;
 mod callback;
+pub mod codec;
 mod helper;
+mod log_archiver;
 mod log_reader_ext;
 mod log_state;
+mod point_in_time;
+mod recovery_report;
+mod seed;
 mod snapshot;
 mod snapshot_meta;
 mod snapshot_signature;
+mod storage_stamp;
 mod v2;
 
+pub use self::app_index::AppIndexStore;
+pub use self::app_index::MemAppIndexStore;
 pub use self::callback::IOFlushed;
 pub use self::callback::LogApplied;
 #[allow(deprecated)]
 pub use self::callback::LogFlushed;
 pub use self::helper::StorageHelper;
+pub use self::log_archiver::LogArchiver;
 pub use self::log_reader_ext::RaftLogReaderExt;
 pub use self::log_state::LogState;
+pub use self::point_in_time::restore_state_machine;
+pub use self::recovery_report::MembershipSource;
+pub use self::recovery_report::RecoveryReport;
+pub use self::seed::seed_from_snapshot;
 pub use self::snapshot::Snapshot;
 pub use self::snapshot_meta::SnapshotMeta;
 pub use self::snapshot_signature::SnapshotSignature;
+pub use self::storage_stamp::StorageStamp;
 pub use self::v2::RaftLogReader;
 pub use self::v2::RaftLogStorage;
 pub use self::v2::RaftLogStorageExt;
@@ -0,0 +1,110 @@
+use anyerror::AnyError;
+use openraft_macros::since;
+
+use crate::LogIdOptionExt;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::entry::RaftEntry;
+use crate::storage::RaftStateMachine;
+use crate::storage::Snapshot;
+use crate::type_config::alias::LogIdOf;
+
+/// The max number of entries passed to [`RaftStateMachine::apply()`] at a time.
+const APPLY_BATCH: usize = 1024;
+
+/// Rebuild the state of `state_machine` as of log id `upto`, from a snapshot and archived logs,
+/// without a running Raft node.
+///
+/// If `snapshot` is given, it is installed first. Otherwise, the state machine continues from its
+/// own last applied log id. Then the entries following it, in index order, are applied up to and
+/// including `upto`. `entries` is usually the concatenation of the segments shipped by a
+/// [`LogArchiver`](crate::storage::LogArchiver): entries that are already applied, e.g., archived
+/// more than once, are skipped, and the entries after `upto` are not read.
+///
+/// It returns an error if the snapshot or the state machine is newer than `upto`, if an entry is
+/// missing, or if the entry at the index of `upto` has a different log id. The state machine may
+/// have applied some entries when an error is returned.
+///
+/// ```ignore
+/// let mut sm = MyStateMachine::new_in_memory();
+/// let entries = archive.segments().flat_map(|seg| seg.entries());
+/// restore_state_machine(&mut sm, Some(snapshot), entries, &audit_log_id).await?;
+/// ```
+#[since(version = "0.10.0")]
+pub async fn restore_state_machine<C, SM, I>(
+    state_machine: &mut SM,
+    snapshot: Option<Snapshot<C>>,
+    entries: I,
+    upto: &LogIdOf<C>,
+) -> Result<(), StorageError<C>>
+where
+    C: RaftTypeConfig,
+    SM: RaftStateMachine<C>,
+    I: IntoIterator<Item = C::Entry>,
+{
+    if let Some(snapshot) = snapshot {
+        if snapshot.meta.last_log_id.index() > Some(upto.index()) {
+            return Err(StorageError::read_snapshot(
+                Some(snapshot.meta.signature()),
+                AnyError::error(format!("snapshot is newer than the log id to restore to: {}", upto)),
+            ));
+        }
+        state_machine.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+    }
+
+    let (applied, _) = state_machine.applied_state().await?;
+
+    if applied.index() > Some(upto.index()) {
+        return Err(StorageError::read_state_machine(AnyError::error(format!(
+            "state machine is applied beyond the log id to restore to: {}",
+            upto
+        ))));
+    }
+
+    if applied.as_ref() == Some(upto) {
+        return Ok(());
+    }
+
+    let mut next = applied.next_index();
+    let mut batch = Vec::with_capacity(APPLY_BATCH);
+
+    for entry in entries {
+        let index = entry.index();
+
+        if index < next {
+            continue;
+        }
+
+        if index > next {
+            return Err(StorageError::read_log_at_index(
+                next,
+                AnyError::error("missing in the entries to restore from"),
+            ));
+        }
+
+        if index == upto.index() {
+            if &entry.log_id() != upto {
+                return Err(StorageError::read_log_entry(
+                    upto.clone(),
+                    AnyError::error(format!("found a different log id: {}", entry.log_id())),
+                ));
+            }
+
+            batch.push(entry);
+            state_machine.apply(batch).await?;
+            return Ok(());
+        }
+
+        batch.push(entry);
+        next += 1;
+
+        if batch.len() >= APPLY_BATCH {
+            state_machine.apply(std::mem::replace(&mut batch, Vec::with_capacity(APPLY_BATCH))).await?;
+        }
+    }
+
+    Err(StorageError::read_log_at_index(
+        next,
+        AnyError::error("missing in the entries to restore from"),
+    ))
+}
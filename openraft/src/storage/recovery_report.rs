@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+use crate::StoredMembership;
+use crate::display_ext::DisplayOptionExt;
+use crate::storage::SnapshotMeta;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+
+/// Where the effective membership of a node is recovered from at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MembershipSource {
+    /// A membership log entry that is not yet applied to the state machine.
+    Log,
+
+    /// The last membership applied to the state machine, or installed with a snapshot.
+    StateMachine,
+
+    /// No membership is found: the node is not initialized, nor added to a cluster.
+    Empty,
+}
+
+impl fmt::Display for MembershipSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MembershipSource::Log => write!(f, "log"),
+            MembershipSource::StateMachine => write!(f, "state-machine"),
+            MembershipSource::Empty => write!(f, "empty"),
+        }
+    }
+}
+
+/// The state a Raft node recovered from its storage when it started.
+///
+/// Returned by [`Raft::recovery_report()`](crate::Raft::recovery_report), so that an application
+/// can log the starting state of a node in a single line, and refuse to go on with a state it
+/// considers suspicious, e.g., a node that is expected to have data but recovered nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct RecoveryReport<C>
+where C: RaftTypeConfig
+{
+    /// The vote read from the log store.
+    pub vote: VoteOf<C>,
+
+    /// The committed log id read from the log store, if it is saved.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The last log id in the log store.
+    pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The last purged log id in the log store.
+    pub purged: Option<LogIdOf<C>>,
+
+    /// The last log id applied to the state machine, after it is restored.
+    pub last_applied: Option<LogIdOf<C>>,
+
+    /// Whether the current snapshot is installed to restore a state machine that is behind it.
+    pub snapshot_installed: bool,
+
+    /// Number of committed log entries applied again to restore the state machine.
+    pub reapplied_logs: u64,
+
+    /// The meta of the current snapshot, or `None` if there is no snapshot.
+    pub snapshot: Option<SnapshotMeta<C>>,
+
+    /// Whether the snapshot is built at startup, because there are purged logs but no snapshot.
+    pub snapshot_rebuilt: bool,
+
+    /// The effective membership.
+    pub membership: StoredMembership<C>,
+
+    /// Where the effective membership is recovered from.
+    pub membership_source: MembershipSource,
+}
+
+impl<C> fmt::Display for RecoveryReport<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RecoveryReport{{vote: {}, committed: {}, last_log_id: {}, purged: {}, last_applied: {}, \
+             snapshot_installed: {}, reapplied_logs: {}, snapshot: {}, snapshot_rebuilt: {}, \
+             membership: {} from {}}}",
+            self.vote,
+            self.committed.display(),
+            self.last_log_id.display(),
+            self.purged.display(),
+            self.last_applied.display(),
+            self.snapshot_installed,
+            self.reapplied_logs,
+            self.snapshot.display(),
+            self.snapshot_rebuilt,
+            self.membership,
+            self.membership_source,
+        )
+    }
+}
@@ -0,0 +1,77 @@
+use anyerror::AnyError;
+use openraft_macros::since;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::display_ext::DisplayOptionExt;
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftStateMachine;
+use crate::storage::Snapshot;
+
+/// Seed the storage of a new node with a snapshot, before the node is started for the first time.
+///
+/// The snapshot is usually copied to the new node out of band, e.g., with rsync or from an object
+/// storage, so that a multi-GB state does not have to be streamed through the leader. The snapshot
+/// is installed into `state_machine` and the logs included in it are marked as purged in
+/// `log_store`. When the node is then started with [`Raft::new()`](crate::Raft::new) and added as a
+/// learner, the leader only replicates the logs after the snapshot, as long as it has not purged
+/// them.
+///
+/// It returns an error if the snapshot is empty, or if the storage is not pristine: a vote is
+/// saved, a log is written, or the state machine has applied a log.
+///
+/// ```ignore
+/// let (meta, data) = load_snapshot_file("/data/seed.snap")?;
+/// seed_from_snapshot(&mut log_store, &mut sm, Snapshot::new(meta, data)).await?;
+/// let raft = Raft::new(id, config, network, log_store, sm).await?;
+/// ```
+#[since(version = "0.10.0")]
+pub async fn seed_from_snapshot<C, LS, SM>(
+    log_store: &mut LS,
+    state_machine: &mut SM,
+    snapshot: Snapshot<C>,
+) -> Result<(), StorageError<C>>
+where
+    C: RaftTypeConfig,
+    LS: RaftLogStorage<C>,
+    SM: RaftStateMachine<C>,
+{
+    let Some(last_log_id) = snapshot.meta.last_log_id.clone() else {
+        return Err(StorageError::read_snapshot(
+            Some(snapshot.meta.signature()),
+            AnyError::error("can not seed from a snapshot without logs"),
+        ));
+    };
+
+    let mut log_reader = log_store.get_log_reader().await;
+    if let Some(vote) = log_reader.read_vote().await? {
+        return Err(StorageError::read_vote(AnyError::error(format!(
+            "can not seed a storage with a saved vote: {}",
+            vote
+        ))));
+    }
+
+    let st = log_store.get_log_state().await?;
+    if st.last_log_id.is_some() {
+        return Err(StorageError::read_logs(AnyError::error(format!(
+            "can not seed a storage with logs: last_log_id: {}",
+            st.last_log_id.display()
+        ))));
+    }
+
+    let (applied, _) = state_machine.applied_state().await?;
+    if applied.is_some() {
+        return Err(StorageError::read_state_machine(AnyError::error(format!(
+            "can not seed a state machine with applied logs: last_applied: {}",
+            applied.display()
+        ))));
+    }
+
+    tracing::info!(snapshot = display(&snapshot.meta), "seed storage from snapshot");
+
+    state_machine.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+    log_store.purge(last_log_id).await?;
+
+    Ok(())
+}
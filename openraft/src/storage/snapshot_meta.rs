@@ -1,5 +1,6 @@
 use std::fmt;
 
+use crate::Membership;
 use crate::RaftTypeConfig;
 use crate::SnapshotId;
 use crate::StoredMembership;
@@ -59,4 +60,18 @@ where C: RaftTypeConfig
     pub fn last_log_id(&self) -> Option<&LogIdOf<C>> {
         self.last_log_id.as_ref()
     }
+
+    /// Rewrite this metadata to seed a new cluster of `membership`.
+    ///
+    /// The last log id and the log id of the membership are reset to the very first log id, the
+    /// one [`Raft::initialize()`](crate::Raft::initialize) writes the initial membership at, so
+    /// that the new cluster starts its terms and log indexes over from the snapshot.
+    pub(crate) fn into_bootstrap(self, membership: Membership<C>) -> Self {
+        let first = LogIdOf::<C>::default();
+        Self {
+            last_log_id: Some(first.clone()),
+            last_membership: StoredMembership::new(Some(first), membership),
+            snapshot_id: self.snapshot_id,
+        }
+    }
 }
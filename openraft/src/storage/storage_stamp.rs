@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::RaftTypeConfig;
+
+/// Identifies the cluster and the node a storage belongs to.
+///
+/// Openraft saves it with [`RaftLogStorage::save_stamp`] the first time a storage is used, and
+/// checks it on every following startup, so that a data directory copied to the wrong node, or
+/// to a node of another cluster, is refused before it takes part in consensus.
+///
+/// [`RaftLogStorage::save_stamp`]: crate::storage::RaftLogStorage::save_stamp
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct StorageStamp<C>
+where C: RaftTypeConfig
+{
+    /// The [`Config::cluster_name`](crate::Config::cluster_name) of the cluster.
+    pub cluster_name: String,
+
+    /// The id of the node that owns the storage.
+    pub node_id: C::NodeId,
+}
+
+impl<C> StorageStamp<C>
+where C: RaftTypeConfig
+{
+    /// Create a new stamp for node `node_id` in cluster `cluster_name`.
+    pub fn new(cluster_name: impl ToString, node_id: C::NodeId) -> Self {
+        Self {
+            cluster_name: cluster_name.to_string(),
+            node_id,
+        }
+    }
+}
+
+impl<C> fmt::Display for StorageStamp<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.cluster_name, self.node_id)
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 use std::ops::RangeBounds;
 use std::ops::RangeInclusive;
 
+use anyerror::AnyError;
 use openraft_macros::add_async_trait;
 use openraft_macros::since;
 
@@ -74,6 +75,55 @@ where C: RaftTypeConfig
         self.try_get_log_entries(start..end).await
     }
 
+    /// Returns a copy of a log entry read by this reader, or `None` if it can not be copied.
+    ///
+    /// A leader caches the log entries it reads for replication only if they can be copied, so
+    /// that lagging followers are served without reading the same logs again. See
+    /// [`Config::replication_cache_entries`](crate::Config::replication_cache_entries).
+    ///
+    /// The default implementation returns `None`, which disables the cache. An implementation
+    /// whose entry type is `Clone` can just return `Some(entry.clone())`.
+    #[since(version = "0.10.0")]
+    fn clone_entry(&self, entry: &C::Entry) -> Option<C::Entry> {
+        let _ = entry;
+        None
+    }
+
+    /// Returns the size in bytes of a log entry, or `None` if it is unknown.
+    ///
+    /// The size is checked against [`Config::max_entry_size`] when a client write is proposed,
+    /// and counted against [`Config::max_payload_bytes`] when log entries are batched into an
+    /// `AppendEntries` request. It only needs to be an estimate, such as the serialized size.
+    ///
+    /// The default implementation returns `None`, with which neither limit applies: `Raft::new()`
+    /// logs a warning if either of them is set.
+    ///
+    /// [`Config::max_entry_size`]: crate::Config::max_entry_size
+    /// [`Config::max_payload_bytes`]: crate::Config::max_payload_bytes
+    #[since(version = "0.10.0")]
+    fn entry_size(&self, entry: &C::Entry) -> Option<u64> {
+        let _ = entry;
+        None
+    }
+
+    /// Returns a checksum of a log entry, or `None` if it is unknown.
+    ///
+    /// The leader sends the checksums of committed log ranges to followers, which compare them with
+    /// the checksums of their own copies to detect a diverged or corrupted log. See
+    /// [`Config::log_checksum_interval`].
+    ///
+    /// The checksum must be computed from the content of the entry, including its log id, in the
+    /// same way on every node, e.g., a CRC of the serialized entry.
+    ///
+    /// The default implementation returns `None`, with which logs are not verified.
+    ///
+    /// [`Config::log_checksum_interval`]: crate::Config::log_checksum_interval
+    #[since(version = "0.10.0")]
+    fn entry_checksum(&self, entry: &C::Entry) -> Option<u64> {
+        let _ = entry;
+        None
+    }
+
     /// Retrieves a list of key log ids that mark the beginning of each Leader.
     ///
     /// This method returns log entries that represent leadership transitions in the log history,
@@ -115,4 +165,50 @@ where C: RaftTypeConfig
     async fn get_key_log_ids(&mut self, range: RangeInclusive<LogIdOf<C>>) -> Result<Vec<LogIdOf<C>>, StorageError<C>> {
         LogIdList::get_key_log_ids(range, self).await
     }
+
+    /// Appends a local entry and returns the sequence number assigned to it.
+    ///
+    /// A local entry is written by the application with
+    /// [`Raft::append_local_entry()`](crate::Raft::append_local_entry), for node-local bookkeeping,
+    /// e.g., a local index. It is not a Raft log entry: it is not replicated, it does not take a
+    /// log index, and it is never read by Openraft.
+    ///
+    /// Local entries are accessed with a reader returned by [`RaftLogStorage::get_log_reader()`],
+    /// in a task outside `RaftCore`, so that a slow local entry IO does not delay Raft. Thus a
+    /// reader that supports them must share them with the other readers of the same log store.
+    ///
+    /// # Optional feature
+    ///
+    /// By default local entries are not supported and this method returns an error.
+    ///
+    /// ### To ensure correctness:
+    ///
+    /// - Sequence numbers must be increasing, including across restarts and after
+    ///   [`Self::purge_local`].
+    /// - The entry must be persisted on disk before returning.
+    ///
+    /// [`RaftLogStorage::get_log_reader()`]: crate::storage::RaftLogStorage::get_log_reader
+    #[since(version = "0.10.0")]
+    async fn append_local(&mut self, data: Vec<u8>) -> Result<u64, StorageError<C>> {
+        let _ = data;
+        Err(StorageError::write_logs(AnyError::error(
+            "local entries are not supported by this log store",
+        )))
+    }
+
+    /// Returns at most `max` local entries with a sequence number no less than `start`, as
+    /// `(seq, data)` in sequence order.
+    #[since(version = "0.10.0")]
+    async fn read_local(&mut self, start: u64, max: u64) -> Result<Vec<(u64, Vec<u8>)>, StorageError<C>> {
+        // By default local entries are not supported and there is none.
+        let _ = (start, max);
+        Ok(vec![])
+    }
+
+    /// Removes the local entries with a sequence number up to `upto`, inclusive.
+    #[since(version = "0.10.0")]
+    async fn purge_local(&mut self, upto: u64) -> Result<(), StorageError<C>> {
+        let _ = upto;
+        Ok(())
+    }
 }
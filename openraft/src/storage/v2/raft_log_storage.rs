@@ -7,6 +7,7 @@ use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::storage::IOFlushed;
 use crate::storage::LogState;
+use crate::storage::StorageStamp;
 use crate::type_config::alias::LogIdOf;
 use crate::type_config::alias::VoteOf;
 
@@ -84,6 +85,33 @@ where C: RaftTypeConfig
         Ok(None)
     }
 
+    /// Saves the stamp identifying the cluster and the node this storage belongs to.
+    ///
+    /// Openraft calls it once, when [`Self::read_stamp`] returns `None` on startup.
+    ///
+    /// # Optional feature
+    ///
+    /// If the stamp is not saved, Openraft can not detect a storage that is started by the wrong
+    /// node, e.g., a data directory copied to another node. See [`StorageStamp`].
+    ///
+    /// ### To ensure correctness:
+    ///
+    /// The stamp must be persisted on disk before returning.
+    async fn save_stamp(&mut self, _stamp: &StorageStamp<C>) -> Result<(), StorageError<C>> {
+        // By default the stamp is not saved
+        Ok(())
+    }
+
+    /// Return the stamp saved by [`Self::save_stamp`].
+    ///
+    /// On startup, if it differs from the node id and the cluster name the node is started with,
+    /// [`Raft::new()`](crate::Raft::new) fails with
+    /// [`Fatal::StorageStampMismatch`](crate::error::Fatal::StorageStampMismatch).
+    async fn read_stamp(&mut self) -> Result<Option<StorageStamp<C>>, StorageError<C>> {
+        // By default the stamp is not saved and this method just returns None.
+        Ok(None)
+    }
+
     /// Append log entries and call the `callback` once logs are persisted on disk.
     ///
     /// It should return immediately after saving the input log entries in memory and calls the
@@ -102,6 +130,11 @@ where C: RaftTypeConfig
     ///
     /// - There must not be a **hole** in logs. Because Raft only examines the last log id to ensure
     ///   correctness.
+    ///
+    /// - When the `callback` is called, every [`truncate()`] called before this `append()` must be
+    ///   persisted too. See [`truncate()`].
+    ///
+    /// [`truncate()`]: Self::truncate
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<C>) -> Result<(), StorageError<C>>
     where
         I: IntoIterator<Item = C::Entry> + OptionalSend,
@@ -109,9 +142,30 @@ where C: RaftTypeConfig
 
     /// Truncate logs since `log_id`, inclusive
     ///
+    /// When a follower receives entries that conflict with its local logs, Openraft calls
+    /// `truncate()` to remove the conflicting entries, and then [`append()`] to write the new
+    /// ones. Openraft waits for `truncate()` to return before calling [`append()`], but does not
+    /// wait for the truncation to be persisted: the callback of the following [`append()`] is the
+    /// barrier for both.
+    ///
     /// ### To ensure correctness:
     ///
     /// - It must not leave a **hole** in logs.
+    ///
+    /// - When this method returns, the removed entries must not be readable.
+    ///
+    /// - The truncation does not have to be persisted when this method returns, but it must be
+    ///   persisted no later than the entries of the next [`append()`], i.e., when the callback of
+    ///   the next [`append()`] is called. Otherwise, after a crash, the store may contain the new
+    ///   entries followed by stale conflicting entries that were not truncated, and a stale entry
+    ///   may be mistaken as replicated.
+    ///
+    ///   A store that writes both operations to the same write-ahead log in order, and flushes the
+    ///   log before calling the append callback, satisfies this. The test suite
+    ///   [`Suite::crash_recovery`] checks it if the store can be reopened.
+    ///
+    /// [`append()`]: Self::append
+    /// [`Suite::crash_recovery`]: crate::testing::log::Suite::crash_recovery
     async fn truncate(&mut self, log_id: LogIdOf<C>) -> Result<(), StorageError<C>>;
 
     /// Purge logs up to `log_id`, inclusive
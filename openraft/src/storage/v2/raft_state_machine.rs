@@ -7,9 +7,12 @@ use crate::RaftSnapshotBuilder;
 use crate::RaftTypeConfig;
 use crate::StorageError;
 use crate::StoredMembership;
+use crate::storage::AppIndexStore;
+use crate::storage::MemAppIndexStore;
 use crate::storage::Snapshot;
 use crate::storage::SnapshotMeta;
 use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
 
 /// API for state machine and snapshot.
 ///
@@ -133,9 +136,14 @@ where C: RaftTypeConfig
     ///
     /// Before this method returns:
     /// - The state machine should be replaced with the new contents of the snapshot,
+    /// - the last applied log id and the last membership should be set to `meta.last_log_id` and
+    ///   `meta.last_membership`, i.e., [`Self::applied_state`] should return them,
     /// - the input snapshot should be saved, i.e., [`Self::get_current_snapshot`] should return it.
     /// - and all other snapshots should be deleted at this point.
     ///
+    /// Restoring `meta.last_membership` is required: a node that is restored only from a
+    /// snapshot, with no logs, learns the cluster membership from the state machine.
+    ///
     /// ### snapshot
     ///
     /// A snapshot created from an earlier call to `begin_receiving_snapshot` which provided the
@@ -161,4 +169,123 @@ where C: RaftTypeConfig
     /// last-applied-membership config as part of the snapshot, which should be decoded for
     /// creating this method's response data.
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<C>>, StorageError<C>>;
+
+    /// Returns a copy of a snapshot returned by [`Self::get_current_snapshot`], or `None` if it
+    /// can not be copied.
+    ///
+    /// When several followers need a snapshot at the same time, a leader gets the current
+    /// snapshot once and sends each of them a copy, instead of calling `get_current_snapshot()`
+    /// for every follower. The snapshot is kept until a newer one is needed or a snapshot is
+    /// installed.
+    ///
+    /// The copy must be readable from the start, e.g., a new reader of the same snapshot file.
+    ///
+    /// The default implementation returns `None`, and the current snapshot is read for every
+    /// follower.
+    #[since(version = "0.10.0")]
+    fn clone_snapshot(&self, snapshot: &Snapshot<C>) -> Option<Snapshot<C>> {
+        let _ = snapshot;
+        None
+    }
+
+    /// Returns the checksum of the state machine when the last applied log id is `at_log_id`, or
+    /// `None` if it is not supported.
+    ///
+    /// Openraft calls it right after `at_log_id` is applied and before any later entry is applied,
+    /// so that every replica computes it on the same state. Replicas whose state machines return
+    /// different checksums for the same `at_log_id` have diverged. See
+    /// [`Raft::state_machine_checksums`].
+    ///
+    /// The checksum must only depend on the state that is replicated, e.g., not on the order
+    /// entries of a hash map are iterated in.
+    ///
+    /// The default implementation returns `None`.
+    ///
+    /// [`Raft::state_machine_checksums`]: crate::Raft::state_machine_checksums
+    #[since(version = "0.10.0")]
+    async fn checksum(&mut self, at_log_id: &LogIdOf<C>) -> Result<Option<u64>, StorageError<C>> {
+        let _ = at_log_id;
+        Ok(None)
+    }
+
+    /// Return the store of the application key index, called once when Raft starts.
+    ///
+    /// The default is a [`MemAppIndexStore`]. A state machine that needs lookups to survive a
+    /// restart returns a persistent store: logs applied before the restart are not applied again,
+    /// so their keys are not published again.
+    #[since(version = "0.10.0")]
+    fn app_index_store(&mut self) -> Box<dyn AppIndexStore<C>> {
+        Box::new(MemAppIndexStore::default())
+    }
+
+    /// Set the fencing token of the entries passed to the following [`Self::apply`] calls.
+    ///
+    /// Openraft calls it before applying every batch of entries, with the vote of this node at
+    /// the time the batch is scheduled: on the leader it is the leader's own vote, on a follower it
+    /// is usually the vote of the leader it follows. The vote is made of the term, the leader id
+    /// and whether it is committed by a quorum, and it never decreases.
+    ///
+    /// A state machine that causes side effects in an external system, e.g., writing objects to
+    /// S3, attaches the token to its requests. The external system rejects a request with a token
+    /// smaller than the greatest one it has seen, so that a stale leader, which has been replaced
+    /// but has not learned about it yet, can not overwrite the effects of the new one.
+    ///
+    /// The default ignores the token.
+    #[since(version = "0.10.0")]
+    fn set_fencing_token(&mut self, vote: VoteOf<C>) {
+        let _ = vote;
+    }
+
+    /// Take the application keys published by the last [`Self::apply`] call.
+    ///
+    /// Openraft calls it after every `apply()` and inserts every `(key, log_id)` into the
+    /// [`AppIndexStore`], so that [`Raft::lookup_app_key`] returns `log_id` for `key`, e.g., the
+    /// log id a transaction id is committed at. A state machine publishes a key by buffering it in
+    /// `apply()` and returning it here.
+    ///
+    /// A key is visible to lookups before the applied log id is reported in metrics.
+    ///
+    /// The default publishes no key.
+    ///
+    /// [`Raft::lookup_app_key`]: crate::Raft::lookup_app_key
+    #[since(version = "0.10.0")]
+    fn take_app_index_keys(&mut self) -> Vec<(String, LogIdOf<C>)> {
+        Vec::new()
+    }
+
+    /// Take the results computed by the last [`Self::apply`] call, to stream them to followers.
+    ///
+    /// Openraft calls it after every `apply()` if [`Config::apply_results_wait`] is enabled. It
+    /// returns one `(log_id, result)` for every applied entry, in log order, or nothing if the
+    /// results should not be streamed. A result is anything a follower needs to update its state
+    /// without computing it again, e.g., the new value of the keys that an entry changes.
+    ///
+    /// On a follower that computes the results itself, they are compared with the ones of the
+    /// leader, to detect a state machine that is not deterministic.
+    ///
+    /// The default returns nothing.
+    ///
+    /// [`Config::apply_results_wait`]: crate::Config::apply_results_wait
+    #[since(version = "0.10.0")]
+    fn take_computed_results(&mut self) -> Vec<(LogIdOf<C>, Vec<u8>)> {
+        Vec::new()
+    }
+
+    /// Apply the given entries with the results computed by the state machine of the leader,
+    /// instead of computing them.
+    ///
+    /// `results[i]` is the result of `entries[i]`, returned by [`Self::take_computed_results`] on
+    /// the leader. An implementation should update the state as [`Self::apply`] does, and return a
+    /// response for every entry.
+    ///
+    /// The default ignores the results and calls [`Self::apply`].
+    #[since(version = "0.10.0")]
+    async fn apply_computed<I>(&mut self, entries: I, results: Vec<Vec<u8>>) -> Result<Vec<C::R>, StorageError<C>>
+    where
+        I: IntoIterator<Item = C::Entry> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let _ = results;
+        self.apply(entries).await
+    }
 }
@@ -0,0 +1,481 @@
+//! Runtime-checkable safety invariants from the Raft TLA+ specification.
+//!
+//! [`InvariantChecker`] checks snapshots of every node in a cluster, taken as
+//! [`NodeObservation`]s, against these properties of the
+//! [Raft TLA+ spec](https://github.com/ongardie/raft.tla):
+//!
+//! - **Election Safety**: at most one leader is elected for a given leader id.
+//! - **Log Matching**: if two logs contain an entry with the same log id, the logs are identical in
+//!   all entries up through it.
+//! - **Leader Completeness**: a committed entry is present in the log of every later leader.
+//! - **State Machine Safety**: once an index is committed, no node commits a different entry at it.
+//!
+//! The checker keeps history across calls, so that a violation between two observations, such as
+//! two leaders elected in the same term at different times, is also caught. A simulation or chaos
+//! test calls [`InvariantChecker::check()`] repeatedly as the cluster evolves.
+//!
+//! This module is available with feature `verify`.
+
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+
+use crate::LogIdOptionExt;
+use crate::RaftLogReader;
+use crate::RaftMetrics;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::StorageError;
+use crate::entry::RaftEntry;
+use crate::storage::RaftLogStorage;
+use crate::type_config::alias::CommittedLeaderIdOf;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::RaftLeaderId;
+use crate::vote::RaftVote;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// A snapshot of the protocol state of one node, the input of [`InvariantChecker`].
+///
+/// The fields are read at slightly different times, so an observation should be taken when the
+/// node is not changing quickly, e.g., after waiting for the cluster to settle; otherwise a
+/// transient state may be reported as a violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeObservation<C>
+where C: RaftTypeConfig
+{
+    /// The id of the observed node.
+    pub id: C::NodeId,
+
+    /// The vote of the node.
+    pub vote: VoteOf<C>,
+
+    /// The role of the node.
+    pub server_state: ServerState,
+
+    /// The greatest log id the node knows to be committed.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The last purged log id. Entries up to it are no longer in [`log_ids`](Self::log_ids).
+    pub purged: Option<LogIdOf<C>>,
+
+    /// The log ids of the entries in the log, after `purged`, in index order.
+    pub log_ids: Vec<LogIdOf<C>>,
+}
+
+impl<C> NodeObservation<C>
+where C: RaftTypeConfig
+{
+    /// Observe a node from its metrics and its log store.
+    ///
+    /// `committed` is the greater of the committed log id saved in the log store and the last
+    /// applied log id in the metrics, because a log store is not required to save the committed
+    /// log id.
+    pub async fn read<LS>(metrics: &RaftMetrics<C>, log_store: &mut LS) -> Result<Self, StorageError<C>>
+    where LS: RaftLogStorage<C> {
+        let committed = log_store.read_committed().await?;
+        let committed = committed.max(metrics.last_applied.clone());
+
+        let log_state = log_store.get_log_state().await?;
+        let start = log_state.last_purged_log_id.next_index();
+        let end = log_state.last_log_id.next_index();
+
+        let mut reader = log_store.get_log_reader().await;
+        let entries = reader.try_get_log_entries(start..end).await?;
+
+        Ok(Self {
+            id: metrics.id.clone(),
+            vote: metrics.vote.clone(),
+            server_state: metrics.state,
+            committed,
+            purged: log_state.last_purged_log_id,
+            log_ids: entries.iter().map(|e| e.log_id()).collect(),
+        })
+    }
+
+    /// Whether this node believes it is the leader elected by a quorum.
+    pub fn is_leader(&self) -> bool {
+        self.server_state == ServerState::Leader
+            && self.vote.is_committed()
+            && self.vote.leader_node_id() == Some(&self.id)
+    }
+
+    /// The index of the first entry that is not purged.
+    fn first_index(&self) -> u64 {
+        self.purged.next_index()
+    }
+
+    /// Returns the log id at `index`, or `None` if it is purged or not in the log.
+    fn log_id_at(&self, index: u64) -> Option<&LogIdOf<C>> {
+        let offset = index.checked_sub(self.first_index())?;
+        self.log_ids.get(offset as usize)
+    }
+}
+
+/// A violation of a Raft safety invariant, found by [`InvariantChecker`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvariantViolation<C>
+where C: RaftTypeConfig
+{
+    /// Two nodes are elected as leader with the same leader id.
+    #[error("ElectionSafety: both node {first} and node {second} are elected with leader id {leader_id}")]
+    ElectionSafety {
+        leader_id: CommittedLeaderIdOf<C>,
+        first: C::NodeId,
+        second: C::NodeId,
+    },
+
+    /// Two logs contain the same log id, but differ at an earlier index.
+    #[error("LogMatching: node {a} and node {b} both contain {log_id}, but differ at index {index}")]
+    LogMatching {
+        a: C::NodeId,
+        b: C::NodeId,
+        log_id: LogIdOf<C>,
+        index: u64,
+    },
+
+    /// A leader does not contain an entry committed before it.
+    #[error("LeaderCompleteness: leader node {leader} does not contain committed {committed}")]
+    LeaderCompleteness { leader: C::NodeId, committed: LogIdOf<C> },
+
+    /// A node commits an entry at an index where a different entry is already committed.
+    #[error("StateMachineSafety: node {node} commits {log_id} at index {index}, but {committed} is already committed")]
+    StateMachineSafety {
+        node: C::NodeId,
+        index: u64,
+        committed: LogIdOf<C>,
+        log_id: LogIdOf<C>,
+    },
+}
+
+/// Checks [`NodeObservation`]s of a cluster against the Raft safety invariants.
+///
+/// See the [module docs](self) for the checked invariants.
+#[derive(Debug, Clone, Default)]
+pub struct InvariantChecker<C>
+where C: RaftTypeConfig
+{
+    /// Every leader observed so far.
+    leaders: BTreeMap<CommittedLeaderIdOf<C>, C::NodeId>,
+
+    /// Every committed log id observed so far, by index.
+    committed: BTreeMap<u64, LogIdOf<C>>,
+}
+
+impl<C> InvariantChecker<C>
+where C: RaftTypeConfig
+{
+    /// Create a checker without history.
+    pub fn new() -> Self {
+        Self {
+            leaders: BTreeMap::new(),
+            committed: BTreeMap::new(),
+        }
+    }
+
+    /// Check an observation of the cluster, and add it to the history.
+    ///
+    /// It returns the first violation found.
+    pub fn check(&mut self, nodes: &[NodeObservation<C>]) -> Result<(), InvariantViolation<C>> {
+        self.check_election_safety(nodes)?;
+        Self::check_log_matching(nodes)?;
+        self.check_state_machine_safety(nodes)?;
+        self.check_leader_completeness(nodes)?;
+        Ok(())
+    }
+
+    fn check_election_safety(&mut self, nodes: &[NodeObservation<C>]) -> Result<(), InvariantViolation<C>> {
+        for n in nodes.iter().filter(|n| n.is_leader()) {
+            let leader_id = n.vote.leader_id().unwrap().to_committed();
+
+            match self.leaders.entry(leader_id) {
+                Entry::Vacant(e) => {
+                    e.insert(n.id.clone());
+                }
+                Entry::Occupied(e) => {
+                    if e.get() != &n.id {
+                        return Err(InvariantViolation::ElectionSafety {
+                            leader_id: e.key().clone(),
+                            first: e.get().clone(),
+                            second: n.id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_log_matching(nodes: &[NodeObservation<C>]) -> Result<(), InvariantViolation<C>> {
+        for (i, a) in nodes.iter().enumerate() {
+            for b in &nodes[i + 1..] {
+                // The last log id present in both logs; every earlier entry must be the same.
+                let Some(same) = a.log_ids.iter().rev().find(|x| b.log_id_at(x.index) == Some(*x)) else {
+                    continue;
+                };
+
+                let start = a.first_index().max(b.first_index());
+                for index in start..same.index {
+                    if a.log_id_at(index) != b.log_id_at(index) {
+                        return Err(InvariantViolation::LogMatching {
+                            a: a.id.clone(),
+                            b: b.id.clone(),
+                            log_id: same.clone(),
+                            index,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_state_machine_safety(&mut self, nodes: &[NodeObservation<C>]) -> Result<(), InvariantViolation<C>> {
+        for n in nodes {
+            let Some(committed) = &n.committed else {
+                continue;
+            };
+
+            for log_id in n.log_ids.iter().take_while(|x| x.index <= committed.index) {
+                match self.committed.entry(log_id.index) {
+                    Entry::Vacant(e) => {
+                        e.insert(log_id.clone());
+                    }
+                    Entry::Occupied(e) => {
+                        if e.get() != log_id {
+                            return Err(InvariantViolation::StateMachineSafety {
+                                node: n.id.clone(),
+                                index: log_id.index,
+                                committed: e.get().clone(),
+                                log_id: log_id.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_leader_completeness(&self, nodes: &[NodeObservation<C>]) -> Result<(), InvariantViolation<C>> {
+        for leader in nodes.iter().filter(|n| n.is_leader()) {
+            let leader_id = leader.vote.leader_id().unwrap().to_committed();
+
+            // Entries committed by this leader or earlier ones must be in its log, unless purged.
+            let earlier = self.committed.range(leader.first_index()..).map(|(_, x)| x);
+            for committed in earlier.filter(|x| x.committed_leader_id() <= &leader_id) {
+                if leader.log_id_at(committed.index) != Some(committed) {
+                    return Err(InvariantViolation::LeaderCompleteness {
+                        leader: leader.id.clone(),
+                        committed: committed.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InvariantChecker;
+    use super::InvariantViolation;
+    use super::NodeObservation;
+    use crate::ServerState;
+    use crate::Vote;
+    use crate::declare_raft_types;
+    use crate::engine::testing::UTConfig;
+    use crate::engine::testing::log_id;
+    use crate::impls::leader_id_std::LeaderId;
+    use crate::type_config::alias::LogIdOf;
+
+    fn node(
+        id: u64,
+        vote: Vote<UTConfig>,
+        server_state: ServerState,
+        committed: Option<LogIdOf<UTConfig>>,
+        log_ids: Vec<LogIdOf<UTConfig>>,
+    ) -> NodeObservation<UTConfig> {
+        NodeObservation {
+            id,
+            vote,
+            server_state,
+            committed,
+            purged: None,
+            log_ids,
+        }
+    }
+
+    fn leader(
+        id: u64,
+        term: u64,
+        committed: Option<LogIdOf<UTConfig>>,
+        log_ids: Vec<LogIdOf<UTConfig>>,
+    ) -> NodeObservation<UTConfig> {
+        node(
+            id,
+            Vote::new_committed(term, id),
+            ServerState::Leader,
+            committed,
+            log_ids,
+        )
+    }
+
+    fn follower(id: u64, vote: Vote<UTConfig>, log_ids: Vec<LogIdOf<UTConfig>>) -> NodeObservation<UTConfig> {
+        node(id, vote, ServerState::Follower, None, log_ids)
+    }
+
+    #[test]
+    fn test_no_violation() -> anyhow::Result<()> {
+        let mut checker = InvariantChecker::<UTConfig>::new();
+
+        let logs = vec![log_id(1, 0, 0), log_id(1, 0, 1)];
+        checker.check(&[
+            leader(0, 1, Some(log_id(1, 0, 1)), logs.clone()),
+            follower(1, Vote::new_committed(1, 0), logs.clone()),
+            follower(2, Vote::new_committed(1, 0), logs[..1].to_vec()),
+        ])?;
+
+        // Node 1 is elected in term 2 and a purged node still matches.
+        let logs2 = vec![log_id(1, 0, 0), log_id(1, 0, 1), log_id(2, 1, 2)];
+        let mut purged = follower(2, Vote::new_committed(2, 1), logs2[2..].to_vec());
+        purged.purged = Some(log_id(1, 0, 1));
+
+        checker.check(&[
+            follower(0, Vote::new_committed(2, 1), logs.clone()),
+            leader(1, 2, Some(log_id(2, 1, 2)), logs2),
+            purged,
+        ])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_election_safety() -> anyhow::Result<()> {
+        // With standard Raft leader id, only one leader can be elected in a term.
+        declare_raft_types!(TC: D=u64, R=(), LeaderId=LeaderId<TC>);
+
+        let leader = |id: u64, term: u64| NodeObservation::<TC> {
+            id,
+            vote: Vote::new_committed(term, id),
+            server_state: ServerState::Leader,
+            committed: None,
+            purged: None,
+            log_ids: vec![],
+        };
+
+        let mut checker = InvariantChecker::<TC>::new();
+
+        checker.check(&[leader(0, 1)])?;
+        checker.check(&[leader(0, 1), leader(1, 2)])?;
+
+        // A leader in the same term observed later.
+        let res = checker.check(&[leader(2, 1)]);
+        assert_eq!(
+            Err(InvariantViolation::ElectionSafety {
+                leader_id: crate::testing::log_id::<TC>(1, 0, 0).leader_id,
+                first: 0,
+                second: 2,
+            }),
+            res
+        );
+
+        // An uncommitted vote is not a leader.
+        let mut candidate = leader(3, 2);
+        candidate.vote = Vote::new(2, 3);
+        candidate.server_state = ServerState::Candidate;
+        checker.check(&[candidate])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_matching() -> anyhow::Result<()> {
+        let mut checker = InvariantChecker::<UTConfig>::new();
+
+        let res = checker.check(&[
+            follower(0, Vote::new_committed(2, 0), vec![
+                log_id(1, 0, 0),
+                log_id(1, 0, 1),
+                log_id(2, 0, 2),
+            ]),
+            follower(1, Vote::new_committed(2, 0), vec![
+                log_id(1, 0, 0),
+                log_id(1, 1, 1),
+                log_id(2, 0, 2),
+            ]),
+        ]);
+        assert_eq!(
+            Err(InvariantViolation::LogMatching {
+                a: 0,
+                b: 1,
+                log_id: log_id(2, 0, 2),
+                index: 1,
+            }),
+            res
+        );
+
+        // Diverged logs after the last common entry are fine.
+        checker.check(&[
+            follower(0, Vote::new_committed(2, 0), vec![log_id(1, 0, 0), log_id(1, 0, 1)]),
+            follower(1, Vote::new_committed(2, 0), vec![log_id(1, 0, 0), log_id(1, 1, 1)]),
+        ])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_machine_safety() -> anyhow::Result<()> {
+        let mut checker = InvariantChecker::<UTConfig>::new();
+
+        checker.check(&[leader(0, 1, Some(log_id(1, 0, 1)), vec![
+            log_id(1, 0, 0),
+            log_id(1, 0, 1),
+        ])])?;
+
+        let res = checker.check(&[node(
+            1,
+            Vote::new_committed(2, 1),
+            ServerState::Follower,
+            Some(log_id(2, 1, 1)),
+            vec![log_id(1, 0, 0), log_id(2, 1, 1)],
+        )]);
+        assert_eq!(
+            Err(InvariantViolation::StateMachineSafety {
+                node: 1,
+                index: 1,
+                committed: log_id(1, 0, 1),
+                log_id: log_id(2, 1, 1),
+            }),
+            res
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_leader_completeness() -> anyhow::Result<()> {
+        let mut checker = InvariantChecker::<UTConfig>::new();
+
+        checker.check(&[leader(0, 1, Some(log_id(1, 0, 1)), vec![
+            log_id(1, 0, 0),
+            log_id(1, 0, 1),
+        ])])?;
+
+        // A later leader that lost a committed entry.
+        let res = checker.check(&[leader(1, 2, None, vec![log_id(1, 0, 0)])]);
+        assert_eq!(
+            Err(InvariantViolation::LeaderCompleteness {
+                leader: 1,
+                committed: log_id(1, 0, 1),
+            }),
+            res
+        );
+
+        // Purged entries are not checked.
+        let mut purged = leader(2, 3, None, vec![]);
+        purged.purged = Some(log_id(1, 0, 1));
+        checker.check(&[purged])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+//! Crash-recovery tests that reopen a store between storage calls.
+//!
+//! A fixed sequence of operations is run against a store built by [`StoreBuilder::build`].
+//! For every prefix of the sequence, the store is dropped after the prefix is done, as if the
+//! process is killed, and then reopened from disk with [`StoreBuilder::reopen`].
+//! The reopened store must contain everything whose durability has been acknowledged:
+//!
+//! - A vote is durable when [`RaftLogStorage::save_vote`] returns.
+//! - Log entries are durable when the callback passed to [`RaftLogStorage::append`] is called.
+//! - A truncation is durable when the callback of the next [`RaftLogStorage::append`] is called:
+//!   after it, the reopened store must not contain any entry that is not acknowledged.
+//!
+//! Purge and a truncation not followed by an append do not have to be durable, and a reopened
+//! store may return the state before or after such an operation.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::RaftLogReader;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::entry::RaftEntry;
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftStateMachine;
+use crate::testing::log::StoreBuilder;
+use crate::testing::log::Suite;
+use crate::testing::log::suite::append;
+use crate::testing::log::suite::log_id_0;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// An operation run before a simulated crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CrashOp {
+    /// Save vote of `term`.
+    SaveVote { term: u64 },
+
+    /// Append entries in range `[start, end)` of `term`.
+    Append { term: u64, start: u64, end: u64 },
+
+    /// Truncate logs since `since`(inclusive).
+    Truncate { since: u64 },
+
+    /// Purge logs up to `upto`(inclusive).
+    Purge { upto: u64 },
+}
+
+impl fmt::Display for CrashOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrashOp::SaveVote { term } => write!(f, "save_vote(T{})", term),
+            CrashOp::Append { term, start, end } => write!(f, "append(T{}, [{}, {}))", term, start, end),
+            CrashOp::Truncate { since } => write!(f, "truncate({})", since),
+            CrashOp::Purge { upto } => write!(f, "purge({})", upto),
+        }
+    }
+}
+
+/// The operation sequence to crash in.
+const CRASH_OPS: [CrashOp; 12] = [
+    CrashOp::SaveVote { term: 1 },
+    CrashOp::Append {
+        term: 1,
+        start: 0,
+        end: 5,
+    },
+    CrashOp::Append {
+        term: 1,
+        start: 5,
+        end: 8,
+    },
+    CrashOp::SaveVote { term: 2 },
+    CrashOp::Truncate { since: 6 },
+    CrashOp::Append {
+        term: 2,
+        start: 6,
+        end: 10,
+    },
+    CrashOp::Purge { upto: 3 },
+    CrashOp::SaveVote { term: 3 },
+    CrashOp::Append {
+        term: 3,
+        start: 10,
+        end: 12,
+    },
+    // The append re-covers fewer entries than are truncated.
+    CrashOp::SaveVote { term: 4 },
+    CrashOp::Truncate { since: 9 },
+    CrashOp::Append {
+        term: 4,
+        start: 9,
+        end: 10,
+    },
+];
+
+/// The state whose durability has been acknowledged by the store.
+struct Acked<C>
+where C: RaftTypeConfig
+{
+    vote: Option<VoteOf<C>>,
+    logs: BTreeMap<u64, LogIdOf<C>>,
+
+    /// Whether all truncations are durable, i.e., no truncation is issued after the last append.
+    ///
+    /// If it is true, the reopened store must contain no other entries than [`Self::logs`].
+    truncation_durable: bool,
+}
+
+#[allow(unused)]
+impl<C, LS, SM, B, G> Suite<C, LS, SM, B, G>
+where
+    C: RaftTypeConfig,
+    C::D: Debug,
+    C::R: Debug,
+    C::Term: From<u64>,
+    C::NodeId: From<u64>,
+    C::Node: Default,
+    LS: RaftLogStorage<C>,
+    SM: RaftStateMachine<C>,
+    B: StoreBuilder<C, LS, SM, G>,
+    G: Send + Sync,
+{
+    /// Crash after every prefix of an operation sequence and check the reopened store keeps all
+    /// acknowledged writes.
+    ///
+    /// It does nothing if the builder does not support [`StoreBuilder::reopen`].
+    pub async fn crash_recovery(builder: &B) -> Result<(), StorageError<C>> {
+        for crash_at in 0..=CRASH_OPS.len() {
+            let (g, mut store, sm) = builder.build().await?;
+
+            let mut acked = Acked::<C> {
+                vote: None,
+                logs: BTreeMap::new(),
+                truncation_durable: true,
+            };
+
+            for op in &CRASH_OPS[..crash_at] {
+                Self::run_crash_op(&mut store, &mut acked, *op).await?;
+            }
+
+            // Crash
+            drop(store);
+            drop(sm);
+
+            // Give background IO tasks a chance to release resources, such as file locks.
+            C::sleep(Duration::from_millis(10)).await;
+
+            let Some((mut store, _sm)) = builder.reopen(&g).await? else {
+                tracing::info!("crash_recovery: StoreBuilder::reopen() is not supported, skip");
+                return Ok(());
+            };
+
+            Self::check_acked(&mut store, &acked, crash_at).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_crash_op(store: &mut LS, acked: &mut Acked<C>, op: CrashOp) -> Result<(), StorageError<C>> {
+        match op {
+            CrashOp::SaveVote { term } => {
+                let vote = VoteOf::<C>::from_term_node_id(term.into(), 0u64.into());
+                store.save_vote(&vote).await?;
+                acked.vote = Some(vote);
+            }
+            CrashOp::Append { term, start, end } => {
+                let entries = (start..end).map(|i| C::Entry::new_blank(log_id_0::<C>(term, i))).collect::<Vec<_>>();
+
+                // `append()` returns when the callback is called.
+                append(store, entries).await?;
+
+                for i in start..end {
+                    acked.logs.insert(i, log_id_0::<C>(term, i));
+                }
+                acked.truncation_durable = true;
+            }
+            CrashOp::Truncate { since } => {
+                store.truncate(acked.logs[&since].clone()).await?;
+                acked.logs.split_off(&since);
+                acked.truncation_durable = false;
+            }
+            CrashOp::Purge { upto } => {
+                store.purge(acked.logs[&upto].clone()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_acked(store: &mut LS, acked: &Acked<C>, crash_at: usize) -> Result<(), StorageError<C>> {
+        let ctx = || {
+            let ops = CRASH_OPS[..crash_at].iter().map(|x| x.to_string()).collect::<Vec<_>>();
+            format!("crashed after: [{}]", ops.join(", "))
+        };
+
+        let mut reader = store.get_log_reader().await;
+
+        let vote = reader.read_vote().await?;
+        assert_eq!(acked.vote, vote, "vote must be durable; {}", ctx());
+
+        let st = store.get_log_state().await?;
+        let purged_index = st.last_purged_log_id.as_ref().map(|x| x.index());
+
+        let entries = reader.try_get_log_entries(0..).await?;
+        let got = entries.into_iter().map(|e| (e.index(), e.log_id())).collect::<BTreeMap<_, _>>();
+
+        for (index, log_id) in acked.logs.iter() {
+            if Some(*index) <= purged_index {
+                continue;
+            }
+            assert_eq!(
+                Some(log_id),
+                got.get(index),
+                "acknowledged log entry must be durable; {}",
+                ctx()
+            );
+        }
+
+        if acked.truncation_durable {
+            for (index, log_id) in got.iter() {
+                if Some(*index) <= purged_index {
+                    continue;
+                }
+                assert_eq!(
+                    Some(log_id),
+                    acked.logs.get(index),
+                    "truncated log entry must not be present after the next append is durable; {}",
+                    ctx()
+                );
+            }
+        }
+
+        if let Some(last) = acked.logs.values().next_back() {
+            assert_eq!(Some(last), st.last_log_id.as_ref(), "last_log_id; {}", ctx());
+        }
+
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@
 //! [`RaftLogStorage`]: crate::storage::RaftLogStorage
 //! [`RaftStateMachine`]: crate::storage::RaftStateMachine
 
+mod crash;
+mod random_ops;
 mod store_builder;
 mod suite;
 
@@ -0,0 +1,317 @@
+//! Property based tests that run random sequences of storage operations.
+//!
+//! Every operation is applied to both the store under test and a simple in-memory model.
+//! After each operation, the store is checked against the model.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Debug;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use crate::RaftLogReader;
+use crate::RaftSnapshotBuilder;
+use crate::RaftTypeConfig;
+use crate::StorageError;
+use crate::display_ext::DisplayOptionExt;
+use crate::entry::RaftEntry;
+use crate::raft_state::LogStateReader;
+use crate::storage::RaftLogStorage;
+use crate::storage::RaftStateMachine;
+use crate::storage::StorageHelper;
+use crate::testing::log::StoreBuilder;
+use crate::testing::log::Suite;
+use crate::testing::log::suite::append;
+use crate::testing::log::suite::log_id_0;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// Seeds of the random operation sequences run by [`Suite::random_ops`].
+///
+/// Fixed seeds make a failure reproducible.
+const SEEDS: [u64; 4] = [1, 7, 42, 2024];
+
+/// Number of operations in every sequence.
+const OPS_PER_SEED: usize = 64;
+
+/// A storage operation generated by [`Suite::random_ops`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    /// Append `n` blank entries after the last log entry.
+    Append { n: u64 },
+
+    /// Start a new term and save a vote for it.
+    SaveVote,
+
+    /// Truncate logs since `since`(inclusive).
+    Truncate { since: u64 },
+
+    /// Apply logs up to `upto`(inclusive) to the state machine and build a snapshot.
+    Snapshot { upto: u64 },
+
+    /// Purge logs up to `upto`(inclusive).
+    Purge { upto: u64 },
+
+    /// Load the initial state from storage, as a restarted node does.
+    Restart,
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Append { n } => write!(f, "append({})", n),
+            Op::SaveVote => write!(f, "save_vote"),
+            Op::Truncate { since } => write!(f, "truncate({})", since),
+            Op::Snapshot { upto } => write!(f, "snapshot({})", upto),
+            Op::Purge { upto } => write!(f, "purge({})", upto),
+            Op::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+/// The expected state of a store.
+struct Model<C>
+where C: RaftTypeConfig
+{
+    term: u64,
+    logs: BTreeMap<u64, LogIdOf<C>>,
+    last_purged: Option<LogIdOf<C>>,
+    applied: Option<LogIdOf<C>>,
+    vote: Option<VoteOf<C>>,
+}
+
+impl<C> Model<C>
+where
+    C: RaftTypeConfig,
+    C::Term: From<u64>,
+    C::NodeId: From<u64>,
+{
+    fn new() -> Self {
+        Self {
+            term: 1,
+            logs: BTreeMap::new(),
+            last_purged: None,
+            applied: None,
+            vote: None,
+        }
+    }
+
+    fn last_log_id(&self) -> Option<LogIdOf<C>> {
+        self.logs.values().next_back().cloned().or_else(|| self.last_purged.clone())
+    }
+
+    fn next_index(&self) -> u64 {
+        self.last_log_id().map(|x| x.index() + 1).unwrap_or_default()
+    }
+
+    /// The first log index that may be truncated: applied logs are committed and can not be
+    /// truncated.
+    fn truncate_floor(&self) -> u64 {
+        self.applied.as_ref().map(|x| x.index() + 1).unwrap_or_default()
+    }
+
+    /// Generate a random operation that is valid for the current state.
+    fn random_op(&self, rng: &mut StdRng) -> Op {
+        let next = self.next_index();
+        let applied = self.applied.as_ref().map(|x| x.index());
+        let purged = self.last_purged.as_ref().map(|x| x.index());
+
+        loop {
+            let op = match rng.random_range(0..10) {
+                0..=3 => Op::Append {
+                    n: rng.random_range(1..=5),
+                },
+                4 => Op::SaveVote,
+                5 => {
+                    let floor = self.truncate_floor();
+                    if floor >= next {
+                        continue;
+                    }
+                    Op::Truncate {
+                        since: rng.random_range(floor..next),
+                    }
+                }
+                6 | 7 => {
+                    let floor = applied.map(|x| x + 1).unwrap_or_default();
+                    if floor >= next {
+                        continue;
+                    }
+                    Op::Snapshot {
+                        upto: rng.random_range(floor..next),
+                    }
+                }
+                8 => {
+                    let Some(applied) = applied else {
+                        continue;
+                    };
+                    let floor = purged.map(|x| x + 1).unwrap_or_default();
+                    if floor > applied {
+                        continue;
+                    }
+                    Op::Purge {
+                        upto: rng.random_range(floor..=applied),
+                    }
+                }
+                _ => Op::Restart,
+            };
+            return op;
+        }
+    }
+}
+
+#[allow(unused)]
+impl<C, LS, SM, B, G> Suite<C, LS, SM, B, G>
+where
+    C: RaftTypeConfig,
+    C::D: Debug,
+    C::R: Debug,
+    C::Term: From<u64>,
+    C::NodeId: From<u64>,
+    C::Node: Default,
+    LS: RaftLogStorage<C>,
+    SM: RaftStateMachine<C>,
+    B: StoreBuilder<C, LS, SM, G>,
+    G: Send + Sync,
+{
+    /// Run random interleavings of append, truncate, purge, snapshot and restart on a store and
+    /// check the store against an in-memory model after every operation.
+    pub async fn random_ops(builder: &B) -> Result<(), StorageError<C>> {
+        for seed in SEEDS {
+            let (_g, mut store, mut sm) = builder.build().await?;
+            Self::random_ops_with_seed(&mut store, &mut sm, seed).await?;
+        }
+        Ok(())
+    }
+
+    async fn random_ops_with_seed(store: &mut LS, sm: &mut SM, seed: u64) -> Result<(), StorageError<C>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut model = Model::<C>::new();
+        let mut history = Vec::with_capacity(OPS_PER_SEED);
+
+        for _ in 0..OPS_PER_SEED {
+            let op = model.random_op(&mut rng);
+            history.push(op);
+
+            tracing::debug!(seed, "random_ops: {}", op);
+
+            Self::run_op(store, sm, &mut model, op).await?;
+            Self::check_model(store, sm, &model, seed, &history).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_op(store: &mut LS, sm: &mut SM, model: &mut Model<C>, op: Op) -> Result<(), StorageError<C>> {
+        match op {
+            Op::Append { n } => {
+                let start = model.next_index();
+                let entries =
+                    (start..start + n).map(|i| C::Entry::new_blank(log_id_0::<C>(model.term, i))).collect::<Vec<_>>();
+                append(store, entries).await?;
+
+                for i in start..start + n {
+                    model.logs.insert(i, log_id_0::<C>(model.term, i));
+                }
+            }
+            Op::SaveVote => {
+                model.term += 1;
+                let vote = VoteOf::<C>::from_term_node_id(model.term.into(), 0u64.into());
+                store.save_vote(&vote).await?;
+                model.vote = Some(vote);
+            }
+            Op::Truncate { since } => {
+                let log_id = model.logs[&since].clone();
+                store.truncate(log_id).await?;
+                model.logs.split_off(&since);
+            }
+            Op::Snapshot { upto } => {
+                let start = model.applied.as_ref().map(|x| x.index() + 1).unwrap_or_default();
+                let entries = store.get_log_reader().await.try_get_log_entries(start..upto + 1).await?;
+                sm.apply(entries).await?;
+                model.applied = Some(model.logs[&upto].clone());
+
+                let snapshot = sm.get_snapshot_builder().await.build_snapshot().await?;
+                assert_eq!(
+                    model.applied, snapshot.meta.last_log_id,
+                    "snapshot({}) last_log_id",
+                    upto
+                );
+            }
+            Op::Purge { upto } => {
+                let log_id = model.logs[&upto].clone();
+                store.purge(log_id.clone()).await?;
+                model.logs = model.logs.split_off(&(upto + 1));
+                model.last_purged = Some(log_id);
+            }
+            Op::Restart => {
+                let state = StorageHelper::new(store, sm).get_initial_state().await?;
+
+                assert_eq!(
+                    model.last_log_id().as_ref(),
+                    state.last_log_id(),
+                    "restart: last_log_id"
+                );
+                assert_eq!(
+                    model.last_purged.as_ref(),
+                    state.last_purged_log_id(),
+                    "restart: last_purged_log_id"
+                );
+                assert_eq!(model.applied.as_ref(), state.io_applied(), "restart: applied");
+                assert_eq!(
+                    model.vote.clone().unwrap_or_default(),
+                    state.vote_ref().clone(),
+                    "restart: vote"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Assert the store is consistent with the model.
+    async fn check_model(
+        store: &mut LS,
+        sm: &mut SM,
+        model: &Model<C>,
+        seed: u64,
+        history: &[Op],
+    ) -> Result<(), StorageError<C>> {
+        let ctx = || {
+            let ops = history.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+            format!("seed: {}, ops: [{}]", seed, ops.join(", "))
+        };
+
+        let st = store.get_log_state().await?;
+        assert_eq!(
+            model.last_purged,
+            st.last_purged_log_id,
+            "last_purged_log_id; {}",
+            ctx()
+        );
+        assert_eq!(model.last_log_id(), st.last_log_id, "last_log_id; {}", ctx());
+
+        let start = model.last_purged.as_ref().map(|x| x.index() + 1).unwrap_or_default();
+        let mut reader = store.get_log_reader().await;
+
+        let got = reader.try_get_log_entries(start..).await?.into_iter().map(|e| e.log_id()).collect::<Vec<_>>();
+        let want = model.logs.values().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            want,
+            got,
+            "log entries after {}; {}",
+            model.last_purged.display(),
+            ctx()
+        );
+
+        let vote = reader.read_vote().await?;
+        assert_eq!(model.vote, vote, "vote; {}", ctx());
+
+        let (applied, _) = sm.applied_state().await?;
+        assert_eq!(model.applied, applied, "applied; {}", ctx());
+
+        Ok(())
+    }
+}
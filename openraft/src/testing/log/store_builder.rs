@@ -24,4 +24,17 @@ where
 {
     /// Build a [`RaftLogStorage`] and [`RaftStateMachine`] implementation
     async fn build(&self) -> Result<(G, LS, SM), StorageError<C>>;
+
+    /// Reopen the [`RaftLogStorage`] and [`RaftStateMachine`] built by [`build`](Self::build),
+    /// from the resources held by the guard `g`.
+    ///
+    /// It is used to simulate a process crash: the previously built stores are dropped without
+    /// any shutdown, then reopened from where they persist data.
+    ///
+    /// Returns `None` if the store does not support reopening, for example, an in-memory store.
+    /// By default it returns `None`, and crash recovery tests are skipped.
+    async fn reopen(&self, g: &G) -> Result<Option<(LS, SM)>, StorageError<C>> {
+        let _ = g;
+        Ok(None)
+    }
 }
@@ -26,6 +26,7 @@ use crate::storage::RaftLogReaderExt;
 use crate::storage::RaftLogStorage;
 use crate::storage::RaftStateMachine;
 use crate::storage::StorageHelper;
+use crate::storage::StorageStamp;
 use crate::testing::log::StoreBuilder;
 use crate::type_config::TypeConfigExt;
 use crate::type_config::alias::LogIdOf;
@@ -144,6 +145,7 @@ where
         run_test(builder, Self::get_initial_state_log_ids).await?;
         run_test(builder, Self::get_initial_state_re_apply_committed).await?;
         run_test(builder, Self::save_vote).await?;
+        run_test(builder, Self::save_stamp).await?;
         run_test(builder, Self::get_log_entries).await?;
         run_test(builder, Self::limited_get_log_entries).await?;
         run_test(builder, Self::try_get_log_entry).await?;
@@ -165,6 +167,10 @@ where
         run_test(builder, Self::apply_multiple).await?;
 
         Self::transfer_snapshot(builder).await?;
+        Self::install_snapshot_restores_membership(builder).await?;
+
+        Self::random_ops(builder).await?;
+        Self::crash_recovery(builder).await?;
 
         // TODO(xp): test: do_log_compaction
 
@@ -833,6 +839,20 @@ where
         Ok(())
     }
 
+    /// The stamp is optional: a store that does not save it always reads `None`.
+    pub async fn save_stamp(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
+        assert_eq!(None, store.read_stamp().await?, "a new store has no stamp");
+
+        let stamp = StorageStamp::<C>::new("foo", NODE_ID.into());
+        store.save_stamp(&stamp).await?;
+
+        let got = store.read_stamp().await?;
+        if got.is_some() {
+            assert_eq!(Some(stamp), got);
+        }
+        Ok(())
+    }
+
     pub async fn get_log_entries(mut store: LS, mut sm: SM) -> Result<(), StorageError<C>> {
         Self::feed_10_logs_vote_self(&mut store).await?;
 
@@ -1365,6 +1385,57 @@ where
         Ok(())
     }
 
+    /// A node restored only from a snapshot, with no logs, must know the membership at the
+    /// snapshot point: `install_snapshot()` restores `meta.last_membership` into the state machine.
+    pub async fn install_snapshot_restores_membership(builder: &B) -> Result<(), StorageError<C>> {
+        let (_g_l, _store_l, mut sm_l) = builder.build().await?;
+        let (_g_f, mut store_f, mut sm_f) = builder.build().await?;
+
+        tracing::info!("--- build a snapshot that contains a membership config");
+        sm_l.apply(vec![
+            membership_ent_0::<C>(1, 1, btreeset! {1, 2}),
+            membership_ent_0::<C>(2, 2, btreeset! {3, 4, 5}),
+            blank_ent_0::<C>(2, 3),
+        ])
+        .await?;
+        let snapshot = sm_l.try_create_snapshot_builder(true).await.unwrap().build_snapshot().await?;
+
+        let want_membership = StoredMembership::new(
+            Some(log_id_0(2, 2)),
+            Membership::new_with_defaults(vec![btreeset! {3, 4, 5}], []),
+        );
+        assert_eq!(snapshot.meta.last_membership, want_membership);
+
+        tracing::info!("--- install the snapshot on an empty node");
+        sm_f.install_snapshot(&snapshot.meta, snapshot.snapshot).await?;
+
+        let (last_applied, last_membership) = sm_f.applied_state().await?;
+        assert_eq!(Some(log_id_0(2, 3)), last_applied);
+        assert_eq!(want_membership, last_membership, "membership restored from snapshot");
+
+        tracing::info!("--- the initial state of the node is built from the snapshot");
+        {
+            let initial = StorageHelper::new(&mut store_f, &mut sm_f).get_initial_state().await?;
+
+            assert_eq!(Some(&log_id_0(2, 3)), initial.last_log_id());
+            assert_eq!(Some(&log_id_0(2, 3)), initial.committed());
+            assert_eq!(
+                Some(&log_id_0(2, 2)),
+                initial.membership_state.effective().log_id().as_ref()
+            );
+            assert_eq!(
+                want_membership.membership(),
+                initial.membership_state.effective().membership()
+            );
+            assert_eq!(
+                Some(&log_id_0(2, 2)),
+                initial.membership_state.committed().log_id().as_ref()
+            );
+        }
+
+        Ok(())
+    }
+
     /// Helper to feed 10 log entries and vote.
     pub async fn feed_10_logs_vote_self(sto: &mut LS) -> Result<(), StorageError<C>> {
         append(sto, [blank_ent_0::<C>(0, 0)]).await?;
@@ -1387,7 +1458,7 @@ where
 }
 
 /// Create a log id with node id 0 for testing.
-fn log_id_0<C>(term: impl Into<C::Term>, index: u64) -> LogIdOf<C>
+pub(crate) fn log_id_0<C>(term: impl Into<C::Term>, index: u64) -> LogIdOf<C>
 where
     C: RaftTypeConfig,
     C::NodeId: From<u64>,
@@ -1441,7 +1512,7 @@ where
 }
 
 /// A wrapper for calling nonblocking `RaftLogStorage::append()`
-async fn append<C, LS, I>(store: &mut LS, entries: I) -> Result<(), StorageError<C>>
+pub(crate) async fn append<C, LS, I>(store: &mut LS, entries: I) -> Result<(), StorageError<C>>
 where
     C: RaftTypeConfig,
     LS: RaftLogStorage<C>,
@@ -5,7 +5,9 @@
 //! ## Modules
 //!
 //! - [`common`] - Common test utilities and assertions
+//! - `invariants` - Raft safety invariants checker, with feature `verify`
 //! - [`log`] - Log storage test suite
+//! - [`network`] - In-process network with scriptable faults
 //! - [`runtime`] - Runtime test utilities
 //!
 //! ## Overview
@@ -29,7 +31,10 @@
 //! These tests help ensure correctness and catch subtle protocol violations.
 
 pub mod common;
+#[cfg(feature = "verify")]
+pub mod invariants;
 pub mod log;
+pub mod network;
 pub mod runtime;
 
 pub use common::*;
@@ -0,0 +1,450 @@
+//! An in-process network with scriptable faults, for testing applications built on Openraft.
+//!
+//! [`MockNetwork`] delivers RPCs directly to the [`Raft`] instances added to it, and lets a test
+//! script how the next RPCs of a type to a peer behave: delayed, dropped, answered with a higher
+//! vote, or only partially accepted. This makes it possible to unit test application level
+//! failover logic against realistic network behavior without a real transport:
+//!
+//! ```ignore
+//! let network = MockNetwork::<MyTypeConfig>::new();
+//! let raft = Raft::new(1, config, network.clone(), log_store, state_machine).await?;
+//! network.add_node(1, raft.clone());
+//!
+//! // The next 3 heartbeats or replication RPCs to node 2 are lost.
+//! network.drop_next(2, RPCTypes::AppendEntries, 3);
+//!
+//! // Node 2 answers the next AppendEntries with a higher vote, and the leader steps down.
+//! network.script(2, RPCTypes::AppendEntries, MockBehavior::HigherVote(Vote::new(10, 2)));
+//! ```
+//!
+//! A script applies to the RPCs sent to a peer by any node of the network.
+//!
+//! With the default feature [`adapt-network-v1`], the network of a peer implements
+//! [`RaftNetwork`], and snapshots are sent in chunks. Otherwise, it implements [`RaftNetworkV2`]
+//! and also delivers `TransferLeader` RPCs.
+//!
+//! [`adapt-network-v1`]: crate::docs::feature_flags#feature-flag-adapt-network-v1
+//! [`RaftNetwork`]: crate::network::RaftNetwork
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyerror::AnyError;
+
+use crate::Raft;
+use crate::RaftTypeConfig;
+use crate::entry::RaftEntry;
+use crate::error::NetworkError;
+use crate::error::RPCError;
+use crate::error::Unreachable;
+use crate::network::RPCTypes;
+use crate::network::RaftNetworkFactory;
+use crate::network::v2::RaftNetworkV2;
+use crate::raft::AppendEntriesRequest;
+use crate::raft::AppendEntriesResponse;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::VoteOf;
+
+/// How the next RPC of a type sent to a peer behaves, see [`MockNetwork::script()`].
+#[derive(Debug, Clone)]
+pub enum MockBehavior<C>
+where C: RaftTypeConfig
+{
+    /// Deliver the RPC after the given duration.
+    Delay(Duration),
+
+    /// Do not deliver the RPC and return a [`NetworkError`].
+    Drop,
+
+    /// Do not deliver the RPC and return an [`Unreachable`] error, which makes the sender back
+    /// off.
+    Unreachable,
+
+    /// Do not deliver the RPC and answer it with the given vote, as if the peer has seen a
+    /// higher term.
+    ///
+    /// It applies to `AppendEntries`, `Vote` and `InstallSnapshot`; Other RPCs are delivered.
+    HigherVote(VoteOf<C>),
+
+    /// Deliver only the first `n` log entries of an `AppendEntries` and answer it with
+    /// [`AppendEntriesResponse::PartialSuccess`].
+    ///
+    /// An `AppendEntries` with no more than `n` entries and other RPCs are delivered.
+    PartialSuccess(u64),
+}
+
+impl<C> fmt::Display for MockBehavior<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MockBehavior::Delay(d) => write!(f, "Delay({:?})", d),
+            MockBehavior::Drop => write!(f, "Drop"),
+            MockBehavior::Unreachable => write!(f, "Unreachable"),
+            MockBehavior::HigherVote(vote) => write!(f, "HigherVote({})", vote),
+            MockBehavior::PartialSuccess(n) => write!(f, "PartialSuccess({})", n),
+        }
+    }
+}
+
+/// The state of a peer in a [`MockNetwork`].
+struct Peer<C>
+where C: RaftTypeConfig
+{
+    raft: Option<Raft<C>>,
+
+    /// The latency added to every RPC sent to this peer.
+    delay: Option<Duration>,
+
+    /// The behaviors of the next RPCs sent to this peer, by RPC type.
+    scripts: HashMap<RPCTypes, VecDeque<MockBehavior<C>>>,
+}
+
+impl<C> Default for Peer<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self {
+            raft: None,
+            delay: None,
+            scripts: HashMap::new(),
+        }
+    }
+}
+
+/// An in-process [`RaftNetworkFactory`] that delivers RPCs to the [`Raft`] instances added to
+/// it, with faults scripted per peer.
+///
+/// It is cheap to clone, and all the clones share the same peers and scripts. The same network is
+/// usually passed to every [`Raft`] of a test.
+pub struct MockNetwork<C>
+where C: RaftTypeConfig
+{
+    peers: Arc<Mutex<BTreeMap<C::NodeId, Peer<C>>>>,
+}
+
+impl<C> Clone for MockNetwork<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self {
+            peers: self.peers.clone(),
+        }
+    }
+}
+
+impl<C> Default for MockNetwork<C>
+where C: RaftTypeConfig
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> MockNetwork<C>
+where C: RaftTypeConfig
+{
+    /// Create a network without any peer.
+    pub fn new() -> Self {
+        Self {
+            peers: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Deliver the RPCs sent to `id` to `raft`.
+    pub fn add_node(&self, id: C::NodeId, raft: Raft<C>) {
+        let mut peers = self.peers.lock().unwrap();
+        peers.entry(id).or_default().raft = Some(raft);
+    }
+
+    /// Stop delivering RPCs to `id`; They fail with an [`Unreachable`] error.
+    ///
+    /// The scripts of the peer are kept.
+    pub fn remove_node(&self, id: &C::NodeId) -> Option<Raft<C>> {
+        let mut peers = self.peers.lock().unwrap();
+        peers.get_mut(id).and_then(|p| p.raft.take())
+    }
+
+    /// Add `delay` to every RPC sent to `target`, or remove it if `delay` is `None`.
+    pub fn set_delay(&self, target: C::NodeId, delay: Option<Duration>) {
+        let mut peers = self.peers.lock().unwrap();
+        peers.entry(target).or_default().delay = delay;
+    }
+
+    /// Append a behavior for the next RPC of `rpc_type` sent to `target`.
+    ///
+    /// The behaviors of a peer and a type are used in the order they are scripted, one for every
+    /// RPC. An RPC without a scripted behavior is delivered.
+    pub fn script(&self, target: C::NodeId, rpc_type: RPCTypes, behavior: MockBehavior<C>) {
+        let mut peers = self.peers.lock().unwrap();
+        peers.entry(target).or_default().scripts.entry(rpc_type).or_default().push_back(behavior);
+    }
+
+    /// Drop the next `n` RPCs of `rpc_type` sent to `target`.
+    pub fn drop_next(&self, target: C::NodeId, rpc_type: RPCTypes, n: usize) {
+        for _ in 0..n {
+            self.script(target.clone(), rpc_type, MockBehavior::Drop);
+        }
+    }
+
+    /// Returns the number of scripted behaviors of `rpc_type` for `target` that are not used yet.
+    pub fn pending(&self, target: &C::NodeId, rpc_type: RPCTypes) -> usize {
+        let peers = self.peers.lock().unwrap();
+        peers.get(target).and_then(|p| p.scripts.get(&rpc_type)).map(|s| s.len()).unwrap_or_default()
+    }
+
+    /// Remove the delay and all the scripted behaviors of `target`.
+    pub fn clear(&self, target: &C::NodeId) {
+        let mut peers = self.peers.lock().unwrap();
+        if let Some(peer) = peers.get_mut(target) {
+            peer.delay = None;
+            peer.scripts.clear();
+        }
+    }
+}
+
+impl<C> RaftNetworkFactory<C> for MockNetwork<C>
+where
+    C: RaftTypeConfig,
+    MockPeer<C>: RaftNetworkV2<C>,
+{
+    type Network = MockPeer<C>;
+
+    async fn new_client(&mut self, target: C::NodeId, _node: &C::Node) -> Self::Network {
+        MockPeer {
+            target,
+            network: self.clone(),
+        }
+    }
+}
+
+/// The network of a [`MockNetwork`] that sends RPCs to one peer.
+pub struct MockPeer<C>
+where C: RaftTypeConfig
+{
+    target: C::NodeId,
+    network: MockNetwork<C>,
+}
+
+impl<C> MockPeer<C>
+where C: RaftTypeConfig
+{
+    /// Apply the delay and the next scripted behavior of `rpc_type`.
+    ///
+    /// It returns the target [`Raft`] to deliver the RPC to, and the behavior that changes the
+    /// response, if there is one.
+    async fn prepare<E>(&self, rpc_type: RPCTypes) -> Result<(Raft<C>, Option<MockBehavior<C>>), RPCError<C, E>>
+    where E: Error {
+        let (raft, delay, behavior) = {
+            let mut peers = self.network.peers.lock().unwrap();
+            let peer = peers.entry(self.target.clone()).or_default();
+            let behavior = peer.scripts.get_mut(&rpc_type).and_then(|s| s.pop_front());
+            (peer.raft.clone(), peer.delay, behavior)
+        };
+
+        tracing::debug!(
+            target = display(&self.target),
+            rpc_type = display(rpc_type),
+            behavior = debug(&behavior),
+            "MockNetwork: send RPC"
+        );
+
+        if let Some(delay) = delay {
+            C::sleep(delay).await;
+        }
+
+        let behavior = match behavior {
+            Some(MockBehavior::Delay(d)) => {
+                C::sleep(d).await;
+                None
+            }
+            Some(MockBehavior::Drop) => {
+                let e = AnyError::error(format!("MockNetwork: drop {} to {}", rpc_type, self.target));
+                return Err(RPCError::Network(NetworkError::new(&e)));
+            }
+            Some(MockBehavior::Unreachable) => {
+                return Err(self.unreachable("scripted unreachable"));
+            }
+            x => x,
+        };
+
+        let Some(raft) = raft else {
+            return Err(self.unreachable("node not found"));
+        };
+
+        Ok((raft, behavior))
+    }
+
+    fn unreachable<E>(&self, reason: impl fmt::Display) -> RPCError<C, E>
+    where E: Error {
+        let e = AnyError::error(format!("MockNetwork: {}: target={}", reason, self.target));
+        RPCError::Unreachable(Unreachable::new(&e))
+    }
+
+    async fn send_append_entries<E>(
+        &mut self,
+        mut rpc: AppendEntriesRequest<C>,
+    ) -> Result<AppendEntriesResponse<C>, RPCError<C, E>>
+    where
+        E: Error,
+    {
+        let (raft, behavior) = self.prepare(RPCTypes::AppendEntries).await?;
+
+        let truncated = match behavior {
+            Some(MockBehavior::HigherVote(vote)) => return Ok(AppendEntriesResponse::HigherVote(vote)),
+            Some(MockBehavior::PartialSuccess(n)) if (n as usize) < rpc.entries.len() => {
+                rpc.entries.truncate(n as usize);
+                let matching = rpc.entries.last().map(|e| e.log_id()).or_else(|| rpc.prev_log_id.clone());
+                Some(matching)
+            }
+            _ => None,
+        };
+
+        let resp = raft.append_entries(rpc).await.map_err(|e| self.unreachable(e))?;
+
+        match (resp, truncated) {
+            (AppendEntriesResponse::Success, Some(matching)) => Ok(AppendEntriesResponse::PartialSuccess(matching)),
+            (resp, _) => Ok(resp),
+        }
+    }
+
+    async fn send_vote<E>(&mut self, rpc: VoteRequest<C>) -> Result<VoteResponse<C>, RPCError<C, E>>
+    where E: Error {
+        let (raft, behavior) = self.prepare(RPCTypes::Vote).await?;
+
+        if let Some(MockBehavior::HigherVote(vote)) = behavior {
+            return Ok(VoteResponse::new(vote, None, false));
+        }
+
+        raft.vote(rpc).await.map_err(|e| self.unreachable(e))
+    }
+}
+
+#[cfg(not(all(feature = "tokio-rt", feature = "adapt-network-v1")))]
+mod network_v2 {
+    use std::future::Future;
+
+    use super::MockBehavior;
+    use super::MockPeer;
+    use crate::OptionalSend;
+    use crate::RaftTypeConfig;
+    use crate::error::RPCError;
+    use crate::error::ReplicationClosed;
+    use crate::error::StreamingError;
+    use crate::network::RPCOption;
+    use crate::network::RPCTypes;
+    use crate::network::v2::RaftNetworkV2;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::AppendEntriesResponse;
+    use crate::raft::SnapshotResponse;
+    use crate::raft::VoteRequest;
+    use crate::raft::VoteResponse;
+    use crate::raft::message::TransferLeaderRequest;
+    use crate::storage::Snapshot;
+    use crate::type_config::alias::VoteOf;
+
+    impl<C> RaftNetworkV2<C> for MockPeer<C>
+    where C: RaftTypeConfig
+    {
+        async fn append_entries(
+            &mut self,
+            rpc: AppendEntriesRequest<C>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<C>, RPCError<C>> {
+            self.send_append_entries(rpc).await
+        }
+
+        async fn vote(&mut self, rpc: VoteRequest<C>, _option: RPCOption) -> Result<VoteResponse<C>, RPCError<C>> {
+            self.send_vote(rpc).await
+        }
+
+        async fn full_snapshot(
+            &mut self,
+            vote: VoteOf<C>,
+            snapshot: Snapshot<C>,
+            _cancel: impl Future<Output = ReplicationClosed> + OptionalSend + 'static,
+            _option: RPCOption,
+        ) -> Result<SnapshotResponse<C>, StreamingError<C>> {
+            let (raft, behavior) = self.prepare(RPCTypes::InstallSnapshot).await?;
+
+            if let Some(MockBehavior::HigherVote(vote)) = behavior {
+                return Ok(SnapshotResponse::new(vote));
+            }
+
+            let resp = raft.install_full_snapshot(vote, snapshot).await.map_err(|e| self.unreachable(e))?;
+            Ok(resp)
+        }
+
+        async fn transfer_leader(
+            &mut self,
+            req: TransferLeaderRequest<C>,
+            _option: RPCOption,
+        ) -> Result<(), RPCError<C>> {
+            let (raft, _behavior) = self.prepare(RPCTypes::TransferLeader).await?;
+            raft.handle_transfer_leader(req).await.map_err(|e| self.unreachable(e))
+        }
+    }
+}
+
+#[cfg(all(feature = "tokio-rt", feature = "adapt-network-v1"))]
+mod network_v1 {
+    use super::MockBehavior;
+    use super::MockPeer;
+    use crate::RaftNetwork;
+    use crate::RaftTypeConfig;
+    use crate::error::InstallSnapshotError;
+    use crate::error::RPCError;
+    use crate::error::RaftError;
+    use crate::network::RPCOption;
+    use crate::network::RPCTypes;
+    use crate::raft::AppendEntriesRequest;
+    use crate::raft::AppendEntriesResponse;
+    use crate::raft::InstallSnapshotRequest;
+    use crate::raft::InstallSnapshotResponse;
+    use crate::raft::VoteRequest;
+    use crate::raft::VoteResponse;
+
+    impl<C> RaftNetwork<C> for MockPeer<C>
+    where
+        C: RaftTypeConfig,
+        C::SnapshotData: tokio::io::AsyncRead + tokio::io::AsyncWrite + tokio::io::AsyncSeek + Unpin,
+    {
+        async fn append_entries(
+            &mut self,
+            rpc: AppendEntriesRequest<C>,
+            _option: RPCOption,
+        ) -> Result<AppendEntriesResponse<C>, RPCError<C, RaftError<C>>> {
+            self.send_append_entries(rpc).await
+        }
+
+        async fn install_snapshot(
+            &mut self,
+            rpc: InstallSnapshotRequest<C>,
+            _option: RPCOption,
+        ) -> Result<InstallSnapshotResponse<C>, RPCError<C, RaftError<C, InstallSnapshotError>>> {
+            let (raft, behavior) = self.prepare(RPCTypes::InstallSnapshot).await?;
+
+            if let Some(MockBehavior::HigherVote(vote)) = behavior {
+                return Ok(InstallSnapshotResponse { vote });
+            }
+
+            raft.install_snapshot(rpc).await.map_err(|e| self.unreachable(e))
+        }
+
+        async fn vote(
+            &mut self,
+            rpc: VoteRequest<C>,
+            _option: RPCOption,
+        ) -> Result<VoteResponse<C>, RPCError<C, RaftError<C>>> {
+            self.send_vote(rpc).await
+        }
+    }
+}
@@ -44,6 +44,7 @@ pub struct Suite<Rt: AsyncRuntime> {
 impl<Rt: AsyncRuntime> Suite<Rt> {
     pub async fn test_all() {
         Self::test_spawn_join_handle().await;
+        Self::test_spawn_named().await;
         Self::test_sleep().await;
         Self::test_instant().await;
         Self::test_sleep_until().await;
@@ -82,6 +83,11 @@ impl<Rt: AsyncRuntime> Suite<Rt> {
         }
     }
 
+    pub async fn test_spawn_named() {
+        let handle = Rt::spawn_named("openraft-test", async { 42 });
+        assert_eq!(42, handle.await.unwrap());
+    }
+
     pub async fn test_sleep() {
         let start_time = std::time::Instant::now();
         let dur_10ms = std::time::Duration::from_millis(10);
@@ -0,0 +1,121 @@
+//! The binary format of an [`EngineTrace`]:
+//!
+//! ```text
+//! MAGIC | frame(header) | frame(input) | frame(input) | ...
+//! ```
+//!
+//! A frame is a 4 bytes little-endian length followed by the JSON encoded value. Inputs are
+//! encoded one per frame so that a trace written by an interrupted process can still be decoded
+//! up to its last complete frame.
+
+use crate::RaftTypeConfig;
+use crate::engine::EngineConfig;
+use crate::trace::EngineInput;
+use crate::trace::EngineTrace;
+use crate::trace::initial_state::InitialState;
+
+const MAGIC: &[u8; 8] = b"ORTRACE1";
+
+/// Error returned when the bytes can not be decoded into an [`EngineTrace`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TraceDecodeError {
+    /// The bytes do not start with the engine trace header.
+    #[error("not an engine trace: magic header mismatch")]
+    InvalidMagic,
+
+    /// A frame is shorter than its declared length.
+    #[error("incomplete frame at byte {offset}")]
+    Incomplete { offset: usize },
+
+    /// A frame can not be decoded.
+    #[error("invalid frame at byte {offset}: {reason}")]
+    InvalidFrame { offset: usize, reason: String },
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(bound = "")]
+struct Header<C>
+where C: RaftTypeConfig
+{
+    config: EngineConfig<C>,
+    initial_state: InitialState<C>,
+    truncated: bool,
+}
+
+impl<C> EngineTrace<C>
+where C: RaftTypeConfig
+{
+    /// Encode the trace into bytes that can be attached to a bug report.
+    ///
+    /// Decode it with [`EngineTrace::decode()`].
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = MAGIC.to_vec();
+
+        let header = Header {
+            config: self.config.clone(),
+            initial_state: self.initial_state.clone(),
+            truncated: self.truncated,
+        };
+        write_frame(&mut buf, &header);
+
+        for input in self.inputs.iter() {
+            write_frame(&mut buf, input);
+        }
+
+        buf
+    }
+
+    /// Decode a trace encoded by [`EngineTrace::encode()`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, TraceDecodeError> {
+        let Some(mut rest) = bytes.strip_prefix(MAGIC.as_slice()) else {
+            return Err(TraceDecodeError::InvalidMagic);
+        };
+
+        let mut offset = MAGIC.len();
+
+        let header: Header<C> = read_frame(&mut rest, &mut offset)?;
+        let mut inputs = vec![];
+
+        while !rest.is_empty() {
+            let input: EngineInput<C> = read_frame(&mut rest, &mut offset)?;
+            inputs.push(input);
+        }
+
+        Ok(Self {
+            config: header.config,
+            initial_state: header.initial_state,
+            inputs,
+            truncated: header.truncated,
+        })
+    }
+}
+
+fn write_frame<T: serde::Serialize>(buf: &mut Vec<u8>, value: &T) {
+    // Safe unwrap(): serializing to a Vec<u8> with the derived Serialize never fails.
+    let frame = serde_json::to_vec(value).unwrap();
+    buf.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&frame);
+}
+
+fn read_frame<T>(rest: &mut &[u8], offset: &mut usize) -> Result<T, TraceDecodeError>
+where T: serde::de::DeserializeOwned {
+    let Some((len, body)) = rest.split_first_chunk::<4>() else {
+        return Err(TraceDecodeError::Incomplete { offset: *offset });
+    };
+    let len = u32::from_le_bytes(*len) as usize;
+
+    if body.len() < len {
+        return Err(TraceDecodeError::Incomplete { offset: *offset });
+    }
+
+    let (frame, remaining) = body.split_at(len);
+    let value = serde_json::from_slice(frame).map_err(|e| TraceDecodeError::InvalidFrame {
+        offset: *offset,
+        reason: e.to_string(),
+    })?;
+
+    *rest = remaining;
+    *offset += 4 + len;
+
+    Ok(value)
+}
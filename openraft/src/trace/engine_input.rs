@@ -0,0 +1,385 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::RaftState;
+use crate::RaftTypeConfig;
+use crate::ServerState;
+use crate::StoredMembership;
+use crate::display_ext::DisplayOptionExt;
+use crate::display_ext::DisplaySliceExt;
+use crate::raft::VoteRequest;
+use crate::raft::VoteResponse;
+use crate::raft_state::LogStateReader;
+use crate::raft_state::io_state::io_id::IOId;
+use crate::storage::SnapshotMeta;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::vote::RaftVote;
+use crate::vote::raft_vote::RaftVoteExt;
+
+/// An input fed to the Raft engine, as recorded by [`Raft::engine_trace()`].
+///
+/// An input is recorded only when it reaches the engine: messages that are dropped by `RaftCore`,
+/// such as a stale response, are not recorded.
+///
+/// [`Raft::engine_trace()`]: crate::Raft::engine_trace
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub enum EngineInput<C>
+where C: RaftTypeConfig
+{
+    /// The engine is started.
+    Startup,
+
+    /// Initialize the cluster with the first membership entry.
+    Initialize { entry: TraceEntry<C> },
+
+    /// Initialize the cluster from a snapshot.
+    ///
+    /// The snapshot data is not recorded, thus it can not be replayed.
+    InitializeFromSnapshot { meta: SnapshotMeta<C> },
+
+    /// Start an election, e.g., by `Raft::trigger().elect()` or a leader transfer.
+    Elect,
+
+    /// The election timeout passed and an election is started.
+    ElectionTimeout,
+
+    /// A `RequestVote` RPC is received.
+    VoteRequest { req: VoteRequest<C> },
+
+    /// A response to a `RequestVote` RPC sent by this candidate is received.
+    VoteResponse { target: C::NodeId, resp: VoteResponse<C> },
+
+    /// A remote node replied with a vote greater than this leader's.
+    HigherVote { higher: VoteOf<C> },
+
+    /// An `AppendEntries` RPC is received.
+    AppendEntries {
+        vote: VoteOf<C>,
+        prev_log_id: Option<LogIdOf<C>>,
+        entries: Vec<TraceEntry<C>>,
+        leader_commit: Option<LogIdOf<C>>,
+    },
+
+    /// Start receiving a snapshot in chunks.
+    BeginReceivingSnapshot,
+
+    /// A full snapshot is received.
+    ///
+    /// The snapshot data is not recorded, thus it can not be replayed.
+    InstallFullSnapshot { vote: VoteOf<C>, meta: SnapshotMeta<C> },
+
+    /// The leader proposes a log entry.
+    WriteEntry { entry: TraceEntry<C> },
+
+    /// The leader sends a heartbeat to every follower.
+    Heartbeat,
+
+    /// The leader starts replicating to idle replication streams.
+    InitiateReplication,
+
+    /// Build a snapshot.
+    TriggerSnapshot,
+
+    /// Purge logs up to the index.
+    PurgeLog { upto: u64 },
+
+    /// Start transferring leadership to a node.
+    TriggerTransferLeader { to: C::NodeId },
+
+    /// The current leader asked to transfer leadership to `to`.
+    HandleTransferLeader { to: C::NodeId },
+
+    /// Allow or disallow the next log reversion of a follower.
+    AllowNextRevert { to: C::NodeId, allow: bool },
+
+    /// Resync a follower or learner with a full snapshot.
+    Resync { to: C::NodeId },
+
+    /// A leader that is not in the committed membership steps down.
+    LeaderStepDown,
+
+    /// A local IO, writing vote or log, is flushed to disk.
+    LocalIO { io_id: TraceIOId<C> },
+
+    /// A replication stream reported the matching or conflicting log id of a follower.
+    ReplicationProgress {
+        target: C::NodeId,
+        result: Result<Result<Option<LogIdOf<C>>, LogIdOf<C>>, String>,
+        has_payload: bool,
+    },
+
+    /// A follower acknowledged a heartbeat or replication sent at `sending_time`.
+    ///
+    /// `sending_time` is the duration since the recording started.
+    LeaderClock { target: C::NodeId, sending_time: Duration },
+
+    /// A panicked replication stream is restarted.
+    RestartReplication { target: C::NodeId },
+
+    /// The state machine finished, or deferred, building a snapshot.
+    SnapshotBuilt { meta: Option<SnapshotMeta<C>> },
+
+    /// The state machine installed a snapshot.
+    SnapshotInstalled {
+        io_id: TraceIOId<C>,
+        meta: Option<SnapshotMeta<C>>,
+    },
+
+    /// The state machine applied logs up to `last_applied`.
+    Applied { last_applied: LogIdOf<C> },
+
+    /// `RaftCore` ran the queued engine commands, reaching the state `digest`.
+    ///
+    /// This is a checkpoint rather than an input: the replayer runs the queued commands the same
+    /// way and compares its state with `digest`.
+    RunCommands { digest: StateDigest<C> },
+}
+
+impl<C> fmt::Display for EngineInput<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineInput::Startup => write!(f, "Startup"),
+            EngineInput::Initialize { entry } => write!(f, "Initialize({})", entry),
+            EngineInput::InitializeFromSnapshot { meta } => write!(f, "InitializeFromSnapshot({})", meta),
+            EngineInput::Elect => write!(f, "Elect"),
+            EngineInput::ElectionTimeout => write!(f, "ElectionTimeout"),
+            EngineInput::VoteRequest { req } => write!(f, "VoteRequest({})", req),
+            EngineInput::VoteResponse { target, resp } => {
+                write!(f, "VoteResponse(target={}, {})", target, resp)
+            }
+            EngineInput::HigherVote { higher } => write!(f, "HigherVote({})", higher),
+            EngineInput::AppendEntries {
+                vote,
+                prev_log_id,
+                entries,
+                leader_commit,
+            } => {
+                write!(
+                    f,
+                    "AppendEntries(vote={}, prev_log_id={}, entries={}, leader_commit={})",
+                    vote,
+                    prev_log_id.display(),
+                    entries.display(),
+                    leader_commit.display()
+                )
+            }
+            EngineInput::BeginReceivingSnapshot => write!(f, "BeginReceivingSnapshot"),
+            EngineInput::InstallFullSnapshot { vote, meta } => {
+                write!(f, "InstallFullSnapshot(vote={}, meta={})", vote, meta)
+            }
+            EngineInput::WriteEntry { entry } => write!(f, "WriteEntry({})", entry),
+            EngineInput::Heartbeat => write!(f, "Heartbeat"),
+            EngineInput::InitiateReplication => write!(f, "InitiateReplication"),
+            EngineInput::TriggerSnapshot => write!(f, "TriggerSnapshot"),
+            EngineInput::PurgeLog { upto } => write!(f, "PurgeLog(upto={})", upto),
+            EngineInput::TriggerTransferLeader { to } => write!(f, "TriggerTransferLeader(to={})", to),
+            EngineInput::HandleTransferLeader { to } => write!(f, "HandleTransferLeader(to={})", to),
+            EngineInput::AllowNextRevert { to, allow } => {
+                write!(f, "AllowNextRevert(to={}, allow={})", to, allow)
+            }
+            EngineInput::Resync { to } => write!(f, "Resync(to={})", to),
+            EngineInput::LeaderStepDown => write!(f, "LeaderStepDown"),
+            EngineInput::LocalIO { io_id } => write!(f, "LocalIO({})", io_id),
+            EngineInput::ReplicationProgress {
+                target,
+                result,
+                has_payload,
+            } => {
+                let result = match result {
+                    Ok(Ok(matching)) => format!("Match:{}", matching.display()),
+                    Ok(Err(conflict)) => format!("Conflict:{}", conflict),
+                    Err(e) => format!("Error:{}", e),
+                };
+                write!(
+                    f,
+                    "ReplicationProgress(target={}, {}, has_payload={})",
+                    target, result, has_payload
+                )
+            }
+            EngineInput::LeaderClock { target, sending_time } => {
+                write!(f, "LeaderClock(target={}, sending_time={:?})", target, sending_time)
+            }
+            EngineInput::RestartReplication { target } => write!(f, "RestartReplication(target={})", target),
+            EngineInput::SnapshotBuilt { meta } => write!(f, "SnapshotBuilt({})", meta.display()),
+            EngineInput::SnapshotInstalled { io_id, meta } => {
+                write!(f, "SnapshotInstalled(io_id={}, meta={})", io_id, meta.display())
+            }
+            EngineInput::Applied { last_applied } => write!(f, "Applied({})", last_applied),
+            EngineInput::RunCommands { digest } => write!(f, "RunCommands({})", digest),
+        }
+    }
+}
+
+/// A log entry in a trace.
+///
+/// [`RaftTypeConfig::Entry`] is not required to be `Clone`, thus an entry is copied through its
+/// serde form. Engine trace is disabled if the `serde` feature is not enabled.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TraceEntry<C>(pub C::Entry)
+where C: RaftTypeConfig;
+
+impl<C> TraceEntry<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn copy_from(entry: &C::Entry) -> Self {
+        #[cfg(feature = "serde")]
+        {
+            // Safe unwrap(): an entry is decoded from the value it is just encoded to.
+            let value = serde_json::to_value(entry).unwrap();
+            Self(serde_json::from_value(value).unwrap())
+        }
+
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = entry;
+            unreachable!("engine trace is disabled without feature `serde`")
+        }
+    }
+}
+
+impl<C> Clone for TraceEntry<C>
+where C: RaftTypeConfig
+{
+    fn clone(&self) -> Self {
+        Self::copy_from(&self.0)
+    }
+}
+
+impl<C> fmt::Debug for TraceEntry<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<C> fmt::Display for TraceEntry<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// The serializable form of an IO id: the vote that submitted the IO and the last log id written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct TraceIOId<C>
+where C: RaftTypeConfig
+{
+    /// The vote of the IO.
+    pub vote: VoteOf<C>,
+
+    /// The last log id written by the IO, or `None` if it only saves the vote.
+    pub log_id: Option<LogIdOf<C>>,
+}
+
+impl<C> fmt::Display for TraceIOId<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.vote, self.log_id.display())
+    }
+}
+
+impl<C> From<IOId<C>> for TraceIOId<C>
+where C: RaftTypeConfig
+{
+    fn from(io_id: IOId<C>) -> Self {
+        match io_id {
+            IOId::Vote(v) => Self {
+                vote: v.into_vote(),
+                log_id: None,
+            },
+            IOId::Log(l) => Self {
+                vote: l.committed_vote.into_vote(),
+                log_id: l.log_id,
+            },
+        }
+    }
+}
+
+impl<C> TraceIOId<C>
+where C: RaftTypeConfig
+{
+    /// Rebuild the IO id: a log IO is always submitted by a committed vote.
+    pub(crate) fn to_io_id(&self) -> IOId<C> {
+        if self.vote.is_committed() {
+            IOId::new_log_io(self.vote.clone().into_committed(), self.log_id.clone())
+        } else {
+            IOId::new_vote_io(self.vote.clone().into_non_committed())
+        }
+    }
+}
+
+/// A summary of the engine state, used to detect a divergence when replaying a trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct StateDigest<C>
+where C: RaftTypeConfig
+{
+    /// The vote of this node.
+    pub vote: VoteOf<C>,
+
+    /// The server state, such as `Leader` or `Follower`.
+    pub server_state: ServerState,
+
+    /// The last log id in the log.
+    pub last_log_id: Option<LogIdOf<C>>,
+
+    /// The last known committed log id.
+    pub committed: Option<LogIdOf<C>>,
+
+    /// The last log id applied to the state machine.
+    pub applied: Option<LogIdOf<C>>,
+
+    /// The last log id in the last built or installed snapshot.
+    pub snapshot: Option<LogIdOf<C>>,
+
+    /// The last purged log id.
+    pub purged: Option<LogIdOf<C>>,
+
+    /// The effective membership.
+    pub membership: StoredMembership<C>,
+}
+
+impl<C> StateDigest<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(state: &RaftState<C>) -> Self {
+        Self {
+            vote: state.vote_ref().clone(),
+            server_state: state.server_state,
+            last_log_id: state.last_log_id().cloned(),
+            committed: state.committed().cloned(),
+            applied: state.io_applied().cloned(),
+            snapshot: state.io_snapshot_last_log_id().cloned(),
+            purged: state.io_state().purged().cloned(),
+            membership: state.membership_state.effective().stored_membership().as_ref().clone(),
+        }
+    }
+}
+
+impl<C> fmt::Display for StateDigest<C>
+where C: RaftTypeConfig
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{vote: {}, server_state: {:?}, last_log_id: {}, committed: {}, applied: {}, snapshot: {}, purged: {}, membership: {}}}",
+            self.vote,
+            self.server_state,
+            self.last_log_id.display(),
+            self.committed.display(),
+            self.applied.display(),
+            self.snapshot.display(),
+            self.purged.display(),
+            self.membership
+        )
+    }
+}
@@ -0,0 +1,71 @@
+use crate::RaftTypeConfig;
+use crate::engine::EngineConfig;
+use crate::trace::EngineInput;
+use crate::trace::ReplayError;
+use crate::trace::ReplayReport;
+use crate::trace::initial_state::InitialState;
+use crate::trace::replay::Replayer;
+
+/// The inputs fed to the Raft engine of a node, together with the state the node started with.
+///
+/// It is returned by [`Raft::engine_trace()`] and can be replayed with [`EngineTrace::replay()`]
+/// to reproduce exactly how the engine state evolved.
+///
+/// [`Raft::engine_trace()`]: crate::Raft::engine_trace
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub struct EngineTrace<C>
+where C: RaftTypeConfig
+{
+    pub(crate) config: EngineConfig<C>,
+    pub(crate) initial_state: InitialState<C>,
+    pub(crate) inputs: Vec<EngineInput<C>>,
+
+    /// Whether inputs are dropped because `Config::engine_trace_max_inputs` is reached.
+    pub(crate) truncated: bool,
+}
+
+impl<C> EngineTrace<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(config: EngineConfig<C>, initial_state: InitialState<C>) -> Self {
+        Self {
+            config,
+            initial_state,
+            inputs: vec![],
+            truncated: false,
+        }
+    }
+
+    /// The id of the node that recorded this trace.
+    pub fn node_id(&self) -> &C::NodeId {
+        &self.config.id
+    }
+
+    /// The recorded inputs, in the order they are fed to the engine.
+    pub fn inputs(&self) -> &[EngineInput<C>] {
+        &self.inputs
+    }
+
+    /// Returns `true` if inputs after the first `Config::engine_trace_max_inputs` are dropped.
+    ///
+    /// A truncated trace can still be replayed, up to the last recorded input.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Feed the recorded inputs to a new engine built from the recorded initial state.
+    ///
+    /// At every [`EngineInput::RunCommands`] checkpoint the state of the replayed engine is
+    /// compared with the recorded one, and the first difference is returned as
+    /// [`ReplayError::Diverged`].
+    pub fn replay(&self) -> Result<ReplayReport, ReplayError<C>> {
+        let mut replayer = Replayer::new(self.initial_state.to_raft_state(&self.config.id), self.config.clone());
+
+        for (at, input) in self.inputs.iter().enumerate() {
+            replayer.apply(at, input)?;
+        }
+
+        Ok(replayer.into_report())
+    }
+}
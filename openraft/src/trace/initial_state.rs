@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use validit::Valid;
+
+use crate::EffectiveMembership;
+use crate::LogIdOptionExt;
+use crate::MembershipState;
+use crate::RaftState;
+use crate::RaftTypeConfig;
+use crate::StoredMembership;
+use crate::engine::LogIdList;
+use crate::raft_state::IOState;
+use crate::raft_state::LogStateReader;
+use crate::storage::SnapshotMeta;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::LogIdOf;
+use crate::type_config::alias::VoteOf;
+use crate::utime::Leased;
+
+/// The persisted state a Raft node started with, from which a trace is replayed.
+///
+/// It contains only what [`RaftState`] loads from storage; the volatile fields are rebuilt the same
+/// way as when a node starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize), serde(bound = ""))]
+pub(crate) struct InitialState<C>
+where C: RaftTypeConfig
+{
+    pub(crate) vote: VoteOf<C>,
+    pub(crate) purged: Option<LogIdOf<C>>,
+    pub(crate) key_log_ids: Vec<LogIdOf<C>>,
+    pub(crate) committed_membership: StoredMembership<C>,
+    pub(crate) effective_membership: StoredMembership<C>,
+    pub(crate) snapshot_meta: SnapshotMeta<C>,
+    pub(crate) applied: Option<LogIdOf<C>>,
+    pub(crate) snapshot: Option<LogIdOf<C>>,
+    pub(crate) allow_io_notification_reorder: bool,
+}
+
+impl<C> InitialState<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(state: &RaftState<C>, allow_io_notification_reorder: bool) -> Self {
+        let mem = &state.membership_state;
+        Self {
+            vote: state.vote_ref().clone(),
+            purged: state.last_purged_log_id().cloned(),
+            key_log_ids: state.log_ids.key_log_ids().to_vec(),
+            committed_membership: mem.committed().stored_membership().as_ref().clone(),
+            effective_membership: mem.effective().stored_membership().as_ref().clone(),
+            snapshot_meta: state.snapshot_meta.clone(),
+            applied: state.io_applied().cloned(),
+            snapshot: state.io_snapshot_last_log_id().cloned(),
+            allow_io_notification_reorder,
+        }
+    }
+
+    pub(crate) fn to_raft_state(&self, id: &C::NodeId) -> RaftState<C> {
+        let io_state = IOState::new(
+            &id.to_string(),
+            &self.vote,
+            self.applied.clone(),
+            self.snapshot.clone(),
+            self.purged.clone(),
+            self.allow_io_notification_reorder,
+        );
+
+        let mem_state = MembershipState::new(
+            Arc::new(EffectiveMembership::new_from_stored_membership(
+                self.committed_membership.clone(),
+            )),
+            Arc::new(EffectiveMembership::new_from_stored_membership(
+                self.effective_membership.clone(),
+            )),
+        );
+
+        RaftState {
+            vote: Leased::new(C::now(), Duration::default(), self.vote.clone()),
+            purged_next: self.purged.next_index(),
+            log_ids: LogIdList::new(self.key_log_ids.clone()),
+            membership_state: mem_state,
+            snapshot_meta: self.snapshot_meta.clone(),
+
+            server_state: Default::default(),
+            io_state: Valid::new(io_state),
+            purge_upto: self.purged.clone(),
+            archive_next: None,
+        }
+    }
+}
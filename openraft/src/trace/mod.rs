@@ -0,0 +1,58 @@
+//! Record the inputs of the Raft engine and replay them deterministically.
+//!
+//! The engine is the deterministic core of a Raft node: given the same initial state and the same
+//! sequence of inputs (RPCs, ticks, client requests and IO completions), it produces the same state
+//! and the same commands. When [`Config::engine_trace_max_inputs`] is set, a node records the
+//! inputs fed to its engine, and [`Raft::engine_trace()`] returns them as an [`EngineTrace`].
+//!
+//! An application can attach the trace of a misbehaving node to a bug report, with
+//! `EngineTrace::encode()` when the `serde` feature is enabled, and a developer replays it with
+//! [`EngineTrace::replay()`] to reconstruct how the state evolved, without the cluster, the
+//! network or the storage:
+//!
+//! ```ignore
+//! // On the misbehaving node:
+//! let trace = raft.engine_trace().unwrap();
+//! std::fs::write("node-1.trace", trace.encode())?;
+//!
+//! // On the developer's machine:
+//! let trace = EngineTrace::<TypeConfig>::decode(&std::fs::read("node-1.trace")?)?;
+//! trace.replay()?;
+//! ```
+//!
+//! A replay that reaches a different state stops with [`ReplayError::Diverged`] at the first
+//! checkpoint that differs.
+//!
+//! # Limitations
+//!
+//! - The engine reads the wall clock, and the replay does not reproduce it: a decision that depends
+//!   on elapsed time, such as whether a leader lease has expired when a vote request is received,
+//!   is made again with the clock of the replay, and may differ from the recorded one. A trace of a
+//!   cluster in which elections and heartbeats race with each other, as with the default
+//!   [`Config`], is therefore not guaranteed to replay without divergence.
+//! - The data of a snapshot received from the leader is not recorded.
+//! - Recording requires the `serde` feature to copy log entries. Without it,
+//!   [`Config::engine_trace_max_inputs`] is ignored, a warning is logged when Raft starts, and
+//!   [`Raft::engine_trace()`] returns `None`.
+//!
+//! [`Config`]: crate::Config
+//! [`Config::engine_trace_max_inputs`]: crate::Config::engine_trace_max_inputs
+//! [`Raft::engine_trace()`]: crate::Raft::engine_trace
+
+#[cfg(feature = "serde")]
+mod codec;
+mod engine_input;
+mod engine_trace;
+pub(crate) mod initial_state;
+pub(crate) mod recorder;
+mod replay;
+
+#[cfg(feature = "serde")]
+pub use codec::TraceDecodeError;
+pub use engine_input::EngineInput;
+pub use engine_input::StateDigest;
+pub use engine_input::TraceEntry;
+pub use engine_input::TraceIOId;
+pub use engine_trace::EngineTrace;
+pub use replay::ReplayError;
+pub use replay::ReplayReport;
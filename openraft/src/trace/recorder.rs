@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::Instant;
+use crate::RaftTypeConfig;
+use crate::trace::EngineInput;
+use crate::trace::EngineTrace;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
+
+/// Records the inputs of the engine into an [`EngineTrace`].
+///
+/// Once `max_inputs` inputs are recorded, further inputs are dropped and the trace is marked as
+/// truncated: a trace can only be replayed from the initial state, so the earliest inputs are
+/// kept.
+pub(crate) struct TraceRecorder<C>
+where C: RaftTypeConfig
+{
+    max_inputs: u64,
+
+    /// When the recording started; time in the trace is relative to it.
+    start: InstantOf<C>,
+
+    trace: EngineTrace<C>,
+}
+
+impl<C> TraceRecorder<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(max_inputs: u64, trace: EngineTrace<C>) -> Self {
+        Self {
+            max_inputs,
+            start: C::now(),
+            trace,
+        }
+    }
+
+    pub(crate) fn record(&mut self, input: EngineInput<C>) {
+        // Nothing is fed to the engine since the last checkpoint.
+        if matches!(input, EngineInput::RunCommands { .. })
+            && matches!(self.trace.inputs.last(), Some(EngineInput::RunCommands { .. }))
+        {
+            return;
+        }
+
+        if self.trace.inputs.len() as u64 >= self.max_inputs {
+            self.trace.truncated = true;
+            return;
+        }
+
+        self.trace.inputs.push(input);
+    }
+}
+
+/// A handle to a [`TraceRecorder`] shared by `RaftCore` and `Raft`, or a no-op if engine trace is
+/// disabled.
+#[derive(Clone)]
+pub(crate) struct EngineRecorder<C>
+where C: RaftTypeConfig
+{
+    inner: Option<Arc<Mutex<TraceRecorder<C>>>>,
+}
+
+impl<C> EngineRecorder<C>
+where C: RaftTypeConfig
+{
+    /// Create a recorder that records at most `max_inputs` inputs, or a disabled one if
+    /// `max_inputs` is 0 or feature `serde` is not enabled.
+    pub(crate) fn new(max_inputs: u64, trace: impl FnOnce() -> EngineTrace<C>) -> Self {
+        let inner = if max_inputs == 0 {
+            None
+        } else if cfg!(not(feature = "serde")) {
+            tracing::warn!("engine trace is disabled: it requires feature `serde` to copy log entries");
+            None
+        } else {
+            Some(Arc::new(Mutex::new(TraceRecorder::new(max_inputs, trace()))))
+        };
+
+        Self { inner }
+    }
+
+    /// Record an input built by `f`, which is called only if the recording is enabled.
+    pub(crate) fn record(&self, f: impl FnOnce() -> EngineInput<C>) {
+        if let Some(inner) = &self.inner {
+            inner.lock().unwrap().record(f());
+        }
+    }
+
+    /// Record a leader clock update, converting `sending_time` to the time since the recording
+    /// started.
+    pub(crate) fn record_leader_clock(&self, target: &C::NodeId, sending_time: InstantOf<C>) {
+        if let Some(inner) = &self.inner {
+            let mut r = inner.lock().unwrap();
+            let sending_time = sending_time.saturating_duration_since(r.start);
+            r.record(EngineInput::LeaderClock {
+                target: target.clone(),
+                sending_time,
+            });
+        }
+    }
+
+    /// Returns a copy of the trace recorded so far, or `None` if the recording is disabled.
+    pub(crate) fn trace(&self) -> Option<EngineTrace<C>> {
+        self.inner.as_ref().map(|x| x.lock().unwrap().trace.clone())
+    }
+}
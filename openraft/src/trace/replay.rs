@@ -0,0 +1,299 @@
+use std::fmt;
+
+use crate::RaftState;
+use crate::RaftTypeConfig;
+use crate::engine::Command;
+use crate::engine::Engine;
+use crate::engine::EngineConfig;
+use crate::entry::RaftEntry;
+use crate::raft_state::io_state::io_id::IOId;
+use crate::replication::response::ReplicationResult;
+use crate::trace::EngineInput;
+use crate::trace::StateDigest;
+use crate::type_config::TypeConfigExt;
+use crate::type_config::alias::InstantOf;
+
+/// A summary of a successful replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// The number of replayed inputs, including checkpoints.
+    pub inputs: usize,
+
+    /// The number of checkpoints at which the replayed state matched the recorded one.
+    pub checkpoints: usize,
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ReplayReport{{inputs: {}, checkpoints: {}}}",
+            self.inputs, self.checkpoints
+        )
+    }
+}
+
+/// Error returned when a trace can not be replayed to the end.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ReplayError<C>
+where C: RaftTypeConfig
+{
+    /// The replayed state differs from the recorded one at the checkpoint `at`.
+    #[error("replay diverged at input {at}: recorded: {recorded}, replayed: {replayed}")]
+    Diverged {
+        at: usize,
+        recorded: StateDigest<C>,
+        replayed: StateDigest<C>,
+    },
+
+    /// The input `at` does not contain enough data to be replayed.
+    #[error("input {at} can not be replayed: {input}")]
+    Unsupported { at: usize, input: String },
+}
+
+/// Feeds recorded inputs to an [`Engine`] and runs its commands the same way `RaftCore` does,
+/// without doing any IO.
+pub(crate) struct Replayer<C>
+where C: RaftTypeConfig
+{
+    engine: Engine<C>,
+
+    /// The time the recorded relative times are added to.
+    start: InstantOf<C>,
+
+    inputs: usize,
+    checkpoints: usize,
+}
+
+impl<C> Replayer<C>
+where C: RaftTypeConfig
+{
+    pub(crate) fn new(state: RaftState<C>, config: EngineConfig<C>) -> Self {
+        Self {
+            engine: Engine::new(state, config),
+            start: C::now(),
+            inputs: 0,
+            checkpoints: 0,
+        }
+    }
+
+    pub(crate) fn into_report(self) -> ReplayReport {
+        ReplayReport {
+            inputs: self.inputs,
+            checkpoints: self.checkpoints,
+        }
+    }
+
+    pub(crate) fn apply(&mut self, at: usize, input: &EngineInput<C>) -> Result<(), ReplayError<C>> {
+        self.inputs += 1;
+
+        let engine = &mut self.engine;
+
+        match input.clone() {
+            EngineInput::Startup => engine.startup(),
+            EngineInput::Initialize { entry } => {
+                let _ = engine.initialize(entry.0);
+            }
+            EngineInput::Elect => engine.elect(),
+            EngineInput::ElectionTimeout => {
+                engine.reset_greater_log();
+                engine.elect();
+            }
+            EngineInput::VoteRequest { req } => {
+                let _ = engine.handle_vote_req(req);
+            }
+            EngineInput::VoteResponse { target, resp } => engine.handle_vote_resp(target, resp),
+            EngineInput::HigherVote { higher } => {
+                let _ = engine.vote_handler().update_vote(&higher);
+            }
+            EngineInput::AppendEntries {
+                vote,
+                prev_log_id,
+                entries,
+                leader_commit,
+            } => {
+                let (tx, _rx) = C::oneshot();
+                let entries = entries.into_iter().map(|x| x.0).collect();
+                if engine.handle_append_entries(&vote, prev_log_id, entries, tx) {
+                    engine.handle_commit_entries(leader_commit);
+                }
+            }
+            EngineInput::BeginReceivingSnapshot => {
+                let (tx, _rx) = C::oneshot();
+                engine.handle_begin_receiving_snapshot(tx);
+            }
+            EngineInput::InstallFullSnapshot { .. } | EngineInput::InitializeFromSnapshot { .. } => {
+                return Err(ReplayError::Unsupported {
+                    at,
+                    input: input.to_string(),
+                });
+            }
+            EngineInput::WriteEntry { entry } => {
+                if let Ok(mut lh) = engine.leader_handler() {
+                    lh.leader_append_entries(vec![entry.0]);
+                }
+            }
+            EngineInput::Heartbeat => {
+                if let Ok(mut lh) = engine.leader_handler() {
+                    lh.send_heartbeat();
+                }
+            }
+            EngineInput::InitiateReplication => {
+                if let Ok(mut lh) = engine.leader_handler() {
+                    lh.replication_handler().initiate_replication();
+                }
+            }
+            EngineInput::TriggerSnapshot => {
+                engine.snapshot_handler().trigger_snapshot();
+            }
+            EngineInput::PurgeLog { upto } => engine.trigger_purge_log(upto),
+            EngineInput::TriggerTransferLeader { to } => engine.trigger_transfer_leader(to),
+            EngineInput::HandleTransferLeader { to } => {
+                engine.state.vote.disable_lease();
+                if engine.config.id == to {
+                    engine.elect();
+                }
+            }
+            EngineInput::AllowNextRevert { to, allow } => {
+                if let Ok(mut lh) = engine.leader_handler() {
+                    let _ = lh.replication_handler().allow_next_revert(to, allow);
+                }
+            }
+            EngineInput::Resync { to } => {
+                if let Ok(mut lh) = engine.leader_handler() {
+                    let _ = lh.replication_handler().resync(to);
+                }
+            }
+            EngineInput::LeaderStepDown => engine.leader_step_down(),
+            EngineInput::LocalIO { io_id } => {
+                let io_id = io_id.to_io_id();
+                engine.state.log_progress_mut().flush(io_id.clone());
+
+                if let IOId::Log(log_io_id) = io_id {
+                    let is_my_io = engine.leader.as_ref().map(|l| &l.committed_vote) == Some(&log_io_id.committed_vote);
+                    if is_my_io {
+                        engine.replication_handler().update_local_progress(log_io_id.log_id);
+                    }
+                }
+            }
+            EngineInput::ReplicationProgress {
+                target,
+                result,
+                has_payload,
+            } => {
+                if engine.leader.is_some() {
+                    let result = result.map(ReplicationResult);
+                    engine.replication_handler().update_progress(target, result, has_payload);
+                }
+            }
+            EngineInput::LeaderClock { target, sending_time } => {
+                if engine.leader.is_some() {
+                    engine.replication_handler().update_leader_clock(target, self.start + sending_time);
+                }
+            }
+            EngineInput::RestartReplication { target } => {
+                if engine.leader.is_some() {
+                    engine.replication_handler().restart_replication_stream(target);
+                }
+            }
+            EngineInput::SnapshotBuilt { meta } => engine.on_building_snapshot_done(meta),
+            EngineInput::SnapshotInstalled { io_id, meta } => {
+                engine.state.log_progress_mut().flush(io_id.to_io_id());
+
+                if let Some(last) = meta.and_then(|m| m.last_log_id) {
+                    let st = engine.state.io_state_mut();
+                    st.apply_progress.flush(last.clone());
+                    st.snapshot.flush(last);
+                }
+            }
+            EngineInput::Applied { last_applied } => {
+                engine.state.apply_progress_mut().flush(last_applied);
+            }
+            EngineInput::RunCommands { digest } => {
+                self.run_commands();
+
+                let replayed = StateDigest::new(&self.engine.state);
+                if replayed != digest {
+                    return Err(ReplayError::Diverged {
+                        at,
+                        recorded: digest,
+                        replayed,
+                    });
+                }
+                self.checkpoints += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run queued commands in the same order as `RaftCore::run_engine_commands()`.
+    fn run_commands(&mut self) {
+        self.send_satisfied_responds();
+
+        while let Some(cmd) = self.engine.output.pop_command() {
+            if let Some(condition) = cmd.condition()
+                && !condition.is_met(&self.engine.state.io_state)
+            {
+                if self.engine.output.postpone_command(cmd).is_ok() {
+                    continue;
+                }
+                break;
+            }
+
+            self.run_command(cmd);
+        }
+
+        while let Some(cmd) = self.engine.next_progress_driven_command() {
+            self.run_command(cmd);
+        }
+    }
+
+    fn send_satisfied_responds(&mut self) {
+        let io_state = self.engine.state.io_state();
+        for (_phase, respond) in self.engine.output.pending_responds.drain_satisfied(io_state) {
+            respond.send();
+        }
+    }
+
+    /// Update the IO progress the same way `RaftCore::run_command()` does when submitting an IO.
+    ///
+    /// The IO itself is not run: its completion is a recorded input.
+    fn run_command(&mut self, cmd: Command<C>) {
+        let st = &mut self.engine.state;
+
+        match cmd {
+            Command::UpdateIOProgress { io_id, .. } => st.log_progress_mut().submit(io_id),
+            Command::AppendEntries {
+                committed_vote,
+                entries,
+            } => {
+                let last_log_id = entries.last().unwrap().log_id();
+                st.log_progress_mut().submit(IOId::new_log_io(committed_vote, Some(last_log_id)));
+            }
+            Command::SaveVote { vote } => st.log_progress_mut().submit(IOId::new(&vote)),
+            Command::PurgeLog { upto } => st.io_state_mut().update_purged(Some(upto)),
+            Command::SaveCommittedAndApply { upto, .. } => st.apply_progress_mut().submit(upto),
+            Command::StateMachine { command } => {
+                if let Some(io_id) = command.get_log_progress() {
+                    st.log_progress_mut().submit(io_id);
+                }
+                if let Some(log_id) = command.get_apply_progress() {
+                    st.apply_progress_mut().submit(log_id);
+                }
+                if let Some(log_id) = command.get_snapshot_progress() {
+                    st.snapshot_progress_mut().submit(log_id);
+                }
+            }
+            Command::Respond { resp, .. } => resp.send(),
+            Command::TruncateLog { .. }
+            | Command::SendVote { .. }
+            | Command::ReplicateCommitted { .. }
+            | Command::BroadcastHeartbeat { .. }
+            | Command::Replicate { .. }
+            | Command::BroadcastTransferLeader { .. }
+            | Command::RebuildReplicationStreams { .. }
+            | Command::RestartReplicationStream { .. } => {}
+        }
+    }
+}
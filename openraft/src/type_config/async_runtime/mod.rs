@@ -33,6 +33,7 @@ pub use mpsc_unbounded::TryRecvError;
 pub use mutex::Mutex;
 pub use oneshot::Oneshot;
 pub use oneshot::OneshotSender;
+use openraft_macros::since;
 pub use watch::Watch;
 
 use crate::Instant;
@@ -49,6 +50,11 @@ use crate::OptionalSync;
 /// ## Note
 ///
 /// The default asynchronous runtime is `tokio`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as the `AsyncRuntime` of a `RaftTypeConfig`",
+    label = "`AsyncRuntime` is not implemented for `{Self}`",
+    note = "`declare_raft_types!` uses `openraft::impls::TokioRuntime` if `AsyncRuntime` is omitted, which requires the feature `tokio-rt`"
+)]
 pub trait AsyncRuntime: Debug + Default + PartialEq + Eq + OptionalSend + OptionalSync + 'static {
     /// The error type of [`Self::JoinHandle`].
     type JoinError: Debug + Display + OptionalSend;
@@ -81,6 +87,20 @@ pub trait AsyncRuntime: Debug + Default + PartialEq + Eq + OptionalSend + Option
         T: Future + OptionalSend + 'static,
         T::Output: OptionalSend + 'static;
 
+    /// Spawn a new task with a name, such as `openraft-core-1`, that identifies it in tools like
+    /// `tokio-console`.
+    ///
+    /// The default ignores the name and calls [`Self::spawn`].
+    #[since(version = "0.10.0")]
+    fn spawn_named<T>(name: &str, future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        let _ = name;
+        Self::spawn(future)
+    }
+
     /// Wait until `duration` has elapsed.
     fn sleep(duration: Duration) -> Self::Sleep;
 
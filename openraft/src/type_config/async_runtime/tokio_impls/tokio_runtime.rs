@@ -44,6 +44,33 @@ impl AsyncRuntime for TokioRuntime {
         }
     }
 
+    /// Name the task only if built with `--cfg tokio_unstable` and the `tokio-console` feature,
+    /// which Tokio requires to name a task.
+    #[inline]
+    fn spawn_named<T>(name: &str, future: T) -> Self::JoinHandle<T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        #[cfg(all(tokio_unstable, feature = "tokio-console"))]
+        {
+            let builder = tokio::task::Builder::new().name(name);
+
+            #[cfg(feature = "singlethreaded")]
+            let res = builder.spawn_local(future);
+            #[cfg(not(feature = "singlethreaded"))]
+            let res = builder.spawn(future);
+
+            // It fails the same way `tokio::task::spawn()` panics: outside a runtime.
+            res.expect("failed to spawn a task")
+        }
+        #[cfg(not(all(tokio_unstable, feature = "tokio-console")))]
+        {
+            let _ = name;
+            Self::spawn(future)
+        }
+    }
+
     #[inline]
     fn sleep(duration: Duration) -> Self::Sleep {
         tokio::time::sleep(duration)
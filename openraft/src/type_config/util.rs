@@ -125,6 +125,15 @@ pub trait TypeConfigExt: RaftTypeConfig {
     {
         AsyncRuntimeOf::<Self>::spawn(future)
     }
+
+    /// Spawn a new task with a name that identifies it in tools like `tokio-console`.
+    fn spawn_named<T>(name: &str, future: T) -> JoinHandleOf<Self, T::Output>
+    where
+        T: Future + OptionalSend + 'static,
+        T::Output: OptionalSend + 'static,
+    {
+        AsyncRuntimeOf::<Self>::spawn_named(name, future)
+    }
 }
 
 impl<T> TypeConfigExt for T where T: RaftTypeConfig {}
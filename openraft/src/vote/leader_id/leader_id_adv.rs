@@ -1,8 +1,12 @@
 //! [`RaftLeaderId`] implementation that allows multiple leaders per term.
 
 use std::fmt;
+use std::str::FromStr;
 
 use crate::RaftTypeConfig;
+use crate::storage::codec::CodecError;
+use crate::storage::codec::parse;
+use crate::storage::codec::split_leader_id;
 use crate::vote::RaftLeaderId;
 
 /// ID of a `leader`, allowing multiple leaders per term.
@@ -30,6 +34,26 @@ where C: RaftTypeConfig
     }
 }
 
+/// Parse the `Display` output, such as `T1-N2`.
+impl<C> FromStr for LeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FromStr,
+    <C::Term as FromStr>::Err: fmt::Display,
+    C::NodeId: FromStr,
+    <C::NodeId as FromStr>::Err: fmt::Display,
+{
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (term, node_id) = split_leader_id(s)?;
+        Ok(Self {
+            term: parse(term, "term")?,
+            node_id: parse(node_id, "node_id")?,
+        })
+    }
+}
+
 /// The unique identifier of a leader that is already granted by a quorum in phase-1(voting).
 ///
 /// [`CommittedLeaderId`] may contain less information than [`LeaderId`], because it implies the
@@ -4,9 +4,13 @@
 use std::cmp::Ordering;
 use std::fmt;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 use crate::RaftTypeConfig;
 use crate::display_ext::DisplayOptionExt;
+use crate::storage::codec::CodecError;
+use crate::storage::codec::parse;
+use crate::storage::codec::split_leader_id;
 use crate::vote::LeaderIdCompare;
 use crate::vote::RaftLeaderId;
 
@@ -45,6 +49,30 @@ where C: RaftTypeConfig
     }
 }
 
+/// Parse the `Display` output, such as `T1-N2`, or `T1-NNone` if it has not voted.
+impl<C> FromStr for LeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FromStr,
+    <C::Term as FromStr>::Err: fmt::Display,
+    C::NodeId: FromStr,
+    <C::NodeId as FromStr>::Err: fmt::Display,
+{
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (term, voted_for) = split_leader_id(s)?;
+        let voted_for = match voted_for {
+            "None" => None,
+            node_id => Some(parse(node_id, "node_id")?),
+        };
+        Ok(Self {
+            term: parse(term, "term")?,
+            voted_for,
+        })
+    }
+}
+
 impl<C> RaftLeaderId<C> for LeaderId<C>
 where C: RaftTypeConfig
 {
@@ -104,6 +132,20 @@ where C: RaftTypeConfig
     }
 }
 
+/// Parse the `Display` output, which is the term.
+impl<C> FromStr for CommittedLeaderId<C>
+where
+    C: RaftTypeConfig,
+    C::Term: FromStr,
+    <C::Term as FromStr>::Err: fmt::Display,
+{
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(parse(s, "term")?, C::NodeId::default()))
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::nonminimal_bool)]
 mod tests {
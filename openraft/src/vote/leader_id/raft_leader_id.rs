@@ -22,6 +22,11 @@ use crate::vote::leader_id::raft_committed_leader_id::RaftCommittedLeaderId;
 /// neither can overwrite the other.
 ///
 /// [`Vote`]: crate::vote::Vote
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as the `LeaderId` of `{C}`",
+    label = "`RaftLeaderId<{C}>` is not implemented for `{Self}`",
+    note = "the `LeaderId` must be built for the type config that declares it, e.g., `LeaderId = openraft::impls::leader_id_adv::LeaderId<Self>`, which is the default of `declare_raft_types!`"
+)]
 pub trait RaftLeaderId<C>
 where
     C: RaftTypeConfig,
@@ -14,6 +14,11 @@ use crate::base::OptionalFeatures;
 ///
 /// Common implementations are provided for standard integer types like `u64`, `i64`, etc.
 #[since(version = "0.10.0")]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as the `Term` of a `RaftTypeConfig`",
+    label = "`RaftTerm` is not implemented for `{Self}`",
+    note = "`RaftTerm` is implemented for the primitive integer types; `declare_raft_types!` uses `u64` if `Term` is omitted"
+)]
 pub trait RaftTerm
 where Self: OptionalFeatures + Ord + Debug + Display + Copy + Default + 'static
 {
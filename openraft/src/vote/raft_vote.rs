@@ -13,6 +13,11 @@ use crate::vote::vote_status::VoteStatus;
 
 /// Represents a vote in Raft consensus, including both votes for leader candidates
 /// and committed leader (a leader granted by a quorum).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be used as the `Vote` of `{C}`",
+    label = "`RaftVote<{C}>` is not implemented for `{Self}`",
+    note = "the `Vote` must be built for the type config that declares it, e.g., `Vote = openraft::impls::Vote<Self>`, which is the default of `declare_raft_types!`"
+)]
 pub trait RaftVote<C>
 where
     C: RaftTypeConfig,
@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
 use std::fmt::Formatter;
+use std::str::FromStr;
 
 use crate::RaftTypeConfig;
+use crate::storage::codec::CodecError;
 use crate::vote::RaftLeaderId;
 use crate::vote::RaftVote;
 use crate::vote::raft_vote::RaftVoteExt;
@@ -34,6 +36,36 @@ where C: RaftTypeConfig
     }
 }
 
+/// Parse the `Display` output, such as `<T1-N2:Q>` for a committed vote, or `<T1-N2:->`.
+impl<C> FromStr for Vote<C>
+where
+    C: RaftTypeConfig,
+    C::LeaderId: FromStr<Err = CodecError>,
+{
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CodecError::invalid(format_args!("vote: {:?}", s));
+
+        let (leader_id, committed) = s
+            .strip_prefix('<')
+            .and_then(|x| x.strip_suffix('>'))
+            .and_then(|x| x.rsplit_once(':'))
+            .ok_or_else(invalid)?;
+
+        let committed = match committed {
+            "Q" => true,
+            "-" => false,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self {
+            leader_id: leader_id.parse()?,
+            committed,
+        })
+    }
+}
+
 impl<C> RaftVote<C> for Vote<C>
 where C: RaftTypeConfig
 {
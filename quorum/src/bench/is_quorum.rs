@@ -1,11 +1,13 @@
 extern crate test;
 
+use alloc::vec;
+
 use maplit::btreeset;
 use test::Bencher;
 use test::black_box;
 
-use crate::quorum::AsJoint;
-use crate::quorum::QuorumSet;
+use crate::AsJoint;
+use crate::QuorumSet;
 
 #[bench]
 fn quorum_set_slice_ids_slice(b: &mut Bencher) {
@@ -1,11 +1,11 @@
-use crate::quorum::QuorumSet;
+use crate::QuorumSet;
 
 /// **Coherent** quorum set A and B is defined as: `∀ qᵢ ∈ A, ∀ qⱼ ∈ B: qᵢ ∩ qⱼ != ø`, i.e., `A ~
 /// B`.
 /// A distributed consensus protocol such as openraft is only allowed to switch membership
 /// between two **coherent** quorum sets. Being coherent is one of the two restrictions. The other
 /// restriction is to disable another smaller candidate to elect.
-pub(crate) trait Coherent<ID, Other>
+pub trait Coherent<ID, Other>
 where
     ID: PartialOrd + Ord + 'static,
     Self: QuorumSet<ID>,
@@ -15,7 +15,9 @@ where
     fn is_coherent_with(&self, other: &Other) -> bool;
 }
 
-pub(crate) trait FindCoherent<ID, Other>
+/// Find an intermediate quorum set to switch membership between two quorum sets that are not
+/// coherent.
+pub trait FindCoherent<ID, Other>
 where
     ID: PartialOrd + Ord + 'static,
     Self: QuorumSet<ID>,
@@ -1,7 +1,10 @@
-use crate::quorum::Coherent;
-use crate::quorum::Joint;
-use crate::quorum::QuorumSet;
-use crate::quorum::coherent::FindCoherent;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Coherent;
+use crate::Joint;
+use crate::QuorumSet;
+use crate::coherent::FindCoherent;
 
 impl<ID, QS> Coherent<ID, Joint<ID, QS, Vec<QS>>> for Joint<ID, QS, Vec<QS>>
 where
@@ -11,7 +14,7 @@ where
     /// Check if two `joint` are coherent.
     ///
     /// Read more about:
-    /// [Extended membership change](crate::docs::data::extended_membership)
+    /// [Extended membership change](https://docs.rs/openraft/latest/openraft/docs/data/extended_membership/index.html)
     fn is_coherent_with(&self, other: &Joint<ID, QS, Vec<QS>>) -> bool {
         for a in self.children() {
             for b in other.children() {
@@ -1,9 +1,11 @@
+use alloc::vec;
+
 use maplit::btreeset;
 
-use crate::quorum::Joint;
-use crate::quorum::coherent::Coherent;
-use crate::quorum::coherent::FindCoherent;
-use crate::quorum::joint::AsJoint;
+use crate::Joint;
+use crate::coherent::Coherent;
+use crate::coherent::FindCoherent;
+use crate::joint::AsJoint;
 
 #[test]
 fn test_is_coherent_vec() -> anyhow::Result<()> {
@@ -1,13 +1,13 @@
-use std::marker::PhantomData;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
-use maplit::btreeset;
-
-use crate::quorum::QuorumSet;
+use crate::QuorumSet;
 
 /// Use another data as a joint quorum set.
 ///
 /// The ids have to be a quorum in every sub-config to constitute a joint-quorum.
-pub(crate) trait AsJoint<'d, ID, QS, D>
+pub trait AsJoint<'d, ID, QS, D>
 where
     ID: 'static,
     QS: QuorumSet<ID>,
@@ -22,7 +22,7 @@ where
 #[derive(Clone, Debug, Default)]
 #[derive(PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub(crate) struct Joint<ID, QS, D>
+pub struct Joint<ID, QS, D>
 where
     ID: 'static,
     QS: QuorumSet<ID>,
@@ -36,11 +36,13 @@ where
     ID: 'static,
     QS: QuorumSet<ID>,
 {
-    pub(crate) fn new(data: D) -> Self {
+    /// Create a joint quorum set from its children quorum sets in `data`.
+    pub fn new(data: D) -> Self {
         Self { data, _p: PhantomData }
     }
 
-    pub(crate) fn children(&self) -> &D {
+    /// Returns the children quorum sets.
+    pub fn children(&self) -> &D {
         &self.data
     }
 }
@@ -51,7 +53,7 @@ where
     ID: PartialOrd + Ord + 'static,
     QS: QuorumSet<ID>,
 {
-    type Iter = std::collections::btree_set::IntoIter<ID>;
+    type Iter = alloc::collections::btree_set::IntoIter<ID>;
 
     fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
         for child in self.data.iter() {
@@ -63,7 +65,7 @@ where
     }
 
     fn ids(&self) -> Self::Iter {
-        let mut ids = btreeset! {};
+        let mut ids = BTreeSet::new();
         for child in self.data.iter() {
             ids.extend(child.ids())
         }
@@ -77,7 +79,7 @@ where
     ID: PartialOrd + Ord + 'static,
     QS: QuorumSet<ID>,
 {
-    type Iter = std::collections::btree_set::IntoIter<ID>;
+    type Iter = alloc::collections::btree_set::IntoIter<ID>;
 
     fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
         for child in self.data.iter() {
@@ -89,7 +91,7 @@ where
     }
 
     fn ids(&self) -> Self::Iter {
-        let mut ids = btreeset! {};
+        let mut ids = BTreeSet::new();
         for child in self.data.iter() {
             ids.extend(child.ids())
         }
@@ -1,6 +1,8 @@
-use crate::quorum::AsJoint;
-use crate::quorum::Joint;
-use crate::quorum::QuorumSet;
+use alloc::vec::Vec;
+
+use crate::AsJoint;
+use crate::Joint;
+use crate::QuorumSet;
 
 /// Use a vec of some implementation of `QuorumSet` as a joint quorum set.
 impl<'d, ID, QS> AsJoint<'d, ID, QS, &'d [QS]> for Vec<QS>
@@ -2,6 +2,17 @@
 //! The most common quorum is **majority**.
 //! A quorum set is a collection of quorums, e.g., the quorum set of the majority of `{a,b,c}` is
 //! `{a,b}, {b,c}, {a,c}`.
+//!
+//! This crate is pure quorum math without IO. It is `no_std` and depends only on `core` and
+//! `alloc`, so that it can be reused in an embedded environment.
+
+#![no_std]
+#![cfg_attr(feature = "bench", feature(test))]
+
+extern crate alloc;
+
+#[cfg(test)]
+extern crate std;
 
 mod coherent;
 mod coherent_impl;
@@ -19,8 +30,8 @@ mod coherent_test;
 #[cfg(test)]
 mod quorum_set_test;
 
-pub(crate) use coherent::Coherent;
-pub(crate) use coherent::FindCoherent;
-pub(crate) use joint::AsJoint;
-pub(crate) use joint::Joint;
-pub(crate) use quorum_set::QuorumSet;
+pub use coherent::Coherent;
+pub use coherent::FindCoherent;
+pub use joint::AsJoint;
+pub use joint::Joint;
+pub use quorum_set::QuorumSet;
@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use alloc::sync::Arc;
 
 /// A set of quorums is a collection of quorum.
 ///
 /// A quorum is a collection of nodes that a read or write operation in a distributed system has to
 /// contact. See: <http://web.mit.edu/6.033/2005/wwwdocs/quorum_note.html>
-pub(crate) trait QuorumSet<ID: 'static> {
+pub trait QuorumSet<ID: 'static> {
     type Iter: Iterator<Item = ID>;
 
     /// Check if a series of ID constitute a quorum that is defined by this quorum set.
@@ -1,12 +1,13 @@
-use std::collections::BTreeSet;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
 
-use crate::quorum::quorum_set::QuorumSet;
+use crate::quorum_set::QuorumSet;
 
 /// Impl a simple majority quorum set
 impl<ID> QuorumSet<ID> for BTreeSet<ID>
 where ID: PartialOrd + Ord + Clone + 'static
 {
-    type Iter = std::collections::btree_set::IntoIter<ID>;
+    type Iter = alloc::collections::btree_set::IntoIter<ID>;
 
     fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
         let mut count = 0;
@@ -31,7 +32,7 @@ where ID: PartialOrd + Ord + Clone + 'static
 impl<ID> QuorumSet<ID> for Vec<ID>
 where ID: PartialOrd + Ord + Clone + 'static
 {
-    type Iter = std::collections::btree_set::IntoIter<ID>;
+    type Iter = alloc::collections::btree_set::IntoIter<ID>;
 
     fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
         let mut count = 0;
@@ -56,7 +57,7 @@ where ID: PartialOrd + Ord + Clone + 'static
 impl<ID> QuorumSet<ID> for &[ID]
 where ID: PartialOrd + Ord + Copy + 'static
 {
-    type Iter = std::collections::btree_set::IntoIter<ID>;
+    type Iter = alloc::collections::btree_set::IntoIter<ID>;
 
     fn is_quorum<'a, I: Iterator<Item = &'a ID> + Clone>(&self, ids: I) -> bool {
         let mut count = 0;
@@ -1,8 +1,11 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
 use maplit::btreeset;
 
-use crate::quorum::AsJoint;
-use crate::quorum::Joint;
-use crate::quorum::QuorumSet;
+use crate::AsJoint;
+use crate::Joint;
+use crate::QuorumSet;
 
 #[test]
 fn test_simple_quorum_set_impl() -> anyhow::Result<()> {
@@ -32,6 +32,7 @@ use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftSnapshotBuilder;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::storage::StorageStamp;
 use serde::Deserialize;
 use serde::Serialize;
 use tokio::sync::RwLock;
@@ -57,6 +58,14 @@ pub struct ClientRequest {
     pub status: String,
 }
 
+impl ClientRequest {
+    /// The key `{client}/{serial}` the state machine publishes to the application key index when
+    /// this request is applied.
+    pub fn app_key(&self) -> String {
+        format!("{}/{}", self.client, self.serial)
+    }
+}
+
 /// Helper trait to build `ClientRequest` for `MemStore` in generic test code.
 pub trait IntoMemClientRequest<T> {
     fn make_request(client_id: impl ToString, serial: u64) -> T;
@@ -113,6 +122,9 @@ pub struct MemStoreStateMachine {
 
     /// The current status of a client by ID.
     pub client_status: HashMap<String, String>,
+
+    /// The version of the apply logic, set by the last version bump entry.
+    pub version: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -124,6 +136,12 @@ pub enum BlockOperation {
     DelayBuildingSnapshot,
     BuildSnapshot,
     PurgeLog,
+    /// Delay applying every batch of logs to the state machine.
+    Apply,
+    /// Delay reading every range of logs from the log store.
+    ReadLog,
+    /// Delay appending every node-local entry to the log store.
+    AppendLocal,
 }
 
 /// Block operations for testing purposes.
@@ -169,6 +187,15 @@ pub struct MemLogStore {
 
     /// The current hard state.
     vote: RwLock<Option<Vote<TypeConfig>>>,
+
+    /// The cluster and node this store belongs to.
+    stamp: RwLock<Option<StorageStamp<TypeConfig>>>,
+
+    /// Node-local entries appended by the application, by sequence number.
+    local: RwLock<BTreeMap<u64, Vec<u8>>>,
+
+    /// The sequence number of the next local entry, not reset by purging.
+    next_local_seq: AtomicU64,
 }
 
 impl MemLogStore {
@@ -182,8 +209,27 @@ impl MemLogStore {
             log,
             block,
             vote: RwLock::new(None),
+            stamp: RwLock::new(None),
+            local: RwLock::new(BTreeMap::new()),
+            next_local_seq: AtomicU64::new(0),
         }
     }
+
+    /// Overwrite a stored log entry without going through Raft, to simulate a corrupted log.
+    ///
+    /// This method is only used for testing purposes.
+    pub async fn corrupt_log(&self, entry: Entry<TypeConfig>) {
+        let s = serde_json::to_string(&entry).unwrap();
+        self.log.write().await.insert(entry.log_id.index(), s);
+    }
+
+    /// Overwrite the stored log entry at `index` with bytes that cannot be deserialized, to
+    /// simulate a corrupted disk.
+    ///
+    /// This method is only used for testing purposes.
+    pub async fn garble_log(&self, index: u64) {
+        self.log.write().await.insert(index, "garbled".to_string());
+    }
 }
 
 /// An in-memory key-value storage implementing the `RaftStateMachine` trait.
@@ -203,6 +249,18 @@ pub struct MemStateMachine {
 
     /// Counter for testing: tracks how many times `try_create_snapshot_builder` is called.
     pub try_create_snapshot_builder_count: Arc<AtomicU64>,
+
+    /// Counter for testing: tracks how many times `get_current_snapshot` is called.
+    pub get_current_snapshot_count: Arc<AtomicU64>,
+
+    /// Keys of the client requests applied since the last `take_app_index_keys()`.
+    app_index_keys: Mutex<Vec<(String, LogId<TypeConfig>)>>,
+
+    /// The serialized responses computed by the last `apply()`, to stream to followers.
+    computed_results: Mutex<Vec<(LogId<TypeConfig>, Vec<u8>)>>,
+
+    /// The fencing token of the last applied batch.
+    fencing_token: Mutex<Option<Vote<TypeConfig>>>,
 }
 
 impl MemStateMachine {
@@ -217,6 +275,10 @@ impl MemStateMachine {
             current_snapshot,
             block,
             try_create_snapshot_builder_count: Arc::new(AtomicU64::new(0)),
+            get_current_snapshot_count: Arc::new(AtomicU64::new(0)),
+            app_index_keys: Mutex::new(Vec::new()),
+            computed_results: Mutex::new(Vec::new()),
+            fencing_token: Mutex::new(None),
         }
     }
 
@@ -229,6 +291,11 @@ impl MemStateMachine {
         self.try_create_snapshot_builder_count.swap(0, Ordering::Relaxed)
     }
 
+    /// Get and reset the counter for `get_current_snapshot` calls.
+    pub fn take_get_current_snapshot_count(&self) -> u64 {
+        self.get_current_snapshot_count.swap(0, Ordering::Relaxed)
+    }
+
     /// Remove the current snapshot.
     ///
     /// This method is only used for testing purposes.
@@ -237,6 +304,11 @@ impl MemStateMachine {
         *current = None;
     }
 
+    /// Get the fencing token of the last applied batch.
+    pub fn fencing_token(&self) -> Option<Vote<TypeConfig>> {
+        *self.fencing_token.lock().unwrap()
+    }
+
     /// Get a handle to the state machine for testing purposes.
     pub async fn get_state_machine(&self) -> MemStoreStateMachine {
         self.sm.write().await.clone()
@@ -247,6 +319,14 @@ impl MemStateMachine {
         let mut sm = self.sm.write().await;
         *sm = MemStoreStateMachine::default();
     }
+
+    /// Overwrite the status of a client without applying a log entry.
+    ///
+    /// This method is only used for testing purposes, to make the state machine diverge.
+    pub async fn corrupt_client_status(&self, client: &str, status: &str) {
+        let mut sm = self.sm.write().await;
+        sm.client_status.insert(client.to_string(), status.to_string());
+    }
 }
 
 pub fn new_mem_store() -> (Arc<MemLogStore>, Arc<MemStateMachine>) {
@@ -262,6 +342,11 @@ impl RaftLogReader<TypeConfig> for Arc<MemLogStore> {
         &mut self,
         range: RB,
     ) -> Result<Vec<Entry<TypeConfig>>, StorageError<TypeConfig>> {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::ReadLog) {
+            tracing::info!(?d, "block reading log");
+            tokio::time::sleep(d).await;
+        }
+
         let mut entries = vec![];
         {
             let log = self.log.read().await;
@@ -277,6 +362,46 @@ impl RaftLogReader<TypeConfig> for Arc<MemLogStore> {
     async fn read_vote(&mut self) -> Result<Option<Vote<TypeConfig>>, StorageError<TypeConfig>> {
         Ok(*self.vote.read().await)
     }
+
+    fn clone_entry(&self, entry: &Entry<TypeConfig>) -> Option<Entry<TypeConfig>> {
+        Some(entry.clone())
+    }
+
+    fn entry_size(&self, entry: &Entry<TypeConfig>) -> Option<u64> {
+        serde_json::to_vec(entry).ok().map(|x| x.len() as u64)
+    }
+
+    fn entry_checksum(&self, entry: &Entry<TypeConfig>) -> Option<u64> {
+        // FNV-1a of the serialized entry.
+        let bytes = serde_json::to_vec(entry).ok()?;
+        let checksum = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+            (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Some(checksum)
+    }
+
+    async fn append_local(&mut self, data: Vec<u8>) -> Result<u64, StorageError<TypeConfig>> {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::AppendLocal) {
+            tracing::info!(?d, "block appending local entry");
+            tokio::time::sleep(d).await;
+        }
+
+        let mut local = self.local.write().await;
+        let seq = self.next_local_seq.fetch_add(1, Ordering::Relaxed);
+        local.insert(seq, data);
+        Ok(seq)
+    }
+
+    async fn read_local(&mut self, start: u64, max: u64) -> Result<Vec<(u64, Vec<u8>)>, StorageError<TypeConfig>> {
+        let local = self.local.read().await;
+        Ok(local.range(start..).take(max as usize).map(|(seq, data)| (*seq, data.clone())).collect())
+    }
+
+    async fn purge_local(&mut self, upto: u64) -> Result<(), StorageError<TypeConfig>> {
+        let mut local = self.local.write().await;
+        *local = local.split_off(&(upto + 1));
+        Ok(())
+    }
 }
 
 impl RaftSnapshotBuilder<TypeConfig> for Arc<MemStateMachine> {
@@ -407,6 +532,16 @@ impl RaftLogStorage<TypeConfig> for Arc<MemLogStore> {
         Ok(*self.committed.read().await)
     }
 
+    async fn save_stamp(&mut self, stamp: &StorageStamp<TypeConfig>) -> Result<(), StorageError<TypeConfig>> {
+        tracing::debug!(%stamp, "save_stamp");
+        *self.stamp.write().await = Some(stamp.clone());
+        Ok(())
+    }
+
+    async fn read_stamp(&mut self) -> Result<Option<StorageStamp<TypeConfig>>, StorageError<TypeConfig>> {
+        Ok(self.stamp.read().await.clone())
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     async fn append<I>(&mut self, entries: I, callback: IOFlushed<TypeConfig>) -> Result<(), StorageError<TypeConfig>>
     where I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend {
@@ -480,26 +615,87 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
         I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
         I::IntoIter: OptionalSend,
     {
+        if let Some(d) = self.block.get_blocking(&BlockOperation::Apply) {
+            tracing::info!(?d, "delay apply");
+            tokio::time::sleep(d).await;
+        }
+
         let mut res = Vec::new();
 
         let mut sm = self.sm.write().await;
+        let mut app_index_keys = self.app_index_keys.lock().unwrap();
+        let mut computed_results = self.computed_results.lock().unwrap();
+        computed_results.clear();
 
         for entry in entries {
             tracing::debug!(%entry.log_id, "replicate to sm");
 
             sm.last_applied_log = Some(entry.log_id);
 
-            match entry.payload {
-                EntryPayload::Blank => res.push(ClientResponse(None)),
+            let resp = match entry.payload {
+                EntryPayload::Blank => ClientResponse(None),
                 EntryPayload::Normal(ref data) => {
                     let previous = sm.client_status.insert(data.client.clone(), data.status.clone());
-                    res.push(ClientResponse(previous));
+                    app_index_keys.push((data.app_key(), entry.log_id));
+                    ClientResponse(previous)
+                }
+                EntryPayload::Membership(ref mem) => {
+                    sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
+                    ClientResponse(None)
+                }
+                EntryPayload::VersionBump(version) => {
+                    sm.version = sm.version.max(version);
+                    ClientResponse(None)
+                }
+            };
+
+            computed_results.push((entry.log_id, serde_json::to_vec(&resp).unwrap()));
+            res.push(resp);
+        }
+        Ok(res)
+    }
+
+    /// Apply entries with the responses computed by the leader, instead of reading the previous
+    /// status from the state machine.
+    #[tracing::instrument(level = "trace", skip(self, entries, results))]
+    async fn apply_computed<I>(
+        &mut self,
+        entries: I,
+        results: Vec<Vec<u8>>,
+    ) -> Result<Vec<ClientResponse>, StorageError<TypeConfig>>
+    where
+        I: IntoIterator<Item = Entry<TypeConfig>> + OptionalSend,
+        I::IntoIter: OptionalSend,
+    {
+        let mut res = Vec::new();
+
+        let mut sm = self.sm.write().await;
+        let mut app_index_keys = self.app_index_keys.lock().unwrap();
+        self.computed_results.lock().unwrap().clear();
+
+        for (entry, result) in entries.into_iter().zip(results) {
+            tracing::debug!(%entry.log_id, "replicate to sm with the result of the leader");
+
+            let resp: ClientResponse =
+                serde_json::from_slice(&result).map_err(|e| StorageError::apply(entry.log_id, &e))?;
+
+            sm.last_applied_log = Some(entry.log_id);
+
+            match entry.payload {
+                EntryPayload::Blank => {}
+                EntryPayload::Normal(ref data) => {
+                    sm.client_status.insert(data.client.clone(), data.status.clone());
+                    app_index_keys.push((data.app_key(), entry.log_id));
                 }
                 EntryPayload::Membership(ref mem) => {
                     sm.last_membership = StoredMembership::new(Some(entry.log_id), mem.clone());
-                    res.push(ClientResponse(None))
+                }
+                EntryPayload::VersionBump(version) => {
+                    sm.version = sm.version.max(version);
                 }
             };
+
+            res.push(resp);
         }
         Ok(res)
     }
@@ -562,6 +758,8 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
 
     #[tracing::instrument(level = "trace", skip(self))]
     async fn get_current_snapshot(&mut self) -> Result<Option<Snapshot<TypeConfig>>, StorageError<TypeConfig>> {
+        self.get_current_snapshot_count.fetch_add(1, Ordering::Relaxed);
+
         match &*self.current_snapshot.read().await {
             Some(snapshot) => {
                 let data = snapshot.data.clone();
@@ -573,4 +771,36 @@ impl RaftStateMachine<TypeConfig> for Arc<MemStateMachine> {
             None => Ok(None),
         }
     }
+
+    async fn checksum(&mut self, at_log_id: &LogId<TypeConfig>) -> Result<Option<u64>, StorageError<TypeConfig>> {
+        let sm = self.sm.read().await;
+        debug_assert_eq!(Some(at_log_id), sm.last_applied_log.as_ref());
+
+        // FNV-1a of the serialized client statuses, in the order of client ids.
+        let statuses = sm.client_status.iter().collect::<BTreeMap<_, _>>();
+        let bytes = serde_json::to_vec(&statuses).map_err(|e| StorageError::read_state_machine(&e))?;
+        let checksum = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+            (h ^ *b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Ok(Some(checksum))
+    }
+
+    fn clone_snapshot(&self, snapshot: &Snapshot<TypeConfig>) -> Option<Snapshot<TypeConfig>> {
+        Some(Snapshot {
+            meta: snapshot.meta.clone(),
+            snapshot: Cursor::new(snapshot.snapshot.get_ref().clone()),
+        })
+    }
+
+    fn set_fencing_token(&mut self, vote: Vote<TypeConfig>) {
+        *self.fencing_token.lock().unwrap() = Some(vote);
+    }
+
+    fn take_app_index_keys(&mut self) -> Vec<(String, LogId<TypeConfig>)> {
+        std::mem::take(&mut *self.app_index_keys.lock().unwrap())
+    }
+
+    fn take_computed_results(&mut self) -> Vec<(LogId<TypeConfig>, Vec<u8>)> {
+        std::mem::take(&mut *self.computed_results.lock().unwrap())
+    }
 }
@@ -46,13 +46,7 @@ async fn append_sees_higher_vote() -> Result<()> {
         let resp = router
             .new_client(1, &())
             .await
-            .vote(
-                VoteRequest {
-                    vote: Vote::new(10, 1),
-                    last_log_id: Some(log_id(10, 1, 5)),
-                },
-                option,
-            )
+            .vote(VoteRequest::new(Vote::new(10, 1), Some(log_id(10, 1, 5))), option)
             .await?;
 
         assert!(resp.is_granted_to(&Vote::new(10, 1)));
@@ -44,12 +44,7 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 0: prev_log_id == None, no logs");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: None,
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(Vote::new_committed(1, 2), None, vec![], Some(log_id(1, 0, 2)));
 
     let resp = r0.append_entries(req).await?;
 
@@ -58,12 +53,12 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 0: prev_log_id == None, 1 logs");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: None,
-        entries: vec![blank_ent(0, 0, 0)],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        None,
+        vec![blank_ent(0, 0, 0)],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -71,12 +66,12 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 0: prev_log_id == 1-1, 0 logs");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(0, 0, 0)),
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(0, 0, 0)),
+        vec![],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -86,17 +81,19 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 0: prev_log_id.index == 0, ");
 
-    let req = || AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(0, 0, 0)),
-        entries: vec![
-            blank_ent(1, 0, 1),
-            blank_ent(1, 0, 2),
-            blank_ent(1, 0, 3),
-            blank_ent(1, 0, 4),
-        ],
-        // this set the last_applied to 2
-        leader_commit: Some(log_id(1, 0, 2)),
+    let req = || {
+        AppendEntriesRequest::new(
+            Vote::new_committed(1, 2),
+            Some(log_id(0, 0, 0)),
+            vec![
+                blank_ent(1, 0, 1),
+                blank_ent(1, 0, 2),
+                blank_ent(1, 0, 3),
+                blank_ent(1, 0, 4),
+            ],
+            // this set the last_applied to 2
+            Some(log_id(1, 0, 2)),
+        )
     };
 
     let resp = r0.append_entries(req()).await?;
@@ -116,12 +113,12 @@ async fn append_conflicts() -> Result<()> {
     // committed index is 2
     tracing::info!("--- case 1: 0 < prev_log_id.index < commit_index");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(1, 0, 1)),
-        entries: vec![blank_ent(1, 0, 2)],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(1, 0, 1)),
+        vec![blank_ent(1, 0, 2)],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -131,13 +128,13 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 2:  prev_log_id.index == last_applied, inconsistent log should be removed");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(1, 0, 2)),
-        entries: vec![blank_ent(2, 0, 3)],
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(1, 0, 2)),
+        vec![blank_ent(2, 0, 3)],
         // this set the last_applied to 2
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -146,12 +143,12 @@ async fn append_conflicts() -> Result<()> {
     check_logs(&mut sto0, vec![0, 1, 1, 2]).await?;
 
     // check last_log_id is updated:
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(1, 0, 2000)),
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(1, 0, 2000)),
+        vec![],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(!resp.is_success());
@@ -161,12 +158,12 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id mismatch, inconsistent log is removed");
 
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(3, 0, 3)),
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(3, 0, 3)),
+        vec![],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(!resp.is_success());
@@ -176,12 +173,12 @@ async fn append_conflicts() -> Result<()> {
 
     tracing::info!("--- case 3,4: prev_log_id.index <= last_log_id, prev_log_id matches, inconsistent log is removed");
     // refill logs
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(1, 0, 2)),
-        entries: vec![blank_ent(2, 0, 3), blank_ent(2, 0, 4), blank_ent(2, 0, 5)],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(1, 0, 2)),
+        vec![blank_ent(2, 0, 3), blank_ent(2, 0, 4), blank_ent(2, 0, 5)],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -191,12 +188,12 @@ async fn append_conflicts() -> Result<()> {
     check_logs(&mut sto0, vec![0, 1, 1, 2, 2, 2]).await?;
 
     // prev_log_id matches
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(2, 0, 3)),
-        entries: vec![blank_ent(3, 0, 4)],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(2, 0, 3)),
+        vec![blank_ent(3, 0, 4)],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(resp.is_success());
@@ -207,12 +204,12 @@ async fn append_conflicts() -> Result<()> {
     tracing::info!("--- case 5: last_log_id.index < prev_log_id.index");
 
     // refill logs
-    let req = AppendEntriesRequest {
-        vote: Vote::new_committed(1, 2),
-        prev_log_id: Some(log_id(1, 0, 200)),
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, 2)),
-    };
+    let req = AppendEntriesRequest::new(
+        Vote::new_committed(1, 2),
+        Some(log_id(1, 0, 200)),
+        vec![],
+        Some(log_id(1, 0, 2)),
+    );
 
     let resp = r0.append_entries(req).await?;
     assert!(!resp.is_success());
@@ -38,12 +38,12 @@ async fn append_entries_with_bigger_term() -> Result<()> {
     router.assert_storage_state(1, log_index, Some(0), log_id(1, 0, log_index), None).await?;
 
     // append entries with term 2 and leader_id, this MUST cause hard state changed in node 0
-    let req = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-        vote: Vote::new_committed(2, 1),
-        prev_log_id: Some(log_id(1, 0, log_index)),
-        entries: vec![],
-        leader_commit: Some(log_id(1, 0, log_index)),
-    };
+    let req = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+        Vote::new_committed(2, 1),
+        Some(log_id(1, 0, log_index)),
+        vec![],
+        Some(log_id(1, 0, log_index)),
+    );
 
     let option = RPCOption::new(Duration::from_millis(1_000));
 
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::error::ClusterMismatch;
+use openraft::error::Fatal;
+use openraft::error::RaftError;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::VoteRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// RPCs carrying another cluster name are rejected without touching the state of the receiver.
+///
+/// - Bring up a cluster named `foo`; its own RPCs carry the name and are accepted.
+/// - Send append-entries and vote requests with a bigger vote but another cluster name.
+///
+/// Check they are rejected and the vote of the receiver is not changed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn reject_rpc_from_other_cluster() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            cluster_name: "foo".to_string(),
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0, 1, 2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let want_err = |got: &str| RaftError::Fatal(Fatal::ClusterMismatch(ClusterMismatch::new("foo", got)));
+
+    tracing::info!(log_index, "--- append-entries from another cluster is rejected");
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(5, 2), Some(log_id(1, 0, log_index)), [], None)
+            .with_cluster_name(Some("bar".to_string()));
+
+        let res = n1.append_entries(req).await;
+        assert_eq!(Err(want_err("bar")), res);
+    }
+
+    tracing::info!(log_index, "--- vote from another cluster is rejected");
+    {
+        let req = VoteRequest::new(Vote::new(5, 2), Some(log_id(5, 2, 100))).with_cluster_name(Some("bar".to_string()));
+
+        let res = n1.vote(req).await;
+        assert_eq!(Err(want_err("bar")), res);
+    }
+
+    router
+        .wait(&1, timeout())
+        .vote(Vote::new_committed(1, 0), "vote is not changed by rejected RPCs")
+        .await?;
+
+    tracing::info!(log_index, "--- RPC without cluster name is accepted");
+    {
+        let req = VoteRequest::new(Vote::new(5, 2), Some(log_id(5, 2, 100)));
+
+        // Not granted because the leader lease has not expired; but it is handled.
+        let resp = n1.vote(req).await?;
+        assert_eq!(Vote::new_committed(1, 0), resp.vote);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+#[cfg(not(feature = "single-term-leader"))]
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::raft::AppendEntriesRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A follower that receives an `AppendEntries` from another leader of the same term within the
+/// lease of the current leader reports a split-brain in metrics.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn split_brain_detected() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 200,
+            election_timeout_min: 1000,
+            election_timeout_max: 1001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let node1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- AppendEntries from the current leader is not a split-brain"
+    );
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(1, 0), None, vec![], None);
+        node1.append_entries(req).await?;
+
+        let m = node1.metrics().borrow().clone();
+        assert_eq!(0, m.split_brain_detected);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- AppendEntries from a stale leader of a smaller term is not a split-brain"
+    );
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(0, 2), None, vec![], None);
+        let resp = node1.append_entries(req).await?;
+        assert!(!resp.is_success(), "stale leader is rejected");
+
+        let m = node1.metrics().borrow().clone();
+        assert_eq!(0, m.split_brain_detected);
+    }
+
+    // With a single leader per term, two committed leaders of the same term can not be built.
+    #[cfg(not(feature = "single-term-leader"))]
+    {
+        tracing::info!(
+            log_index,
+            "--- AppendEntries from another leader of the same term within the lease"
+        );
+
+        let req = AppendEntriesRequest::new(Vote::new_committed(1, 2), None, vec![], None);
+        node1.append_entries(req).await?;
+
+        router
+            .wait(&1, timeout())
+            .metrics(|m| m.split_brain_detected >= 1, "split-brain is counted")
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "single-term-leader"))]
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -17,5 +17,16 @@ mod t13_trigger_snapshot;
 mod t14_transfer_leader;
 mod t16_with_raft_state;
 mod t16_with_state_machine;
+mod t17_client_write_cancelled;
+mod t17_client_write_overloaded;
+mod t18_lookup_app_key;
+mod t19_wait_applied;
+mod t20_client_write_trace_id;
+mod t21_client_write_max_entry_size;
+mod t22_client_write_readonly;
+mod t23_bump_version;
+mod t24_read_only_handle;
+mod t25_cold_start_reads;
+mod t26_pending_writes_on_step_down;
 mod t50_lagging_network_write;
 mod t51_write_when_leader_quit;
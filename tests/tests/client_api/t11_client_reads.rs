@@ -7,6 +7,7 @@ use maplit::btreeset;
 use openraft::Config;
 use openraft::LogIdOptionExt;
 use openraft::RPCTypes;
+use openraft::ReadGuarantee;
 use openraft::ReadPolicy;
 use openraft::ServerState;
 use openraft::error::NetworkError;
@@ -263,6 +264,54 @@ async fn ensure_linearizable_with_read_index() -> Result<()> {
     Ok(())
 }
 
+/// Concurrent `ReadIndex` reads within `read_index_batch_delay` share one round of heartbeats.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn ensure_linearizable_batched_reads() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            read_index_batch_delay: 100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- 10 concurrent reads send one heartbeat to every follower"
+    );
+    {
+        let before = *router.get_rpc_count().get(&RPCTypes::AppendEntries).unwrap_or(&0);
+
+        let reads = (0..10).map(|_| router.ensure_linearizable(0, ReadPolicy::ReadIndex));
+        for res in futures::future::join_all(reads).await {
+            res?;
+        }
+
+        let after = *router.get_rpc_count().get(&RPCTypes::AppendEntries).unwrap_or(&0);
+        assert_eq!(2, after - before, "one heartbeat to each of node-1 and node-2");
+    }
+
+    tracing::info!(log_index, "--- a batched read on an isolated leader fails");
+    {
+        router.set_network_error(1, true);
+        router.set_network_error(2, true);
+
+        let res = router.ensure_linearizable(0, ReadPolicy::ReadIndex).await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn ensure_linearizable_with_lease_read() -> Result<()> {
@@ -400,6 +449,67 @@ async fn ensure_linearizable_not_process_from_followers() -> Result<()> {
     Ok(())
 }
 
+/// `Raft::read()` serves every guarantee: the linearizable ones only on the leader, `Stale` on any
+/// node without contacting other nodes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn read_with_guarantee() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.network_send_delay(0);
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- linearizable reads on the leader");
+    {
+        let log_id = n0.read(ReadGuarantee::ReadIndex).await?;
+        assert_eq!(Some(log_index), log_id.index());
+
+        let log_id = n0.read(ReadGuarantee::LeaderLease).await?;
+        assert_eq!(Some(log_index), log_id.index());
+    }
+
+    tracing::info!(log_index, "--- linearizable reads on a follower fail");
+    {
+        n1.read(ReadGuarantee::ReadIndex).await.expect_err("ReadIndex on a follower");
+        n1.read(ReadGuarantee::LeaderLease).await.expect_err("LeaderLease on a follower");
+    }
+
+    tracing::info!(
+        log_index,
+        "--- stale read on an isolated follower returns its applied log id"
+    );
+    {
+        n1.wait(timeout()).applied_index(Some(log_index), "n1 applied all logs").await?;
+        router.set_network_error(1, true);
+
+        let rpc_count_before = router.get_rpc_count();
+        let log_id = n1.read(ReadGuarantee::Stale).await?;
+        assert_eq!(Some(log_index), log_id.index());
+        assert_eq!(rpc_count_before, router.get_rpc_count(), "stale read sends no RPC");
+    }
+
+    tracing::info!(log_index, "--- stale read fails after shutdown");
+    {
+        n1.shutdown().await?;
+        n1.read(ReadGuarantee::Stale).await.expect_err("read after shutdown");
+    }
+
+    Ok(())
+}
+
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn ensure_linearizable_process_from_followers() -> Result<()> {
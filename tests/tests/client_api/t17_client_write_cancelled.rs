@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A client write cancelled while it waits for room in the full API channel releases its place in
+/// the queue of client writes, so that later writes are not rejected with `Overloaded`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_cancelled() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_queued_client_writes: 3,
+            api_channel_size: Some(3),
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- block RaftCore and fill the API channel");
+    let (tx_unblock, rx_unblock) = std::sync::mpsc::channel::<()>();
+    n0.external_request(move |_st| {
+        rx_unblock.recv_timeout(Duration::from_secs(5)).ok();
+    })
+    .await?;
+
+    for _ in 0..3 {
+        n0.external_request(|_st| {}).await?;
+    }
+
+    tracing::info!(log_index, "--- cancel writes waiting for room in the API channel");
+    for i in 0..3 {
+        let write = n0.client_write(ClientRequest::make_request("foo", i));
+        let res = tokio::time::timeout(Duration::from_millis(100), write).await;
+        assert!(res.is_err(), "write-{} waits for room in the API channel", i);
+    }
+
+    tracing::info!(log_index, "--- unblock RaftCore, writes are accepted");
+    {
+        tx_unblock.send(())?;
+
+        for i in 3..6 {
+            n0.client_write(ClientRequest::make_request("foo", i)).await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::ClientWriteError;
+use openraft::error::Overloaded;
+use openraft::error::RaftError;
+use openraft::impls::OneshotResponder;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// When `max_queued_client_writes` writes are queued, a new client write is rejected at once with
+/// `Overloaded`, and accepted again once the queue is drained.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_overloaded() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_queued_client_writes: 3,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- block RaftCore so that client writes are queued");
+    let (tx_unblock, rx_unblock) = std::sync::mpsc::channel::<()>();
+    n0.external_request(move |_st| {
+        rx_unblock.recv_timeout(Duration::from_secs(5)).ok();
+    })
+    .await?;
+
+    let mut queued = vec![];
+    for i in 0..3 {
+        let (responder, rx) = OneshotResponder::new_pair();
+        n0.client_write_ff(ClientRequest::make_request("foo", i), Some(responder)).await?;
+        queued.push(rx);
+    }
+
+    tracing::info!(log_index, "--- the 4th write is rejected");
+    {
+        let want = Overloaded::new(3, Duration::from_millis(config.heartbeat_interval));
+
+        let (responder, rx) = OneshotResponder::new_pair();
+        n0.client_write_ff(ClientRequest::make_request("foo", 3), Some(responder)).await?;
+        assert_eq!(ClientWriteError::Overloaded(want.clone()), rx.await?.unwrap_err());
+
+        let err = n0.client_write(ClientRequest::make_request("foo", 4)).await.unwrap_err();
+        assert_eq!(RaftError::APIError(ClientWriteError::Overloaded(want)), err);
+    }
+
+    tracing::info!(log_index, "--- unblock RaftCore, the queued writes are committed");
+    {
+        tx_unblock.send(())?;
+
+        for rx in queued {
+            rx.await??;
+        }
+        log_index += 3;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "queued writes are applied").await?;
+    }
+
+    tracing::info!(log_index, "--- writes are accepted again");
+    {
+        n0.client_write(ClientRequest::make_request("foo", 5)).await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
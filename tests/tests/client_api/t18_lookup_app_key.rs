@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// The keys published by the state machine are looked up on every node once they are applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn lookup_app_key() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let key = ClientRequest::make_request("foo", 3).app_key();
+    assert_eq!("foo/3", key);
+
+    tracing::info!(log_index, "--- unknown key");
+    {
+        for id in [0, 1, 2] {
+            assert_eq!(None, router.get_raft_handle(&id)?.lookup_app_key(&key));
+        }
+    }
+
+    tracing::info!(log_index, "--- write logs");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "logs are applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- key is found at the index it is applied at");
+    {
+        for id in [0, 1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            assert_eq!(Some(log_id(1, 0, log_index - 1)), n.lookup_app_key(&key));
+            assert_eq!(None, n.lookup_app_key("foo/5"));
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::metrics::WaitError;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `Raft::wait_applied()` resolves on every node once the log id returned by a write is applied,
+/// and times out on a node that can not receive it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn wait_applied() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- read-your-writes on every node");
+    {
+        let resp = n0.client_write(ClientRequest::make_request("foo", 1)).await?;
+
+        for id in [0, 1, 2, 3] {
+            let applied = router.get_raft_handle(&id)?.wait_applied(resp.log_id, timeout()).await?;
+            assert!(applied >= resp.log_id);
+        }
+    }
+
+    tracing::info!(log_index, "--- time out on an isolated learner");
+    {
+        router.set_network_error(3, true);
+
+        let resp = n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+
+        let n3 = router.get_raft_handle(&3)?;
+        let res = n3.wait_applied(resp.log_id, Some(Duration::from_millis(300))).await;
+        assert!(matches!(res, Err(WaitError::Timeout(_, _))), "{:?}", res);
+
+        tracing::info!(log_index, "--- resolve once the learner catches up");
+        router.set_network_error(3, false);
+
+        let applied = n3.wait_applied(resp.log_id, timeout()).await?;
+        assert_eq!(resp.log_id, applied);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
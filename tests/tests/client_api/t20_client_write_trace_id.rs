@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ReadGuarantee;
+use openraft::network::RPCTypes;
+use openraft::raft::AppendEntriesRequest;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The trace id of a client write is sent along with the entry it is written to.
+///
+/// - Write an entry with a trace id and another without.
+/// - Check the followers receive the trace id only for the first entry.
+/// - A read with a trace id is served as well.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_trace_id() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let received: Arc<Mutex<BTreeMap<u64, BTreeMap<u64, String>>>> = Arc::new(Mutex::new(BTreeMap::new()));
+    {
+        let received = received.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, to| {
+            let req: AppendEntriesRequest<_> = req.try_into().unwrap();
+            received.lock().unwrap().entry(to).or_default().extend(req.trace_ids().clone());
+            Ok(())
+        });
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write with and without trace id");
+    {
+        n0.client_write_with_trace_id(ClientRequest::make_request("foo", 1), "req-1").await?;
+        n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index + 2), "logs are applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- followers receive the trace id with the entry");
+    {
+        let received = received.lock().unwrap().clone();
+        let want = btreemap! {log_index + 1 => "req-1".to_string()};
+        assert_eq!(btreemap! {1 => want.clone(), 2 => want}, received);
+    }
+
+    tracing::info!(log_index, "--- read with a trace id");
+    {
+        let read_log_id = n0.read_with_trace_id(ReadGuarantee::ReadIndex, "req-2").await?;
+        assert_eq!(Some(log_index + 2), read_log_id.map(|x| x.index));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
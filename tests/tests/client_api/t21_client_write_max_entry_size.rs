@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A client write whose entry is larger than `max_entry_size` is rejected with `EntryTooLarge`
+/// without being proposed, while smaller writes are accepted.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_max_entry_size() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            max_entry_size: 200,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a large write is rejected");
+    {
+        let err = n0.client_write(ClientRequest::make_request("x".repeat(1_000), 1)).await.unwrap_err();
+
+        let RaftError::APIError(ClientWriteError::EntryTooLarge(e)) = err else {
+            panic!("expect EntryTooLarge, got: {:?}", err);
+        };
+        assert_eq!(200, e.max);
+        assert!(e.size > 1_000, "size: {}", e.size);
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index), metrics.last_log_index, "the write is not proposed");
+    }
+
+    tracing::info!(log_index, "--- a small write is accepted");
+    {
+        n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).applied_index(Some(log_index), "small write is applied").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::raft::TransferLeaderRequest;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use tokio::time::sleep;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A read-only node rejects client writes with `ReadOnly`, does not campaign, but keeps accepting
+/// replication.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn client_write_readonly() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- a read-only leader rejects writes");
+    {
+        n0.enter_readonly();
+        assert!(n0.is_readonly());
+
+        let err = n0.client_write(ClientRequest::make_request("foo", 1)).await.unwrap_err();
+
+        let RaftError::APIError(ClientWriteError::ReadOnly(e)) = err else {
+            panic!("expect ReadOnly, got: {:?}", err);
+        };
+        assert_eq!(0, e.node_id);
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(Some(log_index), metrics.last_log_index, "the write is not proposed");
+    }
+
+    tracing::info!(log_index, "--- the leader accepts writes after exiting read-only mode");
+    {
+        n0.exit_readonly();
+        assert!(!n0.is_readonly());
+
+        n0.client_write(ClientRequest::make_request("foo", 2)).await?;
+        log_index += 1;
+    }
+
+    tracing::info!(log_index, "--- a read-only follower keeps accepting replication");
+    {
+        n1.enter_readonly();
+
+        log_index += router.client_request_many(0, "foo", 3).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "read-only follower applies logs").await?;
+    }
+
+    tracing::info!(log_index, "--- a read-only follower does not campaign");
+    {
+        n1.trigger().elect().await?;
+
+        let metrics = n0.metrics().borrow().clone();
+        let req = TransferLeaderRequest::new(metrics.vote, 1, metrics.last_applied);
+        n1.handle_transfer_leader(req).await?;
+
+        sleep(Duration::from_millis(500)).await;
+
+        let metrics = n1.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, metrics.state);
+        assert_eq!(Some(0), metrics.current_leader);
+        assert_eq!(1, metrics.vote.leader_id().term);
+    }
+
+    tracing::info!(log_index, "--- the follower campaigns after exiting read-only mode");
+    {
+        n1.exit_readonly();
+        n1.trigger().elect().await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::ClientWriteError;
+use openraft::error::RaftError;
+use openraft::error::VersionUnsupported;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A version bump is proposed only if every member, including learners, supports it, and it is
+/// applied by the state machine of every node.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn bump_version() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a learner does not support the version");
+    {
+        let err = n0.bump_version(2, |id, _node| *id != 3).await.unwrap_err();
+
+        assert_eq!(
+            RaftError::APIError(ClientWriteError::VersionUnsupported(VersionUnsupported::new(2, 3))),
+            err
+        );
+
+        let metrics = n0.metrics().borrow().clone();
+        assert_eq!(
+            Some(log_index),
+            metrics.last_log_index,
+            "the version bump is not proposed"
+        );
+    }
+
+    tracing::info!(log_index, "--- every member supports the version");
+    {
+        let resp = n0.bump_version(2, |_id, _node| true).await?;
+        log_index += 1;
+        assert_eq!(log_index, resp.log_id.index);
+
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "version bump applied").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            assert_eq!(2, sm.get_state_machine().await.version, "node-{} version", id);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ReadPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A read-only handle observes the same node as the `Raft` it is created from.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn read_only_handle() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?.read_only_handle();
+    let n1 = router.get_raft_handle(&1)?.read_only_handle();
+
+    tracing::info!(log_index, "--- read metrics and membership");
+    {
+        assert_eq!(Some(0), n0.current_leader().await);
+        assert_eq!(Some(0), n1.current_leader().await);
+        assert!(n1.is_initialized().await?);
+        assert_eq!(btreeset! {0,1,2}, n1.effective_membership().voter_ids().collect());
+    }
+
+    tracing::info!(log_index, "--- wait for writes made with the Raft handle");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        let applied = n1.wait_applied(log_id(1, 0, log_index), timeout()).await?;
+        assert_eq!(log_id(1, 0, log_index), applied);
+        assert_eq!(Some(log_id(1, 0, log_index)), n1.metrics().borrow().last_applied);
+    }
+
+    tracing::info!(log_index, "--- linearizable read on the leader");
+    {
+        let read_log_id = n0.ensure_linearizable(ReadPolicy::ReadIndex).await?;
+        assert_eq!(Some(log_id(1, 0, log_index)), read_log_id);
+
+        let err = n1.ensure_linearizable(ReadPolicy::ReadIndex).await.unwrap_err();
+        assert!(
+            err.forward_to_leader().is_some(),
+            "follower forwards to leader: {}",
+            err
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
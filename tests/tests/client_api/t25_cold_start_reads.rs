@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::ColdStartReadPolicy;
+use openraft::Config;
+use openraft::ReadGuarantee;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::RaftError;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With [`ColdStartReadPolicy::WaitForLeader`], a stale read on a restarted node waits for the
+/// leader to be discovered, and is refused if it is not discovered in time.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn cold_start_reads() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            cold_start_reads: ColdStartReadPolicy::WaitForLeader(1_000),
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    router.wait(&1, timeout()).metrics(|m| m.leader_discovered, "node-1 discovered the leader").await?;
+
+    tracing::info!(log_index, "--- restart node-1 isolated from the leader");
+    {
+        let (n1, sto1, sm1) = router.remove_node(1).unwrap();
+        n1.shutdown().await?;
+
+        router.set_network_error(1, true);
+        router.new_raft_node_with_sto(1, sto1, sm1).await;
+    }
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- the leader is not discovered in time, stale read is refused"
+    );
+    {
+        let m = n1.metrics().borrow().clone();
+        assert!(!m.leader_discovered);
+
+        let err = n1.read(ReadGuarantee::Stale).await.unwrap_err();
+        assert!(
+            matches!(err, RaftError::APIError(CheckIsLeaderError::ForwardToLeader(_))),
+            "got: {:?}",
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- stale read waits until the leader replicates to node-1");
+    {
+        let read = tokio::spawn({
+            let n1 = n1.clone();
+            async move { n1.read(ReadGuarantee::Stale).await }
+        });
+
+        router.set_network_error(1, false);
+        router.client_request_many(0, "0", 1).await?;
+
+        read.await??;
+        assert!(n1.metrics().borrow().leader_discovered);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
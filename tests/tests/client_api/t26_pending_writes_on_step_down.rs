@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::PendingWritePolicy;
+use openraft::Vote;
+use openraft::error::ClientWriteError;
+use openraft::error::ForwardToLeader;
+use openraft::error::LeaderChanged;
+use openraft::error::RaftError;
+use openraft::raft::AppendEntriesRequest;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use tokio::sync::oneshot;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With [`PendingWritePolicy::Fail`], a client write that is not committed when the leader steps
+/// down receives a [`LeaderChanged`] error at once, telling it is persisted locally.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn pending_writes_fail_on_step_down() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            pending_writes_on_step_down: PendingWritePolicy::Fail,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- block replication so that no log will be committed");
+    router.set_unreachable(1, true);
+
+    let (tx, rx) = oneshot::channel();
+
+    tracing::info!(log_index, "--- write a log in another task");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        tokio::spawn(async move {
+            let res = n0.client_write(ClientRequest::make_request("cli", 1)).await;
+            tx.send(res).unwrap();
+        });
+
+        router.wait(&0, timeout()).log_index(Some(log_index + 1), "log appended on leader").await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    tracing::info!(log_index, "--- node 1 becomes the leader without the log");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.append_entries(AppendEntriesRequest::heartbeat(
+            Vote::new_committed(10, 1),
+            Some(log_id(1, 0, log_index)),
+            Some(log_id(1, 0, log_index)),
+        ))
+        .await?;
+    }
+
+    let write_res = rx.await?;
+    tracing::info!(log_index, "--- write_res: {:?}", write_res);
+
+    assert_eq!(
+        write_res.unwrap_err(),
+        RaftError::APIError(ClientWriteError::LeaderChanged(LeaderChanged::new(
+            log_id(1, 0, log_index + 1),
+            true,
+            ForwardToLeader {
+                leader_id: Some(1),
+                leader_node: Some(()),
+            }
+        )))
+    );
+
+    Ok(())
+}
+
+/// With [`PendingWritePolicy::Fail`], a client write that is committed when the leader steps down
+/// still receives an OK response once it is applied.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn committed_writes_succeed_on_step_down() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            pending_writes_on_step_down: PendingWritePolicy::Fail,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- block replication so that no log will be committed");
+    router.set_unreachable(1, true);
+
+    let (tx, rx) = oneshot::channel();
+
+    tracing::info!(log_index, "--- write a log in another task");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        tokio::spawn(async move {
+            let res = n0.client_write(ClientRequest::make_request("cli", 1)).await;
+            tx.send(res).unwrap();
+        });
+
+        router.wait(&0, timeout()).log_index(Some(log_index + 1), "log appended on leader").await?;
+    }
+
+    tracing::info!(log_index, "--- node 1 becomes the leader, and commits the log");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.append_entries(AppendEntriesRequest::heartbeat(
+            Vote::new_committed(10, 1),
+            Some(log_id(1, 0, log_index + 1)),
+            Some(log_id(1, 0, log_index + 1)),
+        ))
+        .await?;
+    }
+
+    let write_res = rx.await?;
+    tracing::info!(log_index, "--- write_res: {:?}", write_res);
+
+    let ok_resp = write_res?;
+    assert_eq!(ok_resp.log_id, log_id(1, 0, log_index + 1), "client write committed");
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
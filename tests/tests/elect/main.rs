@@ -9,3 +9,14 @@ mod fixtures;
 
 mod t10_elect_compare_last_log;
 mod t11_elect_seize_leadership;
+mod t12_elect_invariants;
+mod t13_vote_request_limits;
+mod t14_elect_manual_tick;
+mod t15_election_metrics;
+mod t16_election_veto;
+mod t17_leader_fitness_handoff;
+mod t18_blank_entry_policy;
+mod t19_mock_network;
+mod t20_leadership_info;
+mod t21_storage_check_before_election;
+mod t22_no_election_under_api_backlog;
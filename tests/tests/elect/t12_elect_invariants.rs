@@ -0,0 +1,67 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The Raft safety invariants hold while the leadership moves between nodes and logs are written.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_invariants() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- create cluster of 0,1,2");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+    router.check_invariants().await?;
+
+    for (round, id) in [1, 2, 0, 2, 1, 0].into_iter().enumerate() {
+        tracing::info!(log_index, "--- round {}: trigger election on node {}", round, id);
+        {
+            // Let the leader lease expire, otherwise the vote requests are rejected and, with
+            // elections disabled, never retried.
+            TypeConfig::sleep(Duration::from_millis(700)).await;
+
+            let n = router.get_raft_handle(&id)?;
+            n.trigger().elect().await?;
+            n.wait(timeout()).state(ServerState::Leader, "becomes leader").await?;
+
+            // The blank log of the new leader.
+            log_index += 1;
+            for i in [0, 1, 2] {
+                router.wait(&i, timeout()).applied_index(Some(log_index), "blank log is applied").await?;
+            }
+            router.check_invariants().await?;
+        }
+
+        tracing::info!(log_index, "--- round {}: write logs to node {}", round, id);
+        {
+            log_index += router.client_request_many(id, "foo", 5).await?;
+            for i in [0, 1, 2] {
+                router.wait(&i, timeout()).applied_index(Some(log_index), "logs are applied").await?;
+            }
+            router.check_invariants().await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2000))
+}
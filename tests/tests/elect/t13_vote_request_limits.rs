@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::raft::VoteRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Vote requests from a candidate campaigning too often are rejected.
+///
+/// - Bring up a cluster of 3 voters, with `vote_request_min_interval` enabled, and wait for the
+///   leader lease to expire.
+/// - Send vote requests to node-1 from node-2.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn vote_request_limits() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            vote_request_min_interval: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let last_log_id = Some(log_id(1, 0, log_index + 100));
+
+    tracing::info!(log_index, "--- wait for the leader lease on node-1 to expire");
+    {
+        tokio::time::sleep(Duration::from_millis(1_000)).await;
+    }
+
+    tracing::info!(log_index, "--- the first vote request from a member is granted");
+    {
+        let resp = n1.vote(VoteRequest::new(Vote::new(5, 2), last_log_id)).await?;
+        assert!(resp.is_granted_to(&Vote::new(5, 2)));
+    }
+
+    tracing::info!(
+        log_index,
+        "--- another one within vote_request_min_interval is rejected"
+    );
+    {
+        let resp = n1.vote(VoteRequest::new(Vote::new(6, 2), last_log_id)).await?;
+        assert!(!resp.vote_granted);
+        assert_eq!(Vote::new(5, 2), resp.vote);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- after vote_request_min_interval the candidate is served again"
+    );
+    {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let resp = n1.vote(VoteRequest::new(Vote::new(7, 2), last_log_id)).await?;
+        assert!(resp.is_granted_to(&Vote::new(7, 2)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `manual_tick` enabled, a follower does not elect until the application calls
+/// [`Raft::tick()`](openraft::Raft::tick).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn elect_manual_tick() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            manual_tick: true,
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- shutdown the leader");
+    {
+        let (n0, _, _) = router.remove_node(0).unwrap();
+        n0.shutdown().await?;
+    }
+
+    tracing::info!(log_index, "--- no election without ticks");
+    {
+        TypeConfig::sleep(Duration::from_millis(1_000)).await;
+
+        for id in [1, 2] {
+            let m = router.get_raft_handle(&id)?.metrics().borrow().clone();
+            assert_eq!(ServerState::Follower, m.state, "node-{} is still a follower", id);
+            assert_eq!(Some(0), m.current_leader, "node-{} still follows node-0", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- ticks drive an election");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let n2 = router.get_raft_handle(&2)?;
+
+        let elected = async {
+            loop {
+                n1.tick().await?;
+                n2.tick().await?;
+
+                let leaders = [&n1, &n2].iter().filter(|n| n.metrics().borrow().state == ServerState::Leader).count();
+                if leaders > 0 {
+                    return anyhow::Ok(());
+                }
+
+                TypeConfig::sleep(Duration::from_millis(config.heartbeat_interval)).await;
+            }
+        };
+
+        tokio::time::timeout(Duration::from_millis(5_000), elected).await??;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Elections and their outcome are counted in
+/// [`RaftMetrics::elections`](openraft::RaftMetrics::elections).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn election_metrics() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            // Keep the lease of the leader valid while node-2 asks for votes, on a slow machine.
+            election_timeout_min: 3_000,
+            election_timeout_max: 3_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- node-0 won the first election");
+    {
+        let m = router.get_raft_handle(&0)?.metrics().borrow().clone();
+        assert_eq!(1, m.elections.started);
+        assert_eq!(1, m.elections.won);
+        assert_eq!(0, m.elections.lost);
+    }
+
+    tracing::info!(log_index, "--- transfer leadership to node-1");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().transfer_leader(1).await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        n1.wait(timeout())
+            .metrics(
+                |m| m.elections.started == 1 && m.elections.won == 1,
+                "node-1 won an election",
+            )
+            .await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(
+            Some("leadership is transferred to this node".to_string()),
+            m.elections.last_start_reason
+        );
+
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.elections.higher_term_seen >= 1, "node-0 saw the term of node-1")
+            .await?;
+
+        // The lease of node-1 is extended on the followers by the blank log it appends.
+        for id in [0, 2] {
+            router
+                .wait(&id, timeout())
+                .applied_index(Some(log_index + 1), "received the blank log of node-1")
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- node-2 is rejected while the lease of node-1 is valid");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect().await?;
+
+        n2.wait(timeout()).metrics(|m| m.elections.votes_rejected >= 1, "node-2 is rejected").await?;
+
+        tracing::info!(
+            log_index,
+            "--- node-2 starts another election, the previous one is lost"
+        );
+
+        n2.trigger().elect().await?;
+        n2.wait(timeout()).metrics(|m| m.elections.lost >= 1, "node-2 lost an election").await?;
+
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(0, m.elections.won);
+        assert_eq!(2, m.elections.started);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// An election vetoed by the hook set with `Raft::set_election_veto()` is not started, and the
+/// veto is counted in the election metrics.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn election_veto() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let n1 = router.get_raft_handle(&1)?;
+
+    n1.set_election_veto(|_reason| Err("disk is unhealthy".to_string())).await?;
+
+    tracing::info!(log_index, "--- node-1 does not elect when triggered");
+    {
+        n1.trigger().elect().await?;
+
+        let m = n1.wait(timeout()).metrics(|m| m.elections.vetoed == 1, "election of node-1 is vetoed").await?;
+        assert_eq!(0, m.elections.started);
+        assert_eq!(Some("disk is unhealthy".to_string()), m.elections.last_veto_reason);
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(1, m.current_term);
+    }
+
+    tracing::info!(log_index, "--- node-1 does not take over a transferred leadership");
+    {
+        n0.trigger().transfer_leader(1).await?;
+
+        let m = n1.wait(timeout()).metrics(|m| m.elections.vetoed == 2, "election of node-1 is vetoed").await?;
+        assert_eq!(0, m.elections.started);
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(Some(0), m.current_leader);
+    }
+
+    tracing::info!(log_index, "--- node-1 elects once the hook is removed");
+    {
+        n1.remove_election_veto().await?;
+        n0.trigger().transfer_leader(1).await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(1, m.elections.started);
+        assert_eq!(2, m.elections.vetoed);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
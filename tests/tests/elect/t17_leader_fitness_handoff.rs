@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A leader hands off its leadership to an up-to-date voter that is significantly fitter, scored
+/// by the function set with `Raft::set_leader_fitness()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leader_fitness_handoff() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            leader_fitness_interval: 100,
+            leader_fitness_margin: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- a voter within the margin does not take over");
+    {
+        n0.set_leader_fitness(|id, _node| if *id == 1 { 5 } else { 0 }).await?;
+
+        sleep(Duration::from_millis(500)).await;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, m.state);
+        assert_eq!(0, m.leader_fitness_handoffs);
+    }
+
+    tracing::info!(log_index, "--- a significantly fitter voter takes over");
+    {
+        for id in [0, 1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            n.set_leader_fitness(|id, _node| if *id == 2 { 100 } else { 0 }).await?;
+        }
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.wait(timeout()).state(ServerState::Leader, "node-2 becomes leader").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(1, m.leader_fitness_handoffs);
+    }
+
+    tracing::info!(log_index, "--- the fittest leader keeps the leadership");
+    {
+        sleep(Duration::from_millis(500)).await;
+
+        let m = router.get_raft_handle(&2)?.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, m.state);
+        assert_eq!(0, m.leader_fitness_handoffs);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3000))
+}
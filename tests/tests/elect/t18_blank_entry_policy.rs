@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::BlankEntryPolicy;
+use openraft::Config;
+use openraft::ReadPolicy;
+use openraft::ServerState;
+use tokio::time::sleep;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `BlankEntryPolicy::Never`, a leader appends no blank log: the logs of previous terms are
+/// committed with the first log it proposes, and a leader whose logs are all committed serves
+/// reads at once.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn blank_entry_policy_never() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            blank_entry_policy: BlankEntryPolicy::Never,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    for id in [0, 1, 2] {
+        router.new_raft_node(id).await;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- the first leader appends no blank log");
+    {
+        n0.initialize(btreeset! {0,1,2}).await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node-0 becomes leader").await?;
+
+        sleep(Duration::from_millis(300)).await;
+
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(Some(0), m.last_log_index, "only the membership log");
+        assert_eq!(None, m.last_applied, "the membership log is not committed");
+        assert!(!m.current_term_committed);
+    }
+
+    tracing::info!("--- the first client write commits the logs of previous terms");
+    {
+        router.client_request(0, "0", 0).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(1), "client write is applied").await?;
+        }
+        let m = n0.wait(timeout()).metrics(|m| m.current_term_committed, "current term committed").await?;
+        assert_eq!(Some(1), m.last_log_index);
+    }
+
+    tracing::info!("--- a leader whose logs are all committed serves reads without a blank log");
+    {
+        n0.trigger().transfer_leader(1).await?;
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let read_log_id = n1.ensure_linearizable(ReadPolicy::ReadIndex).await?;
+        assert_eq!(Some(log_id(1, 0, 1)), read_log_id);
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(Some(1), m.last_log_index, "no blank log is appended");
+        assert!(!m.current_term_committed);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::Raft;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft::testing::network::MockBehavior;
+use openraft::testing::network::MockNetwork;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::ut_harness;
+
+/// Scripted faults of a `MockNetwork` delay replication to a follower, and a scripted higher vote
+/// makes the leader step down.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn mock_network() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let network = MockNetwork::<MemConfig>::new();
+
+    let mut nodes = vec![];
+    for id in 0..3 {
+        let (log_store, sm) = openraft_memstore::new_mem_store();
+        let raft = Raft::new(id, config.clone(), network.clone(), log_store, sm).await?;
+        network.add_node(id, raft.clone());
+        nodes.push(raft);
+    }
+    let n0 = nodes[0].clone();
+
+    tracing::info!("--- initialize node 0 as the leader");
+    let mut log_index = {
+        n0.initialize(btreeset! {0,1,2}).await?;
+        n0.wait(timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+        1
+    };
+
+    tracing::info!(log_index, "--- node 1 catches up after dropped AppendEntries");
+    {
+        network.drop_next(1, RPCTypes::AppendEntries, 2);
+
+        n0.client_write(ClientRequest::make_request("0", 1)).await?;
+        log_index += 1;
+
+        nodes[1].wait(timeout()).applied_index(Some(log_index), "node 1 catches up").await?;
+        assert_eq!(0, network.pending(&1, RPCTypes::AppendEntries));
+    }
+
+    tracing::info!(log_index, "--- node 2 catches up after unreachable and partial success");
+    {
+        network.script(2, RPCTypes::AppendEntries, MockBehavior::Unreachable);
+        network.script(2, RPCTypes::AppendEntries, MockBehavior::PartialSuccess(1));
+
+        for serial in 2..5 {
+            n0.client_write(ClientRequest::make_request("0", serial)).await?;
+            log_index += 1;
+        }
+
+        nodes[2].wait(timeout()).applied_index(Some(log_index), "node 2 catches up").await?;
+        assert_eq!(0, network.pending(&2, RPCTypes::AppendEntries));
+    }
+
+    tracing::info!(log_index, "--- node 0 steps down when node 1 replies a higher vote");
+    {
+        network.script(1, RPCTypes::AppendEntries, MockBehavior::HigherVote(Vote::new(10, 1)));
+        n0.trigger().heartbeat().await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.current_term == 10 && m.state != ServerState::Leader,
+                "node 0 sees the higher vote",
+            )
+            .await?;
+        assert_eq!(0, network.pending(&1, RPCTypes::AppendEntries));
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
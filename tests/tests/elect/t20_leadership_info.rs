@@ -0,0 +1,88 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::raft::NoElectionReason;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// `Raft::leadership_info()` tells the leader a node believes in, and why it does not elect.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn leadership_info() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            // Keep the vote of the followers valid during the test, on a slow machine.
+            election_timeout_min: 3_000,
+            election_timeout_max: 3_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- the leader");
+    {
+        let info = router.get_raft_handle(&0)?.leadership_info().await?;
+        assert_eq!(0, info.id);
+        assert_eq!(ServerState::Leader, info.state);
+        assert!(info.vote_committed);
+        assert_eq!(Some(0), info.current_leader);
+        assert_eq!(Some(NoElectionReason::Leader), info.no_election_reason);
+    }
+
+    tracing::info!(log_index, "--- a follower heard from the leader");
+    {
+        let info = router.get_raft_handle(&1)?.leadership_info().await?;
+        assert_eq!(ServerState::Follower, info.state);
+        assert!(info.vote_committed);
+        assert_eq!(Some(0), info.current_leader);
+
+        let since_last_heard = info.since_last_heard.unwrap();
+        assert!(since_last_heard < Duration::from_millis(3_000));
+
+        let Some(NoElectionReason::LeaseNotExpired { remaining }) = info.no_election_reason else {
+            panic!("expect LeaseNotExpired, got: {:?}", info.no_election_reason);
+        };
+        assert!(remaining > Duration::from_millis(3_000));
+    }
+
+    tracing::info!(log_index, "--- a learner does not elect");
+    {
+        let info = router.get_raft_handle(&3)?.leadership_info().await?;
+        assert_eq!(ServerState::Learner, info.state);
+        assert_eq!(Some(0), info.current_leader);
+        assert_eq!(Some(NoElectionReason::NotVoter), info.no_election_reason);
+    }
+
+    tracing::info!(log_index, "--- a follower with election disabled");
+    {
+        let n2 = router.get_raft_handle(&2)?;
+        n2.runtime_config().elect(false);
+
+        let info = n2.leadership_info().await?;
+        assert_eq!(Some(NoElectionReason::ElectionDisabled), info.no_election_reason);
+    }
+
+    tracing::info!(log_index, "--- a node that is not initialized");
+    {
+        router.new_raft_node(4).await;
+
+        let info = router.get_raft_handle(&4)?.leadership_info().await?;
+        assert_eq!(ServerState::Learner, info.state);
+        assert!(!info.vote_committed);
+        assert_eq!(None, info.current_leader);
+        assert_eq!(Some(NoElectionReason::Uninitialized), info.no_election_reason);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::raft::NoElectionReason;
+use openraft::storage::RaftLogReader;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A node whose last log entry can not be read back does not start an election, and reports the
+/// failed storage check, until the storage is repaired.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_check_before_election() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let (mut sto1, _sm1) = router.get_storage_handle(&1)?;
+
+    tracing::info!(log_index, "--- garble the last log of node-1");
+    let last = sto1.try_get_log_entries(log_index..=log_index).await?.pop().unwrap();
+    sto1.garble_log(log_index).await;
+
+    tracing::info!(log_index, "--- node-1 does not elect when triggered");
+    {
+        n1.trigger().elect().await?;
+
+        let m = n1
+            .wait(timeout())
+            .metrics(|m| m.storage_check_failure.is_some(), "storage check of node-1 fails")
+            .await?;
+        assert_eq!(0, m.elections.started);
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(1, m.current_term);
+
+        let info = n1.leadership_info().await?;
+        assert!(
+            matches!(
+                info.no_election_reason,
+                Some(NoElectionReason::StorageCheckFailed { .. })
+            ),
+            "got: {:?}",
+            info.no_election_reason
+        );
+    }
+
+    tracing::info!(log_index, "--- node-1 elects once the log is repaired");
+    {
+        sto1.corrupt_log(last).await;
+        router.get_raft_handle(&0)?.trigger().transfer_leader(1).await?;
+
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(None, m.storage_check_failure);
+        assert_eq!(1, m.elections.started);
+    }
+
+    Ok(())
+}
+
+/// A slow log store does not block `RaftCore` while the storage is checked before an election:
+/// the node keeps responding, and elects once the check passes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_check_before_election_slow_log_reads() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let (_sto1, sm1) = router.get_storage_handle(&1)?;
+
+    tracing::info!(log_index, "--- slow down reading logs on node-1, then trigger election");
+    {
+        sm1.block.set_blocking(BlockOperation::ReadLog, Duration::from_millis(2_000));
+        n1.trigger().elect().await?;
+    }
+
+    tracing::info!(log_index, "--- node-1 keeps responding while its storage is checked");
+    {
+        let state = tokio::time::timeout(Duration::from_millis(500), n1.with_raft_state(|st| st.server_state))
+            .await
+            .expect("RaftCore should not be blocked by the storage check")?;
+        assert_eq!(ServerState::Follower, state);
+        assert_eq!(0, n1.metrics().borrow().elections.started);
+    }
+
+    tracing::info!(log_index, "--- node-1 elects once the check passes");
+    {
+        n1.wait(Some(Duration::from_millis(5_000)))
+            .state(ServerState::Leader, "node-1 becomes leader")
+            .await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(None, m.storage_check_failure);
+        assert_eq!(1, m.elections.started);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
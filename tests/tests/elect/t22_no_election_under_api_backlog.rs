@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A follower whose `RaftCore` falls behind its API messages handles the Leader's queued
+/// heartbeats before the ticks queued after them, and does not start an election while the
+/// Leader is alive.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn no_election_under_api_backlog() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let term = n1.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- stall RaftCore of node-1 and flood its API channel");
+    {
+        // Block RaftCore for longer than the election timeout, so that heartbeats and ticks are
+        // queued behind the flood.
+        n1.external_request(|_| std::thread::sleep(Duration::from_millis(1_000))).await?;
+
+        let mut flood = Vec::new();
+        for _ in 0..200 {
+            let n1 = n1.clone();
+            flood.push(tokio::spawn(async move { n1.with_raft_state(|_| ()).await }));
+        }
+
+        for h in flood {
+            h.await??;
+        }
+    }
+
+    tracing::info!(log_index, "--- node-1 keeps following node-0");
+    {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(ServerState::Follower, m.state);
+        assert_eq!(Some(0), m.current_leader);
+        assert_eq!(term, m.current_term, "node-1 does not start an election");
+        assert_eq!(0, m.elections.started);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+#![cfg(feature = "failpoints")]
+#![cfg_attr(feature = "bt", feature(error_generic_member_access))]
+
+#[macro_use]
+#[path = "../fixtures/mod.rs"]
+mod fixtures;
+
+// Failpoints are global to the process:
+// every test holds a `fail::FailScenario` so that they do not run at the same time.
+
+mod t10_save_vote;
+mod t20_send_append_entries;
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft::error::Fatal;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A node stops with a storage error at the failpoints around saving a vote, and the vote is saved
+/// only if it fails after saving.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn save_vote() -> Result<()> {
+    let scenario = fail::FailScenario::setup();
+
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- node 1 fails before saving the vote");
+    {
+        let (mut sto, _sm) = router.get_storage_handle(&1)?;
+        let vote = sto.read_vote().await?;
+
+        fail::cfg("openraft::before_save_vote", "return").unwrap();
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.trigger().elect().await?;
+        n1.wait(timeout()).state(ServerState::Shutdown, "node 1 stops").await?;
+
+        let running_state = n1.metrics().borrow().running_state.clone();
+        assert!(matches!(running_state, Err(Fatal::StorageError(_))));
+        assert_eq!(vote, sto.read_vote().await?, "vote is not saved");
+
+        fail::remove("openraft::before_save_vote");
+    }
+
+    tracing::info!(log_index, "--- node 2 fails after saving the vote");
+    {
+        fail::cfg("openraft::after_save_vote", "return").unwrap();
+
+        let n2 = router.get_raft_handle(&2)?;
+        n2.trigger().elect().await?;
+        n2.wait(timeout()).state(ServerState::Shutdown, "node 2 stops").await?;
+
+        let running_state = n2.metrics().borrow().running_state.clone();
+        assert!(matches!(running_state, Err(Fatal::StorageError(_))));
+
+        let (mut sto, _sm) = router.get_storage_handle(&2)?;
+        assert_eq!(Some(Vote::new(2, 2)), sto.read_vote().await?, "vote is saved");
+    }
+
+    scenario.teardown();
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Replication sends the logs again after the failpoint fails sending them.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn send_append_entries() -> Result<()> {
+    let scenario = fail::FailScenario::setup();
+
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- logs are replicated after the first sends fail");
+    {
+        fail::cfg("openraft::before_send_append_entries", "2*return").unwrap();
+
+        router.client_request_many(0, "0", 3).await?;
+        log_index += 3;
+
+        router.wait_for_log(&btreeset! {0,1,2}, Some(log_index), timeout(), "replicated").await?;
+    }
+
+    scenario.teardown();
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -51,15 +51,23 @@ use openraft::network::RPCOption;
 use openraft::network::RaftNetworkFactory;
 use openraft::raft::AppendEntriesRequest;
 use openraft::raft::AppendEntriesResponse;
+use openraft::raft::ApplyResultsRequest;
 use openraft::raft::ClientWriteResponse;
+use openraft::raft::DecommissionRequest;
+use openraft::raft::HelloRequest;
 use openraft::raft::InstallSnapshotRequest;
+use openraft::raft::LogRepairRequest;
+use openraft::raft::NodeInfo;
 use openraft::raft::SnapshotResponse;
+use openraft::raft::StateMachineChecksumRequest;
 use openraft::raft::TransferLeaderRequest;
 use openraft::raft::VoteRequest;
 use openraft::raft::VoteResponse;
 use openraft::storage::RaftLogStorage;
 use openraft::storage::RaftStateMachine;
 use openraft::storage::Snapshot;
+use openraft::testing::invariants::InvariantChecker;
+use openraft::testing::invariants::NodeObservation;
 use openraft_memstore::ClientRequest;
 use openraft_memstore::ClientResponse;
 use openraft_memstore::IntoMemClientRequest;
@@ -194,6 +202,7 @@ impl fmt::Display for Direction {
 
 use Direction::NetRecv;
 use Direction::NetSend;
+use openraft::alias::EntryOf;
 use openraft::alias::LogIdOf;
 use openraft::alias::VoteOf;
 use openraft::entry::RaftEntry;
@@ -231,6 +240,21 @@ impl RPCErrorType {
                 RPCTypes::TransferLeader => {
                     unreachable!("TransferLeader RPC should not be too large")
                 }
+                RPCTypes::Decommission => {
+                    unreachable!("Decommission RPC should not be too large")
+                }
+                RPCTypes::StateMachineChecksum => {
+                    unreachable!("StateMachineChecksum RPC should not be too large")
+                }
+                RPCTypes::RepairLog => {
+                    unreachable!("RepairLog RPC should not be too large")
+                }
+                RPCTypes::ApplyResults => {
+                    unreachable!("ApplyResults RPC should not be too large")
+                }
+                RPCTypes::Hello => {
+                    unreachable!("Hello RPC should not be too large")
+                }
             },
         }
     }
@@ -249,6 +273,11 @@ where C::SnapshotData: fmt::Debug
     InstallFullSnapshot(Snapshot<C>),
     Vote(VoteRequest<C>),
     TransferLeader(TransferLeaderRequest<C>),
+    Decommission(DecommissionRequest<C>),
+    StateMachineChecksum(StateMachineChecksumRequest<C>),
+    RepairLog(LogRepairRequest<C>),
+    ApplyResults(ApplyResultsRequest<C>),
+    Hello(HelloRequest<C>),
 }
 
 impl<C: RaftTypeConfig> RPCRequest<C>
@@ -261,6 +290,11 @@ where C::SnapshotData: fmt::Debug
             RPCRequest::InstallFullSnapshot(_) => RPCTypes::InstallSnapshot,
             RPCRequest::Vote(_) => RPCTypes::Vote,
             RPCRequest::TransferLeader(_) => RPCTypes::TransferLeader,
+            RPCRequest::Decommission(_) => RPCTypes::Decommission,
+            RPCRequest::StateMachineChecksum(_) => RPCTypes::StateMachineChecksum,
+            RPCRequest::RepairLog(_) => RPCTypes::RepairLog,
+            RPCRequest::ApplyResults(_) => RPCTypes::ApplyResults,
+            RPCRequest::Hello(_) => RPCTypes::Hello,
         }
     }
 }
@@ -301,6 +335,9 @@ pub struct TypedRaftRouter {
 
     /// A hook function to be called when before an RPC is sent to target node.
     rpc_pre_hook: Arc<Mutex<HashMap<RPCTypes, RPCPreHook>>>,
+
+    /// Checks the Raft safety invariants, with the history of every check.
+    invariants: Arc<Mutex<InvariantChecker<MemConfig>>>,
 }
 
 /// Default `RaftRouter` for memstore.
@@ -337,6 +374,7 @@ impl Builder {
             append_entries_quota: Arc::new(Mutex::new(None)),
             rpc_count: Default::default(),
             rpc_pre_hook: Default::default(),
+            invariants: Default::default(),
         }
     }
 }
@@ -503,6 +541,15 @@ impl TypedRaftRouter {
         rt.insert(id, (node, log_store, sm));
     }
 
+    /// Create and register a new Raft node that sends RPCs with the given network factory.
+    pub async fn new_raft_node_with_network<N>(&mut self, id: MemNodeId, network: N)
+    where N: RaftNetworkFactory<MemConfig> {
+        let (log_store, sm) = self.new_store();
+        let node = Raft::new(id, self.config.clone(), network, log_store.clone(), sm.clone()).await.unwrap();
+        let mut rt = self.nodes.lock().unwrap();
+        rt.insert(id, (node, log_store, sm));
+    }
+
     /// Remove the target node from the routing table & isolation.
     pub fn remove_node(&mut self, id: MemNodeId) -> Option<(MemRaft, MemLogStore, MemStateMachine)> {
         let opt_handles = {
@@ -599,7 +646,8 @@ impl TypedRaftRouter {
         let request = request.into();
         let typ = request.get_type();
 
-        let rpc_pre_hook = self.rpc_pre_hook.lock().unwrap();
+        // A hook may panic on purpose to test panic handling, which poisons the lock.
+        let rpc_pre_hook = self.rpc_pre_hook.lock().unwrap_or_else(|e| e.into_inner());
 
         if let Some(hook) = rpc_pre_hook.get(&typ) {
             let res = hook(self, request, from, to);
@@ -660,6 +708,23 @@ impl TypedRaftRouter {
         Ok((x.1, x.2))
     }
 
+    /// Check the Raft safety invariants against every node in the cluster.
+    ///
+    /// Leaders and committed logs seen by earlier checks are remembered, so that a violation across
+    /// checks is found too.
+    pub async fn check_invariants(&self) -> anyhow::Result<()> {
+        let nodes = self.nodes.lock().unwrap().clone();
+
+        let mut observations = vec![];
+        for (raft, mut log_store, _sm) in nodes.into_values() {
+            let metrics = raft.metrics().borrow().clone();
+            observations.push(NodeObservation::read(&metrics, &mut log_store).await?);
+        }
+
+        self.invariants.lock().unwrap().check(&observations)?;
+        Ok(())
+    }
+
     /// Wait for metrics until it satisfies some condition.
     #[tracing::instrument(level = "info", skip(self, func))]
     pub async fn wait_for_metrics<T>(
@@ -1158,6 +1223,121 @@ impl RaftNetworkV2<MemConfig> for RaftRouterNetwork {
             ))))
         })
     }
+
+    async fn decommission(
+        &mut self,
+        rpc: DecommissionRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<(), RPCError<MemConfig>> {
+        let from_id = rpc.from_leader().leader_id().to_node_id().unwrap();
+
+        self.owner.count_rpc(RPCTypes::Decommission);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_decommission(rpc).await;
+        resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })
+    }
+
+    async fn state_machine_checksum(
+        &mut self,
+        rpc: StateMachineChecksumRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<Option<u64>, RPCError<MemConfig>> {
+        let from_id = rpc.from_leader().leader_id().to_node_id().unwrap();
+
+        self.owner.count_rpc(RPCTypes::StateMachineChecksum);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_state_machine_checksum(rpc).await;
+        resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })
+    }
+
+    async fn repair_log(
+        &mut self,
+        rpc: LogRepairRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<Vec<EntryOf<MemConfig>>, RPCError<MemConfig>> {
+        let from_id = *rpc.from();
+
+        self.owner.count_rpc(RPCTypes::RepairLog);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_repair_log(rpc).await;
+        resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })
+    }
+
+    async fn apply_results(
+        &mut self,
+        rpc: ApplyResultsRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<(), RPCError<MemConfig>> {
+        let from_id = rpc.from_leader().leader_id().to_node_id().unwrap();
+
+        self.owner.count_rpc(RPCTypes::ApplyResults);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_apply_results(rpc).await;
+        resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })
+    }
+
+    async fn hello(
+        &mut self,
+        rpc: HelloRequest<MemConfig>,
+        _option: RPCOption,
+    ) -> Result<NodeInfo, RPCError<MemConfig>> {
+        let from_id = *rpc.from();
+
+        self.owner.count_rpc(RPCTypes::Hello);
+        self.owner.call_rpc_pre_hook(rpc.clone(), from_id, self.target)?;
+        self.owner.emit_rpc_error(from_id, self.target)?;
+        self.owner.rand_send_delay().await;
+
+        let node = self.owner.get_raft_handle(&self.target)?;
+
+        let resp = node.handle_hello(rpc).await;
+        resp.map_err(|e| {
+            RPCError::Unreachable(Unreachable::new(&AnyError::error(format!(
+                "error: {} target={}",
+                e, self.target
+            ))))
+        })
+    }
 }
 
 pub enum ValueTest<T> {
@@ -9,6 +9,10 @@ mod fixtures;
 
 mod t10_initialization;
 mod t11_shutdown;
+mod t12_task_panic;
+mod t13_storage_stamp;
+mod t14_recovery_report;
+mod t15_initialize_from_snapshot;
 mod t50_follower_restart_does_not_interrupt;
 mod t50_leader_restart_clears_state;
 mod t50_single_follower_restart;
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::error::Fatal;
+use openraft::error::TaskPanicked;
+use openraft::network::RPCTypes;
+use openraft_memstore::MemNodeId;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::PreHookResult;
+use crate::fixtures::RPCRequest;
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A panic in a replication stream stops the leader with [`Fatal::TaskPanicked`], which carries
+/// the panic message.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_panic_is_fatal() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let _log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!("--- panic when replicating to node-2");
+    router.set_rpc_pre_hook(RPCTypes::AppendEntries, panic_once_to(2));
+
+    let _ = router.client_request(0, "foo", 1).await;
+
+    router.wait(&0, timeout()).state(ServerState::Shutdown, "leader stops").await?;
+
+    tracing::info!("--- calls to the stopped leader get the panic");
+    {
+        let err = router.client_request(0, "foo", 2).await.unwrap_err();
+        assert_eq!(
+            Fatal::TaskPanicked(TaskPanicked::new(
+                "replication(target=2)",
+                "replication to node-2 panicked"
+            )),
+            err.into_fatal().unwrap()
+        );
+    }
+
+    Ok(())
+}
+
+/// With [`Config::restart_replication_on_panic`] enabled, a panicked replication stream is
+/// restarted and replication resumes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn restart_replication_on_panic() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            restart_replication_on_panic: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!("--- panic when replicating to node-2");
+    router.set_rpc_pre_hook(RPCTypes::AppendEntries, panic_once_to(2));
+
+    router.client_request_many(0, "foo", 10).await?;
+    log_index += 10;
+
+    tracing::info!(log_index, "--- node-2 still receives all logs");
+    {
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node-2 catches up").await?;
+
+        let m = router.get_metrics(&0)?;
+        assert_eq!(ServerState::Leader, m.state);
+    }
+
+    Ok(())
+}
+
+/// Build an AppendEntries pre-hook that panics the first time logs are sent to `target`.
+fn panic_once_to(
+    target: MemNodeId,
+) -> impl Fn(&RaftRouter, RPCRequest<TypeConfig>, MemNodeId, MemNodeId) -> PreHookResult + Send + 'static {
+    let panicked = AtomicBool::new(false);
+
+    move |_router, req, _from, to| {
+        if let RPCRequest::AppendEntries(a) = req
+            && to == target
+            && !a.entries.is_empty()
+            && !panicked.swap(true, Ordering::Relaxed)
+        {
+            panic!("replication to node-{} panicked", target);
+        }
+        Ok(())
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
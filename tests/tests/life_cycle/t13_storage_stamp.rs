@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Raft;
+use openraft::error::Fatal;
+use openraft::error::StorageStampMismatch;
+use openraft::storage::RaftLogStorage;
+use openraft::storage::StorageStamp;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A storage is stamped with the node id and cluster name on the first start; starting another
+/// node, or a node of another cluster, on it fails with [`Fatal::StorageStampMismatch`].
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn storage_stamp_mismatch() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            cluster_name: "foo".to_string(),
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let (node, mut sto, sm) = router.remove_node(0).unwrap();
+    node.shutdown().await?;
+
+    assert_eq!(Some(StorageStamp::new("foo", 0)), sto.read_stamp().await?);
+
+    tracing::info!(log_index, "--- start node-1 on the storage of node-0");
+    {
+        let res = Raft::new(1, config.clone(), router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().unwrap();
+        assert_eq!(
+            Fatal::StorageStampMismatch(StorageStampMismatch::new(
+                StorageStamp::new("foo", 1),
+                StorageStamp::new("foo", 0)
+            )),
+            err
+        );
+    }
+
+    tracing::info!(
+        log_index,
+        "--- start node-0 of another cluster on the storage of node-0"
+    );
+    {
+        let bar = Arc::new(
+            Config {
+                cluster_name: "bar".to_string(),
+                ..config.as_ref().clone()
+            }
+            .validate()?,
+        );
+        let res = Raft::new(0, bar, router.clone(), sto.clone(), sm.clone()).await;
+        let err = res.err().unwrap();
+        assert_eq!("storage belongs to foo/0, but this node is bar/0", err.to_string());
+    }
+
+    tracing::info!(log_index, "--- restart node-0 on its own storage");
+    {
+        router.new_raft_node_with_sto(0, sto, sm).await;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node-0 restarted").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Raft;
+use openraft::Vote;
+use openraft::storage::MembershipSource;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// [`Raft::recovery_report`](openraft::Raft::recovery_report) reports the state recovered from the
+/// storage when a node starts.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn recovery_report() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- a new node recovers nothing");
+    {
+        let (sto, sm) = router.new_store();
+        let node = Raft::new(5, config.clone(), router.clone(), sto, sm).await?;
+
+        let report = node.recovery_report();
+        assert_eq!(Vote::default(), report.vote);
+        assert_eq!(None, report.last_log_id);
+        assert_eq!(None, report.last_applied);
+        assert_eq!(None, report.snapshot);
+        assert_eq!(MembershipSource::Empty, report.membership_source);
+
+        node.shutdown().await?;
+    }
+
+    tracing::info!("--- bring up cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+    log_index += router.client_request_many(0, "foo", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+    let (node, sto, sm) = router.remove_node(0).unwrap();
+    node.shutdown().await?;
+
+    tracing::info!(log_index, "--- a restarted node recovers its logs and state machine");
+    {
+        let node = Raft::new(0, config.clone(), router.clone(), sto, sm).await?;
+
+        let report = node.recovery_report();
+        assert_eq!(Vote::new_committed(1, 0), report.vote);
+        assert_eq!(Some(log_id(1, 0, log_index)), report.last_log_id);
+        assert_eq!(Some(log_id(1, 0, log_index)), report.last_applied);
+        assert_eq!(None, report.purged);
+        assert_eq!(0, report.reapplied_logs);
+        assert_eq!(Some(log_id(0, 0, 0)), *report.membership.log_id());
+        assert_eq!(MembershipSource::StateMachine, report.membership_source);
+
+        tracing::info!("{}", report);
+
+        node.shutdown().await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
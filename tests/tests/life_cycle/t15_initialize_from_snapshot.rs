@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A snapshot exported with `Raft::export_bootstrap()` seeds a brand new cluster with other node
+/// ids, initialized with `Raft::initialize_from_snapshot()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn initialize_from_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing the old cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- no snapshot to export yet");
+    {
+        let bundle = n0.export_bootstrap(btreeset! {10,11,12}).await?;
+        assert!(bundle.is_none());
+    }
+
+    tracing::info!(log_index, "--- write logs and build a snapshot");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "snapshot built").await?;
+    }
+
+    let bundle = n0.export_bootstrap(btreeset! {10,11,12}).await?.unwrap();
+
+    tracing::info!(log_index, "--- the bundle starts at the first log id");
+    {
+        assert_eq!(Some(log_id(0, 0, 0)), bundle.meta.last_log_id);
+        assert_eq!(Some(log_id(0, 0, 0)), *bundle.meta.last_membership.log_id());
+        assert_eq!(
+            btreeset! {10,11,12},
+            bundle.meta.last_membership.membership().voter_ids().collect()
+        );
+    }
+
+    tracing::info!(log_index, "--- initialize the new cluster from the bundle");
+    {
+        for id in [10, 11, 12] {
+            router.new_raft_node(id).await;
+        }
+
+        let n10 = router.get_raft_handle(&10)?;
+        n10.initialize_from_snapshot(bundle).await?;
+        n10.wait(timeout()).state(ServerState::Leader, "node-10 becomes leader").await?;
+    }
+
+    tracing::info!(log_index, "--- the new cluster has the data of the old one");
+    {
+        let new_log_index = router.client_request_many(10, "bar", 3).await? + 1;
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        let old = sm0.get_state_machine().await.client_status;
+
+        for id in [10, 11, 12] {
+            router.wait(&id, timeout()).applied_index(Some(new_log_index), "applied new logs").await?;
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            let status = sm.get_state_machine().await.client_status;
+            assert_eq!(
+                old.get("foo"),
+                status.get("foo"),
+                "node-{} has the data of the old cluster",
+                id
+            );
+            assert!(
+                status.contains_key("bar"),
+                "node-{} has the data of the new cluster",
+                id
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- the old cluster is not affected");
+    {
+        let m = n0.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, m.state);
+        assert_eq!(
+            btreeset! {0,1,2},
+            m.membership_config.membership().voter_ids().collect()
+        );
+        assert_eq!(Some(log_id(1, 0, log_index)), m.last_applied);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
@@ -8,3 +8,6 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_save_committed;
+mod t20_local_entries;
+mod t30_log_archiver;
+mod t31_restore_state_machine;
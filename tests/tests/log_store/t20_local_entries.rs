@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Local entries appended through the `Raft` handle are kept in the log store of the node only,
+/// and do not affect the Raft log.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn local_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- append local entries on a follower");
+    {
+        assert_eq!(0, n1.append_local_entry(b"a".to_vec()).await?);
+        assert_eq!(1, n1.append_local_entry(b"b".to_vec()).await?);
+        assert_eq!(2, n1.append_local_entry(b"c".to_vec()).await?);
+
+        let got = n1.read_local_entries(1, 10).await?;
+        assert_eq!(vec![(1, b"b".to_vec()), (2, b"c".to_vec())], got);
+
+        let got = router.get_raft_handle(&0)?.read_local_entries(0, 10).await?;
+        assert!(got.is_empty(), "local entries are not replicated");
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).log_index(Some(log_index), "no log is appended").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- purge local entries, sequence numbers keep increasing");
+    {
+        n1.purge_local_entries(1).await?;
+        assert_eq!(vec![(2, b"c".to_vec())], n1.read_local_entries(0, 10).await?);
+
+        assert_eq!(3, n1.append_local_entry(b"d".to_vec()).await?);
+        assert_eq!(vec![(2, b"c".to_vec())], n1.read_local_entries(0, 1).await?);
+    }
+
+    tracing::info!(log_index, "--- replication is not affected");
+    {
+        log_index += router.client_request_many(0, "0", 5).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A slow local entry IO does not block `RaftCore`: the node keeps responding to API calls and
+/// replicating logs while a local entry is being appended.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn slow_local_entries_do_not_block_raft() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let (_ls1, sm1) = router.get_storage_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- append a local entry on a follower, with a slow log store"
+    );
+    let appending = {
+        sm1.block.set_blocking(BlockOperation::AppendLocal, Duration::from_millis(3_000));
+
+        let n1 = n1.clone();
+        tokio::spawn(async move { n1.append_local_entry(b"a".to_vec()).await })
+    };
+
+    TypeConfig::sleep(Duration::from_millis(200)).await;
+
+    tracing::info!(log_index, "--- the follower keeps responding and replicating");
+    {
+        let state = tokio::time::timeout(Duration::from_millis(500), n1.with_raft_state(|st| st.server_state))
+            .await
+            .expect("RaftCore should not be blocked by the local entry IO")?;
+        assert_eq!(ServerState::Follower, state);
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "write logs").await?;
+
+        assert!(!appending.is_finished(), "the local entry is still being appended");
+    }
+
+    tracing::info!(log_index, "--- the local entry is appended once the log store responds");
+    {
+        assert_eq!(0, appending.await??);
+        assert_eq!(vec![(0, b"a".to_vec())], n1.read_local_entries(0, 10).await?);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,107 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+use openraft::base::BoxFuture;
+use openraft::storage::LogArchiver;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Records the indexes of archived entries, or fails if `fail` is set.
+struct Archiver {
+    fail: bool,
+    archived: Arc<Mutex<Vec<u64>>>,
+}
+
+impl LogArchiver<TypeConfig> for Archiver {
+    fn archive(&mut self, entries: Vec<openraft::Entry<TypeConfig>>) -> BoxFuture<'_, Result<(), io::Error>> {
+        Box::pin(async move {
+            if self.fail {
+                return Err(io::Error::other("cold storage is unavailable"));
+            }
+            self.archived.lock().unwrap().extend(entries.iter().map(|e| e.log_id.index));
+            Ok(())
+        })
+    }
+}
+
+/// Logs are passed to the `LogArchiver` before they are purged, and are kept if archiving fails.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_archiver() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: u64::MAX,
+            max_payload_entries: 4,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    log_index += router.client_request_many(0, "0", 10).await?;
+    router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+    n0.trigger().snapshot().await?;
+    router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+
+    let archived = Arc::new(Mutex::new(vec![]));
+
+    tracing::info!(log_index, "--- logs are kept if archiving fails");
+    {
+        n0.set_log_archiver(Archiver {
+            fail: true,
+            archived: archived.clone(),
+        })
+        .await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        let res = router
+            .wait(&0, Some(Duration::from_millis(500)))
+            .purged(Some(log_id(1, 0, log_index)), "not purged")
+            .await;
+        assert!(res.is_err(), "logs are not purged if not archived");
+        assert!(archived.lock().unwrap().is_empty());
+    }
+
+    tracing::info!(log_index, "--- logs kept are archived along with the next purge");
+    {
+        n0.set_log_archiver(Archiver {
+            fail: false,
+            archived: archived.clone(),
+        })
+        .await?;
+
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write logs").await?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+
+        n0.trigger().purge_log(log_index).await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purged").await?;
+
+        let want = (0..=log_index).collect::<Vec<_>>();
+        assert_eq!(want, *archived.lock().unwrap());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
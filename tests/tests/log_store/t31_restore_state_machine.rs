@@ -0,0 +1,126 @@
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Entry;
+use openraft::SnapshotPolicy;
+use openraft::base::BoxFuture;
+use openraft::storage::LogArchiver;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::restore_state_machine;
+use openraft_memstore::TypeConfig;
+use openraft_memstore::new_mem_store;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Keeps every archived entry in memory.
+struct Archiver {
+    archived: Arc<Mutex<Vec<Entry<TypeConfig>>>>,
+}
+
+impl LogArchiver<TypeConfig> for Archiver {
+    fn archive(&mut self, entries: Vec<Entry<TypeConfig>>) -> BoxFuture<'_, Result<(), io::Error>> {
+        self.archived.lock().unwrap().extend(entries);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Rebuild the state as of a historical log id from a snapshot and the archived logs.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn restore_state_machine_from_archive() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let archived = Arc::new(Mutex::new(vec![]));
+    n0.set_log_archiver(Archiver {
+        archived: archived.clone(),
+    })
+    .await?;
+
+    tracing::info!(log_index, "--- write logs of client a, snapshot and purge them");
+    let snapshot = {
+        log_index += router.client_request_many(0, "a", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write a").await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge a").await?;
+        n0.get_snapshot().await?.unwrap()
+    };
+
+    tracing::info!(log_index, "--- write logs of client b and c, snapshot and purge them");
+    let b_index = {
+        log_index += router.client_request_many(0, "b", 5).await?;
+        let b_index = log_index;
+        log_index += router.client_request_many(0, "c", 5).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "write b and c").await?;
+
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).purged(Some(log_id(1, 0, log_index)), "purge b and c").await?;
+        b_index
+    };
+
+    let entries = archived.lock().unwrap().clone();
+    assert_eq!(
+        (0..=log_index).collect::<Vec<_>>(),
+        entries.iter().map(|e| e.log_id.index).collect::<Vec<_>>()
+    );
+
+    tracing::info!(log_index, "--- restore the state right after client b is written");
+    {
+        let (_, mut sm) = new_mem_store();
+        let upto = log_id(1, 0, b_index);
+        restore_state_machine(&mut sm, Some(snapshot), entries.clone(), &upto).await?;
+
+        assert_eq!(Some(upto), sm.applied_state().await?.0);
+
+        let got = sm.get_state_machine().await.client_status;
+        assert_eq!(Some("request-4"), got.get("a").map(|s| s.as_str()));
+        assert_eq!(Some("request-4"), got.get("b").map(|s| s.as_str()));
+        assert_eq!(None, got.get("c"));
+    }
+
+    tracing::info!(log_index, "--- restore the latest state from archived logs only");
+    {
+        let (_, mut sm) = new_mem_store();
+        restore_state_machine(&mut sm, None, entries.clone(), &log_id(1, 0, log_index)).await?;
+
+        let (_, live) = router.get_storage_handle(&0)?;
+        assert_eq!(
+            live.get_state_machine().await.client_status,
+            sm.get_state_machine().await.client_status
+        );
+    }
+
+    tracing::info!(log_index, "--- missing logs are an error");
+    {
+        let (_, mut sm) = new_mem_store();
+        let res = restore_state_machine(&mut sm, None, entries, &log_id(1, 0, log_index + 1)).await;
+        assert!(res.is_err(), "log {} is not archived", log_index + 1);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
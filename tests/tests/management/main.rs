@@ -8,3 +8,8 @@ mod fixtures;
 // The later tests may depend on the earlier ones.
 
 mod t10_raft_config;
+mod t20_cluster_health;
+mod t25_replication_progress;
+mod t27_state_machine_checksums;
+mod t28_runtime_log_level;
+mod t30_engine_trace;
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Get the health of every member via [`Raft::cluster_health`](openraft::Raft::cluster_health).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn cluster_health() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all members are healthy");
+    {
+        // The lag is computed from the replication progress on the leader, which may not have
+        // received the response from the learner yet.
+        n0.wait(timeout())
+            .metrics(
+                |m| {
+                    let repl = m.replication.as_ref();
+                    repl.is_some_and(|r| r.values().all(|x| x.as_ref().map(|l| l.index) == Some(log_index)))
+                },
+                "all members are replicated",
+            )
+            .await?;
+
+        let health = n0.cluster_health(Duration::from_millis(500)).await?;
+
+        assert_eq!(0, health.leader_id);
+        assert_eq!(1, health.term);
+        assert_eq!(Some(log_id(1, 0, log_index)), health.last_log_id);
+        assert_eq!(btreeset! {0,1,2,3}, health.nodes.keys().copied().collect());
+
+        for (id, node) in health.nodes.iter() {
+            assert_eq!(*id != 3, node.voter, "node-{} voter", id);
+            assert!(node.reachable, "node-{} reachable", id);
+            assert_eq!(0, node.lag, "node-{} lag", id);
+            assert_eq!(Some(1), node.term, "node-{} term", id);
+            assert_eq!(None, node.last_error, "node-{} last_error", id);
+        }
+
+        assert!(health.voters_healthy(0));
+        assert_eq!(0, health.unreachable().count());
+    }
+
+    tracing::info!(log_index, "--- node-2 is unreachable and falls behind");
+    {
+        router.set_unreachable(2, true);
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner-3 receives logs").await?;
+
+        let health = n0.cluster_health(Duration::from_millis(200)).await?;
+
+        let n2 = &health.nodes[&2];
+        assert!(!n2.reachable);
+        assert_eq!(None, n2.term);
+        assert_eq!(5, n2.lag);
+        assert!(n2.last_error.is_some());
+
+        assert_eq!(vec![&2], health.unreachable().collect::<Vec<_>>());
+        assert!(!health.voters_healthy(10));
+    }
+
+    tracing::info!(log_index, "--- node-2 is reachable again, its last error is cleared");
+    {
+        router.set_unreachable(2, false);
+
+        let health = n0.cluster_health(Duration::from_millis(500)).await?;
+
+        let n2 = &health.nodes[&2];
+        assert!(n2.reachable);
+        assert_eq!(Some(1), n2.term);
+        assert_eq!(None, n2.last_error);
+    }
+
+    tracing::info!(log_index, "--- a follower can not report the cluster health");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.cluster_health(Duration::from_millis(200)).await;
+
+        let err = res.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().unwrap().leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
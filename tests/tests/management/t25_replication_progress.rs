@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::CheckIsLeaderError;
+use openraft::error::RaftError;
+use openraft::raft::InflightData;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Get the replication progress of every target via
+/// [`Raft::replication_progress`](openraft::Raft::replication_progress).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_progress() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- write 5 logs");
+    {
+        log_index += router.client_request_many(0, "0", 5).await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- every target is replicated");
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| {
+                    let repl = m.replication.as_ref();
+                    repl.is_some_and(|r| r.values().all(|x| x.as_ref().map(|l| l.index) == Some(log_index)))
+                },
+                "all members are replicated",
+            )
+            .await?;
+
+        let progress = n0.replication_progress().await?;
+        assert_eq!(btreeset! {0,1,2,3}, progress.keys().copied().collect());
+
+        for (id, p) in progress.iter() {
+            assert_eq!(*id != 3, p.voter, "node-{} voter", id);
+            assert_eq!(Some(log_id(1, 0, log_index)), p.matching, "node-{} matching", id);
+            assert_eq!(log_index + 1, p.searching_end, "node-{} searching_end", id);
+        }
+
+        for id in [1, 2, 3] {
+            assert_eq!(InflightData::None, progress[&id].inflight, "node-{} inflight", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- a non-leader node returns ForwardToLeader");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.replication_progress().await;
+
+        let Err(RaftError::APIError(CheckIsLeaderError::ForwardToLeader(forward))) = res else {
+            panic!("expect ForwardToLeader, got: {:?}", res);
+        };
+        assert_eq!(Some(0), forward.leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft_memstore::ClientRequest;
+use openraft_memstore::IntoMemClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Compare the state machines of every member via
+/// [`Raft::state_machine_checksums`](openraft::Raft::state_machine_checksums).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn state_machine_checksums() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1, 2, 3] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- all state machines are the same");
+    {
+        let checksums = n0.state_machine_checksums(Duration::from_millis(500)).await?;
+        log_index += 1;
+
+        assert_eq!(0, checksums.leader_id);
+        assert_eq!(Some(log_id(1, 0, log_index)), checksums.at, "at a blank entry");
+        assert_eq!(btreeset! {0,1,2,3}, checksums.checksums.keys().copied().collect());
+
+        let expected = checksums.checksums[&0];
+        assert!(expected.is_some());
+        for (id, checksum) in checksums.checksums.iter() {
+            assert_eq!(expected, *checksum, "node-{} checksum", id);
+        }
+        assert_eq!(0, checksums.mismatched().count());
+    }
+
+    tracing::info!(log_index, "--- the state machine of node-2 diverges");
+    {
+        let (_sto2, sm2) = router.get_storage_handle(&2)?;
+        sm2.corrupt_client_status("0", "corrupted").await;
+
+        log_index += router.client_request_many(0, "1", 1).await?;
+
+        let checksums = n0.state_machine_checksums(Duration::from_millis(500)).await?;
+        log_index += 1;
+
+        assert_eq!(Some(log_id(1, 0, log_index)), checksums.at);
+        assert_eq!(vec![2], checksums.mismatched().copied().collect::<Vec<_>>());
+        assert_eq!(0, checksums.unknown().count());
+    }
+
+    tracing::info!(log_index, "--- learner-3 is unreachable, its checksum is unknown");
+    {
+        router.set_unreachable(3, true);
+
+        let checksums = n0.state_machine_checksums(Duration::from_millis(200)).await?;
+        log_index += 1;
+
+        assert_eq!(Some(log_id(1, 0, log_index)), checksums.at);
+        assert_eq!(None, checksums.checksums[&3]);
+        assert_eq!(vec![3], checksums.unknown().copied().collect::<Vec<_>>());
+        assert_eq!(vec![2], checksums.mismatched().copied().collect::<Vec<_>>());
+
+        router.set_unreachable(3, false);
+    }
+
+    tracing::info!(log_index, "--- no member is skipped while logs are being written");
+    {
+        let writes = {
+            let n0 = n0.clone();
+            tokio::spawn(async move {
+                for i in 0..50 {
+                    n0.client_write(ClientRequest::make_request("2", i)).await?;
+                }
+                anyhow::Ok(())
+            })
+        };
+
+        for _ in 0..5 {
+            let checksums = n0.state_machine_checksums(Duration::from_millis(1_000)).await?;
+            assert_eq!(0, checksums.unknown().count(), "no member is skipped: {}", checksums);
+            assert_eq!(vec![2], checksums.mismatched().copied().collect::<Vec<_>>());
+        }
+
+        writes.await??;
+    }
+
+    tracing::info!(log_index, "--- only the leader collects checksums");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.state_machine_checksums(Duration::from_millis(200)).await;
+        let err = res.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().unwrap().leader_id);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::LogSubsystem;
+use tracing::Level;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Override log levels of subsystems at runtime via
+/// [`RuntimeConfigHandle`](openraft::raft::RuntimeConfigHandle), while replicating.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn runtime_log_level() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(
+        log_index,
+        "--- override levels of every subsystem and of the replication to node-2"
+    );
+    {
+        for subsystem in [
+            LogSubsystem::Replication,
+            LogSubsystem::Election,
+            LogSubsystem::StorageIo,
+            LogSubsystem::Snapshot,
+        ] {
+            n0.runtime_config().log_level(subsystem, Some(Level::DEBUG));
+        }
+        n0.runtime_config().replication_log_level(&2, Some(Level::TRACE));
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "replicated with overrides").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- remove the overrides");
+    {
+        for subsystem in [
+            LogSubsystem::Replication,
+            LogSubsystem::Election,
+            LogSubsystem::StorageIo,
+            LogSubsystem::Snapshot,
+        ] {
+            n0.runtime_config().log_level(subsystem, None);
+        }
+        n0.runtime_config().replication_log_level(&2, None);
+
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "replicated without overrides").await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
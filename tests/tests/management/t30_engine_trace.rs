@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::trace::EngineInput;
+use openraft::trace::EngineTrace;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Record the engine inputs of every node with
+/// [`Raft::engine_trace`](openraft::Raft::engine_trace), and replay them without divergence.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn engine_trace_replay() -> Result<()> {
+    // The replay does not reproduce the wall clock, see the limitations in `openraft::trace`:
+    // heartbeats and elections are disabled so that no recorded decision depends on a leader
+    // lease or on an election timeout.
+    let config = Arc::new(
+        Config {
+            engine_trace_max_inputs: 100_000,
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!(log_index, "--- write logs");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        for id in [0, 1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "logs are applied").await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- replay the trace of every node");
+    for id in [0, 1, 2, 3] {
+        let n = router.get_raft_handle(&id)?;
+        let trace = n.engine_trace().unwrap();
+
+        assert_eq!(&id, trace.node_id());
+        assert!(!trace.is_truncated());
+        assert!(matches!(trace.inputs()[0], EngineInput::Startup));
+
+        let report = trace.replay()?;
+        assert_eq!(trace.inputs().len(), report.inputs);
+        assert!(report.checkpoints > 0, "node-{} has checkpoints", id);
+
+        tracing::info!(log_index, "--- node-{}: encode and decode the trace", id);
+        {
+            let bytes = trace.encode();
+            let decoded = EngineTrace::<TypeConfig>::decode(&bytes)?;
+            assert_eq!(report, decoded.replay()?);
+
+            let err = EngineTrace::<TypeConfig>::decode(&bytes[..bytes.len() - 1]).unwrap_err();
+            assert!(err.to_string().starts_with("incomplete frame at byte"), "{}", err);
+        }
+    }
+
+    tracing::info!(log_index, "--- the trace is available after shutdown");
+    {
+        let n0 = router.remove_node(0).unwrap();
+        n0.0.shutdown().await?;
+
+        let trace = n0.0.engine_trace().unwrap();
+        trace.replay()?;
+    }
+
+    Ok(())
+}
+
+/// The recording stops at `engine_trace_max_inputs`, and the recorded prefix can be replayed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn engine_trace_truncated() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            engine_trace_max_inputs: 10,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let trace = router.get_raft_handle(&0)?.engine_trace().unwrap();
+    assert!(trace.is_truncated());
+    assert_eq!(10, trace.inputs().len());
+    trace.replay()?;
+
+    Ok(())
+}
+
+/// No trace is recorded by default.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn engine_trace_disabled() -> Result<()> {
+    let config = Arc::new(Config::default().validate()?);
+
+    let mut router = RaftRouter::new(config.clone());
+    router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    assert!(router.get_raft_handle(&0)?.engine_trace().is_none());
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
@@ -6,6 +6,7 @@ use maplit::btreemap;
 use maplit::btreeset;
 use openraft::ChangeMembers;
 use openraft::Config;
+use openraft::LogIdOptionExt;
 use openraft::Membership;
 use openraft::RaftLogReader;
 use openraft::StorageHelper;
@@ -91,6 +92,19 @@ async fn add_learner_basic() -> Result<()> {
         let metrics = router.get_raft_handle(&0)?.metrics().borrow().clone();
         let node_ids = metrics.membership_config.membership().nodes().map(|x| *x.0).collect::<Vec<_>>();
         assert_eq!(vec![0, 1], node_ids);
+
+        router.wait(&1, timeout()).log_index(Some(log_index), "node-1 receives re-adding log").await?;
+
+        for id in [0, 1] {
+            let membership = router.get_raft_handle(&id)?.effective_membership();
+            assert_eq!(Some(log_index), membership.log_id().index());
+            assert!(membership.is_voter(&0));
+            assert!(!membership.is_voter(&1));
+            assert!(membership.contains(&1));
+            assert!(!membership.contains(&2));
+            assert_eq!(vec![0], membership.voter_ids().collect::<Vec<_>>());
+            assert_eq!(vec![1], membership.learner_ids().collect::<Vec<_>>());
+        }
     }
 
     Ok(())
@@ -159,6 +173,59 @@ async fn add_learner_non_blocking() -> Result<()> {
     Ok(())
 }
 
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn add_learner_and_wait() -> Result<()> {
+    //
+    // - Add an unreachable learner, expect it to return when timeout, without catching up.
+    // - Re-add it when it is reachable, expect it to return when the learner catches up.
+
+    let config = Arc::new(
+        Config {
+            replication_lag_threshold: 0,
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    router.client_request_many(0, "learner_add", 100 - log_index as usize).await?;
+    log_index = 100;
+
+    router.new_raft_node(1).await;
+    let raft = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- add unreachable node-1, returns when timeout");
+    {
+        router.set_network_error(1, true);
+
+        let resp = raft.add_learner_and_wait(1, (), Duration::from_millis(500)).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.write.log_id.index());
+        assert!(!resp.caught_up);
+        assert_eq!(None, resp.matched);
+    }
+
+    tracing::info!(log_index, "--- re-add reachable node-1, returns when caught up");
+    {
+        router.set_network_error(1, false);
+
+        let resp = raft.add_learner_and_wait(1, (), Duration::from_millis(3_000)).await?;
+        log_index += 1;
+
+        assert_eq!(log_index, resp.write.log_id.index());
+        assert!(resp.caught_up);
+        assert_eq!(Some(log_index), resp.matched.map(|x| x.index()));
+        assert_eq!(Some(0), resp.lag);
+    }
+
+    Ok(())
+}
+
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
 async fn add_learner_with_set_nodes() -> Result<()> {
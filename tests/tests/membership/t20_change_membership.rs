@@ -8,6 +8,7 @@ use openraft::RaftLogReader;
 use openraft::ServerState;
 use openraft::error::ChangeMembershipError;
 use openraft::error::ClientWriteError;
+use openraft::error::InProgress;
 
 use crate::fixtures::RaftRouter;
 use crate::fixtures::ut_harness;
@@ -215,6 +216,80 @@ async fn change_with_turn_removed_voter_to_learner() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// A change-membership is rejected with `InProgress` while the previous one is not committed, and
+/// the uncommitted change is reported in metrics.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn change_when_previous_membership_not_committed() -> anyhow::Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1,2}).await?;
+    let leader = router.get_raft_handle(&0)?;
+
+    assert_eq!(None, leader.metrics().borrow().membership_in_progress());
+
+    tracing::info!(
+        log_index,
+        "--- block replication to node-1, the joint config can not commit"
+    );
+    let pending = {
+        router.set_network_error(1, true);
+
+        let n0 = leader.clone();
+        let pending = tokio::spawn(async move { n0.change_membership([0, 1], false).await });
+
+        leader.wait(timeout()).log_index(Some(log_index + 1), "joint config is appended").await?;
+        pending
+    };
+
+    let in_progress = InProgress {
+        committed: *leader.metrics().borrow().committed_membership.log_id(),
+        membership_log_id: *leader.metrics().borrow().membership_config.log_id(),
+    };
+
+    tracing::info!(log_index, "--- metrics report the membership change in progress");
+    {
+        assert_eq!(Some(log_index), in_progress.committed.index());
+        assert_eq!(Some(log_index + 1), in_progress.membership_log_id.index());
+        assert_eq!(
+            Some(in_progress.clone()),
+            leader.metrics().borrow().membership_in_progress()
+        );
+
+        let server_metrics = leader.server_metrics().borrow().clone();
+        assert_eq!(Some(log_index), server_metrics.committed_membership.log_id().index());
+    }
+
+    tracing::info!(log_index, "--- another change-membership is rejected");
+    {
+        let res = leader.change_membership([0, 2], false).await;
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(
+            ClientWriteError::ChangeMembershipError(ChangeMembershipError::InProgress(in_progress)),
+            err
+        );
+    }
+
+    tracing::info!(log_index, "--- unblock node-1, the pending change completes");
+    {
+        router.set_network_error(1, false);
+
+        pending.await??;
+        leader.wait(timeout()).membership_committed("membership change committed").await?;
+
+        leader.change_membership([0, 2], false).await?;
+    }
+
+    Ok(())
+}
+
 fn timeout() -> Option<Duration> {
     Some(Duration::from_millis(1000))
 }
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+use openraft::raft::DecommissionRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Decommission a voter, a learner and the leader with
+/// [`Raft::decommission`](openraft::Raft::decommission).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn decommission() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            election_timeout_min: 150,
+            election_timeout_max: 300,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let _log_index = router.new_cluster(btreeset! {0,1,2,3}, btreeset! {4}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!("--- a follower can not decommission a node");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let err = n1.decommission(3, timeout()).await.unwrap_err();
+        assert_eq!(Some(0), err.forward_to_leader().unwrap().leader_id);
+    }
+
+    tracing::info!("--- a decommission request for another node is rejected");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.handle_decommission(DecommissionRequest::new(Vote::new_committed(1, 0), 3)).await;
+
+        let err = res.unwrap_err().into_api_error().unwrap();
+        assert_eq!(1, err.node_id);
+        assert_eq!(3, err.target);
+        assert_eq!(ServerState::Follower, n1.metrics().borrow().state);
+    }
+
+    tracing::info!("--- decommission voter-3");
+    {
+        let resp = n0.decommission(3, timeout()).await?;
+        assert!(resp.shutdown_signaled);
+
+        let m = n0.metrics().borrow().membership_config.clone();
+        assert_eq!(btreeset! {0,1,2}, m.membership().voter_ids().collect());
+        assert!(m.membership().get_node(&3).is_none());
+
+        router.wait(&3, Some(timeout())).state(ServerState::Shutdown, "node-3 shuts down").await?;
+    }
+
+    tracing::info!("--- decommission learner-4");
+    {
+        let resp = n0.decommission(4, timeout()).await?;
+        assert!(resp.shutdown_signaled);
+
+        let m = n0.metrics().borrow().membership_config.clone();
+        assert!(m.membership().get_node(&4).is_none());
+        assert!(n0.metrics().borrow().read_replicas.as_ref().unwrap().is_empty());
+
+        router.wait(&4, Some(timeout())).state(ServerState::Shutdown, "node-4 shuts down").await?;
+    }
+
+    tracing::info!("--- decommission leader-0: transfer leadership, then retry on the new leader");
+    {
+        let err = n0.decommission(0, timeout()).await.unwrap_err();
+        let leader_id = err.forward_to_leader().unwrap().leader_id.unwrap();
+        assert!(leader_id == 1 || leader_id == 2, "new leader: {}", leader_id);
+
+        let leader = router.get_raft_handle(&leader_id)?;
+        leader.wait(Some(timeout())).state(ServerState::Leader, "new leader").await?;
+
+        let resp = leader.decommission(0, timeout()).await?;
+        assert!(resp.shutdown_signaled);
+
+        let m = leader.metrics().borrow().membership_config.clone();
+        assert_eq!(btreeset! {1,2}, m.membership().voter_ids().collect());
+
+        router.wait(&0, Some(timeout())).state(ServerState::Shutdown, "node-0 shuts down").await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Duration {
+    Duration::from_millis(2_000)
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::NonMemberRpcPolicy;
+use openraft::Vote;
+use openraft::error::Fatal;
+use openraft::error::NonMemberRejected;
+use openraft::error::RaftError;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::VoteRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `accept_rpc_from_non_members=reject`, RPCs from a non-member are rejected, while a new
+/// learner, which is not a member itself yet, still accepts logs from the leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn non_member_rpc_reject() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            accept_rpc_from_non_members: NonMemberRpcPolicy::Reject,
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+    let rejected = RaftError::Fatal(Fatal::NonMemberRejected(NonMemberRejected::new(5)));
+
+    tracing::info!(log_index, "--- append-entries from a non-member is rejected");
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(5, 5), Some(log_id(1, 0, log_index)), [], None);
+        assert_eq!(Err(rejected.clone()), n1.append_entries(req).await);
+    }
+
+    tracing::info!(log_index, "--- vote request from a non-member is rejected");
+    {
+        let req = VoteRequest::new(Vote::new(5, 5), Some(log_id(5, 5, 100)));
+        assert_eq!(Err(rejected.clone()), n1.vote(req).await);
+    }
+
+    router
+        .wait(&1, timeout())
+        .vote(Vote::new_committed(1, 0), "vote is not changed by rejected RPCs")
+        .await?;
+
+    tracing::info!(log_index, "--- a new learner accepts logs from the leader");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner-3 receives logs").await?;
+    }
+
+    Ok(())
+}
+
+/// With `accept_rpc_from_non_members=accept-read-only`, a vote request from a non-member is
+/// answered without being granted, and append-entries that would change the vote are rejected.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn non_member_rpc_accept_read_only() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            accept_rpc_from_non_members: NonMemberRpcPolicy::AcceptReadOnly,
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n1 = router.get_raft_handle(&1)?;
+
+    tracing::info!(
+        log_index,
+        "--- vote request from a non-member is answered but not granted"
+    );
+    {
+        let req = VoteRequest::new(Vote::new(5, 5), Some(log_id(5, 5, 100)));
+        let resp = n1.vote(req).await?;
+        assert!(!resp.vote_granted);
+        assert_eq!(Vote::new_committed(1, 0), resp.vote);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- append-entries from a non-member with another vote is rejected"
+    );
+    {
+        let req = AppendEntriesRequest::new(Vote::new_committed(5, 5), Some(log_id(1, 0, log_index)), [], None);
+        assert_eq!(
+            Err(RaftError::Fatal(Fatal::NonMemberRejected(NonMemberRejected::new(5)))),
+            n1.append_entries(req).await
+        );
+    }
+
+    router
+        .wait(&1, timeout())
+        .vote(Vote::new_committed(1, 0), "vote is not changed by non-members")
+        .await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -15,3 +15,10 @@ mod t20_metrics_state_machine_consistency;
 mod t30_leader_metrics;
 mod t40_metrics_wait;
 mod t50_progress_api;
+mod t60_capacity_hint;
+mod t60_write_latency;
+mod t61_read_replicas;
+mod t62_metrics_history;
+mod t63_node_infos;
+mod t64_transitions;
+mod t65_apply_lag_alarm;
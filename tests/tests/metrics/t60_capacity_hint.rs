@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader reports a write capacity estimate that shrinks when a voter lags behind.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn capacity_hint() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            max_payload_entries: 10,
+            max_append_entries_inflight: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- voters are up to date, the window is available");
+    {
+        n0.wait(timeout())
+            .metrics(
+                |m| m.capacity.as_ref().is_some_and(|c| c.available() == 20),
+                "leader has full capacity",
+            )
+            .await?;
+
+        let hint = n0.capacity_hint().unwrap();
+        assert_eq!(20, hint.window);
+        assert_eq!(0, hint.uncommitted);
+        assert_eq!(0, hint.max_voter_lag);
+    }
+
+    tracing::info!(log_index, "--- node-2 is unreachable, its lag reduces the capacity");
+    {
+        router.set_unreachable(2, true);
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.capacity.as_ref().is_some_and(|c| c.max_voter_lag == 5 && c.uncommitted == 0),
+                "node-2 lags 5 entries, all are committed",
+            )
+            .await?;
+
+        let hint = n0.capacity_hint().unwrap();
+        assert_eq!(15, hint.available());
+    }
+
+    tracing::info!(log_index, "--- followers do not report capacity");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        assert_eq!(None, n1.capacity_hint());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
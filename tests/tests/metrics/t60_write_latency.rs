@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader records the latency of every write in `RaftMetrics::write_latency`, followers do not.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn write_latency() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let before = n0.metrics().borrow().write_latency.entries;
+
+    tracing::info!(log_index, "--- write 10 logs, the latency of each of them is recorded");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        n0.wait(timeout())
+            .metrics(
+                |m| m.write_latency.entries >= before + 10,
+                "leader records write latency of 10 logs",
+            )
+            .await?;
+
+        let m = n0.metrics().borrow().write_latency.clone();
+        tracing::info!(log_index, "write latency: {}", m);
+        assert!(m.commit.p99 >= m.commit.p50);
+    }
+
+    tracing::info!(log_index, "--- followers do not record write latency");
+    {
+        for id in [1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "follower applied").await?;
+
+            let n = router.get_raft_handle(&id)?;
+            assert_eq!(0, n.metrics().borrow().write_latency.entries);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::type_config::TypeConfigExt;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// The leader reports the data freshness of every learner in `read_replicas`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn read_replicas() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {2}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- only learner-2 is a read replica, and it is up to date");
+    {
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| {
+                    m.read_replicas
+                        .as_ref()
+                        .and_then(|r| r.get(&2))
+                        .is_some_and(|r| r.matched == Some(log_id(1, 0, log_index)))
+                },
+                "learner-2 is up to date",
+            )
+            .await?;
+
+        let replicas = m.read_replicas.unwrap();
+        assert_eq!(btreeset! {2}, replicas.keys().copied().collect());
+
+        let r2 = &replicas[&2];
+        assert_eq!(0, r2.lag);
+        assert!(r2.last_acked.is_some());
+        assert!(r2.is_fresh(0, Duration::from_secs(10)));
+
+        // `metrics` and `data_metrics` are sent by the same report. A later ack may have updated
+        // `last_acked` after `m` was observed, thus compare the latest of both.
+        let deadline = TypeConfig::now() + timeout().unwrap();
+        loop {
+            let replicas = n0.metrics().borrow().read_replicas.clone();
+            let data_replicas = n0.data_metrics().borrow().read_replicas.clone();
+            if replicas == data_replicas || TypeConfig::now() > deadline {
+                assert_eq!(replicas, data_replicas);
+                break;
+            }
+            TypeConfig::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- learner-2 is unreachable and falls behind");
+    {
+        router.set_unreachable(2, true);
+
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        let m = n0
+            .wait(timeout())
+            .metrics(
+                |m| m.last_log_index == Some(log_index) && m.read_replicas.as_ref().unwrap()[&2].lag == 5,
+                "learner-2 lags 5 logs",
+            )
+            .await?;
+
+        let r2 = &m.read_replicas.unwrap()[&2];
+        assert!(!r2.is_fresh(4, Duration::from_secs(10)));
+        assert!(r2.is_fresh(5, Duration::from_secs(10)));
+    }
+
+    tracing::info!(log_index, "--- followers do not report read replicas");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        assert!(n1.metrics().borrow().read_replicas.is_none());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
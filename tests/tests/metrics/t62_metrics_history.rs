@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use tokio::time::sleep;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The last metrics samples are kept in [`Raft::metrics_history`](openraft::Raft::metrics_history).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn metrics_history() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 20,
+            election_timeout_min: 500,
+            election_timeout_max: 501,
+            metrics_history_size: 5,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- write logs and wait for samples to be taken");
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "applied").await?;
+
+        sleep(Duration::from_millis(500)).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- only the last samples are kept, the latest one is up to date"
+    );
+    {
+        let history = n0.metrics_history().unwrap();
+        assert_eq!(5, history.len());
+
+        for w in history.windows(2) {
+            assert!(w[0].at <= w[1].at, "samples are ordered by time");
+        }
+
+        let last = history.last().unwrap();
+        assert_eq!(Some(log_index), last.metrics.last_applied.map(|x| x.index));
+    }
+
+    tracing::info!(log_index, "--- history is disabled by default");
+    {
+        let config = Arc::new(Config::default().validate()?);
+        let mut router = RaftRouter::new(config);
+        router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+        let n0 = router.get_raft_handle(&0)?;
+        assert!(n0.metrics_history().is_none());
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
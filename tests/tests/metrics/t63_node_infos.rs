@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::raft::NodeInfo;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The Leader exchanges node infos with every replication target with a hello request, and
+/// both sides report them in [`RaftMetrics::node_infos`](openraft::RaftMetrics::node_infos).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn node_infos() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            app_version: "1.2.0".to_string(),
+            capabilities: vec!["app:v2-codec".to_string()],
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    router.new_cluster(btreeset! {0,1,2}, btreeset! {3}).await?;
+
+    tracing::info!("--- leader knows every voter and learner");
+    {
+        let m = router
+            .wait(&0, timeout())
+            .metrics(|m| m.node_infos.len() == 4, "leader received infos of all nodes")
+            .await?;
+
+        for (id, info) in m.node_infos.iter() {
+            assert_eq!(env!("CARGO_PKG_VERSION"), info.openraft_version, "node-{}", id);
+            assert_eq!("1.2.0", info.app_version, "node-{}", id);
+            assert!(info.supports(NodeInfo::APPLY_RESULTS), "node-{}", id);
+            assert!(info.supports("app:v2-codec"), "node-{}", id);
+        }
+    }
+
+    tracing::info!("--- followers and learners know the leader");
+    for id in [1, 2, 3] {
+        let m = router
+            .wait(&id, timeout())
+            .metrics(|m| m.node_infos.contains_key(&0), "received info of the leader")
+            .await?;
+        assert_eq!("1.2.0", m.node_infos[&0].app_version);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The time of the last change of the server state, the vote, the snapshot and the membership is
+/// reported in [`RaftMetrics::transitions`](openraft::RaftMetrics::transitions).
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn transitions() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- node-0 became leader, no snapshot is built yet");
+    let leader_since = {
+        let m = n0.metrics().borrow().clone();
+        let t = m.transitions;
+
+        assert!(t.vote_persisted.is_some());
+        assert!(t.membership_changed.is_some());
+        assert!(t.snapshot_updated.is_none());
+
+        t.server_state_changed.unwrap()
+    };
+
+    tracing::info!(
+        log_index,
+        "--- building a snapshot does not change the server state time"
+    );
+    {
+        n0.trigger().snapshot().await?;
+        let m = n0.wait(timeout()).metrics(|m| m.transitions.snapshot_updated.is_some(), "snapshot built").await?;
+
+        assert!(m.transitions.snapshot_updated.unwrap() >= leader_since);
+        assert_eq!(Some(leader_since), m.transitions.server_state_changed);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- node-0 steps down after transferring leadership to node-1"
+    );
+    {
+        n0.trigger().transfer_leader(1).await?;
+        n0.wait(timeout()).state(ServerState::Follower, "node-0 becomes follower").await?;
+
+        let m = n0.metrics().borrow().clone();
+        assert!(m.transitions.server_state_changed.unwrap() > leader_since);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1000))
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::metrics::ApplyLagEvent;
+use openraft_memstore::BlockOperation;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// An alarm is raised when a follower falls behind in applying committed logs for long enough, and
+/// is cleared when it catches up.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn apply_lag_alarm() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            apply_lag_alarm_threshold: 3,
+            apply_lag_alarm_duration: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let follower = router.get_raft_handle(&1)?;
+    let mut events = follower.apply_lag_events();
+
+    tracing::info!(log_index, "--- delay applying logs on the follower");
+    let mut block = {
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        sm1.block.set_blocking(BlockOperation::Apply, Duration::from_millis(5_000));
+        sm1.block.clone()
+    };
+
+    tracing::info!(log_index, "--- write logs the follower can not apply in time");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&1, timeout()).log_index(Some(log_index), "follower received logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the alarm is raised on the follower");
+    {
+        let ev = tokio::time::timeout(timeout().unwrap(), events.recv()).await?.unwrap();
+        assert!(
+            matches!(ev, ApplyLagEvent::Raised { lag, duration, .. } if lag > 3 && duration >= Duration::from_millis(200)),
+            "got: {}",
+            ev
+        );
+    }
+
+    tracing::info!(log_index, "--- the alarm is cleared once the follower catches up");
+    {
+        block.clear_blocking(BlockOperation::Apply);
+
+        let ev = tokio::time::timeout(Duration::from_millis(20_000), events.recv()).await?.unwrap();
+        assert!(
+            matches!(ev, ApplyLagEvent::Cleared { lag, .. } if lag <= 3),
+            "got: {}",
+            ev
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
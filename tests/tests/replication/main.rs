@@ -8,6 +8,16 @@ mod t10_append_entries_partial_success;
 mod t50_append_entries_backoff;
 mod t50_append_entries_backoff_rejoin;
 mod t51_append_entries_too_large;
+mod t52_network_events;
+mod t53_pipelined_append_entries;
+mod t54_replication_entry_cache;
+mod t55_append_entries_max_payload_bytes;
+mod t56_network_layer;
 mod t60_feature_loosen_follower_log_revert;
 mod t61_allow_follower_log_revert;
 mod t62_follower_clear_restart_recover;
+mod t63_resync_follower;
+mod t64_quarantine_diverged_follower;
+mod t65_log_checksum;
+mod t66_compression;
+mod t67_busy_follower;
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::async_runtime::MpscUnboundedReceiver;
+use openraft::network::NetworkEvent;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// The leader emits connection lifecycle events when a target becomes unreachable and recovers.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn network_events() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 1_000,
+            election_timeout_max: 1_001,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+    let mut events = n0.network_events();
+
+    tracing::info!(log_index, "--- node 2 becomes unreachable");
+    {
+        router.set_unreachable(2, true);
+
+        let ev = next_event_of(&mut events, 2).await?;
+        assert!(matches!(ev, NetworkEvent::Disconnected { .. }), "got: {}", ev);
+    }
+
+    tracing::info!(log_index, "--- replicating to unreachable node 2 starts backoff");
+    {
+        router.client_request_many(0, "0", 1).await?;
+        log_index += 1;
+
+        let ev = next_event_of(&mut events, 2).await?;
+        assert_eq!(NetworkEvent::BackoffStarted { target: 2 }, ev);
+    }
+
+    tracing::info!(log_index, "--- node 2 recovers");
+    {
+        router.set_unreachable(2, false);
+
+        let ev = next_event_of(&mut events, 2).await?;
+        assert_eq!(NetworkEvent::Connected { target: 2 }, ev);
+    }
+
+    Ok(())
+}
+
+/// Receive the next event about `target`.
+async fn next_event_of<R>(events: &mut R, target: u64) -> Result<NetworkEvent<MemConfig>>
+where R: MpscUnboundedReceiver<NetworkEvent<MemConfig>> {
+    loop {
+        let ev = tokio::time::timeout(Duration::from_millis(3_000), events.recv())
+            .await?
+            .expect("network event channel closed");
+
+        if ev.target() == &target {
+            return Ok(ev);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `max_append_entries_inflight` > 1, a lagging learner catches up with pipelined
+/// AppendEntries, even if the RPCs arrive out of order.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn pipelined_append_entries() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            max_payload_entries: 2,
+            max_append_entries_inflight: 4,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::builder(config.clone()).send_delay(10).build();
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 50 logs");
+    {
+        log_index += router.client_request_many(0, "0", 50).await?;
+        for i in [0, 1, 2] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 50)).await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- add learner-3, it catches up");
+    {
+        router.new_raft_node(3).await;
+        router.add_learner(0, 3).await?;
+        log_index += 1;
+
+        router.wait(&3, timeout()).applied_index(Some(log_index), "learner-3 catches up").await?;
+    }
+
+    tracing::info!(log_index, "--- write another 50 logs");
+    {
+        log_index += router.client_request_many(0, "0", 50).await?;
+        for i in [0, 1, 2, 3] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 50)).await?;
+        }
+    }
+
+    router.assert_storage_state(1, log_index, Some(0), log_id(1, 0, log_index), None).await?;
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `replication_cache_entries` > 0, lagging learners replicating the same logs share the
+/// entries read by the leader, and the cached entries are evicted once every target has them.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn replication_entry_cache() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_payload_entries: 5,
+            replication_cache_entries: 1_000,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 100 logs");
+    {
+        log_index += router.client_request_many(0, "0", 100).await?;
+    }
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- add 3 learners at once, they catch up");
+    {
+        for id in [1, 2, 3] {
+            router.new_raft_node(id).await;
+        }
+        for id in [1, 2, 3] {
+            n0.add_learner(id, (), false).await?;
+            log_index += 1;
+        }
+
+        for id in [1, 2, 3] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "learner catches up").await?;
+        }
+    }
+
+    tracing::info!(
+        log_index,
+        "--- the learners are served by the cache, which is then emptied"
+    );
+    {
+        n0.wait(timeout())
+            .metrics(|m| m.replication_cache.entries == 0, "replicated entries are evicted")
+            .await?;
+
+        let m = n0.metrics().borrow().replication_cache.clone();
+        tracing::info!("replication cache metrics: {}", m);
+        assert!(m.hits > 0, "expect cache hits, got: {}", m);
+        assert!(m.misses > 0, "expect cache misses, got: {}", m);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::raft::AppendEntriesRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// With `max_payload_bytes`, an AppendEntries RPC carries no more entries than fit in the budget,
+/// but at least one, with or without pipelining.
+///
+/// Every memstore entry is larger than the 1 byte budget, thus every RPC carries one entry.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn append_entries_max_payload_bytes() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            max_payload_entries: 5,
+            max_payload_bytes: 1,
+            max_append_entries_inflight: 2,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster of 1 node");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 entries to leader");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "10 writes").await?;
+    }
+
+    let max_entries = Arc::new(AtomicU64::new(0));
+    {
+        let max_entries = max_entries.clone();
+        router.set_rpc_pre_hook(RPCTypes::AppendEntries, move |_router, req, _from, _to| {
+            let req: AppendEntriesRequest<_> = req.try_into().unwrap();
+            max_entries.fetch_max(req.entries.len() as u64, Ordering::Relaxed);
+            Ok(())
+        });
+    }
+
+    tracing::info!(log_index, "--- add node-1 as learner, it catches up");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "learner-1 catches up").await?;
+    }
+
+    assert_eq!(1, max_entries.load(Ordering::Relaxed), "every RPC carries one entry");
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::ServerState;
+use openraft::base::BoxFuture;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::network::RPCOption;
+use openraft::network::layer::LayeredNetworkFactory;
+use openraft::network::layer::NetworkLayer;
+use openraft::network::layer::PendingRpc;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// RPCs of a leader pass through the layers of its network factory, the outermost first, and a
+/// layer can send an RPC again after it fails.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn network_layer() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let sent = Arc::new(Mutex::new(HashMap::new()));
+    let attempts = Arc::new(Mutex::new(BTreeMap::new()));
+
+    tracing::info!("--- create node 0 with layers: count, retry, fail the first vote");
+    {
+        let network = LayeredNetworkFactory::new(router.clone())
+            .layer(FailFirstVote {
+                attempts: attempts.clone(),
+            })
+            .layer(RetryOnce)
+            .layer(Count { sent: sent.clone() });
+
+        router.new_raft_node_with_network(0, network).await;
+        router.new_raft_node(1).await;
+        router.new_raft_node(2).await;
+    }
+
+    tracing::info!("--- node 0 is elected with votes sent twice");
+    {
+        router.initialize(0).await?;
+        router.wait(&0, timeout()).state(ServerState::Leader, "node 0 becomes leader").await?;
+
+        let sent = sent.lock().unwrap();
+        let attempts = attempts.lock().unwrap();
+        for target in [1, 2] {
+            let n = sent[&(target, RPCTypes::Vote)];
+            assert!(n >= 1);
+            assert_eq!(n * 2, attempts[&target], "every vote to {} is sent twice", target);
+        }
+    }
+
+    tracing::info!("--- logs are replicated through the layers");
+    {
+        router.client_request_many(0, "0", 1).await?;
+        router.wait_for_log(&btreeset! {0,1,2}, Some(2), timeout(), "replicate through layers").await?;
+
+        let n = router.get_raft_handle(&1)?.metrics().borrow().last_applied;
+        assert_eq!(Some(log_id(1, 0, 2)), n);
+
+        let sent = sent.lock().unwrap();
+        assert!(sent[&(1, RPCTypes::AppendEntries)] >= 1);
+        assert!(sent[&(2, RPCTypes::AppendEntries)] >= 1);
+    }
+
+    Ok(())
+}
+
+/// Counts the RPCs by target and type.
+struct Count {
+    sent: Arc<Mutex<HashMap<(u64, RPCTypes), u64>>>,
+}
+
+impl NetworkLayer<MemConfig> for Count {
+    fn call<'a>(
+        &'a self,
+        rpc: &'a mut dyn PendingRpc<MemConfig>,
+        option: RPCOption,
+    ) -> BoxFuture<'a, Result<(), RPCError<MemConfig>>> {
+        let key = (*rpc.target(), rpc.rpc_type());
+        *self.sent.lock().unwrap().entry(key).or_default() += 1;
+        rpc.send(option)
+    }
+}
+
+/// Sends an RPC again if it fails and can be sent again.
+struct RetryOnce;
+
+impl NetworkLayer<MemConfig> for RetryOnce {
+    fn call<'a>(
+        &'a self,
+        rpc: &'a mut dyn PendingRpc<MemConfig>,
+        option: RPCOption,
+    ) -> BoxFuture<'a, Result<(), RPCError<MemConfig>>> {
+        Box::pin(async move {
+            let res = rpc.send(option.clone()).await;
+            if res.is_err() && rpc.can_send() {
+                return rpc.send(option).await;
+            }
+            res
+        })
+    }
+}
+
+/// Fails every other vote to a target without sending it, starting with the first one.
+struct FailFirstVote {
+    attempts: Arc<Mutex<BTreeMap<u64, u64>>>,
+}
+
+impl NetworkLayer<MemConfig> for FailFirstVote {
+    fn call<'a>(
+        &'a self,
+        rpc: &'a mut dyn PendingRpc<MemConfig>,
+        option: RPCOption,
+    ) -> BoxFuture<'a, Result<(), RPCError<MemConfig>>> {
+        if rpc.rpc_type() != RPCTypes::Vote {
+            return rpc.send(option);
+        }
+
+        let n = {
+            let mut attempts = self.attempts.lock().unwrap();
+            let n = attempts.entry(*rpc.target()).or_default();
+            *n += 1;
+            *n
+        };
+
+        if n % 2 == 1 {
+            let err = NetworkError::new(&anyerror::AnyError::error("injected failure"));
+            return Box::pin(async move { Err(RPCError::Network(err)) });
+        }
+        rpc.send(option)
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -8,6 +8,7 @@ use openraft::error::AllowNextRevertError;
 use openraft::error::ForwardToLeader;
 use openraft::error::NodeNotFound;
 use openraft::error::Operation;
+use openraft::error::RaftError;
 
 use crate::fixtures::RaftRouter;
 use crate::fixtures::ut_harness;
@@ -67,6 +68,89 @@ async fn allow_follower_log_revert() -> Result<()> {
     Ok(())
 }
 
+/// With `Raft::allow_next_revert()` an operator permits the reversion of a wiped learner that is
+/// already quarantined, and the permission is consumed by that reversion.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn allow_quarantined_follower_log_revert() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            max_payload_entries: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- erase Learner-1 and restart, it is quarantined");
+    {
+        let (_raft, _ls, _sm) = router.remove_node(1).unwrap();
+        let (log, sm) = openraft_memstore::new_mem_store();
+        router.new_raft_node_with_sto(1, log, sm).await;
+
+        log_index += router.client_request_many(0, "0", 1).await?;
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.quarantined == Some(btreeset! {1}), "Learner-1 is quarantined")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- allow the reversion, Learner-1 catches up");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.allow_next_revert(&1, true).await?;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "Learner-1 catches up").await?;
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.quarantined == Some(btreeset! {}), "Learner-1 is released")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- erase Learner-1 again, the permission is consumed");
+    {
+        let (_raft, _ls, _sm) = router.remove_node(1).unwrap();
+        let (log, sm) = openraft_memstore::new_mem_store();
+        router.new_raft_node_with_sto(1, log, sm).await;
+
+        log_index += router.client_request_many(0, "0", 1).await?;
+        router
+            .wait(&0, timeout())
+            .metrics(
+                |m| m.quarantined == Some(btreeset! {1}),
+                "Learner-1 is quarantined again",
+            )
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- allow_next_revert() on a non-leader node");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.allow_next_revert(&0, true).await;
+        assert!(
+            matches!(res, Err(RaftError::APIError(AllowNextRevertError::ForwardToLeader(_)))),
+            "{:?}",
+            res
+        );
+    }
+
+    Ok(())
+}
+
 /// Test error returned when `Trigger::allow_next_revert()` is called.
 #[tracing::instrument]
 #[test_harness::test(harness = ut_harness)]
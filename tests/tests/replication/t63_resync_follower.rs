@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::ForwardToLeader;
+use openraft::error::NodeNotFound;
+use openraft::error::Operation;
+use openraft::error::ResyncError;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `Trigger::resync()` the leader resyncs an erased learner with a snapshot, without
+/// `allow_log_reversion`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn resync_follower() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            // Make sure the replication is done in more than one steps
+            max_payload_entries: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    let snapshot_index = log_index;
+
+    tracing::info!(log_index, "--- erase Learner-1, resync it and restart it");
+    {
+        let (_raft, _ls, _sm) = router.remove_node(1).unwrap();
+
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().resync(&1).await??;
+
+        router
+            .wait(&0, timeout())
+            .snapshot(log_id(1, 0, snapshot_index), "a snapshot is built for the resync")
+            .await?;
+
+        let (log, sm) = openraft_memstore::new_mem_store();
+        router.new_raft_node_with_sto(1, log, sm).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1; // add learner
+    }
+
+    tracing::info!(log_index, "--- Learner-1 installs the snapshot and catches up");
+    {
+        router
+            .wait(&1, timeout())
+            .snapshot(log_id(1, 0, snapshot_index), "Learner-1 installs the snapshot")
+            .await?;
+        router.wait(&1, timeout()).applied_index(Some(log_index), "Learner-1 catches up").await?;
+    }
+
+    tracing::info!(log_index, "--- write another 10 logs, leader should not panic");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Test error returned when `Trigger::resync()` is called.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn resync_follower_errors() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- resync unknown node");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        let res = n0.trigger().resync(&2).await?;
+        assert_eq!(
+            Err(ResyncError::NodeNotFound(NodeNotFound::new(2, Operation::Resync))),
+            res
+        );
+    }
+
+    tracing::info!(log_index, "--- resync on non-leader node");
+    {
+        let n1 = router.get_raft_handle(&1)?;
+        let res = n1.trigger().resync(&0).await?;
+        assert_eq!(Err(ResyncError::ForwardToLeader(ForwardToLeader::new(0, ()))), res);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A learner that keeps reporting a reverted log is quarantined by the leader instead of panicking
+/// it, and is released by `Trigger::resync()`.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn quarantine_diverged_follower() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            enable_heartbeat: false,
+            max_payload_entries: 1,
+            quarantine_after_log_reversions: 3,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- erase Learner-1 and restart it");
+    {
+        let (_raft, _ls, _sm) = router.remove_node(1).unwrap();
+        let (log, sm) = openraft_memstore::new_mem_store();
+        router.new_raft_node_with_sto(1, log, sm).await;
+    }
+
+    tracing::info!(log_index, "--- write a log, the leader finds Learner-1 diverged");
+    {
+        log_index += router.client_request_many(0, "0", 1).await?;
+
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.quarantined == Some(btreeset! {1}), "Learner-1 is quarantined")
+            .await?;
+
+        let m = router.get_raft_handle(&0)?.metrics().borrow().clone();
+        assert_eq!(
+            Some(log_index),
+            m.last_applied.map(|x| x.index),
+            "the leader keeps working"
+        );
+    }
+
+    tracing::info!(log_index, "--- resync Learner-1 to release it");
+    {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().resync(&1).await??;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "Learner-1 catches up").await?;
+        router
+            .wait(&0, timeout())
+            .metrics(|m| m.quarantined == Some(btreeset! {}), "Learner-1 is released")
+            .await?;
+    }
+
+    tracing::info!(log_index, "--- write another 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::EntryPayload;
+use openraft::RaftLogReader;
+use openraft::ServerState;
+use openraft_memstore::BlockOperation;
+use openraft_memstore::ClientRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Followers verify their logs against the checksums the leader sends with heartbeats, and report a
+/// corrupted log entry as a mismatch.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_checksum() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            log_checksum_interval: 100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1, 2] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- followers verify their logs");
+    {
+        for i in [1, 2] {
+            router
+                .wait(&i, timeout())
+                .metrics(
+                    |m| m.log_checksums_verified >= 1 && m.log_checksum_mismatches == 0,
+                    "log checksum verified",
+                )
+                .await?;
+        }
+    }
+
+    tracing::info!(log_index, "--- corrupt a log entry on node-1");
+    {
+        let (mut sto1, _sm1) = router.get_storage_handle(&1)?;
+        let mut entries = sto1.try_get_log_entries(log_index..=log_index).await?;
+        let mut entry = entries.pop().unwrap();
+        entry.payload = EntryPayload::Normal(ClientRequest {
+            client: "0".to_string(),
+            serial: 0,
+            status: "corrupted".to_string(),
+        });
+        sto1.corrupt_log(entry).await;
+    }
+
+    tracing::info!(log_index, "--- node-1 reports a mismatch");
+    {
+        router
+            .wait(&1, timeout())
+            .metrics(|m| m.log_checksum_mismatches >= 1, "log checksum mismatch")
+            .await?;
+
+        let n2 = router.get_raft_handle(&2)?;
+        let m = n2.metrics().borrow().clone();
+        assert_eq!(0, m.log_checksum_mismatches, "node-2 log is intact");
+    }
+
+    Ok(())
+}
+
+/// The leader computes log checksums without blocking heartbeats: slow log reads on the leader do
+/// not let followers time out and start an election.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn log_checksum_slow_log_reads() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 50,
+            election_timeout_min: 150,
+            election_timeout_max: 200,
+            log_checksum_interval: 100,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- write 10 logs");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        for i in [0, 1, 2] {
+            router.wait(&i, timeout()).applied_index(Some(log_index), format!("{} writes", 10)).await?;
+        }
+    }
+
+    let term = router.get_raft_handle(&0)?.metrics().borrow().current_term;
+
+    tracing::info!(log_index, "--- slow down log reads on the leader");
+    {
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        sm0.block.set_blocking(BlockOperation::ReadLog, Duration::from_millis(1_000));
+    }
+
+    tracing::info!(log_index, "--- node-0 stays the leader");
+    {
+        tokio::time::sleep(Duration::from_millis(2_000)).await;
+
+        for i in [0, 1, 2] {
+            let m = router.get_raft_handle(&i)?.metrics().borrow().clone();
+            assert_eq!(Some(0), m.current_leader, "node-{} follows node-0", i);
+            assert_eq!(term, m.current_term, "node-{} does not elect", i);
+        }
+
+        let m = router.get_raft_handle(&0)?.metrics().borrow().clone();
+        assert_eq!(ServerState::Leader, m.state);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
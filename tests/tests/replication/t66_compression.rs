@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::base::BoxFuture;
+use openraft::error::RPCError;
+use openraft::network::RPCOption;
+use openraft::network::layer::LayeredNetworkFactory;
+use openraft::network::layer::NetworkLayer;
+use openraft::network::layer::PendingRpc;
+use openraft_memstore::TypeConfig as MemConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// A leader negotiates the compression scheme with every target and passes it to the network only
+/// for the AppendEntries payloads reaching the threshold.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn compression() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            compression: vec!["lz4".to_string(), "zstd".to_string()],
+            compression_threshold: 1024,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    let compressions = Arc::new(Mutex::new(vec![]));
+
+    tracing::info!("--- create node 0 recording the compression of AppendEntries");
+    {
+        let network = LayeredNetworkFactory::new(router.clone()).layer(Record {
+            compressions: compressions.clone(),
+        });
+
+        router.new_raft_node_with_network(0, network).await;
+    }
+
+    let mut log_index = 0;
+
+    tracing::info!(log_index, "--- initialize, node 0 negotiates the scheme with node 1");
+    {
+        router.initialize(0).await?;
+        log_index += 1;
+        router.wait(&0, timeout()).applied_index(Some(log_index), "node 0 becomes leader").await?;
+
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router.wait(&0, timeout()).metrics(|m| m.compression.contains_key(&1), "scheme negotiated").await?;
+        assert_eq!(
+            btreemap! {1 => "lz4".to_string()},
+            router.get_raft_handle(&0)?.metrics().borrow().compression
+        );
+    }
+
+    tracing::info!(log_index, "--- small payloads are not compressed");
+    {
+        compressions.lock().unwrap().clear();
+
+        log_index += router.client_request_many(0, "0", 1).await?;
+        router.wait_for_log(&btreeset! {0,1}, Some(log_index), timeout(), "replicate a log").await?;
+
+        let got = compressions.lock().unwrap().clone();
+        assert!(!got.is_empty());
+        assert!(got.iter().all(|c| c.is_none()), "got: {:?}", got);
+    }
+
+    tracing::info!(
+        log_index,
+        "--- a large payload is compressed with the negotiated scheme"
+    );
+    {
+        router.set_network_error(1, true);
+        log_index += router.client_request_many(0, "0", 30).await?;
+
+        compressions.lock().unwrap().clear();
+        router.set_network_error(1, false);
+        log_index += router.client_request_many(0, "0", 1).await?;
+
+        router.wait_for_log(&btreeset! {0,1}, Some(log_index), timeout(), "learner catches up").await?;
+
+        let got = compressions.lock().unwrap().clone();
+        assert!(got.contains(&Some("lz4".to_string())), "got: {:?}", got);
+    }
+
+    Ok(())
+}
+
+/// Records the compression scheme of every AppendEntries RPC.
+struct Record {
+    compressions: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl NetworkLayer<MemConfig> for Record {
+    fn call<'a>(
+        &'a self,
+        rpc: &'a mut dyn PendingRpc<MemConfig>,
+        option: RPCOption,
+    ) -> BoxFuture<'a, Result<(), RPCError<MemConfig>>> {
+        if rpc.rpc_type() == RPCTypes::AppendEntries {
+            self.compressions.lock().unwrap().push(option.compression().map(String::from));
+        }
+        rpc.send(option)
+    }
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::raft::AppendEntriesRequest;
+use openraft::raft::AppendEntriesResponse;
+use openraft_memstore::BlockOperation;
+use tokio::time::Instant;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A follower that falls behind in applying logs responds with `Busy`, and the leader pauses
+/// sending logs to it.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn busy_follower() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            busy_apply_backlog: 5,
+            busy_pause: 500,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0,1}, btreeset! {}).await?;
+
+    let follower = router.get_raft_handle(&1)?;
+
+    tracing::info!(log_index, "--- delay applying logs on the follower");
+    let mut block = {
+        let (_sto1, sm1) = router.get_storage_handle(&1)?;
+        sm1.block.set_blocking(BlockOperation::Apply, Duration::from_millis(5_000));
+        sm1.block.clone()
+    };
+
+    tracing::info!(log_index, "--- write logs the follower can not apply in time");
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        router.wait(&1, timeout()).log_index(Some(log_index), "follower received logs").await?;
+    }
+
+    tracing::info!(log_index, "--- the follower responds with Busy");
+    {
+        let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::heartbeat(
+            Vote::new_committed(1, 0),
+            Some(log_id(1, 0, log_index)),
+            Some(log_id(1, 0, log_index)),
+        );
+
+        follower.append_entries(rpc.clone()).await?;
+        let resp = follower.append_entries(rpc).await?;
+
+        assert!(resp.is_success());
+        assert!(
+            matches!(resp, AppendEntriesResponse::Busy { apply_backlog } if apply_backlog >= 5),
+            "got: {}",
+            resp
+        );
+    }
+
+    tracing::info!(log_index, "--- the leader pauses sending logs to the busy follower");
+    {
+        log_index += router.client_request_many(0, "0", 1).await?;
+
+        let now = Instant::now();
+        log_index += router.client_request_many(0, "0", 1).await?;
+        let elapsed = now.elapsed();
+
+        assert!(
+            elapsed >= Duration::from_millis(200),
+            "the write waits for the pause, elapsed: {:?}",
+            elapsed
+        );
+    }
+
+    tracing::info!(log_index, "--- the follower catches up once applying is not delayed");
+    {
+        block.clear_blocking(BlockOperation::Apply);
+        router
+            .wait(&1, Some(Duration::from_millis(20_000)))
+            .applied_index(Some(log_index), "follower applied")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(5_000))
+}
@@ -126,12 +126,12 @@ async fn build_snapshot() -> Result<()> {
             .new_client(1, &())
             .await
             .append_entries(
-                AppendEntriesRequest {
-                    vote: Vote::new_committed(1, 0),
-                    prev_log_id: Some(log_id(1, 0, 2)),
-                    entries: vec![],
-                    leader_commit: Some(log_id(0, 0, 0)),
-                },
+                AppendEntriesRequest::new(
+                    Vote::new_committed(1, 0),
+                    Some(log_id(1, 0, 2)),
+                    vec![],
+                    Some(log_id(0, 0, 0)),
+                ),
                 option,
             )
             .await?;
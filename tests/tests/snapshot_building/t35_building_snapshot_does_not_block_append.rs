@@ -61,12 +61,12 @@ async fn building_snapshot_does_not_block_append() -> Result<()> {
         "--- send append-entries request to the follower that is building snapshot"
     );
     {
-        let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig> {
-            vote: Vote::new_committed(1, 0),
-            prev_log_id: Some(log_id(1, 0, log_index)),
-            entries: vec![blank_ent(1, 0, 15)],
-            leader_commit: None,
-        };
+        let rpc = AppendEntriesRequest::<openraft_memstore::TypeConfig>::new(
+            Vote::new_committed(1, 0),
+            Some(log_id(1, 0, log_index)),
+            vec![blank_ent(1, 0, 15)],
+            None,
+        );
 
         let mut cli = router.new_client(1, &()).await;
         let option = RPCOption::new(Duration::from_millis(1_000));
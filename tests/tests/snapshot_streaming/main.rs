@@ -12,8 +12,14 @@ mod t31_snapshot_overrides_membership;
 mod t32_snapshot_uses_prev_snap_membership;
 mod t33_snapshot_delete_conflict_logs;
 mod t34_replication_does_not_block_purge;
+mod t35_purge_keeps_logs_for_lagging;
 mod t50_snapshot_line_rate_to_snapshot;
 mod t50_snapshot_when_lacking_log;
 mod t51_after_snapshot_add_learner_and_request_a_log;
+mod t52_snapshot_with_buffered_log_tail;
+mod t53_max_inflight_snapshots;
+mod t54_share_snapshot_among_followers;
+mod t55_seed_learner_from_snapshot;
 mod t60_snapshot_chunk_size;
+mod t61_snapshot_receive_progress;
 mod t90_issue_808_snapshot_to_unreachable_node_should_not_block;
@@ -37,17 +37,19 @@ async fn snapshot_arguments() -> Result<()> {
     log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
 
     let n = router.remove_node(0).unwrap();
-    let make_req = || InstallSnapshotRequest {
-        // force it to be a follower
-        vote: Vote::new_committed(2, 1),
-        meta: SnapshotMeta {
-            snapshot_id: "ss1".into(),
-            last_log_id: Some(log_id(1, 0, 0)),
-            last_membership: Default::default(),
-        },
-        offset: 0,
-        data: vec![1, 2, 3],
-        done: false,
+    let make_req = || {
+        InstallSnapshotRequest::new(
+            // force it to be a follower
+            Vote::new_committed(2, 1),
+            SnapshotMeta {
+                snapshot_id: "ss1".into(),
+                last_log_id: Some(log_id(1, 0, 0)),
+                last_membership: Default::default(),
+            },
+            0,
+            vec![1, 2, 3],
+            false,
+        )
     };
 
     tracing::info!(log_index, "--- only allow to begin a new session when offset is 0");
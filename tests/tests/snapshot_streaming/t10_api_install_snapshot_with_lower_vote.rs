@@ -35,28 +35,23 @@ async fn install_snapshot_lower_vote() -> Result<()> {
     log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
 
     let (n0, _, _) = router.remove_node(0).unwrap();
-    let make_req = || InstallSnapshotRequest {
-        vote: Vote::new_committed(2, 1),
-        meta: SnapshotMeta {
-            snapshot_id: "ss1".into(),
-            last_log_id: Some(log_id(1, 0, 0)),
-            last_membership: Default::default(),
-        },
-        offset: 0,
-        data: vec![1, 2, 3],
-        done: false,
+    let make_req = || {
+        InstallSnapshotRequest::new(
+            Vote::new_committed(2, 1),
+            SnapshotMeta {
+                snapshot_id: "ss1".into(),
+                last_log_id: Some(log_id(1, 0, 0)),
+                last_membership: Default::default(),
+            },
+            0,
+            vec![1, 2, 3],
+            false,
+        )
     };
 
     tracing::info!(log_index, "--- force the vote on target node to be higher");
     {
-        let _res = n0
-            .append_entries(AppendEntriesRequest {
-                vote: Vote::new_committed(2, 1),
-                prev_log_id: None,
-                entries: vec![],
-                leader_commit: None,
-            })
-            .await;
+        let _res = n0.append_entries(AppendEntriesRequest::new(Vote::new_committed(2, 1), None, vec![], None)).await;
         let vote = n0.with_raft_state(|st| *st.vote_ref()).await?;
         assert_eq!(Vote::new_committed(2, 1), vote);
     }
@@ -81,15 +81,15 @@ async fn snapshot_overrides_membership() -> Result<()> {
 
         tracing::info!(log_index, "--- add a membership config log to the learner");
         {
-            let req = AppendEntriesRequest {
-                vote: Vote::new_committed(1, 0),
-                prev_log_id: None,
-                entries: vec![blank_ent(0, 0, 0), Entry {
+            let req = AppendEntriesRequest::new(
+                Vote::new_committed(1, 0),
+                None,
+                vec![blank_ent(0, 0, 0), Entry {
                     log_id: log_id(1, 0, 1),
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {2,3}], [])),
                 }],
-                leader_commit: Some(log_id(0, 0, 0)),
-            };
+                Some(log_id(0, 0, 0)),
+            );
             let option = RPCOption::new(Duration::from_millis(1_000));
 
             router.new_client(1, &()).await.append_entries(req, option).await?;
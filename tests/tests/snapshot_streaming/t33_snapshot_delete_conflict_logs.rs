@@ -85,10 +85,10 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
     {
         router.new_raft_node(1).await;
 
-        let req = AppendEntriesRequest {
-            vote: Vote::new_committed(1, 0),
-            prev_log_id: None,
-            entries: vec![
+        let req = AppendEntriesRequest::new(
+            Vote::new_committed(1, 0),
+            None,
+            vec![
                 blank_ent(0, 0, 0),
                 blank_ent(1, 0, 1),
                 // conflict membership will be replaced with membership in snapshot
@@ -110,8 +110,8 @@ async fn snapshot_delete_conflicting_logs() -> Result<()> {
                     payload: EntryPayload::Membership(Membership::new_with_defaults(vec![btreeset! {4,5}], [])),
                 },
             ],
-            leader_commit: Some(log_id(1, 0, 2)),
-        };
+            Some(log_id(1, 0, 2)),
+        );
         let option = RPCOption::new(Duration::from_millis(1_000));
 
         router.new_client(1, &()).await.append_entries(req, option).await?;
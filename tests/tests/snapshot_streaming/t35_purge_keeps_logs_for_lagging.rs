@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::PurgePolicy;
+use openraft::RaftLogReader;
+use tokio::time::sleep;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With [`PurgePolicy::KeepForLagging`], a leader keeps the logs a lagging learner needs, plus a
+/// margin, and the learner catches up with logs instead of a snapshot.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn purge_keeps_logs_for_lagging() -> Result<()> {
+    let margin = 2;
+
+    let config = Arc::new(
+        Config {
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            purge_policy: PurgePolicy::KeepForLagging { margin, max_lag: 100 },
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {1}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+    let learner = router.get_raft_handle(&1)?;
+
+    let (mut sto0, mut _sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- block replication to learner, build snapshot on leader");
+    let learner_index = log_index;
+    {
+        router.set_network_error(1, true);
+
+        log_index += router.client_request_many(0, "0", 10).await?;
+        leader.trigger().snapshot().await?;
+        leader.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+
+        // Wait for purge to complete.
+        sleep(Duration::from_millis(500)).await;
+
+        let logs = sto0.try_get_log_entries(..).await?;
+        assert_eq!(
+            learner_index + 1 - margin,
+            logs[0].log_id.index(),
+            "the logs the learner needs and the margin are kept"
+        );
+    }
+
+    tracing::info!(log_index, "--- restore replication, learner catches up with logs");
+    {
+        router.set_network_error(1, false);
+
+        learner.wait(timeout()).applied_index(Some(log_index), "learner catches up").await?;
+        assert_eq!(
+            None,
+            learner.metrics().borrow().snapshot,
+            "no snapshot is sent to learner"
+        );
+
+        leader
+            .wait(timeout())
+            .purged(Some(log_id(1, 0, log_index - margin)), "purged up to the margin")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
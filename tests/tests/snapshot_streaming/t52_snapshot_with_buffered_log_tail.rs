@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RaftLogReader;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A learner that is far behind receives a snapshot, and the logs after the snapshot are buffered
+/// by the leader while the snapshot is sent.
+///
+/// The buffer is smaller than the log tail, so the first logs after the snapshot are sent from
+/// the buffer and the rest are read from the storage.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_with_buffered_log_tail() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            snapshot_tail_buffer_size: 5,
+            enable_heartbeat: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs in it");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        leader.trigger().snapshot().await?;
+        leader.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        leader.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+
+        log_index
+    };
+
+    tracing::info!(log_index, "--- write logs after the snapshot");
+    {
+        log_index += router.client_request_many(0, "0", 20).await?;
+    }
+
+    tracing::info!(log_index, "--- add learner, it receives the snapshot and the log tail");
+    {
+        router.new_raft_node(1).await;
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        router
+            .wait(&1, timeout())
+            .snapshot(log_id(1, 0, snapshot_index), "learner-1 installs snapshot")
+            .await?;
+        router
+            .wait_for_log(
+                &btreeset! {0, 1},
+                Some(log_index),
+                timeout(),
+                "learner-1 receives log tail",
+            )
+            .await?;
+
+        let (mut sto1, _sm1) = router.get_storage_handle(&1)?;
+        let logs = sto1.try_get_log_entries(..).await?;
+        let indexes = logs.iter().map(|e| e.log_id.index()).collect::<Vec<_>>();
+
+        assert_eq!((snapshot_index + 1..=log_index).collect::<Vec<_>>(), indexes);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
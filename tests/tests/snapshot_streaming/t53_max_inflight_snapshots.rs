@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// With `max_inflight_snapshots`, the leader sends no more snapshots at the same time; the others
+/// wait in a queue and are sent one after another.
+///
+/// - Add 3 learners that need a snapshot at once, with `max_inflight_snapshots = 1`.
+/// - Every transfer takes at least 300 ms, thus the transfers start at least 300 ms apart.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn max_inflight_snapshots() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            max_inflight_snapshots: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs in it");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        leader.trigger().snapshot().await?;
+        leader.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        leader.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+
+        log_index
+    };
+
+    let started = Arc::new(Mutex::new(vec![]));
+    {
+        let started = started.clone();
+        router.set_rpc_pre_hook(RPCTypes::InstallSnapshot, move |_router, _req, _from, _to| {
+            started.lock().unwrap().push(Instant::now());
+            std::thread::sleep(Duration::from_millis(300));
+            Ok(())
+        });
+    }
+
+    tracing::info!(log_index, "--- add 3 learners, they receive the snapshot one by one");
+    {
+        for id in [1, 2, 3] {
+            router.new_raft_node(id).await;
+        }
+
+        // Add them with one membership change, so that the replications are not restarted.
+        leader
+            .change_membership(ChangeMembers::AddNodes(btreemap! {1 => (), 2 => (), 3 => ()}), false)
+            .await?;
+        log_index += 1;
+
+        for id in [1, 2, 3] {
+            router
+                .wait(&id, timeout())
+                .snapshot(
+                    log_id(1, 0, snapshot_index),
+                    format!("learner-{} installs snapshot", id),
+                )
+                .await?;
+        }
+
+        let started = started.lock().unwrap().clone();
+        assert_eq!(3, started.len());
+        for w in started.windows(2) {
+            assert!(
+                w[1] - w[0] >= Duration::from_millis(300),
+                "transfers are not concurrent: {:?}",
+                started
+            );
+        }
+    }
+
+    tracing::info!(log_index, "--- metrics report the queued transfers");
+    {
+        leader
+            .wait(timeout())
+            .metrics(
+                |m| m.snapshot_transfers.sending == 0 && m.snapshot_transfers.queued == 0,
+                "no transfer is running",
+            )
+            .await?;
+
+        let m = leader.metrics().borrow().snapshot_transfers.clone();
+        assert_eq!(2, m.total_queued);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
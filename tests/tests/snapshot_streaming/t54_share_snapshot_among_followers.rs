@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreemap;
+use maplit::btreeset;
+use openraft::ChangeMembers;
+use openraft::Config;
+use openraft::SnapshotPolicy;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// When several followers need the same snapshot, the leader gets it from the state machine once
+/// and sends each of them a copy.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn share_snapshot_among_followers() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+    let mut router = RaftRouter::new(config.clone());
+
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let leader = router.get_raft_handle(&0)?;
+    let (_sto0, sm0) = router.get_storage_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot and purge the logs in it");
+    let snapshot_index = {
+        log_index += router.client_request_many(0, "0", 10).await?;
+
+        leader.trigger().snapshot().await?;
+        leader.wait(timeout()).snapshot(log_id(1, 0, log_index), "build snapshot").await?;
+        leader.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "purge logs in snapshot").await?;
+
+        log_index
+    };
+
+    tracing::info!(log_index, "--- add 3 learners, they receive the same snapshot");
+    {
+        sm0.take_get_current_snapshot_count();
+
+        for id in [1, 2, 3] {
+            router.new_raft_node(id).await;
+        }
+        leader
+            .change_membership(ChangeMembers::AddNodes(btreemap! {1 => (), 2 => (), 3 => ()}), false)
+            .await?;
+        log_index += 1;
+
+        for id in [1, 2, 3] {
+            router
+                .wait(&id, timeout())
+                .snapshot(
+                    log_id(1, 0, snapshot_index),
+                    format!("learner-{} installs snapshot", id),
+                )
+                .await?;
+        }
+
+        assert_eq!(1, sm0.take_get_current_snapshot_count(), "snapshot is got once");
+    }
+
+    tracing::info!(log_index, "--- learners catch up");
+    {
+        router
+            .wait_for_log(&btreeset! {0, 1, 2, 3}, Some(log_index), timeout(), "learners catch up")
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(3_000))
+}
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::RPCTypes;
+use openraft::SnapshotPolicy;
+use openraft::storage::RaftStateMachine;
+use openraft::storage::seed_from_snapshot;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A new learner seeded from a snapshot file catches up with only the log tail from the leader,
+/// although the leader has purged the logs in the snapshot.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn seed_learner_from_snapshot() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            snapshot_policy: SnapshotPolicy::Never,
+            max_in_snapshot_log_to_keep: 0,
+            purge_batch_size: 1,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    let n0 = router.get_raft_handle(&0)?;
+
+    tracing::info!(log_index, "--- build a snapshot on the leader and purge its logs");
+    let snapshot_index;
+    {
+        log_index += router.client_request_many(0, "0", 10).await?;
+        snapshot_index = log_index;
+
+        n0.trigger().snapshot().await?;
+        n0.wait(timeout()).snapshot(log_id(1, 0, log_index), "leader built snapshot").await?;
+        n0.wait(timeout()).purged(Some(log_id(1, 0, log_index)), "leader purged logs").await?;
+    }
+
+    tracing::info!(log_index, "--- seed node-1 with the leader's snapshot");
+    {
+        let (_sto0, mut sm0) = router.get_storage_handle(&0)?;
+        let snapshot = sm0.get_current_snapshot().await?.unwrap();
+
+        let (mut sto1, mut sm1) = router.new_store();
+        seed_from_snapshot(&mut sto1, &mut sm1, snapshot).await?;
+
+        tracing::info!(log_index, "--- seeding a non-pristine storage is refused");
+        {
+            let snapshot = sm0.get_current_snapshot().await?.unwrap();
+            let res = seed_from_snapshot(&mut sto1, &mut sm1, snapshot).await;
+            assert!(res.is_err());
+        }
+
+        router.new_raft_node_with_sto(1, sto1, sm1).await;
+    }
+
+    tracing::info!(log_index, "--- add node-1 as learner, it receives only the log tail");
+    {
+        router.add_learner(0, 1).await?;
+        log_index += 1;
+
+        log_index += router.client_request_many(0, "0", 5).await?;
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).applied_index(Some(log_index), "learner catches up").await?;
+
+        let m = n1.metrics().borrow().clone();
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), m.snapshot);
+        assert_eq!(Some(log_id(1, 0, snapshot_index)), m.purged);
+
+        assert_eq!(
+            None,
+            router.get_rpc_count().get(&RPCTypes::InstallSnapshot),
+            "no snapshot is sent to the learner"
+        );
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::metrics::SnapshotReceivePhase;
+use openraft::raft::InstallSnapshotRequest;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A node receiving a snapshot in chunks reports the bytes received and the phase in its metrics,
+/// until the snapshot is installed.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn snapshot_receive_progress() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let log_index = router.new_cluster(btreeset! {0}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- build a snapshot on node-0");
+    let snapshot = {
+        let n0 = router.get_raft_handle(&0)?;
+        n0.trigger().snapshot().await?;
+        router.wait(&0, timeout()).snapshot(log_id(1, 0, log_index), "node-0 snapshot").await?;
+        n0.get_snapshot().await?.unwrap()
+    };
+
+    let meta = snapshot.meta.clone();
+    let data = snapshot.snapshot.into_inner();
+    let half = data.len() / 2;
+
+    router.new_raft_node(1).await;
+    let n1 = router.get_raft_handle(&1)?;
+
+    let make_req = |offset: usize, end: usize, done: bool| {
+        InstallSnapshotRequest::new(
+            Vote::new_committed(1, 0),
+            meta.clone(),
+            offset as u64,
+            data[offset..end].to_vec(),
+            done,
+        )
+    };
+
+    tracing::info!(log_index, "--- send the first half to node-1");
+    {
+        n1.install_snapshot(make_req(0, half, false)).await?;
+
+        let m = n1.wait(timeout()).metrics(|m| m.snapshot_receiving.is_some(), "node-1 receiving snapshot").await?;
+        let got = m.snapshot_receiving.unwrap();
+        assert_eq!(meta.snapshot_id, got.snapshot_id);
+        assert_eq!(SnapshotReceivePhase::Receiving, got.phase);
+        assert_eq!(half as u64, got.received);
+        assert_eq!(None, got.total);
+    }
+
+    tracing::info!(log_index, "--- the total size reported by the application is kept");
+    {
+        n1.report_snapshot_receiving(&meta.snapshot_id, half as u64, Some(data.len() as u64));
+
+        let m = n1
+            .wait(timeout())
+            .metrics(
+                |m| m.snapshot_receiving.as_ref().is_some_and(|x| x.total.is_some()),
+                "node-1 knows the total size",
+            )
+            .await?;
+        assert_eq!(Some(data.len() as u64), m.snapshot_receiving.unwrap().total);
+    }
+
+    tracing::info!(log_index, "--- send the rest, node-1 installs the snapshot");
+    {
+        n1.install_snapshot(make_req(half, data.len(), true)).await?;
+
+        n1.wait(timeout()).snapshot(log_id(1, 0, log_index), "node-1 installed snapshot").await?;
+        n1.wait(timeout())
+            .metrics(
+                |m| m.snapshot_receiving.is_none(),
+                "node-1 snapshot progress is cleared",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
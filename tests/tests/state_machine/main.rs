@@ -9,3 +9,6 @@ mod fixtures;
 
 mod t10_total_order_apply;
 mod t20_state_machine_apply_membership;
+mod t30_repair_corrupted_log;
+mod t40_apply_leader_results;
+mod t50_fencing_token;
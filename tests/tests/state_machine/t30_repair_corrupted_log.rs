@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::error::Fatal;
+use openraft::network::RPCTypes;
+
+use crate::fixtures::MemStateMachine;
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// A follower that cannot read a committed log entry to apply fetches a copy of it from the
+/// leader, instead of shutting down, if `Config::repair_corrupted_log` is enabled.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn repair_corrupted_log() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            repair_corrupted_log: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- block the state machine of node-2");
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let n2 = n2.clone();
+        tokio::spawn(async move {
+            n2.with_state_machine(|_sm: &mut MemStateMachine| {
+                Box::pin(async move {
+                    let _ = release_rx.await;
+                })
+            })
+            .await
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    tracing::info!(log_index, "--- write 5 logs, node-2 commits them but does not apply");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        let want = Some(log_id(1, 0, log_index));
+        loop {
+            let committed = n2.with_raft_state(|st| st.committed().cloned()).await?;
+            if committed == want {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- corrupt a log entry on node-2");
+    {
+        let (sto2, _sm2) = router.get_storage_handle(&2)?;
+        sto2.garble_log(log_index - 2).await;
+    }
+
+    tracing::info!(
+        log_index,
+        "--- release the state machine, node-2 applies logs repaired by the leader"
+    );
+    {
+        let _ = release_tx.send(());
+
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node-2 applied all logs").await?;
+
+        assert!(router.get_rpc_count().get(&RPCTypes::RepairLog).copied().unwrap_or_default() >= 1);
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        let (_sto2, sm2) = router.get_storage_handle(&2)?;
+        assert_eq!(
+            sm0.get_state_machine().await.client_status,
+            sm2.get_state_machine().await.client_status
+        );
+    }
+
+    tracing::info!(log_index, "--- node-2 is still running");
+    {
+        log_index += router.client_request_many(0, "foo", 1).await?;
+        router.wait(&2, timeout()).applied_index(Some(log_index), "node-2 applied more logs").await?;
+    }
+
+    Ok(())
+}
+
+/// A follower stops with the read error, instead of waiting forever, if the leader cannot provide
+/// a copy of the committed log entry it cannot read either.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn repair_corrupted_log_gives_up() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            heartbeat_interval: 10,
+            election_timeout_min: 50,
+            election_timeout_max: 60,
+            enable_heartbeat: false,
+            enable_elect: false,
+            repair_corrupted_log: true,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let n2 = router.get_raft_handle(&2)?;
+
+    tracing::info!(log_index, "--- block the state machine of node-2");
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let n2 = n2.clone();
+        tokio::spawn(async move {
+            n2.with_state_machine(|_sm: &mut MemStateMachine| {
+                Box::pin(async move {
+                    let _ = release_rx.await;
+                })
+            })
+            .await
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    tracing::info!(log_index, "--- write 5 logs, node-2 commits them but does not apply");
+    {
+        log_index += router.client_request_many(0, "foo", 5).await?;
+
+        let want = Some(log_id(1, 0, log_index));
+        loop {
+            let committed = n2.with_raft_state(|st| st.committed().cloned()).await?;
+            if committed == want {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- corrupt the same log entry on node-2 and the leader");
+    {
+        for id in [0, 2] {
+            let (sto, _sm) = router.get_storage_handle(&id)?;
+            sto.garble_log(log_index - 2).await;
+        }
+    }
+
+    tracing::info!(log_index, "--- release the state machine, node-2 gives up and stops");
+    {
+        let _ = release_tx.send(());
+
+        n2.wait(Some(Duration::from_millis(5_000)))
+            .metrics(|m| m.running_state.is_err(), "node-2 stops")
+            .await?;
+
+        let running_state = n2.metrics().borrow().running_state.clone();
+        assert!(matches!(running_state, Err(Fatal::StorageError(_))));
+        assert!(router.get_rpc_count().get(&RPCTypes::RepairLog).copied().unwrap_or_default() >= 1);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use anyerror::AnyError;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::Vote;
+use openraft::error::NetworkError;
+use openraft::error::RPCError;
+use openraft::network::RPCTypes;
+use openraft::raft::ApplyResultsRequest;
+use openraft::raft::ComputedResult;
+use openraft_memstore::TypeConfig;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::log_id;
+use crate::fixtures::ut_harness;
+
+/// Followers apply entries with the results computed by the leader, if
+/// `Config::apply_results_wait` is enabled, and end up with the same state as the leader.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn apply_leader_results() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            apply_results_wait: 500,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(
+        log_index,
+        "--- write logs, followers apply them with the results of the leader"
+    );
+    {
+        log_index += router.client_request_many(0, "foo", 10).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "applied all logs").await?;
+        }
+
+        assert!(router.get_rpc_count().get(&RPCTypes::ApplyResults).copied().unwrap_or_default() >= 1);
+
+        let (_sto0, sm0) = router.get_storage_handle(&0)?;
+        for id in [1, 2] {
+            let n = router.get_raft_handle(&id)?;
+            let m = n.metrics().borrow().clone();
+            assert!(
+                m.applied_from_leader_results > 0,
+                "node-{} applied with results of the leader",
+                id
+            );
+            assert_eq!(0, m.apply_result_mismatches, "node-{} computes the same results", id);
+
+            let (_sto, sm) = router.get_storage_handle(&id)?;
+            assert_eq!(
+                sm0.get_state_machine().await.client_status,
+                sm.get_state_machine().await.client_status
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// A follower ignores apply results not sent by the current leader, and applies the entries by
+/// computing the results itself.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn ignore_results_not_from_leader() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_heartbeat: false,
+            enable_elect: false,
+            apply_results_wait: 200,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    tracing::info!(log_index, "--- block apply results sent by the leader to node-1");
+    router.set_rpc_pre_hook(RPCTypes::ApplyResults, |_router, _req, _from, to| {
+        if to == 1 {
+            let any_err = AnyError::error("block apply results to node 1");
+            Err(RPCError::Network(NetworkError::new(&any_err)))
+        } else {
+            Ok(())
+        }
+    });
+
+    let applied_from_leader = router.get_raft_handle(&1)?.metrics().borrow().applied_from_leader_results;
+
+    tracing::info!(log_index, "--- node-2 sends forged results to node-1");
+    {
+        let results = (log_index + 1..=log_index + 3)
+            .map(|i| ComputedResult::<TypeConfig>::new(log_id(1, 0, i), b"forged".to_vec()))
+            .collect::<Vec<_>>();
+
+        let n1 = router.get_raft_handle(&1)?;
+        n1.handle_apply_results(ApplyResultsRequest::new(Vote::new_committed(1, 2), results)).await?;
+    }
+
+    tracing::info!(log_index, "--- write logs, node-1 applies them without the forged results");
+    {
+        log_index += router.client_request_many(0, "foo", 3).await?;
+
+        router.wait(&1, timeout()).applied_index(Some(log_index), "node-1 applied all logs").await?;
+
+        let m = router.get_raft_handle(&1)?.metrics().borrow().clone();
+        assert_eq!(applied_from_leader, m.applied_from_leader_results);
+        assert_eq!(0, m.apply_result_mismatches);
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(2_000))
+}
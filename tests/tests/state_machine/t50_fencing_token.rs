@@ -0,0 +1,68 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use maplit::btreeset;
+use openraft::Config;
+use openraft::ServerState;
+use openraft::Vote;
+
+use crate::fixtures::RaftRouter;
+use crate::fixtures::ut_harness;
+
+/// Every applied batch is tagged with the vote of the leader, as a fencing token that increases
+/// when the leadership changes.
+#[tracing::instrument]
+#[test_harness::test(harness = ut_harness)]
+async fn fencing_token() -> Result<()> {
+    let config = Arc::new(
+        Config {
+            enable_tick: false,
+            ..Default::default()
+        }
+        .validate()?,
+    );
+
+    let mut router = RaftRouter::new(config.clone());
+
+    tracing::info!("--- initializing cluster");
+    let mut log_index = router.new_cluster(btreeset! {0,1,2}, btreeset! {}).await?;
+
+    let first_token = Vote::new_committed(1, 0);
+
+    tracing::info!(log_index, "--- entries are applied with the vote of node-0");
+    {
+        log_index += router.client_request_many(0, "0", 3).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+            let (_, sm) = router.get_storage_handle(&id)?;
+            assert_eq!(Some(first_token), sm.fencing_token(), "node-{}", id);
+        }
+    }
+
+    tracing::info!(log_index, "--- node-1 becomes leader, the token increases");
+    {
+        router.get_raft_handle(&0)?.trigger().transfer_leader(1).await?;
+        let n1 = router.get_raft_handle(&1)?;
+        n1.wait(timeout()).state(ServerState::Leader, "node-1 becomes leader").await?;
+
+        let token = n1.metrics().borrow().vote;
+        assert!(token > first_token);
+
+        log_index += 1; // the blank log of the new leader
+        log_index += router.client_request_many(1, "0", 3).await?;
+
+        for id in [0, 1, 2] {
+            router.wait(&id, timeout()).applied_index(Some(log_index), "write logs").await?;
+            let (_, sm) = router.get_storage_handle(&id)?;
+            assert_eq!(Some(token), sm.fencing_token(), "node-{}", id);
+        }
+    }
+
+    Ok(())
+}
+
+fn timeout() -> Option<Duration> {
+    Some(Duration::from_millis(1_000))
+}